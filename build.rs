@@ -415,6 +415,9 @@ fn load_locale_source(locales_dir: &Path, locale_code: &str) -> LocaleTable {
     load_locale_lang_table(&lang_path)
 }
 
+/// 其他语言允许缺少键（运行时回退到该语言），但回退语言本身必须完整
+const FALLBACK_LOCALE_CODE: &str = "en-US";
+
 fn validate_locale_tables(locale_tables: &[(String, LocaleTable)]) {
     let Some((base_locale, base_table)) = locale_tables.first() else {
         return;
@@ -438,29 +441,40 @@ fn validate_locale_tables(locale_tables: &[(String, LocaleTable)]) {
             .cloned()
             .collect::<Vec<_>>();
 
-        if !missing.is_empty() || !extra.is_empty() {
-            let missing_preview = missing
-                .iter()
-                .take(12)
-                .cloned()
-                .collect::<Vec<_>>()
-                .join(", ");
-            let extra_preview = extra
-                .iter()
-                .take(12)
-                .cloned()
-                .collect::<Vec<_>>()
-                .join(", ");
-            panic!(
-                "locale key mismatch: base={} target={} missing={} [{}] extra={} [{}]",
-                base_locale,
-                locale_code,
-                missing.len(),
-                missing_preview,
-                extra.len(),
-                extra_preview
-            );
+        if extra.is_empty() && (missing.is_empty() || locale_code != FALLBACK_LOCALE_CODE) {
+            if !missing.is_empty() {
+                println!(
+                    "cargo:warning=locale {} is missing {} of {} keys; they fall back to {}",
+                    locale_code,
+                    missing.len(),
+                    base_keys.len(),
+                    FALLBACK_LOCALE_CODE
+                );
+            }
+            continue;
         }
+
+        let missing_preview = missing
+            .iter()
+            .take(12)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ");
+        let extra_preview = extra
+            .iter()
+            .take(12)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ");
+        panic!(
+            "locale key mismatch: base={} target={} missing={} [{}] extra={} [{}]",
+            base_locale,
+            locale_code,
+            missing.len(),
+            missing_preview,
+            extra.len(),
+            extra_preview
+        );
     }
 }
 
@@ -942,6 +956,28 @@ fn load_key_hex(
     Ok(hex::encode(local_bytes))
 }
 
+/// 联机策略的签名公钥与地址来自发布构建的密钥（见 docs/ONLINE_POLICY.md），
+/// 未提供时客户端不拉取远端策略，只使用内置默认值
+fn emit_online_policy_settings() {
+    println!("cargo:rerun-if-env-changed=BMCBL_ONLINE_POLICY_PUBLIC_KEY");
+    println!("cargo:rerun-if-env-changed=BMCBL_ONLINE_POLICY_URL");
+
+    let public_key = env::var("BMCBL_ONLINE_POLICY_PUBLIC_KEY")
+        .map(|key| key.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+    if !public_key.is_empty() {
+        if !hex::decode(&public_key).is_ok_and(|bytes| bytes.len() == 32) {
+            panic!("BMCBL_ONLINE_POLICY_PUBLIC_KEY 必须是 32 字节 Ed25519 公钥的十六进制");
+        }
+        println!("cargo:rustc-env=BMCBL_ONLINE_POLICY_PUBLIC_KEY={public_key}");
+    }
+
+    let url = env::var("BMCBL_ONLINE_POLICY_URL").unwrap_or_default();
+    if !url.trim().is_empty() {
+        println!("cargo:rustc-env=BMCBL_ONLINE_POLICY_URL={}", url.trim());
+    }
+}
+
 fn main() {
     let build_version = resolve_build_version();
     let build_channel = resolve_build_channel(&build_version);
@@ -960,6 +996,7 @@ fn main() {
     generate_asset_bundles_rs();
     generate_i18n_tables_rs();
     generate_dependency_metadata_rs();
+    emit_online_policy_settings();
 
     let out_dir = env::var_os("OUT_DIR").unwrap();
     let dest_path = Path::new(&out_dir).join("secrets.rs");
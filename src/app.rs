@@ -227,6 +227,9 @@ pub(crate) fn run(bootstrap: AppBootstrap) -> Result<()> {
             crate::ui::navigation::set_route(cx, crate::ui::navigation::AppRoute::Home);
             let main_window_opened = open_main_window(&bootstrap, cx);
             if main_window_opened {
+                crate::utils::render_recovery::spawn_frontend_ready_watchdog(Duration::from_secs(
+                    bootstrap.config.launcher.frontend_ready_timeout_secs,
+                ));
                 schedule_post_startup_warmups(cx);
                 if bootstrap.debug_enabled {
                     schedule_debug_window_after_startup(cx);
@@ -318,6 +321,9 @@ fn build_app_state(cx: &mut App, bootstrap: &AppBootstrap) {
     cx.default_global::<crate::plugins::runtime::PluginRegistry>();
     cx.default_global::<AppSubscriptions>();
 
+    crate::i18n::set_fallback_markers(
+        bootstrap.debug_enabled && bootstrap.config.launcher.i18n_fallback_markers,
+    );
     cx.update_global(|i18n: &mut I18n, _cx| {
         i18n.set_locale(bootstrap.initial_locale);
     });
@@ -6,6 +6,7 @@ use crate::core::minecraft::appx::utils::{get_manifest_identity, patch_manifest}
 #[cfg(target_os = "linux")]
 use crate::core::minecraft::appx_utils::{get_manifest_identity, patch_manifest};
 use crate::core::minecraft::key_patcher::{PatchResult, patch_path};
+use crate::core::version::integrity::write_integrity_lock;
 use crate::result::CoreResult;
 use crate::tasks::task_manager::{
     create_task_with_details, finish_task, is_cancelled, update_progress,
@@ -14,7 +15,7 @@ use crate::utils::file_ops;
 use std::fs;
 use std::fs::File;
 use std::path::{Path, PathBuf};
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 fn task_target_name(name: &str, fallback: &str) -> String {
     Path::new(name)
@@ -159,6 +160,7 @@ async fn run_import_appx_task(task_id: String, source_path: String, file_name: O
                 finish_error(&task_id, format!("创建 mods 目录失败：{error}"));
                 return;
             }
+            record_integrity_lock(&extract_to).await;
             finish_task(
                 &task_id,
                 "completed",
@@ -188,6 +190,7 @@ pub async fn extract_zip_appx(
     force_replace: bool,
     delete_signature: bool,
 ) -> Result<String, String> {
+    crate::utils::data_root::ensure_data_root_writable()?;
     crate::archive::register_archive_task_stage_labels();
     debug!(
         "extract_zip_appx: file_name='{}', destination='{}', force_replace={}, delete_signature={}",
@@ -302,6 +305,7 @@ async fn run_extract_zip_appx_task(
             if !finish_appx_install(&task_id, &extract_to, delete_signature).await {
                 return;
             }
+            record_integrity_lock(&extract_to).await;
 
             finish_task(
                 &task_id,
@@ -326,6 +330,16 @@ async fn run_extract_zip_appx_task(
     }
 }
 
+/// 安装完成后生成完整性锁文件；失败只记录日志，不影响安装结果
+async fn record_integrity_lock(version_dir: &Path) {
+    let version_dir = version_dir.to_path_buf();
+    match tokio::task::spawn_blocking(move || write_integrity_lock(&version_dir)).await {
+        Ok(Ok(())) => {}
+        Ok(Err(error)) => warn!("生成完整性锁文件失败: {error}"),
+        Err(error) => warn!("生成完整性锁文件任务失败: {error}"),
+    }
+}
+
 fn open_zip_archive(path: &Path, task_id: &str) -> Option<zip::ZipArchive<File>> {
     let file = match File::open(path) {
         Ok(file) => file,
@@ -0,0 +1,120 @@
+//! 按文件头识别压缩包格式。有些 `.mcworld` / `.mcpack` 实际是 RAR 或 7z 改了扩展名，
+//! 直接交给 zip 库只会得到难以理解的解析错误，这里提前识别并给出可操作的提示。
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+const ZIP_MAGICS: [&[u8]; 3] = [b"PK\x03\x04", b"PK\x05\x06", b"PK\x07\x08"];
+const RAR_MAGIC: &[u8] = b"Rar!\x1a\x07";
+const SEVEN_ZIP_MAGIC: &[u8] = b"7z\xbc\xaf\x27\x1c";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Zip,
+    Rar,
+    SevenZip,
+    /// 读不到文件头或不认识的格式，交给 zip 库自行判断（例如自解压 zip）
+    Unknown,
+}
+
+impl ArchiveFormat {
+    fn from_magic(header: &[u8]) -> Self {
+        if ZIP_MAGICS.iter().any(|magic| header.starts_with(magic)) {
+            Self::Zip
+        } else if header.starts_with(RAR_MAGIC) {
+            Self::Rar
+        } else if header.starts_with(SEVEN_ZIP_MAGIC) {
+            Self::SevenZip
+        } else {
+            Self::Unknown
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Zip => "ZIP",
+            Self::Rar => "RAR",
+            Self::SevenZip => "7z",
+            Self::Unknown => "未知",
+        }
+    }
+}
+
+/// 读取前 8 个字节判断格式
+pub fn detect_archive_format(path: &Path) -> ArchiveFormat {
+    let mut header = [0_u8; 8];
+    let Ok(mut file) = File::open(path) else {
+        return ArchiveFormat::Unknown;
+    };
+    let mut read = 0;
+    while read < header.len() {
+        match file.read(&mut header[read..]) {
+            Ok(0) => break,
+            Ok(count) => read += count,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(_) => return ArchiveFormat::Unknown,
+        }
+    }
+    ArchiveFormat::from_magic(&header[..read])
+}
+
+/// 确认文件可以按 zip 打开；RAR / 7z 返回提示用户重新压缩的错误
+pub fn ensure_zip_archive(path: &Path) -> Result<(), String> {
+    let format = detect_archive_format(path);
+    if matches!(format, ArchiveFormat::Zip | ArchiveFormat::Unknown) {
+        return Ok(());
+    }
+    let extension = path
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_else(|| "无扩展名".to_string());
+    Err(format!(
+        "该文件实际是 {} 压缩包（扩展名为 {}），游戏只支持 ZIP 格式。请先解压，再重新压缩为 ZIP 后导入",
+        format.label(),
+        extension
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn detects_formats_by_magic_bytes_not_extension() {
+        let dir =
+            std::env::temp_dir().join(format!("bmcbl-archive-format-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).expect("create test dir");
+        let cases: [(&str, &[u8], ArchiveFormat); 5] = [
+            (
+                "zip.mcworld",
+                b"PK\x03\x04\x14\x00\x00\x00",
+                ArchiveFormat::Zip,
+            ),
+            ("rar.mcworld", b"Rar!\x1a\x07\x01\x00", ArchiveFormat::Rar),
+            (
+                "7z.mcpack",
+                b"7z\xbc\xaf\x27\x1c\x00\x04",
+                ArchiveFormat::SevenZip,
+            ),
+            ("short.mcpack", b"PK", ArchiveFormat::Unknown),
+            ("text.mcpack", b"<html>", ArchiveFormat::Unknown),
+        ];
+        for (name, header, expected) in cases {
+            let path = dir.join(name);
+            fs::write(&path, header).expect("write test archive");
+            assert_eq!(detect_archive_format(&path), expected, "{name}");
+        }
+
+        let error = ensure_zip_archive(&dir.join("rar.mcworld")).expect_err("rar rejected");
+        assert!(error.contains("RAR") && error.contains(".mcworld"));
+        assert!(ensure_zip_archive(&dir.join("zip.mcworld")).is_ok());
+        assert_eq!(
+            detect_archive_format(&dir.join("missing.zip")),
+            ArchiveFormat::Unknown
+        );
+
+        fs::remove_dir_all(&dir).expect("remove test dir");
+    }
+}
@@ -1,4 +1,5 @@
 pub mod api;
+pub mod format;
 pub mod manager;
 pub mod runtime;
 pub mod zip;
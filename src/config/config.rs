@@ -8,9 +8,10 @@ pub use super::defaults::{
     default_theme_mode, get_default_config,
 };
 use super::defaults::{
-    default_config_version, default_error_report_sentry_enabled, default_music_volume,
-    default_proton_gdk_source, default_renderer_backend, default_true,
-    default_update_check_interval_minutes,
+    default_config_version, default_error_report_sentry_enabled,
+    default_frontend_ready_timeout_secs, default_inject_timeout_secs, default_music_volume,
+    default_proton_gdk_source, default_renderer_backend, default_scheduled_grace_minutes,
+    default_true, default_update_check_interval_minutes,
 };
 
 pub(super) const CURRENT_CONFIG_VERSION: u32 = 1;
@@ -22,6 +23,17 @@ pub const FONT_SOURCE_SYSTEM: &str = "system";
 pub const THEME_MODE_LIGHT: &str = "light";
 pub const THEME_MODE_DARK: &str = "dark";
 pub const DEFAULT_MUSIC_VOLUME: f32 = 0.5;
+pub const DEFAULT_SCHEDULED_GRACE_MINUTES: u32 = 120;
+pub const DEFAULT_INJECT_TIMEOUT_SECS: u64 = 30;
+pub const DEFAULT_FRONTEND_READY_TIMEOUT_SECS: u64 = 30;
+/// 发布构建通过 `BMCBL_ONLINE_POLICY_URL` 注入，未注入时为空，不拉取远端联机策略
+pub const DEFAULT_ONLINE_POLICY_URL: &str = match option_env!("BMCBL_ONLINE_POLICY_URL") {
+    Some(url) => url,
+    None => "",
+};
+pub const DEFAULT_PAPERCONNECT_MAX_CONNECTIONS: usize = 64;
+pub const DEFAULT_PAPERCONNECT_CLIENTS_URL: &str =
+    "https://updater.bmcbl.com/online/paperconnect_clients.json";
 
 pub fn get_config_file_path() -> std::path::PathBuf {
     super::storage::get_config_file_path()
@@ -136,6 +148,14 @@ pub struct GameConfig {
     pub keep_downloaded_game_package: bool, // 安装完成保留下载的游戏包（默认关闭）
     pub modify_appx_manifest: bool,  // 是否修改 AppxManifest.xml
     pub uwp_minimize_fix: bool,
+    #[serde(default)]
+    pub verify_before_launch: bool, // 启动前按完整性锁文件快速检查版本目录（只警告，不阻止启动）
+    #[serde(default = "default_inject_timeout_secs")]
+    pub inject_timeout_secs: u64, // 单个延迟注入的 DLL 等待加载完成的秒数，超时记为 timed_out 并继续下一个
+    #[serde(default)]
+    pub require_signed_dlls: bool, // 只注入 Authenticode 签名受信任的 DLL，其余跳过并发出安全警告
+    #[serde(default)]
+    pub game_log_dir: String, // 游戏日志目录，留空时按当前注册的包查找
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
@@ -190,6 +210,8 @@ pub struct DownloadConfig {
     pub curseforge_api_source: String,
     #[serde(default)]
     pub curseforge_api_base: String,
+    #[serde(default = "default_scheduled_grace_minutes")]
+    pub scheduled_grace_minutes: u32, // 计划任务错过开始时间后仍可补做的分钟数
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -215,6 +237,9 @@ pub struct OnlineConfig {
     pub game_ports: String,
     pub disable_p2p: bool,
     pub no_tun: bool,
+    pub policy_url: String,
+    pub paperconnect_max_connections: usize,
+    pub paperconnect_clients_url: String, // 兼容 PaperConnect 的第三方客户端清单
 }
 
 impl Default for OnlineConfig {
@@ -225,10 +250,34 @@ impl Default for OnlineConfig {
             game_ports: "7551".to_string(),
             disable_p2p: false,
             no_tun: true,
+            policy_url: DEFAULT_ONLINE_POLICY_URL.to_string(),
+            paperconnect_max_connections: DEFAULT_PAPERCONNECT_MAX_CONNECTIONS,
+            paperconnect_clients_url: DEFAULT_PAPERCONNECT_CLIENTS_URL.to_string(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct NetworkConfig {
+    pub respect_metered: bool, // 按流量计费网络下只拉取元数据
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            respect_metered: true,
         }
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ImportConfig {
+    pub upgrade_legacy_manifests: bool, // 导入时把 v1 清单升级为 format_version 2，原文件保留为 .bak
+    pub zip_fallback_codepage: u32, // 非 UTF-8 的 zip 条目名按该代码页解码，0 为按系统自动选择（中文系统为 GBK）
+}
+
 impl Default for MusicConfig {
     fn default() -> Self {
         Self {
@@ -252,8 +301,14 @@ pub struct Launcher {
     pub renderer_backend: String,
     #[serde(default = "default_gpu_adapter_name")]
     pub gpu_adapter_name: String,
+    #[serde(default = "default_frontend_ready_timeout_secs")]
+    pub frontend_ready_timeout_secs: u64, // 主窗口超过该时间仍未渲染时提示恢复渲染设置，0 为不检查
+    #[serde(default)]
+    pub i18n_fallback_markers: bool, // 调试模式下用 ⟦…⟧ 标出回退到 en-US 的文案，便于翻译校对
     #[serde(default = "default_true")]
     pub stats_upload: bool, // 上传基础统计信息 (默认开启)
+    #[serde(default = "default_true")]
+    pub local_usage_stats: bool, // 在本机记录游戏时长等使用统计，不会上传 (默认开启)
     #[serde(default = "default_error_report_sentry_enabled")]
     pub error_report_sentry_enabled: bool,
     #[serde(default = "default_error_report_sentry_dsn")]
@@ -289,6 +344,10 @@ pub struct Config {
     pub music: MusicConfig,
     #[serde(default)]
     pub online: OnlineConfig,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    #[serde(default)]
+    pub import: ImportConfig,
     pub agreement_accepted: bool,
 }
 
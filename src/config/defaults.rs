@@ -1,7 +1,7 @@
 use super::config::{
     CURRENT_CONFIG_VERSION, Config, CustomStyle, DEFAULT_ERROR_REPORT_SENTRY_DSN,
-    DEFAULT_MUSIC_VOLUME, DownloadConfig, FONT_SOURCE_DEFAULT, GameConfig, Launcher, MusicConfig,
-    OnlineConfig, ProxyConfig, ProxyType, UpdateChannel,
+    DEFAULT_MUSIC_VOLUME, DownloadConfig, FONT_SOURCE_DEFAULT, GameConfig, ImportConfig, Launcher,
+    MusicConfig, NetworkConfig, OnlineConfig, ProxyConfig, ProxyType, UpdateChannel,
 };
 
 pub(super) fn default_true() -> bool {
@@ -36,6 +36,18 @@ pub(super) fn default_update_check_interval_minutes() -> u32 {
     60
 }
 
+pub(super) fn default_scheduled_grace_minutes() -> u32 {
+    super::config::DEFAULT_SCHEDULED_GRACE_MINUTES
+}
+
+pub(super) fn default_inject_timeout_secs() -> u64 {
+    super::config::DEFAULT_INJECT_TIMEOUT_SECS
+}
+
+pub(super) fn default_frontend_ready_timeout_secs() -> u64 {
+    super::config::DEFAULT_FRONTEND_READY_TIMEOUT_SECS
+}
+
 pub fn default_gpu_adapter_name() -> String {
     "auto".to_string()
 }
@@ -84,7 +96,10 @@ pub fn get_default_config() -> Config {
             language: "auto".to_string(),
             renderer_backend: default_renderer_backend(),
             gpu_adapter_name: default_gpu_adapter_name(),
+            frontend_ready_timeout_secs: default_frontend_ready_timeout_secs(),
+            i18n_fallback_markers: false,
             stats_upload: true,
+            local_usage_stats: true,
             error_report_sentry_enabled: true,
             error_report_sentry_dsn: default_error_report_sentry_dsn(),
             error_report_sentry_auto: false,
@@ -100,6 +115,7 @@ pub fn get_default_config() -> Config {
                 },
                 curseforge_api_source: "mirror".to_string(),
                 curseforge_api_base: "https://mod.mcimirror.top/curseforge".to_string(),
+                scheduled_grace_minutes: default_scheduled_grace_minutes(),
             },
             update_channel: UpdateChannel::Stable,
             auto_check_updates: true,
@@ -115,9 +131,15 @@ pub fn get_default_config() -> Config {
             keep_downloaded_game_package: false,
             modify_appx_manifest: true,
             uwp_minimize_fix: true,
+            verify_before_launch: false,
+            inject_timeout_secs: default_inject_timeout_secs(),
+            require_signed_dlls: false,
+            game_log_dir: String::new(),
         },
         music: MusicConfig::default(),
         online: OnlineConfig::default(),
+        network: NetworkConfig::default(),
+        import: ImportConfig::default(),
         agreement_accepted: false,
     }
 }
@@ -0,0 +1,350 @@
+//! 启动器缓存登记表：各缓存模块导出一个 [`CacheDescriptor`]（名称、占用统计、清理函数、可选的预热函数），
+//! 在 [`REGISTERED_CACHES`] 中登记一行后即可出现在 `rebuild_caches` 和缓存占用概览中。
+//! 用户在资源管理器里手动移动包或存档后，各缓存可能各自过期，`rebuild_caches` 统一清理并按需重新填充。
+
+use crate::tasks::task_manager::{
+    create_task_with_details, finish_task, is_cancelled, register_task_stage_labels,
+    set_task_message, update_progress,
+};
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Instant;
+use tracing::{info, warn};
+use walkdir::WalkDir;
+
+const CACHE_TASK_STAGE_LABELS: [(&str, &str); 2] =
+    [("clearing", "清理缓存"), ("warming", "重新填充缓存")];
+
+/// 缓存占用；内存缓存无法估算字节数时 `bytes` 为 0
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CacheUsage {
+    pub entries: u64,
+    pub bytes: u64,
+}
+
+pub(crate) struct CacheDescriptor {
+    /// `rebuild_caches` 中使用的分类名
+    pub name: &'static str,
+    pub label: &'static str,
+    /// 未指定分类时是否处理；HTTP 下载缓存等重新获取代价高的缓存为 false
+    pub default_selected: bool,
+    pub usage: fn() -> CacheUsage,
+    /// 清空缓存，返回清理前的占用
+    pub clear: fn() -> Result<CacheUsage, String>,
+    /// 按最近一次使用的参数重新填充，返回填充后的条目数
+    pub warm: Option<fn() -> Result<u64, String>>,
+}
+
+static REGISTERED_CACHES: &[&CacheDescriptor] = &[
+    &crate::core::minecraft::world_chunk_stats::CACHE,
+    &crate::core::minecraft::pack_index::CACHE,
+    &crate::core::library_search::CACHE,
+    &crate::core::minecraft::pack_duplicates::CACHE,
+    &crate::core::minecraft::import::CACHE,
+    &crate::core::version::gdk_users::CACHE,
+    &crate::core::minecraft::skin_pack_preview::CACHE,
+    &crate::core::minecraft::map_info_cache::CACHE,
+    &crate::core::sponsors::CACHE,
+    &crate::core::minecraft::launcher::history::CACHE,
+    &crate::music::cover_cache::CACHE,
+    &crate::downloads::api::CACHE,
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheStorageEntry {
+    pub name: String,
+    pub label: String,
+    pub entries: u64,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheRebuildEntry {
+    pub name: String,
+    pub label: String,
+    /// 清理前的占用
+    pub cleared: CacheUsage,
+    /// 未预热或不支持预热时为 `None`
+    pub warmed_entries: Option<u64>,
+    pub duration_ms: u64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheRebuildReport {
+    pub task_id: String,
+    pub cancelled: bool,
+    pub caches: Vec<CacheRebuildEntry>,
+}
+
+/// 目录下文件数和总大小
+pub(crate) fn dir_usage(path: &Path) -> CacheUsage {
+    WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .fold(CacheUsage::default(), |usage, metadata| CacheUsage {
+            entries: usage.entries + 1,
+            bytes: usage.bytes + metadata.len(),
+        })
+}
+
+/// 删除缓存目录，返回删除前的占用；目录不存在视为已清理
+pub(crate) fn clear_dir(path: &Path) -> Result<CacheUsage, String> {
+    let usage = dir_usage(path);
+    match fs::remove_dir_all(path) {
+        Ok(()) => Ok(usage),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(usage),
+        Err(error) => Err(format!("删除缓存目录失败 {}: {}", path.display(), error)),
+    }
+}
+
+fn select_caches(
+    registry: &'static [&'static CacheDescriptor],
+    categories: Option<Vec<String>>,
+) -> Result<Vec<&'static CacheDescriptor>, String> {
+    let Some(categories) = categories.filter(|categories| !categories.is_empty()) else {
+        return Ok(registry
+            .iter()
+            .copied()
+            .filter(|cache| cache.default_selected)
+            .collect());
+    };
+    let mut selected = Vec::new();
+    for category in categories {
+        let category = category.trim();
+        let cache = registry
+            .iter()
+            .copied()
+            .find(|cache| cache.name.eq_ignore_ascii_case(category))
+            .ok_or_else(|| format!("未知的缓存分类: {}", category))?;
+        if !selected
+            .iter()
+            .any(|picked: &&CacheDescriptor| picked.name == cache.name)
+        {
+            selected.push(cache);
+        }
+    }
+    Ok(selected)
+}
+
+fn rebuild_cache(cache: &CacheDescriptor, warm: bool) -> CacheRebuildEntry {
+    let started = Instant::now();
+    let mut entry = CacheRebuildEntry {
+        name: cache.name.to_string(),
+        label: cache.label.to_string(),
+        cleared: CacheUsage::default(),
+        warmed_entries: None,
+        duration_ms: 0,
+        error: None,
+    };
+    match (cache.clear)() {
+        Ok(cleared) => entry.cleared = cleared,
+        Err(error) => entry.error = Some(error),
+    }
+    if warm
+        && entry.error.is_none()
+        && let Some(warm_fn) = cache.warm
+    {
+        match warm_fn() {
+            Ok(entries) => entry.warmed_entries = Some(entries),
+            Err(error) => entry.error = Some(error),
+        }
+    }
+    entry.duration_ms = started.elapsed().as_millis() as u64;
+    entry
+}
+
+/// 清理选中的缓存，`warm` 为 true 时随后重新填充支持预热的缓存；
+/// `categories` 为空时处理全部默认分类（不含 HTTP 下载缓存）。作为可取消任务运行，逐个缓存检查取消
+pub async fn rebuild_caches(
+    categories: Option<Vec<String>>,
+    warm: bool,
+) -> Result<CacheRebuildReport, String> {
+    let selected = select_caches(REGISTERED_CACHES, categories)?;
+    register_task_stage_labels(CACHE_TASK_STAGE_LABELS);
+    let task_id = create_task_with_details(
+        None,
+        "重建缓存",
+        Some(
+            selected
+                .iter()
+                .map(|cache| cache.label)
+                .collect::<Vec<_>>()
+                .join("、"),
+        ),
+        "clearing",
+        Some(selected.len() as u64),
+        false,
+    );
+
+    let blocking_task_id = task_id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let mut caches = Vec::with_capacity(selected.len());
+        for cache in selected {
+            if is_cancelled(&blocking_task_id) {
+                return (caches, true);
+            }
+            let stage = if warm && cache.warm.is_some() {
+                "warming"
+            } else {
+                "clearing"
+            };
+            set_task_message(&blocking_task_id, Some(cache.label.to_string()));
+            let entry = rebuild_cache(cache, warm);
+            if let Some(error) = &entry.error {
+                warn!(cache = cache.name, "重建缓存失败: {}", error);
+            }
+            update_progress(&blocking_task_id, 1, None, Some(stage));
+            caches.push(entry);
+        }
+        (caches, false)
+    })
+    .await;
+
+    let (caches, cancelled) = match result {
+        Ok(result) => result,
+        Err(error) => {
+            let message = format!("重建缓存任务失败: {}", error);
+            finish_task(&task_id, "error", Some(message.clone()));
+            return Err(message);
+        }
+    };
+    let failed = caches.iter().filter(|entry| entry.error.is_some()).count();
+    info!(rebuilt = caches.len(), failed, cancelled, "缓存重建结束");
+    if cancelled {
+        finish_task(&task_id, "cancelled", None);
+    } else if failed > 0 {
+        finish_task(
+            &task_id,
+            "error",
+            Some(format!("{} 个缓存重建失败", failed)),
+        );
+    } else {
+        finish_task(
+            &task_id,
+            "completed",
+            Some(format!("已重建 {} 个缓存", caches.len())),
+        );
+    }
+    Ok(CacheRebuildReport {
+        task_id,
+        cancelled,
+        caches,
+    })
+}
+
+/// 存储概览中“缓存”分类的明细，按登记顺序列出全部缓存
+pub async fn get_cache_storage_overview() -> Result<Vec<CacheStorageEntry>, String> {
+    tokio::task::spawn_blocking(|| {
+        REGISTERED_CACHES
+            .iter()
+            .map(|cache| {
+                let usage = (cache.usage)();
+                CacheStorageEntry {
+                    name: cache.name.to_string(),
+                    label: cache.label.to_string(),
+                    entries: usage.entries,
+                    bytes: usage.bytes,
+                }
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| format!("读取缓存占用任务失败: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static TEST_ENTRIES: AtomicU64 = AtomicU64::new(3);
+
+    fn test_usage() -> CacheUsage {
+        CacheUsage {
+            entries: TEST_ENTRIES.load(Ordering::Relaxed),
+            bytes: 0,
+        }
+    }
+
+    fn test_clear() -> Result<CacheUsage, String> {
+        Ok(CacheUsage {
+            entries: TEST_ENTRIES.swap(0, Ordering::Relaxed),
+            bytes: 0,
+        })
+    }
+
+    fn test_warm() -> Result<u64, String> {
+        TEST_ENTRIES.store(5, Ordering::Relaxed);
+        Ok(5)
+    }
+
+    fn failing_clear() -> Result<CacheUsage, String> {
+        Err("locked".to_string())
+    }
+
+    static WARMABLE: CacheDescriptor = CacheDescriptor {
+        name: "warmable",
+        label: "可预热",
+        default_selected: true,
+        usage: test_usage,
+        clear: test_clear,
+        warm: Some(test_warm),
+    };
+    static OPT_IN: CacheDescriptor = CacheDescriptor {
+        name: "opt_in",
+        label: "默认不处理",
+        default_selected: false,
+        usage: CacheUsage::default,
+        clear: failing_clear,
+        warm: None,
+    };
+    static TEST_REGISTRY: &[&CacheDescriptor] = &[&WARMABLE, &OPT_IN];
+
+    #[test]
+    fn selects_defaults_and_rebuilds_with_counts() {
+        let defaults = select_caches(TEST_REGISTRY, None).expect("select defaults");
+        assert_eq!(
+            defaults.iter().map(|cache| cache.name).collect::<Vec<_>>(),
+            ["warmable"]
+        );
+        let explicit = select_caches(
+            TEST_REGISTRY,
+            Some(vec!["OPT_IN".to_string(), "opt_in".to_string()]),
+        )
+        .expect("select explicit");
+        assert_eq!(explicit.len(), 1);
+        assert!(select_caches(TEST_REGISTRY, Some(vec!["http".to_string()])).is_err());
+
+        let rebuilt = rebuild_cache(&WARMABLE, true);
+        assert_eq!(rebuilt.cleared.entries, 3);
+        assert_eq!(rebuilt.warmed_entries, Some(5));
+        assert_eq!((WARMABLE.usage)().entries, 5);
+        let failed = rebuild_cache(&OPT_IN, true);
+        assert_eq!(failed.error.as_deref(), Some("locked"));
+        assert_eq!(failed.warmed_entries, None);
+
+        let dir =
+            std::env::temp_dir().join(format!("bmcbl-cache-registry-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("nested")).expect("create cache dir");
+        fs::write(dir.join("a.png"), b"1234").expect("write cache file");
+        fs::write(dir.join("nested/b.png"), b"56").expect("write cache file");
+        assert_eq!(
+            clear_dir(&dir).expect("clear dir"),
+            CacheUsage {
+                entries: 2,
+                bytes: 6
+            }
+        );
+        assert!(!dir.exists());
+        assert_eq!(
+            clear_dir(&dir).expect("clear missing dir"),
+            CacheUsage::default()
+        );
+    }
+}
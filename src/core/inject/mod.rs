@@ -1,2 +1,3 @@
 pub mod inject;
 pub mod pe;
+pub mod signature;
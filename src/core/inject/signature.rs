@@ -0,0 +1,228 @@
+#![cfg(target_os = "windows")]
+//! 校验 mods 目录中 DLL 的 Authenticode 签名。未签名或签名不受信任的 DLL 可能来路不明，
+//! 开启 `require_signed_dlls` 后启动时会跳过这些 DLL 并发出安全警告事件。
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::fs;
+use std::os::windows::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use tokio::sync::broadcast;
+use tracing::warn;
+use windows::Win32::Foundation::{HANDLE, HWND};
+use windows::Win32::Security::Cryptography::{CERT_NAME_SIMPLE_DISPLAY_TYPE, CertGetNameStringW};
+use windows::Win32::Security::WinTrust::{
+    WINTRUST_ACTION_GENERIC_VERIFY_V2, WINTRUST_DATA, WINTRUST_DATA_0, WINTRUST_FILE_INFO,
+    WTD_CHOICE_FILE, WTD_REVOKE_NONE, WTD_STATEACTION_CLOSE, WTD_STATEACTION_VERIFY, WTD_UI_NONE,
+    WTHelperGetProvSignerFromChain, WTHelperProvDataFromStateData, WinVerifyTrust,
+};
+use windows::core::PCWSTR;
+
+/// 文件没有嵌入签名时 WinVerifyTrust 返回的错误码
+const TRUST_E_NOSIGNATURE: i32 = 0x800B0100_u32 as i32;
+const TRUST_E_SUBJECT_FORM_UNKNOWN: i32 = 0x800B0003_u32 as i32;
+const TRUST_E_PROVIDER_UNKNOWN: i32 = 0x800B0001_u32 as i32;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DllSignatureStatus {
+    pub dll_name: String,
+    pub is_signed: bool,
+    /// 签名证书的显示名称
+    pub subject: Option<String>,
+    pub is_trusted: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DllSecurityWarning {
+    pub dll_name: String,
+    pub path: String,
+    pub message: String,
+}
+
+static DLL_SECURITY_EVENTS: Lazy<broadcast::Sender<DllSecurityWarning>> =
+    Lazy::new(|| broadcast::channel(32).0);
+
+pub fn subscribe_dll_security_events() -> broadcast::Receiver<DllSecurityWarning> {
+    DLL_SECURITY_EVENTS.subscribe()
+}
+
+fn wide_path(path: &Path) -> Vec<u16> {
+    path.as_os_str().encode_wide().chain(Some(0)).collect()
+}
+
+/// 从 WinVerifyTrust 的状态数据中取第一个签名者证书的名称
+unsafe fn signer_subject(state: HANDLE) -> Option<String> {
+    unsafe {
+        let provider = WTHelperProvDataFromStateData(state);
+        if provider.is_null() {
+            return None;
+        }
+        let signer = WTHelperGetProvSignerFromChain(provider, 0, false.into(), 0);
+        if signer.is_null() || (*signer).csCertChain == 0 || (*signer).pasCertChain.is_null() {
+            return None;
+        }
+        let cert = (*(*signer).pasCertChain).pCert;
+        if cert.is_null() {
+            return None;
+        }
+        let mut buffer = [0u16; 256];
+        let length = CertGetNameStringW(
+            cert,
+            CERT_NAME_SIMPLE_DISPLAY_TYPE,
+            0,
+            None,
+            Some(&mut buffer),
+        ) as usize;
+        // 返回值包含结尾的 0
+        let name = String::from_utf16_lossy(&buffer[..length.saturating_sub(1)]);
+        (!name.is_empty()).then_some(name)
+    }
+}
+
+/// 校验单个文件的 Authenticode 签名
+pub fn verify_dll_signature(path: &Path) -> DllSignatureStatus {
+    let dll_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let wide = wide_path(path);
+    let mut file_info = WINTRUST_FILE_INFO {
+        cbStruct: size_of::<WINTRUST_FILE_INFO>() as u32,
+        pcwszFilePath: PCWSTR(wide.as_ptr()),
+        ..Default::default()
+    };
+    let mut data = WINTRUST_DATA {
+        cbStruct: size_of::<WINTRUST_DATA>() as u32,
+        dwUIChoice: WTD_UI_NONE,
+        fdwRevocationChecks: WTD_REVOKE_NONE,
+        dwUnionChoice: WTD_CHOICE_FILE,
+        Anonymous: WINTRUST_DATA_0 {
+            pFile: &mut file_info,
+        },
+        dwStateAction: WTD_STATEACTION_VERIFY,
+        ..Default::default()
+    };
+    let mut action = WINTRUST_ACTION_GENERIC_VERIFY_V2;
+
+    // SAFETY: data / file_info / wide 在整个调用期间有效，验证后用 STATEACTION_CLOSE 释放状态
+    let (status, subject) = unsafe {
+        let status = WinVerifyTrust(
+            HWND::default(),
+            &mut action,
+            &mut data as *mut WINTRUST_DATA as *mut _,
+        );
+        let subject = signer_subject(data.hWVTStateData);
+        data.dwStateAction = WTD_STATEACTION_CLOSE;
+        WinVerifyTrust(
+            HWND::default(),
+            &mut action,
+            &mut data as *mut WINTRUST_DATA as *mut _,
+        );
+        (status, subject)
+    };
+
+    match status {
+        0 => DllSignatureStatus {
+            dll_name,
+            is_signed: true,
+            subject,
+            is_trusted: true,
+            error: None,
+        },
+        TRUST_E_NOSIGNATURE | TRUST_E_SUBJECT_FORM_UNKNOWN | TRUST_E_PROVIDER_UNKNOWN => {
+            DllSignatureStatus {
+                dll_name,
+                is_signed: false,
+                subject: None,
+                is_trusted: false,
+                error: None,
+            }
+        }
+        code => DllSignatureStatus {
+            dll_name,
+            is_signed: true,
+            subject,
+            is_trusted: false,
+            error: Some(format!(
+                "签名不受信任: {}",
+                windows::core::Error::from_hresult(windows::core::HRESULT(code)).message()
+            )),
+        },
+    }
+}
+
+fn list_dlls(mods_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut dlls = Vec::new();
+    for entry in fs::read_dir(mods_dir)
+        .map_err(|e| format!("读取 mods 目录失败 {}: {}", mods_dir.display(), e))?
+        .flatten()
+    {
+        let path = entry.path();
+        if path.is_dir() {
+            // 每个模组一个子目录
+            if let Ok(children) = fs::read_dir(&path) {
+                dlls.extend(
+                    children
+                        .flatten()
+                        .map(|child| child.path())
+                        .filter(|p| is_dll(p)),
+                );
+            }
+        } else if is_dll(&path) {
+            dlls.push(path);
+        }
+    }
+    dlls.sort();
+    Ok(dlls)
+}
+
+fn is_dll(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("dll"))
+}
+
+/// 检查 mods 目录（含一层模组子目录）中每个 DLL 的签名状态
+pub async fn check_inject_dll_signatures(
+    mods_dir: String,
+) -> Result<Vec<DllSignatureStatus>, String> {
+    tokio::task::spawn_blocking(move || {
+        Ok(list_dlls(Path::new(&mods_dir))?
+            .iter()
+            .map(|path| verify_dll_signature(path))
+            .collect())
+    })
+    .await
+    .map_err(|e| format!("检查 DLL 签名任务失败: {}", e))?
+}
+
+/// 过滤掉未签名或签名不受信任的 DLL，每个被跳过的 DLL 发出一条安全警告
+pub fn retain_signed_dlls(
+    mods: Vec<(PathBuf, u64)>,
+) -> (Vec<(PathBuf, u64)>, Vec<DllSecurityWarning>) {
+    let mut kept = Vec::with_capacity(mods.len());
+    let mut warnings = Vec::new();
+    for (path, delay) in mods {
+        let status = verify_dll_signature(&path);
+        if status.is_signed && status.is_trusted {
+            kept.push((path, delay));
+            continue;
+        }
+        let message = if status.is_signed {
+            status.error.unwrap_or_else(|| "签名不受信任".to_string())
+        } else {
+            "DLL 未签名".to_string()
+        };
+        let warning = DllSecurityWarning {
+            dll_name: status.dll_name,
+            path: path.to_string_lossy().into_owned(),
+            message: format!("已跳过注入: {message}"),
+        };
+        warn!(dll = %warning.path, "{}", warning.message);
+        let _ = DLL_SECURITY_EVENTS.send(warning.clone());
+        warnings.push(warning);
+    }
+    (kept, warnings)
+}
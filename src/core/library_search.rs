@@ -0,0 +1,567 @@
+//! 存档 / 资源包 / 行为包 / 皮肤包 / 版本的统一搜索。
+//! 扫描结果按 (范围, 路径参数, 语言) 缓存成内存索引，逐键输入时只在索引上做模糊匹配；
+//! 索引缺失或过期时才回退到目录扫描。
+
+use crate::core::cache_registry::{CacheDescriptor, CacheUsage};
+use crate::core::minecraft::paths::GamePathOptions;
+use crate::utils::file_ops;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+const INDEX_TTL: Duration = Duration::from_secs(60);
+const DEFAULT_LIMIT: usize = 50;
+
+const PREFIX_BONUS: i32 = 400;
+const SUBSTRING_SCORE: i32 = 600;
+const SUBSEQUENCE_SCORE: i32 = 200;
+const WORD_BOUNDARY_BONUS: i32 = 80;
+const CONSECUTIVE_BONUS: i32 = 15;
+
+static LIBRARY_INDEX: LazyLock<Mutex<HashMap<String, IndexSlot>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+/// 建过索引的请求参数，`rebuild_caches` 清空索引后按这些参数重新扫描
+static INDEXED_REQUESTS: LazyLock<Mutex<HashMap<String, IndexRequest>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+pub(crate) static CACHE: CacheDescriptor = CacheDescriptor {
+    name: "library_index",
+    label: "资源库搜索索引",
+    default_selected: true,
+    usage: library_index_usage,
+    clear: clear_library_index,
+    warm: Some(warm_library_index),
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LibraryScope {
+    Worlds,
+    ResourcePacks,
+    BehaviorPacks,
+    SkinPacks,
+    Versions,
+}
+
+impl LibraryScope {
+    pub const ALL: [LibraryScope; 5] = [
+        LibraryScope::Worlds,
+        LibraryScope::ResourcePacks,
+        LibraryScope::BehaviorPacks,
+        LibraryScope::SkinPacks,
+        LibraryScope::Versions,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            LibraryScope::Worlds => "worlds",
+            LibraryScope::ResourcePacks => "resource_packs",
+            LibraryScope::BehaviorPacks => "behavior_packs",
+            LibraryScope::SkinPacks => "skin_packs",
+            LibraryScope::Versions => "versions",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchedField {
+    Name,
+    FolderName,
+    Uuid,
+    /// 包描述；版本条目为版本号
+    Description,
+}
+
+/// 命中区间，按字符（非字节）计的左闭右开区间
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct HighlightSpan {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LibrarySearchHit {
+    pub entity_type: LibraryScope,
+    pub name: String,
+    pub path: String,
+    pub score: i32,
+    pub matched_field: MatchedField,
+    pub matched_text: String,
+    pub highlights: Vec<HighlightSpan>,
+}
+
+#[derive(Debug, Clone)]
+struct IndexedEntry {
+    entity_type: LibraryScope,
+    name: String,
+    folder_name: String,
+    uuid: Option<String>,
+    description: Option<String>,
+    path: String,
+}
+
+impl IndexedEntry {
+    fn fields(&self) -> [(MatchedField, Option<&str>, i32); 4] {
+        // 名称优先，其次文件夹名、UUID，描述权重最低
+        [
+            (MatchedField::Name, Some(self.name.as_str()), 0),
+            (
+                MatchedField::FolderName,
+                Some(self.folder_name.as_str()),
+                -50,
+            ),
+            (MatchedField::Uuid, self.uuid.as_deref(), -100),
+            (MatchedField::Description, self.description.as_deref(), -200),
+        ]
+    }
+}
+
+#[derive(Clone)]
+struct IndexRequest {
+    scope: LibraryScope,
+    locale_code: String,
+    options: GamePathOptions,
+}
+
+struct IndexSlot {
+    built_at: Instant,
+    entries: Arc<Vec<IndexedEntry>>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct FuzzyMatch {
+    score: i32,
+    spans: Vec<HighlightSpan>,
+}
+
+fn fold_char(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn is_word_boundary(chars: &[char], index: usize) -> bool {
+    index == 0 || !chars[index - 1].is_alphanumeric()
+}
+
+/// 大小写不敏感的模糊匹配：连续子串优先（开头命中额外加分），否则按子序列匹配，
+/// 连续命中与单词边界加分、跨越的字符扣分
+fn fuzzy_match(query: &[char], candidate: &str) -> Option<FuzzyMatch> {
+    if query.is_empty() {
+        return None;
+    }
+    let chars: Vec<char> = candidate.chars().map(fold_char).collect();
+    if chars.len() < query.len() {
+        return None;
+    }
+
+    if let Some(start) = chars
+        .windows(query.len())
+        .position(|window| window == query)
+    {
+        let mut score = SUBSTRING_SCORE - start.min(200) as i32;
+        if start == 0 {
+            score += PREFIX_BONUS;
+        } else if is_word_boundary(&chars, start) {
+            score += WORD_BOUNDARY_BONUS;
+        }
+        // 完整命中短名称的结果排在前面
+        score -= (chars.len() - query.len()).min(100) as i32;
+        return Some(FuzzyMatch {
+            score,
+            spans: vec![HighlightSpan {
+                start,
+                end: start + query.len(),
+            }],
+        });
+    }
+
+    let mut spans: Vec<HighlightSpan> = Vec::new();
+    let mut score = SUBSEQUENCE_SCORE;
+    let mut query_index = 0;
+    let mut last_match: Option<usize> = None;
+    for (index, c) in chars.iter().enumerate() {
+        if query_index == query.len() {
+            break;
+        }
+        if *c != query[query_index] {
+            continue;
+        }
+        match last_match {
+            Some(last) if last + 1 == index => {
+                score += CONSECUTIVE_BONUS;
+                if let Some(span) = spans.last_mut() {
+                    span.end = index + 1;
+                }
+            }
+            previous => {
+                score -= (index - previous.map_or(0, |last| last + 1)).min(20) as i32;
+                spans.push(HighlightSpan {
+                    start: index,
+                    end: index + 1,
+                });
+            }
+        }
+        if index == 0 {
+            score += PREFIX_BONUS / 2;
+        } else if is_word_boundary(&chars, index) {
+            score += WORD_BOUNDARY_BONUS / 4;
+        }
+        last_match = Some(index);
+        query_index += 1;
+    }
+
+    (query_index == query.len()).then_some(FuzzyMatch { score, spans })
+}
+
+fn match_entry(query: &[char], entry: &IndexedEntry) -> Option<LibrarySearchHit> {
+    let mut best: Option<(MatchedField, &str, FuzzyMatch)> = None;
+    for (field, text, weight) in entry.fields() {
+        let Some(text) = text.filter(|text| !text.is_empty()) else {
+            continue;
+        };
+        let Some(mut found) = fuzzy_match(query, text) else {
+            continue;
+        };
+        found.score += weight;
+        if best
+            .as_ref()
+            .is_none_or(|(_, _, current)| found.score > current.score)
+        {
+            best = Some((field, text, found));
+        }
+    }
+
+    let (matched_field, matched_text, found) = best?;
+    Some(LibrarySearchHit {
+        entity_type: entry.entity_type,
+        name: entry.name.clone(),
+        path: entry.path.clone(),
+        score: found.score,
+        matched_field,
+        matched_text: matched_text.to_string(),
+        highlights: found.spans,
+    })
+}
+
+fn index_key(scope: LibraryScope, locale_code: &str, options: &GamePathOptions) -> String {
+    if scope == LibraryScope::Versions {
+        return scope.as_str().to_string();
+    }
+    let options = serde_json::to_string(options).unwrap_or_default();
+    format!("{}|{}|{}", scope.as_str(), locale_code, options)
+}
+
+fn scan_scope(
+    scope: LibraryScope,
+    locale_code: &str,
+    options: &GamePathOptions,
+) -> Result<Vec<IndexedEntry>, String> {
+    let entries = match scope {
+        LibraryScope::Worlds => crate::core::minecraft::map::list_worlds_standard(options)
+            .map_err(|error| format!("读取地图失败: {error:?}"))?
+            .into_iter()
+            .map(|world| IndexedEntry {
+                entity_type: scope,
+                name: world
+                    .level_name
+                    .clone()
+                    .filter(|name| !name.trim().is_empty())
+                    .unwrap_or_else(|| world.folder_name.clone()),
+                folder_name: world.folder_name,
+                uuid: None,
+                description: None,
+                path: world.folder_path,
+            })
+            .collect(),
+        LibraryScope::ResourcePacks | LibraryScope::BehaviorPacks => {
+            let kind = if scope == LibraryScope::ResourcePacks {
+                "resource_packs"
+            } else {
+                "behavior_packs"
+            };
+            crate::core::minecraft::resource_packs::read_packs_standard(kind, locale_code, options)
+                .map_err(|error| format!("读取资源包失败: {error:?}"))?
+                .into_iter()
+                .map(|pack| {
+                    let header = pack.manifest_parsed.and_then(|manifest| manifest.header);
+                    IndexedEntry {
+                        entity_type: scope,
+                        name: pack.display_name,
+                        folder_name: pack.folder_name,
+                        uuid: header.as_ref().and_then(|header| header.uuid.clone()),
+                        description: header
+                            .and_then(|header| header.description)
+                            .or(pack.short_description),
+                        path: pack.folder_path,
+                    }
+                })
+                .collect()
+        }
+        LibraryScope::SkinPacks => {
+            crate::core::minecraft::skin_packs::read_skin_packs_standard(locale_code, options)
+                .map_err(|error| format!("读取皮肤包失败: {error:?}"))?
+                .into_iter()
+                .map(|pack| IndexedEntry {
+                    entity_type: scope,
+                    name: pack.display_name,
+                    folder_name: pack.folder_name,
+                    uuid: None,
+                    description: pack.description,
+                    path: pack.folder_path,
+                })
+                .collect()
+        }
+        LibraryScope::Versions => {
+            let versions_dir = file_ops::versions_dir();
+            if !versions_dir.is_dir() {
+                return Ok(Vec::new());
+            }
+            crate::core::version::version_manager::get_appx_version_list_blocking(&versions_dir)
+                .map_err(|error| format!("读取版本列表失败: {error:?}"))?
+                .into_iter()
+                .map(|version| IndexedEntry {
+                    entity_type: scope,
+                    name: version.name.to_string(),
+                    folder_name: version.folder.to_string(),
+                    uuid: None,
+                    description: Some(version.version.to_string()),
+                    path: version.path.to_string(),
+                })
+                .collect()
+        }
+    };
+    Ok(entries)
+}
+
+/// 读取范围内的索引；缓存过期或不存在时重新扫描目录
+fn indexed_entries(
+    scope: LibraryScope,
+    locale_code: &str,
+    options: &GamePathOptions,
+) -> Result<Arc<Vec<IndexedEntry>>, String> {
+    let key = index_key(scope, locale_code, options);
+    {
+        let index = LIBRARY_INDEX.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(slot) = index.get(&key)
+            && slot.built_at.elapsed() < INDEX_TTL
+        {
+            return Ok(slot.entries.clone());
+        }
+    }
+
+    let started_at = Instant::now();
+    let entries = Arc::new(scan_scope(scope, locale_code, options)?);
+    debug!(
+        scope = scope.as_str(),
+        count = entries.len(),
+        elapsed_ms = started_at.elapsed().as_millis(),
+        "library index rebuilt"
+    );
+    INDEXED_REQUESTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(
+            key.clone(),
+            IndexRequest {
+                scope,
+                locale_code: locale_code.to_string(),
+                options: options.clone(),
+            },
+        );
+    LIBRARY_INDEX
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(
+            key,
+            IndexSlot {
+                built_at: Instant::now(),
+                entries: entries.clone(),
+            },
+        );
+    Ok(entries)
+}
+
+/// 丢弃搜索索引，导入、删除或重命名后调用，下次搜索会重新扫描
+pub fn invalidate_library_index() {
+    LIBRARY_INDEX
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clear();
+}
+
+fn library_index_usage() -> CacheUsage {
+    let index = LIBRARY_INDEX.lock().unwrap_or_else(|e| e.into_inner());
+    CacheUsage {
+        entries: index.values().map(|slot| slot.entries.len() as u64).sum(),
+        bytes: 0,
+    }
+}
+
+fn clear_library_index() -> Result<CacheUsage, String> {
+    let usage = library_index_usage();
+    invalidate_library_index();
+    Ok(usage)
+}
+
+/// 按之前建过索引的参数重新扫描，返回重建后的条目总数
+fn warm_library_index() -> Result<u64, String> {
+    let requests: Vec<IndexRequest> = INDEXED_REQUESTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .values()
+        .cloned()
+        .collect();
+    let mut entries = 0;
+    for request in requests {
+        match indexed_entries(request.scope, &request.locale_code, &request.options) {
+            Ok(indexed) => entries += indexed.len() as u64,
+            Err(error) => warn!(
+                scope = request.scope.as_str(),
+                "重建搜索索引失败: {}", error
+            ),
+        }
+    }
+    Ok(entries)
+}
+
+fn search_entries<'a>(
+    query: &str,
+    entries: impl Iterator<Item = &'a IndexedEntry>,
+    limit: usize,
+) -> Vec<LibrarySearchHit> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(fold_char)
+        .collect();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut hits: Vec<LibrarySearchHit> = entries
+        .filter_map(|entry| match_entry(&query, entry))
+        .collect();
+    hits.sort_by(|left, right| {
+        right
+            .score
+            .cmp(&left.score)
+            .then_with(|| left.name.cmp(&right.name))
+    });
+    hits.truncate(limit);
+    hits
+}
+
+pub fn search_library_blocking(
+    query: &str,
+    scopes: &[LibraryScope],
+    limit: usize,
+    locale_code: &str,
+    options: &GamePathOptions,
+) -> Result<Vec<LibrarySearchHit>, String> {
+    let scopes: &[LibraryScope] = if scopes.is_empty() {
+        &LibraryScope::ALL
+    } else {
+        scopes
+    };
+
+    let mut indexes = Vec::with_capacity(scopes.len());
+    for scope in scopes {
+        match indexed_entries(*scope, locale_code, options) {
+            Ok(entries) => indexes.push(entries),
+            Err(error) => warn!(scope = scope.as_str(), %error, "library search scope skipped"),
+        }
+    }
+
+    Ok(search_entries(
+        query,
+        indexes.iter().flat_map(|entries| entries.iter()),
+        limit,
+    ))
+}
+
+/// 在指定范围内按名称（本地化）、文件夹名、UUID 与描述模糊搜索，结果按得分排序；
+/// `scopes` 为空时搜索全部范围，`limit` 为 0 时使用默认上限
+pub async fn search_library(
+    query: String,
+    scopes: Vec<LibraryScope>,
+    limit: usize,
+    locale_code: String,
+    options: GamePathOptions,
+) -> Result<Vec<LibrarySearchHit>, String> {
+    let limit = if limit == 0 { DEFAULT_LIMIT } else { limit };
+    tokio::task::spawn_blocking(move || {
+        search_library_blocking(&query, &scopes, limit, &locale_code, &options)
+    })
+    .await
+    .map_err(|e| format!("搜索任务失败: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(text: &str) -> Vec<char> {
+        text.chars().map(fold_char).collect()
+    }
+
+    fn entry(name: &str, folder_name: &str, uuid: Option<&str>) -> IndexedEntry {
+        IndexedEntry {
+            entity_type: LibraryScope::ResourcePacks,
+            name: name.to_string(),
+            folder_name: folder_name.to_string(),
+            uuid: uuid.map(str::to_string),
+            description: None,
+            path: format!("/packs/{folder_name}"),
+        }
+    }
+
+    #[test]
+    fn prefix_match_outranks_substring_and_subsequence() {
+        let prefix = fuzzy_match(&query("fa"), "Faithful").expect("prefix");
+        let substring = fuzzy_match(&query("fa"), "Default Faithful").expect("substring");
+        let subsequence = fuzzy_match(&query("fh"), "Faithful").expect("subsequence");
+
+        assert!(prefix.score > substring.score);
+        assert!(substring.score > subsequence.score);
+        assert_eq!(prefix.spans, vec![HighlightSpan { start: 0, end: 2 }]);
+        assert_eq!(
+            subsequence.spans,
+            vec![
+                HighlightSpan { start: 0, end: 1 },
+                HighlightSpan { start: 4, end: 5 },
+            ]
+        );
+        assert!(fuzzy_match(&query("xyz"), "Faithful").is_none());
+    }
+
+    #[test]
+    fn highlights_use_character_offsets() {
+        let found = fuzzy_match(&query("材质"), "原版材质包").expect("cjk substring");
+        assert_eq!(found.spans, vec![HighlightSpan { start: 2, end: 4 }]);
+    }
+
+    #[test]
+    fn search_ranks_name_matches_and_falls_back_to_uuid() {
+        let entries = [
+            entry("Better Leaves", "leaves_pack", None),
+            entry(
+                "Clear Water",
+                "water",
+                Some("0f3a1e2b-aaaa-bbbb-cccc-0123456789ab"),
+            ),
+            entry("Leaf Decay", "decay", None),
+        ];
+
+        let hits = search_entries("Lea", entries.iter(), 10);
+        let names: Vec<_> = hits.iter().map(|hit| hit.name.as_str()).collect();
+        assert_eq!(names, vec!["Leaf Decay", "Better Leaves", "Clear Water"]);
+
+        let hits = search_entries("0F3A1E2B", entries.iter(), 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].matched_field, MatchedField::Uuid);
+
+        assert!(search_entries("   ", entries.iter(), 10).is_empty());
+    }
+}
@@ -0,0 +1,270 @@
+//! 附加包兼容性检查：按已安装包的 UUID 核对每个包 `dependencies` 中声明的依赖，
+//! 找出游戏会静默忽略的缺失或版本过旧的依赖。
+
+use crate::core::minecraft::analysis_suppressions::{SuppressionFilter, TOOL_ADDON_COMPATIBILITY};
+use crate::core::minecraft::import::{
+    ManifestVersion, PartialManifest, VersionComparison, compare_version_vectors,
+    parse_manifest_str, strip_json_comments, version_to_string, version_vector,
+};
+use crate::core::minecraft::pack_names::resolve_pack_display_name;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+const PACK_DIR_NAMES: [&str; 4] = [
+    "behavior_packs",
+    "resource_packs",
+    "development_behavior_packs",
+    "development_resource_packs",
+];
+
+pub const ISSUE_MISSING_DEPENDENCY: &str = "missing_dependency";
+pub const ISSUE_OUTDATED_DEPENDENCY: &str = "outdated_dependency";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CompatibilityIssue {
+    pub pack_name: String,
+    pub pack_uuid: Option<String>,
+    pub required_uuid: String,
+    pub required_version: Option<String>,
+    pub installed_version: Option<String>,
+    /// `missing_dependency` 或 `outdated_dependency`
+    pub issue_type: String,
+}
+
+struct InstalledPack {
+    name: String,
+    uuid: Option<String>,
+    version: Option<ManifestVersion>,
+    manifest: PartialManifest,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CompatibilityReport {
+    pub issues: Vec<CompatibilityIssue>,
+    /// 被分析忽略列表隐藏的问题数
+    pub suppressed_count: usize,
+}
+
+impl CompatibilityIssue {
+    /// 忽略列表中使用的结果标识
+    pub fn finding_id(&self) -> String {
+        format!(
+            "{}:{}",
+            self.issue_type,
+            self.required_uuid.trim().to_ascii_lowercase()
+        )
+    }
+}
+
+/// 检查 `com.mojang` 目录下所有已安装包的依赖
+pub async fn list_addon_compatibility_issues(
+    mojang_dir: String,
+) -> Result<CompatibilityReport, String> {
+    let mojang_dir = PathBuf::from(mojang_dir);
+    tokio::task::spawn_blocking(move || {
+        let issues = list_issues_blocking(&mojang_dir)?;
+        let filter = SuppressionFilter::load(TOOL_ADDON_COMPATIBILITY);
+        let total = issues.len();
+        let issues: Vec<CompatibilityIssue> = issues
+            .into_iter()
+            .filter(|issue| !filter.is_suppressed(issue.pack_uuid.as_deref(), &issue.finding_id()))
+            .collect();
+        Ok(CompatibilityReport {
+            suppressed_count: total - issues.len(),
+            issues,
+        })
+    })
+    .await
+    .map_err(|e| format!("检查依赖任务失败: {}", e))?
+}
+
+fn list_issues_blocking(mojang_dir: &Path) -> Result<Vec<CompatibilityIssue>, String> {
+    if !mojang_dir.is_dir() {
+        return Err(format!("目录不存在: {}", mojang_dir.display()));
+    }
+    let packs = PACK_DIR_NAMES
+        .iter()
+        .flat_map(|dir_name| read_installed_packs(&mojang_dir.join(dir_name)))
+        .collect::<Vec<_>>();
+    Ok(find_issues(&packs))
+}
+
+fn read_installed_packs(packs_dir: &Path) -> Vec<InstalledPack> {
+    let Ok(entries) = fs::read_dir(packs_dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .filter_map(|pack_dir| {
+            let raw = fs::read_to_string(pack_dir.join("manifest.json")).ok()?;
+            let clean = strip_json_comments(raw.trim_start_matches('\u{feff}'));
+            let manifest = match parse_manifest_str(&clean) {
+                Ok((manifest, _)) => manifest,
+                Err(e) => {
+                    warn!("解析清单失败 {}: {}", pack_dir.display(), e);
+                    return None;
+                }
+            };
+            let header = manifest.header.as_ref();
+            let uuid = header.and_then(|header| header.uuid.clone());
+            let folder_name = pack_dir
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let (name, _) = resolve_pack_display_name(
+                &pack_dir,
+                header.and_then(|header| header.name.as_deref()),
+                uuid.as_deref(),
+                &folder_name,
+            );
+            Some(InstalledPack {
+                name,
+                uuid,
+                version: header.and_then(|header| header.version.clone()),
+                manifest,
+            })
+        })
+        .collect()
+}
+
+/// 同一 UUID 装了多份时以最高版本为准
+fn installed_index(packs: &[InstalledPack]) -> HashMap<String, &ManifestVersion> {
+    let mut index: HashMap<String, &ManifestVersion> = HashMap::new();
+    for pack in packs {
+        let (Some(uuid), Some(version)) = (pack.uuid.as_deref(), pack.version.as_ref()) else {
+            continue;
+        };
+        let key = uuid.trim().to_ascii_lowercase();
+        let is_newer = index.get(&key).is_none_or(|existing| {
+            compare_version_vectors(&version_vector(version), &version_vector(existing))
+                == VersionComparison::Newer
+        });
+        if is_newer {
+            index.insert(key, version);
+        }
+    }
+    index
+}
+
+fn find_issues(packs: &[InstalledPack]) -> Vec<CompatibilityIssue> {
+    let index = installed_index(packs);
+    let installed_uuids = packs
+        .iter()
+        .filter_map(|pack| pack.uuid.as_deref())
+        .map(|uuid| uuid.trim().to_ascii_lowercase())
+        .collect::<HashSet<_>>();
+
+    let mut issues = Vec::new();
+    for pack in packs {
+        // 依赖脚本 API 的 module_name 项不是包依赖
+        let dependencies = pack
+            .manifest
+            .dependencies
+            .iter()
+            .flatten()
+            .filter_map(|dependency| Some((dependency.uuid.as_deref()?, dependency)));
+        for (required_uuid, dependency) in dependencies {
+            let key = required_uuid.trim().to_ascii_lowercase();
+            let required_version = dependency.version.as_ref();
+            let installed_version = index.get(&key).copied();
+            let issue_type = if !installed_uuids.contains(&key) {
+                ISSUE_MISSING_DEPENDENCY
+            } else if let (Some(required), Some(installed)) = (required_version, installed_version)
+                && compare_version_vectors(&version_vector(installed), &version_vector(required))
+                    == VersionComparison::Older
+            {
+                ISSUE_OUTDATED_DEPENDENCY
+            } else {
+                continue;
+            };
+            issues.push(CompatibilityIssue {
+                pack_name: pack.name.clone(),
+                pack_uuid: pack.uuid.clone(),
+                required_uuid: required_uuid.to_string(),
+                required_version: required_version.map(version_to_string),
+                installed_version: installed_version.map(version_to_string),
+                issue_type: issue_type.to_string(),
+            });
+        }
+    }
+    issues.sort_by(|left, right| {
+        left.pack_name
+            .cmp(&right.pack_name)
+            .then_with(|| left.required_uuid.cmp(&right.required_uuid))
+    });
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_pack(mojang_dir: &Path, dir_name: &str, folder: &str, manifest: &str) {
+        let pack_dir = mojang_dir.join(dir_name).join(folder);
+        fs::create_dir_all(&pack_dir).expect("create pack dir");
+        fs::write(pack_dir.join("manifest.json"), manifest).expect("write manifest");
+    }
+
+    #[test]
+    fn reports_missing_and_outdated_pack_dependencies() {
+        let mojang_dir =
+            std::env::temp_dir().join(format!("bmcbl-addon-compat-{}", uuid::Uuid::new_v4()));
+        write_pack(
+            &mojang_dir,
+            "behavior_packs",
+            "bp",
+            r#"{
+                "format_version": 2,
+                "header": {"name": "§aMobs BP", "uuid": "bp-uuid", "version": [1, 0, 0]},
+                "modules": [{"type": "data", "uuid": "bp-module", "version": [1, 0, 0]}],
+                "dependencies": [
+                    {"uuid": "rp-uuid", "version": [1, 2, 0]},
+                    {"uuid": "absent-uuid", "version": "2.0.0"},
+                    {"module_name": "@minecraft/server", "version": "1.8.0"}
+                ]
+            }"#,
+        );
+        write_pack(
+            &mojang_dir,
+            "resource_packs",
+            "rp",
+            r#"{
+                "format_version": 2,
+                "header": {"name": "Mobs RP", "uuid": "rp-uuid", "version": [1, 1, 5]},
+                "modules": [{"type": "resources", "uuid": "rp-module", "version": [1, 1, 5]}],
+                "dependencies": [{"uuid": "bp-uuid", "version": [1, 0, 0]}]
+            }"#,
+        );
+
+        let issues = list_issues_blocking(&mojang_dir).expect("list issues");
+
+        assert_eq!(
+            issues,
+            vec![
+                CompatibilityIssue {
+                    pack_name: "Mobs BP".to_string(),
+                    pack_uuid: Some("bp-uuid".to_string()),
+                    required_uuid: "absent-uuid".to_string(),
+                    required_version: Some("2.0.0".to_string()),
+                    installed_version: None,
+                    issue_type: ISSUE_MISSING_DEPENDENCY.to_string(),
+                },
+                CompatibilityIssue {
+                    pack_name: "Mobs BP".to_string(),
+                    pack_uuid: Some("bp-uuid".to_string()),
+                    required_uuid: "rp-uuid".to_string(),
+                    required_version: Some("1.2.0".to_string()),
+                    installed_version: Some("1.1.5".to_string()),
+                    issue_type: ISSUE_OUTDATED_DEPENDENCY.to_string(),
+                },
+            ]
+        );
+
+        fs::remove_dir_all(&mojang_dir).expect("remove test dir");
+    }
+}
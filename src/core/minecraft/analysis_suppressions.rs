@@ -0,0 +1,282 @@
+//! 分析结果忽略列表：保存在 `BMCBL/analysis_suppressions.json`，按（工具、包 UUID、结果标识）
+//! 匹配检查结果。刻意覆盖原版或保留重复包的用户不必每次都看到同样的提示；
+//! 被忽略的结果仍计入各报告的 `suppressed_count`，到期的条目自动失效、结果重新显示。
+
+use crate::utils::file_ops;
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeZone};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::warn;
+
+const SUPPRESSIONS_FILE_NAME: &str = "analysis_suppressions.json";
+
+/// `list_addon_compatibility_issues`，结果标识为 `<issue_type>:<依赖 UUID>`
+pub const TOOL_ADDON_COMPATIBILITY: &str = "addon_compatibility";
+/// `find_duplicate_packs`，结果标识为分组指纹
+pub const TOOL_DUPLICATE_PACKS: &str = "duplicate_packs";
+
+static SUPPRESSIONS_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnalysisSuppression {
+    pub id: String,
+    pub tool: String,
+    /// 结果涉及的包 UUID，成对的结果（如重复包）填两个；为空时匹配任意包
+    #[serde(default)]
+    pub pack_uuids: Vec<String>,
+    /// 结果标识或路径，支持 `*` / `?` 通配，不区分大小写
+    pub finding_pattern: String,
+    #[serde(default)]
+    pub reason: String,
+    /// ISO 8601 格式
+    pub created_at: String,
+    /// 到期时间（RFC3339 或 `YYYY-MM-DD`，后者到当天结束），到期后结果重新显示
+    #[serde(default)]
+    pub expires_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewAnalysisSuppression {
+    pub tool: String,
+    #[serde(default)]
+    pub pack_uuids: Vec<String>,
+    pub finding_pattern: String,
+    #[serde(default)]
+    pub reason: String,
+    #[serde(default)]
+    pub expires_at: Option<String>,
+}
+
+fn suppressions_path() -> PathBuf {
+    file_ops::bmcbl_subdir(SUPPRESSIONS_FILE_NAME)
+}
+
+fn load_suppressions(path: &Path) -> Vec<AnalysisSuppression> {
+    let Ok(raw) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    match serde_json::from_str(&raw) {
+        Ok(suppressions) => suppressions,
+        Err(e) => {
+            warn!("解析分析忽略列表失败，已忽略: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// 先写临时文件再替换，写入中断不会损坏已有列表
+fn save_suppressions(path: &Path, suppressions: &[AnalysisSuppression]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+    }
+    let raw = serde_json::to_string_pretty(suppressions).map_err(|e| e.to_string())?;
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, raw).map_err(|e| format!("写入分析忽略列表失败: {}", e))?;
+    fs::rename(&temp_path, path).map_err(|e| format!("保存分析忽略列表失败: {}", e))
+}
+
+fn parse_expiry(value: &str) -> Result<DateTime<Local>, String> {
+    let value = value.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Local));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| {
+            Local
+                .from_local_datetime(&date.and_time(NaiveTime::from_hms_opt(23, 59, 59)?))
+                .earliest()
+        })
+        .ok_or_else(|| format!("无法识别的到期时间: {}", value))
+}
+
+fn is_expired(suppression: &AnalysisSuppression, now: DateTime<Local>) -> bool {
+    suppression
+        .expires_at
+        .as_deref()
+        .filter(|value| !value.trim().is_empty())
+        .is_some_and(|value| parse_expiry(value).is_ok_and(|expires_at| expires_at <= now))
+}
+
+/// `*` 匹配任意长度，`?` 匹配单个字符
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// 某个工具当前生效的忽略条目
+pub(crate) struct SuppressionFilter {
+    entries: Vec<AnalysisSuppression>,
+}
+
+impl SuppressionFilter {
+    pub(crate) fn load(tool: &str) -> Self {
+        let _guard = SUPPRESSIONS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        Self::from_entries(tool, load_suppressions(&suppressions_path()), Local::now())
+    }
+
+    fn from_entries(tool: &str, entries: Vec<AnalysisSuppression>, now: DateTime<Local>) -> Self {
+        Self {
+            entries: entries
+                .into_iter()
+                .filter(|entry| entry.tool == tool && !is_expired(entry, now))
+                .collect(),
+        }
+    }
+
+    /// 条目中的包 UUID 都出现在 `pack_uuids` 里，且结果标识匹配时视为已忽略
+    pub(crate) fn is_suppressed<'a>(
+        &self,
+        pack_uuids: impl IntoIterator<Item = &'a str> + Clone,
+        finding_id: &str,
+    ) -> bool {
+        self.entries.iter().any(|entry| {
+            entry.pack_uuids.iter().all(|uuid| {
+                pack_uuids
+                    .clone()
+                    .into_iter()
+                    .any(|candidate| candidate.trim().eq_ignore_ascii_case(uuid.trim()))
+            }) && wildcard_match(&entry.finding_pattern, finding_id)
+        })
+    }
+}
+
+pub fn add_analysis_suppression(
+    request: NewAnalysisSuppression,
+) -> Result<AnalysisSuppression, String> {
+    let tool = request.tool.trim().to_string();
+    if tool.is_empty() {
+        return Err("未指定分析工具".to_string());
+    }
+    let finding_pattern = request.finding_pattern.trim().to_string();
+    if finding_pattern.is_empty() {
+        return Err("未指定要忽略的结果".to_string());
+    }
+    let expires_at = request
+        .expires_at
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    if let Some(value) = &expires_at {
+        parse_expiry(value)?;
+    }
+
+    let suppression = AnalysisSuppression {
+        id: uuid::Uuid::new_v4().to_string(),
+        tool,
+        pack_uuids: request
+            .pack_uuids
+            .iter()
+            .map(|uuid| uuid.trim().to_ascii_lowercase())
+            .filter(|uuid| !uuid.is_empty())
+            .collect(),
+        finding_pattern,
+        reason: request.reason.trim().to_string(),
+        created_at: Local::now().to_rfc3339(),
+        expires_at,
+    };
+    let _guard = SUPPRESSIONS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = suppressions_path();
+    let mut suppressions = load_suppressions(&path);
+    suppressions.push(suppression.clone());
+    save_suppressions(&path, &suppressions)?;
+    Ok(suppression)
+}
+
+/// 列出忽略条目（包括已到期的），`tool` 为空时列出全部
+pub fn list_analysis_suppressions(
+    tool: Option<String>,
+) -> Result<Vec<AnalysisSuppression>, String> {
+    let _guard = SUPPRESSIONS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let tool = tool.filter(|tool| !tool.trim().is_empty());
+    Ok(load_suppressions(&suppressions_path())
+        .into_iter()
+        .filter(|entry| tool.as_deref().is_none_or(|tool| entry.tool == tool.trim()))
+        .collect())
+}
+
+/// 删除忽略条目，返回是否找到
+pub fn remove_analysis_suppression(id: String) -> Result<bool, String> {
+    let _guard = SUPPRESSIONS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = suppressions_path();
+    let mut suppressions = load_suppressions(&path);
+    let before = suppressions.len();
+    suppressions.retain(|entry| entry.id != id);
+    if suppressions.len() == before {
+        return Ok(false);
+    }
+    save_suppressions(&path, &suppressions)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suppression(
+        pack_uuids: &[&str],
+        pattern: &str,
+        expires_at: Option<&str>,
+    ) -> AnalysisSuppression {
+        AnalysisSuppression {
+            id: uuid::Uuid::new_v4().to_string(),
+            tool: TOOL_ADDON_COMPATIBILITY.to_string(),
+            pack_uuids: pack_uuids.iter().map(ToString::to_string).collect(),
+            finding_pattern: pattern.to_string(),
+            reason: "intentional".to_string(),
+            created_at: "2026-01-01T00:00:00+00:00".to_string(),
+            expires_at: expires_at.map(ToString::to_string),
+        }
+    }
+
+    #[test]
+    fn matches_scoped_patterns_and_skips_expired_entries() {
+        let now = Local.with_ymd_and_hms(2026, 6, 1, 12, 0, 0).unwrap();
+        let mut other_tool = suppression(&[], "*", None);
+        other_tool.tool = TOOL_DUPLICATE_PACKS.to_string();
+        let filter = SuppressionFilter::from_entries(
+            TOOL_ADDON_COMPATIBILITY,
+            vec![
+                suppression(&["BP-UUID"], "missing_dependency:*", None),
+                suppression(
+                    &["pair-a", "pair-b"],
+                    "outdated_dependency:rp-?uid",
+                    Some("2026-12-31"),
+                ),
+                suppression(&[], "missing_dependency:absent-uuid", Some("2026-05-31")),
+                other_tool,
+            ],
+            now,
+        );
+
+        assert!(filter.is_suppressed(["bp-uuid"], "missing_dependency:absent-uuid"));
+        assert!(!filter.is_suppressed(["bp-uuid"], "outdated_dependency:rp-uuid"));
+        assert!(filter.is_suppressed(["pair-b", "pair-a", "x"], "outdated_dependency:rp-uuid"));
+        assert!(!filter.is_suppressed(["pair-a"], "outdated_dependency:rp-uuid"));
+        // 已到期的条目不再生效
+        assert!(!filter.is_suppressed(["other"], "missing_dependency:absent-uuid"));
+        assert!(parse_expiry("soon").is_err());
+        assert!(wildcard_match("textures/ui/*.json", "Textures/UI/hud.json"));
+        assert!(!wildcard_match("textures/ui/*.json", "textures/ui/hud.png"));
+    }
+}
@@ -1,3 +1,4 @@
 pub mod register;
 pub mod remove;
+pub mod signature;
 pub mod utils;
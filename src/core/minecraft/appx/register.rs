@@ -1,5 +1,6 @@
 #![cfg(target_os = "windows")]
 use std::io;
+use std::path::Path;
 use tracing::{error, info};
 use windows::Foundation::Uri;
 use windows::Management::Deployment::{DeploymentOptions, DeploymentResult, PackageManager};
@@ -51,3 +52,51 @@ pub async fn register_appx_package_async(package_folder: &str) -> WinResult<Depl
         Err(WinError::new(extended_error, error_text))
     }
 }
+
+/// 去掉 `\\?\` 前缀后转为 `file:` URI，路径中的空格、`#`、`%` 和非 ASCII 字符会被百分号编码
+fn appx_file_uri(path: &Path) -> Option<String> {
+    let text = path.to_string_lossy();
+    let plain = if let Some(rest) = text.strip_prefix(r"\\?\UNC\") {
+        format!(r"\\{rest}")
+    } else if let Some(rest) = text.strip_prefix(r"\\?\") {
+        rest.to_string()
+    } else {
+        text.into_owned()
+    };
+    url::Url::from_file_path(plain).ok().map(String::from)
+}
+
+/// 直接从 .appx 文件安装包，不解压为散装目录。
+pub async fn add_appx_package_async(appx_file: &Path) -> WinResult<DeploymentResult> {
+    let absolute_path = std::fs::canonicalize(appx_file).map_err(|e| {
+        windows::core::Error::from(io::Error::new(
+            io::ErrorKind::Other,
+            format!("获取绝对路径失败: {}", e),
+        ))
+    })?;
+
+    let uri_str = appx_file_uri(&absolute_path).ok_or_else(|| {
+        windows::core::Error::from(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("无法转换为文件 URI: {}", absolute_path.display()),
+        ))
+    })?;
+    info!("从 APPX 文件安装包，使用 URI：{}", uri_str);
+
+    let package_manager = PackageManager::new()?;
+    let uri = Uri::CreateUri(&HSTRING::from(uri_str.as_str()))?;
+
+    let async_op = package_manager.AddPackageAsync(&uri, None, DeploymentOptions::None)?;
+    let result: DeploymentResult = async_op.await?;
+
+    let extended_error = result.ExtendedErrorCode()?;
+    let error_text = result.ErrorText()?.to_string_lossy();
+
+    if extended_error == HRESULT(0) {
+        info!("APPX 文件安装成功");
+        Ok(result)
+    } else {
+        error!("APPX 文件安装失败: {:?} - {}", extended_error, error_text);
+        Err(WinError::new(extended_error, error_text))
+    }
+}
@@ -0,0 +1,202 @@
+//! 读取 APPX 包目录中 AppxSignature.p7x 的签名者证书信息
+
+use chrono::{DateTime, Utc};
+use cms::cert::CertificateChoices;
+use cms::content_info::ContentInfo;
+use cms::signed_data::{SignedData, SignerIdentifier};
+use der::oid::AssociatedOid;
+use der::{Decode, Encode};
+use serde::Serialize;
+use sha1::{Digest, Sha1};
+use std::fs;
+use std::path::Path;
+use x509_cert::Certificate;
+use x509_cert::ext::pkix::SubjectKeyIdentifier;
+use x509_cert::time::Time;
+
+const SIGNATURE_FILE_NAME: &str = "AppxSignature.p7x";
+/// p7x 文件头，其后紧跟 DER 编码的 PKCS#7 ContentInfo
+const P7X_MAGIC: &[u8] = b"PKCX";
+const MICROSOFT_ORGANIZATION: &str = "O=Microsoft Corporation";
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PackageSignatureInfo {
+    pub is_signed: bool,
+    pub subject: String,
+    pub issuer: String,
+    pub thumbprint: String,
+    pub valid_from: String,
+    pub valid_to: String,
+    /// 仅表示签名者证书的主体写着 Microsoft Corporation，未校验证书链，自签名证书同样可以声明
+    pub claims_microsoft_subject: bool,
+}
+
+/// 读取包目录的签名信息；没有签名文件时返回 `is_signed = false`
+pub fn get_package_signature_info(package_folder: &str) -> Result<PackageSignatureInfo, String> {
+    let signature_path = Path::new(package_folder).join(SIGNATURE_FILE_NAME);
+    if !signature_path.is_file() {
+        return Ok(PackageSignatureInfo::default());
+    }
+
+    let bytes = fs::read(&signature_path)
+        .map_err(|e| format!("读取签名文件失败 {}: {}", signature_path.display(), e))?;
+    parse_p7x(&bytes)
+}
+
+fn parse_p7x(bytes: &[u8]) -> Result<PackageSignatureInfo, String> {
+    let der_bytes = bytes
+        .strip_prefix(P7X_MAGIC)
+        .ok_or_else(|| "签名文件格式无效：缺少 PKCX 文件头".to_string())?;
+
+    let content_info =
+        ContentInfo::from_der(der_bytes).map_err(|e| format!("解析 PKCS#7 结构失败: {}", e))?;
+    let signed_data = content_info
+        .content
+        .decode_as::<SignedData>()
+        .map_err(|e| format!("解析 SignedData 失败: {}", e))?;
+
+    let certificates: Vec<&Certificate> = signed_data
+        .certificates
+        .as_ref()
+        .map(|set| {
+            set.0
+                .iter()
+                .filter_map(|choice| match choice {
+                    CertificateChoices::Certificate(certificate) => Some(certificate),
+                    _ => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // 证书集合里还包含中间 CA，只能按 SignerInfo 的标识找签名者证书
+    let signer_certificate = signed_data
+        .signer_infos
+        .0
+        .iter()
+        .find_map(|signer| {
+            certificates
+                .iter()
+                .copied()
+                .find(|certificate| identifies(&signer.sid, certificate))
+        })
+        .ok_or_else(|| "签名中未找到签名者证书".to_string())?;
+
+    let tbs = &signer_certificate.tbs_certificate;
+    let subject = tbs.subject.to_string();
+    let issuer = tbs.issuer.to_string();
+    let certificate_der = signer_certificate
+        .to_der()
+        .map_err(|e| format!("编码签名者证书失败: {}", e))?;
+    let thumbprint = hex::encode_upper(Sha1::digest(&certificate_der));
+
+    Ok(PackageSignatureInfo {
+        is_signed: true,
+        claims_microsoft_subject: subject.contains(MICROSOFT_ORGANIZATION),
+        subject,
+        issuer,
+        thumbprint,
+        valid_from: time_to_iso(&tbs.validity.not_before),
+        valid_to: time_to_iso(&tbs.validity.not_after),
+    })
+}
+
+fn identifies(signer: &SignerIdentifier, certificate: &Certificate) -> bool {
+    let tbs = &certificate.tbs_certificate;
+    match signer {
+        SignerIdentifier::IssuerAndSerialNumber(identifier) => {
+            tbs.issuer == identifier.issuer && tbs.serial_number == identifier.serial_number
+        }
+        SignerIdentifier::SubjectKeyIdentifier(identifier) => tbs
+            .extensions
+            .iter()
+            .flatten()
+            .filter(|extension| extension.extn_id == SubjectKeyIdentifier::OID)
+            .filter_map(|extension| {
+                SubjectKeyIdentifier::from_der(extension.extn_value.as_bytes()).ok()
+            })
+            .any(|key_identifier| key_identifier == *identifier),
+    }
+}
+
+fn time_to_iso(time: &Time) -> String {
+    let date_time: DateTime<Utc> = time.to_system_time().into();
+    date_time.to_rfc3339()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cms::cert::IssuerAndSerialNumber;
+    use der::asn1::OctetString;
+
+    #[test]
+    fn package_without_signature_file_is_reported_unsigned() {
+        let test_dir = std::env::temp_dir().join(format!(
+            "bmcbl-appx-signature-test-{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .expect("system time should be after unix epoch")
+                .as_nanos()
+        ));
+        fs::create_dir_all(&test_dir).expect("create test dir");
+
+        let info = get_package_signature_info(&test_dir.to_string_lossy())
+            .expect("missing signature should not be an error");
+        assert!(!info.is_signed);
+        assert!(info.thumbprint.is_empty());
+
+        fs::remove_dir_all(&test_dir).expect("remove test dir");
+    }
+
+    /// openssl 生成的 Ed25519 自签名证书，主体写着 O=Microsoft Corporation
+    const SELF_SIGNED_CERTIFICATE_HEX: &str = concat!(
+        "3082018130820133a00302010202145c87422ccd297e189f7a72d1b217a412113384e5300506032b65703036",
+        "3114301206035504030c0b54657374205369676e6572311e301c060355040a0c154d6963726f736f66742043",
+        "6f72706f726174696f6e301e170d3236313031363135343330385a170d3236313031373135343330385a3036",
+        "3114301206035504030c0b54657374205369676e6572311e301c060355040a0c154d6963726f736f66742043",
+        "6f72706f726174696f6e302a300506032b6570032100973f7789e20d2d3e178e6a42eb4302f4a1702006c370",
+        "48ceeb5ccdd451d6362da3533051301d0603551d0e041604147c82436cb658946b7527f70786d85a4c35d177",
+        "b5301f0603551d230418301680147c82436cb658946b7527f70786d85a4c35d177b5300f0603551d130101ff",
+        "040530030101ff300506032b6570034100e39143e9d02d50761b912230da7a273f796bcd9cc88c7c45f84306",
+        "ef1bfb9eb4127ae2e3787db638de2e0ed832b37a8865536c9f8d2c02af1719f64d0dc62209",
+    );
+    const SELF_SIGNED_KEY_IDENTIFIER_HEX: &str = "7c82436cb658946b7527f70786d85a4c35d177b5";
+
+    fn key_identifier(hex_value: &str) -> SignerIdentifier {
+        let bytes = hex::decode(hex_value).expect("decode key identifier");
+        SignerIdentifier::SubjectKeyIdentifier(SubjectKeyIdentifier(
+            OctetString::new(bytes).expect("build key identifier"),
+        ))
+    }
+
+    #[test]
+    fn signer_certificate_is_matched_by_its_identifier() {
+        let der = hex::decode(SELF_SIGNED_CERTIFICATE_HEX).expect("decode certificate");
+        let certificate = Certificate::from_der(&der).expect("parse certificate");
+        let by_serial = SignerIdentifier::IssuerAndSerialNumber(IssuerAndSerialNumber {
+            issuer: certificate.tbs_certificate.issuer.clone(),
+            serial_number: certificate.tbs_certificate.serial_number.clone(),
+        });
+
+        assert!(identifies(&by_serial, &certificate));
+        assert!(identifies(
+            &key_identifier(SELF_SIGNED_KEY_IDENTIFIER_HEX),
+            &certificate
+        ));
+        assert!(!identifies(&key_identifier(&"00".repeat(20)), &certificate));
+        assert!(
+            certificate
+                .tbs_certificate
+                .subject
+                .to_string()
+                .contains(MICROSOFT_ORGANIZATION)
+        );
+    }
+
+    #[test]
+    fn p7x_without_magic_header_is_rejected() {
+        assert!(parse_p7x(b"\x30\x03\x02\x01\x01").is_err());
+        assert!(parse_p7x(b"PKCX\x30\x00").is_err());
+    }
+}
@@ -499,6 +499,29 @@ pub fn get_manifest_identity_from_dir_blocking(
     parse_manifest_identity(&xml)
 }
 
+/// 不解压直接从 .appx 包中读取 Identity 的 Name 和 Version
+pub fn get_manifest_identity_from_appx_file_blocking(
+    appx_file: &Path,
+) -> Result<(String, String), String> {
+    let file = File::open(appx_file)
+        .map_err(|error| format!("无法打开 APPX 文件 {}: {}", appx_file.display(), error))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|error| format!("不是有效的 APPX 包 {}: {}", appx_file.display(), error))?;
+    let manifest_name = archive
+        .file_names()
+        .find(|name| name.eq_ignore_ascii_case("AppxManifest.xml"))
+        .map(str::to_owned)
+        .ok_or_else(|| format!("APPX 包中未找到 AppxManifest.xml: {}", appx_file.display()))?;
+
+    let mut xml = String::new();
+    archive
+        .by_name(&manifest_name)
+        .map_err(|error| format!("无法读取 {manifest_name}: {error}"))?
+        .read_to_string(&mut xml)
+        .map_err(|error| format!("无法读取 {manifest_name}: {error}"))?;
+    parse_manifest_identity(strip_bom(&xml))
+}
+
 fn find_manifest_path(directory: &Path) -> io::Result<Option<PathBuf>> {
     for name in ["AppxManifest.xml", "appxmanifest.xml"] {
         let candidate = directory.join(name);
@@ -591,8 +614,11 @@ pub async fn get_manifest_identity(appx_path: &str) -> Result<(String, String),
 
 #[cfg(test)]
 mod tests {
-    use super::get_manifest_identity_from_dir_blocking;
+    use super::{
+        get_manifest_identity_from_appx_file_blocking, get_manifest_identity_from_dir_blocking,
+    };
     use std::fs;
+    use std::io::Write;
     use std::time::{SystemTime, UNIX_EPOCH};
 
     #[test]
@@ -618,4 +644,39 @@ mod tests {
 
         fs::remove_dir_all(directory).expect("test directory should be removed");
     }
+
+    #[test]
+    fn appx_file_identity_is_read_without_extraction() {
+        let appx_path = std::env::temp_dir().join(format!(
+            "bmcbl-appx-identity-test-{}-{}.appx",
+            std::process::id(),
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("system clock should be after unix epoch")
+                .as_nanos()
+        ));
+        let file = fs::File::create(&appx_path).expect("appx file should be created");
+        let mut writer = zip::ZipWriter::new(file);
+        writer
+            .start_file("AppxManifest.xml", zip::write::SimpleFileOptions::default())
+            .expect("manifest entry should be started");
+        writer
+            .write_all(
+                br#"<Package><Identity Name="Microsoft.MinecraftUWP" Version="1.20.81.1" /></Package>"#,
+            )
+            .expect("manifest should be written");
+        writer.finish().expect("appx archive should be finalized");
+
+        let identity = get_manifest_identity_from_appx_file_blocking(&appx_path)
+            .expect("identity should be read from archive");
+        assert_eq!(
+            identity,
+            (
+                "Microsoft.MinecraftUWP".to_string(),
+                "1.20.81.1".to_string()
+            )
+        );
+
+        fs::remove_file(appx_path).expect("appx file should be removed");
+    }
 }
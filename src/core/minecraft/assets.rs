@@ -1,12 +1,28 @@
 // src-tauri/src/commands/assets.rs
+use crate::core::cache_registry::dir_usage;
 use crate::core::minecraft::import::{
-    ImportCheckResult, PackagePreview, check_import_file, import_files_batch, inspect_archive,
+    ImportCheckResult, PackContentDiff, PackagePreview, check_import_file, diff_pack_contents,
+    import_files_batch, inspect_archive, read_manifest_from_dir,
 };
-use crate::core::minecraft::paths::{BuildType, Edition, GamePathOptions, resolve_target_parent};
-use serde::Deserialize;
+use crate::core::minecraft::pack_references::{
+    PackReferencingWorld, find_worlds_referencing_pack, remove_pack_references,
+};
+use crate::core::minecraft::paths::{
+    BuildType, Edition, GamePathOptions, GameTargetDir, com_mojang_dir, game_target_dirs,
+    get_game_root, resolve_target_parent, user_com_mojang_dir,
+};
+use crate::core::minecraft::recent_deletions;
+use crate::tasks::task_manager::{create_task_with_details, finish_task, update_progress};
+use crate::utils::file_locks::{self, BlockedFile};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::HashMap;
 use std::fs; // 引入新模块
-use tracing::{debug, error};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{debug, error, warn};
 
 #[derive(Debug, Deserialize)]
 pub struct DeleteAssetPayload {
@@ -29,6 +45,9 @@ pub struct ImportAssetsRequest {
     pub file_paths: Vec<String>,
     pub overwrite: bool,             // [新增] 覆盖选项
     pub allow_shared_fallback: bool, // [新增] 允许回退到 Shared
+    /// 复合包逐个子包推进该任务的进度，取消该任务会在子包之间停止导入
+    #[serde(default)]
+    pub task_id: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -46,8 +65,27 @@ pub struct CheckImportRequest {
 pub struct ImportAssetsResult {
     pub imported_count: usize,
     pub failed_count: usize,
+    /// 重试后仍被其他程序（多为杀毒软件）占用的文件
+    pub blocked_files: Vec<BlockedFile>,
+    /// 例如 "3 个文件被 MsMpEng.exe 占用"，没有被占用的文件时为 None
+    pub blocked_files_summary: Option<String>,
 }
 
+/// 删除计划的有效期，过期后需重新确认
+const DELETE_PLAN_TTL: Duration = Duration::from_secs(10 * 60);
+/// com.mojang 下的分类目录，与数据根一样任何情况下都不能整体删除
+const PROTECTED_CATEGORY_DIRS: [&str; 9] = [
+    "minecraftWorlds",
+    "world_templates",
+    "skin_packs",
+    "behavior_packs",
+    "resource_packs",
+    "development_behavior_packs",
+    "development_resource_packs",
+    "minecraftpe",
+    "Screenshots",
+];
+
 fn map_delete_type_to_dir(delete_type: &str) -> Option<&'static str> {
     match delete_type {
         "maps" => Some("minecraftWorlds"),
@@ -55,12 +93,123 @@ fn map_delete_type_to_dir(delete_type: &str) -> Option<&'static str> {
         "skins" => Some("skin_packs"),
         "behaviorPacks" => Some("behavior_packs"),
         "resourcePacks" => Some("resource_packs"),
+        "screenshots" => Some("Screenshots"),
         _ => None,
     }
 }
 
-pub fn delete_game_asset(payload: DeleteAssetPayload) -> Result<serde_json::Value, String> {
+#[derive(Debug, Clone, Serialize)]
+pub struct DeleteAssetPlan {
+    /// 一次性令牌，传给 `delete_game_asset` 执行删除
+    pub token: String,
+    pub target_path: String,
+    pub item_count: u64,
+    pub total_bytes: u64,
+    /// 仍在引用该包的世界（仅行为包 / 资源包），非空时需要 `force` 才能删除
+    pub referencing_worlds: Vec<PackReferencingWorld>,
+}
+
+struct PendingDelete {
+    target_path: PathBuf,
+    display_name: String,
+    item_count: u64,
+    pack_uuid: Option<String>,
+    referencing_worlds: Vec<PackReferencingWorld>,
+    created_at: Instant,
+}
+
+static PENDING_DELETES: Lazy<Mutex<HashMap<String, PendingDelete>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 解析当前版本实际的数据目录：每个 com.mojang（GDK 下每个用户一份，含 Shared）、
+/// 它到游戏根目录之间的各级目录，以及其中的分类目录
+fn protected_roots(options: &GamePathOptions, game_root: &Path) -> Vec<PathBuf> {
+    let com_mojang_dirs = match options.build_type {
+        BuildType::Uwp => vec![com_mojang_dir(game_root)],
+        BuildType::Gdk => {
+            let mut user_ids: Vec<String> = fs::read_dir(game_root.join("Users"))
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .collect();
+            user_ids.push("Shared".to_string());
+            user_ids.extend(options.user_id.clone());
+            user_ids
+                .iter()
+                .map(|user_id| user_com_mojang_dir(game_root, user_id))
+                .collect()
+        }
+    };
+    protected_roots_for(game_root, &com_mojang_dirs)
+}
+
+fn protected_roots_for(game_root: &Path, com_mojang_dirs: &[PathBuf]) -> Vec<PathBuf> {
+    let mut roots = vec![game_root.to_path_buf()];
+    for com_mojang in com_mojang_dirs {
+        roots.extend(
+            com_mojang
+                .ancestors()
+                .take_while(|dir| dir.starts_with(game_root))
+                .map(Path::to_path_buf),
+        );
+        roots.extend(
+            PROTECTED_CATEGORY_DIRS
+                .iter()
+                .map(|dir| com_mojang.join(dir)),
+        );
+    }
+    roots.sort();
+    roots.dedup();
+    roots
+}
+
+/// `target` 就是 `root` 或是它的上级目录；两者都存在时同时按解析后的真实路径比较
+fn contains_root(target: &Path, root: &Path) -> bool {
+    if root.starts_with(target) {
+        return true;
+    }
+    match (fs::canonicalize(target), fs::canonicalize(root)) {
+        (Ok(target), Ok(root)) => root.starts_with(target),
+        _ => false,
+    }
+}
+
+/// 目标是数据根或分类目录本身（而不是其中的某一项）时返回拒绝原因
+fn protected_path_reason(
+    target: &Path,
+    game_root: Option<&Path>,
+    protected_roots: &[PathBuf],
+) -> Option<&'static str> {
+    if target.parent().is_none() || target.file_name().is_none() {
+        return Some("不能删除磁盘根目录");
+    }
+    if let Some(root) = game_root
+        && contains_root(target, root)
+    {
+        return Some("不能删除游戏数据根目录");
+    }
+    if protected_roots
+        .iter()
+        .any(|root| contains_root(target, root))
+    {
+        return Some("不能删除游戏数据目录本身");
+    }
+    None
+}
+
+fn measure_target(path: &Path) -> (u64, u64) {
+    if path.is_file() {
+        let size = fs::metadata(path).map_or(0, |metadata| metadata.len());
+        return (1, size);
+    }
+    let usage = dir_usage(path);
+    (usage.entries, usage.bytes)
+}
+
+fn plan_delete_blocking(payload: DeleteAssetPayload) -> Result<DeleteAssetPlan, String> {
     if payload.name.is_empty()
+        || payload.name == "."
         || payload.name.contains("..")
         || payload.name.contains('/')
         || payload.name.contains('\\')
@@ -87,18 +236,148 @@ pub fn delete_game_asset(payload: DeleteAssetPayload) -> Result<serde_json::Valu
 
     let parent_dir = resolve_target_parent(&options, dir_name, is_shared_preferred)
         .ok_or_else(|| "Could not resolve target directory".to_string())?;
-
     let target_path = parent_dir.join(&payload.name);
-
+    let game_root = get_game_root(&options);
+    let roots = game_root
+        .as_deref()
+        .map(|root| protected_roots(&options, root))
+        .unwrap_or_default();
+    if let Some(reason) = protected_path_reason(&target_path, game_root.as_deref(), &roots) {
+        warn!("拒绝删除 {}: {}", target_path.display(), reason);
+        return Err(format!("{}: {}", reason, target_path.display()));
+    }
     if !target_path.exists() {
-        return Ok(
-            json!({ "success": false, "message": format!("Path not found: {}", target_path.display()) }),
-        );
+        return Err(format!("Path not found: {}", target_path.display()));
     }
 
-    fs::remove_dir_all(&target_path).map_err(|e| format!("Delete failed: {}", e))?;
+    let display_name = if dir_name == "minecraftWorlds" {
+        fs::read_to_string(target_path.join("levelname.txt"))
+            .ok()
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| payload.name.clone())
+    } else {
+        payload.name.clone()
+    };
+    let (item_count, total_bytes) = measure_target(&target_path);
+    let pack_uuid = matches!(dir_name, "behavior_packs" | "resource_packs")
+        .then(|| read_manifest_from_dir(&target_path))
+        .flatten()
+        .and_then(|manifest| manifest.header?.uuid);
+    let referencing_worlds = pack_uuid
+        .as_deref()
+        .map(|uuid| {
+            find_worlds_referencing_pack(
+                &game_target_dirs(&options, GameTargetDir::MinecraftWorlds),
+                uuid,
+            )
+        })
+        .unwrap_or_default();
+    let token = uuid::Uuid::new_v4().to_string();
+
+    let mut pending = PENDING_DELETES.lock().unwrap_or_else(|e| e.into_inner());
+    pending.retain(|_, plan| plan.created_at.elapsed() < DELETE_PLAN_TTL);
+    pending.insert(
+        token.clone(),
+        PendingDelete {
+            target_path: target_path.clone(),
+            display_name,
+            item_count,
+            pack_uuid,
+            referencing_worlds: referencing_worlds.clone(),
+            created_at: Instant::now(),
+        },
+    );
+    Ok(DeleteAssetPlan {
+        token,
+        target_path: target_path.to_string_lossy().into_owned(),
+        item_count,
+        total_bytes,
+        referencing_worlds,
+    })
+}
+
+/// 删除前的确认步骤：解析出实际路径并统计文件数与大小，删除包时还会列出仍引用它的世界，
+/// 返回执行删除所需的一次性令牌
+pub async fn plan_delete_game_asset(
+    payload: DeleteAssetPayload,
+) -> Result<DeleteAssetPlan, String> {
+    tokio::task::spawn_blocking(move || plan_delete_blocking(payload))
+        .await
+        .map_err(|e| format!("Task failed: {:?}", e))?
+}
+
+/// 按 `plan_delete_game_asset` 返回的令牌执行删除；令牌只能使用一次。
+/// 删除的都是用户内容（存档、包、截图），一律移入回收站并登记撤销记录。
+/// 包仍被世界引用时必须传 `force`；`clean_references` 会同时从这些世界的包列表中移除该包（先备份）
+pub async fn delete_game_asset(
+    token: String,
+    force: bool,
+    clean_references: bool,
+) -> Result<serde_json::Value, String> {
+    let plan = {
+        let mut pending = PENDING_DELETES.lock().unwrap_or_else(|e| e.into_inner());
+        let plan = pending
+            .get(&token)
+            .filter(|plan| plan.created_at.elapsed() < DELETE_PLAN_TTL)
+            .ok_or_else(|| "删除确认已失效，请重新确认".to_string())?;
+        if !force && !plan.referencing_worlds.is_empty() {
+            return Err(format!(
+                "仍有 {} 个世界引用该包，确认后需强制删除",
+                plan.referencing_worlds.len()
+            ));
+        }
+        pending
+            .remove(&token)
+            .ok_or_else(|| "删除确认已失效，请重新确认".to_string())?
+    };
+
+    let task_id = create_task_with_details(
+        None,
+        "删除游戏资源",
+        Some(plan.display_name.clone()),
+        "recycle",
+        Some(plan.item_count),
+        false,
+    );
+    let blocking_task_id = task_id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        if !plan.target_path.exists() {
+            return Err(format!("Path not found: {}", plan.target_path.display()));
+        }
+        // 整个目标一次移入回收站才能整体撤销，没有逐项进度，完成后一次推进到总数
+        let record = recent_deletions::delete_with_undo(&plan.target_path, &plan.display_name)?;
+        update_progress(&blocking_task_id, plan.item_count, None, Some("recycle"));
+
+        let mut cleaned_worlds = 0;
+        if let Some(uuid) = plan.pack_uuid.as_deref().filter(|_| clean_references) {
+            for world in &plan.referencing_worlds {
+                match remove_pack_references(world, uuid) {
+                    Ok(_) => cleaned_worlds += 1,
+                    Err(e) => warn!("清理世界 {} 的包引用失败: {}", world.world_name, e),
+                }
+            }
+        }
+        Ok((record, cleaned_worlds))
+    })
+    .await
+    .map_err(|e| format!("Task failed: {:?}", e))
+    .and_then(|result| result);
 
-    Ok(json!({ "success": true }))
+    match result {
+        Ok((record, cleaned_worlds)) => {
+            finish_task(&task_id, "completed", None);
+            Ok(json!({
+                "success": true,
+                "deletion": record,
+                "cleaned_worlds": cleaned_worlds,
+            }))
+        }
+        Err(error) => {
+            finish_task(&task_id, "error", Some(error.clone()));
+            Err(error)
+        }
+    }
 }
 
 // [新增] 导入资源命令
@@ -122,30 +401,46 @@ pub async fn import_assets(request: ImportAssetsRequest) -> Result<ImportAssetsR
         allow_shared_fallback: request.allow_shared_fallback,
     };
 
-    let result = tokio::task::spawn_blocking(move || {
-        import_files_batch(request.file_paths, &options, request.overwrite)
+    let (result, blocked_files) = tokio::task::spawn_blocking(move || {
+        let lock_scope = file_locks::LockReportScope::enter();
+        let result = import_files_batch(
+            request.file_paths,
+            &options,
+            request.overwrite,
+            request.task_id,
+        );
+        (result, lock_scope.take())
     })
     .await
     .map_err(|error| {
         error!("Import assets task failed: {error:?}");
         format!("Task failed: {:?}", error)
-    })?
-    .map_err(|error| {
+    })?;
+    let blocked_files_summary = file_locks::summarize_blocked_files(&blocked_files);
+    if let Some(summary) = &blocked_files_summary {
+        warn!("导入时有文件被占用: {}", summary);
+    }
+    let (success, fail) = result.map_err(|error| {
         error!("Import assets execution failed: {error:?}");
-        format!("Import failed: {:?}", error)
+        match &blocked_files_summary {
+            Some(summary) => format!("Import failed: {:?}（{}）", error, summary),
+            None => format!("Import failed: {:?}", error),
+        }
     })?;
 
-    let (success, fail) = result;
     debug!("Import assets result: success={}, fail={}", success, fail);
     Ok(ImportAssetsResult {
         imported_count: success,
         failed_count: fail,
+        blocked_files,
+        blocked_files_summary,
     })
 }
 
 pub async fn inspect_import_file(
     file_path: String,
     lang: Option<String>,
+    include_raw_manifest: bool,
 ) -> Result<PackagePreview, String> {
     let path = std::path::PathBuf::from(file_path);
     if !path.exists() {
@@ -161,7 +456,7 @@ pub async fn inspect_import_file(
 
     // 在 blocking thread 中执行，因为涉及 ZIP 解压读取
     tokio::task::spawn_blocking(move || {
-        inspect_archive(&path, lang.as_deref()).map_err(|e| e.to_string())
+        inspect_archive(&path, lang.as_deref(), include_raw_manifest).map_err(|e| e.to_string())
     })
     .await
     .map_err(|error| {
@@ -213,3 +508,64 @@ pub async fn check_import_conflict(
         format!("Task failed: {:?}", error)
     })?
 }
+
+/// 冲突对话框打开后再计算文件差异，不阻塞导入检查
+pub async fn diff_import_conflict(
+    file_path: String,
+    existing_pack_path: String,
+) -> Result<PackContentDiff, String> {
+    tokio::task::spawn_blocking(move || diff_pack_contents(&file_path, &existing_pack_path))
+        .await
+        .map_err(|error| format!("Task failed: {:?}", error))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_roots_and_category_dirs_are_refused() {
+        let root = Path::new("C:/Users/me/AppData/Roaming/Minecraft Bedrock");
+        let com_mojang = user_com_mojang_dir(root, "123");
+        let roots = protected_roots_for(
+            root,
+            &[com_mojang.clone(), user_com_mojang_dir(root, "Shared")],
+        );
+        for refused in [
+            PathBuf::from("/"),
+            PathBuf::from("C:/Users/me/AppData"),
+            root.to_path_buf(),
+            root.join("Users"),
+            root.join("Users/123"),
+            root.join("Users/Shared"),
+            root.join("Users/123/games"),
+            root.join("Users/Shared/games/com.mojang/resource_packs"),
+            com_mojang.clone(),
+            com_mojang.join("minecraftWorlds"),
+            com_mojang.join("resource_packs"),
+            com_mojang.join("Screenshots"),
+        ] {
+            assert!(
+                protected_path_reason(&refused, Some(root), &roots).is_some(),
+                "{} should be refused",
+                refused.display()
+            );
+        }
+
+        // 只按叶子名称比较时会被误拒：名字和分类目录相同的存档 / 包
+        for allowed in [
+            com_mojang.join("minecraftWorlds/abc="),
+            com_mojang.join("minecraftWorlds/Screenshots"),
+            com_mojang.join("resource_packs/pack_0000"),
+            com_mojang.join("resource_packs/games"),
+            com_mojang.join("Screenshots/2024-01-01.jpeg"),
+        ] {
+            assert_eq!(
+                protected_path_reason(&allowed, Some(root), &roots),
+                None,
+                "{}",
+                allowed.display()
+            );
+        }
+    }
+}
@@ -0,0 +1,636 @@
+//! UWP 与 GDK 系统数据互相迁移：UWP 的 `LocalState/games/com.mojang/` 与
+//! GDK 的 `<数据目录>/Users/<用户>/games/com.mojang/` 目录结构一一对应，
+//! 按类别逐项复制（保留修改时间），同名项目追加 ` (2)` 等序号。
+//! 移动模式下存档需确认副本的 level.dat 可以解析后才删除源（删除走回收站）。
+
+use crate::core::minecraft::launcher::get_active_launches;
+use crate::core::minecraft::nbt::read_level_dat;
+use crate::core::minecraft::paths::{
+    BuildType, Edition, GamePathOptions, GameTargetDir, com_mojang_dir, get_game_root,
+    user_com_mojang_dir,
+};
+use crate::core::minecraft::recent_deletions::delete_with_undo;
+use crate::tasks::task_manager::{
+    append_task_log, create_task_with_details, finish_task, is_cancelled, update_progress,
+};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, FileTimes};
+use std::path::{Path, PathBuf};
+use sysinfo::{ProcessesToUpdate, System};
+use tracing::{info, warn};
+
+const GAME_EXECUTABLE_NAME: &str = "Minecraft.Windows.exe";
+const OPTIONS_FILE: &str = "options.txt";
+/// 重名时最多尝试到 `name (10000)`
+const MAX_NAME_SUFFIX: u32 = 10_000;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationMode {
+    #[default]
+    Copy,
+    /// 复制并校验成功后把源移入回收站
+    Move,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MigrationSelection {
+    #[serde(default)]
+    pub worlds: bool,
+    #[serde(default)]
+    pub resource_packs: bool,
+    #[serde(default)]
+    pub behavior_packs: bool,
+    /// `minecraftpe/options.txt`
+    #[serde(default)]
+    pub options: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationCategory {
+    Worlds,
+    ResourcePacks,
+    BehaviorPacks,
+    Options,
+}
+
+impl MigrationCategory {
+    fn target_dir(self) -> GameTargetDir {
+        match self {
+            Self::Worlds => GameTargetDir::MinecraftWorlds,
+            Self::ResourcePacks => GameTargetDir::ResourcePacks,
+            Self::BehaviorPacks => GameTargetDir::BehaviorPacks,
+            Self::Options => GameTargetDir::MinecraftPe,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Worlds => "存档",
+            Self::ResourcePacks => "资源包",
+            Self::BehaviorPacks => "行为包",
+            Self::Options => "设置",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationItemStatus {
+    Copied,
+    Moved,
+    Failed,
+    /// 任务取消时尚未处理
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationItemResult {
+    pub category: MigrationCategory,
+    pub source: String,
+    pub destination: Option<String>,
+    /// 目标已有同名项目而改用了带序号的名称
+    pub renamed: bool,
+    pub status: MigrationItemStatus,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationReport {
+    pub task_id: String,
+    pub mode: MigrationMode,
+    pub source_root: String,
+    pub destination_root: String,
+    pub items: Vec<MigrationItemResult>,
+    pub migrated: usize,
+    pub failed: usize,
+    pub cancelled: bool,
+}
+
+struct MigrationItem {
+    category: MigrationCategory,
+    source: PathBuf,
+}
+
+fn validate_user_folder(user_folder: &str) -> Result<(), String> {
+    if user_folder.is_empty()
+        || user_folder.contains("..")
+        || user_folder.contains('/')
+        || user_folder.contains('\\')
+    {
+        return Err("无效的 GDK 用户目录".to_string());
+    }
+    Ok(())
+}
+
+/// 系统模式下指定布局的 com.mojang 目录；GDK 按用户目录区分
+fn system_com_mojang_dir(
+    build_type: BuildType,
+    edition: &Edition,
+    user_folder: &str,
+) -> Result<PathBuf, String> {
+    let options = GamePathOptions {
+        build_type: build_type.clone(),
+        edition: edition.clone(),
+        version_name: String::new(),
+        enable_isolation: false,
+        user_id: Some(user_folder.to_string()),
+        allow_shared_fallback: false,
+    };
+    let root = get_game_root(&options)
+        .ok_or_else(|| format!("无法定位 {:?} 版的游戏数据目录", build_type))?;
+    Ok(match build_type {
+        BuildType::Uwp => com_mojang_dir(&root),
+        BuildType::Gdk => user_com_mojang_dir(&root, user_folder),
+    })
+}
+
+fn selected_categories(what: &MigrationSelection) -> Vec<MigrationCategory> {
+    [
+        (what.worlds, MigrationCategory::Worlds),
+        (what.resource_packs, MigrationCategory::ResourcePacks),
+        (what.behavior_packs, MigrationCategory::BehaviorPacks),
+        (what.options, MigrationCategory::Options),
+    ]
+    .into_iter()
+    .filter_map(|(selected, category)| selected.then_some(category))
+    .collect()
+}
+
+fn collect_items(source_com_mojang: &Path, categories: &[MigrationCategory]) -> Vec<MigrationItem> {
+    let mut items = Vec::new();
+    for &category in categories {
+        let dir = source_com_mojang.join(category.target_dir().name());
+        if category == MigrationCategory::Options {
+            let options = dir.join(OPTIONS_FILE);
+            if options.is_file() {
+                items.push(MigrationItem {
+                    category,
+                    source: options,
+                });
+            }
+            continue;
+        }
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut children = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect::<Vec<_>>();
+        children.sort();
+        items.extend(
+            children
+                .into_iter()
+                .map(|source| MigrationItem { category, source }),
+        );
+    }
+    items
+}
+
+/// 目标目录中不冲突的名称：`name`、`name (2)`、`name (3)`……文件的序号加在扩展名之前
+fn unique_destination(parent: &Path, name: &str, is_file: bool) -> Result<PathBuf, String> {
+    let candidate = parent.join(name);
+    if !candidate.exists() {
+        return Ok(candidate);
+    }
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension)) if is_file && !stem.is_empty() => (stem, Some(extension)),
+        _ => (name, None),
+    };
+    (2..=MAX_NAME_SUFFIX)
+        .map(|index| {
+            let renamed = match extension {
+                Some(extension) => format!("{stem} ({index}).{extension}"),
+                None => format!("{stem} ({index})"),
+            };
+            parent.join(renamed)
+        })
+        .find(|path| !path.exists())
+        .ok_or_else(|| format!("目标目录中同名项目过多: {}", candidate.display()))
+}
+
+fn copy_times(source: &Path, destination: &Path) {
+    let Ok(metadata) = fs::metadata(source) else {
+        return;
+    };
+    let mut times = FileTimes::new();
+    if let Ok(modified) = metadata.modified() {
+        times = times.set_modified(modified);
+    }
+    if let Ok(accessed) = metadata.accessed() {
+        times = times.set_accessed(accessed);
+    }
+    let result = File::options()
+        .read(true)
+        .write(metadata.is_file())
+        .open(destination)
+        .and_then(|file| file.set_times(times));
+    // 部分平台无法修改目录时间，只影响排序展示，不中断迁移
+    if let Err(e) = result
+        && metadata.is_file()
+    {
+        warn!("保留文件时间失败 {}: {}", destination.display(), e);
+    }
+}
+
+/// 递归复制并保留修改时间；取消时返回错误，由调用方清理残留副本
+fn copy_preserving_times(source: &Path, destination: &Path, task_id: &str) -> Result<(), String> {
+    if is_cancelled(task_id) {
+        return Err("迁移已取消".to_string());
+    }
+    if source.is_dir() {
+        fs::create_dir_all(destination)
+            .map_err(|e| format!("创建目录失败 {}: {}", destination.display(), e))?;
+        let entries = fs::read_dir(source)
+            .map_err(|e| format!("读取目录失败 {}: {}", source.display(), e))?;
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("读取目录失败 {}: {}", source.display(), e))?;
+            copy_preserving_times(&entry.path(), &destination.join(entry.file_name()), task_id)?;
+        }
+    } else {
+        fs::copy(source, destination).map_err(|e| {
+            format!(
+                "复制文件失败 {} -> {}: {}",
+                source.display(),
+                destination.display(),
+                e
+            )
+        })?;
+    }
+    copy_times(source, destination);
+    Ok(())
+}
+
+fn remove_partial_copy(path: &Path) {
+    let result = if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    };
+    if let Err(e) = result
+        && path.exists()
+    {
+        warn!("清理未完成的副本失败 {}: {}", path.display(), e);
+    }
+}
+
+/// 设置文件只能有一份：目标已有 options.txt 时先改名保留，再放入迁移来的设置；
+/// 返回目标路径与改名保留的原文件
+fn options_destination(destination_dir: &Path) -> Result<(PathBuf, Option<PathBuf>), String> {
+    let destination = destination_dir.join(OPTIONS_FILE);
+    if !destination.exists() {
+        return Ok((destination, None));
+    }
+    let kept = unique_destination(destination_dir, OPTIONS_FILE, true)?;
+    fs::rename(&destination, &kept)
+        .map_err(|e| format!("保留原有设置失败 {}: {}", destination.display(), e))?;
+    Ok((destination, Some(kept)))
+}
+
+/// 迁移失败时把改名保留的设置放回原位，目标版本不会丢失正在使用的设置
+fn restore_kept_options(kept: &Path, destination: &Path) {
+    if let Err(e) = fs::rename(kept, destination) {
+        warn!(
+            "恢复原有设置失败 {} -> {}: {}",
+            kept.display(),
+            destination.display(),
+            e
+        );
+    }
+}
+
+fn migrate_item(
+    task_id: &str,
+    item: &MigrationItem,
+    destination_com_mojang: &Path,
+    mode: MigrationMode,
+) -> Result<(PathBuf, bool), String> {
+    let destination_dir = destination_com_mojang.join(item.category.target_dir().name());
+    fs::create_dir_all(&destination_dir)
+        .map_err(|e| format!("创建目录失败 {}: {}", destination_dir.display(), e))?;
+    let name = item
+        .source
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .ok_or_else(|| format!("无效的源路径: {}", item.source.display()))?;
+    let (destination, kept_options) = if item.category == MigrationCategory::Options {
+        options_destination(&destination_dir)?
+    } else {
+        (unique_destination(&destination_dir, &name, false)?, None)
+    };
+    let renamed = kept_options.is_some() || destination.file_name() != item.source.file_name();
+
+    if let Err(e) = copy_preserving_times(&item.source, &destination, task_id) {
+        remove_partial_copy(&destination);
+        if let Some(kept) = &kept_options {
+            restore_kept_options(kept, &destination);
+        }
+        return Err(e);
+    }
+
+    if mode == MigrationMode::Move {
+        if item.category == MigrationCategory::Worlds
+            && let Err(e) = read_level_dat(&destination.join("level.dat"))
+        {
+            remove_partial_copy(&destination);
+            return Err(format!("副本的 level.dat 无法解析，已保留源存档: {}", e));
+        }
+        delete_with_undo(&item.source, &format!("{} {}", item.category.label(), name))?;
+    }
+    Ok((destination, renamed))
+}
+
+fn migrate_blocking(
+    task_id: &str,
+    items: Vec<MigrationItem>,
+    destination_com_mojang: &Path,
+    mode: MigrationMode,
+) -> (Vec<MigrationItemResult>, bool) {
+    let mut results = Vec::with_capacity(items.len());
+    let mut cancelled = false;
+    for item in items {
+        let source = item.source.to_string_lossy().into_owned();
+        if cancelled || is_cancelled(task_id) {
+            cancelled = true;
+            results.push(MigrationItemResult {
+                category: item.category,
+                source,
+                destination: None,
+                renamed: false,
+                status: MigrationItemStatus::Skipped,
+                error: None,
+            });
+            continue;
+        }
+
+        update_progress(task_id, 0, None, Some("migrate"));
+        let result = migrate_item(task_id, &item, destination_com_mojang, mode);
+        if is_cancelled(task_id) {
+            cancelled = true;
+        }
+        let entry = match result {
+            Ok((destination, renamed)) => {
+                append_task_log(
+                    task_id,
+                    format!("已迁移 {} -> {}", source, destination.display()),
+                );
+                MigrationItemResult {
+                    category: item.category,
+                    source,
+                    destination: Some(destination.to_string_lossy().into_owned()),
+                    renamed,
+                    status: match mode {
+                        MigrationMode::Copy => MigrationItemStatus::Copied,
+                        MigrationMode::Move => MigrationItemStatus::Moved,
+                    },
+                    error: None,
+                }
+            }
+            Err(error) => {
+                append_task_log(task_id, format!("迁移失败 {}: {}", source, error));
+                MigrationItemResult {
+                    category: item.category,
+                    source,
+                    destination: None,
+                    renamed: false,
+                    status: if cancelled {
+                        MigrationItemStatus::Skipped
+                    } else {
+                        MigrationItemStatus::Failed
+                    },
+                    error: Some(error),
+                }
+            }
+        };
+        results.push(entry);
+        update_progress(task_id, 1, None, Some("migrate"));
+    }
+    (results, cancelled)
+}
+
+/// 任何 Minecraft.Windows.exe（UWP 或 GDK）在运行或启动器正在启动游戏
+fn is_any_game_running() -> bool {
+    if !get_active_launches().is_empty() {
+        return true;
+    }
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::All, true);
+    system.processes().values().any(|process| {
+        process
+            .name()
+            .to_string_lossy()
+            .eq_ignore_ascii_case(GAME_EXECUTABLE_NAME)
+    })
+}
+
+async fn migrate_between(
+    from: BuildType,
+    to: BuildType,
+    edition: Edition,
+    user_folder: String,
+    what: MigrationSelection,
+    mode: MigrationMode,
+) -> Result<MigrationReport, String> {
+    validate_user_folder(&user_folder)?;
+    let categories = selected_categories(&what);
+    if categories.is_empty() {
+        return Err("未选择要迁移的内容".to_string());
+    }
+    let running = tokio::task::spawn_blocking(is_any_game_running)
+        .await
+        .map_err(|e| format!("检测游戏进程失败: {}", e))?;
+    if running {
+        return Err("游戏正在运行，请先关闭 UWP 与 GDK 版游戏再迁移数据".to_string());
+    }
+
+    let source_root = system_com_mojang_dir(from.clone(), &edition, &user_folder)?;
+    let destination_root = system_com_mojang_dir(to.clone(), &edition, &user_folder)?;
+    if !source_root.is_dir() {
+        return Err(format!("源数据目录不存在: {}", source_root.display()));
+    }
+
+    let items = collect_items(&source_root, &categories);
+    let task_id = create_task_with_details(
+        None,
+        "迁移游戏数据",
+        Some(format!("{:?} -> {:?}", from, to)),
+        "migrate",
+        Some(items.len() as u64),
+        false,
+    );
+    info!(
+        from = ?from,
+        to = ?to,
+        user_folder = %user_folder,
+        items = items.len(),
+        ?mode,
+        "开始迁移游戏数据"
+    );
+
+    let blocking_task_id = task_id.clone();
+    let blocking_destination = destination_root.clone();
+    let outcome = tokio::task::spawn_blocking(move || {
+        migrate_blocking(&blocking_task_id, items, &blocking_destination, mode)
+    })
+    .await
+    .map_err(|e| format!("迁移任务失败: {}", e));
+    let (items, cancelled) = match outcome {
+        Ok(outcome) => outcome,
+        Err(error) => {
+            finish_task(&task_id, "error", Some(error.clone()));
+            return Err(error);
+        }
+    };
+
+    let migrated = items
+        .iter()
+        .filter(|item| {
+            matches!(
+                item.status,
+                MigrationItemStatus::Copied | MigrationItemStatus::Moved
+            )
+        })
+        .count();
+    let failed = items
+        .iter()
+        .filter(|item| item.status == MigrationItemStatus::Failed)
+        .count();
+    if migrated > 0 {
+        crate::core::library_search::invalidate_library_index();
+    }
+    let summary = format!("已迁移 {} 项，失败 {} 项", migrated, failed);
+    if cancelled {
+        finish_task(&task_id, "cancelled", Some(summary));
+    } else if failed > 0 {
+        finish_task(&task_id, "error", Some(summary));
+    } else {
+        finish_task(&task_id, "completed", Some(summary));
+    }
+
+    Ok(MigrationReport {
+        task_id,
+        mode,
+        source_root: source_root.to_string_lossy().into_owned(),
+        destination_root: destination_root.to_string_lossy().into_owned(),
+        items,
+        migrated,
+        failed,
+        cancelled,
+    })
+}
+
+/// 把 UWP 版数据迁移到指定 GDK 用户目录；任一版本游戏运行中时拒绝执行
+pub async fn migrate_uwp_to_gdk(
+    edition: Edition,
+    user_folder: String,
+    what: MigrationSelection,
+    mode: MigrationMode,
+) -> Result<MigrationReport, String> {
+    migrate_between(
+        BuildType::Uwp,
+        BuildType::Gdk,
+        edition,
+        user_folder,
+        what,
+        mode,
+    )
+    .await
+}
+
+/// 把指定 GDK 用户目录的数据迁移回 UWP 版；任一版本游戏运行中时拒绝执行
+pub async fn migrate_gdk_to_uwp(
+    edition: Edition,
+    user_folder: String,
+    what: MigrationSelection,
+    mode: MigrationMode,
+) -> Result<MigrationReport, String> {
+    migrate_between(
+        BuildType::Gdk,
+        BuildType::Uwp,
+        edition,
+        user_folder,
+        what,
+        mode,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collisions_get_numbered_and_options_are_kept_aside() {
+        let root =
+            std::env::temp_dir().join(format!("bmcbl-edition-migration-{}", uuid::Uuid::new_v4()));
+        let source = root.join("uwp");
+        let destination = root.join("gdk");
+        fs::create_dir_all(source.join("minecraftWorlds/abc=")).expect("create source world");
+        fs::write(source.join("minecraftWorlds/abc=/levelname.txt"), "World")
+            .expect("write source world");
+        fs::create_dir_all(source.join("minecraftpe")).expect("create source settings");
+        fs::write(source.join("minecraftpe/options.txt"), "gfx_fov:70").expect("write options");
+        fs::create_dir_all(destination.join("minecraftWorlds/abc=")).expect("create dest world");
+        fs::create_dir_all(destination.join("minecraftWorlds/abc= (2)"))
+            .expect("create dest world copy");
+        fs::create_dir_all(destination.join("minecraftpe")).expect("create dest settings");
+        fs::write(destination.join("minecraftpe/options.txt"), "gfx_fov:90")
+            .expect("write dest options");
+
+        let items = collect_items(
+            &source,
+            &[MigrationCategory::Worlds, MigrationCategory::Options],
+        );
+        assert_eq!(items.len(), 2);
+        let task_id = "edition-migration-test";
+        let world = migrate_item(task_id, &items[0], &destination, MigrationMode::Copy)
+            .expect("migrate world");
+        assert_eq!(world, (destination.join("minecraftWorlds/abc= (3)"), true));
+        assert_eq!(
+            fs::read_to_string(world.0.join("levelname.txt")).expect("read copied world"),
+            "World"
+        );
+        assert_eq!(
+            fs::metadata(world.0.join("levelname.txt"))
+                .and_then(|metadata| metadata.modified())
+                .expect("copied mtime"),
+            fs::metadata(source.join("minecraftWorlds/abc=/levelname.txt"))
+                .and_then(|metadata| metadata.modified())
+                .expect("source mtime")
+        );
+
+        let options = migrate_item(task_id, &items[1], &destination, MigrationMode::Copy)
+            .expect("migrate options");
+        assert_eq!(options, (destination.join("minecraftpe/options.txt"), true));
+        assert_eq!(
+            fs::read_to_string(destination.join("minecraftpe/options.txt")).expect("read options"),
+            "gfx_fov:70"
+        );
+        assert_eq!(
+            fs::read_to_string(destination.join("minecraftpe/options (2).txt"))
+                .expect("read kept options"),
+            "gfx_fov:90"
+        );
+
+        // 移动模式下副本无法解析 level.dat 时保留源存档
+        let error = migrate_item(task_id, &items[0], &destination, MigrationMode::Move)
+            .expect_err("unparsable world is not moved");
+        assert!(error.contains("level.dat"));
+        assert!(source.join("minecraftWorlds/abc=").is_dir());
+        assert!(!destination.join("minecraftWorlds/abc= (4)").exists());
+
+        // 复制设置失败时，改名保留的原设置放回原位
+        fs::remove_file(source.join("minecraftpe/options.txt")).expect("remove source options");
+        assert!(migrate_item(task_id, &items[1], &destination, MigrationMode::Copy).is_err());
+        assert_eq!(
+            fs::read_to_string(destination.join("minecraftpe/options.txt")).expect("read options"),
+            "gfx_fov:70"
+        );
+        assert!(!destination.join("minecraftpe/options (3).txt").exists());
+
+        fs::remove_dir_all(&root).expect("remove test dir");
+    }
+}
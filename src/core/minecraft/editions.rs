@@ -0,0 +1,247 @@
+//! 版本注册表：包标识 → AUMID → 包系列名 → 数据目录 的映射。
+//! 内置默认表，启动时合并 `BMCBL/editions.json` 中的覆盖项，新增或改名的版本无需发版。
+
+use crate::core::minecraft::paths::Edition;
+use crate::utils::file_ops;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::sync::RwLock;
+use tracing::{debug, warn};
+
+const OVERRIDE_FILE_NAME: &str = "editions.json";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EditionEntry {
+    /// 包标识名，例如 `Microsoft.MinecraftUWP`
+    pub identity: String,
+    /// `<包系列名>!<应用 ID>`
+    pub aumid: String,
+    pub edition: Edition,
+    pub display_name: String,
+    /// UWP 数据所在的 `%LOCALAPPDATA%\Packages` 子目录；缺省为包系列名
+    #[serde(default)]
+    pub data_package_dir: Option<String>,
+    /// GDK 数据所在的 `%APPDATA%` 子目录
+    #[serde(default)]
+    pub gdk_data_dir: Option<String>,
+}
+
+impl EditionEntry {
+    pub fn family_name(&self) -> &str {
+        self.aumid.split('!').next().unwrap_or_default()
+    }
+
+    pub fn uwp_data_dir_name(&self) -> &str {
+        self.data_package_dir
+            .as_deref()
+            .unwrap_or_else(|| self.family_name())
+    }
+}
+
+fn entry(
+    identity: &str,
+    app_id: &str,
+    edition: Edition,
+    display_name: &str,
+    data_package_dir: Option<&str>,
+    gdk_data_dir: &str,
+) -> EditionEntry {
+    EditionEntry {
+        identity: identity.to_string(),
+        aumid: format!("{identity}_8wekyb3d8bbwe!{app_id}"),
+        edition,
+        display_name: display_name.to_string(),
+        data_package_dir: data_package_dir.map(ToString::to_string),
+        gdk_data_dir: Some(gdk_data_dir.to_string()),
+    }
+}
+
+/// 内置默认表；第一项是未知包标识的兜底
+fn default_editions() -> Vec<EditionEntry> {
+    vec![
+        entry(
+            "Microsoft.MinecraftUWP",
+            "App",
+            Edition::Release,
+            "正式版",
+            None,
+            "Minecraft Bedrock",
+        ),
+        entry(
+            "Microsoft.MinecraftWindowsBeta",
+            "App",
+            Edition::Preview,
+            "预览版",
+            None,
+            "Minecraft Bedrock Preview",
+        ),
+        entry(
+            "Microsoft.MinecraftEducationEdition",
+            "Microsoft.MinecraftEducationEdition",
+            Edition::Education,
+            "教育版",
+            None,
+            "Minecraft Education Edition",
+        ),
+        entry(
+            "Microsoft.MinecraftEducationPreview",
+            "Microsoft.MinecraftEducationEdition",
+            Edition::EducationPreview,
+            "教育预览版",
+            Some("Microsoft.MinecraftEducationEditionBeta_8wekyb3d8bbwe"),
+            "Minecraft Education Edition Preview",
+        ),
+    ]
+}
+
+static REGISTRY: Lazy<RwLock<Vec<EditionEntry>>> = Lazy::new(|| RwLock::new(build_registry().0));
+
+/// AUMID 必须是 `<包标识>_<发布者 ID>!<应用 ID>`
+fn validate_entry(entry: &EditionEntry) -> Result<(), String> {
+    let identity = entry.identity.trim();
+    if identity.is_empty() {
+        return Err("包标识不能为空".to_string());
+    }
+    let Some((family_name, app_id)) = entry.aumid.split_once('!') else {
+        return Err(format!("{identity}: AUMID 缺少 '!' 分隔的应用 ID"));
+    };
+    let publisher_id = family_name
+        .strip_prefix(identity)
+        .and_then(|rest| rest.strip_prefix('_'));
+    if publisher_id.is_none_or(str::is_empty)
+        || app_id.is_empty()
+        || entry.aumid.chars().any(char::is_whitespace)
+    {
+        return Err(format!("{identity}: AUMID 格式无效: {}", entry.aumid));
+    }
+    Ok(())
+}
+
+/// 把覆盖项合并进默认表：同名包标识替换默认项，其余追加；返回合并结果和所有校验错误
+fn merge_overrides(
+    mut editions: Vec<EditionEntry>,
+    overrides: Vec<EditionEntry>,
+) -> (Vec<EditionEntry>, Vec<String>) {
+    let mut errors = Vec::new();
+    let mut seen = HashSet::new();
+    for entry in overrides {
+        if let Err(error) = validate_entry(&entry) {
+            errors.push(error);
+            continue;
+        }
+        if !seen.insert(entry.identity.to_ascii_lowercase()) {
+            errors.push(format!("{}: 包标识重复", entry.identity));
+            continue;
+        }
+        match editions
+            .iter_mut()
+            .find(|existing| existing.identity.eq_ignore_ascii_case(&entry.identity))
+        {
+            Some(existing) => *existing = entry,
+            None => editions.push(entry),
+        }
+    }
+    (editions, errors)
+}
+
+fn build_registry() -> (Vec<EditionEntry>, Vec<String>) {
+    let path = file_ops::bmcbl_subdir(OVERRIDE_FILE_NAME);
+    let raw = match fs::read_to_string(&path) {
+        Ok(raw) => raw,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return (default_editions(), Vec::new());
+        }
+        Err(e) => {
+            return (
+                default_editions(),
+                vec![format!("读取 {} 失败: {}", path.display(), e)],
+            );
+        }
+    };
+    match serde_json::from_str::<Vec<EditionEntry>>(&raw) {
+        Ok(overrides) => merge_overrides(default_editions(), overrides),
+        Err(e) => (
+            default_editions(),
+            vec![format!("解析 {} 失败: {}", path.display(), e)],
+        ),
+    }
+}
+
+/// 重新加载注册表，返回覆盖文件中的错误；无效的覆盖项会被跳过，其余照常生效
+pub fn load_edition_registry() -> Vec<String> {
+    let (editions, errors) = build_registry();
+    for error in &errors {
+        warn!("版本注册表覆盖项无效: {}", error);
+    }
+    debug!("版本注册表已加载: {} 项", editions.len());
+    *REGISTRY.write().unwrap_or_else(|e| e.into_inner()) = editions;
+    errors
+}
+
+pub(crate) fn known_editions() -> Vec<EditionEntry> {
+    REGISTRY.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+pub(crate) fn edition_by_identity(identity: &str) -> Option<EditionEntry> {
+    known_editions()
+        .into_iter()
+        .find(|entry| entry.identity.eq_ignore_ascii_case(identity))
+}
+
+/// 该版本类别在表中的第一项
+pub(crate) fn edition_entry(edition: &Edition) -> Option<EditionEntry> {
+    known_editions()
+        .into_iter()
+        .find(|entry| entry.edition == *edition)
+}
+
+/// 合并后的完整版本表，供界面动态渲染版本选择
+pub fn list_known_editions() -> Result<Vec<EditionEntry>, String> {
+    Ok(known_editions())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_replace_append_and_reject_invalid_entries() {
+        let mut renamed_preview = default_editions()[1].clone();
+        renamed_preview.display_name = "Beta".to_string();
+        let china = EditionEntry {
+            identity: "NetEase.MinecraftChina".to_string(),
+            aumid: "NetEase.MinecraftChina_abcdefghijk!App".to_string(),
+            edition: Edition::Release,
+            display_name: "中国版".to_string(),
+            data_package_dir: None,
+            gdk_data_dir: None,
+        };
+        let malformed = EditionEntry {
+            aumid: "NetEase.Other!App".to_string(),
+            identity: "NetEase.Other".to_string(),
+            ..china.clone()
+        };
+
+        let (editions, errors) = merge_overrides(
+            default_editions(),
+            vec![renamed_preview, china.clone(), china.clone(), malformed],
+        );
+
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("重复"));
+        assert!(errors[1].contains("AUMID"));
+        assert_eq!(editions.len(), 5);
+        assert_eq!(editions[1].display_name, "Beta");
+        assert_eq!(editions[4], china);
+        assert_eq!(
+            editions[4].uwp_data_dir_name(),
+            "NetEase.MinecraftChina_abcdefghijk"
+        );
+        assert_eq!(
+            editions[3].uwp_data_dir_name(),
+            "Microsoft.MinecraftEducationEditionBeta_8wekyb3d8bbwe"
+        );
+    }
+}
@@ -0,0 +1,311 @@
+//! 把存档目录导出为 .mcworld、把已安装的资源包/行为包导出为 .mcpack：文件逐个流式写入 zip
+//! （Deflate，启用 zip64），超过 2GB 的大存档和单个超过 4GB 的数据库文件也能导出。
+//! 条目路径统一用 `/` 分隔并保留 Unicode 文件夹名；`level.dat`、`levelname.txt`、`world_icon.jpeg`
+//! 和 `world_*_packs.json`（包则为 `manifest.json` 和 `pack_icon.png`）写在归档根目录最前面，
+//! 导入预览只需读取开头几个条目即可识别内容。
+
+use crate::core::minecraft::import::{parse_manifest_str, strip_json_comments};
+use crate::core::minecraft::pack_export::{ArchiveLayout, ExportStats, write_directory_archive};
+use crate::tasks::task_manager::{
+    create_task_with_details, finish_task, is_cancelled, set_total, update_progress,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::info;
+use walkdir::WalkDir;
+
+/// 归档根目录中优先写入的世界元数据文件
+const WORLD_METADATA_FILES: [&str; 5] = [
+    "level.dat",
+    "levelname.txt",
+    "world_icon.jpeg",
+    "world_behavior_packs.json",
+    "world_resource_packs.json",
+];
+/// 包根目录中优先写入的文件
+const PACK_METADATA_FILES: [&str; 2] = ["manifest.json", "pack_icon.png"];
+
+/// 把世界目录写成 .mcworld；`on_file(bytes)` 每写完一个文件调用一次，返回 false 时中止并返回 `None`
+pub(crate) fn write_world_archive(
+    world_dir: &Path,
+    output_path: &Path,
+    on_file: impl FnMut(u64) -> bool,
+) -> Result<Option<ExportStats>, String> {
+    if !world_dir.is_dir() {
+        return Err(format!("世界目录不存在: {}", world_dir.display()));
+    }
+    write_archive(world_dir, output_path, &WORLD_METADATA_FILES, on_file)
+}
+
+/// 元数据文件写在最前，跳过垃圾文件、安装信息和输出文件自身
+fn write_archive(
+    source_dir: &Path,
+    output_path: &Path,
+    leading: &[&str],
+    on_file: impl FnMut(u64) -> bool,
+) -> Result<Option<ExportStats>, String> {
+    let layout = ArchiveLayout {
+        leading,
+        skip_generated: true,
+        directory_entries: false,
+    };
+    write_directory_archive(source_dir, output_path, None, layout, on_file)
+}
+
+/// 包目录的 manifest.json 必须存在、能解析且带 `header.uuid`，否则导出的 .mcpack 无法被游戏导入
+fn validate_pack_manifest(pack_dir: &Path) -> Result<(), String> {
+    let manifest_path = pack_dir.join("manifest.json");
+    let raw = fs::read_to_string(&manifest_path).map_err(|e| {
+        format!(
+            "不是有效的包目录（无法读取 manifest.json）{}: {}",
+            pack_dir.display(),
+            e
+        )
+    })?;
+    let clean = strip_json_comments(raw.trim_start_matches('\u{feff}'));
+    let (manifest, _) = parse_manifest_str(&clean)
+        .map_err(|e| format!("manifest.json 格式错误 {}: {}", manifest_path.display(), e))?;
+    let header = manifest
+        .header
+        .ok_or_else(|| format!("manifest.json 缺少 header: {}", manifest_path.display()))?;
+    if header.uuid.is_none_or(|uuid| uuid.trim().is_empty()) {
+        return Err(format!(
+            "manifest.json 缺少 header.uuid: {}",
+            manifest_path.display()
+        ));
+    }
+    Ok(())
+}
+
+/// 以任务运行导出，按已写入字节数推进进度，可取消；失败或取消时由写入方删除半成品。源目录需事先校验
+async fn run_export_task(
+    title: &str,
+    detail: String,
+    source_dir: PathBuf,
+    output_path: PathBuf,
+    leading: &'static [&'static str],
+) -> Result<(), String> {
+    let task_id = create_task_with_details(None, title, Some(detail), "packing", None, false);
+
+    let blocking_task_id = task_id.clone();
+    let blocking_source_dir = source_dir.clone();
+    let blocking_output = output_path.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let total_bytes: u64 = WalkDir::new(&blocking_source_dir)
+            .follow_links(false)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_file())
+            .filter_map(|entry| entry.metadata().ok())
+            .map(|metadata| metadata.len())
+            .sum();
+        set_total(&blocking_task_id, Some(total_bytes));
+        write_archive(&blocking_source_dir, &blocking_output, leading, |bytes| {
+            update_progress(&blocking_task_id, bytes, None, None);
+            !is_cancelled(&blocking_task_id)
+        })
+    })
+    .await
+    .map_err(|e| format!("{}任务失败: {}", title, e))
+    .and_then(|result| result);
+
+    match result {
+        Ok(Some(stats)) => {
+            info!(
+                source = %source_dir.display(),
+                output = %output_path.display(),
+                files = stats.files_written,
+                total_bytes = stats.total_bytes,
+                compressed_bytes = stats.compressed_bytes,
+                "{}完成",
+                title
+            );
+            finish_task(&task_id, "completed", None);
+            Ok(())
+        }
+        Ok(None) => {
+            finish_task(&task_id, "cancelled", None);
+            Err(format!("已取消{}", title))
+        }
+        Err(error) => {
+            finish_task(&task_id, "error", Some(error.clone()));
+            Err(error)
+        }
+    }
+}
+
+/// 把 `folder_path` 的世界导出为 `output_path`（.mcworld），按已写入字节数推进任务进度，可取消
+pub async fn export_world_to_mcworld(
+    folder_path: String,
+    output_path: String,
+) -> Result<(), String> {
+    let world_dir = PathBuf::from(folder_path);
+    if !world_dir.join("level.dat").is_file() {
+        return Err(format!(
+            "不是有效的世界目录（缺少 level.dat）: {}",
+            world_dir.display()
+        ));
+    }
+    let world_name = fs::read_to_string(world_dir.join("levelname.txt"))
+        .ok()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .or_else(|| {
+            world_dir
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+        })
+        .unwrap_or_default();
+    run_export_task(
+        "导出世界",
+        world_name,
+        world_dir,
+        PathBuf::from(output_path),
+        &WORLD_METADATA_FILES,
+    )
+    .await
+}
+
+/// 把已安装的资源包/行为包目录（如 `resource_packs/pack_<uuid>`）重新打包为 `output_path`（.mcpack）；
+/// manifest.json 缺失或格式错误时直接返回错误，不会生成无法导入的归档
+pub async fn export_pack_to_mcpack(pack_dir: String, output_path: String) -> Result<(), String> {
+    let pack_dir = PathBuf::from(pack_dir);
+    if !pack_dir.is_dir() {
+        return Err(format!("包目录不存在: {}", pack_dir.display()));
+    }
+    validate_pack_manifest(&pack_dir)?;
+    let pack_name = pack_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    run_export_task(
+        "导出包",
+        pack_name,
+        pack_dir,
+        PathBuf::from(output_path),
+        &PACK_METADATA_FILES,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::minecraft::import::PACK_INSTALL_METADATA_NAME;
+    use std::fs::File;
+    use std::io::Read;
+
+    #[test]
+    fn writes_metadata_first_with_forward_slash_unicode_names() {
+        let root =
+            std::env::temp_dir().join(format!("bmcbl-world-export-{}", uuid::Uuid::new_v4()));
+        let world_dir = root.join("我的世界");
+        fs::create_dir_all(world_dir.join("db")).expect("create db dir");
+        fs::create_dir_all(world_dir.join("behavior_packs").join("方块包"))
+            .expect("create pack dir");
+        fs::write(world_dir.join("db").join("000001.ldb"), vec![3u8; 1024]).expect("write db");
+        fs::write(
+            world_dir
+                .join("behavior_packs")
+                .join("方块包")
+                .join("manifest.json"),
+            b"{}",
+        )
+        .expect("write manifest");
+        fs::write(world_dir.join("world_resource_packs.json"), b"[]").expect("write packs");
+        fs::write(world_dir.join("world_icon.jpeg"), b"\xFF\xD8\xFF").expect("write icon");
+        fs::write(world_dir.join("levelname.txt"), "测试世界").expect("write level name");
+        fs::write(world_dir.join("level.dat"), b"level").expect("write level.dat");
+        fs::write(world_dir.join("Thumbs.db"), b"junk").expect("write junk");
+        let output_path = world_dir.join("export.mcworld");
+
+        let stats = write_world_archive(&world_dir, &output_path, |_| true)
+            .expect("export world")
+            .expect("not cancelled");
+
+        assert_eq!(stats.files_written, 6);
+        let mut archive =
+            zip::ZipArchive::new(File::open(&output_path).expect("open zip")).expect("read zip");
+        let names: Vec<String> = archive.file_names().map(str::to_string).collect();
+        assert_eq!(
+            &names[..4],
+            [
+                "level.dat",
+                "levelname.txt",
+                "world_icon.jpeg",
+                "world_resource_packs.json"
+            ]
+        );
+        assert!(names.contains(&"behavior_packs/方块包/manifest.json".to_string()));
+        assert!(names.iter().all(|name| !name.contains('\\')));
+        assert!(
+            !names
+                .iter()
+                .any(|name| name == "Thumbs.db" || name == "export.mcworld")
+        );
+        let mut level_name = String::new();
+        archive
+            .by_name("levelname.txt")
+            .expect("level name entry")
+            .read_to_string(&mut level_name)
+            .expect("read level name");
+        assert_eq!(level_name, "测试世界");
+
+        fs::remove_dir_all(&root).expect("remove test dir");
+    }
+
+    #[tokio::test]
+    async fn exports_pack_with_manifest_first_and_rejects_invalid_manifest() {
+        let root = std::env::temp_dir().join(format!("bmcbl-pack-export-{}", uuid::Uuid::new_v4()));
+        let pack_dir = root.join("pack_4d3f4a6e");
+        fs::create_dir_all(pack_dir.join("texts")).expect("create texts dir");
+        fs::write(
+            pack_dir.join("texts").join("zh_CN.lang"),
+            "pack.name=测试包",
+        )
+        .expect("write lang");
+        fs::write(pack_dir.join("pack_icon.png"), b"\x89PNG").expect("write icon");
+        fs::write(pack_dir.join(PACK_INSTALL_METADATA_NAME), b"{}").expect("write metadata");
+        let output_path = root.join("out").join("pack.mcpack");
+        let export = || {
+            export_pack_to_mcpack(
+                pack_dir.to_string_lossy().into_owned(),
+                output_path.to_string_lossy().into_owned(),
+            )
+        };
+
+        let error = export().await.expect_err("missing manifest");
+        assert!(error.contains("manifest.json"), "{error}");
+        fs::write(pack_dir.join("manifest.json"), "{ \"header\": ").expect("write broken manifest");
+        let error = export().await.expect_err("malformed manifest");
+        assert!(error.contains("格式错误"), "{error}");
+        fs::write(
+            pack_dir.join("manifest.json"),
+            r#"{"header":{"name":"测试包"}}"#,
+        )
+        .expect("write manifest without uuid");
+        assert!(
+            export()
+                .await
+                .expect_err("missing uuid")
+                .contains("header.uuid")
+        );
+        assert!(!output_path.exists());
+
+        fs::write(
+            pack_dir.join("manifest.json"),
+            r#"{"format_version":2,"header":{"name":"pack.name","uuid":"4d3f4a6e-1b52-4c1d-9a0e-0c7e7d1f0a01","version":[1,0,0]}}"#,
+        )
+        .expect("write manifest");
+        export().await.expect("export pack");
+        let archive =
+            zip::ZipArchive::new(File::open(&output_path).expect("open zip")).expect("read zip");
+        let names: Vec<&str> = archive.file_names().collect();
+        assert_eq!(
+            names,
+            ["manifest.json", "pack_icon.png", "texts/zh_CN.lang"]
+        );
+
+        fs::remove_dir_all(&root).expect("remove test dir");
+    }
+}
@@ -0,0 +1,248 @@
+//! 读取游戏自己写的日志（`logs/` 下的 ContentLog 等），用于在启动器里查看报错。
+//! 日志目录默认按当前注册的包定位，也可以在设置中指定 `game.game_log_dir`。
+
+use crate::core::minecraft::paths::{BuildType, Edition, GamePathOptions, get_game_root};
+use crate::core::version::installations::{MinecraftEdition, list_installed_minecraft_editions};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+const LOGS_DIR_NAME: &str = "logs";
+const TAIL_CHUNK_SIZE: u64 = 64 * 1024;
+
+/// ContentLog：`16:08:46[Json][error]-消息`
+static CONTENT_LOG_LINE: Lazy<Option<Regex>> =
+    Lazy::new(|| Regex::new(r"^(\d{1,2}:\d{2}:\d{2})\[[^\]]*\]\[(\w+)\]-?\s*(.*)$").ok());
+/// 服务端风格：`[2024-05-01 12:00:00:123 INFO] 消息`
+static BRACKET_LOG_LINE: Lazy<Option<Regex>> =
+    Lazy::new(|| Regex::new(r"^\[(\d{4}-\d{2}-\d{2}[ T][\d:.]+)\s+(\w+)\]\s*(.*)$").ok());
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GameLogEntry {
+    /// `INFO` / `WARN` / `ERROR`
+    pub level: String,
+    pub timestamp: Option<String>,
+    pub message: String,
+}
+
+/// 未知级别返回 `None`
+fn normalize_level(level: &str) -> Option<&'static str> {
+    match level.to_ascii_lowercase().as_str() {
+        "info" | "inform" | "information" => Some("INFO"),
+        "warn" | "warning" => Some("WARN"),
+        "error" | "fatal" | "critical" => Some("ERROR"),
+        _ => None,
+    }
+}
+
+/// 解析一行日志；不带前缀或级别未知的行视为上一条的续行，返回 `None`
+fn parse_log_line(line: &str) -> Option<GameLogEntry> {
+    let captures = [&*CONTENT_LOG_LINE, &*BRACKET_LOG_LINE]
+        .into_iter()
+        .flatten()
+        .find_map(|pattern| pattern.captures(line))?;
+    Some(GameLogEntry {
+        level: normalize_level(&captures[2])?.to_string(),
+        timestamp: Some(captures[1].to_string()),
+        message: captures[3].trim_end().to_string(),
+    })
+}
+
+fn parse_log_lines<'a>(lines: impl IntoIterator<Item = &'a str>) -> Vec<GameLogEntry> {
+    let mut entries: Vec<GameLogEntry> = Vec::new();
+    for line in lines {
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() {
+            continue;
+        }
+        match (parse_log_line(line), entries.last_mut()) {
+            (Some(entry), _) => entries.push(entry),
+            (None, Some(previous)) => {
+                previous.message.push('\n');
+                previous.message.push_str(line);
+            }
+            (None, None) => entries.push(GameLogEntry {
+                level: "INFO".to_string(),
+                timestamp: None,
+                message: line.to_string(),
+            }),
+        }
+    }
+    entries
+}
+
+/// 从文件末尾按块往前读，直到凑够 `max_lines` 行
+fn tail_lines(path: &Path, max_lines: usize) -> Result<Vec<String>, String> {
+    let mut file =
+        File::open(path).map_err(|e| format!("打开日志失败 {}: {}", path.display(), e))?;
+    let len = file
+        .metadata()
+        .map_err(|e| format!("读取日志信息失败 {}: {}", path.display(), e))?
+        .len();
+    let mut start = len;
+    let mut buffer: Vec<u8> = Vec::new();
+    while start > 0 && buffer.iter().filter(|byte| **byte == b'\n').count() <= max_lines {
+        let read_len = TAIL_CHUNK_SIZE.min(start);
+        start -= read_len;
+        let mut chunk = vec![0u8; read_len as usize];
+        file.seek(SeekFrom::Start(start))
+            .and_then(|_| file.read_exact(&mut chunk))
+            .map_err(|e| format!("读取日志失败 {}: {}", path.display(), e))?;
+        chunk.extend_from_slice(&buffer);
+        buffer = chunk;
+    }
+
+    let text = String::from_utf8_lossy(&buffer);
+    let text = text.trim_start_matches('\u{feff}');
+    let mut lines: Vec<&str> = text.lines().collect();
+    // 没读到文件开头时第一行可能不完整
+    if start > 0 && !lines.is_empty() {
+        lines.remove(0);
+    }
+    let skip = lines.len().saturating_sub(max_lines);
+    Ok(lines[skip..].iter().map(|line| line.to_string()).collect())
+}
+
+fn newest_log_file(dir: &Path) -> Option<PathBuf> {
+    fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter(|entry| {
+            entry.path().extension().is_some_and(|ext| {
+                ext.eq_ignore_ascii_case("txt") || ext.eq_ignore_ascii_case("log")
+            })
+        })
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            metadata
+                .is_file()
+                .then(|| (metadata.modified().ok(), entry.path()))
+        })
+        .max_by(|left, right| left.0.cmp(&right.0))
+        .map(|(_, path)| path)
+}
+
+fn path_edition(edition: MinecraftEdition) -> Edition {
+    match edition {
+        MinecraftEdition::Release => Edition::Release,
+        MinecraftEdition::Preview => Edition::Preview,
+        MinecraftEdition::Education => Edition::Education,
+        MinecraftEdition::EducationPreview => Edition::EducationPreview,
+    }
+}
+
+/// 设置中指定的目录优先，其次是当前注册的各个包的数据目录
+async fn candidate_log_dirs() -> Result<Vec<PathBuf>, String> {
+    let mut dirs = Vec::new();
+    let configured = crate::config::config::read_config()
+        .map(|config| config.game.game_log_dir)
+        .unwrap_or_default();
+    if !configured.trim().is_empty() {
+        dirs.push(PathBuf::from(configured.trim()));
+    }
+    for edition in list_installed_minecraft_editions()
+        .await?
+        .into_iter()
+        .filter(|edition| edition.is_registered)
+    {
+        let options = GamePathOptions {
+            build_type: if edition.is_gdk {
+                BuildType::Gdk
+            } else {
+                BuildType::Uwp
+            },
+            edition: path_edition(edition.edition),
+            version_name: String::new(),
+            enable_isolation: false,
+            user_id: None,
+            allow_shared_fallback: false,
+        };
+        if let Some(root) = get_game_root(&options) {
+            dirs.push(root.join(LOGS_DIR_NAME));
+        }
+    }
+    Ok(dirs)
+}
+
+/// 读取最新一份游戏日志的最后 `max_lines` 行；`filter_level` 为 INFO / WARN / ERROR 时只返回该级别
+pub async fn get_game_logs(
+    max_lines: usize,
+    filter_level: Option<String>,
+) -> Result<Vec<GameLogEntry>, String> {
+    let filter_level = filter_level
+        .as_deref()
+        .map(str::trim)
+        .filter(|level| !level.is_empty())
+        .map(|level| normalize_level(level).ok_or_else(|| format!("无效的日志级别: {level}")))
+        .transpose()?;
+    let dirs = candidate_log_dirs().await?;
+    tokio::task::spawn_blocking(move || {
+        let log_file = dirs
+            .iter()
+            .filter_map(|dir| newest_log_file(dir))
+            .max_by_key(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+            .ok_or_else(|| {
+                "未找到游戏日志，请确认游戏已运行过或在设置中指定日志目录".to_string()
+            })?;
+        let lines = tail_lines(&log_file, max_lines)?;
+        Ok(parse_log_lines(lines.iter().map(String::as_str))
+            .into_iter()
+            .filter(|entry| filter_level.is_none_or(|level| entry.level == level))
+            .collect())
+    })
+    .await
+    .map_err(|e| format!("读取游戏日志任务失败: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_levels_and_continuation_lines_from_log_tail() {
+        let path =
+            std::env::temp_dir().join(format!("bmcbl-game-log-{}.txt", uuid::Uuid::new_v4()));
+        let mut content = String::new();
+        for index in 0..5000 {
+            content.push_str(&format!("10:00:00[Texture][inform]-filler {index}\n"));
+        }
+        content.push_str("16:08:46[Json][warning]-unknown field\n");
+        content.push_str("16:08:47[Scripting][error]-TypeError: x is undefined\n");
+        content.push_str("    at main.js:3\n");
+        content.push_str("16:08:48[Scripting][stacktrace]-frame 2\n");
+        content.push_str("[2024-05-01 12:00:00:123 INFO] Server started\r\n");
+        fs::write(&path, content).expect("write log");
+
+        let lines = tail_lines(&path, 5).expect("tail");
+        assert_eq!(lines.len(), 5);
+        let entries = parse_log_lines(lines.iter().map(String::as_str));
+        assert_eq!(
+            entries,
+            vec![
+                GameLogEntry {
+                    level: "WARN".to_string(),
+                    timestamp: Some("16:08:46".to_string()),
+                    message: "unknown field".to_string(),
+                },
+                GameLogEntry {
+                    level: "ERROR".to_string(),
+                    timestamp: Some("16:08:47".to_string()),
+                    message:
+                        "TypeError: x is undefined\n    at main.js:3\n16:08:48[Scripting][stacktrace]-frame 2"
+                            .to_string(),
+                },
+                GameLogEntry {
+                    level: "INFO".to_string(),
+                    timestamp: Some("2024-05-01 12:00:00:123".to_string()),
+                    message: "Server started".to_string(),
+                },
+            ]
+        );
+        assert_eq!(tail_lines(&path, 6000).expect("tail all").len(), 5005);
+
+        fs::remove_file(&path).expect("remove test log");
+    }
+}
@@ -0,0 +1,59 @@
+//! 游戏进程会话监视：每个拉起的游戏进程只有一个监视线程，运行期间推进使用统计，
+//! 退出后结束统计会话并还原临时启用的世界包。
+
+use crate::core::minecraft::{pack_reverts, usage_stats};
+use once_cell::sync::Lazy;
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessesToUpdate, System};
+use tracing::{debug, warn};
+
+const GAME_EXIT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+static WATCHED_PIDS: Lazy<Mutex<HashSet<u32>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+pub(crate) fn is_process_alive(system: &mut System, pid: u32) -> bool {
+    let pid = Pid::from_u32(pid);
+    system.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+    system.process(pid).is_some()
+}
+
+fn unwatch(pid: u32) {
+    WATCHED_PIDS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&pid);
+}
+
+/// 开始监视游戏进程；同一进程已在监视时直接返回
+pub(crate) fn watch_game_session(pid: u32) {
+    if !WATCHED_PIDS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(pid)
+    {
+        return;
+    }
+    let result = std::thread::Builder::new()
+        .name("bmcbl-game-session-watcher".to_string())
+        .spawn(move || {
+            let mut system = System::new();
+            let mut last_heartbeat = Instant::now();
+            while is_process_alive(&mut system, pid) {
+                std::thread::sleep(GAME_EXIT_POLL_INTERVAL);
+                if last_heartbeat.elapsed() >= usage_stats::HEARTBEAT_INTERVAL {
+                    usage_stats::heartbeat_session(&mut system, pid);
+                    last_heartbeat = Instant::now();
+                }
+            }
+            debug!(pid, "游戏进程已退出");
+            usage_stats::end_session(pid);
+            pack_reverts::revert_exited_games(&mut system);
+            unwatch(pid);
+        });
+    if let Err(e) = result {
+        unwatch(pid);
+        warn!("无法启动游戏会话监视线程: {}", e);
+    }
+}
@@ -597,6 +597,13 @@ impl MsiXVDStream {
                         );
 
                         if hash_page_idx != cached_hash_page_idx {
+                            // 大块解密时一个分块会读取多个哈希页，这里也要响应取消
+                            if is_cancelled(task_id) {
+                                return Err(std::io::Error::new(
+                                    std::io::ErrorKind::Interrupted,
+                                    "cancelled",
+                                ));
+                            }
                             read_exact_at(
                                 file,
                                 hash_page_cache,
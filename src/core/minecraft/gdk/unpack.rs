@@ -1,10 +1,11 @@
 use crate::core::minecraft::gdk::stream::MsiXVDStream;
+use crate::core::version::integrity::write_integrity_lock;
 use crate::tasks::task_manager::{
     create_task_with_details, finish_task, is_cancelled, update_progress,
 };
 use crate::utils::file_ops;
 use std::path::{Path, PathBuf};
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 pub fn start_unpack_gdk_task(
     input_path: impl Into<PathBuf>,
@@ -60,6 +61,9 @@ pub fn start_unpack_gdk_task(
                     "GDK 解包任务完成: task_id={}, folder_name={}, input={:?}, output={:?}",
                     task_id_clone, folder_name, input_path_buf, version_dir
                 );
+                if let Err(e) = write_integrity_lock(&version_dir) {
+                    warn!("生成完整性锁文件失败: {e}");
+                }
                 finish_task(
                     &task_id_clone,
                     "completed",
@@ -13,8 +13,27 @@ use tracing::{debug, error, info, warn};
 use walkdir::WalkDir;
 use zip::ZipArchive;
 
+use crate::archive::format::ensure_zip_archive;
+use crate::core::cache_registry::{CacheDescriptor, CacheUsage, clear_dir, dir_usage};
+use crate::core::minecraft::import_history;
 use crate::core::minecraft::nbt::{NbtTag, parse_root_nbt_with_header};
+use crate::core::minecraft::pack_names::{clean_display_name, strip_minecraft_formatting};
 use crate::core::minecraft::paths::{GamePathOptions, resolve_target_parent};
+use crate::core::minecraft::zip_names::{
+    EntryNameDecoder, by_entry_name, find_entry_index, safe_relative_path,
+};
+use crate::tasks::task_manager::{is_cancelled, reset_progress, set_task_message, update_progress};
+use crate::utils::{file_locks, file_ops};
+
+/// 流式导入时的临时目录后缀，完成后原子重命名为最终目录
+const IMPORTING_SUFFIX: &str = ".importing";
+const REPLACED_SUFFIX: &str = ".bak";
+/// 升级旧版清单时保留的原始文件
+pub(crate) const LEGACY_MANIFEST_BACKUP_NAME: &str = "manifest.v1.json.bak";
+/// 导入时写入包目录的安装信息
+pub(crate) const PACK_INSTALL_METADATA_NAME: &str = ".bmcbl_install.json";
+/// 升级后的清单声明的最低引擎版本
+const UPGRADED_MIN_ENGINE_VERSION: [u32; 3] = [1, 13, 0];
 
 // [修改] 预览信息结构体，现在包含完整的 manifest
 #[derive(Debug, Serialize, Clone)]
@@ -85,11 +104,19 @@ pub struct PackagePreview {
     pub kind: String, // "World", "Resource Pack", etc.
     pub version: Option<String>,
     pub size: u64,
-    pub manifest: Option<PartialManifest>,      // [新增]
+    /// 解压后的文件数；复合包为所有子包（含嵌套包内）的合计，无法读取归档时为 `None`
+    pub file_count: Option<u64>,
+    /// 解压后的总字节数，统计口径同 `file_count`
+    pub uncompressed_size: Option<u64>,
+    pub manifest: Option<PartialManifest>, // [新增]
+    /// 去除注释后格式化的完整 manifest 文本，保留 PartialManifest 未建模的字段
+    pub raw_manifest_json: Option<String>,
     pub sub_packs: Option<Vec<PackagePreview>>, // [新增] 子包信息
     pub world_pack_references: Option<Vec<WorldPackReference>>,
     pub valid: bool,                    // [新增] 规范校验
     pub invalid_reason: Option<String>, // [新增]
+    /// 清单为 v1 旧版结构（header.pack_id / packs_version），已按现代结构解析
+    pub legacy_manifest: bool,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -109,6 +136,50 @@ pub struct ImportCheckResult {
     pub target_name: String,
     pub message: String,
     pub existing_pack_info: Option<PackagePreview>, // [新增]
+    /// UUID 冲突时导入包与已安装包的版本对比
+    pub version_comparison: Option<PackVersionComparison>,
+    /// UUID 冲突时已安装包的目录，界面据此按需调用 `diff_pack_contents`
+    pub existing_pack_path: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionComparison {
+    /// 导入的包更新
+    Newer,
+    Older,
+    Same,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PackVersionComparison {
+    pub incoming_version: Option<String>,
+    pub existing_version: Option<String>,
+    pub comparison: VersionComparison,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct PackDiffCategory {
+    /// 包内顶层目录名，根目录文件为 "root"
+    pub name: String,
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct PackContentDiff {
+    pub added_count: usize,
+    pub removed_count: usize,
+    pub changed_count: usize,
+    /// 以下列表最多保留 PACK_DIFF_LIST_LIMIT 项，超出时 truncated = true
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<String>,
+    pub truncated: bool,
+    pub categories: Vec<PackDiffCategory>,
+    pub incoming_version: Option<String>,
+    pub existing_version: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
@@ -147,6 +218,17 @@ impl ImportTargetType {
     }
 }
 
+/// 一次导入实际落地的包或存档，用于写入导入历史
+#[derive(Debug, Clone)]
+pub(crate) struct ImportedItem {
+    pub(crate) target_type: ImportTargetType,
+    pub(crate) destination: PathBuf,
+    pub(crate) pack_uuid: Option<String>,
+    pub(crate) pack_version: Option<String>,
+    /// 是否替换了目标位置已有的同 UUID 包或同名存档
+    pub(crate) overwrote: bool,
+}
+
 fn is_shared_pack_type(target_type: &ImportTargetType) -> bool {
     matches!(
         target_type,
@@ -198,6 +280,37 @@ fn resolve_world_archive_primary(scan: &ArchiveScanResult) -> Option<String> {
     Some(normalized_world_root)
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ArchiveTotals {
+    pub file_count: u64,
+    pub uncompressed_size: u64,
+}
+
+impl ArchiveTotals {
+    fn add_file(&mut self, size: u64) {
+        self.file_count += 1;
+        self.uncompressed_size = self.uncompressed_size.saturating_add(size);
+    }
+
+    fn merge(self, other: ArchiveTotals) -> ArchiveTotals {
+        ArchiveTotals {
+            file_count: self.file_count + other.file_count,
+            uncompressed_size: self
+                .uncompressed_size
+                .saturating_add(other.uncompressed_size),
+        }
+    }
+
+    fn without(self, other: ArchiveTotals) -> ArchiveTotals {
+        ArchiveTotals {
+            file_count: self.file_count.saturating_sub(other.file_count),
+            uncompressed_size: self
+                .uncompressed_size
+                .saturating_sub(other.uncompressed_size),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ArchiveScanResult {
     pub is_world: bool,
@@ -205,6 +318,10 @@ pub struct ArchiveScanResult {
     pub has_nested_archive: bool,
     pub level_roots: Vec<String>,
     pub packs: Vec<PackEntry>,
+    /// 全部文件条目（不含 `__MACOSX`）的合计；有条目无法读取时为 `None`
+    pub totals: Option<ArchiveTotals>,
+    /// 其中嵌套包（.mcpack 等）文件本身的合计，复合包统计时替换为嵌套包内的合计
+    pub nested_archive_totals: ArchiveTotals,
 }
 
 #[derive(Debug, Clone)]
@@ -213,6 +330,7 @@ pub struct PackEntry {
     pub manifest_path: String,
     pub manifest: PartialManifest,
     pub pack_type: ImportTargetType,
+    pub legacy_manifest: bool,
 }
 
 // --- Manifest 结构定义 ---
@@ -247,14 +365,265 @@ pub struct ManifestModule {
     pub description: Option<String>,      // [新增]
 }
 
+/// 依赖其他包时使用 `uuid`，依赖脚本 API 时使用 `module_name`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ManifestDependency {
+    pub uuid: Option<String>,
+    pub module_name: Option<String>,
+    pub version: Option<ManifestVersion>,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct PartialManifest {
     pub format_version: Option<ManifestFormatVersion>,
     pub header: Option<ManifestHeader>,
     pub modules: Option<Vec<ManifestModule>>,
+    #[serde(default)]
+    pub dependencies: Option<Vec<ManifestDependency>>,
+}
+
+// --- v1 旧版清单（0.15 ~ 0.16 时期）---
+// header 使用 pack_id / packs_version，modules 嵌在 header 内，名称可能只写在模块描述里
+#[derive(Deserialize, Debug)]
+struct LegacyManifestV1 {
+    format_version: Option<ManifestFormatVersion>,
+    header: Option<LegacyManifestHeader>,
+    #[serde(default)]
+    modules: Vec<LegacyManifestModule>,
+}
+
+#[derive(Deserialize, Debug)]
+struct LegacyManifestHeader {
+    name: Option<String>,
+    description: Option<String>,
+    pack_id: Option<String>,
+    uuid: Option<String>,
+    packs_version: Option<ManifestVersion>,
+    version: Option<ManifestVersion>,
+    #[serde(default)]
+    modules: Vec<LegacyManifestModule>,
+}
+
+#[derive(Deserialize, Debug)]
+struct LegacyManifestModule {
+    #[serde(rename = "type")]
+    module_type: Option<String>,
+    description: Option<String>,
+    uuid: Option<String>,
+    version: Option<ManifestVersion>,
+}
+
+impl LegacyManifestV1 {
+    fn is_legacy_schema(&self) -> bool {
+        self.header.as_ref().is_some_and(|header| {
+            header.pack_id.is_some() || header.packs_version.is_some() || !header.modules.is_empty()
+        })
+    }
+
+    fn into_partial(self) -> PartialManifest {
+        let (header, header_modules) = match self.header {
+            Some(mut header) => {
+                let modules = std::mem::take(&mut header.modules);
+                (Some(header), modules)
+            }
+            None => (None, Vec::new()),
+        };
+        let modules: Vec<ManifestModule> = self
+            .modules
+            .into_iter()
+            .chain(header_modules)
+            .filter_map(|module| {
+                Some(ManifestModule {
+                    module_type: normalize_legacy_module_type(&module.module_type?),
+                    version: module.version,
+                    uuid: module.uuid,
+                    description: module.description,
+                })
+            })
+            .collect();
+        let module_name = modules
+            .iter()
+            .filter_map(|module| module.description.clone())
+            .find(|name| !name.trim().is_empty());
+
+        PartialManifest {
+            format_version: self.format_version,
+            header: header.map(|header| ManifestHeader {
+                name: header.name.or(module_name),
+                uuid: header.uuid.or(header.pack_id),
+                description: header.description,
+                version: header.version.or(header.packs_version),
+            }),
+            modules: (!modules.is_empty()).then_some(modules),
+            dependencies: None,
+        }
+    }
+}
+
+/// 旧版模块类型写法各异，统一成 `detect_type_from_manifest` 认识的写法
+fn normalize_legacy_module_type(module_type: &str) -> String {
+    let normalized = module_type
+        .trim()
+        .to_ascii_lowercase()
+        .replace([' ', '-'], "_");
+    let mapped = match normalized.as_str() {
+        "resource" | "resource_pack" | "resource_packs" | "texture" | "textures"
+        | "texture_pack" => "resources",
+        "behavior" | "behaviour" | "behavior_pack" | "behaviour_pack" => "data",
+        "clientdata" => "client_data",
+        "skin" | "skins" | "skinpacks" | "skin_packs" => "skin_pack",
+        _ => return normalized,
+    };
+    mapped.to_string()
+}
+
+/// 解析清单文本；现代结构缺少 uuid 或模块时尝试按 v1 旧版结构解析，返回值第二项表示是否为旧版清单
+pub(crate) fn parse_manifest_str(clean: &str) -> serde_json::Result<(PartialManifest, bool)> {
+    let manifest: PartialManifest = serde_json::from_str(clean)?;
+    let complete = manifest.modules.is_some()
+        && manifest
+            .header
+            .as_ref()
+            .is_some_and(|header| header.uuid.is_some());
+    if complete {
+        return Ok((manifest, false));
+    }
+    match serde_json::from_str::<LegacyManifestV1>(clean) {
+        Ok(legacy) if legacy.is_legacy_schema() => Ok((legacy.into_partial(), true)),
+        _ => Ok((manifest, false)),
+    }
+}
+
+fn manifest_version_numbers(version: Option<&ManifestVersion>) -> Vec<u32> {
+    let mut numbers = match version {
+        Some(ManifestVersion::Numbers(numbers)) => numbers.clone(),
+        Some(ManifestVersion::String(text)) => text
+            .split('.')
+            .map(|part| part.trim().parse().unwrap_or(0))
+            .collect(),
+        None => Vec::new(),
+    };
+    numbers.resize(3, 0);
+    numbers
 }
 
-fn version_to_string(version: &ManifestVersion) -> String {
+/// 按 format_version 2 结构重写清单；缺失的模块 uuid 重新生成
+fn upgraded_manifest_json(manifest: &PartialManifest) -> serde_json::Value {
+    let header = manifest.header.as_ref();
+    let modules: Vec<serde_json::Value> = manifest
+        .modules
+        .iter()
+        .flatten()
+        .map(|module| {
+            serde_json::json!({
+                "type": module.module_type,
+                "uuid": module
+                    .uuid
+                    .clone()
+                    .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+                "version": manifest_version_numbers(module.version.as_ref()),
+                "description": module.description.clone().unwrap_or_default(),
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "format_version": 2,
+        "header": {
+            "name": header.and_then(|header| header.name.clone()).unwrap_or_default(),
+            "description": header
+                .and_then(|header| header.description.clone())
+                .unwrap_or_default(),
+            "uuid": header.and_then(|header| header.uuid.clone()),
+            "version": manifest_version_numbers(header.and_then(|header| header.version.as_ref())),
+            "min_engine_version": UPGRADED_MIN_ENGINE_VERSION,
+        },
+        "modules": modules,
+    })
+}
+
+/// 把包目录中的 v1 旧版清单改写为 format_version 2，原文件保留为 `manifest.v1.json.bak`；
+/// 非旧版清单时返回 false
+fn upgrade_legacy_manifest(pack_dir: &Path) -> Result<bool> {
+    let manifest_path = pack_dir.join("manifest.json");
+    let content = fs::read_to_string(&manifest_path)?;
+    let clean = strip_json_comments(content.trim_start_matches('\u{feff}'));
+    let (manifest, legacy) = parse_manifest_str(&clean)?;
+    if !legacy {
+        return Ok(false);
+    }
+    fs::write(pack_dir.join(LEGACY_MANIFEST_BACKUP_NAME), &content)?;
+    fs::write(
+        &manifest_path,
+        serde_json::to_string_pretty(&upgraded_manifest_json(&manifest))?,
+    )?;
+    Ok(true)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackInstallMetadata {
+    /// ISO 8601 格式的安装时间
+    pub installed_at: String,
+    pub source_file: Option<String>,
+    pub source_size: Option<u64>,
+    pub launcher_version: String,
+}
+
+/// 在包目录写入 `.bmcbl_install.json`，记录安装时间与来源文件
+pub fn write_pack_install_metadata(
+    pack_dir: &Path,
+    source_path: Option<&Path>,
+) -> Result<(), anyhow::Error> {
+    let metadata = PackInstallMetadata {
+        installed_at: chrono::Local::now().to_rfc3339(),
+        source_file: source_path.map(|path| path.to_string_lossy().into_owned()),
+        source_size: source_path
+            .and_then(|path| fs::metadata(path).ok())
+            .map(|metadata| metadata.len()),
+        launcher_version: env!("CARGO_PKG_VERSION").to_string(),
+    };
+    fs::write(
+        pack_dir.join(PACK_INSTALL_METADATA_NAME),
+        serde_json::to_string_pretty(&metadata)?,
+    )?;
+    Ok(())
+}
+
+/// 安装信息只是附加记录，写入失败不影响导入结果
+fn record_pack_install(pack_dir: &Path, source_path: Option<&Path>) {
+    if let Err(e) = write_pack_install_metadata(pack_dir, source_path) {
+        warn!("写入包安装信息失败 {:?}: {:?}", pack_dir, e);
+    }
+}
+
+/// 读取包目录中的安装信息，导入功能加入前安装的包没有这个文件，返回 `None`
+pub fn get_pack_install_date(pack_dir: String) -> Result<Option<PackInstallMetadata>, String> {
+    let path = Path::new(&pack_dir).join(PACK_INSTALL_METADATA_NAME);
+    let raw = match fs::read_to_string(&path) {
+        Ok(raw) => raw,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("读取安装信息失败 {}: {}", path.display(), e)),
+    };
+    serde_json::from_str(&raw)
+        .map(Some)
+        .map_err(|e| format!("解析安装信息失败 {}: {}", path.display(), e))
+}
+
+/// 配置项 `import.upgrade_legacy_manifests` 开启时升级导入后的旧版清单，失败不影响导入结果
+fn maybe_upgrade_legacy_manifest(pack_dir: &Path) {
+    let enabled = crate::config::config::read_config()
+        .map(|config| config.import.upgrade_legacy_manifests)
+        .unwrap_or(false);
+    if !enabled || !pack_dir.join("manifest.json").is_file() {
+        return;
+    }
+    match upgrade_legacy_manifest(pack_dir) {
+        Ok(true) => info!("已将旧版清单升级为 format_version 2: {:?}", pack_dir),
+        Ok(false) => {}
+        Err(e) => warn!("升级旧版清单失败 {:?}: {:?}", pack_dir, e),
+    }
+}
+
+pub(crate) fn version_to_string(version: &ManifestVersion) -> String {
     match version {
         ManifestVersion::Numbers(numbers) => numbers
             .iter()
@@ -292,7 +661,7 @@ fn parse_world_pack_reference_version(value: &serde_json::Value) -> Option<Strin
     }
 }
 
-fn parse_world_pack_references_from_text(
+pub(crate) fn parse_world_pack_references_from_text(
     content: &str,
     file_name: &str,
 ) -> Vec<WorldPackReference> {
@@ -361,7 +730,12 @@ fn world_pack_references_from_dir(dir: &Path) -> Vec<WorldPackReference> {
 }
 
 // --- [核心功能] 检查包信息 (带 i18n 支持) ---
-pub fn inspect_archive(path: &Path, preferred_lang: Option<&str>) -> Result<PackagePreview> {
+pub fn inspect_archive(
+    path: &Path,
+    preferred_lang: Option<&str>,
+    include_raw_manifest: bool,
+) -> Result<PackagePreview> {
+    ensure_zip_archive(path).map_err(anyhow::Error::msg)?;
     let file = File::open(path)?;
     let file_size = file.metadata()?.len();
     let mut archive = ZipArchive::new(file)?;
@@ -374,12 +748,15 @@ pub fn inspect_archive(path: &Path, preferred_lang: Option<&str>) -> Result<Pack
     let mut icon = None;
     let mut version_str = None;
     let mut manifest_data = None;
+    let mut raw_manifest_json = None;
     let mut sub_packs = Vec::new();
     let mut world_pack_references = Vec::new();
     let mut valid = true;
     let mut invalid_reason: Option<String> = None;
+    let mut legacy_manifest = false;
     let mut effective_type = target_type.clone();
     let mut template_pack: Option<PackEntry> = None;
+    let mut totals = scan.totals;
 
     if target_type == ImportTargetType::Compound {
         if let Some(p) = resolve_world_template_primary(&scan) {
@@ -459,10 +836,17 @@ pub fn inspect_archive(path: &Path, preferred_lang: Option<&str>) -> Result<Pack
             }
         } else {
             // 激进路径：直接读取嵌套 zip 的 manifest/icon（避免落盘解压）
-            if let Ok(mut fast_subs) = inspect_nested_archives_quick(&mut archive, preferred_lang) {
-                if !fast_subs.is_empty() {
-                    sub_packs.append(&mut fast_subs);
+            match inspect_nested_archives_quick(&mut archive, preferred_lang) {
+                Ok((mut fast_subs, nested_totals)) => {
+                    // 嵌套包按包内文件计入，替换掉嵌套包文件本身
+                    totals = totals.zip(nested_totals).map(|(outer, nested)| {
+                        outer.without(scan.nested_archive_totals).merge(nested)
+                    });
+                    if !fast_subs.is_empty() {
+                        sub_packs.append(&mut fast_subs);
+                    }
                 }
+                Err(_) => totals = None,
             }
 
             if sub_packs.is_empty() {
@@ -478,6 +862,24 @@ pub fn inspect_archive(path: &Path, preferred_lang: Option<&str>) -> Result<Pack
                         }
                     };
 
+                // 按展开后的子包目录合计，子包目录内嵌套的包目录不重复统计
+                totals = Some(
+                    pack_dirs
+                        .iter()
+                        .filter(|dir| {
+                            !pack_dirs
+                                .iter()
+                                .any(|other| other != *dir && dir.starts_with(other))
+                        })
+                        .map(|dir| dir_usage(dir))
+                        .fold(ArchiveTotals::default(), |sum, usage| {
+                            sum.merge(ArchiveTotals {
+                                file_count: usage.entries,
+                                uncompressed_size: usage.bytes,
+                            })
+                        }),
+                );
+
                 // 从“目录包”直接生成预览（不再打开子 Zip）
                 for dir in pack_dirs {
                     if dir.join("manifest.json").is_file() {
@@ -565,17 +967,18 @@ pub fn inspect_archive(path: &Path, preferred_lang: Option<&str>) -> Result<Pack
 
         if manifest_content.is_none() {
             // 先尝试根目录
-            if archive.by_name("manifest.json").is_ok() {
+            if find_entry_index(&mut archive, "manifest.json").is_some() {
                 root_prefix = "".to_string();
                 if let Some(s) = read_zip_text_by_name(&mut archive, "manifest.json") {
                     manifest_content = Some(s);
                 }
             } else {
                 // 尝试遍历查找第一层子目录下的 manifest
+                let names = EntryNameDecoder::default();
                 for i in 0..archive.len() {
                     // 借用检查修复：直接获取 file，不提前 drop
                     if let Ok(mut file) = archive.by_index(i) {
-                        let fname = file.name()?.to_string();
+                        let fname = names.entry_name(&file)?;
                         if fname.ends_with("/manifest.json") || fname.ends_with("\\manifest.json") {
                             if let Some(parent) = Path::new(&fname).parent() {
                                 // 统一转为 Unix 风格路径后缀
@@ -601,7 +1004,13 @@ pub fn inspect_archive(path: &Path, preferred_lang: Option<&str>) -> Result<Pack
         // C. 解析 Manifest 并应用翻译
         if let Some(content) = manifest_content {
             let clean = strip_json_comments(content.trim_start_matches('\u{feff}'));
-            if let Ok(mut manifest) = serde_json::from_str::<PartialManifest>(&clean) {
+            if include_raw_manifest {
+                raw_manifest_json = serde_json::from_str::<serde_json::Value>(&clean)
+                    .and_then(|value| serde_json::to_string_pretty(&value))
+                    .ok();
+            }
+            if let Ok((mut manifest, legacy)) = parse_manifest_str(&clean) {
+                legacy_manifest = legacy;
                 // 应用翻译
                 if let Some(header) = manifest.header.as_mut() {
                     if let Some(n) = &header.name {
@@ -654,7 +1063,7 @@ pub fn inspect_archive(path: &Path, preferred_lang: Option<&str>) -> Result<Pack
 
             // [修复] 同样拆分 if let ... else if let 逻辑
             {
-                if let Ok(mut f) = archive.by_name(&target_path) {
+                if let Ok(mut f) = by_entry_name(&mut archive, &target_path) {
                     let mut buf = Vec::new();
                     if f.read_to_end(&mut buf).is_ok() {
                         icon_buf = Some(buf);
@@ -667,7 +1076,7 @@ pub fn inspect_archive(path: &Path, preferred_lang: Option<&str>) -> Result<Pack
             }
 
             {
-                if let Ok(mut f) = archive.by_name(&alt_path) {
+                if let Ok(mut f) = by_entry_name(&mut archive, &alt_path) {
                     let mut buf = Vec::new();
                     if f.read_to_end(&mut buf).is_ok() {
                         icon_buf = Some(buf);
@@ -739,7 +1148,7 @@ pub fn inspect_archive(path: &Path, preferred_lang: Option<&str>) -> Result<Pack
     }
 
     Ok(PackagePreview {
-        name,
+        name: clean_display_name(&name),
         description,
         icon,
         kind: if effective_type == ImportTargetType::Compound && !sub_packs.is_empty() {
@@ -755,7 +1164,10 @@ pub fn inspect_archive(path: &Path, preferred_lang: Option<&str>) -> Result<Pack
         },
         version: version_str,
         size: file_size,
+        file_count: totals.map(|totals| totals.file_count),
+        uncompressed_size: totals.map(|totals| totals.uncompressed_size),
         manifest: manifest_data,
+        raw_manifest_json,
         sub_packs: if sub_packs.is_empty() {
             None
         } else {
@@ -768,11 +1180,13 @@ pub fn inspect_archive(path: &Path, preferred_lang: Option<&str>) -> Result<Pack
         },
         valid,
         invalid_reason,
+        legacy_manifest,
     })
 }
 
 // [新增] 检查导入冲突
 pub fn check_import_file(file_path: &Path, options: &GamePathOptions) -> Result<ImportCheckResult> {
+    ensure_zip_archive(file_path).map_err(anyhow::Error::msg)?;
     let file = File::open(file_path)?;
     let mut archive = ZipArchive::new(file)?;
     let (target_type, internal_name, pack_uuid, scan) = analyze_archive(&mut archive, file_path)?;
@@ -784,6 +1198,8 @@ pub fn check_import_file(file_path: &Path, options: &GamePathOptions) -> Result<
             target_name: internal_name,
             message: "Compound file".into(),
             existing_pack_info: None,
+            version_comparison: None,
+            existing_pack_path: None,
         });
     }
 
@@ -794,6 +1210,8 @@ pub fn check_import_file(file_path: &Path, options: &GamePathOptions) -> Result<
             target_name: internal_name,
             message: "Unknown type".into(),
             existing_pack_info: None,
+            version_comparison: None,
+            existing_pack_path: None,
         });
     }
 
@@ -822,6 +1240,8 @@ pub fn check_import_file(file_path: &Path, options: &GamePathOptions) -> Result<
                     target_name: internal_name,
                     message: "目标目录不存在，是否导入到 Shared？".into(),
                     existing_pack_info: None,
+                    version_comparison: None,
+                    existing_pack_path: None,
                 });
             }
             return Err(anyhow::anyhow!("Target dir not found"));
@@ -838,6 +1258,8 @@ pub fn check_import_file(file_path: &Path, options: &GamePathOptions) -> Result<
             target_name: internal_name,
             message: "目标目录不存在，是否导入到 Shared？".into(),
             existing_pack_info: None,
+            version_comparison: None,
+            existing_pack_path: None,
         });
     }
 
@@ -856,6 +1278,8 @@ pub fn check_import_file(file_path: &Path, options: &GamePathOptions) -> Result<
                 target_name: current_name,
                 message: "New import".into(),
                 existing_pack_info: None,
+                version_comparison: None,
+                existing_pack_path: None,
             });
         }
 
@@ -868,6 +1292,14 @@ pub fn check_import_file(file_path: &Path, options: &GamePathOptions) -> Result<
                         .as_ref()
                         .map(|p| p.name.clone())
                         .unwrap_or(current_name);
+                    let incoming_version = pack_entry_for_uuid(&scan, new_uuid)
+                        .and_then(|pack| manifest_header_version(&pack.manifest));
+                    let existing_version = existing_pack_info
+                        .as_ref()
+                        .and_then(|info| info.manifest.as_ref())
+                        .and_then(manifest_header_version);
+                    let version_comparison =
+                        compare_pack_versions(incoming_version, existing_version);
 
                     return Ok(ImportCheckResult {
                         has_conflict: true,
@@ -875,6 +1307,8 @@ pub fn check_import_file(file_path: &Path, options: &GamePathOptions) -> Result<
                         target_name,
                         message: "Existing pack with same UUID found".into(),
                         existing_pack_info,
+                        version_comparison,
+                        existing_pack_path: Some(current_dest.to_string_lossy().into_owned()),
                     });
                 }
             }
@@ -891,13 +1325,239 @@ pub fn check_import_file(file_path: &Path, options: &GamePathOptions) -> Result<
                 target_name: current_name,
                 message: "Renamed".into(),
                 existing_pack_info: None,
+                version_comparison: None,
+                existing_pack_path: None,
             });
         }
     }
 }
 
+const PACK_DIFF_LIST_LIMIT: usize = 200;
+const PACK_DIFF_ROOT_CATEGORY: &str = "root";
+
+fn pack_entry_for_uuid<'a>(scan: &'a ArchiveScanResult, uuid: &str) -> Option<&'a PackEntry> {
+    scan.packs.iter().find(|pack| {
+        pack.manifest
+            .header
+            .as_ref()
+            .and_then(|header| header.uuid.as_deref())
+            == Some(uuid)
+    })
+}
+
+pub(crate) fn manifest_header_version(manifest: &PartialManifest) -> Option<&ManifestVersion> {
+    manifest.header.as_ref()?.version.as_ref()
+}
+
+/// 版本向量：`[1, 2, 0]` 或 `"1.2.0"`，无法解析的段按 0 处理
+pub(crate) fn version_vector(version: &ManifestVersion) -> Vec<u32> {
+    match version {
+        ManifestVersion::Numbers(numbers) => numbers.clone(),
+        ManifestVersion::String(version) => version
+            .split(['.', '-', '+'])
+            .map(|part| part.trim().parse().unwrap_or(0))
+            .collect(),
+    }
+}
+
+pub(crate) fn compare_version_vectors(incoming: &[u32], existing: &[u32]) -> VersionComparison {
+    let length = incoming.len().max(existing.len());
+    for index in 0..length {
+        let left = incoming.get(index).copied().unwrap_or(0);
+        let right = existing.get(index).copied().unwrap_or(0);
+        match left.cmp(&right) {
+            std::cmp::Ordering::Greater => return VersionComparison::Newer,
+            std::cmp::Ordering::Less => return VersionComparison::Older,
+            std::cmp::Ordering::Equal => {}
+        }
+    }
+    VersionComparison::Same
+}
+
+pub(crate) fn compare_pack_versions(
+    incoming: Option<&ManifestVersion>,
+    existing: Option<&ManifestVersion>,
+) -> Option<PackVersionComparison> {
+    let (incoming, existing) = (incoming?, existing?);
+    Some(PackVersionComparison {
+        incoming_version: Some(version_to_string(incoming)),
+        existing_version: Some(version_to_string(existing)),
+        comparison: compare_version_vectors(&version_vector(incoming), &version_vector(existing)),
+    })
+}
+
+/// 对比导入包与已安装包的文件列表（按大小判断是否变更），用于冲突提示
+pub fn diff_pack_contents(
+    archive_path: &str,
+    existing_pack_path: &str,
+) -> Result<PackContentDiff, String> {
+    let archive_path = Path::new(archive_path);
+    let existing_dir = Path::new(existing_pack_path);
+    if !existing_dir.is_dir() {
+        return Err(format!("已安装的包不存在: {}", existing_dir.display()));
+    }
+
+    ensure_zip_archive(archive_path)?;
+    let file = File::open(archive_path).map_err(|e| format!("打开导入文件失败: {e}"))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| format!("读取导入文件失败: {e}"))?;
+    let scan = scan_archive(&mut archive).map_err(|e| e.to_string())?;
+    let existing_uuid = get_pack_uuid_from_dir(existing_dir);
+    let incoming_pack = existing_uuid
+        .as_deref()
+        .and_then(|uuid| pack_entry_for_uuid(&scan, uuid))
+        .or_else(|| scan.packs.first());
+    let pack_root = incoming_pack
+        .map(|pack| pack.root.clone())
+        .unwrap_or_default();
+
+    let mut incoming_files = HashMap::new();
+    let names = EntryNameDecoder::default();
+    for index in 0..archive.len() {
+        let entry = archive
+            .by_index_raw(index)
+            .map_err(|e| format!("读取导入文件条目失败: {e}"))?;
+        if entry.is_dir() {
+            continue;
+        }
+        let name = names
+            .entry_name(&entry)
+            .map_err(|e| e.to_string())?
+            .replace('\\', "/");
+        if name.contains("__MACOSX") {
+            continue;
+        }
+        if let Some(relative) = name.strip_prefix(pack_root.as_str())
+            && !relative.is_empty()
+        {
+            incoming_files.insert(relative.to_string(), entry.size());
+        }
+    }
+
+    let existing_files = pack_dir_file_sizes(existing_dir);
+    let mut diff = build_pack_content_diff(&incoming_files, &existing_files);
+    let existing_manifest = read_manifest_from_dir(existing_dir);
+    let existing_version = existing_manifest.as_ref().and_then(manifest_header_version);
+    let incoming_version = incoming_pack.and_then(|pack| manifest_header_version(&pack.manifest));
+    diff.incoming_version = incoming_version.map(version_to_string);
+    diff.existing_version = existing_version.map(version_to_string);
+    Ok(diff)
+}
+
+/// 包目录内所有文件的相对路径（`/` 分隔）与大小
+pub(crate) fn pack_dir_file_sizes(dir: &Path) -> HashMap<String, u64> {
+    let mut files = HashMap::new();
+    for entry in WalkDir::new(dir)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+    {
+        // 启动器写入的安装信息不属于包内容
+        if entry.file_name() == PACK_INSTALL_METADATA_NAME {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(dir) else {
+            continue;
+        };
+        let size = entry.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+        files.insert(relative.to_string_lossy().replace('\\', "/"), size);
+    }
+    files
+}
+
+pub(crate) fn read_manifest_from_dir(dir: &Path) -> Option<PartialManifest> {
+    let raw = fs::read_to_string(dir.join("manifest.json")).ok()?;
+    parse_manifest_str(&strip_json_comments(raw.trim_start_matches('\u{feff}')))
+        .ok()
+        .map(|(manifest, _)| manifest)
+}
+
+/// 按路径对比两侧文件；值（大小或内容指纹）不同即算修改
+pub(crate) fn build_pack_content_diff<V: PartialEq>(
+    incoming: &HashMap<String, V>,
+    existing: &HashMap<String, V>,
+) -> PackContentDiff {
+    let mut added: Vec<&String> = incoming
+        .keys()
+        .filter(|path| !existing.contains_key(*path))
+        .collect();
+    let mut removed: Vec<&String> = existing
+        .keys()
+        .filter(|path| !incoming.contains_key(*path))
+        .collect();
+    let mut changed: Vec<&String> = incoming
+        .iter()
+        .filter(|(path, value)| {
+            existing
+                .get(*path)
+                .is_some_and(|existing| existing != *value)
+        })
+        .map(|(path, _)| path)
+        .collect();
+    added.sort();
+    removed.sort();
+    changed.sort();
+
+    let mut categories: Vec<PackDiffCategory> = Vec::new();
+    for path in &added {
+        diff_category(&mut categories, path).added += 1;
+    }
+    for path in &removed {
+        diff_category(&mut categories, path).removed += 1;
+    }
+    for path in &changed {
+        diff_category(&mut categories, path).changed += 1;
+    }
+    categories.sort_by(|left, right| left.name.cmp(&right.name));
+
+    let truncated = [added.len(), removed.len(), changed.len()]
+        .iter()
+        .any(|count| *count > PACK_DIFF_LIST_LIMIT);
+    let limited = |paths: &[&String]| -> Vec<String> {
+        paths
+            .iter()
+            .take(PACK_DIFF_LIST_LIMIT)
+            .map(|path| (*path).clone())
+            .collect()
+    };
+
+    PackContentDiff {
+        added_count: added.len(),
+        removed_count: removed.len(),
+        changed_count: changed.len(),
+        added: limited(&added),
+        removed: limited(&removed),
+        changed: limited(&changed),
+        truncated,
+        categories,
+        ..Default::default()
+    }
+}
+
+/// 按包内顶层目录（textures、scripts 等）归类
+fn diff_category<'a>(
+    categories: &'a mut Vec<PackDiffCategory>,
+    path: &str,
+) -> &'a mut PackDiffCategory {
+    let name = match path.split_once('/') {
+        Some((top, _)) => top,
+        None => PACK_DIFF_ROOT_CATEGORY,
+    };
+    let index = match categories.iter().position(|category| category.name == name) {
+        Some(index) => index,
+        None => {
+            categories.push(PackDiffCategory {
+                name: name.to_string(),
+                ..Default::default()
+            });
+            categories.len() - 1
+        }
+    };
+    &mut categories[index]
+}
+
 // 简单的 .lang 文件解析器 (key=value)
-fn parse_lang_config(content: &str) -> HashMap<String, String> {
+pub(crate) fn parse_lang_config(content: &str) -> HashMap<String, String> {
     let mut map = HashMap::new();
     for line in content.lines() {
         // [关键修改] 先按 # 分割，只取前部分，用于屏蔽行尾注释 (如 "key=val #comment")
@@ -972,12 +1632,17 @@ fn filter_level_roots_excluding_template_internal(
 
 // --- 以下是原有的导入逻辑 (基本保持不变) ---
 
+/// `task_id` 不为空时，复合包每导入一个子包推进一次该任务的进度；任务被取消后在下一个子包前停止，
+/// 已写入的包保留在原处
 pub fn import_files_batch(
     files: Vec<String>,
     options: &GamePathOptions,
     overwrite: bool, // [新增] 覆盖选项
+    task_id: Option<String>,
 ) -> Result<(usize, usize)> {
     // (success_count, fail_count)
+    // 复合包会先解压到数据目录下的缓存，不可写时整批提前失败
+    crate::utils::data_root::ensure_data_root_writable().map_err(anyhow::Error::msg)?;
     let mut success = 0;
     let mut fail = 0;
 
@@ -992,7 +1657,12 @@ pub fn import_files_batch(
         overwrite
     );
 
+    let task_id = task_id.as_deref();
     for file_path in files {
+        if task_id.is_some_and(is_cancelled) {
+            info!("Import batch cancelled before {}", file_path);
+            break;
+        }
         let path = PathBuf::from(&file_path);
         if !path.exists() {
             warn!("Import skipped (file not found): {}", file_path);
@@ -1000,11 +1670,21 @@ pub fn import_files_batch(
             continue;
         }
 
-        match process_single_archive(&path, options, overwrite) {
-            Ok(_) => {
+        match process_single_archive(&path, options, overwrite, task_id) {
+            Ok(imported) => {
                 debug!("Import success: {}", file_path);
-                success += 1;
+                // 取消时只导入了部分子包，不计为成功，但已写入的包仍然登记
+                if !task_id.is_some_and(is_cancelled) {
+                    success += 1;
+                }
                 cleanup_compound_cache_for_file(&path);
+                // 只有导入成功后才登记，失败的导入不会留下记录
+                if let Err(e) = import_history::record_imports(&path, options, &imported) {
+                    warn!("Failed to record import history for {}: {}", file_path, e);
+                }
+            }
+            Err(e) if task_id.is_some_and(is_cancelled) => {
+                info!("Import cancelled during {}: {}", file_path, e);
             }
             Err(e) => {
                 error!("Failed to import {}: {:?}", file_path, e);
@@ -1020,7 +1700,9 @@ fn process_single_archive(
     file_path: &Path,
     options: &GamePathOptions,
     overwrite: bool,
-) -> Result<()> {
+    task_id: Option<&str>,
+) -> Result<Vec<ImportedItem>> {
+    ensure_zip_archive(file_path).map_err(anyhow::Error::msg)?;
     let file = File::open(file_path)?;
     let mut archive = ZipArchive::new(file)?;
 
@@ -1032,7 +1714,7 @@ fn process_single_archive(
 
     if target_type == ImportTargetType::Compound {
         info!("Detected compound archive: {:?}", file_path);
-        return process_compound_archive(&mut archive, file_path, options, overwrite);
+        return process_compound_archive(&mut archive, file_path, options, overwrite, task_id);
     }
 
     if let ImportTargetType::Unknown = target_type {
@@ -1041,6 +1723,20 @@ fn process_single_archive(
         ));
     }
 
+    // 存档 / 模板内含嵌套包时需要先展开到缓存，否则直接流式解压到目标目录
+    if scan.has_nested_archive
+        && matches!(
+            target_type,
+            ImportTargetType::World | ImportTargetType::WorldTemplate
+        )
+    {
+        info!(
+            "Nested archives found, falling back to cache import: {:?}",
+            file_path
+        );
+        return process_compound_archive(&mut archive, file_path, options, overwrite, task_id);
+    }
+
     if !matches!(target_type, ImportTargetType::World) && pack_uuid.is_none() {
         return Err(anyhow::anyhow!("manifest.header.uuid 缺失，无法导入"));
     }
@@ -1106,8 +1802,137 @@ fn process_single_archive(
         target_type, target_dir_name, final_dest
     );
     info!("Importing {:?} to {:?}", target_type, final_dest);
-    extract_archive_parallel(file_path, &final_dest)?;
+    ensure_free_space(&[(parent_dir.as_path(), uncompressed_size(&mut archive))])?;
+    let overwrote = final_dest.exists();
+    extract_archive_streaming(file_path, &final_dest, task_id)?;
+    if target_type != ImportTargetType::World {
+        maybe_upgrade_legacy_manifest(&final_dest);
+        record_pack_install(&final_dest, Some(file_path));
+    }
+
+    let pack_version = pack_uuid
+        .as_deref()
+        .and_then(|uuid| pack_entry_for_uuid(&scan, uuid))
+        .and_then(|pack| manifest_header_version(&pack.manifest))
+        .map(version_to_string);
+    Ok(vec![ImportedItem {
+        target_type,
+        destination: final_dest,
+        pack_uuid,
+        pack_version,
+        overwrote,
+    }])
+}
+
+fn sibling_with_suffix(path: &Path, suffix: &str) -> Result<PathBuf> {
+    let name = path
+        .file_name()
+        .map(|name| format!("{}{suffix}", name.to_string_lossy()))
+        .ok_or_else(|| anyhow::anyhow!("无效的导入目标: {:?}", path))?;
+    Ok(path.with_file_name(name))
+}
+
+/// 用 `staging` 替换 `final_dest`：旧目录先改名为 `.bak`，新目录就位后再删除；
+/// 任何一步失败都把旧目录改回原名，不会出现旧的删了一半、新的也没放上的情况
+fn replace_with_staging(staging: &Path, final_dest: &Path) -> Result<()> {
+    let backup = sibling_with_suffix(final_dest, REPLACED_SUFFIX)?;
+    if backup.exists() {
+        // 上次替换中断留下的旧目录：目标不在时它就是原来的内容
+        if final_dest.exists() {
+            fs::remove_dir_all(&backup)
+                .with_context(|| format!("清理残留的旧目录备份失败: {:?}", backup))?;
+        } else {
+            file_locks::rename(&backup, final_dest)
+                .with_context(|| format!("恢复旧目录失败: {:?} -> {:?}", backup, final_dest))?;
+        }
+    }
+
+    let replacing = final_dest.exists();
+    if replacing {
+        file_locks::rename(final_dest, &backup)
+            .with_context(|| format!("覆盖前备份旧目录失败: {:?}", final_dest))?;
+    }
+    if let Err(error) = file_locks::rename(staging, final_dest) {
+        if replacing && let Err(restore_error) = file_locks::rename(&backup, final_dest) {
+            warn!(
+                "Failed to restore {:?} from {:?}: {restore_error}",
+                final_dest, backup
+            );
+        }
+        return Err(error)
+            .with_context(|| format!("重命名导入目录失败: {:?} -> {:?}", staging, final_dest));
+    }
+    if replacing && let Err(error) = fs::remove_dir_all(&backup) {
+        warn!("Failed to remove replaced import dir {:?}: {error}", backup);
+    }
+    Ok(())
+}
+
+/// 直接从外层 zip 解压到目标旁的 `.importing` 临时目录，成功后替换最终目录；
+/// 失败或任务被取消时删除临时目录，不会留下半个存档
+fn extract_archive_streaming(
+    file_path: &Path,
+    final_dest: &Path,
+    task_id: Option<&str>,
+) -> Result<()> {
+    let staging = sibling_with_suffix(final_dest, IMPORTING_SUFFIX)?;
+    if staging.exists() {
+        // 上次导入中断留下的临时目录
+        fs::remove_dir_all(&staging)
+            .with_context(|| format!("清理残留的导入临时目录失败: {:?}", staging))?;
+    }
+
+    let result = extract_archive_parallel(file_path, &staging, task_id)
+        .and_then(|()| replace_with_staging(&staging, final_dest));
+    if result.is_err()
+        && staging.exists()
+        && let Err(error) = fs::remove_dir_all(&staging)
+    {
+        warn!("Failed to remove import staging dir {:?}: {error}", staging);
+    }
+    result
+}
+
+fn uncompressed_size<R: Read + Seek>(archive: &mut ZipArchive<R>) -> u64 {
+    (0..archive.len())
+        .filter_map(|index| archive.by_index_raw(index).ok().map(|file| file.size()))
+        .fold(0u64, u64::saturating_add)
+}
+
+/// 导入前检查磁盘剩余空间；同一磁盘上的多项需求会累加。无法确定所在磁盘时跳过检查
+pub(crate) fn ensure_free_space(requirements: &[(&Path, u64)]) -> Result<()> {
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    let mut required_by_disk: HashMap<PathBuf, (u64, u64)> = HashMap::new();
+    for (path, bytes) in requirements {
+        let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+        let Some(disk) = disks
+            .list()
+            .iter()
+            .filter(|disk| absolute.starts_with(disk.mount_point()))
+            .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        else {
+            debug!(
+                "Free space check skipped, disk not found for {:?}",
+                absolute
+            );
+            continue;
+        };
+        let entry = required_by_disk
+            .entry(disk.mount_point().to_path_buf())
+            .or_insert((0, disk.available_space()));
+        entry.0 = entry.0.saturating_add(*bytes);
+    }
 
+    for (mount_point, (required, available)) in required_by_disk {
+        if required > available {
+            return Err(anyhow::anyhow!(
+                "磁盘空间不足：{:?} 需要 {} MB，剩余 {} MB",
+                mount_point,
+                required / 1024 / 1024,
+                available / 1024 / 1024
+            ));
+        }
+    }
     Ok(())
 }
 
@@ -1121,14 +1946,22 @@ pub fn scan_archive<R: Read + Seek>(archive: &mut ZipArchive<R>) -> Result<Archi
 
     let mut hits: Vec<ScanHit> = Vec::new();
     let mut has_nested_archive = false;
+    let mut totals = Some(ArchiveTotals::default());
+    let mut nested_archive_totals = ArchiveTotals::default();
 
+    let names = EntryNameDecoder::default();
     for i in 0..archive.len() {
         // 顺序读取每个文件条目（避免并行 mutable borrow）
         if let Ok(mut file) = archive.by_index(i) {
-            let name = file.name()?.to_string();
+            let name = names.entry_name(&file)?;
             if name.contains("__MACOSX") {
                 continue;
             }
+            if !file.is_dir()
+                && let Some(totals) = totals.as_mut()
+            {
+                totals.add_file(file.size());
+            }
 
             let path = std::path::Path::new(&name);
             let root = path
@@ -1163,6 +1996,7 @@ pub fn scan_archive<R: Read + Seek>(archive: &mut ZipArchive<R>) -> Result<Archi
                         "mcpack" | "mcaddon" | "mcworld" | "mctemplate" | "zip"
                     ) {
                         has_nested_archive = true;
+                        nested_archive_totals.add_file(file.size());
                     }
                 }
                 continue;
@@ -1173,15 +2007,18 @@ pub fn scan_archive<R: Read + Seek>(archive: &mut ZipArchive<R>) -> Result<Archi
                 continue;
             }
             let clean = strip_json_comments(json.trim_start_matches('\u{feff}'));
-            if let Ok(manifest) = serde_json::from_str::<PartialManifest>(&clean) {
+            if let Ok((manifest, legacy_manifest)) = parse_manifest_str(&clean) {
                 let pack_type = detect_type_from_manifest(&manifest);
                 hits.push(ScanHit::Manifest(PackEntry {
                     root,
                     manifest_path: name,
                     manifest,
                     pack_type,
+                    legacy_manifest,
                 }));
             }
+        } else {
+            totals = None;
         }
     }
 
@@ -1208,6 +2045,8 @@ pub fn scan_archive<R: Read + Seek>(archive: &mut ZipArchive<R>) -> Result<Archi
         has_nested_archive,
         level_roots: level_roots.into_iter().collect(),
         packs,
+        totals,
+        nested_archive_totals,
     })
 }
 
@@ -1454,7 +2293,8 @@ fn process_compound_archive(
     original_file_path: &Path,
     options: &GamePathOptions,
     overwrite: bool,
-) -> Result<()> {
+    task_id: Option<&str>,
+) -> Result<Vec<ImportedItem>> {
     // 高性能策略：
     // 1) 优先复用 inspect 阶段生成的缓存目录（避免二次解压）。
     // 2) 若未命中缓存，则一次性解压外层 + 并行展开嵌套包到缓存目录。
@@ -1480,7 +2320,13 @@ fn process_compound_archive(
                 )?;
 
                 debug!("Compound import (cache): pack_dirs={}", pack_dirs.len());
-                let res = import_from_cache_dirs(&pack_dirs, options, overwrite);
+                let res = import_from_cache_dirs(
+                    &pack_dirs,
+                    options,
+                    overwrite,
+                    original_file_path,
+                    task_id,
+                );
                 if let Err(error) = fs::remove_dir_all(&work_dir) {
                     warn!(
                         "Failed to remove compound cache dir {:?}: {error}",
@@ -1492,7 +2338,15 @@ fn process_compound_archive(
         }
     }
 
-    // 2) 未命中缓存：自己展开一次
+    // 2) 未命中缓存：自己展开一次，缓存与最终目录各需要一份空间
+    let required = uncompressed_size(archive);
+    let cache_base = bmcbl_cache_base_dir();
+    let target_root = crate::core::minecraft::paths::get_game_root(options);
+    let mut requirements = vec![(cache_base.as_path(), required)];
+    if let Some(target_root) = target_root.as_deref() {
+        requirements.push((target_root, required));
+    }
+    ensure_free_space(&requirements)?;
     let (work_dir, pack_dirs) = extract_to_cache_with_nested(archive, "import")?;
     debug!(
         "Compound cache miss: {:?} -> {:?}, pack_dirs={}",
@@ -1500,7 +2354,7 @@ fn process_compound_archive(
         work_dir,
         pack_dirs.len()
     );
-    let res = import_from_cache_dirs(&pack_dirs, options, overwrite);
+    let res = import_from_cache_dirs(&pack_dirs, options, overwrite, original_file_path, task_id);
     if let Err(error) = fs::remove_dir_all(&work_dir) {
         warn!(
             "Failed to remove compound cache dir {:?}: {error}",
@@ -1511,15 +2365,18 @@ fn process_compound_archive(
 }
 
 fn extract_subdir_from_zip(archive: &mut ZipArchive<File>, root: &str, dest: &Path) -> Result<()> {
+    let names = EntryNameDecoder::default();
     for i in 0..archive.len() {
         let mut file = archive.by_index(i)?;
-        let name = file.name()?;
+        let name = names.entry_name(&file)?.replace('\\', "/");
 
         if !name.starts_with(root) || name.contains("__MACOSX") {
             continue;
         }
 
-        let rel = &name[root.len()..];
+        let Some(rel) = safe_relative_path(&name[root.len()..]) else {
+            continue;
+        };
         let target = dest.join(rel);
 
         if file.is_dir() {
@@ -1528,7 +2385,7 @@ fn extract_subdir_from_zip(archive: &mut ZipArchive<File>, root: &str, dest: &Pa
             if let Some(p) = target.parent() {
                 fs::create_dir_all(p)?;
             }
-            let mut out = std::io::BufWriter::new(File::create(&target)?);
+            let mut out = std::io::BufWriter::new(file_locks::create_file(&target)?);
             std::io::copy(&mut file, &mut out)?;
         }
     }
@@ -1536,31 +2393,21 @@ fn extract_subdir_from_zip(archive: &mut ZipArchive<File>, root: &str, dest: &Pa
 }
 
 fn extract_pack_root(archive: &mut ZipArchive<File>, pack_root: &str, dest: &Path) -> Result<()> {
+    let names = EntryNameDecoder::default();
     for i in 0..archive.len() {
         let mut file = archive.by_index(i)?;
-        let name = file.name()?;
+        let name = names.entry_name(&file)?.replace('\\', "/");
 
         if name.contains("__MACOSX") {
             continue;
         }
 
-        // World（root = ""）特殊处理
-        let relative = if pack_root.is_empty() {
-            Path::new(name.as_ref())
-        } else if let Some(stripped) = name.as_ref().strip_prefix(pack_root) {
-            Path::new(stripped)
-        } else {
+        // World（root = ""）时 strip_prefix 返回完整条目名
+        let Some(relative) = name.strip_prefix(pack_root).and_then(safe_relative_path) else {
             continue;
         };
 
-        if relative.as_os_str().is_empty() {
-            continue;
-        }
-
         let out_path = dest.join(relative);
-        if !out_path.starts_with(dest) {
-            continue;
-        }
 
         if file.is_dir() {
             std::fs::create_dir_all(&out_path)?;
@@ -1568,7 +2415,7 @@ fn extract_pack_root(archive: &mut ZipArchive<File>, pack_root: &str, dest: &Pat
             if let Some(p) = out_path.parent() {
                 std::fs::create_dir_all(p)?;
             }
-            let mut out = std::io::BufWriter::new(File::create(&out_path)?);
+            let mut out = std::io::BufWriter::new(file_locks::create_file(&out_path)?);
             std::io::copy(&mut file, &mut out)?;
         }
     }
@@ -1585,9 +2432,12 @@ fn extract_archive(archive: &mut ZipArchive<File>, dest_root: &Path) -> Result<(
     let mut has_files_at_root = false;
 
     // 第一次遍历：检测公共根目录
+    let names = EntryNameDecoder::default();
     for i in 0..archive.len() {
         let file = archive.by_index(i)?;
-        let path = PathBuf::from(file.name()?.as_ref());
+        let Some(path) = safe_relative_path(&names.entry_name(&file)?) else {
+            continue;
+        };
         if path.to_string_lossy().contains("__MACOSX") || file.is_dir() {
             continue;
         }
@@ -1620,7 +2470,9 @@ fn extract_archive(archive: &mut ZipArchive<File>, dest_root: &Path) -> Result<(
     // 第二次遍历：解压
     for i in 0..archive.len() {
         let mut file = archive.by_index(i)?;
-        let original_path = PathBuf::from(file.name()?.as_ref());
+        let Some(original_path) = safe_relative_path(&names.entry_name(&file)?) else {
+            continue;
+        };
         if original_path.to_string_lossy().contains("__MACOSX") {
             continue;
         }
@@ -1651,14 +2503,19 @@ fn extract_archive(archive: &mut ZipArchive<File>, dest_root: &Path) -> Result<(
                     fs::create_dir_all(p)?;
                 }
             }
-            let mut outfile = std::io::BufWriter::new(File::create(&target_path)?);
+            let mut outfile = std::io::BufWriter::new(file_locks::create_file(&target_path)?);
             std::io::copy(&mut file, &mut outfile)?;
         }
     }
     Ok(())
 }
 
-fn extract_archive_parallel(file_path: &Path, dest_root: &Path) -> Result<()> {
+/// `task_id` 对应的任务被取消后，各线程在下一个条目前停止并返回错误
+fn extract_archive_parallel(
+    file_path: &Path,
+    dest_root: &Path,
+    task_id: Option<&str>,
+) -> Result<()> {
     if !dest_root.exists() {
         fs::create_dir_all(dest_root)?;
     }
@@ -1672,9 +2529,13 @@ fn extract_archive_parallel(file_path: &Path, dest_root: &Path) -> Result<()> {
     let mut has_files_at_root = false;
     let mut entries: Vec<(usize, PathBuf, bool)> = Vec::with_capacity(archive.len());
 
+    let names = EntryNameDecoder::default();
     for i in 0..archive.len() {
         let file = archive.by_index(i)?;
-        let path = PathBuf::from(file.name()?.as_ref());
+        // 条目名按回退代码页解码，含非法字符的组件已逐个规范化
+        let Some(path) = safe_relative_path(&names.entry_name(&file)?) else {
+            continue;
+        };
         if path.to_string_lossy().contains("__MACOSX") {
             continue;
         }
@@ -1712,57 +2573,60 @@ fn extract_archive_parallel(file_path: &Path, dest_root: &Path) -> Result<()> {
     // 对于 .mctemplate 这种包含海量小文件的包，这个改动能显著加速。
     let common_root_cloned = common_root.clone();
     const CHUNK_SIZE: usize = 64;
+    // 工作线程沿用调用方的文件占用汇总范围
+    let lock_scope = file_locks::current_scope();
+
+    entries
+        .par_chunks(CHUNK_SIZE)
+        .try_for_each(|chunk| -> Result<()> {
+            lock_scope.run(|| {
+                let file = File::open(file_path)?;
+                let mut z = ZipArchive::new(file)?;
+
+                for (idx, original_path, is_dir) in chunk {
+                    if task_id.is_some_and(is_cancelled) {
+                        return Err(anyhow::anyhow!("导入已取消"));
+                    }
+                    if original_path.to_string_lossy().contains("__MACOSX") {
+                        continue;
+                    }
 
-    entries.par_chunks(CHUNK_SIZE).for_each(|chunk| {
-        let Ok(file) = File::open(file_path) else {
-            return;
-        };
-        let Ok(mut z) = ZipArchive::new(file) else {
-            return;
-        };
-
-        for (idx, original_path, is_dir) in chunk {
-            if original_path.to_string_lossy().contains("__MACOSX") {
-                continue;
-            }
-
-            let relative_path = if let Some(ref root) = common_root_cloned {
-                if let Ok(stripped) = original_path.strip_prefix(root) {
-                    stripped.to_path_buf()
-                } else {
-                    original_path.clone()
-                }
-            } else {
-                original_path.clone()
-            };
+                    let relative_path = if let Some(ref root) = common_root_cloned {
+                        if let Ok(stripped) = original_path.strip_prefix(root) {
+                            stripped.to_path_buf()
+                        } else {
+                            original_path.clone()
+                        }
+                    } else {
+                        original_path.clone()
+                    };
 
-            if relative_path.as_os_str().is_empty() {
-                continue;
-            }
-            let target_path = dest_root.join(&relative_path);
-            if !target_path.starts_with(dest_root) {
-                continue;
-            }
+                    if relative_path.as_os_str().is_empty() {
+                        continue;
+                    }
+                    let target_path = dest_root.join(&relative_path);
+                    if !target_path.starts_with(dest_root) {
+                        continue;
+                    }
 
-            if *is_dir {
-                let _ = fs::create_dir_all(&target_path);
-                continue;
-            }
+                    if *is_dir {
+                        fs::create_dir_all(&target_path)?;
+                        continue;
+                    }
 
-            if let Some(p) = target_path.parent() {
-                let _ = fs::create_dir_all(p);
-            }
+                    if let Some(p) = target_path.parent() {
+                        fs::create_dir_all(p)?;
+                    }
 
-            if let Ok(mut entry) = z.by_index(*idx) {
-                if let Ok(out) = std::fs::File::create(&target_path) {
-                    let mut out = std::io::BufWriter::new(out);
-                    let _ = std::io::copy(&mut entry, &mut out);
+                    let mut entry = z.by_index(*idx)?;
+                    let mut out = std::io::BufWriter::new(file_locks::create_file(&target_path)?);
+                    std::io::copy(&mut entry, &mut out)
+                        .with_context(|| format!("解压失败: {:?}", target_path))?;
+                    out.flush()?;
                 }
-            }
-        }
-    });
-
-    Ok(())
+                Ok(())
+            })
+        })
 }
 
 // ================================
@@ -1806,6 +2670,45 @@ fn cache_map() -> &'static std::sync::Mutex<std::collections::HashMap<String, Ca
     COMPOUND_CACHE.get_or_init(|| std::sync::Mutex::new(std::collections::HashMap::new()))
 }
 
+/// 当前缓存的复合包解压目录数量，供内存诊断使用
+pub(crate) fn compound_cache_entries() -> usize {
+    cache_map().lock().map(|map| map.len()).unwrap_or_default()
+}
+
+pub(crate) static CACHE: CacheDescriptor = CacheDescriptor {
+    name: "import_previews",
+    label: "导入预览解压目录",
+    default_selected: true,
+    usage: compound_cache_usage,
+    clear: clear_compound_cache,
+    warm: None,
+};
+
+fn compound_cache_usage() -> CacheUsage {
+    let map = cache_map().lock().unwrap_or_else(|e| e.into_inner());
+    CacheUsage {
+        entries: map.len() as u64,
+        bytes: map.values().map(|entry| dir_usage(&entry.dir).bytes).sum(),
+    }
+}
+
+fn clear_compound_cache() -> std::result::Result<CacheUsage, String> {
+    let entries: Vec<CacheEntry> = cache_map()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .drain()
+        .map(|(_, entry)| entry)
+        .collect();
+    let mut cleared = CacheUsage {
+        entries: entries.len() as u64,
+        bytes: 0,
+    };
+    for entry in entries {
+        cleared.bytes += clear_dir(&entry.dir)?.bytes;
+    }
+    Ok(cleared)
+}
+
 fn compound_cache_key(file_path: &Path) -> Result<String> {
     let meta = fs::metadata(file_path)?;
     let size = meta.len();
@@ -1988,25 +2891,53 @@ fn import_from_cache_dirs(
     pack_dirs: &[PathBuf],
     options: &GamePathOptions,
     overwrite: bool,
-) -> Result<()> {
-    let failures: Vec<String> = pack_dirs
+    source_path: &Path,
+    task_id: Option<&str>,
+) -> Result<Vec<ImportedItem>> {
+    if let Some(task_id) = task_id {
+        reset_progress(task_id, Some(pack_dirs.len() as u64), Some("importing"));
+    }
+    let results: Vec<Result<ImportedItem, String>> = pack_dirs
         .par_iter()
         .filter_map(|dir| {
+            // 已开始的子包照常写完，取消后不再开始新的子包
+            if task_id.is_some_and(is_cancelled) {
+                return None;
+            }
             let result = if dir.join("manifest.json").is_file() {
-                import_pack_dir(dir, options, overwrite)
+                import_pack_dir(dir, options, overwrite, Some(source_path))
             } else if dir.join("level.dat").is_file() {
                 import_world_dir(dir, options, overwrite)
             } else {
-                Ok(())
+                if let Some(task_id) = task_id {
+                    update_progress(task_id, 1, None, None);
+                }
+                return None;
             };
+            if let Some(task_id) = task_id {
+                set_task_message(
+                    task_id,
+                    dir.file_name()
+                        .map(|name| name.to_string_lossy().into_owned()),
+                );
+                update_progress(task_id, 1, None, None);
+            }
 
-            result.err().map(|error| {
+            Some(result.map_err(|error| {
                 warn!("Failed to import from cache dir {:?}: {:?}", dir, error);
                 format!("{}: {error}", dir.display())
-            })
+            }))
         })
         .collect();
 
+    let mut imported = Vec::with_capacity(results.len());
+    let mut failures = Vec::new();
+    for result in results {
+        match result {
+            Ok(item) => imported.push(item),
+            Err(failure) => failures.push(failure),
+        }
+    }
     if !failures.is_empty() {
         return Err(anyhow::anyhow!(
             "复合包中有 {} 个子包导入失败: {}",
@@ -2014,7 +2945,7 @@ fn import_from_cache_dirs(
             failures.join("; ")
         ));
     }
-    Ok(())
+    Ok(imported)
 }
 
 fn get_world_info_from_dir(dir: &Path) -> Result<PackagePreview> {
@@ -2083,7 +3014,7 @@ fn get_world_info_from_dir(dir: &Path) -> Result<PackagePreview> {
         break;
     }
 
-    let size = dir_size(dir).unwrap_or(0);
+    let size = file_ops::dir_size(dir).unwrap_or(0);
     let world_pack_references = world_pack_references_from_dir(dir);
     info!(
         "World preview summary: world_root={} icon_found={} icon_decode={}",
@@ -2101,21 +3032,29 @@ fn get_world_info_from_dir(dir: &Path) -> Result<PackagePreview> {
     );
 
     Ok(PackagePreview {
-        name,
+        name: clean_display_name(&name),
         description,
         icon,
         kind: ImportTargetType::World.to_display_name().to_string(),
         version: None,
         size,
+        file_count: None,
+        uncompressed_size: None,
         manifest: None,
+        raw_manifest_json: None,
         sub_packs: None,
         world_pack_references: (!world_pack_references.is_empty()).then_some(world_pack_references),
         valid: true,
         invalid_reason: None,
+        legacy_manifest: false,
     })
 }
 
-fn import_world_dir(dir: &Path, options: &GamePathOptions, overwrite: bool) -> Result<()> {
+fn import_world_dir(
+    dir: &Path,
+    options: &GamePathOptions,
+    overwrite: bool,
+) -> Result<ImportedItem> {
     let parent_dir = resolve_target_parent(options, ImportTargetType::World.to_dir_name(), false)
         .ok_or_else(|| anyhow::anyhow!("无法解析目标安装路径"))?;
 
@@ -2130,11 +3069,15 @@ fn import_world_dir(dir: &Path, options: &GamePathOptions, overwrite: bool) -> R
         kind: ImportTargetType::World.to_display_name().to_string(),
         version: None,
         size: 0,
+        file_count: None,
+        uncompressed_size: None,
         manifest: None,
+        raw_manifest_json: None,
         sub_packs: None,
         world_pack_references: None,
         valid: true,
         invalid_reason: None,
+        legacy_manifest: false,
     });
 
     let clean_name = sanitize_filename(&strip_minecraft_formatting(&preview.name));
@@ -2158,7 +3101,8 @@ fn import_world_dir(dir: &Path, options: &GamePathOptions, overwrite: bool) -> R
         }
     }
 
-    if final_dest.exists() {
+    let overwrote = final_dest.exists();
+    if overwrote {
         fs::remove_dir_all(&final_dest)
             .with_context(|| format!("Failed to remove existing world dir {:?}", final_dest))?;
     }
@@ -2167,26 +3111,13 @@ fn import_world_dir(dir: &Path, options: &GamePathOptions, overwrite: bool) -> R
     copy_dir_recursive(dir, &final_dest)
         .with_context(|| format!("Failed to copy world dir {:?} -> {:?}", dir, final_dest))?;
 
-    Ok(())
-}
-
-fn dir_size(path: &Path) -> Result<u64> {
-    let mut total = 0u64;
-    let mut stack = vec![path.to_path_buf()];
-
-    while let Some(p) = stack.pop() {
-        for entry in fs::read_dir(&p)? {
-            let entry = entry?;
-            let ep = entry.path();
-            let meta = entry.metadata()?;
-            if meta.is_dir() {
-                stack.push(ep);
-            } else if meta.is_file() {
-                total = total.saturating_add(meta.len());
-            }
-        }
-    }
-    Ok(total)
+    Ok(ImportedItem {
+        target_type: ImportTargetType::World,
+        destination: final_dest,
+        pack_uuid: None,
+        pack_version: None,
+        overwrote,
+    })
 }
 
 /// 递归收集（带剪枝与深度限制）：
@@ -2274,7 +3205,7 @@ fn collect_inner_archives_and_dirs(
     Ok(())
 }
 
-fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
+pub(crate) fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
     if !dst.exists() {
         fs::create_dir_all(dst)?;
     }
@@ -2295,7 +3226,12 @@ fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
     Ok(())
 }
 
-fn import_pack_dir(dir: &Path, options: &GamePathOptions, overwrite: bool) -> Result<()> {
+fn import_pack_dir(
+    dir: &Path,
+    options: &GamePathOptions,
+    overwrite: bool,
+    source_path: Option<&Path>,
+) -> Result<ImportedItem> {
     // 读取 manifest.json
     let manifest_path = dir.join("manifest.json");
     if !manifest_path.exists() {
@@ -2303,7 +3239,7 @@ fn import_pack_dir(dir: &Path, options: &GamePathOptions, overwrite: bool) -> Re
     }
     let content = fs::read_to_string(&manifest_path)?;
     let clean = strip_json_comments(content.trim_start_matches('\u{feff}'));
-    let manifest: PartialManifest = serde_json::from_str(&clean)
+    let (manifest, _) = parse_manifest_str(&clean)
         .with_context(|| format!("Failed to parse manifest.json in {:?}", dir))?;
 
     let target_type = detect_type_from_manifest(&manifest);
@@ -2382,7 +3318,8 @@ fn import_pack_dir(dir: &Path, options: &GamePathOptions, overwrite: bool) -> Re
         counter += 1;
     }
 
-    if final_dest.exists() {
+    let overwrote = final_dest.exists();
+    if overwrote {
         // overwrite 路径：先清掉旧目录再复制
         fs::remove_dir_all(&final_dest)
             .with_context(|| format!("Failed to remove existing dir {:?}", final_dest))?;
@@ -2391,8 +3328,16 @@ fn import_pack_dir(dir: &Path, options: &GamePathOptions, overwrite: bool) -> Re
     debug!("Import pack dir: {:?} -> {:?}", dir, final_dest);
     copy_dir_recursive(dir, &final_dest)
         .with_context(|| format!("Failed to copy {:?} -> {:?}", dir, final_dest))?;
+    maybe_upgrade_legacy_manifest(&final_dest);
+    record_pack_install(&final_dest, source_path);
 
-    Ok(())
+    Ok(ImportedItem {
+        target_type,
+        destination: final_dest,
+        pack_uuid,
+        pack_version: manifest_header_version(&manifest).map(version_to_string),
+        overwrote,
+    })
 }
 
 fn get_pack_uuid_from_dir(dir: &Path) -> Option<String> {
@@ -2402,11 +3347,11 @@ fn get_pack_uuid_from_dir(dir: &Path) -> Option<String> {
     }
     let content = fs::read_to_string(&manifest_path).ok()?;
     let clean = strip_json_comments(content.trim_start_matches('\u{feff}'));
-    let manifest: PartialManifest = serde_json::from_str(&clean).ok()?;
+    let (manifest, _) = parse_manifest_str(&clean).ok()?;
     manifest.header.and_then(|h| h.uuid)
 }
 
-fn get_pack_info_from_dir(
+pub(crate) fn get_pack_info_from_dir(
     dir: &Path,
     target_type: &ImportTargetType,
     preferred_lang: Option<&str>,
@@ -2414,7 +3359,7 @@ fn get_pack_info_from_dir(
     let manifest_path = dir.join("manifest.json");
     let content = fs::read_to_string(&manifest_path)?;
     let clean = strip_json_comments(content.trim_start_matches('\u{feff}'));
-    let mut manifest: PartialManifest = serde_json::from_str(&clean)?;
+    let (mut manifest, legacy_manifest) = parse_manifest_str(&clean)?;
 
     let detected_type = if *target_type == ImportTargetType::Unknown {
         detect_type_from_manifest(&manifest)
@@ -2494,25 +3439,137 @@ fn get_pack_info_from_dir(
         }
     }
 
-    let size = dir_size(dir).unwrap_or(0);
+    let size = file_ops::dir_size(dir).unwrap_or(0);
 
     Ok(PackagePreview {
-        name,
+        name: clean_display_name(&name),
         description,
         icon,
         kind: detected_type.to_display_name().to_string(),
         version: version_str,
         size,
+        file_count: None,
+        uncompressed_size: None,
         manifest: Some(manifest),
+        raw_manifest_json: None,
         sub_packs: None, // [新增] 默认为 None
         world_pack_references: None,
         valid,
         invalid_reason,
+        legacy_manifest,
     })
 }
 
+/// 字段值正好是语言文件中的键时替换为译文，否则原样保留
+fn translate_manifest_field(field: &mut Option<String>, lang_map: &HashMap<String, String>) {
+    if let Some(value) = field.as_mut()
+        && let Some(translated) = lang_map.get(value.trim())
+    {
+        *value = translated.clone();
+    }
+}
+
+/// 读取包目录的 manifest，用包自带的语言文件解析 header 与各模块描述中的翻译键
+pub fn translate_pack_manifest_keys(
+    pack_dir: String,
+    locale: String,
+) -> Result<PartialManifest, String> {
+    use crate::core::minecraft::resource_packs::load_lang_map_for_pack;
+
+    let pack_dir = PathBuf::from(pack_dir);
+    let content = fs::read_to_string(pack_dir.join("manifest.json"))
+        .map_err(|e| format!("读取 manifest.json 失败: {}", e))?;
+    let clean = strip_json_comments(content.trim_start_matches('\u{feff}'));
+    let (mut manifest, _) =
+        parse_manifest_str(&clean).map_err(|e| format!("解析 manifest.json 失败: {}", e))?;
+
+    let Some(lang_map) = load_lang_map_for_pack(&pack_dir, &normalize_lang_code(&locale)) else {
+        return Ok(manifest);
+    };
+    if let Some(header) = manifest.header.as_mut() {
+        translate_manifest_field(&mut header.name, &lang_map);
+        translate_manifest_field(&mut header.description, &lang_map);
+    }
+    for module in manifest.modules.iter_mut().flatten() {
+        translate_manifest_field(&mut module.description, &lang_map);
+    }
+    Ok(manifest)
+}
+
+fn icon_mime(bytes: &[u8]) -> &'static str {
+    if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        "image/jpeg"
+    } else {
+        "image/png"
+    }
+}
+
+fn read_world_icon_from_dir(dir: &Path) -> Option<Vec<u8>> {
+    ["world_icon.jpeg", "world_icon.jpg", "world_icon.png"]
+        .iter()
+        .find_map(|icon_name| fs::read(dir.join(icon_name)).ok())
+}
+
+fn read_pack_icon_from_archive(path: &Path) -> Result<Option<Vec<u8>>, String> {
+    ensure_zip_archive(path)?;
+    let file = File::open(path).map_err(|e| format!("打开文件失败: {e}"))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| format!("读取压缩包失败: {e}"))?;
+    let (target_type, _, _, scan) =
+        analyze_archive(&mut archive, path).map_err(|e| e.to_string())?;
+
+    if target_type == ImportTargetType::World {
+        let Some(world_root) = resolve_world_archive_primary(&scan)
+            .or_else(|| primary_world_root(&scan).map(normalize_root_key))
+        else {
+            return Ok(None);
+        };
+        return Ok(find_world_icon_under_root(&mut archive, &world_root));
+    }
+
+    for pack in &scan.packs {
+        if let Ok(preview) = get_pack_info_from_zip(&mut archive, pack, None)
+            && let Some(icon) = preview.icon
+        {
+            return Ok(Some(icon.bytes));
+        }
+    }
+    Ok(None)
+}
+
+/// 将包目录或压缩包中的图标原样写入 `output_path`，返回图标的 MIME 类型
+pub fn extract_pack_icon(source: String, output_path: String) -> Result<String, String> {
+    let source = Path::new(&source);
+    let icon = if source.is_dir() {
+        if source.join("manifest.json").is_file() {
+            get_pack_info_from_dir(source, &ImportTargetType::Unknown, None)
+                .map_err(|e| format!("读取包信息失败: {e}"))?
+                .icon
+                .map(|icon| icon.bytes)
+        } else if source.join("level.dat").is_file() {
+            read_world_icon_from_dir(source)
+        } else {
+            return Err(format!("不是有效的包或存档目录: {}", source.display()));
+        }
+    } else if source.is_file() {
+        read_pack_icon_from_archive(source)?
+    } else {
+        return Err(format!("路径不存在: {}", source.display()));
+    };
+
+    let icon = icon.ok_or_else(|| format!("未找到图标: {}", source.display()))?;
+    let output_path = Path::new(&output_path);
+    if let Some(parent) = output_path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {e}"))?;
+    }
+    fs::write(output_path, &icon)
+        .map_err(|e| format!("写入图标失败 {}: {e}", output_path.display()))?;
+    Ok(icon_mime(&icon).to_string())
+}
+
 // [修改] Windows 文件夹名规范化 + 长度限制
-fn sanitize_filename(name: &str) -> String {
+pub(crate) fn sanitize_filename(name: &str) -> String {
     const MAX_LEN: usize = 80;
     const INVALID: [char; 9] = ['\\', '/', ':', '*', '?', '"', '<', '>', '|'];
 
@@ -2561,22 +3618,7 @@ fn pack_folder_name(uuid: &str) -> String {
     }
 }
 
-// [新增] 去除 Minecraft 格式化代码 (§x)
-fn strip_minecraft_formatting(input: &str) -> String {
-    let mut output = String::with_capacity(input.len());
-    let mut chars = input.chars().peekable();
-    while let Some(c) = chars.next() {
-        if c == '§' {
-            // 跳过下一个字符
-            chars.next();
-        } else {
-            output.push(c);
-        }
-    }
-    output
-}
-
-fn strip_json_comments(input: &str) -> String {
+pub(crate) fn strip_json_comments(input: &str) -> String {
     let mut output = String::with_capacity(input.len());
     let mut chars = input.chars().peekable();
     let mut in_string = false;
@@ -2639,9 +3681,10 @@ fn read_zip_text_case_insensitive<R: Read + Seek>(
     target_path: &str,
 ) -> Option<String> {
     let target = target_path.replace('\\', "/").to_ascii_lowercase();
+    let names = EntryNameDecoder::default();
     for i in 0..archive.len() {
         if let Ok(mut file) = archive.by_index(i) {
-            let Ok(name) = file.name() else {
+            let Ok(name) = names.entry_name(&file) else {
                 continue;
             };
             let name = name.replace('\\', "/").to_ascii_lowercase();
@@ -2662,7 +3705,7 @@ fn read_zip_text_by_name<R: Read + Seek>(
     archive: &mut ZipArchive<R>,
     path: &str,
 ) -> Option<String> {
-    let mut file = archive.by_name(path).ok()?;
+    let mut file = by_entry_name(archive, path).ok()?;
     let mut buf = Vec::new();
     if file.read_to_end(&mut buf).is_ok() {
         return decode_text_bytes(&buf);
@@ -2800,12 +3843,13 @@ fn decode_text_bytes(bytes: &[u8]) -> Option<String> {
 
 fn list_nested_archives<R: Read + Seek>(archive: &mut ZipArchive<R>) -> Vec<String> {
     let mut paths = Vec::new();
+    let names = EntryNameDecoder::default();
     for i in 0..archive.len() {
         if let Ok(file) = archive.by_index(i) {
             if file.is_dir() {
                 continue;
             }
-            let Ok(name) = file.name() else {
+            let Ok(name) = names.entry_name(&file) else {
                 continue;
             };
             let name = name.to_string();
@@ -2859,38 +3903,51 @@ fn filter_packs_excluding_template_internal(packs: &[PackEntry]) -> Vec<PackEntr
         .collect()
 }
 
+/// 返回嵌套包的子包预览，以及全部嵌套包内文件的合计（有嵌套包被跳过或无法读取时为 `None`）
 fn inspect_nested_archives_quick(
     archive: &mut ZipArchive<File>,
     preferred_lang: Option<&str>,
-) -> Result<Vec<PackagePreview>> {
+) -> Result<(Vec<PackagePreview>, Option<ArchiveTotals>)> {
     const MAX_NESTED_SIZE: u64 = 200 * 1024 * 1024; // 200 MB
     let names = list_nested_archives(archive);
     if names.is_empty() {
-        return Ok(Vec::new());
+        return Ok((Vec::new(), Some(ArchiveTotals::default())));
     }
 
     let mut previews = Vec::new();
+    let mut totals = Some(ArchiveTotals::default());
 
     for name in names {
-        let mut file = match archive.by_name(&name) {
+        let mut file = match by_entry_name(archive, &name) {
             Ok(f) => f,
-            Err(_) => continue,
+            Err(_) => {
+                totals = None;
+                continue;
+            }
         };
         if file.size() > MAX_NESTED_SIZE {
+            totals = None;
             continue;
         }
         let mut buf = Vec::with_capacity(file.size() as usize);
         if file.read_to_end(&mut buf).is_err() {
+            totals = None;
             continue;
         }
 
         let cursor = Cursor::new(buf);
         let mut nested = match ZipArchive::new(cursor) {
             Ok(z) => z,
-            Err(_) => continue,
+            Err(_) => {
+                totals = None;
+                continue;
+            }
         };
 
         let scan = scan_archive(&mut nested)?;
+        totals = totals
+            .zip(scan.totals)
+            .map(|(sum, nested)| sum.merge(nested));
         let filtered_packs = filter_packs_excluding_template_internal(&scan.packs);
         let mut seen = std::collections::HashSet::new();
         for pack in &filtered_packs {
@@ -2910,7 +3967,7 @@ fn inspect_nested_archives_quick(
         }
     }
 
-    Ok(previews)
+    Ok((previews, totals))
 }
 
 fn read_zip_bytes_case_insensitive<R: Read + Seek>(
@@ -2918,9 +3975,10 @@ fn read_zip_bytes_case_insensitive<R: Read + Seek>(
     target_path: &str,
 ) -> Option<Vec<u8>> {
     let target = target_path.replace('\\', "/").to_ascii_lowercase();
+    let names = EntryNameDecoder::default();
     for i in 0..archive.len() {
         if let Ok(mut file) = archive.by_index(i) {
-            let Ok(name) = file.name() else {
+            let Ok(name) = names.entry_name(&file) else {
                 continue;
             };
             let name = name.replace('\\', "/").to_ascii_lowercase();
@@ -2949,12 +4007,13 @@ fn find_world_icon_under_root<R: Read + Seek>(
         }
     }
 
+    let names = EntryNameDecoder::default();
     for i in 0..archive.len() {
         if let Ok(mut file) = archive.by_index(i) {
             if file.is_dir() {
                 continue;
             }
-            let Ok(name) = file.name() else {
+            let Ok(name) = names.entry_name(&file) else {
                 continue;
             };
             let name = name.replace('\\', "/").to_ascii_lowercase();
@@ -2987,12 +4046,13 @@ fn find_world_icon_near_manifest<R: Read + Seek>(
 
     // Fallback: search anywhere in archive
     let exts = ["world_icon.jpeg", "world_icon.jpg", "world_icon.png"];
+    let names = EntryNameDecoder::default();
     for i in 0..archive.len() {
         if let Ok(mut file) = archive.by_index(i) {
             if file.is_dir() {
                 continue;
             }
-            let Ok(name) = file.name() else {
+            let Ok(name) = names.entry_name(&file) else {
                 continue;
             };
             let name = name.replace('\\', "/").to_ascii_lowercase();
@@ -3030,9 +4090,10 @@ fn find_any_lang_in_texts<R: Read + Seek>(
         }
     }
 
+    let names = EntryNameDecoder::default();
     for i in 0..archive.len() {
         if let Ok(mut file) = archive.by_index(i) {
-            let Ok(name) = file.name() else {
+            let Ok(name) = names.entry_name(&file) else {
                 continue;
             };
             let name = name.replace('\\', "/").to_ascii_lowercase();
@@ -3123,7 +4184,7 @@ fn get_pack_info_from_zip<R: Read + Seek>(
             break;
         }
         let target_path = format!("{}{}", root_prefix, icon_name);
-        if let Ok(mut f) = archive.by_name(&target_path) {
+        if let Ok(mut f) = by_entry_name(archive, &target_path) {
             let mut buf = Vec::new();
             if f.read_to_end(&mut buf).is_ok() {
                 let mime = if buf.starts_with(&[0xFF, 0xD8, 0xFF]) {
@@ -3156,17 +4217,21 @@ fn get_pack_info_from_zip<R: Read + Seek>(
     }
 
     Ok(PackagePreview {
-        name,
+        name: clean_display_name(&name),
         description,
         icon,
         kind: pack.pack_type.to_display_name().to_string(),
         version: version_str,
         size: 0,
+        file_count: None,
+        uncompressed_size: None,
         manifest: Some(manifest),
+        raw_manifest_json: None,
         sub_packs: None,
         world_pack_references: None,
         valid,
         invalid_reason,
+        legacy_manifest: pack.legacy_manifest,
     })
 }
 
@@ -3238,17 +4303,21 @@ fn get_world_info_from_zip<R: Read + Seek>(
     );
 
     Ok(PackagePreview {
-        name,
+        name: clean_display_name(&name),
         description,
         icon,
         kind: ImportTargetType::World.to_display_name().to_string(),
         version: None,
         size: 0,
+        file_count: None,
+        uncompressed_size: None,
         manifest: None,
+        raw_manifest_json: None,
         sub_packs: None,
         world_pack_references: (!world_pack_references.is_empty()).then_some(world_pack_references),
         valid: true,
         invalid_reason: None,
+        legacy_manifest: false,
     })
 }
 
@@ -3257,6 +4326,7 @@ pub fn import_archive_optimized(
     options: &GamePathOptions,
     overwrite: bool,
 ) -> Result<()> {
+    ensure_zip_archive(file_path).map_err(anyhow::Error::msg)?;
     let file = File::open(file_path)?;
     let mut archive = ZipArchive::new(file)?;
 
@@ -3304,8 +4374,562 @@ pub fn import_archive_optimized(
             }
 
             extract_pack_root(&mut archive, &pack.root, &dest)?;
+            maybe_upgrade_legacy_manifest(&dest);
+            record_pack_install(&dest, Some(file_path));
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0.15 时期原版资源包的 pack_manifest 结构
+    const LEGACY_RESOURCE_MANIFEST: &str = r#"{
+        "header": {
+            "pack_id": "66c6e9a8-3093-462a-9c36-dbb052165822",
+            "name": "Vanilla",
+            "packs_version": "0.0.1",
+            "description": "The default Minecraft look",
+            "modules": [
+                {
+                    "description": "Vanilla Resources",
+                    "version": "0.0.1",
+                    "uuid": "743f6949-53be-44b6-b326-398005028819",
+                    "type": "resources"
+                }
+            ]
+        }
+    }"#;
+
+    /// 0.16 时期行为包：名称只写在模块描述里，模块类型为 clientdata
+    const LEGACY_BEHAVIOR_MANIFEST: &str = r#"{
+        "format_version": 1,
+        "header": {
+            "pack_id": "ee649bcf-256c-4013-9068-6a802b89d756",
+            "packs_version": "0.0.1",
+            "description": "Vanilla Behavior Pack",
+            "modules": [
+                {
+                    "description": "Vanilla Behavior",
+                    "version": "0.0.1",
+                    "uuid": "7a4a5a27-5e0a-48f0-93f0-8d0f1c3e2f66",
+                    "type": "clientdata"
+                }
+            ]
+        }
+    }"#;
+
+    /// 早期皮肤包，模块类型写作 skinpacks
+    const LEGACY_SKIN_MANIFEST: &str = r#"{
+        "header": {
+            "pack_id": "c18e65aa-7b21-4637-9b63-8ad63622ef01",
+            "name": "Skin Pack 1",
+            "packs_version": "1.0.0",
+            "modules": [
+                {
+                    "version": "1.0.0",
+                    "uuid": "c18e65aa-7b21-4637-9b63-8ad63622ef02",
+                    "type": "skinpacks"
+                }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn legacy_manifests_map_to_modern_shape_and_type() {
+        let (manifest, legacy) = parse_manifest_str(LEGACY_RESOURCE_MANIFEST).expect("parse");
+        assert!(legacy);
+        let header = manifest.header.as_ref().expect("header");
+        assert_eq!(
+            header.uuid.as_deref(),
+            Some("66c6e9a8-3093-462a-9c36-dbb052165822")
+        );
+        assert_eq!(header.name.as_deref(), Some("Vanilla"));
+        assert_eq!(
+            header.version.as_ref().map(version_to_string).as_deref(),
+            Some("0.0.1")
+        );
+        assert_eq!(
+            detect_type_from_manifest(&manifest),
+            ImportTargetType::ResourcePack
+        );
+
+        let (manifest, legacy) = parse_manifest_str(LEGACY_BEHAVIOR_MANIFEST).expect("parse");
+        assert!(legacy);
+        assert_eq!(
+            manifest.header.as_ref().and_then(|h| h.name.as_deref()),
+            Some("Vanilla Behavior")
+        );
+        assert_eq!(
+            detect_type_from_manifest(&manifest),
+            ImportTargetType::BehaviorPack
+        );
+
+        let (manifest, legacy) = parse_manifest_str(LEGACY_SKIN_MANIFEST).expect("parse");
+        assert!(legacy);
+        assert_eq!(
+            detect_type_from_manifest(&manifest),
+            ImportTargetType::SkinPack
+        );
+    }
+
+    #[test]
+    fn modern_manifest_is_not_treated_as_legacy() {
+        let modern = r#"{
+            "format_version": 2,
+            "header": {
+                "name": "Modern",
+                "uuid": "0b6c0a4e-7f2a-4e62-9a3c-2f0b2d1a9e01",
+                "version": [1, 0, 0]
+            },
+            "modules": [
+                { "type": "data", "uuid": "0b6c0a4e-7f2a-4e62-9a3c-2f0b2d1a9e02", "version": [1, 0, 0] }
+            ]
+        }"#;
+        let (manifest, legacy) = parse_manifest_str(modern).expect("parse");
+        assert!(!legacy);
+        assert_eq!(
+            detect_type_from_manifest(&manifest),
+            ImportTargetType::BehaviorPack
+        );
+    }
+
+    #[test]
+    fn legacy_manifest_upgrade_keeps_original_as_backup() {
+        let pack_dir =
+            std::env::temp_dir().join(format!("bmcbl-legacy-manifest-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&pack_dir).expect("create pack dir");
+        fs::write(pack_dir.join("manifest.json"), LEGACY_RESOURCE_MANIFEST)
+            .expect("write manifest");
+
+        assert!(upgrade_legacy_manifest(&pack_dir).expect("upgrade"));
+        assert_eq!(
+            fs::read_to_string(pack_dir.join(LEGACY_MANIFEST_BACKUP_NAME)).expect("backup"),
+            LEGACY_RESOURCE_MANIFEST
+        );
+
+        let upgraded = fs::read_to_string(pack_dir.join("manifest.json")).expect("manifest");
+        let (manifest, legacy) = parse_manifest_str(&upgraded).expect("parse upgraded");
+        assert!(!legacy);
+        assert!(matches!(
+            manifest.format_version,
+            Some(ManifestFormatVersion::Number(2))
+        ));
+        assert_eq!(
+            manifest.header.and_then(|h| h.uuid).as_deref(),
+            Some("66c6e9a8-3093-462a-9c36-dbb052165822")
+        );
+        assert!(!upgrade_legacy_manifest(&pack_dir).expect("second upgrade"));
+
+        fs::remove_dir_all(&pack_dir).expect("remove test dir");
+    }
+
+    #[test]
+    fn pack_install_metadata_round_trips_and_is_not_pack_content() {
+        let root =
+            std::env::temp_dir().join(format!("bmcbl-install-metadata-{}", uuid::Uuid::new_v4()));
+        let pack_dir = root.join("pack");
+        fs::create_dir_all(&pack_dir).expect("create pack dir");
+        fs::write(pack_dir.join("manifest.json"), "{}").expect("write manifest");
+        let source = root.join("Demo.mcpack");
+        fs::write(&source, b"PK\x03\x04demo").expect("write source");
+
+        let dir = pack_dir.to_string_lossy().into_owned();
+        assert_eq!(get_pack_install_date(dir.clone()), Ok(None));
+        write_pack_install_metadata(&pack_dir, Some(&source)).expect("write metadata");
+        let metadata = get_pack_install_date(dir)
+            .expect("read metadata")
+            .expect("metadata exists");
+        assert_eq!(metadata.source_size, Some(8));
+        assert_eq!(
+            metadata.source_file.as_deref(),
+            Some(source.to_string_lossy().as_ref())
+        );
+        assert!(chrono::DateTime::parse_from_rfc3339(&metadata.installed_at).is_ok());
+        assert_eq!(metadata.launcher_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(
+            pack_dir_file_sizes(&pack_dir)
+                .into_keys()
+                .collect::<Vec<_>>(),
+            vec!["manifest.json".to_string()]
+        );
+
+        fs::remove_dir_all(&root).expect("remove test dir");
+    }
+
+    fn write_zip64_fixture(path: &Path, entries: &[(&str, u64)]) {
+        use zip::write::SimpleFileOptions;
+
+        let mut writer = zip::ZipWriter::new(File::create(path).expect("create fixture"));
+        let options = SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .large_file(true);
+        let chunk = vec![0u8; 1024 * 1024];
+        for (name, size) in entries {
+            writer.start_file(*name, options).expect("start entry");
+            let mut remaining = *size;
+            while remaining > 0 {
+                let len = remaining.min(chunk.len() as u64) as usize;
+                writer.write_all(&chunk[..len]).expect("write entry");
+                remaining -= len as u64;
+            }
+        }
+        writer.finish().expect("finish fixture");
+    }
+
+    #[test]
+    fn parallel_extract_reads_zip64_entries_and_sanitizes_names() {
+        let dir = std::env::temp_dir().join(format!("bmcbl-zip64-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).expect("create test dir");
+        let archive_path = dir.join("fixture.mcpack");
+        write_zip64_fixture(
+            &archive_path,
+            &[("pack/manifest.json", 16), ("pack/textures/a:b?.png", 4096)],
+        );
+
+        let dest = dir.join("out");
+        extract_archive_parallel(&archive_path, &dest, None).expect("extract fixture");
+
+        // 公共根目录 pack/ 被剥离，非法字符按组件替换而不是跳过
+        assert_eq!(fs::metadata(dest.join("manifest.json")).unwrap().len(), 16);
+        assert_eq!(
+            fs::metadata(dest.join("textures").join("a_b_.png"))
+                .unwrap()
+                .len(),
+            4096
+        );
+
+        fs::remove_dir_all(&dir).expect("remove test dir");
+    }
+
+    #[test]
+    fn streaming_extract_stops_and_cleans_up_when_cancelled() {
+        use crate::tasks::task_manager::{cancel_task, create_task};
+
+        let dir =
+            std::env::temp_dir().join(format!("bmcbl-import-cancel-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).expect("create test dir");
+        let archive_path = dir.join("world.mcworld");
+        write_zip64_fixture(&archive_path, &[("level.dat", 16), ("db/000001.ldb", 4096)]);
+        let task_id = create_task(None, "importing", None);
+        cancel_task(&task_id);
+
+        let dest = dir.join("world");
+        let error = extract_archive_streaming(&archive_path, &dest, Some(task_id.as_str()))
+            .expect_err("cancelled extraction");
+        assert!(error.to_string().contains("已取消"), "{error}");
+        assert!(!dest.exists());
+        assert!(!dir.join(format!("world{IMPORTING_SUFFIX}")).exists());
+
+        fs::remove_dir_all(&dir).expect("remove test dir");
+    }
+
+    #[test]
+    fn replacing_keeps_the_old_dir_when_the_swap_fails() {
+        let dir =
+            std::env::temp_dir().join(format!("bmcbl-import-replace-{}", uuid::Uuid::new_v4()));
+        let dest = dir.join("pack");
+        fs::create_dir_all(&dest).expect("create old pack");
+        fs::write(dest.join("manifest.json"), "old").expect("write old manifest");
+        let backup = dir.join(format!("pack{REPLACED_SUFFIX}"));
+
+        let staging = dir.join(format!("pack{IMPORTING_SUFFIX}"));
+        assert!(replace_with_staging(&staging, &dest).is_err());
+        assert_eq!(
+            fs::read_to_string(dest.join("manifest.json")).expect("read old manifest"),
+            "old"
+        );
+        assert!(!backup.exists());
+
+        fs::create_dir_all(&staging).expect("create staging");
+        fs::write(staging.join("manifest.json"), "new").expect("write new manifest");
+        replace_with_staging(&staging, &dest).expect("replace pack");
+        assert_eq!(
+            fs::read_to_string(dest.join("manifest.json")).expect("read new manifest"),
+            "new"
+        );
+        assert!(!backup.exists());
+        assert!(!staging.exists());
+
+        fs::remove_dir_all(&dir).expect("remove test dir");
+    }
+
+    /// 生成并解压一个超过 4GB 的真实 ZIP64 包，耗时较长且需要约 4GB 磁盘空间
+    #[test]
+    #[ignore = "writes more than 4GB to the temp dir"]
+    fn parallel_extract_handles_archives_over_4gb() {
+        const OVER_4GB: u64 = (4 << 30) + (1 << 20);
+        let dir = std::env::temp_dir().join(format!("bmcbl-zip64-large-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).expect("create test dir");
+        let archive_path = dir.join("large.mcworld");
+        write_zip64_fixture(
+            &archive_path,
+            &[("level.dat", 16), ("db/large.ldb", OVER_4GB)],
+        );
+
+        let dest = dir.join("out");
+        extract_archive_parallel(&archive_path, &dest, None).expect("extract large fixture");
+        assert_eq!(
+            fs::metadata(dest.join("db").join("large.ldb"))
+                .unwrap()
+                .len(),
+            OVER_4GB
+        );
+
+        fs::remove_dir_all(&dir).expect("remove test dir");
+    }
+
+    #[test]
+    fn manifest_keys_resolve_from_pack_lang_file() {
+        let pack_dir =
+            std::env::temp_dir().join(format!("bmcbl-translate-manifest-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(pack_dir.join("texts")).expect("create texts dir");
+        fs::write(
+            pack_dir.join("manifest.json"),
+            r#"{
+                "format_version": 2,
+                "header": {"name": "pack.name", "description": "pack.description", "uuid": "5c1c0f0e-2d3a-4b1e-9f45-1c1a2b3c4d5e", "version": [1, 0, 0]},
+                "modules": [
+                    {"type": "resources", "uuid": "0b6c9c4a-6f1c-4f7e-8d5a-2a3b4c5d6e7f", "version": [1, 0, 0], "description": "pack.module.desc"},
+                    {"type": "data", "uuid": "1c7d0d5b-7a2d-4a8f-9e6b-3b4c5d6e7f80", "version": [1, 0, 0], "description": "Plain text"}
+                ]
+            }"#,
+        )
+        .expect("write manifest");
+        fs::write(
+            pack_dir.join("texts").join("zh_CN.lang"),
+            "pack.name=测试包\npack.description=描述\npack.module.desc=资源模块\n",
+        )
+        .expect("write lang");
+
+        let manifest = translate_pack_manifest_keys(
+            pack_dir.to_string_lossy().into_owned(),
+            "zh_CN".to_string(),
+        )
+        .expect("translate manifest");
+
+        let header = manifest.header.expect("header");
+        assert_eq!(header.name.as_deref(), Some("测试包"));
+        assert_eq!(header.description.as_deref(), Some("描述"));
+        let descriptions: Vec<_> = manifest
+            .modules
+            .expect("modules")
+            .into_iter()
+            .map(|module| module.description)
+            .collect();
+        assert_eq!(
+            descriptions,
+            vec![Some("资源模块".to_string()), Some("Plain text".to_string())]
+        );
+
+        fs::remove_dir_all(&pack_dir).expect("remove test dir");
+    }
+
+    fn zip_bytes(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        use zip::write::SimpleFileOptions;
+
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, bytes) in entries {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .expect("start entry");
+            writer.write_all(bytes).expect("write entry");
+        }
+        writer.finish().expect("finish zip").into_inner()
+    }
+
+    #[test]
+    fn preview_counts_files_across_nested_sub_packs() {
+        let manifest = |name: &str, uuid: &str| {
+            format!(
+                r#"{{"format_version":2,"header":{{"name":"{name}","uuid":"{uuid}","version":[1,0,0]}},"modules":[{{"type":"resources","uuid":"{uuid}","version":[1,0,0]}}]}}"#
+            )
+        };
+        let first_manifest = manifest("First", "4d3f4a6e-1b52-4c1d-9a0e-0c7e7d1f0a01");
+        let second_manifest = manifest("Second", "4d3f4a6e-1b52-4c1d-9a0e-0c7e7d1f0a02");
+        let first = zip_bytes(&[
+            ("manifest.json", first_manifest.as_bytes()),
+            ("textures/a.png", &[1u8; 100]),
+        ]);
+        let second = zip_bytes(&[
+            ("manifest.json", second_manifest.as_bytes()),
+            ("textures/b.png", &[2u8; 50]),
+            ("texts/en_US.lang", b"pack.name=Second"),
+        ]);
+        let addon = zip_bytes(&[
+            ("first.mcpack", &first),
+            ("second.mcpack", &second),
+            ("readme.txt", b"hello"),
+        ]);
+
+        let dir = std::env::temp_dir().join(format!("bmcbl-preview-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).expect("create test dir");
+        let addon_path = dir.join("bundle.mcaddon");
+        fs::write(&addon_path, addon).expect("write addon");
+        let pack_path = dir.join("first.mcpack");
+        fs::write(&pack_path, &first).expect("write pack");
+
+        let preview = inspect_archive(&addon_path, None, false).expect("inspect addon");
+        assert_eq!(preview.sub_packs.as_ref().map(Vec::len), Some(2));
+        assert_eq!(preview.file_count, Some(6));
+        let expected_size = first_manifest.len() + 100 + second_manifest.len() + 50 + 16 + 5;
+        assert_eq!(preview.uncompressed_size, Some(expected_size as u64));
+
+        let preview = inspect_archive(&pack_path, None, false).expect("inspect pack");
+        assert_eq!(preview.file_count, Some(2));
+        assert_eq!(
+            preview.uncompressed_size,
+            Some(first_manifest.len() as u64 + 100)
+        );
+
+        fs::remove_dir_all(&dir).expect("remove test dir");
+    }
+
+    #[test]
+    fn cache_dir_import_reports_progress_and_stops_when_cancelled() {
+        use crate::tasks::task_manager::{cancel_task, create_task, finish_task, get_snapshot_arc};
+
+        let dir =
+            std::env::temp_dir().join(format!("bmcbl-import-progress-{}", uuid::Uuid::new_v4()));
+        // 没有 manifest.json / level.dat 的目录会被跳过，不需要真实的游戏目录
+        let pack_dirs: Vec<PathBuf> = (0..3).map(|index| dir.join(index.to_string())).collect();
+        for pack_dir in &pack_dirs {
+            fs::create_dir_all(pack_dir).expect("create pack dir");
+        }
+        let options = GamePathOptions {
+            build_type: crate::core::minecraft::paths::BuildType::Uwp,
+            edition: crate::core::minecraft::paths::Edition::Release,
+            version_name: "progress-test".to_string(),
+            enable_isolation: false,
+            user_id: None,
+            allow_shared_fallback: false,
+        };
+
+        let task_id = create_task(None, "importing", None);
+        let imported =
+            import_from_cache_dirs(&pack_dirs, &options, false, &dir, Some(task_id.as_str()))
+                .expect("import cache dirs");
+        assert!(imported.is_empty());
+        // 进度更新有节流，结束任务后快照才一定是最新的；error 不会把 done 补满
+        finish_task(&task_id, "error", None);
+        let snapshot = get_snapshot_arc(&task_id).expect("task snapshot");
+        assert_eq!((snapshot.done, snapshot.total), (3, Some(3)));
+
+        let cancelled_task_id = create_task(None, "importing", None);
+        cancel_task(&cancelled_task_id);
+        let imported = import_from_cache_dirs(
+            &pack_dirs,
+            &options,
+            false,
+            &dir,
+            Some(cancelled_task_id.as_str()),
+        )
+        .expect("cancelled import");
+        assert!(imported.is_empty());
+        assert_eq!(
+            get_snapshot_arc(&cancelled_task_id)
+                .expect("task snapshot")
+                .done,
+            0
+        );
+
+        fs::remove_dir_all(&dir).expect("remove test dir");
+    }
+
+    #[test]
+    fn pack_content_diff_groups_by_top_level_dir_and_truncates_lists() {
+        let incoming: HashMap<String, u64> = [
+            ("manifest.json", 10),
+            ("textures/new.png", 5),
+            ("textures/same.png", 7),
+            ("scripts/main.js", 3),
+        ]
+        .into_iter()
+        .map(|(path, size)| (path.to_string(), size))
+        .collect();
+        let existing: HashMap<String, u64> = [
+            ("manifest.json", 10),
+            ("textures/same.png", 7),
+            ("scripts/main.js", 4),
+            ("scripts/old.js", 1),
+        ]
+        .into_iter()
+        .map(|(path, size)| (path.to_string(), size))
+        .collect();
+
+        let diff = build_pack_content_diff(&incoming, &existing);
+        assert_eq!(diff.added, vec!["textures/new.png".to_string()]);
+        assert_eq!(diff.removed, vec!["scripts/old.js".to_string()]);
+        assert_eq!(diff.changed, vec!["scripts/main.js".to_string()]);
+        assert!(!diff.truncated);
+        let categories: Vec<_> = diff
+            .categories
+            .iter()
+            .map(|category| {
+                (
+                    category.name.as_str(),
+                    category.added,
+                    category.removed,
+                    category.changed,
+                )
+            })
+            .collect();
+        assert_eq!(
+            categories,
+            vec![("scripts", 0, 1, 1), ("textures", 1, 0, 0)]
+        );
+
+        let many: HashMap<String, u64> = (0..PACK_DIFF_LIST_LIMIT + 5)
+            .map(|index| (format!("textures/{index}.png"), 1))
+            .collect();
+        let diff = build_pack_content_diff(&many, &HashMap::new());
+        assert!(diff.truncated);
+        assert_eq!(diff.added.len(), PACK_DIFF_LIST_LIMIT);
+        assert_eq!(diff.added_count, PACK_DIFF_LIST_LIMIT + 5);
+        assert_eq!(diff.categories[0].added, PACK_DIFF_LIST_LIMIT + 5);
+    }
+
+    #[test]
+    fn diff_pack_contents_compares_archive_with_installed_pack() {
+        let manifest = |version: &str| {
+            format!(
+                r#"{{"format_version":2,"header":{{"name":"Diff","uuid":"7a1e2f3c-4b5d-4e6f-8a9b-0c1d2e3f4a5b","version":{version}}},"modules":[{{"type":"resources","uuid":"7a1e2f3c-4b5d-4e6f-8a9b-0c1d2e3f4a5c","version":[1,0,0]}}]}}"#
+            )
+        };
+        let incoming_manifest = manifest("[1,3,10]");
+        let archive = zip_bytes(&[
+            ("Diff/manifest.json", incoming_manifest.as_bytes()),
+            ("Diff/textures/a.png", &[1u8; 8]),
+            ("Diff/textures/b.png", &[2u8; 4]),
+        ]);
+
+        let dir = std::env::temp_dir().join(format!("bmcbl-pack-diff-{}", uuid::Uuid::new_v4()));
+        let installed = dir.join("installed");
+        fs::create_dir_all(installed.join("scripts")).expect("create installed pack");
+        fs::write(installed.join("manifest.json"), manifest("[1,2,0]")).expect("write manifest");
+        fs::write(installed.join("scripts").join("main.js"), "x").expect("write script");
+        fs::write(installed.join(PACK_INSTALL_METADATA_NAME), "{}").expect("write metadata");
+        let archive_path = dir.join("diff.mcpack");
+        fs::write(&archive_path, archive).expect("write archive");
+
+        let diff = diff_pack_contents(
+            &archive_path.to_string_lossy(),
+            &installed.to_string_lossy(),
+        )
+        .expect("diff pack");
+
+        assert_eq!(
+            diff.added,
+            vec!["textures/a.png".to_string(), "textures/b.png".to_string()]
+        );
+        assert_eq!(diff.removed, vec!["scripts/main.js".to_string()]);
+        assert_eq!(diff.changed, vec!["manifest.json".to_string()]);
+        assert_eq!(diff.incoming_version.as_deref(), Some("1.3.10"));
+        assert_eq!(diff.existing_version.as_deref(), Some("1.2.0"));
+
+        fs::remove_dir_all(&dir).expect("remove test dir");
+    }
+}
@@ -0,0 +1,267 @@
+//! 导入历史：每次成功导入（含覆盖）都记录到 `BMCBL/import_history.json`，
+//! 用于回答"这个包是什么时候装进来的"。超过上限时丢弃最旧的记录。
+
+use crate::core::minecraft::import::ImportedItem;
+use crate::core::minecraft::paths::{BuildType, Edition, GamePathOptions};
+use crate::utils::file_ops;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::{debug, warn};
+
+const HISTORY_FILE_NAME: &str = "import_history.json";
+const MAX_HISTORY_ENTRIES: usize = 500;
+const DEFAULT_QUERY_LIMIT: usize = 100;
+const HASH_BUFFER_SIZE: usize = 256 * 1024;
+
+/// 串行化读改写，避免并发导入互相覆盖记录
+static HISTORY_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportHistoryEntry {
+    /// RFC3339
+    pub timestamp: String,
+    pub source_file_name: String,
+    pub source_sha256: String,
+    /// 目标目录名，例如 `resource_packs`、`minecraftWorlds`
+    pub target_type: String,
+    pub destination_path: String,
+    pub pack_uuid: Option<String>,
+    pub pack_version: Option<String>,
+    pub build_type: BuildType,
+    pub edition: Edition,
+    pub version_name: String,
+    pub user_id: Option<String>,
+    pub overwrote: bool,
+    /// 覆盖前备份的位置；未做备份时为 `None`
+    #[serde(default)]
+    pub backup_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ImportHistoryFilter {
+    /// 只返回该目标目录名的记录
+    #[serde(default)]
+    pub target_type: Option<String>,
+    /// 匹配源文件名、目标路径或包 UUID，不区分大小写
+    #[serde(default)]
+    pub query: Option<String>,
+    #[serde(default)]
+    pub overwrites_only: bool,
+}
+
+fn history_path() -> PathBuf {
+    file_ops::bmcbl_subdir(HISTORY_FILE_NAME)
+}
+
+fn load_history(path: &Path) -> Vec<ImportHistoryEntry> {
+    let Ok(raw) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    match serde_json::from_str(&raw) {
+        Ok(history) => history,
+        Err(e) => {
+            warn!("解析导入历史失败，已忽略: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// 先写临时文件再替换，写入中断不会损坏已有历史
+fn save_history(path: &Path, history: &[ImportHistoryEntry]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+    }
+    let raw = serde_json::to_string_pretty(history).map_err(|e| e.to_string())?;
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, raw).map_err(|e| format!("写入导入历史失败: {}", e))?;
+    fs::rename(&temp_path, path).map_err(|e| format!("保存导入历史失败: {}", e))
+}
+
+fn hash_file(path: &Path) -> Result<String, String> {
+    let mut file =
+        File::open(path).map_err(|e| format!("打开文件失败 {}: {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; HASH_BUFFER_SIZE];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|e| format!("读取文件失败 {}: {}", path.display(), e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// 追加记录并只保留最新的 `cap` 条
+fn append_rotated(
+    history: &mut Vec<ImportHistoryEntry>,
+    entries: Vec<ImportHistoryEntry>,
+    cap: usize,
+) {
+    history.extend(entries);
+    if history.len() > cap {
+        let excess = history.len() - cap;
+        history.drain(..excess);
+    }
+}
+
+fn matches_filter(entry: &ImportHistoryEntry, filter: &ImportHistoryFilter) -> bool {
+    if filter.overwrites_only && !entry.overwrote {
+        return false;
+    }
+    if let Some(target_type) = filter.target_type.as_deref()
+        && !entry.target_type.eq_ignore_ascii_case(target_type)
+    {
+        return false;
+    }
+    let Some(query) = filter
+        .query
+        .as_deref()
+        .map(str::trim)
+        .filter(|query| !query.is_empty())
+    else {
+        return true;
+    };
+    let query = query.to_lowercase();
+    [
+        Some(entry.source_file_name.as_str()),
+        Some(entry.destination_path.as_str()),
+        entry.pack_uuid.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .any(|field| field.to_lowercase().contains(&query))
+}
+
+/// 登记一次成功导入的全部落地项；由导入流程在确认成功后调用
+pub(crate) fn record_imports(
+    source: &Path,
+    options: &GamePathOptions,
+    imported: &[ImportedItem],
+) -> Result<(), String> {
+    if imported.is_empty() {
+        return Ok(());
+    }
+    let source_sha256 = hash_file(source)?;
+    let source_file_name = source
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let timestamp = Utc::now().to_rfc3339();
+    let entries = imported
+        .iter()
+        .map(|item| ImportHistoryEntry {
+            timestamp: timestamp.clone(),
+            source_file_name: source_file_name.clone(),
+            source_sha256: source_sha256.clone(),
+            target_type: item.target_type.to_dir_name().to_string(),
+            destination_path: item.destination.to_string_lossy().into_owned(),
+            pack_uuid: item.pack_uuid.clone(),
+            pack_version: item.pack_version.clone(),
+            build_type: options.build_type.clone(),
+            edition: options.edition.clone(),
+            version_name: options.version_name.clone(),
+            user_id: options.user_id.clone(),
+            overwrote: item.overwrote,
+            backup_path: None,
+        })
+        .collect::<Vec<_>>();
+
+    let _guard = HISTORY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = history_path();
+    let mut history = load_history(&path);
+    debug!(
+        "记录导入历史: source={}, items={}",
+        source_file_name,
+        entries.len()
+    );
+    append_rotated(&mut history, entries, MAX_HISTORY_ENTRIES);
+    save_history(&path, &history)
+}
+
+/// 按时间倒序返回导入历史
+pub fn get_import_history(
+    limit: Option<usize>,
+    filter: ImportHistoryFilter,
+) -> Result<Vec<ImportHistoryEntry>, String> {
+    let _guard = HISTORY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let history = load_history(&history_path());
+    Ok(history
+        .into_iter()
+        .rev()
+        .filter(|entry| matches_filter(entry, &filter))
+        .take(limit.unwrap_or(DEFAULT_QUERY_LIMIT))
+        .collect())
+}
+
+pub fn clear_import_history() -> Result<(), String> {
+    let _guard = HISTORY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    match fs::remove_file(history_path()) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("清空导入历史失败: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(source_file_name: &str, target_type: &str, overwrote: bool) -> ImportHistoryEntry {
+        ImportHistoryEntry {
+            timestamp: "2024-01-01T00:00:00+00:00".to_string(),
+            source_file_name: source_file_name.to_string(),
+            source_sha256: String::new(),
+            target_type: target_type.to_string(),
+            destination_path: format!("/games/{target_type}/{source_file_name}"),
+            pack_uuid: None,
+            pack_version: None,
+            build_type: BuildType::Uwp,
+            edition: Edition::Release,
+            version_name: String::new(),
+            user_id: None,
+            overwrote,
+            backup_path: None,
+        }
+    }
+
+    #[test]
+    fn rotation_keeps_newest_and_filter_matches_fields() {
+        let mut history = vec![entry("a.mcpack", "resource_packs", false)];
+        append_rotated(
+            &mut history,
+            vec![
+                entry("b.mcaddon", "behavior_packs", true),
+                entry("Castle.mcworld", "minecraftWorlds", false),
+            ],
+            2,
+        );
+        let names: Vec<_> = history
+            .iter()
+            .map(|entry| entry.source_file_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["b.mcaddon", "Castle.mcworld"]);
+
+        let by_query = ImportHistoryFilter {
+            query: Some("castle".to_string()),
+            ..Default::default()
+        };
+        assert!(matches_filter(&history[1], &by_query));
+        assert!(!matches_filter(&history[0], &by_query));
+
+        let overwrites = ImportHistoryFilter {
+            target_type: Some("behavior_packs".to_string()),
+            overwrites_only: true,
+            ..Default::default()
+        };
+        assert!(matches_filter(&history[0], &overwrites));
+        assert!(!matches_filter(&history[1], &overwrites));
+    }
+}
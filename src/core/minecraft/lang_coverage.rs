@@ -0,0 +1,158 @@
+//! 资源包翻译覆盖率：以 `texts/en_US.lang` 为基准，统计其他语言缺失的翻译键。
+
+use crate::core::minecraft::import::parse_lang_config;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+const TEXTS_DIR_NAME: &str = "texts";
+const BASELINE_LOCALE: &str = "en_US";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LocaleCoverage {
+    pub locale: String,
+    pub total_keys: usize,
+    pub translated_keys: usize,
+    /// 0 ~ 100
+    pub coverage_pct: f64,
+    pub missing_keys: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LanguageCoverageReport {
+    pub baseline_key_count: usize,
+    pub locales: Vec<LocaleCoverage>,
+}
+
+/// 统计包内各语言相对 en_US 的翻译覆盖率
+pub async fn get_resource_pack_language_coverage(
+    pack_dir: String,
+) -> Result<LanguageCoverageReport, String> {
+    let pack_dir = PathBuf::from(pack_dir);
+    tokio::task::spawn_blocking(move || language_coverage_blocking(&pack_dir))
+        .await
+        .map_err(|e| format!("统计翻译覆盖率任务失败: {}", e))?
+}
+
+/// 读取 `texts/` 下所有 .lang 文件，返回 (语言代码, 键值表)，按语言代码排序
+fn read_lang_files(texts_dir: &Path) -> Result<Vec<(String, HashMap<String, String>)>, String> {
+    let entries = fs::read_dir(texts_dir)
+        .map_err(|e| format!("读取 texts 目录失败 {}: {}", texts_dir.display(), e))?;
+    let mut files = Vec::new();
+    for path in entries.flatten().map(|entry| entry.path()) {
+        let is_lang = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("lang"));
+        if !is_lang || !path.is_file() {
+            continue;
+        }
+        let Some(locale) = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+        else {
+            continue;
+        };
+        match fs::read(&path) {
+            Ok(bytes) => files.push((locale, parse_lang_config(&String::from_utf8_lossy(&bytes)))),
+            Err(e) => warn!("读取语言文件失败 {}: {}", path.display(), e),
+        }
+    }
+    files.sort_by(|left, right| left.0.cmp(&right.0));
+    Ok(files)
+}
+
+fn compute_coverage(
+    baseline: &HashMap<String, String>,
+    locale: String,
+    translations: &HashMap<String, String>,
+) -> LocaleCoverage {
+    let mut missing_keys: Vec<String> = baseline
+        .keys()
+        .filter(|key| {
+            translations
+                .get(*key)
+                .is_none_or(|value| value.trim().is_empty())
+        })
+        .cloned()
+        .collect();
+    missing_keys.sort();
+
+    let total_keys = baseline.len();
+    let translated_keys = total_keys - missing_keys.len();
+    let coverage_pct = if total_keys == 0 {
+        100.0
+    } else {
+        translated_keys as f64 * 100.0 / total_keys as f64
+    };
+    LocaleCoverage {
+        locale,
+        total_keys,
+        translated_keys,
+        coverage_pct,
+        missing_keys,
+    }
+}
+
+fn language_coverage_blocking(pack_dir: &Path) -> Result<LanguageCoverageReport, String> {
+    let texts_dir = pack_dir.join(TEXTS_DIR_NAME);
+    if !texts_dir.is_dir() {
+        return Err(format!("资源包缺少 texts 目录: {}", pack_dir.display()));
+    }
+
+    let mut files = read_lang_files(&texts_dir)?;
+    let baseline_index = files
+        .iter()
+        .position(|(locale, _)| locale.eq_ignore_ascii_case(BASELINE_LOCALE))
+        .ok_or_else(|| format!("未找到基准语言文件 {BASELINE_LOCALE}.lang"))?;
+    let (_, baseline) = files.remove(baseline_index);
+
+    let locales = files
+        .into_iter()
+        .map(|(locale, translations)| compute_coverage(&baseline, locale, &translations))
+        .collect();
+    Ok(LanguageCoverageReport {
+        baseline_key_count: baseline.len(),
+        locales,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_and_blank_translations_are_reported_against_en_us() {
+        let pack_dir =
+            std::env::temp_dir().join(format!("bmcbl-lang-coverage-{}", uuid::Uuid::new_v4()));
+        let texts_dir = pack_dir.join(TEXTS_DIR_NAME);
+        fs::create_dir_all(&texts_dir).expect("create texts dir");
+        fs::write(
+            texts_dir.join("en_US.lang"),
+            "pack.name=Pack\npack.description=Desc\nitem.gem=Gem\nitem.ore=Ore\n",
+        )
+        .expect("write en_US");
+        fs::write(
+            texts_dir.join("zh_CN.lang"),
+            "pack.name=包\npack.description=描述\nitem.gem=宝石 #comment\nitem.ore=\nextra.key=多余\n",
+        )
+        .expect("write zh_CN");
+        fs::write(texts_dir.join("ja_JP.lang"), "pack.name=パック\n").expect("write ja_JP");
+        fs::write(texts_dir.join("languages.json"), "[\"en_US\"]").expect("write languages");
+
+        let report = language_coverage_blocking(&pack_dir).expect("coverage report");
+
+        assert_eq!(report.baseline_key_count, 4);
+        let locales: Vec<_> = report
+            .locales
+            .iter()
+            .map(|coverage| (coverage.locale.as_str(), coverage.translated_keys))
+            .collect();
+        assert_eq!(locales, vec![("ja_JP", 1), ("zh_CN", 3)]);
+        assert_eq!(report.locales[1].missing_keys, vec!["item.ore".to_string()]);
+        assert_eq!(report.locales[1].coverage_pct, 75.0);
+
+        fs::remove_dir_all(&pack_dir).expect("remove test dir");
+    }
+}
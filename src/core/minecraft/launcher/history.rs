@@ -0,0 +1,526 @@
+//! 启动历史：记录每次启动实际注入的 DLL（大小、SHA-256、PE 版本资源），
+//! 便于复现"某个模组组合崩溃"之类的问题。每个 DLL 注入结束时广播 `inject-result`；延迟注入全部结束后，
+//! 各 DLL 的实际注入时刻、耗时和结果汇总为注入报告，附加到对应的历史记录并广播 `inject-summary`。
+
+use crate::core::cache_registry::{CacheDescriptor, CacheUsage};
+use crate::utils::file_ops;
+use once_cell::sync::Lazy;
+use pelite::{FileMap, PeFile};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tokio::sync::broadcast;
+use tracing::{debug, warn};
+
+const LAUNCH_HISTORY_DIR: &str = "launch_history";
+const MAX_HISTORY_ENTRIES: usize = 50;
+const HASH_BUFFER_SIZE: usize = 64 * 1024;
+/// 历史记录始终未写入（例如写入失败）时，暂存的报告最多保留这么多条，超出后丢弃最早的
+const MAX_PENDING_REPORTS: usize = 16;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InjectedDllRecord {
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+    pub file_version: Option<String>,
+    pub product_name: Option<String>,
+    /// 0 表示随启动注入，其余为延迟注入的毫秒数
+    pub delay_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchHistoryEntry {
+    pub id: String,
+    pub version: String,
+    pub game_version: String,
+    pub started_at: i64,
+    pub pid: u32,
+    /// 从开始启动到拿到游戏 PID 的耗时
+    pub launch_duration_ms: u64,
+    pub mod_loading_disabled: bool,
+    pub injected_dlls: Vec<InjectedDllRecord>,
+    /// 延迟注入结束后补上；没有延迟注入的 DLL 时为空
+    #[serde(default)]
+    pub injection_report: Option<InjectionReport>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InjectionOutcome {
+    Injected,
+    Failed,
+    TimedOut,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InjectionRecord {
+    pub dll_name: String,
+    pub path: String,
+    /// 配置的延迟毫秒数
+    pub delay_ms: u64,
+    /// 实际开始注入时距游戏拉起的毫秒数；延迟按顺序累加，前一个注入过慢会推迟后面的
+    pub started_at_ms: u64,
+    pub duration_ms: u64,
+    pub outcome: InjectionOutcome,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InjectionReport {
+    pub pid: u32,
+    /// 按实际注入顺序排列
+    pub records: Vec<InjectionRecord>,
+    pub total_duration_ms: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct InjectResultEvent {
+    pub version: String,
+    pub history_id: String,
+    pub pid: u32,
+    pub record: InjectionRecord,
+}
+
+#[derive(Debug, Clone)]
+pub struct InjectSummaryEvent {
+    pub version: String,
+    pub history_id: String,
+    pub report: InjectionReport,
+}
+
+/// 注入计划中的一项：DLL 路径及延迟
+#[derive(Debug, Clone)]
+pub struct ScheduledDll {
+    pub path: PathBuf,
+    pub delay_ms: u64,
+}
+
+/// 串行化历史文件的读改写，并暂存先于历史记录写入完成的注入报告（键为历史记录 ID）
+static HISTORY_LOCK: Lazy<Mutex<VecDeque<(String, InjectionReport)>>> =
+    Lazy::new(|| Mutex::new(VecDeque::new()));
+
+/// `inject-summary` 事件
+static INJECT_SUMMARY_EVENTS: Lazy<broadcast::Sender<InjectSummaryEvent>> = Lazy::new(|| {
+    let (tx, _rx) = broadcast::channel(8);
+    tx
+});
+
+pub fn subscribe_inject_summary_events() -> broadcast::Receiver<InjectSummaryEvent> {
+    INJECT_SUMMARY_EVENTS.subscribe()
+}
+
+/// `inject-result` 事件
+static INJECT_RESULT_EVENTS: Lazy<broadcast::Sender<InjectResultEvent>> = Lazy::new(|| {
+    let (tx, _rx) = broadcast::channel(32);
+    tx
+});
+
+pub fn subscribe_inject_result_events() -> broadcast::Receiver<InjectResultEvent> {
+    INJECT_RESULT_EVENTS.subscribe()
+}
+
+/// 单个 DLL 注入结束后立即广播结果，不必等全部延迟注入完成
+pub fn publish_inject_result(version: &str, history_id: &str, pid: u32, record: &InjectionRecord) {
+    let _ = INJECT_RESULT_EVENTS.send(InjectResultEvent {
+        version: version.to_string(),
+        history_id: history_id.to_string(),
+        pid,
+        record: record.clone(),
+    });
+}
+
+/// 按 (路径, 修改时间, 大小) 缓存哈希，避免每次启动都重新计算大 DLL
+static DLL_HASH_CACHE: Lazy<Mutex<HashMap<(PathBuf, SystemTime, u64), String>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub(crate) static CACHE: CacheDescriptor = CacheDescriptor {
+    name: "dll_hashes",
+    label: "Mod DLL 哈希",
+    default_selected: true,
+    usage: dll_hash_cache_usage,
+    clear: clear_dll_hash_cache,
+    warm: None,
+};
+
+fn dll_hash_cache_usage() -> CacheUsage {
+    CacheUsage {
+        entries: DLL_HASH_CACHE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .len() as u64,
+        bytes: 0,
+    }
+}
+
+fn clear_dll_hash_cache() -> Result<CacheUsage, String> {
+    let mut cache = DLL_HASH_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let entries = cache.len() as u64;
+    cache.clear();
+    Ok(CacheUsage { entries, bytes: 0 })
+}
+
+/// 启动成功后在后台计算 DLL 信息并写入历史，不占用启动关键路径
+pub fn spawn_record_launch_session(mut entry: LaunchHistoryEntry, schedule: Vec<ScheduledDll>) {
+    tokio::task::spawn_blocking(move || {
+        entry.injected_dlls = schedule.iter().filter_map(describe_dll).collect();
+        if let Err(e) = append_entry(entry) {
+            warn!("写入启动历史失败: {}", e);
+        }
+    });
+}
+
+/// 读取某个版本的启动历史，最新的在前
+pub fn get_launch_history(
+    version: String,
+    limit: usize,
+) -> Result<Vec<LaunchHistoryEntry>, String> {
+    let mut entries = load_entries(&history_path(&version)?)?;
+    entries.reverse();
+    entries.truncate(limit);
+    Ok(entries)
+}
+
+/// 所有版本中最近一次启动的历史记录，供崩溃报告附带
+pub fn latest_launch_session() -> Option<LaunchHistoryEntry> {
+    let _guard = HISTORY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    latest_entry_in(&file_ops::bmcbl_subdir(LAUNCH_HISTORY_DIR))
+}
+
+fn latest_entry_in(dir: &Path) -> Option<LaunchHistoryEntry> {
+    fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|entry| match load_entries(&entry.path()) {
+            Ok(entries) => entries.into_iter().last(),
+            Err(e) => {
+                warn!("{}", e);
+                None
+            }
+        })
+        .max_by_key(|entry| entry.started_at)
+}
+
+fn history_path(version: &str) -> Result<PathBuf, String> {
+    history_path_in(&file_ops::bmcbl_subdir(LAUNCH_HISTORY_DIR), version)
+}
+
+fn history_path_in(dir: &Path, version: &str) -> Result<PathBuf, String> {
+    if version.trim().is_empty()
+        || version.contains("..")
+        || version.contains('/')
+        || version.contains('\\')
+    {
+        return Err(format!("无效的版本名称: {}", version));
+    }
+    Ok(dir.join(format!("{version}.json")))
+}
+
+fn load_entries(path: &Path) -> Result<Vec<LaunchHistoryEntry>, String> {
+    let raw = match fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("读取启动历史失败 {}: {}", path.display(), e)),
+    };
+    serde_json::from_str(&raw).map_err(|e| format!("解析启动历史失败 {}: {}", path.display(), e))
+}
+
+/// 记录延迟注入报告：写入对应的历史记录并广播 `inject-summary`
+pub fn record_injection_report(version: String, history_id: String, report: InjectionReport) {
+    let _ = INJECT_SUMMARY_EVENTS.send(InjectSummaryEvent {
+        version: version.clone(),
+        history_id: history_id.clone(),
+        report: report.clone(),
+    });
+    tokio::task::spawn_blocking(move || {
+        let mut pending = HISTORY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let result = history_path(&version)
+            .and_then(|path| attach_report_at(&path, &history_id, report, &mut pending));
+        if let Err(e) = result {
+            warn!("写入注入报告失败: {}", e);
+        }
+    });
+}
+
+fn append_entry(entry: LaunchHistoryEntry) -> Result<(), String> {
+    let mut pending = HISTORY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = history_path(&entry.version)?;
+    append_entry_at(&path, entry, &mut pending)
+}
+
+fn append_entry_at(
+    path: &Path,
+    mut entry: LaunchHistoryEntry,
+    pending_reports: &mut VecDeque<(String, InjectionReport)>,
+) -> Result<(), String> {
+    let mut entries = load_entries(path).unwrap_or_else(|e| {
+        warn!("{}，将重新创建启动历史", e);
+        Vec::new()
+    });
+    if let Some(index) = pending_reports.iter().position(|(id, _)| *id == entry.id)
+        && let Some((_, report)) = pending_reports.remove(index)
+    {
+        entry.injection_report = Some(report);
+    }
+    entries.push(entry);
+    if entries.len() > MAX_HISTORY_ENTRIES {
+        let overflow = entries.len() - MAX_HISTORY_ENTRIES;
+        entries.drain(..overflow);
+    }
+    save_entries(path, &entries)
+}
+
+/// 历史记录已写入时直接更新，否则先暂存，等 `append_entry_at` 写入时带上
+fn attach_report_at(
+    path: &Path,
+    history_id: &str,
+    report: InjectionReport,
+    pending_reports: &mut VecDeque<(String, InjectionReport)>,
+) -> Result<(), String> {
+    let mut entries = load_entries(path)?;
+    match entries.iter_mut().find(|entry| entry.id == history_id) {
+        Some(entry) => {
+            entry.injection_report = Some(report);
+            save_entries(path, &entries)
+        }
+        None => {
+            pending_reports.retain(|(id, _)| id != history_id);
+            if pending_reports.len() >= MAX_PENDING_REPORTS {
+                pending_reports.pop_front();
+            }
+            pending_reports.push_back((history_id.to_string(), report));
+            Ok(())
+        }
+    }
+}
+
+fn save_entries(path: &Path, entries: &[LaunchHistoryEntry]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("创建目录 {} 失败: {}", parent.display(), e))?;
+    }
+    let raw = serde_json::to_string_pretty(entries).map_err(|e| e.to_string())?;
+    fs::write(path, raw).map_err(|e| format!("写入 {} 失败: {}", path.display(), e))
+}
+
+fn describe_dll(scheduled: &ScheduledDll) -> Option<InjectedDllRecord> {
+    let metadata = match fs::metadata(&scheduled.path) {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            warn!("读取 DLL 信息失败 {}: {}", scheduled.path.display(), e);
+            return None;
+        }
+    };
+    let size = metadata.len();
+    let sha256 = match metadata.modified() {
+        Ok(modified) => cached_sha256(&scheduled.path, modified, size),
+        Err(_) => sha256_file(&scheduled.path),
+    };
+    let sha256 = match sha256 {
+        Ok(sha256) => sha256,
+        Err(e) => {
+            warn!("计算 DLL 哈希失败 {}: {}", scheduled.path.display(), e);
+            String::new()
+        }
+    };
+    let (file_version, product_name) = read_version_strings(&scheduled.path);
+
+    Some(InjectedDllRecord {
+        path: scheduled.path.to_string_lossy().to_string(),
+        size,
+        sha256,
+        file_version,
+        product_name,
+        delay_ms: scheduled.delay_ms,
+    })
+}
+
+fn cached_sha256(path: &Path, modified: SystemTime, size: u64) -> Result<String, String> {
+    let key = (path.to_path_buf(), modified, size);
+    if let Ok(cache) = DLL_HASH_CACHE.lock()
+        && let Some(hash) = cache.get(&key)
+    {
+        return Ok(hash.clone());
+    }
+
+    let hash = sha256_file(path)?;
+    if let Ok(mut cache) = DLL_HASH_CACHE.lock() {
+        cache.insert(key, hash.clone());
+    }
+    Ok(hash)
+}
+
+pub(crate) fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file = File::open(path).map_err(|e| e.to_string())?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; HASH_BUFFER_SIZE];
+    loop {
+        let read = file.read(&mut buffer).map_err(|e| e.to_string())?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// 读取 PE 版本资源中的 FileVersion / ProductName；没有版本资源时返回 None
+fn read_version_strings(path: &Path) -> (Option<String>, Option<String>) {
+    let Ok(file_map) = FileMap::open(path) else {
+        return (None, None);
+    };
+    let Ok(pe_file) = PeFile::from_bytes(file_map.as_ref()) else {
+        debug!("非 64 位 PE 或解析失败，跳过版本资源: {}", path.display());
+        return (None, None);
+    };
+    let Some(version_info) = pe_file
+        .resources()
+        .ok()
+        .and_then(|resources| resources.version_info().ok())
+    else {
+        return (None, None);
+    };
+
+    let language = version_info.translation().first().copied();
+    let file_version = language
+        .and_then(|language| version_info.value(language, "FileVersion"))
+        .or_else(|| {
+            version_info.fixed().map(|fixed| {
+                format!(
+                    "{}.{}.{}.{}",
+                    fixed.dwFileVersion.Major,
+                    fixed.dwFileVersion.Minor,
+                    fixed.dwFileVersion.Patch,
+                    fixed.dwFileVersion.Build
+                )
+            })
+        });
+    let product_name = language.and_then(|language| version_info.value(language, "ProductName"));
+    (file_version, product_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_path_rejects_versions_that_leave_the_history_dir() {
+        let dir = Path::new("launch_history");
+        assert_eq!(
+            history_path_in(dir, "1.21.50").expect("plain version name"),
+            dir.join("1.21.50.json")
+        );
+        for version in ["", " ", "..", "../config", "a/b", "a\\b", "..\\b"] {
+            assert!(history_path_in(dir, version).is_err(), "{version:?}");
+        }
+    }
+
+    #[test]
+    fn sha256_file_matches_known_digest() {
+        let path = std::env::temp_dir().join(format!(
+            "bmcbl-launch-history-test-{}.dll",
+            SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .expect("system time should be after unix epoch")
+                .as_nanos()
+        ));
+        fs::write(&path, b"abc").expect("write test file");
+
+        assert_eq!(
+            sha256_file(&path).expect("hash test file"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+
+        fs::remove_file(&path).expect("remove test file");
+    }
+
+    fn test_entry(id: &str) -> LaunchHistoryEntry {
+        LaunchHistoryEntry {
+            id: id.to_string(),
+            version: "test".to_string(),
+            game_version: "1.21.0.0".to_string(),
+            started_at: 0,
+            pid: 42,
+            launch_duration_ms: 0,
+            mod_loading_disabled: false,
+            injected_dlls: Vec::new(),
+            injection_report: None,
+        }
+    }
+
+    fn test_report(duration_ms: u64) -> InjectionReport {
+        InjectionReport {
+            pid: 42,
+            records: vec![InjectionRecord {
+                dll_name: "a.dll".to_string(),
+                path: "mods/a.dll".to_string(),
+                delay_ms: 1000,
+                started_at_ms: 1003,
+                duration_ms,
+                outcome: InjectionOutcome::TimedOut,
+                error: None,
+            }],
+            total_duration_ms: duration_ms,
+        }
+    }
+
+    #[test]
+    fn injection_report_attaches_before_or_after_entry_is_written() {
+        // 目录不存在，首次写入时应自动创建
+        let dir =
+            std::env::temp_dir().join(format!("bmcbl-launch-history-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("test.json");
+        let mut pending = VecDeque::new();
+
+        // 报告先到：暂存，写入历史时带上
+        attach_report_at(&path, "early", test_report(5), &mut pending).expect("stash report");
+        assert!(pending.iter().any(|(id, _)| id == "early"));
+        append_entry_at(&path, test_entry("early"), &mut pending).expect("append early");
+        assert!(pending.is_empty());
+
+        // 历史先写入：直接更新
+        append_entry_at(&path, test_entry("late"), &mut pending).expect("append late");
+        attach_report_at(&path, "late", test_report(7), &mut pending).expect("attach report");
+
+        let entries = load_entries(&path).expect("load entries");
+        let durations = entries
+            .iter()
+            .map(|entry| {
+                entry
+                    .injection_report
+                    .as_ref()
+                    .map(|report| report.total_duration_ms)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(durations, vec![Some(5), Some(7)]);
+        assert_eq!(
+            latest_entry_in(&dir).map(|entry| entry.id),
+            Some("late".to_string())
+        );
+
+        fs::remove_dir_all(&dir).expect("remove test history");
+    }
+
+    #[test]
+    fn pending_reports_are_bounded() {
+        let dir =
+            std::env::temp_dir().join(format!("bmcbl-launch-history-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("test.json");
+        let mut pending = VecDeque::new();
+
+        for index in 0..MAX_PENDING_REPORTS + 3 {
+            attach_report_at(&path, &format!("id-{index}"), test_report(1), &mut pending)
+                .expect("stash report");
+        }
+
+        assert_eq!(pending.len(), MAX_PENDING_REPORTS);
+        assert_eq!(pending.front().map(|(id, _)| id.as_str()), Some("id-3"));
+        assert!(!dir.exists());
+    }
+}
@@ -0,0 +1,258 @@
+//! 启动并发控制：同一版本同一时间只允许一个启动任务；映射到同一包标识的不同版本
+//! 在卸载 / 注册阶段排队，避免两次部署互相覆盖导致注册失败。
+
+use crate::tasks::task_manager::{append_task_log, cancel_task, is_cancelled, set_task_message};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::OwnedMutexGuard;
+use tracing::info;
+
+pub const LAUNCH_IN_PROGRESS_ERROR: &str = "launch_in_progress";
+/// 用户取消启动时启动流程返回的错误，与真正的启动失败区分
+pub const LAUNCH_CANCELLED_ERROR: &str = "launch_cancelled";
+/// 排队等待包标识期间检查取消的间隔
+pub(crate) const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+static ACTIVE_LAUNCHES: LazyLock<Mutex<HashMap<String, ActiveLaunch>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+static PACKAGE_LOCKS: LazyLock<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActiveLaunchState {
+    Preparing,
+    /// 等待同一包标识的其他启动完成注册
+    Queued,
+    Registering,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ActiveLaunch {
+    pub task_id: String,
+    pub folder_name: String,
+    pub display_name: String,
+    pub version: String,
+    pub state: ActiveLaunchState,
+    /// 开始时间（Unix 毫秒）
+    pub started_at: i64,
+}
+
+pub(crate) enum LaunchClaim {
+    Claimed(LaunchSlot),
+    InProgress { task_id: String },
+}
+
+/// 占用中的启动槽位，启动流程结束（含取消、中止）时随 drop 释放
+pub(crate) struct LaunchSlot {
+    folder_name: String,
+    task_id: String,
+}
+
+impl LaunchSlot {
+    pub(crate) fn task_id(&self) -> &str {
+        &self.task_id
+    }
+}
+
+impl Drop for LaunchSlot {
+    fn drop(&mut self) {
+        let mut launches = ACTIVE_LAUNCHES.lock().unwrap_or_else(|e| e.into_inner());
+        if launches
+            .get(&self.folder_name)
+            .is_some_and(|launch| launch.task_id == self.task_id)
+        {
+            launches.remove(&self.folder_name);
+        }
+    }
+}
+
+/// 为版本占用启动槽位；已有启动进行中时返回其任务 ID，`create_task` 只在占用成功时调用
+pub(crate) fn claim_launch(
+    folder_name: &str,
+    display_name: &str,
+    version: &str,
+    create_task: impl FnOnce() -> String,
+) -> LaunchClaim {
+    let mut launches = ACTIVE_LAUNCHES.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(existing) = launches.get(folder_name) {
+        info!(
+            folder_name,
+            task_id = %existing.task_id,
+            "该版本已有启动任务进行中"
+        );
+        return LaunchClaim::InProgress {
+            task_id: existing.task_id.clone(),
+        };
+    }
+
+    let task_id = create_task();
+    launches.insert(
+        folder_name.to_string(),
+        ActiveLaunch {
+            task_id: task_id.clone(),
+            folder_name: folder_name.to_string(),
+            display_name: display_name.to_string(),
+            version: version.to_string(),
+            state: ActiveLaunchState::Preparing,
+            started_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_millis() as i64)
+                .unwrap_or_default(),
+        },
+    );
+    LaunchClaim::Claimed(LaunchSlot {
+        folder_name: folder_name.to_string(),
+        task_id,
+    })
+}
+
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn set_launch_state(folder_name: &str, state: ActiveLaunchState) {
+    let mut launches = ACTIVE_LAUNCHES.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(launch) = launches.get_mut(folder_name) {
+        launch.state = state;
+    }
+}
+
+/// 获取包标识（包系列名）的注册锁；被其他启动占用时排队等待，期间任务显示为排队状态
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub(crate) async fn lock_package_identity(
+    package_family_name: &str,
+    folder_name: &str,
+    task_id: &str,
+) -> Result<OwnedMutexGuard<()>, String> {
+    let lock = PACKAGE_LOCKS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .entry(package_family_name.to_string())
+        .or_default()
+        .clone();
+
+    let guard = match lock.clone().try_lock_owned() {
+        Ok(guard) => guard,
+        Err(_) => {
+            set_launch_state(folder_name, ActiveLaunchState::Queued);
+            let line = format!("{package_family_name} 正在被其他版本注册，排队等待");
+            let _ = append_task_log(task_id, line.clone());
+            let _ = set_task_message(task_id, Some(line));
+            // 排队期间可以取消，不必等前一个版本注册完成
+            let mut waiting = std::pin::pin!(lock.lock_owned());
+            loop {
+                tokio::select! {
+                    guard = &mut waiting => break guard,
+                    _ = tokio::time::sleep(CANCEL_POLL_INTERVAL) => {
+                        if is_cancelled(task_id) {
+                            return Err(LAUNCH_CANCELLED_ERROR.to_string());
+                        }
+                    }
+                }
+            }
+        }
+    };
+    set_launch_state(folder_name, ActiveLaunchState::Registering);
+    Ok(guard)
+}
+
+/// 包标识当前是否被启动流程占用（正在卸载 / 注册），此时包的变化来自启动器自身
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub(crate) fn is_package_identity_locked(package_family_name: &str) -> bool {
+    PACKAGE_LOCKS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .any(|(family_name, lock)| {
+            family_name.eq_ignore_ascii_case(package_family_name) && lock.try_lock().is_err()
+        })
+}
+
+/// 取消版本进行中的启动，返回被取消的任务 ID。启动流程在阶段之间检查取消，
+/// 不会中断进行中的卸载或注册；游戏进程已拉起时不会结束游戏，只跳过后续步骤
+pub fn cancel_launch(folder_name: String) -> Result<String, String> {
+    let task_id = ACTIVE_LAUNCHES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&folder_name)
+        .map(|launch| launch.task_id.clone())
+        .ok_or_else(|| format!("{folder_name} 没有进行中的启动"))?;
+    info!(folder_name, task_id = %task_id, "用户取消启动");
+    cancel_task(&task_id);
+    Ok(task_id)
+}
+
+/// 列出进行中的启动，界面重载后据此禁用对应的启动按钮
+pub fn get_active_launches() -> Vec<ActiveLaunch> {
+    let launches = ACTIVE_LAUNCHES.lock().unwrap_or_else(|e| e.into_inner());
+    let mut active: Vec<ActiveLaunch> = launches.values().cloned().collect();
+    active.sort_by_key(|launch| launch.started_at);
+    active
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_claim_for_same_version_returns_running_task() {
+        let folder_name = format!("launch-guard-test-{}", uuid::Uuid::new_v4());
+        let LaunchClaim::Claimed(slot) =
+            claim_launch(&folder_name, "Test", "1.21.0.3", || "task-a".to_string())
+        else {
+            panic!("first claim should succeed");
+        };
+
+        let second = claim_launch(&folder_name, "Test", "1.21.0.3", || {
+            panic!("task must not be created for a rejected launch")
+        });
+        assert!(matches!(second, LaunchClaim::InProgress { ref task_id } if task_id == "task-a"));
+        assert!(
+            get_active_launches()
+                .iter()
+                .any(|launch| launch.folder_name == folder_name && launch.task_id == "task-a")
+        );
+
+        drop(slot);
+        assert!(
+            !get_active_launches()
+                .iter()
+                .any(|launch| launch.folder_name == folder_name)
+        );
+        assert!(matches!(
+            claim_launch(&folder_name, "Test", "1.21.0.3", || "task-b".to_string()),
+            LaunchClaim::Claimed(_)
+        ));
+    }
+
+    #[test]
+    fn cancel_launch_flags_the_active_task() {
+        let folder_name = format!("launch-guard-cancel-{}", uuid::Uuid::new_v4());
+        assert!(cancel_launch(folder_name.clone()).is_err());
+        let LaunchClaim::Claimed(slot) = claim_launch(&folder_name, "Test", "1.21.0.3", || {
+            crate::tasks::task_manager::create_task(None, "starting", None)
+        }) else {
+            panic!("claim should succeed");
+        };
+
+        let task_id = cancel_launch(folder_name).expect("cancel active launch");
+        assert_eq!(task_id, slot.task_id());
+        assert!(is_cancelled(&task_id));
+    }
+
+    #[tokio::test]
+    async fn package_identity_reports_locked_only_while_held() {
+        let family_name = format!("Test.Family_{}", uuid::Uuid::new_v4().simple());
+        assert!(!is_package_identity_locked(&family_name));
+
+        let guard = lock_package_identity(&family_name, "missing-folder", "missing-task")
+            .await
+            .expect("lock package identity");
+        assert!(is_package_identity_locked(
+            &family_name.to_ascii_lowercase()
+        ));
+
+        drop(guard);
+        assert!(!is_package_identity_locked(&family_name));
+    }
+}
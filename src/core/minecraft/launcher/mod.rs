@@ -1,5 +1,10 @@
+pub mod history;
+pub mod launch_guard;
 #[cfg(target_os = "windows")]
 pub mod preflight;
+pub mod progress_history;
+#[cfg(target_os = "windows")]
+pub mod smoke_test;
 #[cfg(target_os = "windows")]
 pub mod start;
 #[cfg(target_os = "windows")]
@@ -7,6 +12,10 @@ pub mod task;
 #[cfg(target_os = "linux")]
 #[path = "task_linux.rs"]
 pub mod task;
+pub use launch_guard::{ActiveLaunch, cancel_launch, get_active_launches};
+pub use progress_history::{LaunchProgressHistory, get_launch_progress_history};
+#[cfg(target_os = "windows")]
+pub use smoke_test::{SmokeTestReport, smoke_test_version};
 #[cfg(target_os = "windows")]
 pub use start::{launch_uwp, wait_for_uwp_pid};
-pub use task::{LaunchRequest, start_launch_task};
+pub use task::{LaunchRequest, start_launch_task, try_start_launch_task};
@@ -0,0 +1,235 @@
+//! 启动进度回放：为每次启动保留一份阶段事件环形缓冲（阶段、状态、消息、错误代码、时间戳），
+//! 启动中途才打开的界面或重连的前端可以通过 `get_launch_progress_history` 补齐错过的事件，
+//! 不必等下一次任务更新才知道当前进度。事件来自任务管理器的更新广播，只记录已登记的启动任务，
+//! 阶段、状态和消息都未变化的更新（如下载字节数推进）不重复记录。
+
+use crate::tasks::task_manager::{TaskSnapshot, get_snapshot_arc, subscribe_task_updates};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex, Once};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::warn;
+
+/// 单次启动最多保留的事件数，超出后丢弃最早的事件
+const MAX_EVENTS_PER_LAUNCH: usize = 64;
+/// 最多保留最近几次启动的历史
+const MAX_LAUNCHES: usize = 8;
+
+static NEXT_LAUNCH_ID: AtomicU64 = AtomicU64::new(1);
+static LAUNCH_HISTORIES: LazyLock<Mutex<VecDeque<LaunchProgressHistory>>> =
+    LazyLock::new(|| Mutex::new(VecDeque::new()));
+static RECORDER_STARTED: Once = Once::new();
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LaunchProgressEvent {
+    pub stage: String,
+    /// "running" | "completed" | "cancelled" | "error" 等任务状态
+    pub status: String,
+    pub message: Option<String>,
+    /// 可由前端识别的错误代码，如 `launch_cancelled`、`elevation_required`
+    pub code: Option<String>,
+    /// Unix 毫秒
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LaunchProgressHistory {
+    /// 每次启动递增，前端据此判断是否已是同一次启动
+    pub launch_id: u64,
+    pub task_id: String,
+    pub folder_name: String,
+    pub finished: bool,
+    pub events: VecDeque<LaunchProgressEvent>,
+}
+
+impl LaunchProgressHistory {
+    fn push(&mut self, event: LaunchProgressEvent) {
+        let unchanged = self.events.back().is_some_and(|last| {
+            last.stage == event.stage
+                && last.status == event.status
+                && last.message == event.message
+        });
+        if unchanged {
+            return;
+        }
+        if self.events.len() >= MAX_EVENTS_PER_LAUNCH {
+            self.events.pop_front();
+        }
+        self.finished = is_terminal_status(&event.status);
+        self.events.push_back(event);
+    }
+}
+
+fn is_terminal_status(status: &str) -> bool {
+    matches!(status, "completed" | "cancelled" | "error")
+}
+
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+fn error_code(status: &str, message: Option<&str>) -> Option<String> {
+    if status == "cancelled" {
+        return Some(super::launch_guard::LAUNCH_CANCELLED_ERROR.to_string());
+    }
+    let message = message.filter(|_| status == "error")?;
+    #[cfg(target_os = "windows")]
+    if crate::utils::elevation::ElevationRequired::parse(message).is_some() {
+        return Some("elevation_required".to_string());
+    }
+    (message == super::launch_guard::LAUNCH_IN_PROGRESS_ERROR).then(|| message.to_string())
+}
+
+fn event_from_snapshot(snapshot: &TaskSnapshot) -> LaunchProgressEvent {
+    let message = snapshot.message.as_deref().map(str::to_string);
+    LaunchProgressEvent {
+        stage: snapshot.stage.to_string(),
+        status: snapshot.status.to_string(),
+        code: error_code(&snapshot.status, message.as_deref()),
+        message,
+        timestamp: now_millis(),
+    }
+}
+
+fn insert_launch(
+    histories: &mut VecDeque<LaunchProgressHistory>,
+    task_id: &str,
+    folder_name: &str,
+) -> u64 {
+    let launch_id = NEXT_LAUNCH_ID.fetch_add(1, Ordering::Relaxed);
+    // 同一版本重新启动时丢弃上一次的缓冲
+    histories.retain(|history| history.folder_name != folder_name);
+    if histories.len() >= MAX_LAUNCHES {
+        histories.pop_front();
+    }
+    histories.push_back(LaunchProgressHistory {
+        launch_id,
+        task_id: task_id.to_string(),
+        folder_name: folder_name.to_string(),
+        finished: false,
+        events: VecDeque::new(),
+    });
+    launch_id
+}
+
+fn record_snapshot(histories: &mut VecDeque<LaunchProgressHistory>, snapshot: &TaskSnapshot) {
+    if let Some(history) = histories
+        .iter_mut()
+        .find(|history| history.task_id == snapshot.id.as_ref())
+    {
+        history.push(event_from_snapshot(snapshot));
+    }
+}
+
+/// 广播积压丢失更新后，按当前快照补记未结束的启动
+fn resync_unfinished(histories: &mut VecDeque<LaunchProgressHistory>) {
+    for history in histories.iter_mut().filter(|history| !history.finished) {
+        if let Some(snapshot) = get_snapshot_arc(&history.task_id) {
+            history.push(event_from_snapshot(&snapshot));
+        }
+    }
+}
+
+fn ensure_recorder() {
+    RECORDER_STARTED.call_once(|| {
+        let mut updates = subscribe_task_updates();
+        tokio::spawn(async move {
+            loop {
+                match updates.recv().await {
+                    Ok(snapshot) => record_snapshot(
+                        &mut LAUNCH_HISTORIES.lock().unwrap_or_else(|e| e.into_inner()),
+                        &snapshot,
+                    ),
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!(skipped, "启动进度记录落后，已按当前快照补记");
+                        resync_unfinished(
+                            &mut LAUNCH_HISTORIES.lock().unwrap_or_else(|e| e.into_inner()),
+                        );
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+    });
+}
+
+/// 启动任务创建后调用：分配新的启动 ID，清空该版本上一次的事件并记下初始阶段
+pub(crate) fn begin_launch(task_id: &str, folder_name: &str) {
+    ensure_recorder();
+    let mut histories = LAUNCH_HISTORIES.lock().unwrap_or_else(|e| e.into_inner());
+    insert_launch(&mut histories, task_id, folder_name);
+    if let Some(snapshot) = get_snapshot_arc(task_id) {
+        record_snapshot(&mut histories, &snapshot);
+    }
+}
+
+/// 返回指定启动任务的进度历史，未指定时返回最近一次启动；没有记录时为 `None`
+pub fn get_launch_progress_history(
+    task_id: Option<String>,
+) -> Result<Option<LaunchProgressHistory>, String> {
+    let histories = LAUNCH_HISTORIES.lock().unwrap_or_else(|e| e.into_inner());
+    let history = match task_id.filter(|task_id| !task_id.trim().is_empty()) {
+        Some(task_id) => histories
+            .iter()
+            .find(|history| history.task_id == task_id.trim()),
+        None => histories.back(),
+    };
+    Ok(history.cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(stage: &str, status: &str, message: &str) -> LaunchProgressEvent {
+        LaunchProgressEvent {
+            stage: stage.to_string(),
+            status: status.to_string(),
+            message: Some(message.to_string()),
+            code: None,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn keeps_latest_events_and_resets_on_relaunch() {
+        let mut histories = VecDeque::new();
+        let first = insert_launch(&mut histories, "task-a", "1.21.0");
+        let history = histories.back_mut().expect("history");
+        history.push(event("starting", "running", "准备启动"));
+        history.push(event("starting", "running", "准备启动"));
+        assert_eq!(history.events.len(), 1);
+        for index in 0..MAX_EVENTS_PER_LAUNCH {
+            history.push(event("registering", "running", &index.to_string()));
+        }
+        assert_eq!(history.events.len(), MAX_EVENTS_PER_LAUNCH);
+        assert_eq!(history.events[0].message.as_deref(), Some("0"));
+        history.push(event("registering", "error", "注册失败"));
+        assert!(history.finished);
+
+        let second = insert_launch(&mut histories, "task-b", "1.21.0");
+        assert!(second > first);
+        assert_eq!(histories.len(), 1);
+        assert!(histories[0].events.is_empty());
+        for index in 0..MAX_LAUNCHES {
+            insert_launch(&mut histories, &format!("task-{index}"), &index.to_string());
+        }
+        assert_eq!(histories.len(), MAX_LAUNCHES);
+        assert!(histories.iter().all(|history| history.task_id != "task-b"));
+
+        assert_eq!(
+            error_code(
+                "error",
+                Some(super::super::launch_guard::LAUNCH_IN_PROGRESS_ERROR)
+            )
+            .as_deref(),
+            Some("launch_in_progress")
+        );
+        assert_eq!(error_code("running", Some("launch_in_progress")), None);
+    }
+}
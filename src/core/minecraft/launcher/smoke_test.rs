@@ -0,0 +1,777 @@
+#![cfg(target_os = "windows")]
+//! 新版本冒烟测试：不经过界面，依次执行版本校验、注册、安全模式启动、等待窗口、
+//! PrintWindow 截图和关闭游戏，返回各阶段耗时。截图保存在 `BMCBL/smoke/<版本>/`。
+//! 运行期间占用该版本的启动槽位，用户不能同时启动同一版本；
+//! 临时关闭的 GameDVR 和启用的包调试模式在结束（含取消、失败）时恢复。
+
+use crate::config::config::read_config;
+use crate::core::minecraft::appx::utils::get_manifest_identity;
+use crate::core::minecraft::launcher::launch_guard::{
+    LAUNCH_IN_PROGRESS_ERROR, LaunchClaim, claim_launch,
+};
+use crate::core::minecraft::launcher::preflight::{
+    LaunchPlatform, LaunchPrerequisiteCheck, check_launch_prerequisites, detect_launch_platform,
+};
+use crate::core::minecraft::launcher::start::process_alive;
+use crate::core::minecraft::launcher::task::{
+    LaunchRequest, identity_to_aumid, launch_game, register_launcher_task_stage_labels,
+};
+use crate::core::minecraft::paths::local_appdata_dir;
+use crate::core::minecraft::uwp_minimize_fix::{
+    disable_debugging_for_package, enable_debugging_for_package,
+};
+use crate::core::version::integrity::quick_check_version_integrity;
+use crate::core::version::launch_versions::{InstallKind, LaunchVersionEntry};
+use crate::core::version::packaged::read_packaged_install;
+use crate::tasks::task_manager::{
+    append_task_log, create_task_with_details, finish_task, is_cancelled,
+    register_task_stage_labels, reset_progress, set_task_message, update_progress,
+};
+use crate::utils::file_ops;
+use serde::Serialize;
+use std::ffi::c_void;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime};
+use tokio::time::sleep;
+use tracing::{info, warn};
+use windows::Win32::Foundation::{CloseHandle, FALSE, HWND, LPARAM, RECT, TRUE, WPARAM};
+use windows::Win32::Graphics::Gdi::{
+    BI_RGB, BITMAPINFO, BITMAPINFOHEADER, CreateCompatibleBitmap, CreateCompatibleDC,
+    DIB_RGB_COLORS, DeleteDC, DeleteObject, GetDC, GetDIBits, ReleaseDC, SelectObject,
+};
+use windows::Win32::Storage::Xps::{PRINT_WINDOW_FLAGS, PrintWindow};
+use windows::Win32::System::Threading::{OpenProcess, PROCESS_TERMINATE, TerminateProcess};
+use windows::Win32::UI::WindowsAndMessaging::{
+    EnumChildWindows, EnumWindows, GetClassNameW, GetWindowRect, GetWindowThreadProcessId,
+    IsWindowVisible, PostMessageW, SMTO_ABORTIFHUNG, SendMessageTimeoutW, WM_CLOSE, WM_NULL,
+};
+use windows::core::BOOL;
+use winreg::RegKey;
+use winreg::enums::{HKEY_CURRENT_USER, KEY_SET_VALUE};
+
+const SMOKE_TASK_STAGE_LABELS: [(&str, &str); 4] = [
+    ("validating", "校验中"),
+    ("waiting_window", "等待游戏窗口"),
+    ("capturing", "截图中"),
+    ("terminating", "关闭游戏"),
+];
+const SMOKE_DIR_NAME: &str = "smoke";
+const DEFAULT_SMOKE_TIMEOUT_SECS: u64 = 120;
+const WINDOW_POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// 窗口能响应消息后再等一会儿，让游戏越过启动画面
+const WINDOW_SETTLE_DELAY: Duration = Duration::from_secs(5);
+const WINDOW_RESPONSE_TIMEOUT_MS: u32 = 2000;
+const GRACEFUL_EXIT_TIMEOUT: Duration = Duration::from_secs(10);
+/// PrintWindow 的 PW_RENDERFULLCONTENT，DirectX 绘制的内容也能截到
+const PW_RENDERFULLCONTENT: u32 = 0x2;
+/// 冒烟测试期间关闭的 GameDVR 开关，避免 Xbox Game Bar 弹窗挡住截图
+const GAME_DVR_VALUES: [(&str, &str); 2] = [
+    ("System\\GameConfigStore", "GameDVR_Enabled"),
+    (
+        "Software\\Microsoft\\Windows\\CurrentVersion\\GameDVR",
+        "AppCaptureEnabled",
+    ),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmokePhase {
+    Validate,
+    Register,
+    Launch,
+    WaitForWindow,
+    Screenshot,
+    Terminate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmokePhaseStatus {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SmokePhaseReport {
+    pub phase: SmokePhase,
+    pub status: SmokePhaseStatus,
+    pub duration_ms: u64,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SmokeTestReport {
+    pub version_name: String,
+    pub task_id: String,
+    pub success: bool,
+    pub cancelled: bool,
+    /// 失败或取消时的原因
+    pub error: Option<String>,
+    pub phases: Vec<SmokePhaseReport>,
+    pub total_duration_ms: u64,
+    pub pid: Option<u32>,
+    pub screenshot_path: Option<String>,
+    /// 运行期间新产生的游戏崩溃转储，已复制到截图目录
+    pub crash_dumps: Vec<String>,
+}
+
+impl SmokeTestReport {
+    fn record<T>(
+        &mut self,
+        phase: SmokePhase,
+        started: Instant,
+        result: Result<(T, Option<String>), String>,
+    ) -> Result<T, String> {
+        let duration_ms = started.elapsed().as_millis() as u64;
+        let (status, message, result) = match result {
+            Ok((value, message)) => (SmokePhaseStatus::Passed, message, Ok(value)),
+            Err(error) => (SmokePhaseStatus::Failed, Some(error.clone()), Err(error)),
+        };
+        self.phases.push(SmokePhaseReport {
+            phase,
+            status,
+            duration_ms,
+            message,
+        });
+        result
+    }
+
+    fn skip(&mut self, phase: SmokePhase, message: &str) {
+        self.phases.push(SmokePhaseReport {
+            phase,
+            status: SmokePhaseStatus::Skipped,
+            duration_ms: 0,
+            message: Some(message.to_string()),
+        });
+    }
+}
+
+/// 对已安装的版本执行一次无人值守的冒烟测试；`timeout_secs` 为等待游戏窗口的时间，0 使用默认值。
+/// 测试失败时仍返回报告，只有找不到版本或该版本正在启动时返回错误
+pub async fn smoke_test_version(
+    version_name: String,
+    timeout_secs: u64,
+) -> Result<SmokeTestReport, String> {
+    let entry = crate::core::version::api::get_version_list()
+        .await
+        .map_err(|e| format!("获取版本列表失败: {}", e))?
+        .into_iter()
+        .find(|entry| entry.folder.as_ref() == version_name || entry.name.as_ref() == version_name)
+        .ok_or_else(|| format!("未找到版本: {}", version_name))?;
+
+    let slot = match claim_launch(&entry.folder, &entry.name, &entry.version, || {
+        create_smoke_task(&entry)
+    }) {
+        LaunchClaim::Claimed(slot) => slot,
+        LaunchClaim::InProgress { .. } => return Err(LAUNCH_IN_PROGRESS_ERROR.to_string()),
+    };
+    let task_id = slot.task_id().to_string();
+    let timeout = Duration::from_secs(if timeout_secs == 0 {
+        DEFAULT_SMOKE_TIMEOUT_SECS
+    } else {
+        timeout_secs
+    });
+
+    let run_started = Instant::now();
+    let run_started_at = SystemTime::now();
+    let output_dir = file_ops::bmcbl_subdir(SMOKE_DIR_NAME).join(entry.folder.as_ref());
+    let mut report = SmokeTestReport {
+        version_name: entry.folder.to_string(),
+        task_id: task_id.clone(),
+        success: false,
+        cancelled: false,
+        error: None,
+        phases: Vec::new(),
+        total_duration_ms: 0,
+        pid: None,
+        screenshot_path: None,
+        crash_dumps: Vec::new(),
+    };
+    info!(task_id = %task_id, version = %entry.folder, "开始冒烟测试");
+
+    let result = run_smoke_test(&entry, &task_id, timeout, &output_dir, &mut report).await;
+    drop(slot);
+
+    report.crash_dumps = collect_crash_dumps(run_started_at, &output_dir);
+    for dump in &report.crash_dumps {
+        append_log(&task_id, format!("已收集崩溃转储: {dump}"));
+    }
+    report.total_duration_ms = run_started.elapsed().as_millis() as u64;
+    match result {
+        Ok(()) => {
+            report.success = true;
+            finish_task(&task_id, "completed", Some("冒烟测试通过".to_string()));
+        }
+        Err(error) if is_cancelled(&task_id) => {
+            report.cancelled = true;
+            report.error = Some(error);
+            finish_task(&task_id, "cancelled", Some("冒烟测试已取消".to_string()));
+        }
+        Err(error) => {
+            warn!(task_id = %task_id, "冒烟测试未通过: {error}");
+            append_log(&task_id, format!("冒烟测试未通过: {error}"));
+            report.error = Some(error.clone());
+            finish_task(&task_id, "error", Some(error));
+        }
+    }
+    Ok(report)
+}
+
+fn create_smoke_task(entry: &LaunchVersionEntry) -> String {
+    register_launcher_task_stage_labels();
+    register_task_stage_labels(SMOKE_TASK_STAGE_LABELS);
+    create_task_with_details(
+        None,
+        format!("冒烟测试 {}", entry.name),
+        Some(entry.version.to_string()),
+        "validating",
+        None,
+        false,
+    )
+}
+
+async fn run_smoke_test(
+    entry: &LaunchVersionEntry,
+    task_id: &str,
+    timeout: Duration,
+    output_dir: &Path,
+    report: &mut SmokeTestReport,
+) -> Result<(), String> {
+    set_stage(task_id, "validating", "校验版本文件和运行依赖");
+    let started = Instant::now();
+    let result = validate_version(entry).await.map(|message| ((), message));
+    report.record(SmokePhase::Validate, started, result)?;
+    check_cancelled(task_id)?;
+
+    // 启动器显示方式只在界面启动流程中生效，这里直接调用启动流程，启动器始终保持打开
+    let request = LaunchRequest {
+        safe_mode: true,
+        ..LaunchRequest::new(
+            entry.folder.as_ref(),
+            entry.name.as_ref(),
+            entry.version.as_ref(),
+            entry.path.as_ref(),
+        )
+    };
+    let is_gdk = detect_launch_platform(&entry.kind) == LaunchPlatform::Gdk;
+    if is_gdk {
+        report.skip(SmokePhase::Register, "GDK 版本无需注册");
+    } else {
+        let started = Instant::now();
+        let prepare_only = LaunchRequest {
+            auto_start: false,
+            ..request.clone()
+        };
+        let result = launch_game(&prepare_only, task_id)
+            .await
+            .map(|_| ((), None));
+        report.record(SmokePhase::Register, started, result)?;
+    }
+    check_cancelled(task_id)?;
+
+    let _game_dvr = GameDvrOverride::disable();
+    let _debugging = if is_gdk {
+        None
+    } else {
+        PackageDebuggingOverride::enable(entry).await
+    };
+
+    reset_progress(task_id, None, Some("starting"));
+    let started = Instant::now();
+    let result = launch_game(&request, task_id).await.and_then(|launched| {
+        launched
+            .pid
+            .map(|pid| (pid, Some(format!("PID {pid}"))))
+            .ok_or_else(|| "启动流程未拉起游戏进程".to_string())
+    });
+    let pid = report.record(SmokePhase::Launch, started, result)?;
+    report.pid = Some(pid);
+    let mut game = GameProcess { pid, window: None };
+
+    set_stage(task_id, "waiting_window", "等待游戏窗口就绪");
+    let started = Instant::now();
+    let result = wait_for_game_window(pid, timeout, task_id)
+        .await
+        .map(|window| (window, None));
+    let window = report.record(SmokePhase::WaitForWindow, started, result)?;
+    game.window = Some(window);
+
+    set_stage(task_id, "capturing", "截取游戏窗口");
+    let started = Instant::now();
+    let screenshot_path = output_dir.join(format!(
+        "{}.png",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    let target = screenshot_path.clone();
+    let result = tokio::task::spawn_blocking(move || capture_window_png(window, &target))
+        .await
+        .map_err(|e| format!("截图任务失败: {}", e))
+        .and_then(|result| result)
+        .map(|blank| {
+            let message = blank.then(|| "截图为全黑，游戏可能仍在加载或画面未能捕获".to_string());
+            ((), message)
+        });
+    report.record(SmokePhase::Screenshot, started, result)?;
+    report.screenshot_path = Some(screenshot_path.to_string_lossy().to_string());
+    append_log(
+        task_id,
+        format!("截图已保存: {}", screenshot_path.display()),
+    );
+
+    set_stage(task_id, "terminating", "关闭游戏");
+    let started = Instant::now();
+    let result = game.close().await.map(|graceful| {
+        let message = (!graceful).then(|| "游戏未响应关闭请求，已强制结束".to_string());
+        ((), message)
+    });
+    report.record(SmokePhase::Terminate, started, result)
+}
+
+async fn validate_version(entry: &LaunchVersionEntry) -> Result<Option<String>, String> {
+    let kind = entry.kind.to_string();
+    let path = entry.path.to_string();
+    let check_integrity = entry.install_kind == InstallKind::Extracted;
+    let (check, integrity) = tokio::task::spawn_blocking(move || {
+        let check = check_launch_prerequisites(&kind, &path);
+        let integrity = check_integrity.then(|| quick_check_version_integrity(Path::new(&path)));
+        (check, integrity)
+    })
+    .await
+    .map_err(|e| format!("校验任务失败: {}", e))?;
+
+    if check.has_issues() {
+        return Err(describe_prerequisite_issues(&check));
+    }
+    match integrity {
+        Some(Ok(report)) if !report.is_clean() => Ok(Some(report.warning_lines().join("；"))),
+        Some(Err(error)) => Ok(Some(format!("完整性检查未完成: {error}"))),
+        _ => Ok(None),
+    }
+}
+
+/// 冒烟测试不会弹出安装向导，缺少的运行依赖直接判为失败
+fn describe_prerequisite_issues(check: &LaunchPrerequisiteCheck) -> String {
+    let mut issues = Vec::new();
+    if check.developer_mode_required {
+        issues.push("未开启开发者模式".to_string());
+    }
+    if !check.missing_uwp_dependencies.is_empty() {
+        issues.push(format!(
+            "缺少 {} 个 UWP 运行依赖",
+            check.missing_uwp_dependencies.len()
+        ));
+    }
+    if check.game_input_plan.is_some() {
+        issues.push("缺少 GameInput 运行库".to_string());
+    }
+    if check.windows_app_sdk_plan.is_some() {
+        issues.push("缺少 Windows App SDK 运行库".to_string());
+    }
+    format!("启动前检查未通过: {}", issues.join("，"))
+}
+
+fn append_log(task_id: &str, line: impl Into<String>) {
+    let line = line.into();
+    let _ = append_task_log(task_id, line.clone());
+    let _ = set_task_message(task_id, Some(line));
+}
+
+fn set_stage(task_id: &str, stage: &str, message: &str) {
+    append_log(task_id, message);
+    update_progress(task_id, 0, None, Some(stage));
+}
+
+fn check_cancelled(task_id: &str) -> Result<(), String> {
+    if is_cancelled(task_id) {
+        Err("冒烟测试已取消".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+/// 冒烟测试期间关闭 GameDVR，drop 时恢复原值（原本没有的值会被删除）
+struct GameDvrOverride {
+    previous: Vec<(&'static str, &'static str, Option<u32>)>,
+}
+
+impl GameDvrOverride {
+    fn disable() -> Self {
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        let mut previous = Vec::new();
+        for (key_path, value_name) in GAME_DVR_VALUES {
+            let Ok((key, _)) = hkcu.create_subkey(key_path) else {
+                continue;
+            };
+            let value = key.get_value::<u32, _>(value_name).ok();
+            if value == Some(0) {
+                continue;
+            }
+            match key.set_value(value_name, &0u32) {
+                Ok(()) => previous.push((key_path, value_name, value)),
+                Err(error) => warn!("关闭 {value_name} 失败: {error}"),
+            }
+        }
+        Self { previous }
+    }
+}
+
+impl Drop for GameDvrOverride {
+    fn drop(&mut self) {
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        for (key_path, value_name, value) in self.previous.drain(..) {
+            let result = hkcu
+                .open_subkey_with_flags(key_path, KEY_SET_VALUE)
+                .and_then(|key| match value {
+                    Some(value) => key.set_value(value_name, &value),
+                    None => key.delete_value(value_name),
+                });
+            if let Err(error) = result {
+                warn!("恢复 {value_name} 失败: {error}");
+            }
+        }
+    }
+}
+
+/// UWP 版本在后台时可能被系统挂起导致截图为空，测试期间为包启用调试模式；
+/// 用户已开启 `uwp_minimize_fix` 时由启动流程负责，这里不做改动
+struct PackageDebuggingOverride {
+    family_name: String,
+}
+
+impl PackageDebuggingOverride {
+    async fn enable(entry: &LaunchVersionEntry) -> Option<Self> {
+        if read_config().is_ok_and(|config| config.game.uwp_minimize_fix) {
+            return None;
+        }
+        let identity_name = match read_packaged_install(Path::new(entry.path.as_ref())) {
+            Some(install) => install.identity_name,
+            None => get_manifest_identity(&entry.path).await.ok()?.0,
+        };
+        let family_name = identity_to_aumid(&identity_name)
+            .split('!')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        match enable_debugging_for_package(&family_name) {
+            Ok(()) => Some(Self { family_name }),
+            Err(error) => {
+                warn!("为 {family_name} 启用调试模式失败: {error}");
+                None
+            }
+        }
+    }
+}
+
+impl Drop for PackageDebuggingOverride {
+    fn drop(&mut self) {
+        if let Err(error) = disable_debugging_for_package(&self.family_name) {
+            warn!("为 {} 关闭调试模式失败: {error}", self.family_name);
+        }
+    }
+}
+
+/// 冒烟测试拉起的游戏进程；中途失败或取消时 drop 会强制结束进程
+struct GameProcess {
+    pid: u32,
+    window: Option<isize>,
+}
+
+impl GameProcess {
+    /// 先向窗口发送关闭消息，超时仍未退出再强制结束；返回是否正常退出
+    async fn close(&mut self) -> Result<bool, String> {
+        if let Some(window) = self.window {
+            // SAFETY: 窗口句柄来自 EnumWindows，已失效时 PostMessageW 仅返回错误
+            let _ = unsafe { PostMessageW(Some(hwnd(window)), WM_CLOSE, WPARAM(0), LPARAM(0)) };
+            let deadline = Instant::now() + GRACEFUL_EXIT_TIMEOUT;
+            while Instant::now() < deadline {
+                if !process_alive(self.pid) {
+                    return Ok(true);
+                }
+                sleep(WINDOW_POLL_INTERVAL).await;
+            }
+        }
+        terminate_process(self.pid)?;
+        Ok(false)
+    }
+}
+
+impl Drop for GameProcess {
+    fn drop(&mut self) {
+        if process_alive(self.pid)
+            && let Err(error) = terminate_process(self.pid)
+        {
+            warn!(pid = self.pid, "结束冒烟测试游戏进程失败: {error}");
+        }
+    }
+}
+
+fn hwnd(raw: isize) -> HWND {
+    HWND(raw as *mut c_void)
+}
+
+fn terminate_process(pid: u32) -> Result<(), String> {
+    // SAFETY: 句柄仅用于结束进程，使用后立即关闭
+    unsafe {
+        let handle = OpenProcess(PROCESS_TERMINATE, false, pid)
+            .map_err(|e| format!("打开游戏进程失败: {}", e))?;
+        let result = TerminateProcess(handle, 1).map_err(|e| format!("结束游戏进程失败: {}", e));
+        let _ = CloseHandle(handle);
+        result
+    }
+}
+
+async fn wait_for_game_window(pid: u32, timeout: Duration, task_id: &str) -> Result<isize, String> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        check_cancelled(task_id)?;
+        if !process_alive(pid) {
+            return Err(format!("游戏进程在窗口就绪前退出（PID {pid}）"));
+        }
+        if let Some(window) = find_game_window(pid)
+            && window_responds(window)
+        {
+            sleep(WINDOW_SETTLE_DELAY).await;
+            check_cancelled(task_id)?;
+            if !process_alive(pid) {
+                return Err(format!("游戏进程在窗口出现后退出（PID {pid}）"));
+            }
+            return Ok(window);
+        }
+        if Instant::now() >= deadline {
+            return Err(format!("{} 秒内未检测到游戏窗口", timeout.as_secs()));
+        }
+        sleep(WINDOW_POLL_INTERVAL).await;
+    }
+}
+
+fn window_pid(hwnd: HWND) -> u32 {
+    let mut pid = 0u32;
+    // SAFETY: 仅查询窗口所属进程
+    unsafe { GetWindowThreadProcessId(hwnd, Some(&mut pid)) };
+    pid
+}
+
+fn class_name(hwnd: HWND) -> Option<String> {
+    let mut buffer = [0u16; 256];
+    // SAFETY: 缓冲区长度由切片传入
+    let len = unsafe { GetClassNameW(hwnd, &mut buffer) };
+    (len > 0).then(|| String::from_utf16_lossy(&buffer[..len as usize]))
+}
+
+fn has_visible_area(hwnd: HWND) -> bool {
+    let mut rect = RECT::default();
+    // SAFETY: 仅读取窗口外框
+    unsafe { GetWindowRect(hwnd, &mut rect) }.is_ok()
+        && rect.right > rect.left
+        && rect.bottom > rect.top
+}
+
+/// Win32 / GDK 版本是进程自己的顶层窗口；UWP 版本的顶层窗口属于 ApplicationFrameHost，
+/// 游戏进程的 CoreWindow 是它的子窗口
+fn find_game_window(pid: u32) -> Option<isize> {
+    struct Search {
+        pid: u32,
+        found: Option<isize>,
+    }
+
+    unsafe extern "system" fn enum_child(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        // SAFETY: lparam 指向调用方栈上的 Search
+        let search = unsafe { &mut *(lparam.0 as *mut Search) };
+        if window_pid(hwnd) == search.pid {
+            search.found = Some(hwnd.0 as isize);
+            return FALSE;
+        }
+        TRUE
+    }
+
+    unsafe extern "system" fn enum_top_level(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        // SAFETY: lparam 指向调用方栈上的 Search
+        let search = unsafe { &mut *(lparam.0 as *mut Search) };
+        if !unsafe { IsWindowVisible(hwnd) }.as_bool() || !has_visible_area(hwnd) {
+            return TRUE;
+        }
+        let owned = window_pid(hwnd) == search.pid
+            || (class_name(hwnd).as_deref() == Some("ApplicationFrameWindow") && {
+                let mut child = Search {
+                    pid: search.pid,
+                    found: None,
+                };
+                let _ = unsafe {
+                    EnumChildWindows(
+                        Some(hwnd),
+                        Some(enum_child),
+                        LPARAM(&mut child as *mut _ as isize),
+                    )
+                };
+                child.found.is_some()
+            });
+        if owned {
+            search.found = Some(hwnd.0 as isize);
+            return FALSE;
+        }
+        TRUE
+    }
+
+    let mut search = Search { pid, found: None };
+    // SAFETY: 回调只在 EnumWindows 返回前访问 search
+    let _ = unsafe { EnumWindows(Some(enum_top_level), LPARAM(&mut search as *mut _ as isize)) };
+    search.found
+}
+
+/// 窗口能在限定时间内处理消息，说明游戏没有卡在加载中
+fn window_responds(window: isize) -> bool {
+    // SAFETY: WM_NULL 不带参数，窗口失效时调用只会失败
+    let result = unsafe {
+        SendMessageTimeoutW(
+            hwnd(window),
+            WM_NULL,
+            WPARAM(0),
+            LPARAM(0),
+            SMTO_ABORTIFHUNG,
+            WINDOW_RESPONSE_TIMEOUT_MS,
+            None,
+        )
+    };
+    result.0 != 0
+}
+
+/// 用 PrintWindow 截取窗口并保存为 PNG，返回截图是否全黑
+fn capture_window_png(window: isize, path: &Path) -> Result<bool, String> {
+    let window = hwnd(window);
+    let mut rect = RECT::default();
+    // SAFETY: 仅读取窗口外框
+    unsafe { GetWindowRect(window, &mut rect) }.map_err(|e| format!("获取窗口大小失败: {}", e))?;
+    let width = rect.right - rect.left;
+    let height = rect.bottom - rect.top;
+    if width <= 0 || height <= 0 {
+        return Err("游戏窗口大小无效".to_string());
+    }
+
+    let mut pixels = print_window_bgra(window, width, height)?;
+    for pixel in pixels.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+        pixel[3] = u8::MAX;
+    }
+    let blank = is_blank_capture(&pixels);
+    let image = image::RgbaImage::from_raw(width as u32, height as u32, pixels)
+        .ok_or_else(|| "截图数据大小不匹配".to_string())?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("创建截图目录失败: {}", e))?;
+    }
+    image
+        .save_with_format(path, image::ImageFormat::Png)
+        .map_err(|e| format!("保存截图失败: {}", e))?;
+    Ok(blank)
+}
+
+fn print_window_bgra(window: HWND, width: i32, height: i32) -> Result<Vec<u8>, String> {
+    let mut pixels = vec![0u8; width as usize * height as usize * 4];
+    let mut info = BITMAPINFO {
+        bmiHeader: BITMAPINFOHEADER {
+            biSize: size_of::<BITMAPINFOHEADER>() as u32,
+            biWidth: width,
+            // 负高度表示自上而下的位图
+            biHeight: -height,
+            biPlanes: 1,
+            biBitCount: 32,
+            biCompression: BI_RGB.0,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    // SAFETY: 创建的 DC 和位图在本函数内释放，像素缓冲区大小与位图一致
+    unsafe {
+        let screen_dc = GetDC(None);
+        let memory_dc = CreateCompatibleDC(Some(screen_dc));
+        let bitmap = CreateCompatibleBitmap(screen_dc, width, height);
+        let previous = SelectObject(memory_dc, bitmap.into());
+        let printed =
+            PrintWindow(window, memory_dc, PRINT_WINDOW_FLAGS(PW_RENDERFULLCONTENT)).as_bool();
+        let lines = if printed {
+            GetDIBits(
+                memory_dc,
+                bitmap,
+                0,
+                height as u32,
+                Some(pixels.as_mut_ptr().cast()),
+                &mut info,
+                DIB_RGB_COLORS,
+            )
+        } else {
+            0
+        };
+        SelectObject(memory_dc, previous);
+        let _ = DeleteObject(bitmap.into());
+        let _ = DeleteDC(memory_dc);
+        ReleaseDC(None, screen_dc);
+
+        if !printed {
+            return Err("PrintWindow 截图失败".to_string());
+        }
+        if lines == 0 {
+            return Err("读取截图像素失败".to_string());
+        }
+    }
+    Ok(pixels)
+}
+
+fn is_blank_capture(rgba: &[u8]) -> bool {
+    rgba.chunks_exact(4).all(|pixel| pixel[..3] == [0, 0, 0])
+}
+
+fn is_game_crash_dump(file_name: &str) -> bool {
+    let lower = file_name.to_ascii_lowercase();
+    lower.starts_with("minecraft") && lower.ends_with(".dmp")
+}
+
+/// 收集 Windows 错误报告在 `%LOCALAPPDATA%\CrashDumps` 中新写入的游戏转储
+fn collect_crash_dumps(since: SystemTime, output_dir: &Path) -> Vec<String> {
+    let Some(dumps_dir) = local_appdata_dir()
+        .map(|dir| dir.join("CrashDumps"))
+        .filter(|dir| dir.is_dir())
+    else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(&dumps_dir) else {
+        return Vec::new();
+    };
+
+    let mut collected = Vec::new();
+    for entry in entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let is_new = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .is_ok_and(|modified| modified >= since);
+        if !is_new || !is_game_crash_dump(&file_name) {
+            continue;
+        }
+        let target = output_dir.join(&file_name);
+        let copied = fs::create_dir_all(output_dir).and_then(|_| fs::copy(entry.path(), &target));
+        match copied {
+            Ok(_) => collected.push(target.to_string_lossy().to_string()),
+            Err(error) => {
+                warn!("复制崩溃转储 {} 失败: {error}", entry.path().display());
+                collected.push(entry.path().to_string_lossy().to_string());
+            }
+        }
+    }
+    collected.sort();
+    collected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_blank_captures_and_game_dumps() {
+        assert!(is_blank_capture(&[0, 0, 0, 255, 0, 0, 0, 255]));
+        assert!(!is_blank_capture(&[0, 0, 0, 255, 12, 0, 0, 255]));
+        assert!(is_game_crash_dump("Minecraft.Windows.exe.12345.dmp"));
+        assert!(!is_game_crash_dump("explorer.exe.12345.dmp"));
+        assert!(!is_game_crash_dump("Minecraft.Windows.exe.log"));
+    }
+}
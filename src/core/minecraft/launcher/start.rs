@@ -18,7 +18,7 @@ use windows::Win32::System::Diagnostics::ToolHelp::{
     CreateToolhelp32Snapshot, PROCESSENTRY32W, Process32FirstW, Process32NextW, TH32CS_SNAPPROCESS,
 };
 use windows::Win32::System::Threading::{
-    CREATE_NO_WINDOW, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+    CREATE_NO_WINDOW, GetExitCodeProcess, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
 };
 use windows::Win32::UI::Shell::{
     ACTIVATEOPTIONS, ApplicationActivationManager, IApplicationActivationManager,
@@ -26,6 +26,22 @@ use windows::Win32::UI::Shell::{
 use windows::core::PWSTR;
 use windows::core::{HRESULT, HSTRING, PCWSTR, Result as WindowsResult};
 
+const STILL_ACTIVE: u32 = 259;
+
+/// 进程仍在运行（能打开且退出码为 STILL_ACTIVE）
+pub fn process_alive(pid: u32) -> bool {
+    // SAFETY: 句柄仅用于查询退出码，使用后立即关闭
+    unsafe {
+        let Ok(handle) = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) else {
+            return false;
+        };
+        let mut exit_code = 0u32;
+        let alive = GetExitCodeProcess(handle, &mut exit_code).is_ok() && exit_code == STILL_ACTIVE;
+        let _ = CloseHandle(handle);
+        alive
+    }
+}
+
 // 检查 PID 是否属于目标包
 pub fn is_process_in_package(pid: u32, target_family_name: &str) -> bool {
     unsafe {
@@ -210,19 +226,12 @@ fn launch_uwp_winapi(
 
 // 智能启动 UWP 并返回 PID (完整流程)
 pub async fn launch_uwp(edition: &str, launch_args: Option<&str>) -> io::Result<Option<u32>> {
-    let app_user_model_id = match edition {
-        "Microsoft.MinecraftUWP" => "Microsoft.MinecraftUWP_8wekyb3d8bbwe!App",
-        "Microsoft.MinecraftWindowsBeta" => "Microsoft.MinecraftWindowsBeta_8wekyb3d8bbwe!App",
-        "Microsoft.MinecraftEducationEdition" => {
-            "Microsoft.MinecraftEducationEdition_8wekyb3d8bbwe!Microsoft.MinecraftEducationEdition"
-        }
-        "Microsoft.MinecraftEducationPreview" => {
-            "Microsoft.MinecraftEducationPreview_8wekyb3d8bbwe!Microsoft.MinecraftEducationEdition"
-        }
-        _ => return Ok(None),
+    let Some(edition_entry) = crate::core::minecraft::editions::edition_by_identity(edition) else {
+        return Ok(None);
     };
+    let app_user_model_id = edition_entry.aumid.as_str();
 
-    let package_family_name = app_user_model_id.split('!').next().unwrap_or("");
+    let package_family_name = edition_entry.family_name();
     let target_exe_name = if edition.contains("Education") {
         "Minecraft.Education.exe"
     } else {
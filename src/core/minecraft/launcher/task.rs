@@ -1,23 +1,47 @@
 #![cfg(target_os = "windows")]
-use crate::config::config::read_config;
+use crate::config::config::{DEFAULT_INJECT_TIMEOUT_SECS, read_config};
 use crate::core::inject::inject::{
     grant_all_application_packages_access, inject_existing_process, launch_win32_with_injection,
 };
 use crate::core::inject::pe::{
     ensure_backup, inject_dll_import, is_file_patched, restore_original_pe,
 };
+use crate::core::inject::signature::retain_signed_dlls;
 use crate::core::minecraft::appx::register::register_appx_package_async;
 use crate::core::minecraft::appx::remove::remove_package;
 use crate::core::minecraft::appx::utils::{get_manifest_identity, get_package_info};
-use crate::core::minecraft::launcher::start::{launch_uwp_command_only, wait_for_uwp_pid};
-use crate::core::minecraft::mod_manager::load_mods_config;
+use crate::core::minecraft::editions::{edition_by_identity, edition_entry};
+use crate::core::minecraft::launcher::history::{
+    InjectionOutcome, InjectionRecord, InjectionReport, LaunchHistoryEntry, ScheduledDll,
+    publish_inject_result, record_injection_report, spawn_record_launch_session,
+};
+use crate::core::minecraft::launcher::launch_guard::{
+    CANCEL_POLL_INTERVAL, LAUNCH_CANCELLED_ERROR, LAUNCH_IN_PROGRESS_ERROR, LaunchClaim,
+    LaunchSlot, claim_launch, lock_package_identity,
+};
+use crate::core::minecraft::launcher::progress_history::begin_launch;
+use crate::core::minecraft::launcher::start::{
+    launch_uwp_command_only, process_alive, wait_for_uwp_pid,
+};
+use crate::core::minecraft::mod_manager::{
+    InjectRetryPolicy, load_extra_mods, load_inject_retry_policy, load_mods_config,
+};
 use crate::core::minecraft::mouse_lock::start_window_monitor;
+use crate::core::minecraft::pack_reverts::attach_pending_reverts_to_game;
+use crate::core::minecraft::paths::Edition;
+use crate::core::minecraft::usage_stats::record_game_session_start;
 use crate::core::minecraft::uwp_minimize_fix::enable_debugging_for_package;
-use crate::core::version::settings::get_version_config;
+use crate::core::version::integrity::launch_integrity_warnings;
+use crate::core::version::packaged::{
+    PackagedInstall, ensure_packaged_registered, read_packaged_install,
+};
+use crate::core::version::settings::{VersionConfig, get_version_config, save_version_config};
+use crate::core::version::version_info::{
+    check_launcher_version_compatibility, write_version_creation_metadata,
+};
 use crate::tasks::task_manager::{
     TaskControl, append_task_log, create_task_with_details, finish_task, is_cancelled,
-    register_task_abort_handle, set_task_labels, set_task_message, set_total, task_control,
-    update_progress,
+    set_task_labels, set_task_message, set_total, task_control, update_progress,
 };
 use pelite::pe64::{Pe, PeFile};
 use serde_json::{Value, json};
@@ -25,27 +49,32 @@ use std::cmp::Ordering;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::task::JoinHandle;
 use tokio::time::sleep;
-use tracing::{debug, error, info, warn};
+use tracing::{Instrument as _, debug, error, info, info_span, warn};
 use windows::Management::Deployment::PackageManager;
 use windows::core::HSTRING;
 
+use crate::utils::elevation::{
+    ElevatedOperation, ElevationRequired, ResumeAction, is_access_denied,
+};
 use crate::utils::file_ops;
 
 const INJECTOR_BYTES: &[u8] = include_bytes!("../../../../assets/bin/BLoader.dll");
 const LAUNCH_TOTAL_STEPS: u64 = 5;
 const BLOADER_DEFAULT_REDIRECTION_ROOT: &str = "Minecraft Bedrock";
-const LAUNCHER_TASK_STAGE_LABELS: [(&str, &str); 5] = [
+const LAUNCHER_TASK_STAGE_LABELS: [(&str, &str); 7] = [
     ("parsing", "解析中"),
+    ("integrity_warning", "版本文件已被修改"),
     ("preparing_files", "准备安装"),
     ("patching", "处理中"),
     ("initializing", "初始化中"),
     ("launching", "启动游戏"),
+    ("injecting", "注入模组"),
 ];
 
-fn register_launcher_task_stage_labels() {
+pub(super) fn register_launcher_task_stage_labels() {
     crate::tasks::task_manager::register_task_stage_labels(LAUNCHER_TASK_STAGE_LABELS);
 }
 
@@ -75,6 +104,30 @@ pub struct LaunchRequest {
     pub package_folder: Arc<str>,
     pub auto_start: bool,
     pub launch_args: Option<Arc<str>>,
+    /// 同一版本已在启动时接续到进行中的任务，而不是返回 `launch_in_progress`
+    pub join_existing: bool,
+    /// 安全模式：不加载模组、不锁定鼠标、忽略编辑器模式
+    pub safe_mode: bool,
+}
+
+/// 启动流程的结果；仅执行准备流程时 `pid` 为 `None`
+#[derive(Debug, Default)]
+pub struct LaunchResult {
+    pub pid: Option<u32>,
+    /// 拉起后仍在进行的延迟注入；启动任务不等待它，结果由单独的注入任务汇报
+    pub injection: Option<JoinHandle<InjectionReport>>,
+}
+
+/// 形如“，已注入 3/4 个模组”的摘要，没有启动后注入的 DLL 时为空
+fn injection_summary(records: &[InjectionRecord]) -> String {
+    if records.is_empty() {
+        return String::new();
+    }
+    let injected = records
+        .iter()
+        .filter(|record| record.outcome == InjectionOutcome::Injected)
+        .count();
+    format!("，已注入 {}/{} 个模组", injected, records.len())
 }
 
 impl LaunchRequest {
@@ -91,11 +144,41 @@ impl LaunchRequest {
             package_folder: Arc::from(package_folder.into()),
             auto_start: true,
             launch_args: None,
+            join_existing: false,
+            safe_mode: false,
         }
     }
 }
 
+/// 启动版本；该版本已有启动进行中时直接返回进行中的任务 ID，重复点击不会再次注册
 pub fn start_launch_task(request: LaunchRequest) -> String {
+    match claim_launch(
+        &request.folder_name,
+        &request.display_name,
+        &request.version,
+        || create_launch_task(&request),
+    ) {
+        LaunchClaim::Claimed(slot) => spawn_launch_task(request, slot),
+        LaunchClaim::InProgress { task_id } => task_id,
+    }
+}
+
+/// 与 [`start_launch_task`] 相同，但在该版本已有启动进行中且未设置 `join_existing` 时
+/// 返回 `launch_in_progress` 错误
+pub fn try_start_launch_task(request: LaunchRequest) -> Result<String, String> {
+    match claim_launch(
+        &request.folder_name,
+        &request.display_name,
+        &request.version,
+        || create_launch_task(&request),
+    ) {
+        LaunchClaim::Claimed(slot) => Ok(spawn_launch_task(request, slot)),
+        LaunchClaim::InProgress { task_id } if request.join_existing => Ok(task_id),
+        LaunchClaim::InProgress { .. } => Err(LAUNCH_IN_PROGRESS_ERROR.to_string()),
+    }
+}
+
+fn create_launch_task(request: &LaunchRequest) -> String {
     register_launcher_task_stage_labels();
     let title = format!("启动 {}", request.display_name);
     let detail = Some(request.version.to_string());
@@ -109,6 +192,7 @@ pub fn start_launch_task(request: LaunchRequest) -> String {
     );
     let _ = set_total(&task_id, Some(LAUNCH_TOTAL_STEPS));
     append_log(&task_id, format!("准备启动 {}", request.display_name));
+    begin_launch(&task_id, &request.folder_name);
     info!(
         task_id = %task_id,
         display_name = %request.display_name,
@@ -118,9 +202,15 @@ pub fn start_launch_task(request: LaunchRequest) -> String {
         has_launch_args = request.launch_args.is_some(),
         "已创建游戏启动任务"
     );
+    task_id
+}
 
+fn spawn_launch_task(request: LaunchRequest, slot: LaunchSlot) -> String {
+    let task_id = slot.task_id().to_string();
     let task_id_for_task = task_id.clone();
-    let join_handle = tokio::spawn(async move {
+    // 不登记中止句柄：取消只设置标记，由启动流程在阶段之间检查，避免卸载或注册进行到一半被打断
+    tokio::spawn(async move {
+        let slot = slot;
         info!(
             task_id = %task_id_for_task,
             display_name = %request.display_name,
@@ -129,7 +219,10 @@ pub fn start_launch_task(request: LaunchRequest) -> String {
         );
         let result = launch_game(&request, &task_id_for_task).await;
         match result {
-            Ok(Some(pid)) => {
+            Ok(LaunchResult {
+                pid: Some(pid),
+                injection,
+            }) => {
                 info!(
                     task_id = %task_id_for_task,
                     pid,
@@ -137,8 +230,13 @@ pub fn start_launch_task(request: LaunchRequest) -> String {
                 );
                 append_log(&task_id_for_task, format!("游戏已启动，PID {pid}"));
                 finish_task(&task_id_for_task, "completed", Some("启动完成".to_string()));
+                // 拿到 PID 即释放启动占用，延迟注入期间同一版本可以再次启动
+                drop(slot);
+                if let Some(handle) = injection {
+                    report_injection_results(&request.display_name, handle).await;
+                }
             }
-            Ok(None) => {
+            Ok(_) => {
                 info!(
                     task_id = %task_id_for_task,
                     "游戏启动任务执行完成，当前流程未实际拉起游戏进程"
@@ -163,14 +261,9 @@ pub fn start_launch_task(request: LaunchRequest) -> String {
             }
         }
     });
-    register_abort_handle(&task_id, join_handle);
     task_id
 }
 
-fn register_abort_handle(task_id: &str, join_handle: JoinHandle<()>) {
-    register_task_abort_handle(task_id.to_string(), join_handle.abort_handle());
-}
-
 fn append_log(task_id: &str, line: impl Into<String>) {
     let line = line.into();
     let _ = append_task_log(task_id, line.clone());
@@ -185,7 +278,7 @@ fn advance_step(task_id: &str, stage: &str, message: impl Into<String>) {
 
 fn check_cancelled(task_id: &str) -> Result<(), String> {
     if is_cancelled(task_id) {
-        Err("启动已取消".to_string())
+        Err(LAUNCH_CANCELLED_ERROR.to_string())
     } else {
         Ok(())
     }
@@ -193,7 +286,7 @@ fn check_cancelled(task_id: &str) -> Result<(), String> {
 
 fn check_cancelled_control(control: Option<&TaskControl>) -> Result<(), String> {
     if control.is_some_and(crate::tasks::task_manager::is_cancelled_fast) {
-        Err("启动已取消".to_string())
+        Err(LAUNCH_CANCELLED_ERROR.to_string())
     } else {
         Ok(())
     }
@@ -279,18 +372,12 @@ fn remove_appx_signature_if_present(package_folder: &str) -> Result<bool, String
     Ok(true)
 }
 
-fn identity_to_aumid(identity: &str) -> String {
-    match identity {
-        "Microsoft.MinecraftWindowsBeta" => "Microsoft.MinecraftWindowsBeta_8wekyb3d8bbwe!App",
-        "Microsoft.MinecraftEducationEdition" => {
-            "Microsoft.MinecraftEducationEdition_8wekyb3d8bbwe!Microsoft.MinecraftEducationEdition"
-        }
-        "Microsoft.MinecraftEducationPreview" => {
-            "Microsoft.MinecraftEducationPreview_8wekyb3d8bbwe!Microsoft.MinecraftEducationEdition"
-        }
-        _ => "Microsoft.MinecraftUWP_8wekyb3d8bbwe!App",
-    }
-    .to_string()
+pub(crate) fn identity_to_aumid(identity: &str) -> String {
+    // 未知的包标识按正式版处理
+    edition_by_identity(identity)
+        .or_else(|| edition_entry(&Edition::Release))
+        .map(|entry| entry.aumid)
+        .unwrap_or_else(|| "Microsoft.MinecraftUWP_8wekyb3d8bbwe!App".to_string())
 }
 
 fn find_game_executable(package_folder: &str, identity_name: &str) -> Option<PathBuf> {
@@ -403,7 +490,11 @@ pub fn embedded_dll_version_string() -> Option<String> {
     })
 }
 
-async fn launch_game(request: &LaunchRequest, task_id: &str) -> Result<Option<u32>, String> {
+pub(super) async fn launch_game(
+    request: &LaunchRequest,
+    task_id: &str,
+) -> Result<LaunchResult, String> {
+    let launch_started = Instant::now();
     let control = task_control(task_id);
     check_cancelled(task_id)?;
     check_cancelled_control(control.as_deref())?;
@@ -413,9 +504,15 @@ async fn launch_game(request: &LaunchRequest, task_id: &str) -> Result<Option<u3
     let game_cfg = &config.game;
     let mods_dir = Path::new(package_folder).join("mods");
     let folder_name = request.folder_name.to_string();
-    let version_config = get_version_config(folder_name.clone())
+    let mut version_config = get_version_config(folder_name.clone())
         .await
         .unwrap_or_default();
+    if request.safe_mode {
+        version_config.disable_mod_loading = true;
+        version_config.lock_mouse_on_launch = false;
+        version_config.editor_mode = false;
+        append_log(task_id, "安全模式：本次不加载模组".to_string());
+    }
 
     let _ = set_task_labels(
         task_id,
@@ -430,6 +527,11 @@ async fn launch_game(request: &LaunchRequest, task_id: &str) -> Result<Option<u3
         "进入游戏启动主流程"
     );
 
+    if let Some(install) = read_packaged_install(Path::new(package_folder)) {
+        return launch_packaged_game(request, task_id, install, &version_config, launch_started)
+            .await;
+    }
+
     append_log(task_id, format!("版本目录: {package_folder}"));
     let injector_version = embedded_dll_version_string().unwrap_or_else(|| "unknown".to_string());
     append_log(task_id, format!("预加载器版本: {injector_version}"));
@@ -450,26 +552,50 @@ async fn launch_game(request: &LaunchRequest, task_id: &str) -> Result<Option<u3
         "parsing",
         format!("版本信息已解析: {identity_version} ({identity_name})"),
     );
+    check_cancelled(task_id)?;
 
-    let mut final_launch_args = request.launch_args.as_ref().map(ToString::to_string);
-    if version_config.editor_mode
-        && compare_versions(&identity_version, "1.19.80.20") != Ordering::Less
-    {
-        let is_preview = identity_name.contains("Beta") || identity_name.contains("Preview");
-        final_launch_args = Some(build_editor_deeplink(is_win32, is_preview));
-        append_log(
-            task_id,
-            "检测到编辑器模式，已生成 Deeplink 参数".to_string(),
-        );
+    let final_launch_args = resolve_launch_args(
+        task_id,
+        request,
+        &version_config,
+        &identity_name,
+        &identity_version,
+        is_win32,
+    );
+
+    match check_launcher_version_compatibility(&folder_name, Path::new(package_folder)) {
+        Ok(Some(warning)) => {
+            warn!(task_id = %task_id, "{}", warning.message());
+            append_log(task_id, warning.message());
+        }
+        Ok(None) => {}
+        Err(error) => warn!(task_id = %task_id, "读取版本创建信息失败: {error}"),
+    }
+
+    if game_cfg.verify_before_launch {
+        let warnings = launch_integrity_warnings(PathBuf::from(package_folder)).await;
+        if !warnings.is_empty() {
+            update_progress(task_id, 0, None, Some("integrity_warning"));
+            append_log(
+                task_id,
+                "版本目录在启动器之外被修改（不影响启动）".to_string(),
+            );
+            for warning in warnings {
+                warn!(task_id = %task_id, "{warning}");
+                append_log(task_id, warning);
+            }
+        }
     }
 
     check_cancelled(task_id)?;
     let mut startup_mods_relative_paths = Vec::new();
     let mut delayed_mods = Vec::new();
+    let mut injection_schedule = Vec::new();
     if request.auto_start
         && !version_config.disable_mod_loading
         && let Ok(mods) = load_mods_config(&mods_dir).await
     {
+        let mods = filter_signed_mods(task_id, config.game.require_signed_dlls, mods);
         for (path_buf, delay) in mods {
             let Some(path_string) = path_buf.to_str().map(ToString::to_string) else {
                 continue;
@@ -478,6 +604,10 @@ async fn launch_game(request: &LaunchRequest, task_id: &str) -> Result<Option<u3
                 let _ = grant_all_application_packages_access(&path_buf);
             }
 
+            injection_schedule.push(ScheduledDll {
+                path: path_buf.clone(),
+                delay_ms: delay,
+            });
             if delay == 0 {
                 if let Some(file_name) = path_buf.file_name().and_then(|name| name.to_str()) {
                     startup_mods_relative_paths.push(format!("mods/{file_name}"));
@@ -487,6 +617,18 @@ async fn launch_game(request: &LaunchRequest, task_id: &str) -> Result<Option<u3
             }
         }
     }
+    if request.auto_start && !version_config.disable_mod_loading {
+        schedule_extra_mods(
+            task_id,
+            request,
+            &version_config,
+            config.game.require_signed_dlls,
+            !is_win32,
+            &mut injection_schedule,
+            &mut delayed_mods,
+        )
+        .await;
+    }
     debug!(
         task_id = %task_id,
         startup_mods = startup_mods_relative_paths.len(),
@@ -534,6 +676,13 @@ async fn launch_game(request: &LaunchRequest, task_id: &str) -> Result<Option<u3
         if need_update {
             ensure_file_in_dir(exe_dir, injector_name, INJECTOR_BYTES)?;
         }
+        injection_schedule.insert(
+            0,
+            ScheduledDll {
+                path: injector_target_path.clone(),
+                delay_ms: 0,
+            },
+        );
 
         let file_redirections =
             version_config.effective_file_redirections(Path::new(package_folder));
@@ -569,6 +718,15 @@ async fn launch_game(request: &LaunchRequest, task_id: &str) -> Result<Option<u3
     }
     advance_step(task_id, "patching", "启动环境准备完成".to_string());
 
+    check_cancelled(task_id)?;
+    // 同一包标识的卸载、注册到拉起进程期间只允许一个启动进行，其余排队
+    let package_guard = if is_win32 {
+        None
+    } else {
+        let aumid = identity_to_aumid(&identity_name);
+        let family_name = aumid.split('!').next().unwrap_or("");
+        Some(lock_package_identity(family_name, &request.folder_name, task_id).await?)
+    };
     check_cancelled(task_id)?;
     if !is_win32 {
         if remove_appx_signature_if_present(package_folder)? {
@@ -599,6 +757,7 @@ async fn launch_game(request: &LaunchRequest, task_id: &str) -> Result<Option<u3
         }
 
         if need_remove {
+            check_cancelled(task_id)?;
             info!(
                 task_id = %task_id,
                 family_name,
@@ -608,12 +767,26 @@ async fn launch_game(request: &LaunchRequest, task_id: &str) -> Result<Option<u3
                 .await
                 .map_err(|error| format!("卸载旧包失败 ({family_name}): {error:?}"))?;
             sleep(Duration::from_millis(500)).await;
+            check_cancelled(task_id)?;
         }
         if need_register {
+            check_cancelled(task_id)?;
             info!(task_id = %task_id, package_folder, "准备注册 APPX 包");
             register_appx_package_async(package_folder)
                 .await
-                .map_err(|error| format!("注册 APPX 失败 ({package_folder}): {error:?}"))?;
+                .map_err(|error| {
+                    if is_access_denied(&error) {
+                        ElevationRequired::new(ElevatedOperation::RegisterPackage)
+                            .with_resume(ResumeAction::LaunchVersion {
+                                version_folder: folder_name.clone(),
+                            })
+                            .error_message(format!(
+                                "注册 APPX 被拒绝访问 ({package_folder}): {error:?}"
+                            ))
+                    } else {
+                        format!("注册 APPX 失败 ({package_folder}): {error:?}")
+                    }
+                })?;
         }
         advance_step(task_id, "initializing", "APPX 注册状态已就绪".to_string());
     } else {
@@ -623,12 +796,15 @@ async fn launch_game(request: &LaunchRequest, task_id: &str) -> Result<Option<u3
             "Win32 版本无需重新注册".to_string(),
         );
     }
+    if let Err(error) = write_version_creation_metadata(Path::new(package_folder)) {
+        warn!(task_id = %task_id, "{error}");
+    }
 
     check_cancelled(task_id)?;
     if !request.auto_start {
         info!(task_id = %task_id, "本次仅执行准备流程，不实际启动游戏");
         advance_step(task_id, "launching", "已完成准备，未执行启动".to_string());
-        return Ok(None);
+        return Ok(LaunchResult::default());
     }
 
     if !is_win32 && game_cfg.uwp_minimize_fix {
@@ -638,6 +814,9 @@ async fn launch_game(request: &LaunchRequest, task_id: &str) -> Result<Option<u3
         }
     }
 
+    check_cancelled(task_id)?;
+    let history_id = uuid::Uuid::new_v4().to_string();
+    let mut injection_handle = None;
     let pid = if is_win32 {
         let exe_path = find_game_executable(package_folder, &identity_name)
             .ok_or("未找到游戏 EXE".to_string())?;
@@ -659,7 +838,14 @@ async fn launch_game(request: &LaunchRequest, task_id: &str) -> Result<Option<u3
         .await
         .map_err(|error| format!("启动失败: {error:?}"))?;
         if !version_config.disable_mod_loading {
-            handle_delayed_injection(pid, delayed_mods, log_callback, false);
+            injection_handle = handle_delayed_injection(
+                pid,
+                delayed_mods,
+                log_callback,
+                false,
+                folder_name.clone(),
+                history_id.clone(),
+            );
         }
         info!(task_id = %task_id, pid, "Win32 版本启动成功");
         pid
@@ -671,38 +857,284 @@ async fn launch_game(request: &LaunchRequest, task_id: &str) -> Result<Option<u3
             launch_args = ?final_launch_args.as_deref(),
             "准备启动 UWP 版本"
         );
-        let activated_pid =
-            launch_uwp_command_only(&aumid, final_launch_args.as_deref().or(Some("")))
-                .await
-                .map_err(|error| format!("启动请求失败: {error:?}"))?;
-        let target_exe = if identity_name.contains("Education") {
-            "Minecraft.Education.exe"
-        } else {
-            "Minecraft.Windows.exe"
-        };
-        let pfn = aumid.split('!').next().unwrap_or("").to_string();
-        let pid = match activated_pid {
-            Some(pid) if pid > 0 => pid,
-            _ => wait_for_uwp_pid(target_exe, &pfn)
-                .await
-                .ok_or("启动超时".to_string())?,
-        };
+        let pfn = aumid.split('!').next().unwrap_or("");
+        let pid =
+            activate_uwp_game(&aumid, &identity_name, pfn, final_launch_args.as_deref()).await?;
         if !version_config.disable_mod_loading {
             let log_task_id = task_id.to_string();
-            handle_delayed_injection(
+            injection_handle = handle_delayed_injection(
                 pid,
                 delayed_mods,
                 Arc::new(move |message: String| {
                     append_log(&log_task_id, message);
                 }),
                 false,
+                folder_name.clone(),
+                history_id.clone(),
             );
         }
         info!(task_id = %task_id, pid, "UWP 版本启动成功");
         pid
     };
+    drop(package_guard);
+
+    finish_spawned_launch(
+        task_id,
+        &folder_name,
+        &version_config,
+        launch_started,
+        SpawnedGame {
+            pid,
+            game_version: identity_version,
+            history_id,
+            injection_handle,
+            injection_schedule,
+        },
+    )
+}
+
+/// 从原始 .appx 安装的版本：包目录只读，跳过静态注入与清单修补，模组全部在拉起后按 PID 注入
+async fn launch_packaged_game(
+    request: &LaunchRequest,
+    task_id: &str,
+    install: PackagedInstall,
+    version_config: &VersionConfig,
+    launch_started: Instant,
+) -> Result<LaunchResult, String> {
+    let package_folder = request.package_folder.as_ref();
+    let config = read_config().map_err(|error| error.to_string())?;
+    append_log(task_id, format!("APPX 包: {}", install.appx_path));
+    advance_step(
+        task_id,
+        "parsing",
+        format!(
+            "版本信息已解析: {} ({})",
+            install.identity_version, install.identity_name
+        ),
+    );
+
+    let final_launch_args = resolve_launch_args(
+        task_id,
+        request,
+        version_config,
+        &install.identity_name,
+        &install.identity_version,
+        false,
+    );
 
-    if version_config.lock_mouse_on_launch {
+    check_cancelled(task_id)?;
+    let mut injection_mods = Vec::new();
+    let mut injection_schedule = Vec::new();
+    if request.auto_start
+        && !version_config.disable_mod_loading
+        && let Ok(mods) = load_mods_config(&Path::new(package_folder).join("mods")).await
+    {
+        let mods = filter_signed_mods(task_id, config.game.require_signed_dlls, mods);
+        for (path_buf, delay) in mods {
+            let Some(path_string) = path_buf.to_str().map(ToString::to_string) else {
+                continue;
+            };
+            let _ = grant_all_application_packages_access(&path_buf);
+            injection_schedule.push(ScheduledDll {
+                path: path_buf.clone(),
+                delay_ms: delay,
+            });
+            injection_mods.push((path_string, delay));
+        }
+    }
+    if request.auto_start && !version_config.disable_mod_loading {
+        schedule_extra_mods(
+            task_id,
+            request,
+            version_config,
+            config.game.require_signed_dlls,
+            true,
+            &mut injection_schedule,
+            &mut injection_mods,
+        )
+        .await;
+    }
+    advance_step(
+        task_id,
+        "preparing_files",
+        format!("已准备模组加载信息，启动后注入 {} 个", injection_mods.len()),
+    );
+    advance_step(task_id, "patching", "APPX 包版本无需修补".to_string());
+
+    check_cancelled(task_id)?;
+    let package_guard =
+        lock_package_identity(&install.package_family_name, &request.folder_name, task_id).await?;
+    check_cancelled(task_id)?;
+    if ensure_packaged_registered(&install).await? {
+        append_log(task_id, "已从 APPX 文件安装包".to_string());
+    }
+    advance_step(task_id, "initializing", "APPX 注册状态已就绪".to_string());
+
+    check_cancelled(task_id)?;
+    if !request.auto_start {
+        info!(task_id = %task_id, "本次仅执行准备流程，不实际启动游戏");
+        advance_step(task_id, "launching", "已完成准备，未执行启动".to_string());
+        return Ok(LaunchResult::default());
+    }
+
+    let aumid = identity_to_aumid(&install.identity_name);
+    if config.game.uwp_minimize_fix
+        && let Ok(Some((_, _, package_name))) = get_package_info(&aumid)
+    {
+        let _ = enable_debugging_for_package(&package_name);
+    }
+
+    check_cancelled(task_id)?;
+    info!(
+        task_id = %task_id,
+        aumid = %aumid,
+        launch_args = ?final_launch_args.as_deref(),
+        "准备启动 APPX 包版本"
+    );
+    let pid = activate_uwp_game(
+        &aumid,
+        &install.identity_name,
+        &install.package_family_name,
+        final_launch_args.as_deref(),
+    )
+    .await?;
+    drop(package_guard);
+    let history_id = uuid::Uuid::new_v4().to_string();
+    let mut injection_handle = None;
+    if !version_config.disable_mod_loading {
+        let log_task_id = task_id.to_string();
+        injection_handle = handle_delayed_injection(
+            pid,
+            injection_mods,
+            Arc::new(move |message: String| {
+                append_log(&log_task_id, message);
+            }),
+            false,
+            request.folder_name.to_string(),
+            history_id.clone(),
+        );
+    }
+
+    finish_spawned_launch(
+        task_id,
+        &request.folder_name,
+        version_config,
+        launch_started,
+        SpawnedGame {
+            pid,
+            game_version: install.identity_version,
+            history_id,
+            injection_handle,
+            injection_schedule,
+        },
+    )
+}
+
+/// 启动参数；开启编辑器模式且版本支持时换成编辑器 Deeplink
+fn resolve_launch_args(
+    task_id: &str,
+    request: &LaunchRequest,
+    version_config: &VersionConfig,
+    identity_name: &str,
+    identity_version: &str,
+    is_win32: bool,
+) -> Option<String> {
+    if !version_config.editor_mode
+        || compare_versions(identity_version, "1.19.80.20") == Ordering::Less
+    {
+        return request.launch_args.as_ref().map(ToString::to_string);
+    }
+    let is_preview = identity_name.contains("Beta") || identity_name.contains("Preview");
+    append_log(
+        task_id,
+        "检测到编辑器模式，已生成 Deeplink 参数".to_string(),
+    );
+    Some(build_editor_deeplink(is_win32, is_preview))
+}
+
+/// 额外 mods 目录中启用的模组；BLoader 只加载版本自身 mods 目录，这些模组一律在拉起后按 PID 注入
+async fn schedule_extra_mods(
+    task_id: &str,
+    request: &LaunchRequest,
+    version_config: &VersionConfig,
+    require_signed: bool,
+    grant_package_access: bool,
+    injection_schedule: &mut Vec<ScheduledDll>,
+    delayed_mods: &mut Vec<(String, u64)>,
+) {
+    let extra_mods = load_version_extra_mods(
+        task_id,
+        &request.folder_name,
+        Path::new(request.package_folder.as_ref()),
+        version_config,
+        injection_schedule,
+        require_signed,
+    )
+    .await;
+    for (path_buf, delay) in extra_mods {
+        let Some(path_string) = path_buf.to_str().map(ToString::to_string) else {
+            continue;
+        };
+        if grant_package_access {
+            let _ = grant_all_application_packages_access(&path_buf);
+        }
+        injection_schedule.push(ScheduledDll {
+            path: path_buf,
+            delay_ms: delay,
+        });
+        delayed_mods.push((path_string, delay));
+    }
+}
+
+/// 激活 UWP / APPX 包并等待游戏进程出现
+async fn activate_uwp_game(
+    aumid: &str,
+    identity_name: &str,
+    package_family_name: &str,
+    launch_args: Option<&str>,
+) -> Result<u32, String> {
+    let activated_pid = launch_uwp_command_only(aumid, launch_args.or(Some("")))
+        .await
+        .map_err(|error| format!("启动请求失败: {error:?}"))?;
+    let target_exe = if identity_name.contains("Education") {
+        "Minecraft.Education.exe"
+    } else {
+        "Minecraft.Windows.exe"
+    };
+    match activated_pid {
+        Some(pid) if pid > 0 => Ok(pid),
+        _ => wait_for_uwp_pid(target_exe, package_family_name)
+            .await
+            .ok_or("启动超时".to_string()),
+    }
+}
+
+/// 已拉起的游戏进程及其启动后注入
+struct SpawnedGame {
+    pid: u32,
+    game_version: String,
+    history_id: String,
+    injection_handle: Option<JoinHandle<InjectionReport>>,
+    injection_schedule: Vec<ScheduledDll>,
+}
+
+/// 游戏拉起后的收尾：锁定鼠标、关联待回滚的包和游玩统计、写入启动历史
+fn finish_spawned_launch(
+    task_id: &str,
+    folder_name: &str,
+    version_config: &VersionConfig,
+    launch_started: Instant,
+    spawned: SpawnedGame,
+) -> Result<LaunchResult, String> {
+    let SpawnedGame {
+        pid,
+        game_version,
+        history_id,
+        injection_handle,
+        injection_schedule,
+    } = spawned;
+    let cancelled = skip_after_spawn_if_cancelled(task_id, pid, injection_handle.as_ref());
+    if version_config.lock_mouse_on_launch && !cancelled {
         start_window_monitor(
             "Minecraft",
             &version_config.unlock_mouse_hotkey,
@@ -710,30 +1142,289 @@ async fn launch_game(request: &LaunchRequest, task_id: &str) -> Result<Option<u3
         );
     }
 
-    advance_step(task_id, "launching", format!("游戏已成功拉起，PID {pid}"));
+    if !cancelled {
+        advance_step(task_id, "launching", format!("游戏已成功拉起，PID {pid}"));
+    }
+    attach_pending_reverts_to_game(pid);
+    record_game_session_start(folder_name, pid);
+    spawn_record_launch_session(
+        LaunchHistoryEntry {
+            id: history_id,
+            version: folder_name.to_string(),
+            game_version,
+            started_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_millis() as i64)
+                .unwrap_or_default(),
+            pid,
+            launch_duration_ms: launch_started.elapsed().as_millis() as u64,
+            mod_loading_disabled: version_config.disable_mod_loading,
+            injected_dlls: Vec::new(),
+            injection_report: None,
+        },
+        injection_schedule,
+    );
+    if let Err(error) = check_cancelled(task_id) {
+        if let Some(handle) = injection_handle {
+            handle.abort();
+        }
+        return Err(error);
+    }
     info!(task_id = %task_id, pid, "游戏启动流程已完成");
-    Ok(Some(pid))
+    Ok(LaunchResult {
+        pid: Some(pid),
+        injection: injection_handle,
+    })
+}
+
+/// 游戏进程已拉起后才收到取消：不结束游戏，只中止延迟注入并跳过锁定鼠标等后续步骤
+fn skip_after_spawn_if_cancelled(
+    task_id: &str,
+    pid: u32,
+    injection_handle: Option<&JoinHandle<InjectionReport>>,
+) -> bool {
+    if !is_cancelled(task_id) {
+        return false;
+    }
+    if let Some(handle) = injection_handle {
+        handle.abort();
+    }
+    info!(task_id = %task_id, pid, "游戏已拉起后取消启动，保留游戏进程并跳过后续步骤");
+    append_log(task_id, format!("启动已取消，游戏进程 {pid} 保持运行"));
+    true
+}
+
+/// 开启 `require_signed_dlls` 时跳过未签名或签名不受信任的 DLL
+fn filter_signed_mods(
+    task_id: &str,
+    require_signed: bool,
+    mods: Vec<(PathBuf, u64)>,
+) -> Vec<(PathBuf, u64)> {
+    if !require_signed {
+        return mods;
+    }
+    let (kept, warnings) = retain_signed_dlls(mods);
+    for warning in warnings {
+        append_log(
+            task_id,
+            format!("{}: {}", warning.dll_name, warning.message),
+        );
+    }
+    kept
 }
 
+/// 版本配置 `extra_mod_dirs` 中启用的模组，与已计划的 DLL 去重；首次发现的 DLL 以完整路径写回版本配置
+async fn load_version_extra_mods(
+    task_id: &str,
+    folder_name: &str,
+    package_folder: &Path,
+    version_config: &VersionConfig,
+    scheduled: &[ScheduledDll],
+    require_signed: bool,
+) -> Vec<(PathBuf, u64)> {
+    if version_config.extra_mod_dirs.is_empty() {
+        return Vec::new();
+    }
+    let loaded: Vec<PathBuf> = scheduled.iter().map(|dll| dll.path.clone()).collect();
+    let scan = load_extra_mods(package_folder, version_config, &loaded).await;
+    if !scan.discovered.is_empty() {
+        // 重新读取后只补充新条目，安全模式等本次启动的临时设置不会被写进配置
+        match get_version_config(folder_name.to_string()).await {
+            Ok(mut saved) => {
+                for (key, state) in scan.discovered {
+                    saved.extra_mods.entry(key).or_insert(state);
+                }
+                if let Err(error) = save_version_config(folder_name.to_string(), saved).await {
+                    warn!(task_id = %task_id, "写回额外模组列表失败: {error}");
+                }
+            }
+            Err(error) => warn!(task_id = %task_id, "读取版本配置失败: {error}"),
+        }
+    }
+    if !scan.mods.is_empty() {
+        append_log(
+            task_id,
+            format!("额外 mods 目录中启用了 {} 个模组", scan.mods.len()),
+        );
+    }
+    filter_signed_mods(task_id, require_signed, scan.mods)
+}
+
+/// 按配置顺序逐个延迟注入；每个 DLL 结束时广播 `inject-result`，实际开始时刻、耗时和结果汇总为注入报告写入启动历史
 fn handle_delayed_injection(
     pid: u32,
     mods: Vec<(String, u64)>,
     log_callback: Arc<dyn Fn(String) + Send + Sync>,
     show_console: bool,
-) {
+    version: String,
+    history_id: String,
+) -> Option<JoinHandle<InjectionReport>> {
     if mods.is_empty() {
-        return;
+        return None;
     }
+    let inject_timeout_secs = read_config()
+        .map(|config| config.game.inject_timeout_secs)
+        .unwrap_or(DEFAULT_INJECT_TIMEOUT_SECS)
+        .max(1);
 
-    tokio::spawn(async move {
+    Some(tokio::spawn(async move {
+        let launched_at = Instant::now();
+        let mut records = Vec::with_capacity(mods.len());
         for (path, delay) in mods {
             sleep(Duration::from_millis(delay)).await;
-            let _ =
-                inject_existing_process(pid, path, Some(log_callback.clone()), true, show_console)
-                    .await;
+            let dll_name = Path::new(&path)
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.clone());
+            let span = info_span!("inject_dll", dll = %dll_name, delay_ms = delay, pid);
+            let retry_policy = load_inject_retry_policy(Path::new(&path)).await;
+            let started_at_ms = launched_at.elapsed().as_millis() as u64;
+            let started = Instant::now();
+            // 超时覆盖包括重试在内的全部尝试
+            let result = tokio::time::timeout(
+                Duration::from_secs(inject_timeout_secs),
+                inject_with_retry(
+                    pid,
+                    &path,
+                    &dll_name,
+                    retry_policy,
+                    &log_callback,
+                    show_console,
+                ),
+            )
+            .instrument(span.clone())
+            .await;
+            let (outcome, error) = match result {
+                Ok(Ok(())) => (InjectionOutcome::Injected, None),
+                Ok(Err(e)) => (InjectionOutcome::Failed, Some(e)),
+                Err(_) => (
+                    InjectionOutcome::TimedOut,
+                    Some(format!("超过 {inject_timeout_secs} 秒仍未完成")),
+                ),
+            };
+            let duration_ms = started.elapsed().as_millis() as u64;
+            span.in_scope(|| {
+                info!(started_at_ms, duration_ms, outcome = ?outcome, "延迟注入结束");
+            });
+            if let Some(error) = &error {
+                log_callback(format!("注入 {dll_name} 未成功: {error}"));
+            }
+            let record = InjectionRecord {
+                dll_name,
+                path,
+                delay_ms: delay,
+                started_at_ms,
+                duration_ms,
+                outcome,
+                error,
+            };
+            publish_inject_result(&version, &history_id, pid, &record);
+            records.push(record);
         }
-    });
+        let report = InjectionReport {
+            pid,
+            records,
+            total_duration_ms: launched_at.elapsed().as_millis() as u64,
+        };
+        record_injection_report(version, history_id, report.clone());
+        report
+    }))
+}
+
+/// 注入失败时按重试策略再次尝试；每次重试前确认游戏进程仍在运行，进程已退出时立即放弃
+async fn inject_with_retry(
+    pid: u32,
+    path: &str,
+    dll_name: &str,
+    policy: InjectRetryPolicy,
+    log_callback: &Arc<dyn Fn(String) + Send + Sync>,
+    show_console: bool,
+) -> Result<(), String> {
+    policy
+        .run(
+            pid,
+            || async {
+                inject_existing_process(
+                    pid,
+                    path.to_string(),
+                    Some(log_callback.clone()),
+                    true,
+                    show_console,
+                )
+                .await
+                .map_err(|e| format!("{e:#}"))
+            },
+            process_alive,
+            |attempt, error| {
+                log_callback(format!(
+                    "注入 {dll_name} 失败，{} 毫秒后重试（{attempt}/{}）: {error}",
+                    policy.retry_interval_ms, policy.retries
+                ));
+            },
+        )
+        .await
 }
+
+/// 延迟注入作为独立任务汇报，取消该任务会中止剩余注入，游戏进程保持运行
+async fn report_injection_results(display_name: &str, handle: JoinHandle<InjectionReport>) {
+    let task_id = create_task_with_details(
+        None,
+        format!("注入模组 {display_name}"),
+        None,
+        "injecting",
+        None,
+        false,
+    );
+    let records = await_injection_results(&task_id, handle).await;
+    if is_cancelled(&task_id) {
+        finish_task(
+            &task_id,
+            "cancelled",
+            Some("已取消剩余模组注入".to_string()),
+        );
+        return;
+    }
+    let summary = injection_summary(&records);
+    info!(task_id = %task_id, injected = records.len(), "启动后模组注入已结束");
+    append_log(&task_id, format!("模组注入完成{summary}"));
+    finish_task(
+        &task_id,
+        "completed",
+        Some(format!("模组注入完成{summary}")),
+    );
+}
+
+/// 等待启动后注入全部结束；等待期间取消任务会中止剩余注入
+async fn await_injection_results(
+    task_id: &str,
+    injection_handle: Option<JoinHandle<InjectionReport>>,
+) -> Vec<InjectionRecord> {
+    let Some(mut handle) = injection_handle else {
+        return Vec::new();
+    };
+    let _ = set_task_message(task_id, Some("等待模组注入完成".to_string()));
+    loop {
+        tokio::select! {
+            report = &mut handle => {
+                return match report {
+                    Ok(report) => report.records,
+                    Err(error) => {
+                        warn!(task_id = %task_id, "延迟注入任务异常结束: {error}");
+                        Vec::new()
+                    }
+                };
+            }
+            _ = sleep(CANCEL_POLL_INTERVAL) => {
+                if is_cancelled(task_id) {
+                    handle.abort();
+                    append_log(task_id, "已取消，剩余模组不再注入".to_string());
+                    return Vec::new();
+                }
+            }
+        }
+    }
+}
+
 pub fn build_package_folder(folder_name: &str) -> PathBuf {
     file_ops::bmcbl_subdir("versions").join(folder_name)
 }
@@ -1,4 +1,10 @@
+use crate::config::config::read_config;
 use crate::core::linux_runtime::{RunnerKind, resolve_runner, validate_proton_game_runtime};
+use crate::core::minecraft::launcher::launch_guard::{
+    LAUNCH_IN_PROGRESS_ERROR, LaunchClaim, LaunchSlot, claim_launch,
+};
+use crate::core::minecraft::launcher::progress_history::begin_launch;
+use crate::core::version::integrity::launch_integrity_warnings;
 use crate::tasks::task_manager::{
     append_task_log, create_task_with_details, finish_task, register_task_abort_handle,
     register_task_stage_labels, set_total, update_progress,
@@ -20,8 +26,9 @@ const EARLY_EXIT_GRACE_PERIOD: Duration = Duration::from_secs(8);
 const GAME_INPUT_INSTALL_TIMEOUT: Duration = Duration::from_secs(180);
 const GAME_INPUT_REGISTRATION_TIMEOUT: Duration = Duration::from_secs(15);
 const RECENT_RUNNER_OUTPUT_LIMIT: usize = 32;
-const LAUNCHER_TASK_STAGE_LABELS: [(&str, &str); 4] = [
+const LAUNCHER_TASK_STAGE_LABELS: [(&str, &str); 5] = [
     ("resolving_runner", "检测兼容环境"),
+    ("integrity_warning", "版本文件已被修改"),
     ("preparing_prefix", "准备 Proton Prefix"),
     ("launching", "启动游戏"),
     ("running_game", "游戏运行中"),
@@ -37,6 +44,8 @@ pub struct LaunchRequest {
     pub package_folder: Arc<str>,
     pub auto_start: bool,
     pub launch_args: Option<Arc<str>>,
+    /// 同一版本已在启动时接续到进行中的任务，而不是返回 `launch_in_progress`
+    pub join_existing: bool,
 }
 
 impl LaunchRequest {
@@ -53,6 +62,7 @@ impl LaunchRequest {
             package_folder: Arc::from(package_folder.into()),
             auto_start: true,
             launch_args: None,
+            join_existing: false,
         }
     }
 }
@@ -129,7 +139,35 @@ async fn copy_runner_dlls(
     Ok(())
 }
 
+/// 启动版本；该版本已有启动进行中时直接返回进行中的任务 ID
 pub fn start_launch_task(request: LaunchRequest) -> String {
+    match claim_launch(
+        &request.folder_name,
+        &request.display_name,
+        &request.version,
+        || create_launch_task(&request),
+    ) {
+        LaunchClaim::Claimed(slot) => spawn_launch_task(request, slot),
+        LaunchClaim::InProgress { task_id } => task_id,
+    }
+}
+
+/// 与 [`start_launch_task`] 相同，但在该版本已有启动进行中且未设置 `join_existing` 时
+/// 返回 `launch_in_progress` 错误
+pub fn try_start_launch_task(request: LaunchRequest) -> Result<String, String> {
+    match claim_launch(
+        &request.folder_name,
+        &request.display_name,
+        &request.version,
+        || create_launch_task(&request),
+    ) {
+        LaunchClaim::Claimed(slot) => Ok(spawn_launch_task(request, slot)),
+        LaunchClaim::InProgress { task_id } if request.join_existing => Ok(task_id),
+        LaunchClaim::InProgress { .. } => Err(LAUNCH_IN_PROGRESS_ERROR.to_string()),
+    }
+}
+
+fn create_launch_task(request: &LaunchRequest) -> String {
     register_task_stage_labels(LAUNCHER_TASK_STAGE_LABELS);
     let task_id = create_task_with_details(
         None,
@@ -141,9 +179,15 @@ pub fn start_launch_task(request: LaunchRequest) -> String {
     );
     set_total(&task_id, Some(LAUNCH_TOTAL_STEPS));
     append_task_log(&task_id, format!("准备启动 {}", request.display_name));
+    begin_launch(&task_id, &request.folder_name);
+    task_id
+}
 
+fn spawn_launch_task(request: LaunchRequest, slot: LaunchSlot) -> String {
+    let task_id = slot.task_id().to_string();
     let task_id_for_task = task_id.clone();
     let join_handle = tokio::spawn(async move {
+        let _slot = slot;
         match launch_game(&request, &task_id_for_task).await {
             Ok(Some(process_id)) => {
                 append_task_log(
@@ -238,6 +282,20 @@ async fn launch_game(request: &LaunchRequest, task_id: &str) -> Result<Option<u3
         );
     }
 
+    if read_config().is_ok_and(|config| config.game.verify_before_launch) {
+        let warnings = launch_integrity_warnings(package_path.clone()).await;
+        if !warnings.is_empty() {
+            update_progress(task_id, 0, None, Some("integrity_warning"));
+            append_task_log(
+                task_id,
+                "版本目录在启动器之外被修改（不影响启动）".to_string(),
+            );
+            for warning in warnings {
+                append_task_log(task_id, warning);
+            }
+        }
+    }
+
     // Inject BLoader.dll into the game EXE, matching the Windows launcher.
     // Without this, the Windows App Runtime bootstrapper fails to find
     // runtime 1.8 and the game exits with code 3.
@@ -393,6 +451,11 @@ async fn launch_game(request: &LaunchRequest, task_id: &str) -> Result<Option<u3
         }
     }
     spawn_process_monitor(task_id.to_string(), child);
+    crate::core::minecraft::pack_reverts::attach_pending_reverts_to_game(process_id);
+    crate::core::minecraft::usage_stats::record_game_session_start(
+        &request.folder_name,
+        process_id,
+    );
     update_progress(task_id, 1, Some(LAUNCH_TOTAL_STEPS), Some("launching"));
     update_progress(task_id, 0, Some(LAUNCH_TOTAL_STEPS), Some("running_game"));
     Ok(Some(process_id))
@@ -1,5 +1,11 @@
 // src-tauri/src/commands/map.rs
-use crate::core::minecraft::paths::{GamePathOptions, GameTargetDir, game_target_dirs};
+use crate::core::minecraft::editions::known_editions;
+use crate::core::minecraft::export::write_world_archive;
+use crate::core::minecraft::nbt::{NbtTag, parse_root_nbt_header, write_level_dat};
+use crate::core::minecraft::paths::{
+    GamePathOptions, GameTargetDir, SourceRootProbe, SourceRootStatus, game_target_dirs,
+    local_appdata_dir, probe_game_root, probe_source_root, roaming_appdata_dir,
+};
 use anyhow::Result;
 use bedrock_world::{WorldDiscovery, discover_worlds};
 use chrono::{DateTime, Utc};
@@ -10,7 +16,6 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::{Instant, SystemTime};
 use tracing::debug;
-use walkdir::WalkDir;
 
 const MAP_SIZE_SCAN_FILE_LIMIT: usize = 512;
 
@@ -37,14 +42,53 @@ pub struct McMapInfo {
     pub gdk_user: Option<String>, // 所属用户目录名
 }
 
+/// 世界列表及本次探测过的数据根目录；列表为空时界面可据此说明原因
+#[derive(Debug, Serialize, Clone)]
+pub struct WorldListing {
+    pub worlds: Vec<McMapInfo>,
+    pub sources: Vec<SourceRootProbe>,
+}
+
+impl WorldListing {
+    /// 第一个不可访问或无法解析的数据根目录的说明
+    pub fn unavailable_reason(&self) -> Option<&str> {
+        self.sources
+            .iter()
+            .find(|source| source.inaccessible || source.status == SourceRootStatus::Unresolved)
+            .and_then(|source| source.message.as_deref())
+    }
+}
+
 // ==================================================================================
 // 3. 核心逻辑
 // ==================================================================================
 
 pub(crate) fn list_worlds_standard(options: &GamePathOptions) -> Result<Vec<McMapInfo>> {
-    let start = Instant::now();
+    list_minecraft_worlds(options).map(|listing| listing.worlds)
+}
+
+pub(crate) fn list_minecraft_worlds(options: &GamePathOptions) -> Result<WorldListing> {
+    let root_probe = probe_game_root(options);
+    if !root_probe.is_available() {
+        debug!(
+            "World root unavailable for options {:?}: {:?}",
+            options, root_probe
+        );
+        return Ok(WorldListing {
+            worlds: Vec::new(),
+            sources: vec![root_probe],
+        });
+    }
+
     let world_dirs = game_target_dirs(options, GameTargetDir::MinecraftWorlds);
+    let mut sources = vec![root_probe];
+    sources.extend(world_dirs.iter().map(|dir| probe_source_root(dir)));
+    let worlds = scan_world_dirs(options, world_dirs)?;
+    Ok(WorldListing { worlds, sources })
+}
 
+fn scan_world_dirs(options: &GamePathOptions, world_dirs: Vec<PathBuf>) -> Result<Vec<McMapInfo>> {
+    let start = Instant::now();
     if world_dirs.is_empty() {
         debug!("No world roots found for options: {:?}", options);
         return Ok(Vec::new());
@@ -54,7 +98,12 @@ pub(crate) fn list_worlds_standard(options: &GamePathOptions) -> Result<Vec<McMa
         roots: world_dirs,
         size_scan_file_limit: MAP_SIZE_SCAN_FILE_LIMIT,
     };
-    let results = discover_worlds(&discovery)?
+    let worlds = discover_worlds(&discovery)?;
+    crate::core::minecraft::usage_stats::observe_worlds(worlds.iter().filter_map(|world| {
+        let name = world.level_name.as_deref().unwrap_or(&world.folder_name);
+        Some((world.folder_path.to_str()?, name, world.modified))
+    }));
+    let results = worlds
         .into_iter()
         .map(|world| {
             let gdk_user = if world.folder_path.to_string_lossy().contains("Users") {
@@ -110,8 +159,8 @@ fn collect_world_roots_filtered(
     // 1. 隔离模式 (Isolation Mode)
     // =========================================================
     if let Some(iso_id) = isolation_id {
-        let versions_root = Path::new("./BMCBL/versions");
-        // 基础路径: ./BMCBL/versions/<id>/Minecraft Bedrock
+        let versions_root = crate::utils::file_ops::versions_dir();
+        // 基础路径: <数据目录>/versions/<id>/Minecraft Bedrock
         let version_base = versions_root.join(iso_id).join("Minecraft Bedrock");
 
         if is_gdk {
@@ -226,64 +275,39 @@ fn collect_world_roots_filtered(
             }
         };
 
-        if let Ok(roaming) = std::env::var("APPDATA") {
-            // 正式版
-            scan_users_dir(
-                PathBuf::from(&roaming)
-                    .join("Minecraft Bedrock")
-                    .join("Users"),
-                "正式版",
-            );
-            // 预览版
-            scan_users_dir(
-                PathBuf::from(&roaming)
-                    .join("Minecraft Bedrock Preview")
-                    .join("Users"),
-                "预览版",
-            );
+        if let Some(roaming) = roaming_appdata_dir() {
+            for edition in known_editions() {
+                if let Some(gdk_data_dir) = edition.gdk_data_dir.as_deref() {
+                    scan_users_dir(
+                        roaming.join(gdk_data_dir).join("Users"),
+                        &edition.display_name,
+                    );
+                }
+            }
         }
     }
     // UWP 系统逻辑
     else if is_uwp {
-        if let Ok(local_appdata) = std::env::var("LOCALAPPDATA") {
-            // 正式版
-            let uwp_root = PathBuf::from(&local_appdata)
-                .join("Packages")
-                .join("Microsoft.MinecraftUWP_8wekyb3d8bbwe")
-                .join("LocalState");
-            let uwp_worlds = uwp_root
-                .join("games")
-                .join("com.mojang")
-                .join("minecraftWorlds");
-
-            if uwp_worlds.exists() {
-                roots.push((
-                    uwp_worlds,
-                    "UWP".into(),
-                    "正式版".into(),
-                    uwp_root.to_string_lossy().into(),
-                    None,
-                ));
-            }
-
-            // 预览版
-            let uwp_preview_root = PathBuf::from(&local_appdata)
-                .join("Packages")
-                .join("Microsoft.MinecraftWindowsBeta_8wekyb3d8bbwe")
-                .join("LocalState");
-            let uwp_preview_worlds = uwp_preview_root
-                .join("games")
-                .join("com.mojang")
-                .join("minecraftWorlds");
+        if let Some(local_appdata) = local_appdata_dir() {
+            for edition in known_editions() {
+                let uwp_root = local_appdata
+                    .join("Packages")
+                    .join(edition.uwp_data_dir_name())
+                    .join("LocalState");
+                let uwp_worlds = uwp_root
+                    .join("games")
+                    .join("com.mojang")
+                    .join("minecraftWorlds");
 
-            if uwp_preview_worlds.exists() {
-                roots.push((
-                    uwp_preview_worlds,
-                    "UWP".into(),
-                    "预览版".into(),
-                    uwp_preview_root.to_string_lossy().into(),
-                    None,
-                ));
+                if uwp_worlds.exists() {
+                    roots.push((
+                        uwp_worlds,
+                        "UWP".into(),
+                        edition.display_name.clone(),
+                        uwp_root.to_string_lossy().into(),
+                        None,
+                    ));
+                }
             }
         }
     }
@@ -303,6 +327,56 @@ fn collect_world_roots_filtered(
     roots
 }
 
+/// 1.18+ 世界的高度范围
+const WORLD_MIN_Y: i32 = -64;
+const WORLD_MAX_Y: i32 = 320;
+
+/// 修改存档出生点：写入前把原 level.dat 备份为 level.dat.bak
+pub fn set_world_spawn_point(
+    world_folder_path: String,
+    x: i32,
+    y: i32,
+    z: i32,
+) -> Result<(), String> {
+    if !(WORLD_MIN_Y..WORLD_MAX_Y).contains(&y) {
+        return Err(format!(
+            "出生点 Y 坐标 {y} 超出范围（{WORLD_MIN_Y} ~ {}）",
+            WORLD_MAX_Y - 1
+        ));
+    }
+
+    let level_dat_path = Path::new(&world_folder_path).join("level.dat");
+    let data = fs::read(&level_dat_path).map_err(|e| format!("读取 level.dat 失败: {}", e))?;
+    let (version, mut root) =
+        parse_root_nbt_header(&data).map_err(|e| format!("解析 level.dat 失败: {}", e))?;
+    apply_spawn_point(&mut root, x, y, z)?;
+
+    fs::copy(
+        &level_dat_path,
+        level_dat_path.with_file_name("level.dat.bak"),
+    )
+    .map_err(|e| format!("备份 level.dat 失败: {}", e))?;
+    write_level_dat(&level_dat_path, &root, version)
+        .map_err(|e| format!("写入 level.dat 失败: {}", e))
+}
+
+fn apply_spawn_point(root: &mut NbtTag, x: i32, y: i32, z: i32) -> Result<(), String> {
+    let NbtTag::Compound(compound) = root else {
+        return Err("level.dat 根标签不是 Compound".to_string());
+    };
+    compound.insert("SpawnX".to_string(), NbtTag::Int(x));
+    compound.insert("SpawnY".to_string(), NbtTag::Int(y));
+    compound.insert("SpawnZ".to_string(), NbtTag::Int(z));
+    Ok(())
+}
+
+/// 把存档目录打包为 .mcworld（zip），导出与备份共用
+pub fn export_world_archive(source_dir: &Path, target_file: &Path) -> Result<()> {
+    anyhow::ensure!(source_dir.exists(), "源目录不存在");
+    write_world_archive(source_dir, target_file, |_| true).map_err(anyhow::Error::msg)?;
+    Ok(())
+}
+
 // ==================================================================================
 // 4. 辅助函数
 // ==================================================================================
@@ -323,27 +397,6 @@ fn bytes_to_human(bytes: u64) -> String {
     format!("{:.2} {}", b, UNITS[i])
 }
 
-fn get_dir_size_sync_limited(path: &Path, file_limit: usize) -> Option<u64> {
-    let mut total: u64 = 0;
-    let mut files_seen = 0usize;
-    for entry in WalkDir::new(path)
-        .follow_links(false)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        if let Ok(md) = entry.metadata() {
-            if md.is_file() {
-                files_seen = files_seen.saturating_add(1);
-                if files_seen > file_limit {
-                    return None;
-                }
-                total = total.saturating_add(md.len());
-            }
-        }
-    }
-    Some(total)
-}
-
 fn count_packs(value: &Value) -> usize {
     match value {
         Value::Array(a) => a.len(),
@@ -363,28 +416,26 @@ fn count_packs(value: &Value) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::io::Write as _;
 
     #[test]
-    fn limited_size_scan_stops_before_large_world_walk() {
-        let test_dir = std::env::temp_dir().join(format!(
-            "bmcbl-map-size-test-{}",
-            std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .expect("system time should be after unix epoch")
-                .as_nanos()
-        ));
-        fs::create_dir_all(&test_dir).expect("create test dir");
+    fn spawn_point_overwrites_existing_tags_and_rejects_out_of_range_y() {
+        let mut compound = indexmap::IndexMap::new();
+        compound.insert("LevelName".to_string(), NbtTag::String("Test".to_string()));
+        compound.insert("SpawnY".to_string(), NbtTag::Int(32767));
+        let mut root = NbtTag::Compound(compound);
 
-        for index in 0..3 {
-            let mut file =
-                fs::File::create(test_dir.join(format!("{index}.dat"))).expect("create test file");
-            file.write_all(&[1, 2, 3, 4]).expect("write test file");
-        }
-
-        assert_eq!(get_dir_size_sync_limited(&test_dir, 4), Some(12));
-        assert_eq!(get_dir_size_sync_limited(&test_dir, 2), None);
+        apply_spawn_point(&mut root, 10, -64, -20).expect("apply spawn point");
 
-        fs::remove_dir_all(&test_dir).expect("remove test dir");
+        let NbtTag::Compound(compound) = &root else {
+            panic!("root should stay a compound");
+        };
+        assert!(matches!(compound.get("SpawnX"), Some(NbtTag::Int(10))));
+        assert!(matches!(compound.get("SpawnY"), Some(NbtTag::Int(-64))));
+        assert!(matches!(compound.get("SpawnZ"), Some(NbtTag::Int(-20))));
+        assert!(matches!(compound.get("LevelName"), Some(NbtTag::String(_))));
+
+        let error = set_world_spawn_point("missing-world".to_string(), 0, 320, 0)
+            .expect_err("y = 320 should be rejected");
+        assert!(error.contains("超出范围"));
     }
 }
@@ -1,3 +1,4 @@
+use crate::core::cache_registry::{CacheDescriptor, clear_dir, dir_usage};
 use anyhow::{Context, Result, bail};
 use bedrock_world::{
     BedrockWorld, CancelFlag, ChunkPos, ChunkRecordQuery, ChunkRecordQueryResult, Dimension,
@@ -19,6 +20,24 @@ const MAX_MAP_INFO_QUERY_WORKERS: usize = 4;
 const MIN_TILES_PER_QUERY_WORKER: usize = 8;
 static TEMPORARY_FILE_SEQUENCE: AtomicU64 = AtomicU64::new(0);
 
+/// Map information tiles for every world, rebuilt on demand by the map viewer.
+pub(crate) static CACHE: CacheDescriptor = CacheDescriptor {
+    name: "map_info",
+    label: "地图信息图块",
+    default_selected: true,
+    usage: || {
+        dir_usage(&crate::utils::file_ops::cache_subdir(
+            MAP_INFO_CACHE_DIRECTORY,
+        ))
+    },
+    clear: || {
+        clear_dir(&crate::utils::file_ops::cache_subdir(
+            MAP_INFO_CACHE_DIRECTORY,
+        ))
+    },
+    warm: None,
+};
+
 /// A tile address for BMCBL-owned map information.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct MapInfoTileKey {
@@ -1,26 +1,66 @@
+pub mod addon_compatibility;
+pub mod analysis_suppressions;
 #[cfg(target_os = "windows")]
 pub mod appx;
 #[cfg(target_os = "linux")]
+#[path = "appx/signature.rs"]
+pub mod appx_signature;
+#[cfg(target_os = "linux")]
 #[path = "appx/utils.rs"]
 pub mod appx_utils;
 pub mod assets;
+pub mod edition_migration;
+pub mod editions;
 pub mod entity_avatar;
+pub mod export;
+pub mod game_logs;
+pub mod game_session;
 pub mod gdk;
 pub mod import;
+pub mod import_history;
 pub mod key_patcher;
+pub mod lang_coverage;
 pub mod launcher;
 pub mod map;
 pub mod map_info_cache;
+pub mod mod_bisect;
 pub mod mod_manager;
+pub mod mods_config;
 #[cfg(target_os = "windows")]
 pub mod mouse_lock;
 pub mod nbt;
+pub mod pack_changelog;
+pub mod pack_duplicates;
+pub mod pack_export;
+pub mod pack_index;
+pub mod pack_models;
+pub mod pack_names;
+pub mod pack_references;
+pub mod pack_reverts;
+pub mod pack_scaffold;
+#[cfg(target_os = "windows")]
+pub mod package_watcher;
 pub mod paths;
+pub mod recent_deletions;
 pub mod remote_versions;
 pub mod resource_packs;
 pub mod screenshots;
 pub mod servers;
+pub mod share_links;
 pub(crate) mod skin_pack_preview;
 pub mod skin_packs;
+pub mod ui_definitions;
+pub mod url_import;
+pub mod usage_stats;
 #[cfg(target_os = "windows")]
 pub mod uwp_minimize_fix;
+pub mod world_checksum;
+pub mod world_chunk_stats;
+pub mod world_datapacks;
+pub mod world_entities;
+pub mod world_experiments;
+pub mod world_freeze;
+pub mod world_players;
+pub mod world_statistics;
+pub mod world_structure_blocks;
+pub mod zip_names;
@@ -0,0 +1,352 @@
+//! 注入后黑屏的二分排查：每轮只启用一半候选模组，用户启动游戏后汇报是否正常，
+//! 逐轮缩小范围直到找出导致问题的模组。会话保存在 `BMCBL/bisect_session.json`，
+//! 启动器重启后可以继续；排查结束或取消时恢复会话开始前的启用状态。
+
+use crate::core::minecraft::mods_config::{self, ENABLED_MANIFEST};
+use crate::utils::file_ops;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+const SESSION_FILE_NAME: &str = "bisect_session.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum TestedHalf {
+    First,
+    Second,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct BisectSession {
+    version_name: String,
+    /// 会话开始前启用的模组文件夹，结束或取消时恢复为启用
+    original_enabled: Vec<String>,
+    /// 仍可能导致问题的模组
+    candidates: Vec<String>,
+    /// 本轮启用测试的模组
+    testing: Vec<String>,
+    tested_half: TestedHalf,
+    round: u32,
+    started_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum BisectStep {
+    /// 已只启用 `enabled_mods`，启动游戏后用 `bisect_report_result` 汇报是否正常
+    Testing {
+        version_name: String,
+        round: u32,
+        enabled_mods: Vec<String>,
+        remaining_candidates: usize,
+    },
+    /// 排查结束，启用状态已恢复
+    Finished {
+        version_name: String,
+        culprits: Vec<String>,
+        /// 两半分别启用都正常，只有一起启用时出问题
+        combination: bool,
+    },
+}
+
+/// 会话文件的读改写以及随后的启用状态切换必须串行
+static SESSION_LOCK: Mutex<()> = Mutex::const_new(());
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+fn session_path() -> PathBuf {
+    file_ops::bmcbl_dir().join(SESSION_FILE_NAME)
+}
+
+fn mods_dir(version_name: &str) -> PathBuf {
+    file_ops::versions_dir().join(version_name).join("mods")
+}
+
+fn load_session() -> Result<Option<BisectSession>, String> {
+    let path = session_path();
+    let raw = match fs::read_to_string(&path) {
+        Ok(raw) => raw,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("读取排查会话失败 {}: {}", path.display(), e)),
+    };
+    serde_json::from_str(&raw)
+        .map(Some)
+        .map_err(|e| format!("解析排查会话失败 {}: {}", path.display(), e))
+}
+
+fn save_session(session: &BisectSession) -> Result<(), String> {
+    let path = session_path();
+    let raw = serde_json::to_string_pretty(session).map_err(|e| e.to_string())?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, raw)
+        .and_then(|_| fs::rename(&tmp, &path))
+        .map_err(|e| format!("保存排查会话失败 {}: {}", path.display(), e))
+}
+
+fn remove_session() -> Result<(), String> {
+    let path = session_path();
+    match fs::remove_file(&path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            Err(format!("删除排查会话失败 {}: {}", path.display(), e))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// 当前启用的模组文件夹（存在 `manifest.json`），按名称排序
+fn enabled_mods(mods_dir: &Path) -> Result<Vec<String>, String> {
+    let mut mods: Vec<String> = fs::read_dir(mods_dir)
+        .map_err(|e| format!("读取 mods 目录失败 {}: {}", mods_dir.display(), e))?
+        .flatten()
+        .filter(|entry| entry.path().join(ENABLED_MANIFEST).is_file())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect();
+    mods.sort();
+    Ok(mods)
+}
+
+/// 排查期间被删除的模组直接跳过
+async fn set_enabled_if_present(mods_dir: &Path, name: &str, enabled: bool) -> Result<(), String> {
+    if !mods_dir.join(name).is_dir() {
+        return Ok(());
+    }
+    mods_config::set_mod_enabled(mods_dir, name, enabled)
+        .await
+        .map_err(|e| format!("切换模组启用状态失败 {}: {}", name, e))
+}
+
+impl BisectSession {
+    fn new(version_name: String, enabled: Vec<String>) -> Self {
+        let mut session = Self {
+            version_name,
+            original_enabled: enabled.clone(),
+            candidates: enabled,
+            testing: Vec::new(),
+            tested_half: TestedHalf::First,
+            round: 1,
+            started_at: now_ms(),
+        };
+        session.testing = session.first_half();
+        session
+    }
+
+    fn first_half(&self) -> Vec<String> {
+        self.candidates[..self.candidates.len() / 2].to_vec()
+    }
+
+    fn untested_candidates(&self) -> Vec<String> {
+        self.candidates
+            .iter()
+            .filter(|name| !self.testing.contains(name))
+            .cloned()
+            .collect()
+    }
+
+    /// 记录本轮结果；找到结论时返回 `(culprits, combination)`
+    fn advance(&mut self, ok: bool) -> Option<(Vec<String>, bool)> {
+        if ok {
+            if self.tested_half == TestedHalf::Second {
+                return Some((self.candidates.clone(), true));
+            }
+            self.testing = self.untested_candidates();
+            self.tested_half = TestedHalf::Second;
+            self.round += 1;
+            return None;
+        }
+
+        self.candidates = std::mem::take(&mut self.testing);
+        if self.candidates.len() <= 1 {
+            return Some((self.candidates.clone(), false));
+        }
+        self.testing = self.first_half();
+        self.tested_half = TestedHalf::First;
+        self.round += 1;
+        None
+    }
+
+    fn testing_step(&self) -> BisectStep {
+        BisectStep::Testing {
+            version_name: self.version_name.clone(),
+            round: self.round,
+            enabled_mods: self.testing.clone(),
+            remaining_candidates: self.candidates.len(),
+        }
+    }
+
+    /// 只启用本轮测试的模组，其余原本启用的模组暂时禁用
+    async fn apply(&self, mods_dir: &Path) -> Result<(), String> {
+        for name in &self.original_enabled {
+            set_enabled_if_present(mods_dir, name, self.testing.contains(name)).await?;
+        }
+        Ok(())
+    }
+
+    /// 恢复会话开始前的启用状态，尽量恢复每一个模组，最后汇总错误
+    async fn restore(&self, mods_dir: &Path) -> Result<(), String> {
+        let mut errors = Vec::new();
+        for name in &self.original_enabled {
+            if let Err(e) = set_enabled_if_present(mods_dir, name, true).await {
+                errors.push(e);
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+}
+
+/// 开始一次模组二分排查，返回第一轮要测试的模组；已有进行中的排查时拒绝
+pub async fn bisect_mods(version_name: String) -> Result<BisectStep, String> {
+    let _guard = SESSION_LOCK.lock().await;
+    if let Some(active) = load_session()? {
+        return Err(format!(
+            "版本 {} 的模组排查尚未结束，请先完成或取消",
+            active.version_name
+        ));
+    }
+    let mods_dir = mods_dir(&version_name);
+    let enabled = enabled_mods(&mods_dir)?;
+    if enabled.len() < 2 {
+        return Err("至少需要启用两个模组才能排查".to_string());
+    }
+
+    let session = BisectSession::new(version_name, enabled);
+    // 先落盘再改启用状态，中途崩溃也能从会话恢复
+    save_session(&session)?;
+    if let Err(e) = session.apply(&mods_dir).await {
+        if let Err(restore_error) = session.restore(&mods_dir).await {
+            // 保留会话文件，之后取消排查时还能继续恢复
+            warn!("开始模组排查失败后恢复启用状态失败: {}", restore_error);
+        } else if let Err(remove_error) = remove_session() {
+            warn!("{}", remove_error);
+        }
+        return Err(e);
+    }
+    info!(
+        "开始模组排查: 版本 {}, 共 {} 个启用的模组",
+        session.version_name,
+        session.original_enabled.len()
+    );
+    Ok(session.testing_step())
+}
+
+/// 汇报本轮启动是否正常（`ok` 为 false 表示仍然黑屏），返回下一轮或最终结论
+pub async fn bisect_report_result(ok: bool) -> Result<BisectStep, String> {
+    let _guard = SESSION_LOCK.lock().await;
+    let mut session = load_session()?.ok_or_else(|| "没有进行中的模组排查".to_string())?;
+    let mods_dir = mods_dir(&session.version_name);
+
+    let Some((culprits, combination)) = session.advance(ok) else {
+        save_session(&session)?;
+        session.apply(&mods_dir).await?;
+        return Ok(session.testing_step());
+    };
+    session.restore(&mods_dir).await?;
+    remove_session()?;
+    info!(
+        "模组排查结束: 版本 {}, 问题模组 {:?}{}",
+        session.version_name,
+        culprits,
+        if combination {
+            "（组合冲突）"
+        } else {
+            ""
+        }
+    );
+    Ok(BisectStep::Finished {
+        version_name: session.version_name,
+        culprits,
+        combination,
+    })
+}
+
+/// 放弃进行中的排查并恢复原来的启用状态
+pub async fn bisect_cancel() -> Result<(), String> {
+    let _guard = SESSION_LOCK.lock().await;
+    let Some(session) = load_session()? else {
+        return Ok(());
+    };
+    if let Err(e) = session.restore(&mods_dir(&session.version_name)).await {
+        // 保留会话文件，下次取消时还能继续恢复
+        warn!("取消模组排查时恢复启用状态失败: {}", e);
+        return Err(e);
+    }
+    remove_session()
+}
+
+/// 启动器重启后读取进行中的排查，没有时返回 `None`
+pub async fn current_bisect_step() -> Result<Option<BisectStep>, String> {
+    let _guard = SESSION_LOCK.lock().await;
+    Ok(load_session()?.map(|session| session.testing_step()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::minecraft::mods_config::DISABLED_MANIFEST;
+
+    fn write_mod(mods_dir: &Path, name: &str, enabled: bool) {
+        let dir = mods_dir.join(name);
+        fs::create_dir_all(&dir).expect("create mod dir");
+        let manifest = if enabled {
+            ENABLED_MANIFEST
+        } else {
+            DISABLED_MANIFEST
+        };
+        fs::write(dir.join(manifest), "{}").expect("write manifest");
+    }
+
+    #[tokio::test]
+    async fn bisection_isolates_culprit_and_restores_enabled_mods() {
+        let mods_dir =
+            std::env::temp_dir().join(format!("bmcbl-mod-bisect-{}", uuid::Uuid::new_v4()));
+        for name in ["a", "b", "c", "d", "e"] {
+            write_mod(&mods_dir, name, true);
+        }
+        write_mod(&mods_dir, "off", false);
+
+        let enabled = enabled_mods(&mods_dir).expect("list enabled");
+        assert_eq!(enabled, ["a", "b", "c", "d", "e"]);
+        let mut session = BisectSession::new("1.21.0".to_string(), enabled);
+        let culprit = "d";
+        let verdict = loop {
+            session.apply(&mods_dir).await.expect("apply subset");
+            assert_eq!(enabled_mods(&mods_dir).expect("list"), session.testing);
+            let ok = !session.testing.iter().any(|name| name == culprit);
+            if let Some(verdict) = session.advance(ok) {
+                break verdict;
+            }
+            assert!(session.round <= 8, "bisection did not converge");
+        };
+        assert_eq!(verdict, (vec!["d".to_string()], false));
+
+        session.restore(&mods_dir).await.expect("restore");
+        assert_eq!(
+            enabled_mods(&mods_dir).expect("list restored"),
+            ["a", "b", "c", "d", "e"]
+        );
+        assert!(mods_dir.join("off").join(DISABLED_MANIFEST).is_file());
+
+        // 两半单独都正常：判定为组合冲突
+        let mut pair = BisectSession::new("1.21.0".to_string(), vec!["x".into(), "y".into()]);
+        assert_eq!(pair.advance(true), None);
+        assert_eq!(pair.testing, ["y"]);
+        assert_eq!(
+            pair.advance(true),
+            Some((vec!["x".into(), "y".into()], true))
+        );
+
+        fs::remove_dir_all(&mods_dir).expect("remove test dir");
+    }
+}
@@ -1,9 +1,28 @@
-use anyhow::{Context, Result};
+use crate::core::minecraft::mods_config::lock_mods_dir;
+use crate::core::version::settings::{ExtraModState, VersionConfig};
+use crate::utils::file_locks;
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::future::Future;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tokio::fs;
 use tracing::{debug, warn};
 
+/// 杀毒软件扫描刚解压的 DLL 时会短暂占用文件，先重试；仍被占用时记录占用进程并跳过
+async fn canonical_dll_path(dll_path: &Path) -> Option<PathBuf> {
+    match file_locks::canonicalize(dll_path).await {
+        Ok(path) => Some(path),
+        Err(error) => {
+            if let Some(locked) = file_locks::as_file_locked(&error) {
+                warn!("Mod DLL 被占用，跳过加载: {}", locked);
+            }
+            None
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct ModManifest {
     name: String,
@@ -13,6 +32,99 @@ struct ModManifest {
     /// Only meaningful for `type = "hot-inject"`; handled by `BLoader.dll`.
     #[serde(default)]
     inject_delay_ms: Option<u64>,
+    /// 启动后由启动器按 PID 注入失败时的重试次数，BLoader 不读取
+    #[serde(default)]
+    inject_retries: Option<u32>,
+    #[serde(default)]
+    inject_retry_interval_ms: Option<u64>,
+}
+
+const DEFAULT_INJECT_RETRIES: u32 = 3;
+const DEFAULT_INJECT_RETRY_INTERVAL_MS: u64 = 500;
+
+/// 启动后按 PID 注入的重试策略：游戏刚拉起时加载器锁或模块列表尚未就绪，首次注入可能失败
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InjectRetryPolicy {
+    /// 首次失败后最多再尝试的次数
+    pub retries: u32,
+    pub retry_interval_ms: u64,
+}
+
+impl Default for InjectRetryPolicy {
+    fn default() -> Self {
+        Self {
+            retries: DEFAULT_INJECT_RETRIES,
+            retry_interval_ms: DEFAULT_INJECT_RETRY_INTERVAL_MS,
+        }
+    }
+}
+
+impl InjectRetryPolicy {
+    /// 失败时按策略重试 `attempt`；每次重试前用 `alive` 确认游戏进程仍在运行，进程已退出时立即放弃。
+    /// `on_retry(attempt, error)` 在等待重试前调用
+    pub async fn run<F, Fut>(
+        &self,
+        pid: u32,
+        mut attempt: F,
+        alive: impl Fn(u32) -> bool,
+        mut on_retry: impl FnMut(u32, &str),
+    ) -> Result<(), String>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<(), String>>,
+    {
+        let mut retried = 0;
+        loop {
+            let error = match attempt().await {
+                Ok(()) => return Ok(()),
+                Err(error) => error,
+            };
+            if retried >= self.retries {
+                return Err(if retried == 0 {
+                    error
+                } else {
+                    format!("重试 {retried} 次后仍失败: {error}")
+                });
+            }
+            retried += 1;
+            debug!(
+                attempt = retried,
+                retries = self.retries,
+                "注入失败，稍后重试: {error}"
+            );
+            on_retry(retried, &error);
+            tokio::time::sleep(Duration::from_millis(self.retry_interval_ms)).await;
+            if !alive(pid) {
+                return Err(format!("游戏进程 {pid} 已退出，停止重试: {error}"));
+            }
+        }
+    }
+}
+
+/// 读取 DLL 所在模组目录 manifest 中的重试配置；没有 manifest、entry 不是该 DLL 或未配置时使用默认值
+pub async fn load_inject_retry_policy(dll_path: &Path) -> InjectRetryPolicy {
+    let default = InjectRetryPolicy::default();
+    let Some(manifest_path) = dll_path.parent().map(|dir| dir.join("manifest.json")) else {
+        return default;
+    };
+    let Ok(content) = fs::read_to_string(&manifest_path).await else {
+        return default;
+    };
+    let Ok(manifest) = serde_json::from_str::<ModManifest>(&content) else {
+        return default;
+    };
+    let is_entry = dll_path
+        .file_name()
+        .is_some_and(|name| name.eq_ignore_ascii_case(manifest.entry.as_str()));
+    if !is_entry {
+        return default;
+    }
+    InjectRetryPolicy {
+        retries: manifest.inject_retries.unwrap_or(default.retries),
+        retry_interval_ms: manifest
+            .inject_retry_interval_ms
+            .unwrap_or(default.retry_interval_ms),
+    }
 }
 
 /// 扫描 mods 目录，返回需要加载的 DLL **绝对路径**列表
@@ -21,15 +133,22 @@ struct ModManifest {
 /// 注意：`inject_delay_ms` 的延迟注入由 `BLoader.dll` 处理，这里只负责读取并提供 DLL 路径。
 /// 返回: Vec<(AbsolutePath, DelayMs)> (Delay 固定为 0)
 pub async fn load_mods_config(mods_dir: &Path) -> Result<Vec<(PathBuf, u64)>> {
+    // 与管理页的启用 / 修改互斥，避免读到切换到一半的状态
+    let _guard = lock_mods_dir(mods_dir).await;
     let mut result = Vec::new();
     let mut has_preloader = false;
     let mut preloader_path: Option<PathBuf> = None;
 
     // 确保目录存在
     if !mods_dir.exists() {
-        fs::create_dir_all(mods_dir)
-            .await
-            .context("Failed to create mods directory")?;
+        // 数据目录只读时不能阻止无 Mod 启动
+        if let Err(error) = fs::create_dir_all(mods_dir).await {
+            warn!(
+                "创建 mods 目录失败，按无 Mod 继续启动 {}: {}",
+                mods_dir.display(),
+                error
+            );
+        }
         return Ok(result);
     }
 
@@ -51,7 +170,7 @@ pub async fn load_mods_config(mods_dir: &Path) -> Result<Vec<(PathBuf, u64)>> {
                     if manifest.entry.eq_ignore_ascii_case("PreLoader.dll") {
                         let dll_path = path.join(&manifest.entry);
                         if dll_path.exists() {
-                            if let Ok(abs_path) = fs::canonicalize(&dll_path).await {
+                            if let Some(abs_path) = canonical_dll_path(&dll_path).await {
                                 let mut clean_path = abs_path.clone();
                                 if let Some(str_path) = abs_path.to_str() {
                                     if str_path.starts_with(r"\\?\") {
@@ -112,7 +231,7 @@ pub async fn load_mods_config(mods_dir: &Path) -> Result<Vec<(PathBuf, u64)>> {
                                 let dll_path = path.join(&manifest.entry);
                                 if dll_path.exists() {
                                     // 获取规范路径
-                                    if let Ok(abs_path) = fs::canonicalize(&dll_path).await {
+                                    if let Some(abs_path) = canonical_dll_path(&dll_path).await {
                                         let mut clean_path = abs_path.clone();
                                         // 去除 Windows UNC 前缀
                                         if let Some(str_path) = abs_path.to_str() {
@@ -149,3 +268,268 @@ pub async fn load_mods_config(mods_dir: &Path) -> Result<Vec<(PathBuf, u64)>> {
 
     Ok(result)
 }
+
+/// 规范路径的比较键：Windows 路径不区分大小写
+fn dll_path_key(path: &Path) -> String {
+    path.to_string_lossy().replace('/', "\\").to_lowercase()
+}
+
+/// 解析 `extra_mod_dirs` 中的一项：相对路径按版本目录解析
+fn resolve_extra_mod_dir(package_folder: &Path, dir: &str) -> PathBuf {
+    let dir = Path::new(dir.trim());
+    if dir.is_absolute() {
+        dir.to_path_buf()
+    } else {
+        package_folder.join(dir)
+    }
+}
+
+/// 扫描一个额外 mods 目录中已启用的模组，返回 (规范路径, manifest 中的注入延迟)；
+/// 这些 DLL 不经 BLoader 加载，延迟直接取自 manifest
+async fn scan_extra_mod_dir(mods_dir: &Path) -> Result<Vec<(PathBuf, u64)>> {
+    let _guard = lock_mods_dir(mods_dir).await;
+    let mut result = Vec::new();
+    let mut entries = fs::read_dir(mods_dir).await?;
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let manifest_path = entry.path().join("manifest.json");
+        let Ok(content) = fs::read_to_string(&manifest_path).await else {
+            continue;
+        };
+        let manifest = match serde_json::from_str::<ModManifest>(&content) {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                warn!("Manifest 解析错误 {}: {}", manifest_path.display(), e);
+                continue;
+            }
+        };
+        if manifest.mod_type != "preload-native" && manifest.mod_type != "hot-inject" {
+            continue;
+        }
+        let dll_path = entry.path().join(&manifest.entry);
+        if !dll_path.exists() {
+            warn!("Manifest 指定的 DLL 不存在: {}", dll_path.display());
+            continue;
+        }
+        if let Some(abs_path) = canonical_dll_path(&dll_path).await {
+            let clean_path = abs_path
+                .to_str()
+                .and_then(|path| path.strip_prefix(r"\\?\"))
+                .map(PathBuf::from)
+                .unwrap_or(abs_path);
+            result.push((clean_path, manifest.inject_delay_ms.unwrap_or(0)));
+        }
+    }
+    Ok(result)
+}
+
+#[derive(Debug, Default)]
+pub struct ExtraModsScan {
+    /// 本版本启用的额外模组 (路径, 延迟毫秒)
+    pub mods: Vec<(PathBuf, u64)>,
+    /// 首次发现、需要写回 `extra_mods` 的条目
+    pub discovered: Vec<(String, ExtraModState)>,
+}
+
+/// 加载版本配置 `extra_mod_dirs` 中的模组，启用 / 延迟规则与版本自身 mods 相同；
+/// 与 `loaded`（已加载的版本自身模组）及彼此之间按规范路径去重。
+/// `extra_mods` 中已有的条目以配置为准，不存在的目录只记录警告
+pub async fn load_extra_mods(
+    package_folder: &Path,
+    config: &VersionConfig,
+    loaded: &[PathBuf],
+) -> ExtraModsScan {
+    let mut seen: HashSet<String> = loaded.iter().map(|path| dll_path_key(path)).collect();
+    let mut scan = ExtraModsScan::default();
+    for dir in config
+        .extra_mod_dirs
+        .iter()
+        .filter(|dir| !dir.trim().is_empty())
+    {
+        let mods_dir = resolve_extra_mod_dir(package_folder, dir);
+        if !mods_dir.is_dir() {
+            warn!("额外 mods 目录不存在，已跳过: {}", mods_dir.display());
+            continue;
+        }
+        let mods = match scan_extra_mod_dir(&mods_dir).await {
+            Ok(mods) => mods,
+            Err(error) => {
+                warn!("扫描额外 mods 目录失败 {}: {}", mods_dir.display(), error);
+                continue;
+            }
+        };
+        for (path, manifest_delay) in mods {
+            if !seen.insert(dll_path_key(&path)) {
+                continue;
+            }
+            let key = path.to_string_lossy().into_owned();
+            let state = match config.extra_mods.get(&key) {
+                Some(state) => *state,
+                None => {
+                    let state = ExtraModState {
+                        enabled: true,
+                        inject_delay_ms: manifest_delay,
+                    };
+                    scan.discovered.push((key, state));
+                    state
+                }
+            };
+            if state.enabled {
+                debug!("加载额外 Mod: {}", path.display());
+                scan.mods.push((path, state.inject_delay_ms));
+            }
+        }
+    }
+    scan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn write_mod(mods_dir: &Path, name: &str, manifest: &str, delay: u64) {
+        let dir = mods_dir.join(name);
+        std::fs::create_dir_all(&dir).expect("create mod dir");
+        std::fs::write(dir.join(format!("{name}.dll")), b"MZ").expect("write dll");
+        std::fs::write(
+            dir.join(manifest),
+            json!({
+                "name": name,
+                "entry": format!("{name}.dll"),
+                "type": "hot-inject",
+                "inject_delay_ms": delay
+            })
+            .to_string(),
+        )
+        .expect("write manifest");
+    }
+
+    #[tokio::test]
+    async fn extra_dirs_merge_with_config_state_and_dedup() {
+        let root = std::env::temp_dir().join(format!("bmcbl-extra-mods-{}", uuid::Uuid::new_v4()));
+        let package_folder = root.join("version");
+        let shared = root.join("shared");
+        write_mod(&shared, "fps", "manifest.json", 500);
+        write_mod(&shared, "zoom", "manifest.json", 0);
+        write_mod(&shared, "off", ".manifest.json", 0);
+        std::fs::create_dir_all(&package_folder).expect("create version dir");
+
+        let mut config = VersionConfig {
+            extra_mod_dirs: vec![
+                shared.to_string_lossy().into_owned(),
+                "../shared".to_string(),
+                root.join("missing").to_string_lossy().into_owned(),
+            ],
+            ..VersionConfig::default()
+        };
+        let first = load_extra_mods(&package_folder, &config, &[]).await;
+        assert_eq!(first.mods.len(), 2);
+        assert_eq!(first.discovered.len(), 2);
+        let fps = first
+            .mods
+            .iter()
+            .find(|(path, _)| path.ends_with("fps.dll"))
+            .expect("fps mod");
+        assert_eq!(fps.1, 500);
+
+        for (key, mut state) in first.discovered {
+            state.enabled = !key.ends_with("zoom.dll");
+            config.extra_mods.insert(key, state);
+        }
+        let second = load_extra_mods(&package_folder, &config, &[fps.0.clone()]).await;
+        assert!(second.mods.is_empty());
+        assert!(second.discovered.is_empty());
+
+        std::fs::remove_dir_all(&root).expect("remove test dir");
+    }
+
+    #[tokio::test]
+    async fn retry_policy_reads_entry_manifest_overrides() {
+        let root =
+            std::env::temp_dir().join(format!("bmcbl-retry-policy-{}", uuid::Uuid::new_v4()));
+        write_mod(&root, "fps", "manifest.json", 0);
+        write_mod(&root, "zoom", "manifest.json", 0);
+
+        assert_eq!(
+            load_inject_retry_policy(&root.join("fps").join("fps.dll")).await,
+            InjectRetryPolicy::default()
+        );
+        assert_eq!(
+            load_inject_retry_policy(&root.join("fps").join("other.dll")).await,
+            InjectRetryPolicy::default()
+        );
+        std::fs::write(
+            root.join("zoom").join("manifest.json"),
+            json!({
+                "name": "zoom",
+                "entry": "zoom.dll",
+                "type": "hot-inject",
+                "inject_retries": 5,
+                "inject_retry_interval_ms": 250
+            })
+            .to_string(),
+        )
+        .expect("write retry manifest");
+        assert_eq!(
+            load_inject_retry_policy(&root.join("zoom").join("zoom.dll")).await,
+            InjectRetryPolicy {
+                retries: 5,
+                retry_interval_ms: 250
+            }
+        );
+
+        std::fs::remove_dir_all(&root).expect("remove test dir");
+    }
+
+    #[tokio::test]
+    async fn retry_stops_once_process_is_gone() {
+        let policy = InjectRetryPolicy {
+            retries: 5,
+            retry_interval_ms: 1,
+        };
+        let attempts = std::cell::Cell::new(0u32);
+        let mut retries_logged = Vec::new();
+        let result = policy
+            .run(
+                42,
+                || {
+                    attempts.set(attempts.get() + 1);
+                    async { Err("模块列表未就绪".to_string()) }
+                },
+                |pid| {
+                    assert_eq!(pid, 42);
+                    attempts.get() < 2
+                },
+                |attempt, _| retries_logged.push(attempt),
+            )
+            .await;
+
+        let error = result.expect_err("process exited");
+        assert!(error.contains("已退出"), "{error}");
+        assert_eq!(attempts.get(), 2);
+        assert_eq!(retries_logged, vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn retry_gives_up_after_policy_retries() {
+        let policy = InjectRetryPolicy {
+            retries: 2,
+            retry_interval_ms: 1,
+        };
+        let attempts = std::cell::Cell::new(0u32);
+        let result = policy
+            .run(
+                7,
+                || {
+                    attempts.set(attempts.get() + 1);
+                    async { Err("拒绝访问".to_string()) }
+                },
+                |_| true,
+                |_, _| {},
+            )
+            .await;
+
+        assert_eq!(result, Err("重试 2 次后仍失败: 拒绝访问".to_string()));
+        assert_eq!(attempts.get(), 3);
+    }
+}
@@ -0,0 +1,258 @@
+//! 版本 `mods/` 下各模组的 manifest（启用为 `manifest.json`，禁用为 `.manifest.json`）
+//! 同时被管理页和启动流程读写。这里按 mods 目录提供异步互斥锁，切换启用状态、修改字段、
+//! 列表读取和启动时扫描都在锁内进行；写入使用临时文件 + 重命名，任何时刻读到的都是完整 JSON。
+//! 每次写入递增 `bmcbl_revision`，写回前发现文件被锁外的写入者（如游戏内 BLoader 整理 mods）
+//! 改过时重新读取、合并后再写，不会覆盖对方的修改。
+
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex};
+use tokio::sync::OwnedMutexGuard;
+use tracing::warn;
+
+pub(crate) const ENABLED_MANIFEST: &str = "manifest.json";
+pub(crate) const DISABLED_MANIFEST: &str = ".manifest.json";
+const REVISION_KEY: &str = "bmcbl_revision";
+const MAX_MERGE_ATTEMPTS: usize = 5;
+
+static MODS_DIR_LOCKS: LazyLock<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn lock_key(mods_dir: &Path) -> String {
+    mods_dir
+        .to_string_lossy()
+        .trim_end_matches(['/', '\\'])
+        .replace('\\', "/")
+        .to_lowercase()
+}
+
+/// 获取 mods 目录的读写锁，持有期间其他读写方排队等待
+pub(crate) async fn lock_mods_dir(mods_dir: &Path) -> OwnedMutexGuard<()> {
+    let lock = MODS_DIR_LOCKS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .entry(lock_key(mods_dir))
+        .or_default()
+        .clone();
+    lock.lock_owned().await
+}
+
+/// 先写同目录下的临时文件再重命名覆盖，写入中断不会留下半截 JSON
+pub(crate) fn write_json_atomic(path: &Path, value: &Value) -> io::Result<()> {
+    let raw = serde_json::to_string_pretty(value)?;
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let temp_path = path.with_file_name(format!("{}.{}.tmp", file_name, uuid::Uuid::new_v4()));
+    fs::write(&temp_path, raw)?;
+    if let Err(error) = fs::rename(&temp_path, path) {
+        if let Err(remove_error) = fs::remove_file(&temp_path) {
+            warn!("删除临时文件失败 {}: {}", temp_path.display(), remove_error);
+        }
+        return Err(error);
+    }
+    Ok(())
+}
+
+fn manifest_path(mod_dir: &Path) -> Result<PathBuf, String> {
+    [ENABLED_MANIFEST, DISABLED_MANIFEST]
+        .into_iter()
+        .map(|name| mod_dir.join(name))
+        .find(|path| path.exists())
+        .ok_or_else(|| "未找到 manifest.json 或 .manifest.json".to_string())
+}
+
+fn manifest_revision(manifest: &Map<String, Value>) -> u64 {
+    manifest
+        .get(REVISION_KEY)
+        .and_then(Value::as_u64)
+        .unwrap_or(0)
+}
+
+fn mod_dir(mods_dir: &Path, mod_id: &str) -> Result<PathBuf, String> {
+    let mod_dir = mods_dir.join(mod_id);
+    if !mod_dir.is_dir() {
+        return Err(format!("Mod 目录不存在: {mod_id}"));
+    }
+    Ok(mod_dir)
+}
+
+/// 通过重命名 manifest 启用或禁用模组；两个 manifest 同时存在时按目标状态清理多余的一个
+pub async fn set_mod_enabled(mods_dir: &Path, mod_id: &str, enabled: bool) -> Result<(), String> {
+    let mod_dir = mod_dir(mods_dir, mod_id)?;
+    let _guard = lock_mods_dir(mods_dir).await;
+    tokio::task::spawn_blocking(move || set_mod_enabled_blocking(&mod_dir, enabled))
+        .await
+        .map_err(|e| format!("切换 Mod 状态任务失败: {}", e))?
+}
+
+fn set_mod_enabled_blocking(mod_dir: &Path, enabled: bool) -> Result<(), String> {
+    let enabled_path = mod_dir.join(ENABLED_MANIFEST);
+    let disabled_path = mod_dir.join(DISABLED_MANIFEST);
+
+    if enabled {
+        if enabled_path.exists() {
+            return Ok(());
+        }
+        if disabled_path.exists() {
+            return fs::rename(disabled_path, enabled_path)
+                .map_err(|error| format!("启用 Mod 失败: {error}"));
+        }
+        return Err("未找到 .manifest.json，无法启用".to_string());
+    }
+
+    if disabled_path.exists() {
+        if enabled_path.exists() {
+            fs::remove_file(enabled_path)
+                .map_err(|error| format!("清理冲突 manifest 失败: {error}"))?;
+        }
+        return Ok(());
+    }
+    if enabled_path.exists() {
+        return fs::rename(enabled_path, disabled_path)
+            .map_err(|error| format!("禁用 Mod 失败: {error}"));
+    }
+    Err("未找到 manifest.json，无法禁用".to_string())
+}
+
+/// 在锁内读取最新的 manifest 交给 `update` 修改后写回，返回写入后的 `bmcbl_revision`
+pub async fn update_mod_manifest(
+    mods_dir: &Path,
+    mod_id: &str,
+    update: impl Fn(&mut Map<String, Value>) + Send + 'static,
+) -> Result<u64, String> {
+    let mod_dir = mod_dir(mods_dir, mod_id)?;
+    let _guard = lock_mods_dir(mods_dir).await;
+    tokio::task::spawn_blocking(move || update_mod_manifest_blocking(&mod_dir, &update))
+        .await
+        .map_err(|e| format!("写入 Manifest 任务失败: {}", e))?
+}
+
+fn update_mod_manifest_blocking(
+    mod_dir: &Path,
+    update: &dyn Fn(&mut Map<String, Value>),
+) -> Result<u64, String> {
+    for _ in 0..MAX_MERGE_ATTEMPTS {
+        let path = manifest_path(mod_dir)?;
+        let raw =
+            fs::read_to_string(&path).map_err(|error| format!("读取 Manifest 失败: {error}"))?;
+        let mut manifest: Map<String, Value> =
+            serde_json::from_str(&raw).map_err(|error| format!("Manifest 解析失败: {error}"))?;
+        let revision = manifest_revision(&manifest) + 1;
+        update(&mut manifest);
+        manifest.insert(REVISION_KEY.to_string(), json!(revision));
+
+        // 锁外的写入者不会递增 revision，所以直接比较内容；读取后被改动或改名时重新合并
+        if fs::read_to_string(&path).ok().as_deref() != Some(raw.as_str()) {
+            continue;
+        }
+        write_json_atomic(&path, &Value::Object(manifest))
+            .map_err(|error| format!("写入 Manifest 失败: {error}"))?;
+        return Ok(revision);
+    }
+    Err("Manifest 正在被其他程序频繁修改，请稍后重试".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::minecraft::mod_manager::load_mods_config;
+
+    const MOD_COUNT: usize = 6;
+    const ROUNDS: u64 = 20;
+
+    fn write_mod(mods_dir: &Path, index: usize) {
+        let dir = mods_dir.join(format!("mod-{index}"));
+        fs::create_dir_all(&dir).expect("create mod dir");
+        fs::write(dir.join("mod.dll"), b"MZ").expect("write dll");
+        fs::write(
+            dir.join(ENABLED_MANIFEST),
+            json!({ "name": format!("Mod {index}"), "entry": "mod.dll", "type": "preload-native" })
+                .to_string(),
+        )
+        .expect("write manifest");
+    }
+
+    /// 不加锁直接读取，验证任何时刻磁盘上的 manifest 都是完整 JSON
+    fn assert_manifests_parse(mods_dir: &Path) {
+        for index in 0..MOD_COUNT {
+            for name in [ENABLED_MANIFEST, DISABLED_MANIFEST] {
+                let path = mods_dir.join(format!("mod-{index}")).join(name);
+                let Ok(raw) = fs::read_to_string(&path) else {
+                    continue;
+                };
+                serde_json::from_str::<Value>(&raw)
+                    .unwrap_or_else(|e| panic!("{} is not valid JSON: {e}", path.display()));
+            }
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_toggles_and_launch_scans_lose_no_changes() {
+        let mods_dir =
+            std::env::temp_dir().join(format!("bmcbl-mods-config-{}", uuid::Uuid::new_v4()));
+        for index in 0..MOD_COUNT {
+            write_mod(&mods_dir, index);
+        }
+
+        let mut writers = Vec::new();
+        for index in 0..MOD_COUNT {
+            let mods_dir = mods_dir.clone();
+            writers.push(tokio::spawn(async move {
+                let mod_id = format!("mod-{index}");
+                for round in 0..ROUNDS {
+                    set_mod_enabled(&mods_dir, &mod_id, round % 2 == 1)
+                        .await
+                        .expect("toggle mod");
+                    update_mod_manifest(&mods_dir, &mod_id, move |manifest| {
+                        manifest.insert("inject_delay_ms".to_string(), json!(round));
+                    })
+                    .await
+                    .expect("update manifest");
+                    tokio::task::yield_now().await;
+                }
+            }));
+        }
+        let scan_dir = mods_dir.clone();
+        let scanner = tokio::spawn(async move {
+            for _ in 0..ROUNDS {
+                let loaded = load_mods_config(&scan_dir).await.expect("launch scan");
+                assert!(loaded.len() <= MOD_COUNT);
+                assert_manifests_parse(&scan_dir);
+                tokio::task::yield_now().await;
+            }
+        });
+        for writer in writers {
+            writer.await.expect("writer task");
+        }
+        scanner.await.expect("scanner task");
+
+        for index in 0..MOD_COUNT {
+            let dir = mods_dir.join(format!("mod-{index}"));
+            assert!(!dir.join(DISABLED_MANIFEST).exists());
+            let manifest: Map<String, Value> = serde_json::from_str(
+                &fs::read_to_string(dir.join(ENABLED_MANIFEST)).expect("read manifest"),
+            )
+            .expect("parse manifest");
+            assert_eq!(manifest["inject_delay_ms"], json!(ROUNDS - 1));
+            assert_eq!(manifest_revision(&manifest), ROUNDS);
+            assert_eq!(manifest["type"], json!("preload-native"));
+            let leftovers = fs::read_dir(&dir)
+                .expect("read mod dir")
+                .flatten()
+                .filter(|entry| entry.file_name().to_string_lossy().ends_with(".tmp"))
+                .count();
+            assert_eq!(leftovers, 0);
+        }
+        assert_eq!(
+            load_mods_config(&mods_dir).await.expect("final scan").len(),
+            MOD_COUNT
+        );
+
+        fs::remove_dir_all(&mods_dir).expect("remove test dir");
+    }
+}
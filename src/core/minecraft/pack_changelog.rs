@@ -0,0 +1,257 @@
+//! 生成两个包版本之间的更新日志（Markdown）：版本号变化、manifest 改动数，
+//! 以及新增 / 删除 / 修改的文件列表。两侧都在磁盘上，修改按大小加内容哈希判断，
+//! 不会漏掉大小不变的改动。
+
+use crate::core::minecraft::import::{
+    PackContentDiff, PartialManifest, VersionComparison, build_pack_content_diff,
+    compare_pack_versions, manifest_header_version, pack_dir_file_sizes, read_manifest_from_dir,
+    strip_json_comments, version_to_string,
+};
+use crate::core::minecraft::pack_duplicates::hash_file_into;
+use crate::core::minecraft::pack_names::strip_minecraft_formatting;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use xxhash_rust::xxh3::Xxh3;
+
+const LAUNCHER_VERSION: &str = env!("CARGO_PKG_VERSION");
+const HASH_BUFFER_SIZE: usize = 256 * 1024;
+
+/// 逐个叶子节点对比两份 manifest，返回不同的字段数（一侧缺失也算一处）
+fn count_value_changes(old: Option<&Value>, new: Option<&Value>) -> usize {
+    match (old, new) {
+        (Some(Value::Object(old)), Some(Value::Object(new))) => old
+            .keys()
+            .chain(new.keys().filter(|key| !old.contains_key(*key)))
+            .map(|key| count_value_changes(old.get(key), new.get(key)))
+            .sum(),
+        (Some(Value::Array(old)), Some(Value::Array(new))) => (0..old.len().max(new.len()))
+            .map(|index| count_value_changes(old.get(index), new.get(index)))
+            .sum(),
+        (old, new) if old == new => 0,
+        _ => 1,
+    }
+}
+
+/// 包目录内每个文件的大小与内容哈希，路径规则与 `pack_dir_file_sizes` 一致
+fn pack_dir_file_fingerprints(dir: &Path) -> Result<HashMap<String, (u64, u128)>, String> {
+    let mut buffer = vec![0u8; HASH_BUFFER_SIZE];
+    let mut fingerprints = HashMap::new();
+    for (relative, size) in pack_dir_file_sizes(dir) {
+        let mut hasher = Xxh3::new();
+        hash_file_into(&mut hasher, &dir.join(&relative), &mut buffer)?;
+        fingerprints.insert(relative, (size, hasher.digest128()));
+    }
+    Ok(fingerprints)
+}
+
+fn read_manifest_value(dir: &Path) -> Option<Value> {
+    let raw = fs::read_to_string(dir.join("manifest.json")).ok()?;
+    serde_json::from_str(&strip_json_comments(raw.trim_start_matches('\u{feff}'))).ok()
+}
+
+fn manifest_name(manifest: &PartialManifest) -> Option<String> {
+    let name = manifest.header.as_ref()?.name.as_deref()?;
+    let name = strip_minecraft_formatting(name).trim().to_string();
+    (!name.is_empty()).then_some(name)
+}
+
+fn manifest_version(manifest: Option<&PartialManifest>) -> String {
+    manifest
+        .and_then(manifest_header_version)
+        .map(version_to_string)
+        .unwrap_or_else(|| "未知".to_string())
+}
+
+fn push_file_section(markdown: &mut String, title: &str, files: &[String], total: usize) {
+    let _ = writeln!(markdown, "## {title}（{total}）\n");
+    if files.is_empty() {
+        markdown.push_str("无\n\n");
+        return;
+    }
+    for file in files {
+        let _ = writeln!(markdown, "- `{file}`");
+    }
+    if total > files.len() {
+        let _ = writeln!(markdown, "- ……另有 {} 项未列出", total - files.len());
+    }
+    markdown.push('\n');
+}
+
+fn render_changelog(
+    pack_name: &str,
+    old_manifest: Option<&PartialManifest>,
+    new_manifest: Option<&PartialManifest>,
+    manifest_changes: usize,
+    diff: &PackContentDiff,
+    generated_at: &str,
+) -> String {
+    let mut markdown = String::new();
+    let _ = writeln!(markdown, "# {pack_name} 更新日志\n");
+    let _ = writeln!(markdown, "生成时间：{generated_at}\n");
+
+    markdown.push_str("## 版本\n\n");
+    let _ = writeln!(markdown, "- 旧版本：{}", manifest_version(old_manifest));
+    let _ = writeln!(markdown, "- 新版本：{}", manifest_version(new_manifest));
+    let comparison = compare_pack_versions(
+        new_manifest.and_then(manifest_header_version),
+        old_manifest.and_then(manifest_header_version),
+    );
+    if let Some(comparison) = comparison {
+        let label = match comparison.comparison {
+            VersionComparison::Newer => "升级",
+            VersionComparison::Older => "降级",
+            VersionComparison::Same => "版本号未变",
+        };
+        let _ = writeln!(markdown, "- 变化：{label}");
+    }
+    let _ = writeln!(markdown, "- manifest 改动：{manifest_changes} 处\n");
+
+    push_file_section(&mut markdown, "新增文件", &diff.added, diff.added_count);
+    push_file_section(&mut markdown, "删除文件", &diff.removed, diff.removed_count);
+    push_file_section(&mut markdown, "修改文件", &diff.changed, diff.changed_count);
+
+    let _ = writeln!(
+        markdown,
+        "---\n\n由 Better Minecraft Bedrock Launcher {LAUNCHER_VERSION} 生成"
+    );
+    markdown
+}
+
+fn generate_pack_changelog_blocking(
+    old_dir: &Path,
+    new_dir: &Path,
+    output_path: &Path,
+) -> Result<(), String> {
+    for dir in [old_dir, new_dir] {
+        if !dir.is_dir() {
+            return Err(format!("包目录不存在: {}", dir.display()));
+        }
+    }
+    let old_manifest = read_manifest_from_dir(old_dir);
+    let new_manifest = read_manifest_from_dir(new_dir);
+    if old_manifest.is_none() && new_manifest.is_none() {
+        return Err("两个目录都没有有效的 manifest.json".to_string());
+    }
+
+    let pack_name = new_manifest
+        .as_ref()
+        .and_then(manifest_name)
+        .or_else(|| old_manifest.as_ref().and_then(manifest_name))
+        .unwrap_or_else(|| {
+            new_dir
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        });
+    let manifest_changes = count_value_changes(
+        read_manifest_value(old_dir).as_ref(),
+        read_manifest_value(new_dir).as_ref(),
+    );
+    let diff = build_pack_content_diff(
+        &pack_dir_file_fingerprints(new_dir)?,
+        &pack_dir_file_fingerprints(old_dir)?,
+    );
+    let generated_at = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let markdown = render_changelog(
+        &pack_name,
+        old_manifest.as_ref(),
+        new_manifest.as_ref(),
+        manifest_changes,
+        &diff,
+        &generated_at,
+    );
+
+    if let Some(parent) = output_path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("创建输出目录失败 {}: {}", parent.display(), e))?;
+    }
+    fs::write(output_path, markdown)
+        .map_err(|e| format!("写入更新日志失败 {}: {}", output_path.display(), e))
+}
+
+/// 对比 `old_dir` 与 `new_dir` 两个包目录，把 Markdown 更新日志写入 `output_path`
+pub async fn generate_pack_changelog(
+    old_dir: String,
+    new_dir: String,
+    output_path: String,
+) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        generate_pack_changelog_blocking(
+            Path::new(&old_dir),
+            Path::new(&new_dir),
+            Path::new(&output_path),
+        )
+    })
+    .await
+    .map_err(|e| format!("生成更新日志任务失败: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_pack(dir: &Path, version: &str, files: &[(&str, &str)]) {
+        fs::create_dir_all(dir).expect("create pack dir");
+        fs::write(
+            dir.join("manifest.json"),
+            format!(
+                r#"{{"format_version":2,"header":{{"name":"§aDemo Pack","uuid":"00000000-0000-0000-0000-000000000001","version":[{version}]}},"modules":[]}}"#
+            ),
+        )
+        .expect("write manifest");
+        for (path, content) in files {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().expect("file parent")).expect("create file dir");
+            fs::write(path, content).expect("write pack file");
+        }
+    }
+
+    #[test]
+    fn changelog_lists_file_and_manifest_changes() {
+        let root =
+            std::env::temp_dir().join(format!("bmcbl-pack-changelog-{}", uuid::Uuid::new_v4()));
+        let old_dir = root.join("old");
+        let new_dir = root.join("new");
+        write_pack(
+            &old_dir,
+            "1, 0, 0",
+            &[
+                ("textures/a.png", "a"),
+                ("textures/c.png", "c1"),
+                ("texts/en_US.lang", "x=1"),
+            ],
+        );
+        write_pack(
+            &new_dir,
+            "1, 1, 0",
+            &[
+                ("textures/a.png", "aa"),
+                ("textures/b.png", "b"),
+                ("textures/c.png", "c2"),
+            ],
+        );
+        let output = root.join("out/CHANGELOG.md");
+
+        generate_pack_changelog_blocking(&old_dir, &new_dir, &output).expect("generate changelog");
+        let markdown = fs::read_to_string(&output).expect("read changelog");
+        assert!(markdown.starts_with("# Demo Pack 更新日志"));
+        assert!(markdown.contains("- 旧版本：1.0.0\n- 新版本：1.1.0\n- 变化：升级"));
+        assert!(markdown.contains("- manifest 改动：1 处"));
+        assert!(markdown.contains("## 新增文件（1）\n\n- `textures/b.png`"));
+        assert!(markdown.contains("## 删除文件（1）\n\n- `texts/en_US.lang`"));
+        assert!(markdown.contains("## 修改文件（2）\n\n- `textures/a.png`\n- `textures/c.png`"));
+        assert!(markdown.contains(LAUNCHER_VERSION));
+
+        let empty = root.join("empty");
+        fs::create_dir_all(&empty).expect("create empty dir");
+        assert!(generate_pack_changelog_blocking(&empty, &empty, &output).is_err());
+
+        fs::remove_dir_all(&root).expect("remove test dir");
+    }
+}
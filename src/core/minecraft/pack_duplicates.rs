@@ -0,0 +1,664 @@
+//! 按内容查找重复安装的包：同一个包被不同网站重新打包后 UUID 和文件夹名都不同，
+//! 但内容完全一样。指纹由排序后的相对路径 + 大小，加上最大几个文件的完整哈希组成，
+//! `manifest.json` 与启动器写入的附加文件不参与计算。
+
+use crate::core::cache_registry::{CacheDescriptor, CacheUsage};
+use crate::core::minecraft::analysis_suppressions::{SuppressionFilter, TOOL_DUPLICATE_PACKS};
+use crate::core::minecraft::import::{
+    LEGACY_MANIFEST_BACKUP_NAME, PACK_INSTALL_METADATA_NAME, manifest_header_version,
+    read_manifest_from_dir, version_to_string,
+};
+use crate::core::minecraft::pack_names::clean_display_name;
+use crate::core::minecraft::pack_references::{
+    find_worlds_referencing_pack, replace_pack_references,
+};
+use crate::core::minecraft::paths::{GamePathOptions, GameTargetDir, game_target_dirs};
+use crate::core::minecraft::recent_deletions;
+use crate::tasks::task_manager::{
+    create_task_with_details, finish_task, is_cancelled, set_total, update_progress,
+};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tracing::{debug, info, warn};
+use walkdir::WalkDir;
+use xxhash_rust::xxh3::Xxh3;
+
+/// 除路径与大小外，再对最大的几个文件做完整哈希，避免大小碰巧相同的误判
+const LARGEST_FILES_HASHED: usize = 3;
+const HASH_BUFFER_SIZE: usize = 256 * 1024;
+/// 不属于包内容的文件：清单（重新打包后 UUID 不同）与启动器写入的附加文件
+const EXCLUDED_FILE_NAMES: [&str; 3] = [
+    "manifest.json",
+    PACK_INSTALL_METADATA_NAME,
+    LEGACY_MANIFEST_BACKUP_NAME,
+];
+const PACK_TARGETS: [GameTargetDir; 2] =
+    [GameTargetDir::ResourcePacks, GameTargetDir::BehaviorPacks];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PackFingerprint {
+    hash: u128,
+    total_bytes: u64,
+}
+
+struct CachedFingerprint {
+    /// 包内所有文件的相对路径、大小与修改时间的摘要
+    listing: u128,
+    /// 包内除清单外没有文件时为 `None`
+    fingerprint: Option<PackFingerprint>,
+}
+
+/// 按包目录路径缓存指纹；文件列表摘要变化时重新计算
+static FINGERPRINT_CACHE: Lazy<Mutex<HashMap<PathBuf, CachedFingerprint>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub(crate) static CACHE: CacheDescriptor = CacheDescriptor {
+    name: "pack_fingerprints",
+    label: "包指纹",
+    default_selected: true,
+    usage: fingerprint_cache_usage,
+    clear: clear_fingerprint_cache,
+    warm: None,
+};
+
+fn fingerprint_cache_usage() -> CacheUsage {
+    CacheUsage {
+        entries: FINGERPRINT_CACHE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .len() as u64,
+        bytes: 0,
+    }
+}
+
+fn clear_fingerprint_cache() -> Result<CacheUsage, String> {
+    let mut cache = FINGERPRINT_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let entries = cache.len() as u64;
+    cache.clear();
+    Ok(CacheUsage { entries, bytes: 0 })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DuplicatePackEntry {
+    pub path: String,
+    pub folder_name: String,
+    /// 所在的包目录名，例如 `resource_packs`
+    pub pack_dir: String,
+    pub name: String,
+    pub uuid: Option<String>,
+    pub version: Option<String>,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DuplicatePackGroup {
+    pub fingerprint: String,
+    pub packs: Vec<DuplicatePackEntry>,
+    /// 只保留最大的一份时可以释放的空间
+    pub reclaimable_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicatePackReport {
+    pub task_id: String,
+    pub scanned_packs: usize,
+    pub groups: Vec<DuplicatePackGroup>,
+    pub total_reclaimable_bytes: u64,
+    /// 被分析忽略列表隐藏的分组数
+    pub suppressed_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MergeDuplicatePacksResult {
+    pub removed_paths: Vec<String>,
+    /// 引用改为保留包 UUID 的世界数
+    pub rewritten_worlds: usize,
+}
+
+fn is_excluded(dir: &Path, path: &Path) -> bool {
+    path.parent() == Some(dir)
+        && path.file_name().is_some_and(|name| {
+            EXCLUDED_FILE_NAMES
+                .iter()
+                .any(|excluded| name.eq_ignore_ascii_case(excluded))
+        })
+}
+
+pub(crate) fn hash_file_into(
+    hasher: &mut Xxh3,
+    path: &Path,
+    buffer: &mut [u8],
+) -> Result<(), String> {
+    let mut file =
+        File::open(path).map_err(|e| format!("打开文件失败 {}: {}", path.display(), e))?;
+    loop {
+        let read = file
+            .read(buffer)
+            .map_err(|e| format!("读取文件失败 {}: {}", path.display(), e))?;
+        if read == 0 {
+            return Ok(());
+        }
+        hasher.update(&buffer[..read]);
+    }
+}
+
+struct PackFile {
+    key: String,
+    path: PathBuf,
+    size: u64,
+    modified: Option<SystemTime>,
+    excluded: bool,
+}
+
+fn list_pack_files(dir: &Path) -> Result<Vec<PackFile>, String> {
+    let mut files = Vec::new();
+    for entry in WalkDir::new(dir).follow_links(false) {
+        let entry = entry.map_err(|e| format!("遍历包目录失败: {}", e))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(dir) else {
+            continue;
+        };
+        let metadata = entry.metadata().ok();
+        files.push(PackFile {
+            key: relative.to_string_lossy().replace('\\', "/"),
+            size: metadata.as_ref().map_or(0, |metadata| metadata.len()),
+            modified: metadata.and_then(|metadata| metadata.modified().ok()),
+            excluded: is_excluded(dir, entry.path()),
+            path: entry.into_path(),
+        });
+    }
+    files.sort_by(|left, right| left.key.cmp(&right.key));
+    Ok(files)
+}
+
+/// 文件列表摘要：任何文件增删、大小或修改时间变化都会改变结果，嵌套目录中的修改也一样
+fn listing_digest(files: &[PackFile]) -> u128 {
+    let mut hasher = Xxh3::new();
+    for file in files {
+        hasher.update(file.key.as_bytes());
+        hasher.update(&[0]);
+        hasher.update(&file.size.to_le_bytes());
+        let modified = file
+            .modified
+            .and_then(|modified| modified.duration_since(SystemTime::UNIX_EPOCH).ok())
+            .map_or(0, |modified| modified.as_nanos());
+        hasher.update(&modified.to_le_bytes());
+    }
+    hasher.digest128()
+}
+
+fn fingerprint_from_files(files: &[PackFile]) -> Result<Option<PackFingerprint>, String> {
+    let total_bytes = files.iter().map(|file| file.size).sum();
+    let content: Vec<&PackFile> = files.iter().filter(|file| !file.excluded).collect();
+    if content.is_empty() {
+        return Ok(None);
+    }
+
+    let mut hasher = Xxh3::new();
+    for file in &content {
+        hasher.update(file.key.as_bytes());
+        hasher.update(&[0]);
+        hasher.update(&file.size.to_le_bytes());
+    }
+    let mut largest = content;
+    largest.sort_by(|left, right| {
+        right
+            .size
+            .cmp(&left.size)
+            .then_with(|| left.key.cmp(&right.key))
+    });
+    let mut buffer = vec![0u8; HASH_BUFFER_SIZE];
+    for file in largest.into_iter().take(LARGEST_FILES_HASHED) {
+        hasher.update(file.key.as_bytes());
+        hash_file_into(&mut hasher, &file.path, &mut buffer)?;
+    }
+    Ok(Some(PackFingerprint {
+        hash: hasher.digest128(),
+        total_bytes,
+    }))
+}
+
+/// 不经过缓存重新计算指纹
+fn compute_fingerprint(dir: &Path) -> Result<Option<PackFingerprint>, String> {
+    fingerprint_from_files(&list_pack_files(dir)?)
+}
+
+/// 文件列表摘要没变时复用缓存的指纹
+fn cached_fingerprint(dir: &Path) -> Result<Option<PackFingerprint>, String> {
+    let files = list_pack_files(dir)?;
+    let listing = listing_digest(&files);
+    if let Some(cached) = FINGERPRINT_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(dir)
+        .filter(|cached| cached.listing == listing)
+    {
+        return Ok(cached.fingerprint.clone());
+    }
+    let fingerprint = fingerprint_from_files(&files)?;
+    FINGERPRINT_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(
+            dir.to_path_buf(),
+            CachedFingerprint {
+                listing,
+                fingerprint: fingerprint.clone(),
+            },
+        );
+    Ok(fingerprint)
+}
+
+/// 删除前重新计算指纹（不使用缓存），确认与保留的包内容仍然一致
+fn ensure_same_content(keep: &PackFingerprint, path: &Path) -> Result<(), String> {
+    match compute_fingerprint(path)? {
+        Some(fingerprint) if fingerprint.hash == keep.hash => Ok(()),
+        _ => Err(format!(
+            "{} 与保留的包内容不一致，已取消合并",
+            path.display()
+        )),
+    }
+}
+
+fn pack_entry(dir: &Path, size_bytes: u64) -> DuplicatePackEntry {
+    let manifest = read_manifest_from_dir(dir);
+    let header = manifest
+        .as_ref()
+        .and_then(|manifest| manifest.header.as_ref());
+    let folder_name = dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    DuplicatePackEntry {
+        path: dir.to_string_lossy().into_owned(),
+        pack_dir: dir
+            .parent()
+            .and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        name: header
+            .and_then(|header| header.name.as_deref())
+            .map(clean_display_name)
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| folder_name.clone()),
+        uuid: header.and_then(|header| header.uuid.clone()),
+        version: manifest
+            .as_ref()
+            .and_then(manifest_header_version)
+            .map(version_to_string),
+        folder_name,
+        size_bytes,
+    }
+}
+
+/// 扫描 `pack_roots` 下的每个包并按指纹分组；`on_scanned(scanned, total)` 在每个包之后调用，
+/// 返回 false 时中止并返回 `None`
+fn find_duplicates_in(
+    pack_roots: &[PathBuf],
+    mut on_scanned: impl FnMut(usize, usize) -> bool,
+) -> Result<Option<(usize, Vec<DuplicatePackGroup>)>, String> {
+    let mut pack_dirs: Vec<PathBuf> = pack_roots
+        .iter()
+        .flat_map(|root| fs::read_dir(root).into_iter().flatten().flatten())
+        .map(|entry| entry.path())
+        .filter(|path| path.join("manifest.json").is_file())
+        .collect();
+    pack_dirs.sort();
+    pack_dirs.dedup();
+
+    let mut by_fingerprint: HashMap<(String, u128), Vec<DuplicatePackEntry>> = HashMap::new();
+    for (index, dir) in pack_dirs.iter().enumerate() {
+        match cached_fingerprint(dir) {
+            Ok(Some(fingerprint)) => {
+                let entry = pack_entry(dir, fingerprint.total_bytes);
+                by_fingerprint
+                    .entry((entry.pack_dir.clone(), fingerprint.hash))
+                    .or_default()
+                    .push(entry);
+            }
+            Ok(None) => {}
+            // 扫描期间被删除或无法读取的包直接跳过
+            Err(e) => warn!("计算包指纹失败，已跳过: {}", e),
+        }
+        if !on_scanned(index + 1, pack_dirs.len()) {
+            return Ok(None);
+        }
+    }
+
+    let mut groups: Vec<DuplicatePackGroup> = by_fingerprint
+        .into_iter()
+        .filter(|(_, packs)| packs.len() > 1)
+        .map(|((_, hash), packs)| {
+            let total: u64 = packs.iter().map(|pack| pack.size_bytes).sum();
+            let largest = packs.iter().map(|pack| pack.size_bytes).max().unwrap_or(0);
+            DuplicatePackGroup {
+                fingerprint: format!("{hash:032x}"),
+                packs,
+                reclaimable_bytes: total - largest,
+            }
+        })
+        .collect();
+    groups.sort_by(|left, right| {
+        right
+            .reclaimable_bytes
+            .cmp(&left.reclaimable_bytes)
+            .then_with(|| left.fingerprint.cmp(&right.fingerprint))
+    });
+    Ok(Some((pack_dirs.len(), groups)))
+}
+
+fn pack_roots(options: &GamePathOptions) -> Vec<PathBuf> {
+    PACK_TARGETS
+        .iter()
+        .flat_map(|target| game_target_dirs(options, *target))
+        .collect()
+}
+
+/// 查找内容相同但 UUID / 文件夹名不同的包，作为可取消的任务运行
+pub async fn find_duplicate_packs(options: GamePathOptions) -> Result<DuplicatePackReport, String> {
+    let task_id = create_task_with_details(None, "查找重复包", None, "fingerprint", None, false);
+    let blocking_task_id = task_id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let roots = pack_roots(&options);
+        find_duplicates_in(&roots, |scanned, total| {
+            if scanned == 1 {
+                set_total(&blocking_task_id, Some(total as u64));
+            }
+            update_progress(&blocking_task_id, 1, None, Some("fingerprint"));
+            !is_cancelled(&blocking_task_id)
+        })
+    })
+    .await
+    .map_err(|e| format!("查找重复包任务失败: {}", e))
+    .and_then(|result| result);
+
+    match result {
+        Ok(Some((scanned_packs, groups))) => {
+            let filter = SuppressionFilter::load(TOOL_DUPLICATE_PACKS);
+            let found = groups.len();
+            let groups: Vec<DuplicatePackGroup> = groups
+                .into_iter()
+                .filter(|group| {
+                    let uuids = group.packs.iter().filter_map(|pack| pack.uuid.as_deref());
+                    !filter.is_suppressed(uuids, &group.fingerprint)
+                })
+                .collect();
+            let suppressed_count = found - groups.len();
+            let total_reclaimable_bytes = groups.iter().map(|group| group.reclaimable_bytes).sum();
+            debug!(
+                scanned_packs,
+                groups = groups.len(),
+                total_reclaimable_bytes,
+                "重复包查找完成"
+            );
+            finish_task(
+                &task_id,
+                "completed",
+                Some(format!("发现 {} 组重复包", groups.len())),
+            );
+            Ok(DuplicatePackReport {
+                task_id,
+                scanned_packs,
+                groups,
+                total_reclaimable_bytes,
+                suppressed_count,
+            })
+        }
+        Ok(None) => {
+            finish_task(&task_id, "cancelled", None);
+            Err("已取消查找重复包".to_string())
+        }
+        Err(error) => {
+            finish_task(&task_id, "error", Some(error.clone()));
+            Err(error)
+        }
+    }
+}
+
+fn ensure_in_pack_roots(path: &Path, roots: &[PathBuf]) -> Result<(), String> {
+    if !path.join("manifest.json").is_file() {
+        return Err(format!("不是有效的包目录: {}", path.display()));
+    }
+    let parent = path
+        .parent()
+        .and_then(|parent| fs::canonicalize(parent).ok());
+    let inside = roots.iter().any(|root| {
+        fs::canonicalize(root)
+            .ok()
+            .is_some_and(|root| parent.as_ref() == Some(&root))
+    });
+    if inside {
+        Ok(())
+    } else {
+        Err(format!("路径不在当前版本的包目录中: {}", path.display()))
+    }
+}
+
+fn merge_blocking(
+    options: &GamePathOptions,
+    keep_path: &Path,
+    remove_paths: &[PathBuf],
+    rewrite_references: bool,
+) -> Result<MergeDuplicatePacksResult, String> {
+    let roots = pack_roots(options);
+    ensure_in_pack_roots(keep_path, &roots)?;
+    let keep_fingerprint = compute_fingerprint(keep_path)?
+        .ok_or_else(|| format!("保留的包没有可比较的内容: {}", keep_path.display()))?;
+    let keep = pack_entry(keep_path, 0);
+    let worlds_dirs = game_target_dirs(options, GameTargetDir::MinecraftWorlds);
+
+    // 先检查全部待删除的包，有无法处理的引用时一个都不删
+    let mut rewrites = Vec::new();
+    for path in remove_paths {
+        if path == keep_path {
+            return Err("保留的包不能同时被删除".to_string());
+        }
+        ensure_in_pack_roots(path, &roots)?;
+        ensure_same_content(&keep_fingerprint, path)?;
+        let extra = pack_entry(path, 0);
+        let Some(uuid) = extra.uuid.clone() else {
+            continue;
+        };
+        if keep
+            .uuid
+            .as_deref()
+            .is_some_and(|keep_uuid| keep_uuid.eq_ignore_ascii_case(&uuid))
+        {
+            continue;
+        }
+        let worlds = find_worlds_referencing_pack(&worlds_dirs, &uuid);
+        if worlds.is_empty() {
+            continue;
+        }
+        let names: Vec<&str> = worlds
+            .iter()
+            .map(|world| world.world_name.as_str())
+            .collect();
+        let versions_match = extra.version.is_some() && extra.version == keep.version;
+        if !rewrite_references {
+            return Err(format!(
+                "{} 仍被以下世界使用：{}",
+                extra.name,
+                names.join("、")
+            ));
+        }
+        if !versions_match || keep.uuid.is_none() {
+            return Err(format!(
+                "{} 与保留的包版本不同，不能改写以下世界的引用：{}",
+                extra.name,
+                names.join("、")
+            ));
+        }
+        rewrites.push((uuid, worlds));
+    }
+
+    let keep_uuid = keep.uuid.clone().unwrap_or_default();
+    let mut rewritten_worlds = 0;
+    for (uuid, worlds) in &rewrites {
+        for world in worlds {
+            replace_pack_references(world, uuid, &keep_uuid)?;
+            rewritten_worlds += 1;
+        }
+    }
+
+    let mut removed_paths = Vec::new();
+    for path in remove_paths {
+        let name = pack_entry(path, 0).name;
+        recent_deletions::delete_with_undo(path, &name)?;
+        removed_paths.push(path.to_string_lossy().into_owned());
+    }
+    FINGERPRINT_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .retain(|path, _| !remove_paths.contains(path));
+    info!(
+        keep = %keep_path.display(),
+        removed = removed_paths.len(),
+        rewritten_worlds,
+        "已合并重复包"
+    );
+    Ok(MergeDuplicatePacksResult {
+        removed_paths,
+        rewritten_worlds,
+    })
+}
+
+/// 保留 `keep_path`，把 `remove_paths` 移入回收站。被删除的包仍被世界引用时默认拒绝；
+/// `rewrite_references` 为 true 且版本一致时，改为让这些世界引用保留的包（先备份）
+pub async fn merge_duplicate_packs(
+    options: GamePathOptions,
+    keep_path: String,
+    remove_paths: Vec<String>,
+    rewrite_references: bool,
+) -> Result<MergeDuplicatePacksResult, String> {
+    if remove_paths.is_empty() {
+        return Err("未选择要删除的重复包".to_string());
+    }
+    tokio::task::spawn_blocking(move || {
+        let remove_paths: Vec<PathBuf> = remove_paths.into_iter().map(PathBuf::from).collect();
+        merge_blocking(
+            &options,
+            Path::new(&keep_path),
+            &remove_paths,
+            rewrite_references,
+        )
+    })
+    .await
+    .map_err(|e| format!("合并重复包任务失败: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_pack(root: &Path, folder: &str, uuid: &str, files: &[(&str, &str)]) {
+        let dir = root.join(folder);
+        fs::create_dir_all(&dir).expect("create pack dir");
+        fs::write(
+            dir.join("manifest.json"),
+            format!(
+                r#"{{"format_version":2,"header":{{"name":"Shader","uuid":"{uuid}","version":[1,0,0]}},"modules":[]}}"#
+            ),
+        )
+        .expect("write manifest");
+        for (path, content) in files {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().expect("file parent")).expect("create file dir");
+            fs::write(path, content).expect("write pack file");
+        }
+    }
+
+    #[test]
+    fn groups_repackaged_copies_with_different_uuids() {
+        let root =
+            std::env::temp_dir().join(format!("bmcbl-pack-duplicates-{}", uuid::Uuid::new_v4()));
+        let packs = root.join("resource_packs");
+        let content = [("textures/sky.png", "sky-pixels"), ("shaders/a.json", "{}")];
+        write_pack(
+            &packs,
+            "shader_a",
+            "00000000-0000-0000-0000-00000000000a",
+            &content,
+        );
+        write_pack(
+            &packs,
+            "shader_b",
+            "00000000-0000-0000-0000-00000000000b",
+            &content,
+        );
+        // 路径和大小都相同，只有内容不同
+        write_pack(
+            &packs,
+            "lookalike",
+            "00000000-0000-0000-0000-00000000000c",
+            &[("textures/sky.png", "sky-PIXELS"), ("shaders/a.json", "{}")],
+        );
+        write_pack(&packs, "empty", "00000000-0000-0000-0000-00000000000d", &[]);
+
+        let (scanned, groups) = find_duplicates_in(&[packs.clone()], |_, _| true)
+            .expect("scan")
+            .expect("not cancelled");
+        assert_eq!(scanned, 4);
+        assert_eq!(groups.len(), 1);
+        let folders: Vec<&str> = groups[0]
+            .packs
+            .iter()
+            .map(|pack| pack.folder_name.as_str())
+            .collect();
+        assert_eq!(folders, ["shader_a", "shader_b"]);
+        assert_eq!(groups[0].reclaimable_bytes, groups[0].packs[1].size_bytes);
+        assert_eq!(groups[0].packs[0].version.as_deref(), Some("1.0.0"));
+
+        assert!(
+            find_duplicates_in(&[packs], |scanned, _| scanned < 2)
+                .expect("scan")
+                .is_none()
+        );
+        fs::remove_dir_all(&root).expect("remove test dir");
+    }
+
+    #[test]
+    fn nested_changes_invalidate_cache_and_block_merge() {
+        let root =
+            std::env::temp_dir().join(format!("bmcbl-pack-duplicates-{}", uuid::Uuid::new_v4()));
+        let content = [("textures/sky.png", "sky-pixels")];
+        write_pack(
+            &root,
+            "keep",
+            "00000000-0000-0000-0000-00000000000a",
+            &content,
+        );
+        write_pack(
+            &root,
+            "copy",
+            "00000000-0000-0000-0000-00000000000b",
+            &content,
+        );
+        let keep = compute_fingerprint(&root.join("keep"))
+            .expect("fingerprint keep")
+            .expect("keep has content");
+        let copy = root.join("copy");
+        let before = cached_fingerprint(&copy).expect("fingerprint copy");
+        assert_eq!(
+            before.as_ref().map(|fingerprint| fingerprint.hash),
+            Some(keep.hash)
+        );
+        ensure_same_content(&keep, &copy).expect("same content");
+
+        // 同样大小的嵌套文件被改写，包目录本身的修改时间不变
+        fs::write(copy.join("textures/sky.png"), "sky-PIXELS").expect("rewrite nested file");
+        let after = cached_fingerprint(&copy).expect("fingerprint copy again");
+        assert_ne!(after.map(|fingerprint| fingerprint.hash), Some(keep.hash));
+        assert!(ensure_same_content(&keep, &copy).is_err());
+
+        fs::remove_dir_all(&root).expect("remove test dir");
+    }
+}
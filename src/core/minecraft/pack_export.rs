@@ -0,0 +1,986 @@
+//! 流式打包目录：逐个文件分块写入 zip，不把文件内容整体读进内存，
+//! 适合贴图动辄数百 MB 的大型资源包。也用于把世界引用的附加包导出为 .mcaddon。
+
+use crate::core::minecraft::import::{
+    PACK_INSTALL_METADATA_NAME, parse_world_pack_references_from_text, scan_archive,
+};
+use crate::core::minecraft::pack_index::{IndexedPack, InstalledPackIndex};
+use crate::tasks::task_manager::{
+    create_task_with_details, finish_task, is_cancelled, set_total, update_progress,
+};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Component, Path, PathBuf};
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+
+const CHUNK_SIZE: usize = 256 * 1024;
+const MAX_DEFLATE_LEVEL: u32 = 9;
+const WORLD_PACK_FILE_NAMES: [&str; 2] = ["world_behavior_packs.json", "world_resource_packs.json"];
+/// 系统自动生成、不应随包分享的文件
+const JUNK_FILE_NAMES: [&str; 3] = ["Thumbs.db", ".DS_Store", "desktop.ini"];
+/// 整体导出时打包的 `com.mojang` 子目录
+const EXPORT_ALL_PACK_DIRS: [&str; 3] = ["resource_packs", "behavior_packs", "skin_packs"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ExportAllProgress {
+    /// 已写完的包数量（从 1 开始）
+    pub current_pack: usize,
+    pub total_packs: usize,
+    pub bytes_written: u64,
+}
+
+/// `export-all-progress` 事件
+static EXPORT_ALL_EVENTS: Lazy<broadcast::Sender<ExportAllProgress>> = Lazy::new(|| {
+    let (tx, _rx) = broadcast::channel(16);
+    tx
+});
+
+pub fn subscribe_export_all_events() -> broadcast::Receiver<ExportAllProgress> {
+    EXPORT_ALL_EVENTS.subscribe()
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ExportStats {
+    pub files_written: usize,
+    /// 打包前的文件总大小
+    pub total_bytes: u64,
+    /// 生成的 zip 文件大小
+    pub compressed_bytes: u64,
+}
+
+/// 把 `source_dir` 打包为 `output_path`，`compression_level` 取 0~9
+pub async fn pack_directory_streaming(
+    source_dir: String,
+    output_path: String,
+    compression_level: u32,
+) -> Result<ExportStats, String> {
+    if compression_level > MAX_DEFLATE_LEVEL {
+        return Err(format!(
+            "压缩级别 {compression_level} 超出范围（0 ~ {MAX_DEFLATE_LEVEL}）"
+        ));
+    }
+    let source_dir = PathBuf::from(source_dir);
+    let output_path = PathBuf::from(output_path);
+    tokio::task::spawn_blocking(move || {
+        pack_directory_blocking(&source_dir, &output_path, compression_level)
+    })
+    .await
+    .map_err(|e| format!("打包任务失败: {}", e))?
+}
+
+fn pack_directory_blocking(
+    source_dir: &Path,
+    output_path: &Path,
+    compression_level: u32,
+) -> Result<ExportStats, String> {
+    let layout = ArchiveLayout {
+        directory_entries: true,
+        ..ArchiveLayout::default()
+    };
+    let stats = write_directory_archive(
+        source_dir,
+        output_path,
+        Some(compression_level),
+        layout,
+        |_| true,
+    )?
+    .ok_or_else(|| "打包已中止".to_string())?;
+    info!(
+        source = %source_dir.display(),
+        output = %output_path.display(),
+        files = stats.files_written,
+        total_bytes = stats.total_bytes,
+        compressed_bytes = stats.compressed_bytes,
+        "目录打包完成"
+    );
+    Ok(stats)
+}
+
+/// 目录打包时的条目布局
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ArchiveLayout<'a> {
+    /// 根目录下这些文件按给定顺序写在最前，其余条目保持按路径排序
+    pub(crate) leading: &'a [&'a str],
+    /// 跳过系统生成的垃圾文件和启动器写入的安装信息
+    pub(crate) skip_generated: bool,
+    /// 为目录单独写入条目，保留空目录
+    pub(crate) directory_entries: bool,
+}
+
+struct ArchiveEntry {
+    path: PathBuf,
+    relative: PathBuf,
+    is_dir: bool,
+}
+
+/// 相对路径逐段拼接为归档条目名，统一使用 `/`
+fn archive_entry_name(relative: &Path) -> String {
+    relative
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn leading_rank(relative: &Path, leading: &[&str]) -> usize {
+    let mut components = relative.components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(name)), None) => leading
+            .iter()
+            .position(|metadata| name.eq_ignore_ascii_case(metadata))
+            .unwrap_or(leading.len()),
+        _ => leading.len(),
+    }
+}
+
+fn archive_entries(
+    source_dir: &Path,
+    skipped_outputs: &[PathBuf],
+    layout: ArchiveLayout<'_>,
+) -> Result<Vec<ArchiveEntry>, String> {
+    let mut entries = Vec::new();
+    for entry in WalkDir::new(source_dir)
+        .follow_links(false)
+        .sort_by_file_name()
+    {
+        let entry = entry.map_err(|e| format!("遍历源目录失败: {}", e))?;
+        let relative = entry
+            .path()
+            .strip_prefix(source_dir)
+            .map_err(|e| e.to_string())?
+            .to_path_buf();
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+        let file_type = entry.file_type();
+        if file_type.is_dir() {
+            if layout.directory_entries {
+                entries.push(ArchiveEntry {
+                    path: entry.into_path(),
+                    relative,
+                    is_dir: true,
+                });
+            }
+            continue;
+        }
+        if !file_type.is_file()
+            || skipped_outputs.contains(&relative)
+            || (layout.skip_generated
+                && (is_junk_file(entry.path()) || entry.file_name() == PACK_INSTALL_METADATA_NAME))
+        {
+            continue;
+        }
+        entries.push(ArchiveEntry {
+            path: entry.into_path(),
+            relative,
+            is_dir: false,
+        });
+    }
+    if !layout.leading.is_empty() {
+        entries.sort_by_key(|entry| leading_rank(&entry.relative, layout.leading));
+    }
+    Ok(entries)
+}
+
+/// 把 `source_dir` 流式写入 `output_path`（Deflate，启用 zip64）；`compression_level` 为 `None` 时用默认级别。
+/// `on_file(bytes)` 每写完一个文件调用一次，返回 false 时中止并返回 `None`，出错或中止时已有的输出文件保持不变
+pub(crate) fn write_directory_archive(
+    source_dir: &Path,
+    output_path: &Path,
+    compression_level: Option<u32>,
+    layout: ArchiveLayout<'_>,
+    mut on_file: impl FnMut(u64) -> bool,
+) -> Result<Option<ExportStats>, String> {
+    if !source_dir.is_dir() {
+        return Err(format!("源目录不存在: {}", source_dir.display()));
+    }
+    let (output, file) = PartialOutput::create(output_path)?;
+    // 输出文件位于源目录内时不能把它自己打进去
+    let entries = archive_entries(source_dir, &output.paths_within(source_dir), layout)?;
+    let mut zip = zip::ZipWriter::new(BufWriter::with_capacity(CHUNK_SIZE, file));
+    let options = SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .compression_level(compression_level.map(i64::from))
+        .large_file(true);
+
+    let mut stats = ExportStats::default();
+    for entry in entries {
+        let name = archive_entry_name(&entry.relative);
+        if entry.is_dir {
+            zip.add_directory(name, options)
+                .map_err(|e| format!("写入目录条目失败 {}: {}", entry.path.display(), e))?;
+            continue;
+        }
+        let copied = copy_file_into_zip(&mut zip, &entry.path, name, options)?;
+        stats.files_written += 1;
+        stats.total_bytes += copied;
+        if !on_file(copied) {
+            return Ok(None);
+        }
+    }
+
+    let writer = zip
+        .finish()
+        .map_err(|e| format!("完成 zip 写入失败: {}", e))?;
+    let file = writer
+        .into_inner()
+        .map_err(|e| format!("写入输出文件失败: {}", e.error()))?;
+    stats.compressed_bytes = file
+        .metadata()
+        .map_err(|e| format!("读取输出文件大小失败: {}", e))?
+        .len();
+    output.keep()?;
+    Ok(Some(stats))
+}
+
+pub(crate) fn copy_file_into_zip<W: Write + io::Seek>(
+    zip: &mut zip::ZipWriter<W>,
+    path: &Path,
+    name: String,
+    options: SimpleFileOptions,
+) -> Result<u64, String> {
+    zip.start_file(name, options)
+        .map_err(|e| format!("写入文件条目失败 {}: {}", path.display(), e))?;
+    let source_file =
+        File::open(path).map_err(|e| format!("打开文件失败 {}: {}", path.display(), e))?;
+    let mut reader = BufReader::with_capacity(CHUNK_SIZE, source_file);
+    io::copy(&mut reader, zip).map_err(|e| format!("写入文件内容失败 {}: {}", path.display(), e))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExportedAddonPack {
+    pub name: String,
+    pub uuid: String,
+    pub version: Option<String>,
+    /// 所在的包目录名，例如 `resource_packs`
+    pub pack_dir: String,
+    /// 在 .mcaddon 中的顶层文件夹名
+    pub archive_folder: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MissingAddonPack {
+    pub uuid: String,
+    pub version: Option<String>,
+    pub pack_type: String,
+    /// 已安装但版本不一致的同 UUID 包
+    pub installed_versions: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WorldAddonExport {
+    pub task_id: String,
+    pub dest_file: String,
+    /// 已打包的包清单，供分享对话框展示
+    pub packs: Vec<ExportedAddonPack>,
+    pub missing: Vec<MissingAddonPack>,
+    pub stats: ExportStats,
+}
+
+#[derive(Debug, Default)]
+struct ResolvedWorldAddons {
+    packs: Vec<IndexedPack>,
+    missing: Vec<MissingAddonPack>,
+}
+
+/// 本次导出的输出：先写入目标旁的 `<文件名>.partial`，`keep` 时再替换目标文件；
+/// 没有调用 `keep` 就被丢弃（出错、取消）时只删除临时文件，已有的目标文件保持不变
+pub(crate) struct PartialOutput {
+    partial: PathBuf,
+    target: PathBuf,
+    keep: bool,
+}
+
+impl PartialOutput {
+    /// 创建临时输出文件（及其父目录）
+    pub(crate) fn create(path: &Path) -> Result<(Self, File), String> {
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            fs::create_dir_all(parent).map_err(|e| format!("创建输出目录失败: {}", e))?;
+        }
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| format!("无效的输出路径: {}", path.display()))?;
+        let partial = path.with_file_name(format!("{}.partial", file_name.to_string_lossy()));
+        let file = File::create(&partial)
+            .map_err(|e| format!("创建输出文件失败 {}: {}", partial.display(), e))?;
+        Ok((
+            Self {
+                partial,
+                target: path.to_path_buf(),
+                keep: false,
+            },
+            file,
+        ))
+    }
+
+    /// 正在写入的临时文件
+    pub(crate) fn path(&self) -> &Path {
+        &self.partial
+    }
+
+    /// 目标文件与临时文件位于 `source_dir` 内时，它们相对 `source_dir` 的路径。
+    /// 只解析一次真实路径，遍历时按相对路径跳过，不必逐个文件 canonicalize
+    pub(crate) fn paths_within(&self, source_dir: &Path) -> Vec<PathBuf> {
+        let Ok(source_dir) = fs::canonicalize(source_dir) else {
+            return Vec::new();
+        };
+        [&self.partial, &self.target]
+            .into_iter()
+            .filter_map(|path| {
+                // 目标文件可能还不存在，只解析父目录
+                let parent = path
+                    .parent()
+                    .filter(|parent| !parent.as_os_str().is_empty())
+                    .unwrap_or(Path::new("."));
+                let canonical = fs::canonicalize(parent).ok()?.join(path.file_name()?);
+                canonical
+                    .strip_prefix(&source_dir)
+                    .ok()
+                    .map(Path::to_path_buf)
+            })
+            .collect()
+    }
+
+    /// 用写完的临时文件替换目标文件
+    pub(crate) fn keep(mut self) -> Result<(), String> {
+        fs::rename(&self.partial, &self.target).map_err(|e| {
+            format!(
+                "替换输出文件失败 {} -> {}: {}",
+                self.partial.display(),
+                self.target.display(),
+                e
+            )
+        })?;
+        self.keep = true;
+        Ok(())
+    }
+}
+
+impl Drop for PartialOutput {
+    fn drop(&mut self) {
+        if self.keep {
+            return;
+        }
+        if let Err(e) = fs::remove_file(&self.partial)
+            && e.kind() != io::ErrorKind::NotFound
+        {
+            warn!("删除未完成的输出文件失败 {}: {}", self.partial.display(), e);
+        }
+    }
+}
+
+pub(crate) fn is_junk_file(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| {
+        JUNK_FILE_NAMES
+            .iter()
+            .any(|junk| name.eq_ignore_ascii_case(junk))
+    })
+}
+
+/// 世界自带的包、所在 `com.mojang` 的包，以及 GDK 下 `Users/Shared` 的包
+fn world_pack_indexes(world_path: &Path) -> Vec<InstalledPackIndex> {
+    let mut dirs = vec![world_path.to_path_buf()];
+    if let Some(mojang_dir) = world_path.parent().and_then(Path::parent) {
+        dirs.push(mojang_dir.to_path_buf());
+        if let Some(users_dir) = mojang_dir
+            .parent()
+            .and_then(Path::parent)
+            .and_then(Path::parent)
+        {
+            let shared = users_dir.join("Shared").join("games").join("com.mojang");
+            if shared != mojang_dir && shared.is_dir() {
+                dirs.push(shared);
+            }
+        }
+    }
+    dirs.iter()
+        .map(|dir| InstalledPackIndex::build(dir, |_, _| {}))
+        .collect()
+}
+
+/// 按 UUID + 版本把世界引用的包对应到已安装的包目录；版本缺省时取第一个同 UUID 的包
+fn resolve_world_addons(world_path: &Path, indexes: &[InstalledPackIndex]) -> ResolvedWorldAddons {
+    let mut resolved = ResolvedWorldAddons::default();
+    let mut seen_paths = HashSet::new();
+    for file_name in WORLD_PACK_FILE_NAMES {
+        let Ok(content) = fs::read_to_string(world_path.join(file_name)) else {
+            continue;
+        };
+        for reference in parse_world_pack_references_from_text(&content, file_name) {
+            let candidates: Vec<&IndexedPack> = indexes
+                .iter()
+                .flat_map(|index| index.packs_for_uuid(&reference.uuid))
+                .collect();
+            let matched = candidates.iter().find(|pack| match &reference.version {
+                Some(version) => pack.version.as_deref() == Some(version.as_str()),
+                None => true,
+            });
+            match matched {
+                Some(pack) => {
+                    if seen_paths.insert(pack.path.clone()) {
+                        resolved.packs.push((*pack).clone());
+                    }
+                }
+                None => resolved.missing.push(MissingAddonPack {
+                    uuid: reference.uuid,
+                    version: reference.version,
+                    pack_type: reference.pack_type,
+                    installed_versions: candidates
+                        .iter()
+                        .filter_map(|pack| pack.version.clone())
+                        .collect(),
+                }),
+            }
+        }
+    }
+    resolved
+}
+
+fn pack_source_files(pack_dir: &Path) -> Vec<(PathBuf, u64)> {
+    WalkDir::new(pack_dir)
+        .follow_links(false)
+        .sort_by_file_name()
+        .into_iter()
+        .flatten()
+        .filter(|entry| {
+            entry.file_type().is_file()
+                && !is_junk_file(entry.path())
+                && entry.file_name() != PACK_INSTALL_METADATA_NAME
+        })
+        .map(|entry| {
+            let size = entry.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+            (entry.into_path(), size)
+        })
+        .collect()
+}
+
+fn unique_archive_folder(pack: &IndexedPack, used: &mut HashSet<String>) -> String {
+    let base = Path::new(&pack.path)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| pack.uuid.clone());
+    let mut folder = base.clone();
+    let mut suffix = 2;
+    while !used.insert(folder.to_ascii_lowercase()) {
+        folder = format!("{base}_{suffix}");
+        suffix += 1;
+    }
+    folder
+}
+
+/// 写出 .mcaddon；`on_file(bytes)` 每写完一个文件调用一次，返回 false 时中止。
+/// 出错或中止时删除写了一半的文件；成功时由调用方校验后 `keep`
+fn write_addon_archive(
+    packs: &[IndexedPack],
+    dest_file: &Path,
+    mut on_file: impl FnMut(u64) -> bool,
+) -> Result<Option<(Vec<ExportedAddonPack>, ExportStats, PartialOutput)>, String> {
+    let (output, file) = PartialOutput::create(dest_file)?;
+    let mut zip = zip::ZipWriter::new(BufWriter::with_capacity(CHUNK_SIZE, file));
+    let options = SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .large_file(true);
+
+    let mut used_folders = HashSet::new();
+    let mut exported = Vec::with_capacity(packs.len());
+    let mut stats = ExportStats::default();
+    for pack in packs {
+        let pack_dir = Path::new(&pack.path);
+        let archive_folder = unique_archive_folder(pack, &mut used_folders);
+        for (path, _) in pack_source_files(pack_dir) {
+            let relative_name = path
+                .strip_prefix(pack_dir)
+                .map_err(|e| e.to_string())?
+                .to_string_lossy()
+                .replace('\\', "/");
+            let copied = copy_file_into_zip(
+                &mut zip,
+                &path,
+                format!("{archive_folder}/{relative_name}"),
+                options,
+            )?;
+            stats.files_written += 1;
+            stats.total_bytes += copied;
+            if !on_file(copied) {
+                return Ok(None);
+            }
+        }
+        exported.push(ExportedAddonPack {
+            name: pack.name.clone(),
+            uuid: pack.uuid.clone(),
+            version: pack.version.clone(),
+            pack_dir: pack.pack_dir.clone(),
+            archive_folder,
+        });
+    }
+
+    let writer = zip
+        .finish()
+        .map_err(|e| format!("完成 zip 写入失败: {}", e))?;
+    let file = writer
+        .into_inner()
+        .map_err(|e| format!("写入输出文件失败: {}", e.error()))?;
+    stats.compressed_bytes = file
+        .metadata()
+        .map_err(|e| format!("读取输出文件大小失败: {}", e))?
+        .len();
+    Ok(Some((exported, stats, output)))
+}
+
+/// 重新扫描生成的 .mcaddon，确认每个包都能被识别
+fn verify_addon_archive(dest_file: &Path, exported: &[ExportedAddonPack]) -> Result<(), String> {
+    let file = File::open(dest_file)
+        .map_err(|e| format!("打开导出文件失败 {}: {}", dest_file.display(), e))?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| format!("读取导出文件失败: {}", e))?;
+    let scan = scan_archive(&mut archive).map_err(|e| format!("校验导出文件失败: {}", e))?;
+    if scan.is_world || scan.has_nested_archive {
+        return Err("导出文件结构异常，无法作为 .mcaddon 导入".to_string());
+    }
+    for pack in exported {
+        let root = format!("{}/", pack.archive_folder);
+        let found = scan.packs.iter().any(|entry| {
+            entry.root == root
+                && entry
+                    .manifest
+                    .header
+                    .as_ref()
+                    .and_then(|header| header.uuid.as_deref())
+                    .is_some_and(|uuid| uuid.trim().eq_ignore_ascii_case(&pack.uuid))
+        });
+        if !found {
+            return Err(format!(
+                "导出文件中无法识别包 {}（{}）",
+                pack.name, pack.uuid
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// 把世界 `world_*_packs.json` 引用的已安装包打成一个 .mcaddon，每个包一个顶层文件夹；
+/// 找不到的包在结果的 `missing` 中列出
+pub async fn export_world_addons(
+    world_path: String,
+    dest_file: String,
+) -> Result<WorldAddonExport, String> {
+    let world_path = PathBuf::from(world_path);
+    let dest_file = PathBuf::from(dest_file);
+    let world_name = world_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let task_id = create_task_with_details(
+        None,
+        "导出世界附加包",
+        Some(world_name),
+        "resolving",
+        None,
+        false,
+    );
+    let blocking_task_id = task_id.clone();
+    let blocking_dest = dest_file.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        if !world_path.is_dir() {
+            return Err(format!("世界目录不存在: {}", world_path.display()));
+        }
+        let indexes = world_pack_indexes(&world_path);
+        let resolved = resolve_world_addons(&world_path, &indexes);
+        if resolved.packs.is_empty() {
+            return Err(if resolved.missing.is_empty() {
+                "该世界没有引用任何资源包或行为包".to_string()
+            } else {
+                format!(
+                    "该世界引用的 {} 个包均未安装，无法导出",
+                    resolved.missing.len()
+                )
+            });
+        }
+
+        let total_bytes: u64 = resolved
+            .packs
+            .iter()
+            .flat_map(|pack| pack_source_files(Path::new(&pack.path)))
+            .map(|(_, size)| size)
+            .sum();
+        set_total(&blocking_task_id, Some(total_bytes));
+        let written = write_addon_archive(&resolved.packs, &blocking_dest, |bytes| {
+            update_progress(&blocking_task_id, bytes, None, Some("packing"));
+            !is_cancelled(&blocking_task_id)
+        });
+        let Some((packs, stats, output)) = written? else {
+            return Ok(None);
+        };
+        update_progress(&blocking_task_id, 0, None, Some("verifying"));
+        verify_addon_archive(output.path(), &packs)?;
+        output.keep()?;
+        Ok(Some((packs, resolved.missing, stats)))
+    })
+    .await
+    .map_err(|e| format!("导出世界附加包任务失败: {}", e))
+    .and_then(|result| result);
+
+    match result {
+        Ok(Some((packs, missing, stats))) => {
+            info!(
+                output = %dest_file.display(),
+                packs = packs.len(),
+                missing = missing.len(),
+                total_bytes = stats.total_bytes,
+                "世界附加包导出完成"
+            );
+            finish_task(
+                &task_id,
+                "completed",
+                Some(format!("已导出 {} 个包", packs.len())),
+            );
+            Ok(WorldAddonExport {
+                task_id,
+                dest_file: dest_file.to_string_lossy().into_owned(),
+                packs,
+                missing,
+                stats,
+            })
+        }
+        Ok(None) => {
+            finish_task(&task_id, "cancelled", None);
+            Err("已取消导出世界附加包".to_string())
+        }
+        Err(error) => {
+            finish_task(&task_id, "error", Some(error.clone()));
+            Err(error)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExportAllReport {
+    pub total_packs: usize,
+    pub total_bytes: u64,
+    pub zip_path: String,
+}
+
+/// 把 `mojang_dir` 下的资源包、行为包和皮肤包整体打成一个 zip，条目路径为
+/// `<包目录名>/<文件夹名>/...`，用于迁移到新电脑；每写完一个包广播 `export-all-progress`
+pub async fn export_all_packs(
+    mojang_dir: String,
+    output_path: String,
+) -> Result<ExportAllReport, String> {
+    let mojang_dir = PathBuf::from(mojang_dir);
+    let output_path = PathBuf::from(output_path);
+    tokio::task::spawn_blocking(move || {
+        export_all_packs_blocking(&mojang_dir, &output_path, |progress| {
+            let _ = EXPORT_ALL_EVENTS.send(progress);
+        })
+    })
+    .await
+    .map_err(|e| format!("导出全部包任务失败: {}", e))?
+}
+
+fn export_all_packs_blocking(
+    mojang_dir: &Path,
+    output_path: &Path,
+    mut on_progress: impl FnMut(ExportAllProgress),
+) -> Result<ExportAllReport, String> {
+    if !mojang_dir.is_dir() {
+        return Err(format!("com.mojang 目录不存在: {}", mojang_dir.display()));
+    }
+    let mut pack_dirs = Vec::new();
+    for dir_name in EXPORT_ALL_PACK_DIRS {
+        let Ok(entries) = fs::read_dir(mojang_dir.join(dir_name)) else {
+            continue;
+        };
+        let mut folders: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect();
+        folders.sort();
+        pack_dirs.extend(folders.into_iter().map(|folder| (dir_name, folder)));
+    }
+    if pack_dirs.is_empty() {
+        return Err("没有可导出的包".to_string());
+    }
+
+    let (output, file) = PartialOutput::create(output_path)?;
+    let mut zip = zip::ZipWriter::new(BufWriter::with_capacity(CHUNK_SIZE, file));
+    let options = SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .large_file(true);
+
+    let total_packs = pack_dirs.len();
+    let mut total_bytes = 0u64;
+    for (index, (dir_name, pack_dir)) in pack_dirs.iter().enumerate() {
+        let folder_name = pack_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let skipped_outputs = output.paths_within(pack_dir);
+        for entry in WalkDir::new(pack_dir)
+            .follow_links(false)
+            .sort_by_file_name()
+        {
+            let entry = entry.map_err(|e| format!("遍历包目录失败: {}", e))?;
+            let path = entry.path();
+            let relative = path.strip_prefix(pack_dir).map_err(|e| e.to_string())?;
+            if !entry.file_type().is_file()
+                || skipped_outputs.iter().any(|skipped| skipped == relative)
+            {
+                continue;
+            }
+            let relative_name = relative.to_string_lossy().replace('\\', "/");
+            total_bytes += copy_file_into_zip(
+                &mut zip,
+                path,
+                format!("{dir_name}/{folder_name}/{relative_name}"),
+                options,
+            )?;
+        }
+        on_progress(ExportAllProgress {
+            current_pack: index + 1,
+            total_packs,
+            bytes_written: total_bytes,
+        });
+    }
+
+    zip.finish()
+        .map_err(|e| format!("完成 zip 写入失败: {}", e))?
+        .into_inner()
+        .map_err(|e| format!("写入输出文件失败: {}", e.error()))?;
+    output.keep()?;
+    info!(
+        mojang_dir = %mojang_dir.display(),
+        output = %output_path.display(),
+        total_packs,
+        total_bytes,
+        "全部包导出完成"
+    );
+    Ok(ExportAllReport {
+        total_packs,
+        total_bytes,
+        zip_path: output_path.to_string_lossy().into_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn streams_directory_and_skips_output_inside_source() {
+        let source_dir =
+            std::env::temp_dir().join(format!("bmcbl-pack-export-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(source_dir.join("textures").join("blocks")).expect("create dirs");
+        fs::write(source_dir.join("manifest.json"), b"{}").expect("write manifest");
+        let texture = vec![7u8; CHUNK_SIZE * 2 + 13];
+        fs::write(
+            source_dir.join("textures").join("blocks").join("stone.png"),
+            &texture,
+        )
+        .expect("write texture");
+        let output_path = source_dir.join("export.zip");
+
+        let stats = pack_directory_blocking(&source_dir, &output_path, 6).expect("pack directory");
+
+        assert_eq!(stats.files_written, 2);
+        assert_eq!(stats.total_bytes, texture.len() as u64 + 2);
+        assert_eq!(
+            stats.compressed_bytes,
+            fs::metadata(&output_path).expect("metadata").len()
+        );
+        let mut archive =
+            zip::ZipArchive::new(File::open(&output_path).expect("open zip")).expect("read zip");
+        assert!(archive.by_name("export.zip").is_err());
+        let mut stored = Vec::new();
+        archive
+            .by_name("textures/blocks/stone.png")
+            .expect("texture entry")
+            .read_to_end(&mut stored)
+            .expect("read texture");
+        assert_eq!(stored, texture);
+
+        // 源目录不存在时不会删除已有的输出文件
+        let missing = source_dir.join("missing");
+        assert!(pack_directory_blocking(&missing, &output_path, 6).is_err());
+        assert!(output_path.is_file());
+
+        // 覆盖导出中途取消时保留上一次的输出
+        let previous = fs::read(&output_path).expect("read previous output");
+        let cancelled = write_directory_archive(
+            &source_dir,
+            &output_path,
+            None,
+            ArchiveLayout::default(),
+            |_| false,
+        )
+        .expect("cancelled export");
+        assert!(cancelled.is_none());
+        assert_eq!(fs::read(&output_path).expect("read output"), previous);
+        assert!(!source_dir.join("export.zip.partial").exists());
+
+        // 再次导出时已有的输出和临时文件都不会被打包进去
+        pack_directory_blocking(&source_dir, &output_path, 6).expect("re-export");
+        let mut archive =
+            zip::ZipArchive::new(File::open(&output_path).expect("open zip")).expect("read zip");
+        assert!(archive.by_name("export.zip").is_err());
+        assert!(archive.by_name("export.zip.partial").is_err());
+        assert!(archive.by_name("manifest.json").is_ok());
+
+        fs::remove_dir_all(&source_dir).expect("remove test dir");
+    }
+
+    fn write_pack(dir: &Path, uuid: &str, version: &str) {
+        fs::create_dir_all(dir.join("texts")).expect("create pack dir");
+        fs::write(
+            dir.join("manifest.json"),
+            format!(
+                r#"{{"format_version":2,"header":{{"name":"Pack {uuid}","uuid":"{uuid}","version":[{version}]}},"modules":[{{"type":"resources","uuid":"{uuid}-m","version":[1,0,0]}}]}}"#
+            ),
+        )
+        .expect("write manifest");
+        fs::write(dir.join("texts").join("en_US.lang"), "pack.name=Pack").expect("write lang");
+        fs::write(dir.join("Thumbs.db"), [0u8; 8]).expect("write junk");
+        fs::write(dir.join(PACK_INSTALL_METADATA_NAME), "{}").expect("write install metadata");
+    }
+
+    #[tokio::test]
+    async fn exports_referenced_packs_and_reports_missing_ones() {
+        let root =
+            std::env::temp_dir().join(format!("bmcbl-world-addons-{}", uuid::Uuid::new_v4()));
+        let mojang_dir = root.join("com.mojang");
+        let world = mojang_dir.join("minecraftWorlds").join("castle=");
+        fs::create_dir_all(&world).expect("create world");
+        write_pack(
+            &mojang_dir.join("resource_packs").join("Castle"),
+            "aaaa",
+            "1,0,0",
+        );
+        write_pack(
+            &world.join("resource_packs").join("Castle"),
+            "bbbb",
+            "2,1,0",
+        );
+        write_pack(
+            &mojang_dir.join("resource_packs").join("Old"),
+            "cccc",
+            "1,0,0",
+        );
+        fs::write(
+            world.join("world_resource_packs.json"),
+            r#"[{"pack_id":"aaaa","version":[1,0,0]},{"pack_id":"bbbb","version":[2,1,0]},{"pack_id":"cccc","version":[3,0,0]},{"pack_id":"dddd","version":[1,0,0]}]"#,
+        )
+        .expect("write world packs");
+
+        let resolved = resolve_world_addons(&world, &world_pack_indexes(&world));
+        assert_eq!(resolved.packs.len(), 2);
+        assert_eq!(resolved.missing.len(), 2);
+        assert_eq!(resolved.missing[0].installed_versions, vec!["1.0.0"]);
+        assert!(resolved.missing[1].installed_versions.is_empty());
+
+        let dest = root.join("out").join("castle.mcaddon");
+        let (exported, stats, output) = write_addon_archive(&resolved.packs, &dest, |_| true)
+            .expect("write addon")
+            .expect("not cancelled");
+        output.keep().expect("keep addon");
+        assert_eq!(stats.files_written, 4);
+        let folders: Vec<&str> = exported
+            .iter()
+            .map(|pack| pack.archive_folder.as_str())
+            .collect();
+        assert_eq!(folders.len(), 2);
+        assert!(folders.contains(&"Castle") && folders.contains(&"Castle_2"));
+        verify_addon_archive(&dest, &exported).expect("verify addon");
+        let mut archive =
+            zip::ZipArchive::new(File::open(&dest).expect("open addon")).expect("read addon");
+        assert!((0..archive.len()).all(|index| {
+            !archive
+                .by_index(index)
+                .expect("entry")
+                .name()
+                .ends_with("Thumbs.db")
+        }));
+        assert!(
+            archive
+                .file_names()
+                .all(|name| !name.ends_with(PACK_INSTALL_METADATA_NAME))
+        );
+
+        // 世界目录不存在时不会删除已有的同名文件；中止时删除写了一半的文件
+        fs::write(&dest, b"existing").expect("write existing output");
+        assert!(
+            export_world_addons(
+                root.join("missing=").to_string_lossy().into_owned(),
+                dest.to_string_lossy().into_owned(),
+            )
+            .await
+            .is_err()
+        );
+        assert_eq!(fs::read(&dest).expect("read existing output"), b"existing");
+        assert!(
+            write_addon_archive(&resolved.packs, &dest, |_| false)
+                .expect("write addon")
+                .is_none()
+        );
+        assert!(!dest.exists());
+
+        fs::remove_dir_all(&root).expect("remove test dir");
+    }
+
+    #[test]
+    fn exports_all_pack_dirs_with_type_prefix() {
+        let mojang_dir =
+            std::env::temp_dir().join(format!("bmcbl-export-all-{}", uuid::Uuid::new_v4()));
+        write_pack(
+            &mojang_dir.join("resource_packs").join("RP"),
+            "aaaa",
+            "1,0,0",
+        );
+        write_pack(
+            &mojang_dir.join("behavior_packs").join("BP"),
+            "bbbb",
+            "1,0,0",
+        );
+        fs::create_dir_all(mojang_dir.join("minecraftWorlds").join("world")).expect("world");
+        let output_path = mojang_dir.join("backup.zip");
+
+        let mut events = Vec::new();
+        let report =
+            export_all_packs_blocking(&mojang_dir, &output_path, |progress| events.push(progress))
+                .expect("export all");
+
+        assert_eq!(report.total_packs, 2);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].current_pack, 2);
+        assert_eq!(events[1].bytes_written, report.total_bytes);
+        let archive =
+            zip::ZipArchive::new(File::open(&output_path).expect("open zip")).expect("read zip");
+        let names: Vec<&str> = archive.file_names().collect();
+        assert!(names.contains(&"resource_packs/RP/manifest.json"));
+        assert!(names.contains(&"behavior_packs/BP/texts/en_US.lang"));
+        assert!(
+            names
+                .iter()
+                .all(|name| !name.starts_with("minecraftWorlds"))
+        );
+
+        // 没有可导出的包时不会删除已有的同名文件
+        let empty_dir = mojang_dir.join("empty");
+        fs::create_dir_all(&empty_dir).expect("create empty dir");
+        assert!(export_all_packs_blocking(&empty_dir, &output_path, |_| {}).is_err());
+        assert!(output_path.is_file());
+
+        fs::remove_dir_all(&mojang_dir).expect("remove test dir");
+    }
+}
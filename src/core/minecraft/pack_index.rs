@@ -0,0 +1,293 @@
+//! 已安装包索引：按 UUID 汇总 `com.mojang` 下各包目录中的包，供依赖检查、重复包提示等按 UUID 查询。
+//! 包很多时构建要几秒，重建通过 `pack-index-progress` 事件汇报进度。
+
+use crate::core::cache_registry::{CacheDescriptor, CacheUsage};
+use crate::core::minecraft::import::{parse_manifest_str, strip_json_comments, version_to_string};
+use crate::core::minecraft::pack_names::clean_display_name;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
+use tokio::sync::broadcast;
+use tracing::{debug, warn};
+
+const PACK_DIR_NAMES: [&str; 6] = [
+    "behavior_packs",
+    "resource_packs",
+    "skin_packs",
+    "world_templates",
+    "development_behavior_packs",
+    "development_resource_packs",
+];
+/// 每扫描这么多个包广播一次进度
+const PROGRESS_EVERY: usize = 16;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IndexedPack {
+    pub uuid: String,
+    pub name: String,
+    pub version: Option<String>,
+    /// 所在的包目录名，例如 `resource_packs`
+    pub pack_dir: String,
+    pub path: String,
+}
+
+#[derive(Debug, Default)]
+pub struct InstalledPackIndex {
+    mojang_dir: PathBuf,
+    /// 键为小写 UUID
+    by_uuid: HashMap<String, Vec<IndexedPack>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PackIndexStats {
+    pub total_packs: usize,
+    pub unique_uuids: usize,
+    /// 同一 UUID 多装出来的份数
+    pub duplicates: usize,
+    pub scan_duration_ms: u64,
+    pub mojang_dir: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PackIndexProgress {
+    pub scanned: usize,
+    pub total: usize,
+}
+
+static PACK_INDEX: Lazy<Arc<RwLock<InstalledPackIndex>>> =
+    Lazy::new(|| Arc::new(RwLock::new(InstalledPackIndex::default())));
+
+pub(crate) static CACHE: CacheDescriptor = CacheDescriptor {
+    name: "pack_index",
+    label: "已安装包索引",
+    default_selected: true,
+    usage: pack_index_usage,
+    clear: clear_pack_index,
+    warm: Some(warm_pack_index),
+};
+
+/// `pack-index-progress` 事件
+static PACK_INDEX_EVENTS: Lazy<broadcast::Sender<PackIndexProgress>> = Lazy::new(|| {
+    let (tx, _rx) = broadcast::channel(16);
+    tx
+});
+
+pub fn subscribe_pack_index_events() -> broadcast::Receiver<PackIndexProgress> {
+    PACK_INDEX_EVENTS.subscribe()
+}
+
+/// 最近一次构建的索引
+pub fn installed_pack_index() -> Arc<RwLock<InstalledPackIndex>> {
+    PACK_INDEX.clone()
+}
+
+impl InstalledPackIndex {
+    /// 扫描所有包目录；`progress(scanned, total)` 在扫描过程中被调用
+    pub fn build(mojang_dir: &Path, mut progress: impl FnMut(usize, usize)) -> Self {
+        let candidates = PACK_DIR_NAMES
+            .iter()
+            .flat_map(|dir_name| {
+                fs::read_dir(mojang_dir.join(dir_name))
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| path.is_dir())
+                    .map(move |path| (*dir_name, path))
+            })
+            .collect::<Vec<_>>();
+
+        let total = candidates.len();
+        progress(0, total);
+        let mut by_uuid: HashMap<String, Vec<IndexedPack>> = HashMap::new();
+        for (scanned, (dir_name, pack_dir)) in candidates.into_iter().enumerate() {
+            if let Some(pack) = read_indexed_pack(dir_name, &pack_dir) {
+                by_uuid
+                    .entry(pack.uuid.to_ascii_lowercase())
+                    .or_default()
+                    .push(pack);
+            }
+            let scanned = scanned + 1;
+            if scanned % PROGRESS_EVERY == 0 || scanned == total {
+                progress(scanned, total);
+            }
+        }
+
+        Self {
+            mojang_dir: mojang_dir.to_path_buf(),
+            by_uuid,
+        }
+    }
+
+    pub fn mojang_dir(&self) -> &Path {
+        &self.mojang_dir
+    }
+
+    pub fn packs_for_uuid(&self, uuid: &str) -> &[IndexedPack] {
+        self.by_uuid
+            .get(&uuid.trim().to_ascii_lowercase())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    fn stats(&self, scan_duration_ms: u64) -> PackIndexStats {
+        let total_packs = self.by_uuid.values().map(Vec::len).sum();
+        let unique_uuids = self.by_uuid.len();
+        PackIndexStats {
+            total_packs,
+            unique_uuids,
+            duplicates: total_packs - unique_uuids,
+            scan_duration_ms,
+            mojang_dir: self.mojang_dir.to_string_lossy().into_owned(),
+        }
+    }
+}
+
+fn read_indexed_pack(dir_name: &str, pack_dir: &Path) -> Option<IndexedPack> {
+    let raw = fs::read_to_string(pack_dir.join("manifest.json")).ok()?;
+    let clean = strip_json_comments(raw.trim_start_matches('\u{feff}'));
+    let (manifest, _) = match parse_manifest_str(&clean) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            warn!("解析清单失败 {}: {}", pack_dir.display(), e);
+            return None;
+        }
+    };
+    let header = manifest.header?;
+    let uuid = header.uuid?.trim().to_string();
+    if uuid.is_empty() {
+        return None;
+    }
+    let folder_name = pack_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    Some(IndexedPack {
+        uuid,
+        name: header
+            .name
+            .map(|name| clean_display_name(&name))
+            .filter(|name| !name.is_empty())
+            .unwrap_or(folder_name),
+        version: header.version.as_ref().map(version_to_string),
+        pack_dir: dir_name.to_string(),
+        path: pack_dir.to_string_lossy().into_owned(),
+    })
+}
+
+fn pack_index_usage() -> CacheUsage {
+    let index = PACK_INDEX.read().unwrap_or_else(|e| e.into_inner());
+    CacheUsage {
+        entries: index.by_uuid.values().map(|packs| packs.len() as u64).sum(),
+        bytes: 0,
+    }
+}
+
+/// 清空索引内容，保留 `mojang_dir` 供预热时重新扫描
+fn clear_pack_index() -> Result<CacheUsage, String> {
+    let usage = pack_index_usage();
+    PACK_INDEX
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .by_uuid
+        .clear();
+    Ok(usage)
+}
+
+fn warm_pack_index() -> Result<u64, String> {
+    let mojang_dir = installed_pack_index()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .mojang_dir
+        .clone();
+    // 尚未建过索引时没有可重建的目录
+    if mojang_dir.as_os_str().is_empty() || !mojang_dir.is_dir() {
+        return Ok(0);
+    }
+    let index = InstalledPackIndex::build(&mojang_dir, |_, _| {});
+    *PACK_INDEX.write().unwrap_or_else(|e| e.into_inner()) = index;
+    Ok(pack_index_usage().entries)
+}
+
+/// 重建已安装包索引，扫描过程中广播 `pack-index-progress`
+pub async fn rebuild_pack_index(mojang_dir: String) -> Result<PackIndexStats, String> {
+    let mojang_dir = PathBuf::from(mojang_dir);
+    if !mojang_dir.is_dir() {
+        return Err(format!("目录不存在: {}", mojang_dir.display()));
+    }
+    let (index, scan_duration_ms) = tokio::task::spawn_blocking(move || {
+        let started = Instant::now();
+        let index = InstalledPackIndex::build(&mojang_dir, |scanned, total| {
+            let _ = PACK_INDEX_EVENTS.send(PackIndexProgress { scanned, total });
+        });
+        (index, started.elapsed().as_millis() as u64)
+    })
+    .await
+    .map_err(|e| format!("重建包索引任务失败: {}", e))?;
+
+    let stats = index.stats(scan_duration_ms);
+    debug!(
+        total = stats.total_packs,
+        duplicates = stats.duplicates,
+        elapsed_ms = scan_duration_ms,
+        "已重建包索引"
+    );
+    *PACK_INDEX.write().unwrap_or_else(|e| e.into_inner()) = index;
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_manifest(mojang_dir: &Path, dir_name: &str, folder: &str, uuid: &str, version: &str) {
+        let pack_dir = mojang_dir.join(dir_name).join(folder);
+        fs::create_dir_all(&pack_dir).expect("create pack dir");
+        fs::write(
+            pack_dir.join("manifest.json"),
+            format!(
+                r#"{{"format_version": 2, "header": {{"name": "§l{folder}", "uuid": "{uuid}", "version": {version}}}, "modules": []}}"#
+            ),
+        )
+        .expect("write manifest");
+    }
+
+    #[test]
+    fn build_groups_duplicates_and_reports_progress() {
+        let mojang_dir =
+            std::env::temp_dir().join(format!("bmcbl-pack-index-{}", uuid::Uuid::new_v4()));
+        write_manifest(&mojang_dir, "resource_packs", "a", "AAAA", "[1, 0, 0]");
+        write_manifest(
+            &mojang_dir,
+            "development_resource_packs",
+            "a2",
+            "aaaa",
+            "[1, 1, 0]",
+        );
+        write_manifest(&mojang_dir, "behavior_packs", "b", "bbbb", "[2, 0, 0]");
+        fs::create_dir_all(mojang_dir.join("behavior_packs").join("no_manifest"))
+            .expect("create empty pack dir");
+
+        let mut reports = Vec::new();
+        let index = InstalledPackIndex::build(&mojang_dir, |scanned, total| {
+            reports.push((scanned, total));
+        });
+
+        assert_eq!(reports.first(), Some(&(0, 4)));
+        assert_eq!(reports.last(), Some(&(4, 4)));
+        let copies = index.packs_for_uuid("aaaa");
+        assert_eq!(copies.len(), 2);
+        assert!(copies.iter().any(|pack| pack.name == "a2"));
+        let stats = index.stats(0);
+        assert_eq!(
+            (stats.total_packs, stats.unique_uuids, stats.duplicates),
+            (3, 2, 1)
+        );
+
+        fs::remove_dir_all(&mojang_dir).expect("remove test dir");
+    }
+}
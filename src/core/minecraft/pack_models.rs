@@ -0,0 +1,247 @@
+//! 资源包模型：列出 `models/entity/` 与 `models/blocks/` 下的几何体定义，
+//! 兼容 1.12+ 的 `minecraft:geometry` 数组与 1.8 的 `geometry.xxx` 顶层键两种格式。
+
+use crate::core::minecraft::import::strip_json_comments;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+use walkdir::WalkDir;
+
+const ENTITY_MODELS_DIR: &str = "models/entity";
+const BLOCK_MODELS_DIR: &str = "models/blocks";
+const GEOMETRY_KEY: &str = "minecraft:geometry";
+const LEGACY_GEOMETRY_PREFIX: &str = "geometry.";
+/// 面 UV 未指定 `material_instance` 时使用的默认材质槽
+const DEFAULT_MATERIAL_INSTANCE: &str = "*";
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ModelEntry {
+    pub identifier: String,
+    /// 相对包根目录、以 `/` 分隔的路径
+    pub file_path: String,
+    /// 模型引用的材质槽，`*` 表示默认贴图
+    pub texture_references: Vec<String>,
+    pub bone_count: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ModelCatalog {
+    pub entity_models: Vec<ModelEntry>,
+    pub block_models: Vec<ModelEntry>,
+}
+
+/// 列出资源包中的自定义实体与方块模型
+pub async fn list_resource_pack_models(pack_dir: String) -> Result<ModelCatalog, String> {
+    let pack_dir = PathBuf::from(pack_dir);
+    tokio::task::spawn_blocking(move || list_models_blocking(&pack_dir))
+        .await
+        .map_err(|e| format!("读取模型任务失败: {}", e))?
+}
+
+fn list_models_blocking(pack_dir: &Path) -> Result<ModelCatalog, String> {
+    if !pack_dir.is_dir() {
+        return Err(format!("资源包目录不存在: {}", pack_dir.display()));
+    }
+    Ok(ModelCatalog {
+        entity_models: collect_models(pack_dir, ENTITY_MODELS_DIR),
+        block_models: collect_models(pack_dir, BLOCK_MODELS_DIR),
+    })
+}
+
+fn relative_key(pack_dir: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(pack_dir).unwrap_or(path);
+    let parts: Vec<String> = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    parts.join("/")
+}
+
+fn collect_models(pack_dir: &Path, models_dir: &str) -> Vec<ModelEntry> {
+    let dir = pack_dir.join(models_dir);
+    if !dir.is_dir() {
+        return Vec::new();
+    }
+
+    let mut entries = Vec::new();
+    for entry in WalkDir::new(&dir)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+    {
+        let path = entry.path();
+        let is_json = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+        if !entry.file_type().is_file() || !is_json {
+            continue;
+        }
+
+        let content = match fs::read(path) {
+            Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+            Err(e) => {
+                warn!("读取模型文件失败 {}: {}", path.display(), e);
+                continue;
+            }
+        };
+        let value: Value = match serde_json::from_str(&strip_json_comments(
+            content.trim_start_matches('\u{feff}'),
+        )) {
+            Ok(value) => value,
+            Err(e) => {
+                warn!("解析模型文件失败 {}: {}", path.display(), e);
+                continue;
+            }
+        };
+
+        let file_path = relative_key(pack_dir, path);
+        entries.extend(
+            parse_geometries(&value)
+                .into_iter()
+                .map(|(identifier, geometry)| ModelEntry {
+                    identifier,
+                    file_path: file_path.clone(),
+                    texture_references: texture_references(geometry),
+                    bone_count: bones(geometry).len(),
+                }),
+        );
+    }
+    entries.sort_by(|left, right| {
+        left.file_path
+            .cmp(&right.file_path)
+            .then_with(|| left.identifier.cmp(&right.identifier))
+    });
+    entries
+}
+
+/// 返回文件中的 (标识符, 几何体) 列表
+fn parse_geometries(value: &Value) -> Vec<(String, &Map<String, Value>)> {
+    let Some(root) = value.as_object() else {
+        return Vec::new();
+    };
+    if let Some(geometries) = root.get(GEOMETRY_KEY).and_then(Value::as_array) {
+        return geometries
+            .iter()
+            .filter_map(Value::as_object)
+            .filter_map(|geometry| {
+                let identifier = geometry.get("description")?.get("identifier")?.as_str()?;
+                Some((identifier.to_string(), geometry))
+            })
+            .collect();
+    }
+    // 1.8 格式：`"geometry.name:parent": { ... }`，继承部分不属于标识符
+    root.iter()
+        .filter(|(key, _)| key.starts_with(LEGACY_GEOMETRY_PREFIX))
+        .filter_map(|(key, geometry)| {
+            let identifier = key.split(':').next().unwrap_or(key);
+            Some((identifier.to_string(), geometry.as_object()?))
+        })
+        .collect()
+}
+
+fn bones(geometry: &Map<String, Value>) -> &[Value] {
+    geometry
+        .get("bones")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+/// 收集立方体 UV 引用的材质槽：逐面 UV 可指定 `material_instance`，
+/// 盒式 UV 和未指定材质槽的面都落在默认贴图上
+fn texture_references(geometry: &Map<String, Value>) -> Vec<String> {
+    let mut references = BTreeSet::new();
+    let cubes = bones(geometry)
+        .iter()
+        .filter_map(|bone| bone.get("cubes")?.as_array())
+        .flatten();
+    for cube in cubes {
+        match cube.get("uv") {
+            Some(Value::Object(faces)) => {
+                for face in faces.values() {
+                    let material = face
+                        .get("material_instance")
+                        .and_then(Value::as_str)
+                        .unwrap_or(DEFAULT_MATERIAL_INSTANCE);
+                    references.insert(material.to_string());
+                }
+            }
+            Some(_) => {
+                references.insert(DEFAULT_MATERIAL_INSTANCE.to_string());
+            }
+            None => {}
+        }
+    }
+    references.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn models_report_identifiers_bones_and_materials() {
+        let pack_dir =
+            std::env::temp_dir().join(format!("bmcbl-pack-models-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(pack_dir.join(ENTITY_MODELS_DIR)).expect("create entity dir");
+        fs::create_dir_all(pack_dir.join(BLOCK_MODELS_DIR)).expect("create block dir");
+        fs::write(
+            pack_dir.join(ENTITY_MODELS_DIR).join("robot.geo.json"),
+            r#"// legacy model
+            {
+                "format_version": "1.8.0",
+                "geometry.robot:geometry.humanoid": {
+                    "texturewidth": 64,
+                    "textureheight": 64,
+                    "bones": [
+                        {"name": "body", "cubes": [{"origin": [0, 0, 0], "size": [8, 12, 4], "uv": [16, 16]}]},
+                        {"name": "head"}
+                    ]
+                }
+            }"#,
+        )
+        .expect("write entity model");
+        fs::write(
+            pack_dir.join(BLOCK_MODELS_DIR).join("lamp.geo.json"),
+            r#"{
+                "format_version": "1.12.0",
+                "minecraft:geometry": [{
+                    "description": {"identifier": "geometry.lamp", "texture_width": 16, "texture_height": 16},
+                    "bones": [{"name": "base", "cubes": [{"origin": [0, 0, 0], "size": [16, 4, 16], "uv": {
+                        "up": {"uv": [0, 0], "uv_size": [16, 16], "material_instance": "top"},
+                        "down": {"uv": [0, 0], "uv_size": [16, 16]}
+                    }}]}]
+                }]
+            }"#,
+        )
+        .expect("write block model");
+        fs::write(pack_dir.join(BLOCK_MODELS_DIR).join("broken.json"), "{")
+            .expect("write broken model");
+
+        let catalog = list_models_blocking(&pack_dir).expect("list models");
+
+        assert_eq!(
+            catalog.entity_models,
+            vec![ModelEntry {
+                identifier: "geometry.robot".to_string(),
+                file_path: "models/entity/robot.geo.json".to_string(),
+                texture_references: vec!["*".to_string()],
+                bone_count: 2,
+            }]
+        );
+        assert_eq!(
+            catalog.block_models,
+            vec![ModelEntry {
+                identifier: "geometry.lamp".to_string(),
+                file_path: "models/blocks/lamp.geo.json".to_string(),
+                texture_references: vec!["*".to_string(), "top".to_string()],
+                bone_count: 1,
+            }]
+        );
+
+        fs::remove_dir_all(&pack_dir).expect("remove test dir");
+    }
+}
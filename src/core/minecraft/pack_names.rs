@@ -0,0 +1,142 @@
+//! 包显示名称：去除 `§` 格式化代码，识别未翻译的语言键并尝试用包内全部 .lang 文件补全，
+//! 仍无法解析时退回由文件夹名或 UUID 生成的名称。
+
+use crate::core::minecraft::resource_packs::{pack_lang_files, parse_lang_file};
+use std::path::Path;
+
+const UUID_LABEL_LEN: usize = 8;
+
+/// 去除 Minecraft 格式化代码 (§x)
+pub(crate) fn strip_minecraft_formatting(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut chars = input.chars();
+    while let Some(c) = chars.next() {
+        if c == '§' {
+            // 跳过下一个字符
+            chars.next();
+        } else {
+            output.push(c);
+        }
+    }
+    output
+}
+
+/// 用于展示的名称：去除格式化代码并去掉首尾空白；清理后为空时保留原值
+pub(crate) fn clean_display_name(name: &str) -> String {
+    let cleaned = strip_minecraft_formatting(name).trim().to_string();
+    if cleaned.is_empty() {
+        name.to_string()
+    } else {
+        cleaned
+    }
+}
+
+/// 形如 `pack.name` 的未翻译语言键：仅含小写字母、数字、`_`、`.`，且至少有一个点
+fn looks_like_translation_key(value: &str) -> bool {
+    value.contains('.')
+        && value
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '.')
+}
+
+/// 名称为空、只剩格式化代码或仍是语言键时视为未解析
+fn usable_name(raw: &str) -> Option<String> {
+    let cleaned = strip_minecraft_formatting(raw).trim().to_string();
+    (!cleaned.is_empty() && !looks_like_translation_key(&cleaned)).then_some(cleaned)
+}
+
+/// 依次在包内每个 .lang 文件里查找语言键，不局限于首选语言链
+fn resolve_from_any_lang(pack_dir: &Path, key: &str) -> Option<String> {
+    let mut lang_files = pack_lang_files(pack_dir);
+    lang_files.sort_by(|left, right| left.1.to_lowercase().cmp(&right.1.to_lowercase()));
+    lang_files
+        .iter()
+        .filter_map(|(path, _)| parse_lang_file(path))
+        .find_map(|map| map.get(key).and_then(|value| usable_name(value)))
+}
+
+/// 由文件夹名生成的兜底名称；导入时按 UUID 命名的文件夹改用 UUID 前缀
+fn fallback_label(folder_name: &str, uuid: Option<&str>) -> String {
+    let cleaned = strip_minecraft_formatting(folder_name);
+    let cleaned = cleaned.trim();
+    if let Some(uuid) = uuid.map(str::trim).filter(|uuid| !uuid.is_empty()) {
+        let stem = cleaned.strip_prefix("pack_").unwrap_or(cleaned);
+        if cleaned.is_empty() || stem.eq_ignore_ascii_case(uuid) {
+            let short: String = uuid.chars().take(UUID_LABEL_LEN).collect();
+            return format!("Pack {short}");
+        }
+    }
+    let label = cleaned.replace('_', " ").trim().to_string();
+    if label.is_empty() {
+        folder_name.to_string()
+    } else {
+        label
+    }
+}
+
+/// 解析包的显示名称，返回 (名称, 是否未能解析)
+pub(crate) fn resolve_pack_display_name(
+    pack_dir: &Path,
+    raw_name: Option<&str>,
+    uuid: Option<&str>,
+    folder_name: &str,
+) -> (String, bool) {
+    let raw_name = raw_name.map(str::trim).unwrap_or_default();
+    if let Some(name) = usable_name(raw_name) {
+        return (name, false);
+    }
+    let key = strip_minecraft_formatting(raw_name).trim().to_string();
+    if !key.is_empty()
+        && let Some(name) = resolve_from_any_lang(pack_dir, &key)
+    {
+        return (name, false);
+    }
+    (fallback_label(folder_name, uuid), true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn formatting_heavy_and_key_names_are_resolved_or_flagged() {
+        assert_eq!(strip_minecraft_formatting("§l§6My§r§k Pack§"), "My Pack");
+        assert_eq!(clean_display_name("  §l§6MyPack§r "), "MyPack");
+        assert_eq!(clean_display_name("§l§r"), "§l§r");
+
+        let pack_dir =
+            std::env::temp_dir().join(format!("bmcbl-pack-names-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(pack_dir.join("texts")).expect("create texts dir");
+        fs::write(
+            pack_dir.join("texts").join("ja_JP.lang"),
+            "pack.name=§e光影パック\n",
+        )
+        .expect("write lang");
+
+        assert_eq!(
+            resolve_pack_display_name(&pack_dir, Some("§b§lShaders"), None, "shaders"),
+            ("Shaders".to_string(), false)
+        );
+        assert_eq!(
+            resolve_pack_display_name(&pack_dir, Some("pack.name"), None, "shaders"),
+            ("光影パック".to_string(), false)
+        );
+        assert_eq!(
+            resolve_pack_display_name(&pack_dir, Some("§l§6"), None, "my_cool_pack"),
+            ("my cool pack".to_string(), true)
+        );
+        let uuid = "3f2a9c1e-0000-4000-8000-000000000000";
+        assert_eq!(
+            resolve_pack_display_name(
+                &pack_dir,
+                Some("pack.title"),
+                Some(uuid),
+                &format!("pack_{uuid}")
+            ),
+            ("Pack 3f2a9c1e".to_string(), true)
+        );
+
+        fs::remove_dir_all(&pack_dir).expect("remove test dir");
+    }
+}
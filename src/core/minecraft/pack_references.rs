@@ -0,0 +1,269 @@
+//! 包的反向引用：删除包之前找出哪些世界的 `world_*_packs.json` 仍引用它的 UUID。
+//! 只读这两个 json，不解析 level.dat，世界很多时也能很快扫完。
+
+use crate::core::minecraft::import::parse_world_pack_references_from_text;
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+const WORLD_PACK_FILE_NAMES: [&str; 2] = ["world_behavior_packs.json", "world_resource_packs.json"];
+const MAX_BACKUP_SUFFIX: u32 = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PackReferencingWorld {
+    pub world_folder: String,
+    /// `levelname.txt` 中的名称，读不到时为文件夹名
+    pub world_name: String,
+    pub world_path: String,
+    /// 引用了该包的 `world_*_packs.json` 文件名
+    pub pack_files: Vec<String>,
+}
+
+fn world_display_name(world: &Path, folder: &str) -> String {
+    fs::read_to_string(world.join("levelname.txt"))
+        .ok()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| folder.to_string())
+}
+
+/// 在 `worlds_dirs`（各个 `minecraftWorlds` 目录）下查找引用 `pack_uuid` 的世界
+pub fn find_worlds_referencing_pack(
+    worlds_dirs: &[PathBuf],
+    pack_uuid: &str,
+) -> Vec<PackReferencingWorld> {
+    let mut worlds = Vec::new();
+    for worlds_dir in worlds_dirs {
+        let Ok(entries) = fs::read_dir(worlds_dir) else {
+            continue;
+        };
+        for world in entries.flatten().map(|entry| entry.path()) {
+            if !world.is_dir() {
+                continue;
+            }
+            let pack_files: Vec<String> = WORLD_PACK_FILE_NAMES
+                .iter()
+                .filter(|file_name| {
+                    fs::read_to_string(world.join(file_name)).is_ok_and(|content| {
+                        parse_world_pack_references_from_text(&content, file_name)
+                            .iter()
+                            .any(|reference| reference.uuid.eq_ignore_ascii_case(pack_uuid))
+                    })
+                })
+                .map(|file_name| file_name.to_string())
+                .collect();
+            if pack_files.is_empty() {
+                continue;
+            }
+            let world_folder = world
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            worlds.push(PackReferencingWorld {
+                world_name: world_display_name(&world, &world_folder),
+                world_path: world.to_string_lossy().into_owned(),
+                world_folder,
+                pack_files,
+            });
+        }
+    }
+    worlds.sort_by(|left, right| left.world_name.cmp(&right.world_name));
+    worlds
+}
+
+fn references_pack(item: &Value, pack_uuid: &str) -> bool {
+    item.get("pack_id")
+        .or_else(|| item.get("uuid"))
+        .and_then(Value::as_str)
+        .is_some_and(|id| id.eq_ignore_ascii_case(pack_uuid))
+}
+
+/// 同一秒内多次改写时时间戳相同，加序号避免覆盖上一份备份
+fn unique_backup_path(world_path: &Path, file_name: &str, stamp: &str) -> Result<PathBuf, String> {
+    (0..MAX_BACKUP_SUFFIX)
+        .map(|index| match index {
+            0 => world_path.join(format!("{file_name}.{stamp}.bak")),
+            index => world_path.join(format!("{file_name}.{stamp}-{index}.bak")),
+        })
+        .find(|path| !path.exists())
+        .ok_or_else(|| format!("世界包列表备份过多: {}", world_path.display()))
+}
+
+/// 逐个改写世界引用了该包的列表文件。`edit` 返回改动的条目数，有改动时先把原文件
+/// 另存为 `<文件名>.<时间戳>[-序号].bak` 再写回；返回总改动数
+fn rewrite_pack_lists(
+    world: &PackReferencingWorld,
+    mut edit: impl FnMut(&mut Vec<Value>) -> usize,
+) -> Result<usize, String> {
+    let world_path = Path::new(&world.world_path);
+    let stamp = chrono::Local::now().format("%Y%m%d%H%M%S").to_string();
+    let mut changed = 0;
+    for file_name in &world.pack_files {
+        let path = world_path.join(file_name);
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("读取世界包列表失败 {}: {}", path.display(), e))?;
+        let Ok(Value::Array(mut items)) =
+            serde_json::from_str::<Value>(content.trim_start_matches('\u{feff}'))
+        else {
+            warn!("世界包列表格式无法识别，跳过改写: {}", path.display());
+            continue;
+        };
+        let changed_here = edit(&mut items);
+        if changed_here == 0 {
+            continue;
+        }
+
+        let backup = unique_backup_path(world_path, file_name, &stamp)?;
+        fs::copy(&path, &backup)
+            .map_err(|e| format!("备份世界包列表失败 {}: {}", backup.display(), e))?;
+        let updated =
+            serde_json::to_string_pretty(&Value::Array(items)).map_err(|e| e.to_string())?;
+        let tmp = world_path.join(format!("{file_name}.tmp"));
+        fs::write(&tmp, updated)
+            .and_then(|_| fs::rename(&tmp, &path))
+            .map_err(|e| format!("写入世界包列表失败 {}: {}", path.display(), e))?;
+        changed += changed_here;
+    }
+    Ok(changed)
+}
+
+/// 从世界的包列表中移除 `pack_uuid` 的条目（先备份），返回移除的条目数
+pub fn remove_pack_references(
+    world: &PackReferencingWorld,
+    pack_uuid: &str,
+) -> Result<usize, String> {
+    rewrite_pack_lists(world, |items| {
+        let before = items.len();
+        items.retain(|item| !references_pack(item, pack_uuid));
+        before - items.len()
+    })
+}
+
+/// 把世界对 `old_uuid` 的引用改为 `new_uuid`（先备份）；列表里已有 `new_uuid` 时直接去掉旧条目。
+/// 只改 UUID，调用方需确认两个包版本一致
+pub fn replace_pack_references(
+    world: &PackReferencingWorld,
+    old_uuid: &str,
+    new_uuid: &str,
+) -> Result<usize, String> {
+    rewrite_pack_lists(world, |items| {
+        let already_listed = items.iter().any(|item| references_pack(item, new_uuid));
+        let before = items.len();
+        if already_listed {
+            items.retain(|item| !references_pack(item, old_uuid));
+            return before - items.len();
+        }
+        let mut replaced = 0;
+        for item in items.iter_mut() {
+            if !references_pack(item, old_uuid) {
+                continue;
+            }
+            if let Some(object) = item.as_object_mut() {
+                let key = if object.contains_key("pack_id") {
+                    "pack_id"
+                } else {
+                    "uuid"
+                };
+                object.insert(key.to_string(), Value::String(new_uuid.to_string()));
+                replaced += 1;
+            }
+        }
+        replaced
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PACK_UUID: &str = "1b2c3d4e-0000-4000-8000-00000000abcd";
+
+    fn write_world(worlds_dir: &Path, folder: &str, name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let world = worlds_dir.join(folder);
+        fs::create_dir_all(&world).expect("create world dir");
+        fs::write(world.join("levelname.txt"), name).expect("write levelname");
+        for (file_name, content) in files {
+            fs::write(world.join(file_name), content).expect("write world pack list");
+        }
+        world
+    }
+
+    #[test]
+    fn finds_referencing_worlds_and_cleans_with_backup() {
+        let root =
+            std::env::temp_dir().join(format!("bmcbl-pack-references-{}", uuid::Uuid::new_v4()));
+        let worlds_dir = root.join("minecraftWorlds");
+        let referencing = format!(
+            r#"[{{"pack_id":"{}","version":[1,0,0]}},{{"pack_id":"other","version":[1,0,0]}}]"#,
+            PACK_UUID.to_uppercase()
+        );
+        let castle = write_world(
+            &worlds_dir,
+            "castle=",
+            "Castle",
+            &[
+                ("world_behavior_packs.json", &referencing),
+                ("world_resource_packs.json", r#"[{"pack_id":"other"}]"#),
+            ],
+        );
+        write_world(
+            &worlds_dir,
+            "farm=",
+            "Farm",
+            &[("world_resource_packs.json", r#"[{"pack_id":"other"}]"#)],
+        );
+        write_world(
+            &worlds_dir,
+            "broken=",
+            "Broken",
+            &[("world_resource_packs.json", "{")],
+        );
+
+        let worlds =
+            find_worlds_referencing_pack(&[worlds_dir.clone(), root.join("missing")], PACK_UUID);
+        assert_eq!(worlds.len(), 1);
+        assert_eq!(worlds[0].world_name, "Castle");
+        assert_eq!(worlds[0].pack_files, vec!["world_behavior_packs.json"]);
+
+        assert_eq!(remove_pack_references(&worlds[0], PACK_UUID), Ok(1));
+        let cleaned: Value = serde_json::from_str(
+            &fs::read_to_string(castle.join("world_behavior_packs.json")).expect("read cleaned"),
+        )
+        .expect("parse cleaned");
+        assert_eq!(cleaned.as_array().map(Vec::len), Some(1));
+        let backups = |world: &Path| -> Vec<String> {
+            let mut names: Vec<String> = fs::read_dir(world)
+                .expect("read world dir")
+                .flatten()
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .filter(|name| name.ends_with(".bak"))
+                .collect();
+            names.sort();
+            names
+        };
+        let first_backups = backups(&castle);
+        assert_eq!(first_backups.len(), 1);
+        assert_eq!(
+            fs::read_to_string(castle.join(&first_backups[0])).expect("read backup"),
+            referencing
+        );
+        assert!(find_worlds_referencing_pack(&[worlds_dir], PACK_UUID).is_empty());
+
+        // 紧接着再改写一次，时间戳多半相同，上一份备份不能被覆盖
+        let second = format!(r#"[{{"pack_id":"{PACK_UUID}"}}]"#);
+        fs::write(castle.join("world_behavior_packs.json"), &second).expect("rewrite pack list");
+        assert_eq!(remove_pack_references(&worlds[0], PACK_UUID), Ok(1));
+        let all_backups = backups(&castle);
+        assert_eq!(all_backups.len(), 2);
+        let contents: Vec<String> = all_backups
+            .iter()
+            .map(|name| fs::read_to_string(castle.join(name)).expect("read backup"))
+            .collect();
+        assert!(contents.contains(&referencing));
+        assert!(contents.contains(&second));
+
+        fs::remove_dir_all(&root).expect("remove test dir");
+    }
+}
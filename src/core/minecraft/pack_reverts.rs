@@ -0,0 +1,446 @@
+//! 临时启用的世界包：为调试临时写入 `world_*_packs.json`，游戏退出后自动还原。
+//! 还原前会比对文件是否仍是启用时写入的内容，被游戏改动过的文件只报告冲突，不覆盖。
+
+use crate::core::minecraft::game_session::{is_process_alive, watch_game_session};
+use crate::tasks::manage_service::PackKind;
+use crate::utils::file_ops;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use sysinfo::System;
+use tracing::{debug, info, warn};
+
+const PENDING_REVERTS_FILE_NAME: &str = "pending_reverts.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RevertPolicy {
+    /// 下次启动的游戏退出后还原；启动器崩溃时在下次启动器启动时还原
+    NextGameExit,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingRevert {
+    pub world_path: String,
+    pub pack_file: String,
+    /// 启用前的文件内容，文件原本不存在时为 `None`
+    pub original: Option<String>,
+    /// 启用后写入的文件内容，还原前用来判断游戏是否改动过文件
+    pub applied: String,
+    pub added_pack_ids: Vec<String>,
+    pub policy: RevertPolicy,
+    pub created_at: i64,
+    /// 临时启用后拉起的游戏进程
+    #[serde(default)]
+    pub game_pid: Option<u32>,
+    /// 上次还原时发现的冲突，用户可以取消还原以保留当前内容
+    #[serde(default)]
+    pub conflict: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorldPackEntry {
+    pub pack_id: String,
+    pub version: [u32; 3],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevertReport {
+    pub world_path: String,
+    pub pack_file: String,
+    /// `None` 表示已还原
+    pub conflict: Option<String>,
+}
+
+static PENDING_REVERTS: Lazy<Mutex<Vec<PendingRevert>>> = Lazy::new(|| Mutex::new(load_pending()));
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+fn pending_path() -> PathBuf {
+    file_ops::bmcbl_dir().join(PENDING_REVERTS_FILE_NAME)
+}
+
+fn load_pending() -> Vec<PendingRevert> {
+    let Ok(raw) = fs::read_to_string(pending_path()) else {
+        return Vec::new();
+    };
+    match serde_json::from_str(&raw) {
+        Ok(pending) => pending,
+        Err(e) => {
+            warn!("解析待还原记录失败，已忽略: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+fn save_pending(pending: &[PendingRevert]) -> Result<(), String> {
+    let path = pending_path();
+    let raw = serde_json::to_string_pretty(pending).map_err(|e| e.to_string())?;
+    fs::write(&path, raw).map_err(|e| format!("保存待还原记录失败 {}: {}", path.display(), e))
+}
+
+fn world_pack_file_name(kind: PackKind) -> &'static str {
+    match kind {
+        PackKind::Behavior => "world_behavior_packs.json",
+        PackKind::Resource => "world_resource_packs.json",
+    }
+}
+
+fn world_key(world_path: &Path) -> String {
+    std::path::absolute(world_path)
+        .unwrap_or_else(|_| world_path.to_path_buf())
+        .to_string_lossy()
+        .to_string()
+}
+
+fn parse_pack_list(content: &str) -> Result<Vec<Value>, String> {
+    match serde_json::from_str::<Value>(content.trim_start_matches('\u{feff}')) {
+        Ok(Value::Array(items)) => Ok(items),
+        Ok(_) => Err("世界包列表不是数组".to_string()),
+        Err(e) => Err(format!("解析世界包列表失败: {}", e)),
+    }
+}
+
+fn entry_pack_id(entry: &Value) -> Option<&str> {
+    entry.get("pack_id").and_then(Value::as_str)
+}
+
+/// 在现有列表末尾追加尚未启用的包，返回写入内容和实际新增的包 ID
+fn apply_pack_additions(
+    original: Option<&str>,
+    packs: &[WorldPackEntry],
+) -> Result<(String, Vec<String>), String> {
+    let mut items = match original {
+        Some(content) => parse_pack_list(content)?,
+        None => Vec::new(),
+    };
+    let mut added = Vec::new();
+    for pack in packs {
+        let already_enabled = items.iter().any(|item| {
+            entry_pack_id(item).is_some_and(|id| id.eq_ignore_ascii_case(&pack.pack_id))
+        });
+        if already_enabled || added.contains(&pack.pack_id) {
+            continue;
+        }
+        items.push(json!({ "pack_id": pack.pack_id, "version": pack.version }));
+        added.push(pack.pack_id.clone());
+    }
+    let applied = serde_json::to_string_pretty(&Value::Array(items)).map_err(|e| e.to_string())?;
+    Ok((applied, added))
+}
+
+/// 仅当文件仍与启用时写入的内容一致时才还原快照
+fn revert_world_file(entry: &PendingRevert) -> RevertReport {
+    let path = Path::new(&entry.world_path).join(&entry.pack_file);
+    let conflict = match fs::read_to_string(&path) {
+        Ok(current) => match (parse_pack_list(&current), parse_pack_list(&entry.applied)) {
+            (Ok(current), Ok(applied)) if current == applied => {
+                restore_snapshot(&path, entry).err()
+            }
+            (Ok(_), Ok(_)) => Some("游戏已修改包列表，未自动还原".to_string()),
+            (Err(e), _) | (_, Err(e)) => Some(e),
+        },
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            Some("包列表文件已被删除，未自动还原".to_string())
+        }
+        Err(e) => Some(format!("读取 {} 失败: {}", path.display(), e)),
+    };
+
+    RevertReport {
+        world_path: entry.world_path.clone(),
+        pack_file: entry.pack_file.clone(),
+        conflict,
+    }
+}
+
+fn restore_snapshot(path: &Path, entry: &PendingRevert) -> Result<(), String> {
+    match &entry.original {
+        Some(original) => fs::write(path, original),
+        None => fs::remove_file(path),
+    }
+    .map_err(|e| format!("还原 {} 失败: {}", path.display(), e))
+}
+
+/// 临时启用世界包：写入包列表并登记快照，下次游戏退出后自动还原
+pub fn enable_packs_temporarily(
+    world_path: &Path,
+    kind: PackKind,
+    packs: &[WorldPackEntry],
+) -> Result<PendingRevert, String> {
+    let world_path = world_key(world_path);
+    let pack_file = world_pack_file_name(kind);
+    let file_path = Path::new(&world_path).join(pack_file);
+    if !Path::new(&world_path).join("level.dat").is_file() {
+        return Err(format!("不是有效的世界目录: {world_path}"));
+    }
+
+    let mut pending = PENDING_REVERTS
+        .lock()
+        .map_err(|_| "待还原记录锁已损坏".to_string())?;
+    let current = match fs::read_to_string(&file_path) {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(format!("读取 {} 失败: {}", file_path.display(), e)),
+    };
+    let (applied, added) = apply_pack_additions(current.as_deref(), packs)?;
+    if added.is_empty() {
+        return Err("所选包均已在该世界中启用".to_string());
+    }
+    fs::write(&file_path, &applied)
+        .map_err(|e| format!("写入 {} 失败: {}", file_path.display(), e))?;
+
+    // 同一世界的同一列表再次临时启用时保留最早的快照，还原时回到真正的原始状态
+    let entry = match pending
+        .iter_mut()
+        .find(|entry| entry.world_path == world_path && entry.pack_file == pack_file)
+    {
+        Some(existing) => {
+            existing.applied = applied;
+            existing.added_pack_ids.extend(added);
+            existing.conflict = None;
+            existing.clone()
+        }
+        None => {
+            let entry = PendingRevert {
+                world_path,
+                pack_file: pack_file.to_string(),
+                original: current,
+                applied,
+                added_pack_ids: added,
+                policy: RevertPolicy::NextGameExit,
+                created_at: now_ms(),
+                game_pid: None,
+                conflict: None,
+            };
+            pending.push(entry.clone());
+            entry
+        }
+    };
+    save_pending(&pending)?;
+    info!(
+        world = %entry.world_path,
+        pack_file = %entry.pack_file,
+        packs = ?entry.added_pack_ids,
+        "已临时启用世界包"
+    );
+    Ok(entry)
+}
+
+pub fn list_pending_reverts() -> Vec<PendingRevert> {
+    PENDING_REVERTS
+        .lock()
+        .map(|pending| pending.clone())
+        .unwrap_or_default()
+}
+
+/// 取消世界的待还原记录，使临时启用的包变为永久启用；返回取消的记录数
+pub fn cancel_pending_revert(world_path: &Path) -> Result<usize, String> {
+    let world_path = world_key(world_path);
+    let mut pending = PENDING_REVERTS
+        .lock()
+        .map_err(|_| "待还原记录锁已损坏".to_string())?;
+    let before = pending.len();
+    pending.retain(|entry| entry.world_path != world_path);
+    let cancelled = before - pending.len();
+    if cancelled > 0 {
+        save_pending(&pending)?;
+    }
+    Ok(cancelled)
+}
+
+/// 还原所有关联游戏进程已退出的记录；冲突的记录保留并标记，等待用户处理
+pub(crate) fn revert_exited_games(system: &mut System) -> Vec<RevertReport> {
+    let Ok(mut pending) = PENDING_REVERTS.lock() else {
+        return Vec::new();
+    };
+    let mut reports = Vec::new();
+    pending.retain_mut(|entry| {
+        let Some(pid) = entry.game_pid else {
+            return true;
+        };
+        if is_process_alive(system, pid) {
+            return true;
+        }
+        let report = revert_world_file(entry);
+        let keep = match &report.conflict {
+            Some(conflict) => {
+                warn!(
+                    world = %entry.world_path,
+                    pack_file = %entry.pack_file,
+                    "临时启用的世界包还原冲突: {conflict}"
+                );
+                entry.conflict = Some(conflict.clone());
+                entry.game_pid = None;
+                true
+            }
+            None => {
+                info!(
+                    world = %entry.world_path,
+                    pack_file = %entry.pack_file,
+                    "游戏已退出，临时启用的世界包已还原"
+                );
+                false
+            }
+        };
+        reports.push(report);
+        keep
+    });
+    if !reports.is_empty()
+        && let Err(e) = save_pending(&pending)
+    {
+        warn!("{}", e);
+    }
+    reports
+}
+
+/// 游戏拉起后调用：把尚未绑定进程的待还原记录绑定到该进程，并在其退出后还原
+pub fn attach_pending_reverts_to_game(pid: u32) {
+    let attached = match PENDING_REVERTS.lock() {
+        Ok(mut pending) => {
+            let mut attached = 0;
+            for entry in pending.iter_mut().filter(|entry| entry.game_pid.is_none()) {
+                entry.game_pid = Some(pid);
+                attached += 1;
+            }
+            if attached > 0
+                && let Err(e) = save_pending(&pending)
+            {
+                warn!("{}", e);
+            }
+            attached
+        }
+        Err(_) => 0,
+    };
+    if attached > 0 {
+        debug!(pid, attached, "已为临时启用的世界包绑定游戏进程");
+        watch_game_session(pid);
+    }
+}
+
+/// 启动器启动时调用：上次会话中游戏已退出（或启动器崩溃）的记录立即还原，
+/// 游戏仍在运行的记录继续监视
+pub fn resume_pending_reverts() -> Vec<RevertReport> {
+    let mut system = System::new();
+    let reports = revert_exited_games(&mut system);
+    let running_pids = list_pending_reverts()
+        .into_iter()
+        .filter_map(|entry| entry.game_pid)
+        .collect::<std::collections::BTreeSet<_>>();
+    for pid in running_pids {
+        watch_game_session(pid);
+    }
+    reports
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temporary_world(test_name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!(
+            "bmcbl-pack-revert-{test_name}-{}",
+            uuid::Uuid::new_v4()
+        ));
+        fs::create_dir_all(&directory).expect("temporary world should be created");
+        directory
+    }
+
+    fn pending_for(world: &Path, original: Option<&str>) -> PendingRevert {
+        let packs = [WorldPackEntry {
+            pack_id: "debug-pack".to_string(),
+            version: [1, 0, 0],
+        }];
+        let (applied, added) =
+            apply_pack_additions(original, &packs).expect("additions should apply");
+        fs::write(world.join("world_behavior_packs.json"), &applied)
+            .expect("applied list should be written");
+        PendingRevert {
+            world_path: world.to_string_lossy().to_string(),
+            pack_file: "world_behavior_packs.json".to_string(),
+            original: original.map(ToString::to_string),
+            applied,
+            added_pack_ids: added,
+            policy: RevertPolicy::NextGameExit,
+            created_at: 0,
+            game_pid: None,
+            conflict: None,
+        }
+    }
+
+    #[test]
+    fn additions_skip_packs_that_are_already_enabled() {
+        let original = r#"[{"pack_id":"DEBUG-PACK","version":[1,0,0]}]"#;
+        let packs = [
+            WorldPackEntry {
+                pack_id: "debug-pack".to_string(),
+                version: [1, 0, 0],
+            },
+            WorldPackEntry {
+                pack_id: "other-pack".to_string(),
+                version: [2, 0, 0],
+            },
+        ];
+
+        let (applied, added) =
+            apply_pack_additions(Some(original), &packs).expect("additions should apply");
+
+        assert_eq!(added, vec!["other-pack".to_string()]);
+        assert_eq!(parse_pack_list(&applied).expect("valid list").len(), 2);
+    }
+
+    #[test]
+    fn unchanged_file_is_restored_to_snapshot() {
+        let world = temporary_world("restore");
+        let original = r#"[{"pack_id":"base","version":[1,0,0]}]"#;
+        let entry = pending_for(&world, Some(original));
+
+        let report = revert_world_file(&entry);
+
+        assert_eq!(report.conflict, None);
+        assert_eq!(
+            fs::read_to_string(world.join("world_behavior_packs.json")).expect("file exists"),
+            original
+        );
+        fs::remove_dir_all(world).expect("temporary world should be removed");
+    }
+
+    #[test]
+    fn file_created_by_enable_is_removed_on_revert() {
+        let world = temporary_world("created");
+        let entry = pending_for(&world, None);
+
+        let report = revert_world_file(&entry);
+
+        assert_eq!(report.conflict, None);
+        assert!(!world.join("world_behavior_packs.json").exists());
+        fs::remove_dir_all(world).expect("temporary world should be removed");
+    }
+
+    #[test]
+    fn file_modified_by_game_is_reported_instead_of_overwritten() {
+        let world = temporary_world("conflict");
+        let entry = pending_for(&world, None);
+        let modified =
+            r#"[{"pack_id":"debug-pack","version":[1,0,0]},{"pack_id":"new","version":[1,0,0]}]"#;
+        fs::write(world.join("world_behavior_packs.json"), modified)
+            .expect("modified list should be written");
+
+        let report = revert_world_file(&entry);
+
+        assert!(report.conflict.is_some());
+        assert_eq!(
+            fs::read_to_string(world.join("world_behavior_packs.json")).expect("file exists"),
+            modified
+        );
+        fs::remove_dir_all(world).expect("temporary world should be removed");
+    }
+}
@@ -0,0 +1,161 @@
+//! 新建空白行为包 / 资源包：生成目录结构、带新 UUID 的 manifest、
+//! `texts/` 语言文件和占位图标，供新手作为开发起点。
+
+use crate::core::minecraft::import::{ImportTargetType, PackagePreview, get_pack_info_from_dir};
+use serde_json::json;
+use std::fs;
+use std::path::Path;
+
+const PLACEHOLDER_ICON: &[u8] = include_bytes!("../../../assets/icons/128x128.png");
+const MIN_ENGINE_VERSION: [u32; 3] = [1, 20, 0];
+const PACK_NAME_KEY: &str = "pack.name";
+const PACK_DESCRIPTION_KEY: &str = "pack.description";
+const DEFAULT_LANG: &str = "en_US";
+
+const BEHAVIOR_PACK_DIRS: [&str; 4] = ["entities", "items", "scripts", "texts"];
+const RESOURCE_PACK_DIRS: [&str; 3] = ["textures", "sounds", "texts"];
+
+/// 在 `dest_dir` 新建空白行为包
+pub fn create_empty_behavior_pack(
+    dest_dir: String,
+    pack_name: String,
+    description: String,
+) -> Result<PackagePreview, String> {
+    create_empty_pack(
+        Path::new(&dest_dir),
+        &pack_name,
+        &description,
+        ImportTargetType::BehaviorPack,
+    )
+}
+
+/// 在 `dest_dir` 新建空白资源包
+pub fn create_empty_resource_pack(
+    dest_dir: String,
+    pack_name: String,
+    description: String,
+) -> Result<PackagePreview, String> {
+    create_empty_pack(
+        Path::new(&dest_dir),
+        &pack_name,
+        &description,
+        ImportTargetType::ResourcePack,
+    )
+}
+
+fn create_empty_pack(
+    dest_dir: &Path,
+    pack_name: &str,
+    description: &str,
+    target_type: ImportTargetType,
+) -> Result<PackagePreview, String> {
+    let pack_name = pack_name.trim();
+    if pack_name.is_empty() {
+        return Err("包名称不能为空".to_string());
+    }
+    let is_occupied = fs::read_dir(dest_dir)
+        .map(|mut entries| entries.next().is_some())
+        .unwrap_or(false);
+    if is_occupied {
+        return Err(format!("目标目录不为空: {}", dest_dir.display()));
+    }
+
+    let (sub_dirs, module_type): (&[&str], &str) = match target_type {
+        ImportTargetType::BehaviorPack => (&BEHAVIOR_PACK_DIRS, "data"),
+        _ => (&RESOURCE_PACK_DIRS, "resources"),
+    };
+    for sub_dir in sub_dirs {
+        let path = dest_dir.join(sub_dir);
+        fs::create_dir_all(&path).map_err(|e| format!("创建目录失败 {}: {}", path.display(), e))?;
+    }
+
+    write_file(
+        &dest_dir.join("manifest.json"),
+        build_manifest(module_type).as_bytes(),
+    )?;
+    let lang = format!(
+        "{PACK_NAME_KEY}={}\n{PACK_DESCRIPTION_KEY}={}\n",
+        single_line(pack_name),
+        single_line(description)
+    );
+    write_file(
+        &dest_dir.join("texts").join(format!("{DEFAULT_LANG}.lang")),
+        lang.as_bytes(),
+    )?;
+    write_file(
+        &dest_dir.join("texts").join("languages.json"),
+        json!([DEFAULT_LANG]).to_string().as_bytes(),
+    )?;
+    write_file(&dest_dir.join("pack_icon.png"), PLACEHOLDER_ICON)?;
+
+    get_pack_info_from_dir(dest_dir, &target_type, None).map_err(|e| e.to_string())
+}
+
+/// 名称与描述通过 `texts/*.lang` 的键引用，便于后续本地化
+fn build_manifest(module_type: &str) -> String {
+    let manifest = json!({
+        "format_version": 2,
+        "header": {
+            "name": PACK_NAME_KEY,
+            "description": PACK_DESCRIPTION_KEY,
+            "uuid": uuid::Uuid::new_v4().to_string(),
+            "version": [1, 0, 0],
+            "min_engine_version": MIN_ENGINE_VERSION,
+        },
+        "modules": [{
+            "type": module_type,
+            "uuid": uuid::Uuid::new_v4().to_string(),
+            "version": [1, 0, 0],
+        }],
+    });
+    serde_json::to_string_pretty(&manifest).unwrap_or_else(|_| manifest.to_string())
+}
+
+/// `.lang` 按行解析，换行会截断取值
+fn single_line(value: &str) -> String {
+    value.replace(['\r', '\n'], " ").trim().to_string()
+}
+
+fn write_file(path: &Path, contents: &[u8]) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("写入 {} 失败: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn behavior_pack_scaffold_is_detected_with_lang_name() {
+        let test_dir = std::env::temp_dir().join(format!(
+            "bmcbl-pack-scaffold-test-{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .expect("system time should be after unix epoch")
+                .as_nanos()
+        ));
+        let pack_dir = test_dir.join("my_pack");
+
+        let preview = create_empty_behavior_pack(
+            pack_dir.to_string_lossy().to_string(),
+            "My Pack".to_string(),
+            "First line\nsecond line".to_string(),
+        )
+        .expect("create behavior pack");
+
+        assert!(preview.valid);
+        assert_eq!(preview.name, "My Pack");
+        assert_eq!(preview.description, "First line second line");
+        assert!(pack_dir.join("entities").is_dir());
+        assert!(pack_dir.join("pack_icon.png").is_file());
+        assert!(
+            create_empty_resource_pack(
+                pack_dir.to_string_lossy().to_string(),
+                "Again".to_string(),
+                String::new(),
+            )
+            .is_err()
+        );
+
+        fs::remove_dir_all(&test_dir).expect("remove test dir");
+    }
+}
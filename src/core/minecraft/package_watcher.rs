@@ -0,0 +1,261 @@
+//! 监听 Minecraft 系统包在启动器之外被安装、卸载或更新（例如被 Microsoft Store 或其他启动器替换），
+//! 广播 `package-changed` 事件，让版本列表等缓存及时失效。
+//! 优先订阅 PackageCatalog 事件，不可用时退回定时查询包信息。
+
+use crate::core::minecraft::appx::utils::get_package_info;
+use crate::core::minecraft::editions::known_editions;
+use crate::core::minecraft::launcher::launch_guard::is_package_identity_locked;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+use windows::ApplicationModel::{
+    Package, PackageCatalog, PackageInstallingEventArgs, PackageUninstallingEventArgs,
+    PackageUpdatingEventArgs,
+};
+use windows::Foundation::TypedEventHandler;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PackageChangeKind {
+    Installed,
+    Uninstalled,
+    Updated,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PackageChangedEvent {
+    pub family_name: String,
+    pub change: PackageChangeKind,
+    /// 卸载时为 `None`
+    pub new_version: Option<String>,
+}
+
+/// `package-changed` 事件：被监听的包完成安装、卸载或更新时广播一次
+static PACKAGE_EVENTS: Lazy<broadcast::Sender<PackageChangedEvent>> = Lazy::new(|| {
+    let (tx, _rx) = broadcast::channel(16);
+    tx
+});
+
+static WATCHER_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// 订阅期间保持 PackageCatalog 存活，事件处理器随之在整个会话内有效
+static CATALOG_SUBSCRIPTION: Lazy<Mutex<Option<PackageCatalog>>> = Lazy::new(|| Mutex::new(None));
+
+pub fn subscribe_package_events() -> broadcast::Receiver<PackageChangedEvent> {
+    PACKAGE_EVENTS.subscribe()
+}
+
+/// 只关注版本注册表中登记的包
+fn is_watched_family(family_name: &str) -> bool {
+    known_editions()
+        .iter()
+        .any(|edition| edition.family_name().eq_ignore_ascii_case(family_name))
+}
+
+fn package_family_and_version(package: &Package) -> Option<(String, String)> {
+    let id = package.Id().ok()?;
+    let family_name = id.FamilyName().ok()?.to_string();
+    let version = id.Version().ok()?;
+    Some((
+        family_name,
+        format!(
+            "{}.{}.{}.{}",
+            version.Major, version.Minor, version.Build, version.Revision
+        ),
+    ))
+}
+
+fn publish(family_name: String, change: PackageChangeKind, new_version: Option<String>) {
+    if !is_watched_family(&family_name) {
+        return;
+    }
+    if is_package_identity_locked(&family_name) {
+        debug!(family_name = %family_name, change = ?change, "包正在由启动器注册，忽略变化事件");
+        return;
+    }
+    info!(
+        family_name = %family_name,
+        change = ?change,
+        new_version = new_version.as_deref().unwrap_or("-"),
+        "检测到 Minecraft 包在启动器外发生变化"
+    );
+    crate::core::library_search::invalidate_library_index();
+    let _ = PACKAGE_EVENTS.send(PackageChangedEvent {
+        family_name,
+        change,
+        new_version,
+    });
+}
+
+fn publish_package(package: windows::core::Result<Package>, change: PackageChangeKind) {
+    let Some((family_name, version)) = package.ok().as_ref().and_then(package_family_and_version)
+    else {
+        return;
+    };
+    let new_version = (change != PackageChangeKind::Uninstalled).then_some(version);
+    publish(family_name, change, new_version);
+}
+
+fn subscribe_catalog() -> windows::core::Result<PackageCatalog> {
+    let catalog = PackageCatalog::OpenForCurrentUser()?;
+    // 安装、卸载、更新事件会随进度多次触发，只处理完成时的那一次
+    catalog.PackageInstalling(&TypedEventHandler::new(
+        |_, args: windows::core::Ref<PackageInstallingEventArgs>| {
+            if let Ok(args) = args.ok()
+                && args.IsComplete().unwrap_or(false)
+            {
+                publish_package(args.Package(), PackageChangeKind::Installed);
+            }
+            Ok(())
+        },
+    ))?;
+    catalog.PackageUninstalling(&TypedEventHandler::new(
+        |_, args: windows::core::Ref<PackageUninstallingEventArgs>| {
+            if let Ok(args) = args.ok()
+                && args.IsComplete().unwrap_or(false)
+            {
+                publish_package(args.Package(), PackageChangeKind::Uninstalled);
+            }
+            Ok(())
+        },
+    ))?;
+    catalog.PackageUpdating(&TypedEventHandler::new(
+        |_, args: windows::core::Ref<PackageUpdatingEventArgs>| {
+            if let Ok(args) = args.ok()
+                && args.IsComplete().unwrap_or(false)
+            {
+                publish_package(args.TargetPackage(), PackageChangeKind::Updated);
+            }
+            Ok(())
+        },
+    ))?;
+    Ok(catalog)
+}
+
+fn installed_versions() -> HashMap<String, String> {
+    known_editions()
+        .iter()
+        .filter_map(|edition| {
+            let (version, family_name, _) = get_package_info(&edition.aumid).ok().flatten()?;
+            Some((family_name, version))
+        })
+        .collect()
+}
+
+/// 两次查询结果的差异，按包系列名排序
+fn diff_versions(
+    previous: &HashMap<String, String>,
+    current: &HashMap<String, String>,
+) -> Vec<(String, PackageChangeKind, Option<String>)> {
+    let mut changes = Vec::new();
+    for (family_name, version) in current {
+        match previous.get(family_name) {
+            None => changes.push((
+                family_name.clone(),
+                PackageChangeKind::Installed,
+                Some(version.clone()),
+            )),
+            Some(previous_version) if previous_version != version => changes.push((
+                family_name.clone(),
+                PackageChangeKind::Updated,
+                Some(version.clone()),
+            )),
+            Some(_) => {}
+        }
+    }
+    for family_name in previous.keys() {
+        if !current.contains_key(family_name) {
+            changes.push((family_name.clone(), PackageChangeKind::Uninstalled, None));
+        }
+    }
+    changes.sort_by(|left, right| left.0.cmp(&right.0));
+    changes
+}
+
+fn spawn_poll_fallback() {
+    let result = std::thread::Builder::new()
+        .name("bmcbl-package-watch".to_string())
+        .spawn(|| {
+            let mut previous = installed_versions();
+            loop {
+                std::thread::sleep(POLL_INTERVAL);
+                let current = installed_versions();
+                for (family_name, change, new_version) in diff_versions(&previous, &current) {
+                    publish(family_name, change, new_version);
+                }
+                previous = current;
+            }
+        });
+    if let Err(error) = result {
+        warn!(?error, "启动包变化轮询线程失败");
+    }
+}
+
+/// 启动包变化监听，整个会话只启动一次；返回本次调用是否真正启动了监听
+pub fn start_package_watcher() -> bool {
+    if WATCHER_STARTED.swap(true, Ordering::SeqCst) {
+        return false;
+    }
+    match subscribe_catalog() {
+        Ok(catalog) => {
+            debug!("已订阅 PackageCatalog 包变化事件");
+            *CATALOG_SUBSCRIPTION
+                .lock()
+                .unwrap_or_else(|e| e.into_inner()) = Some(catalog);
+        }
+        Err(error) => {
+            warn!("订阅 PackageCatalog 失败，改为定时查询包信息: {}", error);
+            spawn_poll_fallback();
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn poll_diff_reports_install_update_and_uninstall() {
+        let previous = HashMap::from([
+            ("B_8wekyb3d8bbwe".to_string(), "1.21.0.3".to_string()),
+            ("C_8wekyb3d8bbwe".to_string(), "1.20.0.1".to_string()),
+            ("D_8wekyb3d8bbwe".to_string(), "1.19.0.1".to_string()),
+        ]);
+        let current = HashMap::from([
+            ("A_8wekyb3d8bbwe".to_string(), "1.21.2.2".to_string()),
+            ("B_8wekyb3d8bbwe".to_string(), "1.21.1.3".to_string()),
+            ("D_8wekyb3d8bbwe".to_string(), "1.19.0.1".to_string()),
+        ]);
+
+        assert_eq!(
+            diff_versions(&previous, &current),
+            vec![
+                (
+                    "A_8wekyb3d8bbwe".to_string(),
+                    PackageChangeKind::Installed,
+                    Some("1.21.2.2".to_string())
+                ),
+                (
+                    "B_8wekyb3d8bbwe".to_string(),
+                    PackageChangeKind::Updated,
+                    Some("1.21.1.3".to_string())
+                ),
+                (
+                    "C_8wekyb3d8bbwe".to_string(),
+                    PackageChangeKind::Uninstalled,
+                    None
+                ),
+            ]
+        );
+        assert!(is_watched_family("microsoft.minecraftuwp_8wekyb3d8bbwe"));
+        assert!(!is_watched_family("Microsoft.WindowsStore_8wekyb3d8bbwe"));
+    }
+}
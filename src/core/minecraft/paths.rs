@@ -1,9 +1,13 @@
 // src-tauri/src/core/minecraft/paths.rs
+use crate::core::minecraft::editions::{edition_entry, known_editions};
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::env;
 use std::fs;
+use std::io;
 use std::path::{Path, PathBuf};
+use tracing::warn;
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")] // 前端传 'uwp' 或 'gdk'
@@ -62,6 +66,200 @@ impl GameTargetDir {
     }
 }
 
+/// 用户数据目录。优先读环境变量，缺失时（部分托管/漫游配置的机器上会被清掉）
+/// 改用 `SHGetKnownFolderPath` 查询
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppDataFolder {
+    Local,
+    Roaming,
+}
+
+static LOCAL_APPDATA: Lazy<Option<PathBuf>> = Lazy::new(|| AppDataFolder::Local.lookup());
+static ROAMING_APPDATA: Lazy<Option<PathBuf>> = Lazy::new(|| AppDataFolder::Roaming.lookup());
+
+impl AppDataFolder {
+    pub const fn env_name(self) -> &'static str {
+        match self {
+            Self::Local => "LOCALAPPDATA",
+            Self::Roaming => "APPDATA",
+        }
+    }
+
+    pub fn resolve(self) -> Option<PathBuf> {
+        match self {
+            Self::Local => LOCAL_APPDATA.clone(),
+            Self::Roaming => ROAMING_APPDATA.clone(),
+        }
+    }
+
+    fn lookup(self) -> Option<PathBuf> {
+        if let Some(value) = env::var_os(self.env_name()).filter(|value| !value.is_empty()) {
+            return Some(PathBuf::from(value));
+        }
+        let fallback = self.known_folder();
+        match &fallback {
+            Some(path) => warn!(
+                "环境变量 {} 缺失，改用系统已知文件夹: {}",
+                self.env_name(),
+                path.display()
+            ),
+            None => warn!(
+                "环境变量 {} 缺失，且无法查询系统已知文件夹",
+                self.env_name()
+            ),
+        }
+        fallback
+    }
+
+    #[cfg(target_os = "windows")]
+    fn known_folder(self) -> Option<PathBuf> {
+        use windows::Win32::System::Com::CoTaskMemFree;
+        use windows::Win32::UI::Shell::{
+            FOLDERID_LocalAppData, FOLDERID_RoamingAppData, KF_FLAG_DEFAULT, SHGetKnownFolderPath,
+        };
+
+        let folder_id = match self {
+            Self::Local => &FOLDERID_LocalAppData,
+            Self::Roaming => &FOLDERID_RoamingAppData,
+        };
+        unsafe {
+            let path_ptr = SHGetKnownFolderPath(folder_id, KF_FLAG_DEFAULT, None).ok()?;
+            let path = path_ptr.to_string().ok();
+            CoTaskMemFree(Some(path_ptr.as_ptr() as *const _));
+            path.filter(|path| !path.is_empty()).map(PathBuf::from)
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    fn known_folder(self) -> Option<PathBuf> {
+        None
+    }
+}
+
+pub fn local_appdata_dir() -> Option<PathBuf> {
+    AppDataFolder::Local.resolve()
+}
+
+pub fn roaming_appdata_dir() -> Option<PathBuf> {
+    AppDataFolder::Roaming.resolve()
+}
+
+/// `\\server\share\...` 或 `\\?\UNC\server\share\...` 时返回 `\\server\share`
+pub fn unc_share_root(path: &Path) -> Option<String> {
+    let text = path.to_string_lossy().replace('/', "\\");
+    let rest = if let Some(rest) = text.strip_prefix(r"\\?\UNC\") {
+        rest
+    } else if text.starts_with(r"\\?\") || text.starts_with(r"\\.\") {
+        return None;
+    } else {
+        text.strip_prefix(r"\\")?
+    };
+    let mut parts = rest.split('\\').filter(|part| !part.is_empty());
+    let server = parts.next()?;
+    let share = parts.next()?;
+    Some(format!(r"\\{server}\{share}"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceRootStatus {
+    Available,
+    /// 目录不存在（例如游戏从未运行过）
+    Missing,
+    /// 目录存在但访问失败，或所在网络共享离线
+    Inaccessible,
+    /// 环境变量与已知文件夹都拿不到
+    Unresolved,
+}
+
+/// 一个被探测过的数据根目录，供界面说明为什么列表是空的
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceRootProbe {
+    pub path: Option<String>,
+    pub status: SourceRootStatus,
+    pub inaccessible: bool,
+    /// 系统返回的原始错误
+    pub error: Option<String>,
+    /// 给用户看的说明
+    pub message: Option<String>,
+}
+
+impl SourceRootProbe {
+    fn new(path: Option<&Path>, status: SourceRootStatus) -> Self {
+        Self {
+            path: path.map(|path| path.to_string_lossy().into_owned()),
+            status,
+            inaccessible: status == SourceRootStatus::Inaccessible,
+            error: None,
+            message: None,
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.status == SourceRootStatus::Available
+    }
+}
+
+fn inaccessible_probe(path: &Path, error: &io::Error) -> SourceRootProbe {
+    let message = match unc_share_root(path) {
+        Some(share) => format!("AppData 被重定向到 {share}，该位置当前无法访问：{error}"),
+        None => format!("无法访问 {}：{error}", path.display()),
+    };
+    SourceRootProbe {
+        error: Some(error.to_string()),
+        message: Some(message),
+        ..SourceRootProbe::new(Some(path), SourceRootStatus::Inaccessible)
+    }
+}
+
+/// 探测一个数据根目录是否可用；网络路径不存在时再看共享本身是否在线
+pub fn probe_source_root(path: &Path) -> SourceRootProbe {
+    match fs::metadata(path) {
+        Ok(metadata) if metadata.is_dir() => {
+            SourceRootProbe::new(Some(path), SourceRootStatus::Available)
+        }
+        Ok(_) => SourceRootProbe {
+            message: Some(format!("{} 不是目录", path.display())),
+            ..SourceRootProbe::new(Some(path), SourceRootStatus::Missing)
+        },
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            if let Some(share) = unc_share_root(path)
+                && let Err(share_error) = fs::metadata(&share)
+            {
+                return inaccessible_probe(path, &share_error);
+            }
+            SourceRootProbe {
+                message: Some(format!("{} 不存在", path.display())),
+                ..SourceRootProbe::new(Some(path), SourceRootStatus::Missing)
+            }
+        }
+        Err(error) => inaccessible_probe(path, &error),
+    }
+}
+
+/// 探测 `options` 对应的游戏数据根目录
+pub fn probe_game_root(options: &GamePathOptions) -> SourceRootProbe {
+    if let Some(root) = get_game_root(options) {
+        return probe_source_root(&root);
+    }
+    let folder = match options.build_type {
+        BuildType::Uwp => AppDataFolder::Local,
+        BuildType::Gdk => AppDataFolder::Roaming,
+    };
+    let message = if folder.resolve().is_none() {
+        format!(
+            "无法确定 %{}% 的位置：环境变量缺失，系统也未返回对应的已知文件夹",
+            folder.env_name()
+        )
+    } else {
+        "无法解析该版本的游戏数据目录".to_string()
+    };
+    SourceRootProbe {
+        message: Some(message),
+        ..SourceRootProbe::new(None, SourceRootStatus::Unresolved)
+    }
+}
+
 pub fn com_mojang_dir(root: &Path) -> PathBuf {
     root.join("games").join("com.mojang")
 }
@@ -106,8 +304,7 @@ pub fn discover_local_package_roots_with_vanilla() -> Vec<PathBuf> {
         &mut roots,
     );
 
-    if let Ok(appdata) = env::var("APPDATA") {
-        let appdata = PathBuf::from(appdata);
+    if let Some(appdata) = roaming_appdata_dir() {
         push_package_root_with_vanilla(&mut roots, appdata.join("Minecraft Bedrock"));
         push_package_root_with_vanilla(&mut roots, appdata.join("Minecraft Bedrock Preview"));
         push_direct_child_package_roots_with_vanilla(
@@ -116,14 +313,10 @@ pub fn discover_local_package_roots_with_vanilla() -> Vec<PathBuf> {
         );
     }
 
-    if let Ok(local_appdata) = env::var("LOCALAPPDATA") {
-        let packages = PathBuf::from(local_appdata).join("Packages");
-        for package_name in [
-            "Microsoft.MinecraftUWP_8wekyb3d8bbwe",
-            "Microsoft.MinecraftWindowsBeta_8wekyb3d8bbwe",
-            "Microsoft.MinecraftEducationEdition_8wekyb3d8bbwe",
-            "Microsoft.MinecraftEducationEditionBeta_8wekyb3d8bbwe",
-        ] {
+    if let Some(local_appdata) = local_appdata_dir() {
+        let packages = local_appdata.join("Packages");
+        for edition in known_editions() {
+            let package_name = edition.uwp_data_dir_name();
             push_package_root_with_vanilla(&mut roots, packages.join(package_name));
             push_package_root_with_vanilla(
                 &mut roots,
@@ -267,7 +460,7 @@ pub fn resolve_game_target_parent(
 }
 
 fn read_version_redirection_enabled(version_name: &str) -> Option<bool> {
-    let config_path = Path::new("./BMCBL/versions")
+    let config_path = crate::utils::file_ops::versions_dir()
         .join(version_name)
         .join("config.json");
     let content = fs::read_to_string(config_path).ok()?;
@@ -286,13 +479,13 @@ pub fn normalize_game_path_options(options: &GamePathOptions) -> GamePathOptions
 /// 获取游戏的根目录
 /// UWP: .../LocalState
 /// GDK: .../Users/<uid> (如果不传uid，返回上一级 Users)
-/// Isolation: ./BMCBL/versions/<ver>/Minecraft Bedrock/...
+/// Isolation: <数据目录>/versions/<ver>/Minecraft Bedrock/...
 pub fn get_game_root(options: &GamePathOptions) -> Option<PathBuf> {
     let options = normalize_game_path_options(options);
     if options.enable_isolation {
         // === 隔离模式 ===
-        // 路径: ./BMCBL/versions/<version_name>/Minecraft Bedrock
-        let root = Path::new("./BMCBL/versions")
+        // 路径: <数据目录>/versions/<version_name>/Minecraft Bedrock
+        let root = crate::utils::file_ops::versions_dir()
             .join(&options.version_name)
             .join("Minecraft Bedrock");
         return Some(root);
@@ -301,27 +494,13 @@ pub fn get_game_root(options: &GamePathOptions) -> Option<PathBuf> {
     // === 系统模式 ===
     match options.build_type {
         BuildType::Uwp => {
-            let local_appdata = env::var("LOCALAPPDATA").ok()?;
-            let base = PathBuf::from(local_appdata).join("Packages");
-            let package_name = match options.edition {
-                Edition::Release => "Microsoft.MinecraftUWP_8wekyb3d8bbwe",
-                Edition::Preview => "Microsoft.MinecraftWindowsBeta_8wekyb3d8bbwe",
-                Edition::Education => "Microsoft.MinecraftEducationEdition_8wekyb3d8bbwe",
-                Edition::EducationPreview => {
-                    "Microsoft.MinecraftEducationEditionBeta_8wekyb3d8bbwe"
-                }
-            };
-            Some(base.join(package_name).join("LocalState"))
+            let base = local_appdata_dir()?.join("Packages");
+            let edition = edition_entry(&options.edition)?;
+            Some(base.join(edition.uwp_data_dir_name()).join("LocalState"))
         }
         BuildType::Gdk => {
-            let appdata = env::var("APPDATA").ok()?;
-            let base = PathBuf::from(appdata);
-            let folder = match options.edition {
-                Edition::Release => "Minecraft Bedrock",
-                Edition::Preview => "Minecraft Bedrock Preview",
-                Edition::Education => "Minecraft Education Edition",
-                Edition::EducationPreview => "Minecraft Education Edition Preview",
-            };
+            let base = roaming_appdata_dir()?;
+            let folder = edition_entry(&options.edition)?.gdk_data_dir?;
             Some(base.join(folder))
         }
     }
@@ -539,6 +718,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn source_root_probe_reports_unc_shares_and_missing_dirs() {
+        assert_eq!(
+            unc_share_root(Path::new(r"\\fs01\profiles$\alice\AppData\Local")).as_deref(),
+            Some(r"\\fs01\profiles$")
+        );
+        assert_eq!(
+            unc_share_root(Path::new(r"\\?\UNC\fs01\home\AppData")).as_deref(),
+            Some(r"\\fs01\home")
+        );
+        assert_eq!(unc_share_root(Path::new(r"\\?\C:\Users")), None);
+        assert_eq!(unc_share_root(Path::new(r"C:\Users\alice")), None);
+
+        let root = unique_temp_dir("bmcbl-paths-probe");
+        std::fs::create_dir_all(&root).unwrap_or_else(|error| panic!("create probe root: {error}"));
+        let available = probe_source_root(&root);
+        assert_eq!(available.status, SourceRootStatus::Available);
+        assert!(!available.inaccessible);
+        let missing = probe_source_root(&root.join("LocalState"));
+        assert_eq!(missing.status, SourceRootStatus::Missing);
+        assert!(missing.error.is_none());
+
+        if let Err(error) = std::fs::remove_dir_all(&root) {
+            eprintln!("cleanup probe root {}: {error}", root.display());
+        }
+    }
+
     fn unique_temp_dir(prefix: &str) -> PathBuf {
         let nonce = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -0,0 +1,266 @@
+//! 存档删除记录：删除时移入回收站（失败时移入 BMCBL 备份目录），
+//! 保留最近若干条记录并支持撤销最近一次删除。
+
+use crate::core::minecraft::import::copy_dir_recursive;
+use crate::utils::file_ops;
+use crate::utils::recycle_bin;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast;
+use tracing::{debug, warn};
+
+const MAX_RECENT_DELETIONS: usize = 10;
+const RECENT_DELETIONS_FILE_NAME: &str = "recent_deletions.json";
+const DELETION_BACKUP_DIR: &str = "deleted_worlds";
+const MAX_RESTORE_SUFFIX: usize = 1000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeletionRecord {
+    pub id: String,
+    pub display_name: String,
+    pub original_path: String,
+    /// 回收站中的 `$R` 条目路径
+    pub recycle_item_path: Option<String>,
+    /// 无法使用回收站时的备份目录
+    pub backup_path: Option<String>,
+    pub deleted_at: i64,
+}
+
+impl DeletionRecord {
+    pub fn can_undo(&self) -> bool {
+        self.recycle_item_path.is_some() || self.backup_path.is_some()
+    }
+}
+
+static RECENT_DELETIONS: Lazy<Mutex<VecDeque<DeletionRecord>>> =
+    Lazy::new(|| Mutex::new(load_records()));
+
+/// `deletion-performed` 事件：每次删除完成后广播一次
+static DELETION_EVENTS: Lazy<broadcast::Sender<DeletionRecord>> = Lazy::new(|| {
+    let (tx, _rx) = broadcast::channel(16);
+    tx
+});
+
+pub fn subscribe_deletion_events() -> broadcast::Receiver<DeletionRecord> {
+    DELETION_EVENTS.subscribe()
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+fn records_path() -> PathBuf {
+    file_ops::bmcbl_dir().join(RECENT_DELETIONS_FILE_NAME)
+}
+
+fn load_records() -> VecDeque<DeletionRecord> {
+    let Ok(raw) = fs::read_to_string(records_path()) else {
+        return VecDeque::new();
+    };
+    match serde_json::from_str(&raw) {
+        Ok(records) => records,
+        Err(e) => {
+            warn!("解析删除记录失败，已忽略: {}", e);
+            VecDeque::new()
+        }
+    }
+}
+
+fn save_records(records: &VecDeque<DeletionRecord>) {
+    let path = records_path();
+    let result = serde_json::to_string_pretty(records)
+        .map_err(|e| e.to_string())
+        .and_then(|raw| fs::write(&path, raw).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        warn!("保存删除记录失败 {}: {}", path.display(), e);
+    }
+}
+
+/// 删除目录并登记到最近删除记录：优先移入回收站，失败时移入备份目录
+pub fn delete_with_undo(path: &Path, display_name: &str) -> Result<DeletionRecord, String> {
+    let original_path =
+        std::path::absolute(path).map_err(|e| format!("解析路径失败 {}: {}", path.display(), e))?;
+    let deleted_at = now_ms();
+    let id = uuid::Uuid::new_v4().to_string();
+
+    let (recycle_item_path, backup_path) = match recycle_bin::move_to_recycle_bin(&original_path) {
+        Ok(()) => {
+            let item_path = recycle_bin::find_recycled_item(&original_path);
+            if item_path.is_none() {
+                warn!(
+                    "已移入回收站，但未找到回收站条目，无法在启动器内撤销: {}",
+                    original_path.display()
+                );
+            }
+            (item_path, None)
+        }
+        Err(e) => {
+            warn!("移入回收站失败，改为移动到备份目录: {}", e);
+            let folder_name = original_path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| id.clone());
+            let backup_path = file_ops::bmcbl_subdir(DELETION_BACKUP_DIR)
+                .join(format!("{deleted_at}-{folder_name}"));
+            move_dir(&original_path, &backup_path)?;
+            (None, Some(backup_path))
+        }
+    };
+
+    let record = DeletionRecord {
+        id,
+        display_name: display_name.to_string(),
+        original_path: original_path.to_string_lossy().to_string(),
+        recycle_item_path: recycle_item_path.map(|p| p.to_string_lossy().to_string()),
+        backup_path: backup_path.map(|p| p.to_string_lossy().to_string()),
+        deleted_at,
+    };
+
+    if record.can_undo() {
+        let mut records = RECENT_DELETIONS
+            .lock()
+            .map_err(|_| "删除记录锁已损坏".to_string())?;
+        records.push_back(record.clone());
+        while records.len() > MAX_RECENT_DELETIONS {
+            if let Some(expired) = records.pop_front() {
+                discard_backup(&expired);
+            }
+        }
+        save_records(&records);
+    }
+
+    if DELETION_EVENTS.send(record.clone()).is_err() {
+        debug!("deletion-performed 事件没有订阅者");
+    }
+    Ok(record)
+}
+
+pub fn recent_deletions() -> Vec<DeletionRecord> {
+    RECENT_DELETIONS
+        .lock()
+        .map(|records| records.iter().rev().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// 撤销最近一次删除，返回实际还原到的路径
+pub fn undo_last_deletion() -> Result<String, String> {
+    let last_id = RECENT_DELETIONS
+        .lock()
+        .map_err(|_| "删除记录锁已损坏".to_string())?
+        .back()
+        .map(|record| record.id.clone())
+        .ok_or_else(|| "没有可撤销的删除".to_string())?;
+    undo_deletion(&last_id)
+}
+
+/// 撤销指定的删除记录；原路径已被重新创建时自动追加序号，不会覆盖
+pub fn undo_deletion(id: &str) -> Result<String, String> {
+    let mut records = RECENT_DELETIONS
+        .lock()
+        .map_err(|_| "删除记录锁已损坏".to_string())?;
+    let index = records
+        .iter()
+        .position(|record| record.id == id)
+        .ok_or_else(|| "删除记录已过期，无法撤销".to_string())?;
+    let record = records[index].clone();
+
+    let target = unique_restore_path(Path::new(&record.original_path))?;
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("创建还原目录失败: {}", e))?;
+    }
+
+    if let Some(item_path) = record.recycle_item_path.as_deref() {
+        recycle_bin::restore_recycled_item(Path::new(item_path), &target)?;
+    } else if let Some(backup_path) = record.backup_path.as_deref() {
+        move_dir(Path::new(backup_path), &target)?;
+    } else {
+        return Err("该删除无法撤销".to_string());
+    }
+
+    records.remove(index);
+    save_records(&records);
+    Ok(target.to_string_lossy().to_string())
+}
+
+fn unique_restore_path(original: &Path) -> Result<PathBuf, String> {
+    if !original.exists() {
+        return Ok(original.to_path_buf());
+    }
+    let parent = original
+        .parent()
+        .ok_or_else(|| format!("无效的还原路径: {}", original.display()))?;
+    let name = original
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| format!("无效的还原路径: {}", original.display()))?;
+
+    (1..=MAX_RESTORE_SUFFIX)
+        .map(|index| parent.join(format!("{name} ({index})")))
+        .find(|candidate| !candidate.exists())
+        .ok_or_else(|| format!("找不到可用的还原路径: {}", original.display()))
+}
+
+fn move_dir(source: &Path, target: &Path) -> Result<(), String> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+    }
+    if fs::rename(source, target).is_ok() {
+        return Ok(());
+    }
+
+    // 跨卷时无法直接重命名，先复制再删除源目录
+    copy_dir_recursive(source, target).map_err(|e| {
+        format!(
+            "移动目录失败 {} -> {}: {}",
+            source.display(),
+            target.display(),
+            e
+        )
+    })?;
+    fs::remove_dir_all(source).map_err(|e| format!("删除源目录失败: {}", e))
+}
+
+fn discard_backup(record: &DeletionRecord) {
+    let Some(backup_path) = record.backup_path.as_deref() else {
+        return;
+    };
+    if let Err(e) = fs::remove_dir_all(backup_path) {
+        warn!("清理过期删除备份失败 {}: {}", backup_path, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restore_path_is_suffixed_when_original_was_recreated() {
+        let test_dir = std::env::temp_dir().join(format!(
+            "bmcbl-recent-deletions-test-{}",
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("system time should be after unix epoch")
+                .as_nanos()
+        ));
+        let original = test_dir.join("world");
+
+        assert_eq!(unique_restore_path(&original).expect("free path"), original);
+
+        fs::create_dir_all(&original).expect("create recreated world");
+        fs::create_dir_all(test_dir.join("world (1)")).expect("create first suffix");
+        assert_eq!(
+            unique_restore_path(&original).expect("suffixed path"),
+            test_dir.join("world (2)")
+        );
+
+        fs::remove_dir_all(&test_dir).expect("remove test dir");
+    }
+}
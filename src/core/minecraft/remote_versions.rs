@@ -2,6 +2,7 @@ use crate::config::config::read_config;
 use crate::http::proxy::get_client_for_proxy;
 use crate::http::request::{GLOBAL_CLIENT, RequestOptions, send_request_with_options};
 use crate::utils::file_ops;
+use crate::utils::network::should_save_bandwidth;
 use anyhow::{Context as _, Result};
 use futures_util::StreamExt as _;
 use reqwest::Url;
@@ -391,6 +392,11 @@ async fn load_or_fetch_versions_once(force_refresh: bool) -> Result<Vec<RemoteMi
             if age <= CACHE_TTL && !cache.versions.is_empty() {
                 return Ok(cache.versions);
             }
+            // 计费网络下不重新拉取完整目录，直接使用过期缓存
+            if !cache.versions.is_empty() && should_save_bandwidth() {
+                debug!("metered connection, serving stale remote versions cache");
+                return Ok(cache.versions);
+            }
         }
     }
 
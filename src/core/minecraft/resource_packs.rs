@@ -1,5 +1,7 @@
 // src-tauri/src/commands/resource_packs.rs
 
+use crate::core::minecraft::editions::known_editions;
+use crate::core::minecraft::pack_names::resolve_pack_display_name;
 use crate::core::minecraft::paths::{GamePathOptions, GameTargetDir, game_target_dirs};
 use anyhow::{Result, bail};
 use rayon::prelude::*;
@@ -10,11 +12,16 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::Instant;
 use tracing::debug;
+use walkdir::{DirEntry, WalkDir};
 
 /// 返回给前端的资源包信息结构
 #[derive(Debug, Serialize)]
 pub struct McPackInfo {
     pub folder_name: String,
+    /// 去除格式化代码后的显示名称，未解析时为文件夹名或 UUID 生成的兜底名称
+    pub display_name: String,
+    /// 名称仍是语言键或只有格式化代码，已退回兜底名称
+    pub name_unresolved: bool,
     pub folder_path: String,
     pub manifest: Value,
     pub manifest_raw: String,
@@ -129,6 +136,18 @@ pub(crate) fn read_packs_standard(
             let manifest_parsed: Option<Manifest> =
                 serde_json::from_value(manifest_value.clone()).ok();
 
+            let header_value = manifest_value.get("header");
+            let (display_name, name_unresolved) = resolve_pack_display_name(
+                &folder_path,
+                header_value
+                    .and_then(|h| h.get("name"))
+                    .and_then(Value::as_str),
+                header_value
+                    .and_then(|h| h.get("uuid"))
+                    .and_then(Value::as_str),
+                &folder_name,
+            );
+
             // 描述
             let short_description = manifest_parsed
                 .as_ref()
@@ -171,6 +190,8 @@ pub(crate) fn read_packs_standard(
 
             Some(McPackInfo {
                 folder_name,
+                display_name,
+                name_unresolved,
                 folder_path: folder_path.to_string_lossy().to_string(),
                 manifest: manifest_value,
                 manifest_raw,
@@ -217,7 +238,7 @@ fn collect_pack_folders_filtered(
     // 1. 隔离模式 (Isolation Mode)
     // =========================================================
     if let Some(iso_id) = isolation_id {
-        let versions_root = Path::new("./BMCBL/versions");
+        let versions_root = crate::utils::file_ops::versions_dir();
         let version_base = versions_root.join(iso_id).join("Minecraft Bedrock");
 
         if is_gdk {
@@ -259,44 +280,31 @@ fn collect_pack_folders_filtered(
     // 2. 系统模式 (System Mode)
     // =========================================================
     if is_uwp {
-        if let Ok(local_appdata) = std::env::var("LOCALAPPDATA") {
-            // 正式版
-            let uwp_root = PathBuf::from(&local_appdata)
-                .join("Packages")
-                .join("Microsoft.MinecraftUWP_8wekyb3d8bbwe")
-                .join("LocalState");
-            let uwp_base = uwp_root.join("games").join("com.mojang").join(kind);
-            if uwp_base.exists() && uwp_base.is_dir() {
-                roots.push((
-                    uwp_base,
-                    "UWP".into(),
-                    "正式版".into(),
-                    uwp_root.to_string_lossy().into(),
-                ));
-            }
-
-            // 预览版
-            let uwp_preview_root = PathBuf::from(&local_appdata)
-                .join("Packages")
-                .join("Microsoft.MinecraftWindowsBeta_8wekyb3d8bbwe")
-                .join("LocalState");
-            let uwp_preview_base = uwp_preview_root.join("games").join("com.mojang").join(kind);
-            if uwp_preview_base.exists() && uwp_preview_base.is_dir() {
-                roots.push((
-                    uwp_preview_base,
-                    "UWP".into(),
-                    "预览版".into(),
-                    uwp_preview_root.to_string_lossy().into(),
-                ));
+        if let Some(local_appdata) = crate::core::minecraft::paths::local_appdata_dir() {
+            for edition in known_editions() {
+                let uwp_root = local_appdata
+                    .join("Packages")
+                    .join(edition.uwp_data_dir_name())
+                    .join("LocalState");
+                let uwp_base = uwp_root.join("games").join("com.mojang").join(kind);
+                if uwp_base.exists() && uwp_base.is_dir() {
+                    roots.push((
+                        uwp_base,
+                        "UWP".into(),
+                        edition.display_name.clone(),
+                        uwp_root.to_string_lossy().into(),
+                    ));
+                }
             }
         }
     } else if is_gdk {
-        if let Ok(roaming) = std::env::var("APPDATA") {
-            for (candidate, edition_label) in &[
-                ("Minecraft Bedrock", "正式版"),
-                ("Minecraft Bedrock Preview", "预览版"),
-            ] {
-                let users_dir = PathBuf::from(&roaming).join(candidate).join("Users");
+        if let Some(roaming) = crate::core::minecraft::paths::roaming_appdata_dir() {
+            for edition in known_editions() {
+                let Some(gdk_data_dir) = edition.gdk_data_dir.as_deref() else {
+                    continue;
+                };
+                let edition_label = &edition.display_name;
+                let users_dir = roaming.join(gdk_data_dir).join("Users");
                 if users_dir.exists() && users_dir.is_dir() {
                     // GDK 下，资源包可能存在于具体用户目录下，也可能在 Shared 下
                     // 为了保险，扫描 Users 下所有子目录
@@ -439,48 +447,49 @@ fn strip_json_comments(input: &str) -> String {
     output
 }
 
-pub fn load_lang_map_for_pack(folder: &PathBuf, lang: &str) -> Option<HashMap<String, String>> {
-    let candidates_dirs = ["texts", "text", "lang"];
+pub(crate) fn parse_lang_file(path: &Path) -> Option<HashMap<String, String>> {
+    if let Ok(content) = fs::read_to_string(path) {
+        let mut map = HashMap::new();
+        // 处理 BOM
+        let content = content.strip_prefix('\u{feff}').unwrap_or(&content);
 
-    fn parse_lang_file(path: &PathBuf) -> Option<HashMap<String, String>> {
-        if let Ok(content) = fs::read_to_string(path) {
-            let mut map = HashMap::new();
-            // 处理 BOM
-            let content = content.strip_prefix('\u{feff}').unwrap_or(&content);
-
-            for line in content.lines() {
-                // [关键修改] 先按 # 分割，只取前部分，用于屏蔽行尾注释 (如 "key=val #comment")
-                let raw_line = if let Some((valid, _)) = line.split_once('#') {
-                    valid
-                } else {
-                    line
-                };
+        for line in content.lines() {
+            // [关键修改] 先按 # 分割，只取前部分，用于屏蔽行尾注释 (如 "key=val #comment")
+            let raw_line = if let Some((valid, _)) = line.split_once('#') {
+                valid
+            } else {
+                line
+            };
 
-                let line_str = raw_line.trim();
+            let line_str = raw_line.trim();
 
-                // 过滤无效行
-                if line_str.is_empty() || line_str.starts_with("//") || line_str.starts_with('[') {
-                    continue;
-                }
+            // 过滤无效行
+            if line_str.is_empty() || line_str.starts_with("//") || line_str.starts_with('[') {
+                continue;
+            }
 
-                // 解析 Key-Value
-                if let Some((k, val)) = line_str.split_once('=') {
-                    map.insert(k.trim().to_string(), val.trim().to_string());
-                } else if let Some((k, val)) = line_str.split_once(':') {
-                    map.insert(k.trim().to_string(), val.trim().to_string());
-                } else {
-                    // 尝试 Tab 分割兼容
-                    let parts: Vec<&str> = line_str.splitn(2, '\t').collect();
-                    if parts.len() == 2 {
-                        map.insert(parts[0].trim().to_string(), parts[1].trim().to_string());
-                    }
+            // 解析 Key-Value
+            if let Some((k, val)) = line_str.split_once('=') {
+                map.insert(k.trim().to_string(), val.trim().to_string());
+            } else if let Some((k, val)) = line_str.split_once(':') {
+                map.insert(k.trim().to_string(), val.trim().to_string());
+            } else {
+                // 尝试 Tab 分割兼容
+                let parts: Vec<&str> = line_str.splitn(2, '\t').collect();
+                if parts.len() == 2 {
+                    map.insert(parts[0].trim().to_string(), parts[1].trim().to_string());
                 }
             }
-            if map.is_empty() { None } else { Some(map) }
-        } else {
-            None
         }
+        if map.is_empty() { None } else { Some(map) }
+    } else {
+        None
     }
+}
+
+/// 列出包内 `texts/`、`text/`、`lang/` 下的全部 .lang 文件，返回 (路径, 文件名主干)
+pub(crate) fn pack_lang_files(folder: &Path) -> Vec<(PathBuf, String)> {
+    let candidates_dirs = ["texts", "text", "lang"];
 
     let mut lang_files: Vec<(PathBuf, String)> = Vec::new();
     for dir in &candidates_dirs {
@@ -505,6 +514,11 @@ pub fn load_lang_map_for_pack(folder: &PathBuf, lang: &str) -> Option<HashMap<St
             }
         }
     }
+    lang_files
+}
+
+pub fn load_lang_map_for_pack(folder: &PathBuf, lang: &str) -> Option<HashMap<String, String>> {
+    let lang_files = pack_lang_files(folder);
 
     if lang_files.is_empty() {
         return None;
@@ -606,3 +620,810 @@ fn short_desc_from_opt(raw: Option<String>, max_chars: usize) -> Option<String>
         }
     })
 }
+
+const DEFAULT_FILE_TREE_DEPTH: usize = 4;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileTreeNode {
+    pub name: String,
+    pub path: String,
+    pub is_dir: bool,
+    pub size_bytes: Option<u64>,
+    pub children: Vec<FileTreeNode>,
+}
+
+/// 返回包目录的文件树，目录排在文件之前，跳过隐藏文件和 `__MACOSX`
+pub fn get_pack_file_tree(
+    pack_dir: String,
+    max_depth: Option<usize>,
+) -> Result<FileTreeNode, String> {
+    let root = PathBuf::from(&pack_dir);
+    if !root.is_dir() {
+        return Err(format!("包目录不存在: {}", pack_dir));
+    }
+
+    // WalkDir 按深度优先先序输出，用栈按深度把节点挂到父节点上；目录大小在挂回父节点时
+    // 自下而上累加。超过 `max_depth` 的文件不建节点，只把大小计入最深一层的目录
+    let max_depth = max_depth.unwrap_or(DEFAULT_FILE_TREE_DEPTH);
+    let mut stack: Vec<FileTreeNode> = Vec::new();
+    let walker = WalkDir::new(&root)
+        .follow_links(false)
+        .sort_by(|left, right| {
+            right
+                .file_type()
+                .is_dir()
+                .cmp(&left.file_type().is_dir())
+                .then_with(|| {
+                    left.file_name()
+                        .to_ascii_lowercase()
+                        .cmp(&right.file_name().to_ascii_lowercase())
+                })
+        })
+        .into_iter()
+        .filter_entry(|entry| entry.depth() == 0 || !is_excluded_tree_entry(entry));
+
+    for entry in walker {
+        let entry = entry.map_err(|e| format!("遍历包目录失败: {}", e))?;
+        while stack.len() > entry.depth() {
+            attach_to_parent(&mut stack);
+        }
+
+        let is_dir = entry.file_type().is_dir();
+        let size_bytes = if is_dir {
+            Some(0)
+        } else {
+            entry.metadata().ok().map(|metadata| metadata.len())
+        };
+        if entry.depth() > max_depth {
+            if let Some(deepest) = stack.last_mut() {
+                add_size(deepest, size_bytes);
+            }
+            continue;
+        }
+        stack.push(FileTreeNode {
+            name: entry.file_name().to_string_lossy().to_string(),
+            path: entry.path().to_string_lossy().to_string(),
+            is_dir,
+            size_bytes,
+            children: Vec::new(),
+        });
+    }
+
+    while stack.len() > 1 {
+        attach_to_parent(&mut stack);
+    }
+    stack
+        .pop()
+        .ok_or_else(|| format!("读取包目录失败: {}", pack_dir))
+}
+
+fn add_size(node: &mut FileTreeNode, size_bytes: Option<u64>) {
+    node.size_bytes = Some(node.size_bytes.unwrap_or(0) + size_bytes.unwrap_or(0));
+}
+
+fn attach_to_parent(stack: &mut Vec<FileTreeNode>) {
+    if let Some(node) = stack.pop()
+        && let Some(parent) = stack.last_mut()
+    {
+        add_size(parent, node.size_bytes);
+        parent.children.push(node);
+    }
+}
+
+fn is_excluded_tree_entry(entry: &DirEntry) -> bool {
+    let name = entry.file_name().to_string_lossy();
+    name.starts_with('.') || name == "__MACOSX"
+}
+
+const ENTITIES_DIR_NAME: &str = "entities";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EntityEntry {
+    pub identifier: String,
+    pub is_spawnable: bool,
+    pub has_runtime_id: bool,
+    /// `components` 的键数量，粗略反映实体行为的复杂度
+    pub component_count: usize,
+}
+
+/// 列出行为包 `entities/` 下定义的自定义实体，按标识符排序
+pub fn list_behavior_pack_entities(pack_dir: String) -> Result<Vec<EntityEntry>, String> {
+    let entities_dir = Path::new(&pack_dir).join(ENTITIES_DIR_NAME);
+    if !entities_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let files: Vec<PathBuf> = WalkDir::new(&entities_dir)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+        })
+        .collect();
+
+    let mut entities: Vec<EntityEntry> = files
+        .par_iter()
+        .filter_map(|path| {
+            let raw = fs::read_to_string(path).ok()?;
+            let entry = parse_entity_entry(&raw);
+            if entry.is_none() {
+                debug!("跳过无法识别的实体文件: {}", path.display());
+            }
+            entry
+        })
+        .collect();
+    entities.sort_by(|left, right| left.identifier.cmp(&right.identifier));
+    Ok(entities)
+}
+
+fn parse_entity_entry(raw: &str) -> Option<EntityEntry> {
+    let value: Value =
+        serde_json::from_str(&strip_json_comments(raw.trim_start_matches('\u{feff}'))).ok()?;
+    let entity = value.get("minecraft:entity")?;
+    let description = entity.get("description")?;
+    let identifier = description.get("identifier")?.as_str()?.to_string();
+
+    Some(EntityEntry {
+        identifier,
+        is_spawnable: description
+            .get("is_spawnable")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+        has_runtime_id: description
+            .get("runtime_identifier")
+            .and_then(Value::as_str)
+            .is_some_and(|runtime_id| !runtime_id.is_empty()),
+        component_count: entity
+            .get("components")
+            .and_then(Value::as_object)
+            .map_or(0, |components| components.len()),
+    })
+}
+
+const PARTICLES_DIR_NAME: &str = "particles";
+/// 发射器生命周期组件与对应的类型名
+const EMITTER_LIFETIME_COMPONENTS: [(&str, &str); 3] = [
+    ("minecraft:emitter_lifetime_looping", "looping"),
+    ("minecraft:emitter_lifetime_once", "once"),
+    ("minecraft:emitter_lifetime_expression", "expression"),
+];
+const UNKNOWN_EMITTER_TYPE: &str = "unknown";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ParticleEntry {
+    pub identifier: String,
+    pub file_path: String,
+    /// `looping` / `once` / `expression`，没有生命周期组件时为 `unknown`
+    pub emitter_type: String,
+    pub texture_reference: Option<String>,
+}
+
+/// 列出资源包 `particles/` 下定义的自定义粒子，按标识符排序
+pub fn list_resource_pack_particle_effects(pack_dir: String) -> Result<Vec<ParticleEntry>, String> {
+    let particles_dir = Path::new(&pack_dir).join(PARTICLES_DIR_NAME);
+    if !particles_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let files: Vec<PathBuf> = WalkDir::new(&particles_dir)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+        })
+        .collect();
+
+    let mut particles: Vec<ParticleEntry> = files
+        .par_iter()
+        .filter_map(|path| {
+            let raw = fs::read_to_string(path).ok()?;
+            let entry = parse_particle_entry(&raw, &path.to_string_lossy());
+            if entry.is_none() {
+                debug!("跳过无法识别的粒子文件: {}", path.display());
+            }
+            entry
+        })
+        .collect();
+    particles.sort_by(|left, right| left.identifier.cmp(&right.identifier));
+    Ok(particles)
+}
+
+fn parse_particle_entry(raw: &str, file_path: &str) -> Option<ParticleEntry> {
+    let value: Value =
+        serde_json::from_str(&strip_json_comments(raw.trim_start_matches('\u{feff}'))).ok()?;
+    let effect = value.get("particle_effect")?;
+    let description = effect.get("description")?;
+    let identifier = description.get("identifier")?.as_str()?.to_string();
+    let components = effect.get("components");
+
+    let emitter_type = EMITTER_LIFETIME_COMPONENTS
+        .iter()
+        .find(|(component, _)| components.and_then(|c| c.get(component)).is_some())
+        .map_or(UNKNOWN_EMITTER_TYPE, |(_, emitter_type)| *emitter_type);
+    // 多数包把贴图写在 basic_render_parameters 里，billboard 上的 textures 没有时退回这里
+    let texture_reference = components
+        .and_then(|c| c.get("minecraft:particle_appearance_billboard"))
+        .and_then(|billboard| billboard.get("textures"))
+        .and_then(Value::as_str)
+        .or_else(|| {
+            description
+                .get("basic_render_parameters")
+                .and_then(|parameters| parameters.get("texture"))
+                .and_then(Value::as_str)
+        })
+        .map(ToString::to_string);
+
+    Some(ParticleEntry {
+        identifier,
+        file_path: file_path.to_string(),
+        emitter_type: emitter_type.to_string(),
+        texture_reference,
+    })
+}
+
+const BIOMES_DIR_NAME: &str = "biomes";
+/// 服务端（行为包）与客户端（资源包）生物群系定义的根键
+const BIOME_ROOT_KEYS: [&str; 2] = ["minecraft:biome", "minecraft:client_biome"];
+/// 原版生物群系标识符（不含 `minecraft:` 命名空间）
+const VANILLA_BIOME_IDENTIFIERS: &[&str] = &[
+    "bamboo_jungle",
+    "bamboo_jungle_hills",
+    "basalt_deltas",
+    "beach",
+    "birch_forest",
+    "birch_forest_hills",
+    "birch_forest_hills_mutated",
+    "birch_forest_mutated",
+    "cherry_grove",
+    "cold_beach",
+    "cold_ocean",
+    "cold_taiga",
+    "cold_taiga_hills",
+    "cold_taiga_mutated",
+    "crimson_forest",
+    "deep_cold_ocean",
+    "deep_dark",
+    "deep_frozen_ocean",
+    "deep_lukewarm_ocean",
+    "deep_ocean",
+    "deep_warm_ocean",
+    "desert",
+    "desert_hills",
+    "desert_mutated",
+    "dripstone_caves",
+    "extreme_hills",
+    "extreme_hills_edge",
+    "extreme_hills_mutated",
+    "extreme_hills_plus_trees",
+    "extreme_hills_plus_trees_mutated",
+    "flower_forest",
+    "forest",
+    "forest_hills",
+    "frozen_ocean",
+    "frozen_peaks",
+    "frozen_river",
+    "grove",
+    "hell",
+    "ice_mountains",
+    "ice_plains",
+    "ice_plains_spikes",
+    "jagged_peaks",
+    "jungle",
+    "jungle_edge",
+    "jungle_edge_mutated",
+    "jungle_hills",
+    "jungle_mutated",
+    "legacy_frozen_ocean",
+    "lukewarm_ocean",
+    "lush_caves",
+    "mangrove_swamp",
+    "meadow",
+    "mega_taiga",
+    "mega_taiga_hills",
+    "mesa",
+    "mesa_bryce",
+    "mesa_plateau",
+    "mesa_plateau_mutated",
+    "mesa_plateau_stone",
+    "mesa_plateau_stone_mutated",
+    "mushroom_island",
+    "mushroom_island_shore",
+    "ocean",
+    "pale_garden",
+    "plains",
+    "redwood_taiga_hills_mutated",
+    "redwood_taiga_mutated",
+    "river",
+    "roofed_forest",
+    "roofed_forest_mutated",
+    "savanna",
+    "savanna_mutated",
+    "savanna_plateau",
+    "savanna_plateau_mutated",
+    "snowy_slopes",
+    "soulsand_valley",
+    "stone_beach",
+    "stony_peaks",
+    "sunflower_plains",
+    "swampland",
+    "swampland_mutated",
+    "taiga",
+    "taiga_hills",
+    "taiga_mutated",
+    "the_end",
+    "warm_ocean",
+    "warped_forest",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BiomeEntry {
+    pub identifier: String,
+    pub has_fog: bool,
+    pub grass_color: Option<String>,
+    pub foliage_color: Option<String>,
+    /// 标识符与原版生物群系相同，即覆盖了原版定义
+    pub overrides_vanilla: bool,
+}
+
+/// 列出包内 `biomes/` 下定义或修改的生物群系，按标识符排序
+pub fn list_resource_pack_biomes(pack_dir: String) -> Result<Vec<BiomeEntry>, String> {
+    let biomes_dir = Path::new(&pack_dir).join(BIOMES_DIR_NAME);
+    if !biomes_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let files: Vec<PathBuf> = WalkDir::new(&biomes_dir)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+        })
+        .collect();
+
+    let mut biomes: Vec<BiomeEntry> = files
+        .par_iter()
+        .filter_map(|path| {
+            let raw = fs::read_to_string(path).ok()?;
+            let entry = parse_biome_entry(&raw);
+            if entry.is_none() {
+                debug!("跳过无法识别的生物群系文件: {}", path.display());
+            }
+            entry
+        })
+        .collect();
+    biomes.sort_by(|left, right| left.identifier.cmp(&right.identifier));
+    Ok(biomes)
+}
+
+fn is_vanilla_biome(identifier: &str) -> bool {
+    let name = identifier.strip_prefix("minecraft:").unwrap_or(identifier);
+    VANILLA_BIOME_IDENTIFIERS.contains(&name)
+}
+
+/// 颜色可能写成 `"#79C05A"` 或 `[121, 192, 90]`，统一为十六进制字符串
+fn json_color_hex(value: &Value) -> Option<String> {
+    match value {
+        Value::String(color) => Some(color.clone()),
+        Value::Array(channels) if channels.len() >= 3 => {
+            let mut hex = String::from("#");
+            for channel in &channels[..3] {
+                let channel = channel.as_u64().filter(|channel| *channel <= 255)?;
+                hex.push_str(&format!("{channel:02X}"));
+            }
+            Some(hex)
+        }
+        _ => None,
+    }
+}
+
+fn parse_biome_entry(raw: &str) -> Option<BiomeEntry> {
+    let value: Value =
+        serde_json::from_str(&strip_json_comments(raw.trim_start_matches('\u{feff}'))).ok()?;
+    let biome = BIOME_ROOT_KEYS.iter().find_map(|key| value.get(key))?;
+    let identifier = biome
+        .get("description")?
+        .get("identifier")?
+        .as_str()?
+        .to_string();
+    let components = biome.get("components");
+    let component = |name: &str| components.and_then(|c| c.get(name));
+
+    let has_fog = component("minecraft:fog_appearance")
+        .and_then(|fog| fog.get("fog_identifier"))
+        .is_some();
+    // 颜色优先读 surface_parameters，客户端生物群系则写在各自的 appearance 组件里
+    let surface = component("minecraft:surface_parameters");
+    let color = |surface_key: &str, appearance: &str| {
+        surface
+            .and_then(|surface| surface.get(surface_key))
+            .or_else(|| component(appearance).and_then(|appearance| appearance.get("color")))
+            .and_then(json_color_hex)
+    };
+
+    Some(BiomeEntry {
+        overrides_vanilla: is_vanilla_biome(&identifier),
+        grass_color: color("grass_color", "minecraft:grass_appearance"),
+        foliage_color: color("foliage_color", "minecraft:foliage_appearance"),
+        identifier,
+        has_fog,
+    })
+}
+
+const FOGS_DIR_NAME: &str = "fogs";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FogEntry {
+    pub identifier: String,
+    pub air_fog_color: Option<String>,
+    pub water_fog_color: Option<String>,
+    pub has_volumetric: bool,
+}
+
+/// 列出包内 `fogs/` 下的雾定义，按标识符排序
+pub fn list_resource_pack_fog_definitions(pack_dir: String) -> Result<Vec<FogEntry>, String> {
+    let fogs_dir = Path::new(&pack_dir).join(FOGS_DIR_NAME);
+    if !fogs_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let files: Vec<PathBuf> = WalkDir::new(&fogs_dir)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+        })
+        .collect();
+
+    let mut fogs: Vec<FogEntry> = files
+        .par_iter()
+        .filter_map(|path| {
+            let raw = fs::read_to_string(path).ok()?;
+            let entry = parse_fog_entry(&raw);
+            if entry.is_none() {
+                debug!("跳过无法识别的雾定义文件: {}", path.display());
+            }
+            entry
+        })
+        .collect();
+    fogs.sort_by(|left, right| left.identifier.cmp(&right.identifier));
+    Ok(fogs)
+}
+
+fn parse_fog_entry(raw: &str) -> Option<FogEntry> {
+    let value: Value =
+        serde_json::from_str(&strip_json_comments(raw.trim_start_matches('\u{feff}'))).ok()?;
+    let fog = value.get("minecraft:fog_settings")?;
+    let identifier = fog
+        .get("description")?
+        .get("identifier")?
+        .as_str()?
+        .to_string();
+    let distance = fog.get("distance");
+    let fog_color = |medium: &str| {
+        distance
+            .and_then(|distance| distance.get(medium))
+            .and_then(|medium| medium.get("fog_color"))
+            .and_then(json_color_hex)
+    };
+
+    Some(FogEntry {
+        air_fog_color: fog_color("air"),
+        water_fog_color: fog_color("water"),
+        has_volumetric: fog.get("volumetric").is_some(),
+        identifier,
+    })
+}
+
+/// 支持光影的分支客户端使用的着色器目录，包根目录和 `shaders/` 下都会查找
+const SHADER_DIR_NAMES: [&str; 3] = ["hlsl", "glsl", "shaderpacks"];
+/// 按文件名中以 `.`、`_`、`-` 分隔的片段判断着色器阶段，例如 `uv.vertex.hlsl`、`fs_sky.sc`
+const SHADER_TYPE_TOKENS: [(&str, &[&str]); 3] = [
+    ("vertex", &["vertex", "vert", "vsh", "vs"]),
+    (
+        "fragment",
+        &["fragment", "frag", "pixel", "fsh", "fs", "ps"],
+    ),
+    ("compute", &["compute", "comp", "csh", "cs"]),
+];
+const UNKNOWN_SHADER_TYPE: &str = "unknown";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ShaderEntry {
+    pub file_path: String,
+    /// `vertex` / `fragment` / `compute`，无法从文件名判断时为 `unknown`
+    pub shader_type: String,
+    pub file_size_bytes: u64,
+    /// 同目录下存在同名的 `.json` 配置文件
+    pub has_config: bool,
+}
+
+/// 列出包内 `hlsl/`、`glsl/`、`shaderpacks/` 下的着色器文件，按路径排序
+pub fn list_resource_pack_shaders(pack_dir: String) -> Result<Vec<ShaderEntry>, String> {
+    let pack_dir = Path::new(&pack_dir);
+    if !pack_dir.is_dir() {
+        return Err(format!("资源包目录不存在: {}", pack_dir.display()));
+    }
+
+    let mut shaders: Vec<ShaderEntry> = [pack_dir.to_path_buf(), pack_dir.join("shaders")]
+        .iter()
+        .flat_map(|root| SHADER_DIR_NAMES.iter().map(move |name| root.join(name)))
+        .filter(|dir| dir.is_dir())
+        .flat_map(|dir| {
+            WalkDir::new(dir)
+                .follow_links(false)
+                .into_iter()
+                .filter_map(|entry| entry.ok())
+        })
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| {
+            !entry
+                .path()
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+        })
+        .map(|entry| {
+            let path = entry.path();
+            let file_name = entry.file_name().to_string_lossy();
+            ShaderEntry {
+                file_path: path.to_string_lossy().into_owned(),
+                shader_type: shader_type_from_file_name(&file_name).to_string(),
+                file_size_bytes: entry.metadata().map(|metadata| metadata.len()).unwrap_or(0),
+                // `uv.vertex.hlsl` 对应 `uv.vertex.json`，不能用 with_extension
+                has_config: path.file_stem().is_some_and(|stem| {
+                    path.with_file_name(format!("{}.json", stem.to_string_lossy()))
+                        .is_file()
+                }),
+            }
+        })
+        .collect();
+    shaders.sort_by(|left, right| left.file_path.cmp(&right.file_path));
+    Ok(shaders)
+}
+
+fn shader_type_from_file_name(file_name: &str) -> &'static str {
+    let lower = file_name.to_ascii_lowercase();
+    let tokens: Vec<&str> = lower.split(['.', '_', '-']).collect();
+    SHADER_TYPE_TOKENS
+        .iter()
+        .find(|(_, markers)| tokens.iter().any(|token| markers.contains(token)))
+        .map_or(UNKNOWN_SHADER_TYPE, |(shader_type, _)| *shader_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack_file_tree_lists_directories_first_and_skips_hidden_entries() {
+        let pack_dir = std::env::temp_dir().join(format!(
+            "bmcbl-pack-tree-test-{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .expect("system time should be after unix epoch")
+                .as_nanos()
+        ));
+        fs::create_dir_all(pack_dir.join("textures/blocks")).expect("create textures dir");
+        fs::create_dir_all(pack_dir.join("__MACOSX")).expect("create macosx dir");
+        fs::write(pack_dir.join("manifest.json"), b"{}").expect("write manifest");
+        fs::write(pack_dir.join(".DS_Store"), b"").expect("write hidden file");
+        fs::write(pack_dir.join("textures/blocks/stone.png"), b"png!").expect("write texture");
+
+        let tree = get_pack_file_tree(pack_dir.to_string_lossy().to_string(), Some(1))
+            .expect("build tree");
+
+        let names: Vec<&str> = tree
+            .children
+            .iter()
+            .map(|node| node.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["textures", "manifest.json"]);
+        assert_eq!(tree.size_bytes, Some(6));
+        assert_eq!(tree.children[0].size_bytes, Some(4));
+        assert!(tree.children[0].children.is_empty());
+
+        fs::remove_dir_all(&pack_dir).expect("remove test dir");
+    }
+
+    #[test]
+    fn entity_entry_is_parsed_from_commented_behavior_file() {
+        let raw = r#"{
+            // 自定义实体
+            "format_version": "1.20.0",
+            "minecraft:entity": {
+                "description": {
+                    "identifier": "demo:golem",
+                    "is_spawnable": true,
+                    "runtime_identifier": "minecraft:iron_golem"
+                },
+                /* 组件 */
+                "components": { "minecraft:health": {}, "minecraft:movement": {} }
+            }
+        }"#;
+
+        assert_eq!(
+            parse_entity_entry(raw),
+            Some(EntityEntry {
+                identifier: "demo:golem".to_string(),
+                is_spawnable: true,
+                has_runtime_id: true,
+                component_count: 2,
+            })
+        );
+        assert_eq!(parse_entity_entry(r#"{"minecraft:item": {}}"#), None);
+    }
+
+    #[test]
+    fn particle_entry_reads_emitter_type_and_texture() {
+        let raw = r#"{
+            "format_version": "1.10.0",
+            // 火花
+            "particle_effect": {
+                "description": {
+                    "identifier": "demo:spark",
+                    "basic_render_parameters": {
+                        "material": "particles_alpha",
+                        "texture": "textures/particle/particles"
+                    }
+                },
+                "components": {
+                    "minecraft:emitter_lifetime_once": { "active_time": 1 },
+                    "minecraft:particle_appearance_billboard": { "size": [0.1, 0.1] }
+                }
+            }
+        }"#;
+
+        assert_eq!(
+            parse_particle_entry(raw, "particles/spark.json"),
+            Some(ParticleEntry {
+                identifier: "demo:spark".to_string(),
+                file_path: "particles/spark.json".to_string(),
+                emitter_type: "once".to_string(),
+                texture_reference: Some("textures/particle/particles".to_string()),
+            })
+        );
+
+        let looping = r#"{"particle_effect": {"description": {"identifier": "demo:smoke"},
+            "components": {"minecraft:emitter_lifetime_looping": {},
+            "minecraft:particle_appearance_billboard": {"textures": "textures/custom/smoke"}}}}"#;
+        let entry = parse_particle_entry(looping, "smoke.json").expect("parse looping particle");
+        assert_eq!(entry.emitter_type, "looping");
+        assert_eq!(
+            entry.texture_reference.as_deref(),
+            Some("textures/custom/smoke")
+        );
+        assert_eq!(
+            parse_particle_entry(r#"{"minecraft:entity": {}}"#, "x.json"),
+            None
+        );
+    }
+
+    #[test]
+    fn biome_entry_reads_fog_colors_and_vanilla_override() {
+        let raw = r##"{
+            "format_version": "1.13.0",
+            "minecraft:biome": {
+                "description": { "identifier": "demo:ash_fields" },
+                "components": {
+                    // 自定义雾
+                    "minecraft:fog_appearance": { "fog_identifier": "demo:ash_fog" },
+                    "minecraft:surface_parameters": {
+                        "top_material": "minecraft:grass",
+                        "grass_color": "#5A7F3B",
+                        "foliage_color": [64, 128, 255]
+                    }
+                }
+            }
+        }"##;
+        assert_eq!(
+            parse_biome_entry(raw),
+            Some(BiomeEntry {
+                identifier: "demo:ash_fields".to_string(),
+                has_fog: true,
+                grass_color: Some("#5A7F3B".to_string()),
+                foliage_color: Some("#4080FF".to_string()),
+                overrides_vanilla: false,
+            })
+        );
+
+        let client = r##"{"minecraft:client_biome": {"description": {"identifier": "minecraft:plains"},
+            "components": {"minecraft:grass_appearance": {"color": "#91BD59"}}}}"##;
+        let entry = parse_biome_entry(client).expect("parse client biome");
+        assert!(entry.overrides_vanilla);
+        assert!(!entry.has_fog);
+        assert_eq!(entry.grass_color.as_deref(), Some("#91BD59"));
+        assert_eq!(entry.foliage_color, None);
+        assert!(is_vanilla_biome("deep_dark"));
+        assert_eq!(parse_biome_entry(r#"{"minecraft:entity": {}}"#), None);
+    }
+
+    #[test]
+    fn fog_entry_reads_medium_colors_and_volumetric_flag() {
+        let raw = r##"{
+            "format_version": "1.16.100",
+            "minecraft:fog_settings": {
+                "description": { "identifier": "demo:ash_fog" },
+                /* 水下沿用原版 */
+                "distance": {
+                    "air": { "fog_start": 0.2, "fog_end": 0.8, "fog_color": "#ABD2FF" },
+                    "water": { "fog_start": 0, "fog_end": 60, "fog_color": [68, 175, 245] }
+                },
+                "volumetric": { "density": { "air": { "max_density": 0.2 } } }
+            }
+        }"##;
+        assert_eq!(
+            parse_fog_entry(raw),
+            Some(FogEntry {
+                identifier: "demo:ash_fog".to_string(),
+                air_fog_color: Some("#ABD2FF".to_string()),
+                water_fog_color: Some("#44AFF5".to_string()),
+                has_volumetric: true,
+            })
+        );
+
+        let plain = r#"{"minecraft:fog_settings": {"description": {"identifier": "demo:plain"}}}"#;
+        let entry = parse_fog_entry(plain).expect("parse fog without distance");
+        assert!(!entry.has_volumetric);
+        assert_eq!(entry.air_fog_color, None);
+        assert_eq!(parse_fog_entry(r#"{"minecraft:biome": {}}"#), None);
+    }
+
+    #[test]
+    fn shader_entries_detect_stage_and_config() {
+        let pack_dir =
+            std::env::temp_dir().join(format!("bmcbl-pack-shaders-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(pack_dir.join("shaders/hlsl")).expect("create hlsl dir");
+        fs::create_dir_all(pack_dir.join("glsl")).expect("create glsl dir");
+        fs::create_dir_all(pack_dir.join("shaderpacks/demo")).expect("create shaderpacks dir");
+        fs::write(pack_dir.join("shaders/hlsl/uv.vertex.hlsl"), b"vs").expect("write vertex");
+        fs::write(pack_dir.join("shaders/hlsl/uv.json"), b"{}").expect("write unrelated json");
+        fs::write(pack_dir.join("glsl/sky_fs.glsl"), b"frag!").expect("write fragment");
+        fs::write(pack_dir.join("glsl/sky_fs.json"), b"{}").expect("write config");
+        fs::write(pack_dir.join("shaderpacks/demo/bloom.comp"), b"c").expect("write compute");
+        fs::write(pack_dir.join("shaderpacks/demo/common.inc"), b"").expect("write include");
+
+        let shaders =
+            list_resource_pack_shaders(pack_dir.to_string_lossy().to_string()).expect("list");
+        let summary: Vec<(String, &str, u64, bool)> = shaders
+            .iter()
+            .map(|entry| {
+                (
+                    Path::new(&entry.file_path)
+                        .file_name()
+                        .expect("file name")
+                        .to_string_lossy()
+                        .into_owned(),
+                    entry.shader_type.as_str(),
+                    entry.file_size_bytes,
+                    entry.has_config,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("sky_fs.glsl".to_string(), "fragment", 5, true),
+                ("bloom.comp".to_string(), "compute", 1, false),
+                ("common.inc".to_string(), "unknown", 0, false),
+                ("uv.vertex.hlsl".to_string(), "vertex", 2, false),
+            ]
+        );
+
+        fs::remove_dir_all(&pack_dir).expect("remove test dir");
+    }
+}
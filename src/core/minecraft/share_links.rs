@@ -0,0 +1,385 @@
+//! 网盘分享链接解析：把 OneDrive / Google Drive / Dropbox 的分享页链接改写为直链，
+//! 其余链接跟随跳转后检查内容类型，指向网页时返回 `not_a_direct_download`。
+//! 新网盘实现 `ShareLinkProvider` 并加入 `PROVIDERS` 即可。
+
+use crate::http::proxy::get_client_for_proxy;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use reqwest::header::CONTENT_TYPE;
+use tracing::debug;
+use url::Url;
+
+/// 指向网页而不是文件时的错误前缀，UI 按此识别
+pub const NOT_A_DIRECT_DOWNLOAD: &str = "not_a_direct_download";
+/// 确认页只需读前面一小段
+const MAX_CONFIRM_PAGE_BYTES: usize = 512 * 1024;
+
+pub(crate) trait ShareLinkProvider: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn matches(&self, url: &Url) -> bool;
+
+    /// 改写为直链；短链等需要先跟随跳转才能改写的返回 `None`
+    fn rewrite(&self, url: &Url) -> Option<Url>;
+
+    /// 直链返回了网页时（如大文件的病毒扫描确认页），从网页中取出真正的下载地址
+    fn confirm_url(&self, _page_url: &Url, _html: &str) -> Option<Url> {
+        None
+    }
+}
+
+struct OneDrive;
+
+impl ShareLinkProvider for OneDrive {
+    fn name(&self) -> &'static str {
+        "onedrive"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        url.host_str().is_some_and(|host| {
+            host.eq_ignore_ascii_case("1drv.ms")
+                || host.eq_ignore_ascii_case("onedrive.live.com")
+                || host.to_ascii_lowercase().ends_with(".sharepoint.com")
+        })
+    }
+
+    fn rewrite(&self, url: &Url) -> Option<Url> {
+        let host = url.host_str()?.to_ascii_lowercase();
+        if host == "1drv.ms" {
+            return None;
+        }
+        if host.ends_with(".sharepoint.com") {
+            return Some(with_query_param(url, "download", "1"));
+        }
+        // onedrive.live.com/redir|embed?resid=..&authkey=.. → /download?同样的参数
+        let mut rewritten = url.clone();
+        if matches!(url.path(), "/redir" | "/embed" | "/view.aspx" | "/") {
+            rewritten.set_path("/download");
+        }
+        Some(rewritten)
+    }
+}
+
+struct GoogleDrive;
+
+impl GoogleDrive {
+    fn file_id(url: &Url) -> Option<String> {
+        let mut segments = url.path_segments()?;
+        if segments.next() == Some("file") && segments.next() == Some("d") {
+            return segments.next().map(str::to_string);
+        }
+        query_param(url, "id")
+    }
+}
+
+impl ShareLinkProvider for GoogleDrive {
+    fn name(&self) -> &'static str {
+        "google_drive"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        url.host_str().is_some_and(|host| {
+            [
+                "drive.google.com",
+                "docs.google.com",
+                "drive.usercontent.google.com",
+            ]
+            .iter()
+            .any(|known| host.eq_ignore_ascii_case(known))
+        })
+    }
+
+    fn rewrite(&self, url: &Url) -> Option<Url> {
+        let id = Self::file_id(url).filter(|id| !id.is_empty())?;
+        Url::parse_with_params(
+            "https://drive.usercontent.google.com/download",
+            [("id", id.as_str()), ("export", "download")],
+        )
+        .ok()
+    }
+
+    /// 大文件会先返回"无法扫描病毒"确认页，其中的表单带有 confirm / uuid 等隐藏字段
+    fn confirm_url(&self, page_url: &Url, html: &str) -> Option<Url> {
+        static FORM_ACTION: Lazy<Option<Regex>> =
+            Lazy::new(|| Regex::new(r#"<form[^>]*id="download-form"[^>]*action="([^"]+)""#).ok());
+        static HIDDEN_INPUT: Lazy<Option<Regex>> = Lazy::new(|| {
+            Regex::new(r#"<input[^>]*type="hidden"[^>]*name="([^"]+)"[^>]*value="([^"]*)""#).ok()
+        });
+        static LEGACY_CONFIRM: Lazy<Option<Regex>> =
+            Lazy::new(|| Regex::new(r"confirm=([0-9A-Za-z_-]+)").ok());
+
+        if let Some(action) = FORM_ACTION.as_ref()?.captures(html) {
+            let hidden_input = HIDDEN_INPUT.as_ref()?;
+            let mut confirm = page_url.join(&action[1].replace("&amp;", "&")).ok()?;
+            {
+                let mut query = confirm.query_pairs_mut();
+                query.clear();
+                for input in hidden_input.captures_iter(html) {
+                    query.append_pair(&input[1], &input[2]);
+                }
+            }
+            return Some(confirm);
+        }
+        // 旧版确认页只有一个带 confirm 参数的链接
+        let token = LEGACY_CONFIRM.as_ref()?.captures(html)?;
+        let mut confirm = page_url.clone();
+        confirm.query_pairs_mut().append_pair("confirm", &token[1]);
+        Some(confirm)
+    }
+}
+
+struct Dropbox;
+
+impl ShareLinkProvider for Dropbox {
+    fn name(&self) -> &'static str {
+        "dropbox"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        url.host_str().is_some_and(|host| {
+            host.eq_ignore_ascii_case("dropbox.com") || host.eq_ignore_ascii_case("www.dropbox.com")
+        })
+    }
+
+    fn rewrite(&self, url: &Url) -> Option<Url> {
+        Some(with_query_param(url, "dl", "1"))
+    }
+}
+
+static PROVIDERS: [&dyn ShareLinkProvider; 3] = [&OneDrive, &GoogleDrive, &Dropbox];
+
+fn provider_for(url: &Url) -> Option<&'static dyn ShareLinkProvider> {
+    PROVIDERS
+        .iter()
+        .copied()
+        .find(|provider| provider.matches(url))
+}
+
+fn query_param(url: &Url, key: &str) -> Option<String> {
+    url.query_pairs()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value.into_owned())
+}
+
+/// 设置查询参数，已存在时替换（如 Dropbox 的 `dl=0`）
+fn with_query_param(url: &Url, key: &str, value: &str) -> Url {
+    let pairs = url
+        .query_pairs()
+        .filter(|(name, _)| name != key)
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect::<Vec<_>>();
+    let mut rewritten = url.clone();
+    rewritten
+        .query_pairs_mut()
+        .clear()
+        .extend_pairs(pairs)
+        .append_pair(key, value);
+    rewritten
+}
+
+fn is_html(content_type: &str) -> bool {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    mime == "text/html" || mime == "application/xhtml+xml"
+}
+
+fn not_a_direct_download(content_type: &str) -> String {
+    format!("{NOT_A_DIRECT_DOWNLOAD}: 链接指向网页而不是文件（最终内容类型 {content_type}）")
+}
+
+/// 解析出的直链；`content_length` 取自最终响应头，服务器未提供时为 `None`
+#[derive(Debug, Clone)]
+pub struct DirectDownload {
+    pub url: Url,
+    pub content_length: Option<u64>,
+}
+
+struct Probe {
+    final_url: Url,
+    content_type: String,
+    response: reqwest::Response,
+}
+
+/// 跟随跳转请求链接，只读响应头；需要时再由调用方读取正文
+async fn probe(client: &reqwest::Client, url: &Url) -> Result<Probe, String> {
+    let response = client
+        .get(url.clone())
+        .send()
+        .await
+        .map_err(|e| format!("请求链接失败: {e}"))?;
+    if !response.status().is_success() {
+        return Err(format!("链接返回错误状态: {}", response.status()));
+    }
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("unknown")
+        .to_string();
+    Ok(Probe {
+        final_url: response.url().clone(),
+        content_type,
+        response,
+    })
+}
+
+async fn read_page(mut response: reqwest::Response) -> Result<String, String> {
+    let mut page = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("读取确认页失败: {e}"))?
+    {
+        page.extend_from_slice(&chunk);
+        if page.len() >= MAX_CONFIRM_PAGE_BYTES {
+            break;
+        }
+    }
+    Ok(String::from_utf8_lossy(&page).into_owned())
+}
+
+impl Probe {
+    fn into_direct(self) -> DirectDownload {
+        DirectDownload {
+            content_length: self.response.content_length(),
+            url: self.final_url,
+        }
+    }
+}
+
+/// 把分享链接解析为可直接下载的地址；返回跟随跳转后的最终地址
+pub async fn resolve_direct_download_url(url: &Url) -> Result<DirectDownload, String> {
+    let client = get_client_for_proxy().map_err(|e| e.to_string())?;
+    let mut provider = provider_for(url);
+    let mut candidate = url.clone();
+    if let Some(matched) = provider {
+        match matched.rewrite(url) {
+            Some(rewritten) => candidate = rewritten,
+            None => {
+                // 短链：先展开，再按展开后的地址重新匹配
+                let expanded = probe(&client, url).await?.final_url;
+                provider = provider_for(&expanded);
+                candidate = provider
+                    .and_then(|provider| provider.rewrite(&expanded))
+                    .unwrap_or(expanded);
+            }
+        }
+        debug!(provider = matched.name(), url = %candidate, "已改写分享链接");
+    }
+
+    let probed = probe(&client, &candidate).await?;
+    if !is_html(&probed.content_type) {
+        return Ok(probed.into_direct());
+    }
+    let Some(provider) = provider else {
+        return Err(not_a_direct_download(&probed.content_type));
+    };
+    let page_url = probed.final_url.clone();
+    let page = read_page(probed.response).await?;
+    let Some(confirm) = provider.confirm_url(&page_url, &page) else {
+        return Err(not_a_direct_download(&probed.content_type));
+    };
+    let confirmed = probe(&client, &confirm).await?;
+    if is_html(&confirmed.content_type) {
+        return Err(not_a_direct_download(&confirmed.content_type));
+    }
+    Ok(confirmed.into_direct())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rewrite(url: &str) -> Option<String> {
+        let url = Url::parse(url).expect("parse test url");
+        provider_for(&url)?.rewrite(&url).map(String::from)
+    }
+
+    #[test]
+    fn onedrive_links_become_download_links() {
+        assert_eq!(
+            rewrite("https://onedrive.live.com/redir?resid=ABC%21123&authkey=%21XYZ"),
+            Some("https://onedrive.live.com/download?resid=ABC%21123&authkey=%21XYZ".to_string())
+        );
+        assert_eq!(
+            rewrite("https://contoso-my.sharepoint.com/:u:/g/personal/a/EaBc?e=xyz"),
+            Some(
+                "https://contoso-my.sharepoint.com/:u:/g/personal/a/EaBc?e=xyz&download=1"
+                    .to_string()
+            )
+        );
+        // 短链需要先跟随跳转
+        assert_eq!(rewrite("https://1drv.ms/u/s!AbCdEf"), None);
+    }
+
+    #[test]
+    fn google_drive_links_become_usercontent_downloads() {
+        let expected = Some(
+            "https://drive.usercontent.google.com/download?id=1AbC-xyz_9&export=download"
+                .to_string(),
+        );
+        assert_eq!(
+            rewrite("https://drive.google.com/file/d/1AbC-xyz_9/view?usp=sharing"),
+            expected
+        );
+        assert_eq!(
+            rewrite("https://drive.google.com/open?id=1AbC-xyz_9"),
+            expected
+        );
+        assert_eq!(
+            rewrite("https://drive.google.com/uc?export=download&id=1AbC-xyz_9"),
+            expected
+        );
+        assert_eq!(rewrite("https://drive.google.com/drive/folders"), None);
+    }
+
+    #[test]
+    fn google_drive_confirm_page_yields_confirmed_download() {
+        let page_url =
+            Url::parse("https://drive.usercontent.google.com/download?id=1AbC&export=download")
+                .expect("parse page url");
+        let html = r#"<html><body><form id="download-form" action="https://drive.usercontent.google.com/download" method="get"><input type="submit" id="uc-download-link" value="Download anyway"/><input type="hidden" name="id" value="1AbC"><input type="hidden" name="export" value="download"><input type="hidden" name="confirm" value="t"><input type="hidden" name="uuid" value="0f1e-2d3c"></form></body></html>"#;
+        assert_eq!(
+            GoogleDrive.confirm_url(&page_url, html).map(String::from),
+            Some(
+                "https://drive.usercontent.google.com/download?id=1AbC&export=download&confirm=t&uuid=0f1e-2d3c"
+                    .to_string()
+            )
+        );
+
+        let legacy_url = Url::parse("https://drive.google.com/uc?id=1AbC&export=download")
+            .expect("parse legacy url");
+        let legacy = r#"<a id="uc-download-link" href="/uc?export=download&amp;confirm=Xy_9&amp;id=1AbC">Download anyway</a>"#;
+        assert_eq!(
+            GoogleDrive
+                .confirm_url(&legacy_url, legacy)
+                .map(String::from),
+            Some("https://drive.google.com/uc?id=1AbC&export=download&confirm=Xy_9".to_string())
+        );
+        assert_eq!(GoogleDrive.confirm_url(&legacy_url, "<html></html>"), None);
+    }
+
+    #[test]
+    fn dropbox_links_switch_to_direct_download() {
+        assert_eq!(
+            rewrite("https://www.dropbox.com/s/abc123/world.mcworld?dl=0"),
+            Some("https://www.dropbox.com/s/abc123/world.mcworld?dl=1".to_string())
+        );
+        assert_eq!(
+            rewrite("https://www.dropbox.com/scl/fi/abc/pack.mcpack?rlkey=k&dl=0"),
+            Some("https://www.dropbox.com/scl/fi/abc/pack.mcpack?rlkey=k&dl=1".to_string())
+        );
+        assert_eq!(rewrite("https://example.com/pack.mcpack"), None);
+    }
+
+    #[test]
+    fn html_content_types_are_rejected() {
+        assert!(is_html("text/html; charset=utf-8"));
+        assert!(!is_html("application/octet-stream"));
+        assert!(not_a_direct_download("text/html").starts_with(NOT_A_DIRECT_DOWNLOAD));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::core::cache_registry::{CacheDescriptor, clear_dir, dir_usage};
 use anyhow::{Context as _, Result};
 use image::imageops::{self, FilterType};
 use image::{DynamicImage, GenericImageView as _, ImageFormat, ImageReader, RgbaImage};
@@ -45,6 +46,20 @@ pub(crate) fn skin_texture_dimensions(texture_path: &Path) -> Result<(u32, u32)>
         .with_context(|| format!("读取皮肤纹理尺寸失败: {}", texture_path.display()))
 }
 
+/// 皮肤头像预览图的磁盘缓存目录
+pub(crate) fn preview_cache_dir() -> PathBuf {
+    crate::utils::file_ops::cache_subdir("skin_previews")
+}
+
+pub(crate) static CACHE: CacheDescriptor = CacheDescriptor {
+    name: "skin_previews",
+    label: "皮肤预览缩略图",
+    default_selected: true,
+    usage: || dir_usage(&preview_cache_dir()),
+    clear: || clear_dir(&preview_cache_dir()),
+    warm: None,
+};
+
 fn preview_cache_path(texture_path: &Path, variant: &str) -> Result<PathBuf> {
     let mut hasher = DefaultHasher::new();
     variant.hash(&mut hasher);
@@ -57,7 +72,7 @@ fn preview_cache_path(texture_path: &Path, variant: &str) -> Result<PathBuf> {
         duration.as_nanos().hash(&mut hasher);
     }
     let file_name = format!("{:016x}.png", hasher.finish());
-    Ok(crate::utils::file_ops::cache_subdir("skin_previews").join(file_name))
+    Ok(preview_cache_dir().join(file_name))
 }
 
 fn build_skin_preview_image(source: &DynamicImage) -> Result<RgbaImage> {
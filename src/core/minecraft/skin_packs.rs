@@ -7,6 +7,7 @@ use std::fs;
 use std::path::Path;
 use tracing::{debug, warn};
 
+use crate::core::minecraft::pack_names::clean_display_name;
 use crate::core::minecraft::paths::{GamePathOptions, GameTargetDir, game_target_dirs};
 use crate::core::minecraft::resource_packs::{Header, Module, load_lang_map_for_pack};
 use crate::core::minecraft::skin_pack_preview::generate_skin_preview;
@@ -331,6 +332,7 @@ fn pack_display_name(
                 .and_then(|name| lang_map.get(&format!("skinpack.{name}")).cloned())
         })
         .or_else(|| manifest_header.and_then(|header| header.name.clone()))
+        .map(|name| clean_display_name(&name))
         .filter(|name| !name.trim().is_empty())
         .unwrap_or_else(|| folder_name.to_string())
 }
@@ -0,0 +1,189 @@
+//! 资源包 UI 定义：列出 `ui/` 下的 JSON UI 文件及其命名空间，标记覆盖原版界面的文件。
+
+use crate::core::minecraft::import::strip_json_comments;
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+use walkdir::WalkDir;
+
+const UI_DIR_NAME: &str = "ui";
+
+/// 原版 UI 使用的命名空间，资源包使用同名命名空间时会合并或覆盖原版界面
+const VANILLA_UI_NAMESPACES: &[&str] = &[
+    "achievement",
+    "anvil",
+    "beacon",
+    "book",
+    "brewing_stand",
+    "chat",
+    "chest",
+    "common",
+    "common_buttons",
+    "common_dialogs",
+    "common_store",
+    "common_tabs",
+    "common_toggles",
+    "crafting",
+    "death",
+    "enchanting",
+    "furnace",
+    "hud",
+    "inventory",
+    "inventory_screen",
+    "loading",
+    "pause",
+    "play",
+    "progress",
+    "scoreboard",
+    "settings",
+    "start",
+    "toast_screen",
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UiDefinitionEntry {
+    /// 相对包根目录、以 `/` 分隔的路径
+    pub file_path: String,
+    pub namespace: String,
+    pub element_count: usize,
+    pub overrides_vanilla: bool,
+}
+
+/// 列出资源包中的 UI 定义文件
+pub async fn list_resource_pack_ui_definitions(
+    pack_dir: String,
+) -> Result<Vec<UiDefinitionEntry>, String> {
+    let pack_dir = PathBuf::from(pack_dir);
+    tokio::task::spawn_blocking(move || list_ui_definitions_blocking(&pack_dir))
+        .await
+        .map_err(|e| format!("读取 UI 定义任务失败: {}", e))?
+}
+
+fn relative_key(pack_dir: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(pack_dir).unwrap_or(path);
+    let parts: Vec<String> = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    parts.join("/")
+}
+
+/// 解析单个 UI 文件，返回 (命名空间, 顶层元素数)；没有命名空间的文件不是 UI 定义
+fn parse_ui_definition(content: &str) -> Result<Option<(String, usize)>, String> {
+    let value: Value =
+        serde_json::from_str(&strip_json_comments(content.trim_start_matches('\u{feff}')))
+            .map_err(|e| e.to_string())?;
+    let Some(object) = value.as_object() else {
+        return Ok(None);
+    };
+    let Some(namespace) = object.get("namespace").and_then(Value::as_str) else {
+        return Ok(None);
+    };
+    let element_count = object.keys().filter(|key| *key != "namespace").count();
+    Ok(Some((namespace.to_string(), element_count)))
+}
+
+fn list_ui_definitions_blocking(pack_dir: &Path) -> Result<Vec<UiDefinitionEntry>, String> {
+    if !pack_dir.is_dir() {
+        return Err(format!("资源包目录不存在: {}", pack_dir.display()));
+    }
+    let ui_dir = pack_dir.join(UI_DIR_NAME);
+    if !ui_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries = Vec::new();
+    for entry in WalkDir::new(&ui_dir)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+    {
+        let path = entry.path();
+        let is_json = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+        if !entry.file_type().is_file() || !is_json {
+            continue;
+        }
+
+        let content = match fs::read(path) {
+            Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+            Err(e) => {
+                warn!("读取 UI 文件失败 {}: {}", path.display(), e);
+                continue;
+            }
+        };
+        // _ui_defs.json、_global_variables.json 等没有命名空间，会在这里被跳过
+        let (namespace, element_count) = match parse_ui_definition(&content) {
+            Ok(Some(parsed)) => parsed,
+            Ok(None) => continue,
+            Err(e) => {
+                warn!("解析 UI 文件失败 {}: {}", path.display(), e);
+                continue;
+            }
+        };
+
+        entries.push(UiDefinitionEntry {
+            file_path: relative_key(pack_dir, path),
+            overrides_vanilla: VANILLA_UI_NAMESPACES.contains(&namespace.as_str()),
+            namespace,
+            element_count,
+        });
+    }
+    entries.sort_by(|left, right| left.file_path.cmp(&right.file_path));
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ui_files_report_namespace_and_vanilla_overrides() {
+        let pack_dir =
+            std::env::temp_dir().join(format!("bmcbl-ui-definitions-{}", uuid::Uuid::new_v4()));
+        let ui_dir = pack_dir.join(UI_DIR_NAME);
+        fs::create_dir_all(ui_dir.join("custom")).expect("create ui dir");
+        fs::write(
+            ui_dir.join("hud_screen.json"),
+            "// comment\n{\"namespace\": \"hud\", \"root_panel\": {}, \"hotbar@common.panel\": {}}",
+        )
+        .expect("write hud");
+        fs::write(
+            ui_dir.join("custom").join("my_screen.json"),
+            "{\"namespace\": \"my_pack\", \"screen\": {}}",
+        )
+        .expect("write custom");
+        fs::write(
+            ui_dir.join("_ui_defs.json"),
+            "{\"ui_defs\": [\"ui/custom/my_screen.json\"]}",
+        )
+        .expect("write ui defs");
+        fs::write(ui_dir.join("broken.json"), "{").expect("write broken");
+
+        let entries = list_ui_definitions_blocking(&pack_dir).expect("list ui definitions");
+
+        let summary: Vec<_> = entries
+            .iter()
+            .map(|entry| {
+                (
+                    entry.file_path.as_str(),
+                    entry.namespace.as_str(),
+                    entry.element_count,
+                    entry.overrides_vanilla,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("ui/custom/my_screen.json", "my_pack", 1, false),
+                ("ui/hud_screen.json", "hud", 2, true),
+            ]
+        );
+
+        fs::remove_dir_all(&pack_dir).expect("remove test dir");
+    }
+}
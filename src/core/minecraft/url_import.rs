@@ -0,0 +1,431 @@
+//! 从 URL 导入资源包：下载到临时目录 → 校验 zip 签名 → 生成预览并通过事件通知 UI，
+//! 用户确认后按 `import_files_batch` 导入。两阶段之间用 token 关联，确认时不会重新下载；
+//! 取消、拒绝、成功都会删除临时文件。网盘分享链接会先经 `share_links` 解析为直链。
+
+use crate::core::minecraft::import::{PackagePreview, import_files_batch, inspect_archive};
+use crate::core::minecraft::paths::GamePathOptions;
+use crate::core::minecraft::share_links::resolve_direct_download_url;
+use crate::tasks::task_manager::{cancel_task, get_snapshot, subscribe_task_updates};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tracing::{debug, warn};
+
+/// 单个 URL 导入允许的最大文件大小
+const MAX_URL_IMPORT_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+/// 预览后未确认的导入在该时间后清理
+const PENDING_IMPORT_TTL: Duration = Duration::from_secs(30 * 60);
+const ZIP_LOCAL_HEADER_SIGNATURE: &[u8; 4] = b"PK\x03\x04";
+const DEFAULT_DOWNLOAD_NAME: &str = "download.mcpack";
+const ARCHIVE_EXTENSIONS: [&str; 5] = ["mcpack", "mcaddon", "mcworld", "mctemplate", "zip"];
+
+#[derive(Debug, Clone)]
+pub struct UrlImportPreview {
+    pub token: String,
+    pub url: String,
+    pub preview: PackagePreview,
+}
+
+struct PendingUrlImport {
+    download_task_id: Option<String>,
+    file_path: Option<PathBuf>,
+    options: GamePathOptions,
+    overwrite: bool,
+    created_at: Instant,
+}
+
+static PENDING_IMPORTS: Lazy<Mutex<HashMap<String, PendingUrlImport>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// `url-import-preview` 事件：下载并解析完成、等待用户确认时广播
+static URL_IMPORT_EVENTS: Lazy<broadcast::Sender<UrlImportPreview>> = Lazy::new(|| {
+    let (tx, _rx) = broadcast::channel(8);
+    tx
+});
+
+pub fn subscribe_url_import_events() -> broadcast::Receiver<UrlImportPreview> {
+    URL_IMPORT_EVENTS.subscribe()
+}
+
+/// 第一阶段：下载并生成预览，返回用于确认 / 取消的 token
+pub async fn import_from_url(
+    url: String,
+    options: GamePathOptions,
+    overwrite: bool,
+) -> Result<UrlImportPreview, String> {
+    let url = url.trim().to_string();
+    let parsed = url::Url::parse(&url).map_err(|e| format!("无效的链接: {e}"))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err("仅支持 http / https 链接".to_string());
+    }
+    // 网盘分享链接先改写为直链，网页链接在这里就会被拒绝
+    let direct = resolve_direct_download_url(&parsed).await?;
+    // 服务器声明的大小超限时不开始下载
+    if let Some(size) = direct.content_length
+        && size > MAX_URL_IMPORT_BYTES
+    {
+        return Err(too_large_error(size));
+    }
+
+    prune_expired_imports();
+    let token = uuid::Uuid::new_v4().to_string();
+    let file_name = format!("{token}-{}", download_file_name(&direct.url));
+    with_pending(|pending| {
+        pending.insert(
+            token.clone(),
+            PendingUrlImport {
+                download_task_id: None,
+                file_path: None,
+                options,
+                overwrite,
+                created_at: Instant::now(),
+            },
+        );
+    })?;
+
+    match download_and_inspect(&token, direct.url.as_str(), file_name).await {
+        Ok(preview) => {
+            let preview = UrlImportPreview {
+                token,
+                url,
+                preview,
+            };
+            if URL_IMPORT_EVENTS.send(preview.clone()).is_err() {
+                debug!("url-import-preview 事件没有订阅者");
+            }
+            Ok(preview)
+        }
+        Err(error) => {
+            discard_pending(&token);
+            Err(error)
+        }
+    }
+}
+
+/// 第二阶段：确认导入，返回 (成功数, 失败数)
+pub async fn confirm_url_import(token: String) -> Result<(usize, usize), String> {
+    let (pending, file_path) = with_pending(|pending| {
+        let file_path = pending
+            .get(&token)
+            .ok_or_else(|| "导入已过期或已取消".to_string())?
+            .file_path
+            .clone()
+            .ok_or_else(|| "文件尚未下载完成".to_string())?;
+        pending
+            .remove(&token)
+            .map(|entry| (entry, file_path))
+            .ok_or_else(|| "导入已过期或已取消".to_string())
+    })??;
+
+    tokio::task::spawn_blocking(move || {
+        let result = import_files_batch(
+            vec![file_path.to_string_lossy().to_string()],
+            &pending.options,
+            pending.overwrite,
+            None,
+        )
+        .map_err(|e| e.to_string());
+        remove_download(&file_path);
+        result
+    })
+    .await
+    .map_err(|e| format!("导入任务失败: {e}"))?
+}
+
+/// 取消或拒绝导入：下载中则取消下载任务，已下载则删除临时文件
+pub fn cancel_url_import(token: &str) -> Result<(), String> {
+    if !discard_pending(token) {
+        return Err("导入已过期或已完成".to_string());
+    }
+    Ok(())
+}
+
+async fn download_and_inspect(
+    token: &str,
+    url: &str,
+    file_name: String,
+) -> Result<PackagePreview, String> {
+    let task_id =
+        crate::downloads::api::download_resource_to_cache(url.to_string(), file_name, None, None)
+            .await?;
+    let cancelled = with_pending(|pending| match pending.get_mut(token) {
+        Some(entry) => {
+            entry.download_task_id = Some(task_id.clone());
+            false
+        }
+        None => true,
+    })?;
+    if cancelled {
+        cancel_task(&task_id);
+        return Err("导入已取消".to_string());
+    }
+
+    let downloaded = wait_download_finished(&task_id).await?;
+    // 路径此时还没记入待确认条目，discard_pending 删不到它
+    let file_path =
+        ensure_archive_extension(&downloaded).inspect_err(|_| remove_download(&downloaded))?;
+    let still_pending = with_pending(|pending| match pending.get_mut(token) {
+        Some(entry) => {
+            entry.file_path = Some(file_path.clone());
+            true
+        }
+        None => false,
+    })?;
+    if !still_pending {
+        remove_download(&file_path);
+        return Err("导入已取消".to_string());
+    }
+
+    let size = fs::metadata(&file_path)
+        .map_err(|e| format!("读取下载文件失败: {e}"))?
+        .len();
+    if size > MAX_URL_IMPORT_BYTES {
+        return Err(too_large_error(size));
+    }
+    if !has_zip_signature(&file_path)? {
+        return Err("下载内容不是 zip 包，链接可能指向网页而不是文件".to_string());
+    }
+
+    tokio::task::spawn_blocking(move || {
+        inspect_archive(&file_path, None, false).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| format!("解析下载文件失败: {e}"))?
+}
+
+fn too_large_error(size: u64) -> String {
+    format!(
+        "文件过大（{} MB），超过 {} MB 上限",
+        size / 1024 / 1024,
+        MAX_URL_IMPORT_BYTES / 1024 / 1024
+    )
+}
+
+/// 下载任务已知的大小（总量或已下载量）超过上限时返回该大小
+fn exceeded_import_cap(total: Option<u64>, done: u64) -> Option<u64> {
+    let size = total.unwrap_or_default().max(done);
+    (size > MAX_URL_IMPORT_BYTES).then_some(size)
+}
+
+/// 等待下载结束；下载中大小一旦超过上限就取消任务，不再继续写盘
+async fn wait_download_finished(task_id: &str) -> Result<PathBuf, String> {
+    let mut receiver = subscribe_task_updates();
+    loop {
+        if let Some(snapshot) = get_snapshot(task_id) {
+            if let Some(size) = exceeded_import_cap(snapshot.total, snapshot.done)
+                && !matches!(
+                    snapshot.status.as_ref(),
+                    "completed" | "cancelled" | "error"
+                )
+            {
+                cancel_task(task_id);
+                return Err(too_large_error(size));
+            }
+            match snapshot.status.as_ref() {
+                "completed" => {
+                    return snapshot
+                        .message
+                        .map(|path| PathBuf::from(path.as_ref()))
+                        .ok_or_else(|| "下载完成但未返回文件路径".to_string());
+                }
+                "cancelled" => return Err("下载已取消".to_string()),
+                "error" => {
+                    return Err(format!(
+                        "下载失败: {}",
+                        snapshot.message.as_deref().unwrap_or_default()
+                    ));
+                }
+                _ => {}
+            }
+        }
+        match tokio::time::timeout(Duration::from_millis(500), receiver.recv()).await {
+            Ok(Err(broadcast::error::RecvError::Closed)) => {
+                return Err("任务管理器已关闭".to_string());
+            }
+            Ok(Ok(_)) | Ok(Err(broadcast::error::RecvError::Lagged(_))) | Err(_) => {}
+        }
+    }
+}
+
+/// 链接常常没有扩展名（如 MediaFire 直链），按内容识别后补上 `.zip`，
+/// 否则导入流程无法识别
+fn ensure_archive_extension(path: &Path) -> Result<PathBuf, String> {
+    let has_archive_extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            ARCHIVE_EXTENSIONS
+                .iter()
+                .any(|known| ext.eq_ignore_ascii_case(known))
+        });
+    if has_archive_extension {
+        return Ok(path.to_path_buf());
+    }
+    let renamed = path.with_extension("zip");
+    fs::rename(path, &renamed).map_err(|e| format!("重命名下载文件失败: {e}"))?;
+    Ok(renamed)
+}
+
+fn has_zip_signature(path: &Path) -> Result<bool, String> {
+    let mut signature = [0u8; 4];
+    let mut file = File::open(path).map_err(|e| format!("读取下载文件失败: {e}"))?;
+    match file.read_exact(&mut signature) {
+        Ok(()) => Ok(&signature == ZIP_LOCAL_HEADER_SIGNATURE),
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(e) => Err(format!("读取下载文件失败: {e}")),
+    }
+}
+
+fn download_file_name(url: &url::Url) -> String {
+    url.path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .map(|segment| {
+            percent_decode(segment)
+                .chars()
+                .filter(|ch| !matches!(ch, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|'))
+                .collect::<String>()
+        })
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_DOWNLOAD_NAME.to_string())
+}
+
+/// 解码路径段中的 `%XX`，无效序列原样保留
+fn percent_decode(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escaped = (bytes[index] == b'%')
+            .then(|| bytes.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn with_pending<T>(
+    action: impl FnOnce(&mut HashMap<String, PendingUrlImport>) -> T,
+) -> Result<T, String> {
+    let mut pending = PENDING_IMPORTS
+        .lock()
+        .map_err(|_| "URL 导入状态锁已损坏".to_string())?;
+    Ok(action(&mut pending))
+}
+
+/// 移除并清理待确认的导入，返回是否存在该 token
+fn discard_pending(token: &str) -> bool {
+    let Ok(Some(pending)) = with_pending(|pending| pending.remove(token)) else {
+        return false;
+    };
+    cleanup_pending(&pending);
+    true
+}
+
+fn cleanup_pending(pending: &PendingUrlImport) {
+    if let Some(task_id) = pending.download_task_id.as_deref()
+        && pending.file_path.is_none()
+    {
+        // 下载流水线在取消时会自行删除未完成的文件
+        cancel_task(task_id);
+    }
+    if let Some(file_path) = pending.file_path.as_deref() {
+        remove_download(file_path);
+    }
+}
+
+fn prune_expired_imports() {
+    let expired: Vec<PendingUrlImport> = with_pending(|pending| {
+        let expired_tokens: Vec<String> = pending
+            .iter()
+            .filter(|(_, entry)| entry.created_at.elapsed() > PENDING_IMPORT_TTL)
+            .map(|(token, _)| token.clone())
+            .collect();
+        expired_tokens
+            .iter()
+            .filter_map(|token| pending.remove(token))
+            .collect()
+    })
+    .unwrap_or_default();
+    for entry in &expired {
+        cleanup_pending(entry);
+    }
+}
+
+fn remove_download(path: &Path) {
+    if let Err(e) = fs::remove_file(path)
+        && e.kind() != std::io::ErrorKind::NotFound
+    {
+        warn!("删除 URL 导入临时文件失败 {}: {}", path.display(), e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn import_cap_uses_declared_total_or_downloaded_bytes() {
+        assert_eq!(exceeded_import_cap(None, 1024), None);
+        assert_eq!(exceeded_import_cap(Some(MAX_URL_IMPORT_BYTES), 0), None);
+        assert_eq!(
+            exceeded_import_cap(Some(MAX_URL_IMPORT_BYTES + 1), 0),
+            Some(MAX_URL_IMPORT_BYTES + 1)
+        );
+        // 服务器没给 Content-Length 时按已下载量判断
+        assert_eq!(
+            exceeded_import_cap(None, MAX_URL_IMPORT_BYTES + 10),
+            Some(MAX_URL_IMPORT_BYTES + 10)
+        );
+    }
+
+    #[test]
+    fn download_name_comes_from_last_path_segment() {
+        let url =
+            url::Url::parse("https://example.com/files/My%20Pack.mcpack?dl=1").expect("parse url");
+        assert_eq!(download_file_name(&url), "My Pack.mcpack");
+
+        let url = url::Url::parse("https://example.com/").expect("parse url");
+        assert_eq!(download_file_name(&url), DEFAULT_DOWNLOAD_NAME);
+    }
+
+    #[test]
+    fn zip_signature_is_sniffed_from_content() {
+        let test_dir = std::env::temp_dir().join(format!(
+            "bmcbl-url-import-test-{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .expect("system time should be after unix epoch")
+                .as_nanos()
+        ));
+        fs::create_dir_all(&test_dir).expect("create test dir");
+        let html = test_dir.join("page.mcpack");
+        let zip = test_dir.join("download");
+        fs::write(&html, b"<!DOCTYPE html>").expect("write html");
+        fs::write(&zip, b"PK\x03\x04rest").expect("write zip");
+
+        assert!(!has_zip_signature(&html).expect("sniff html"));
+        assert!(has_zip_signature(&zip).expect("sniff zip"));
+        assert_eq!(
+            ensure_archive_extension(&zip).expect("rename"),
+            test_dir.join("download.zip")
+        );
+
+        fs::remove_dir_all(&test_dir).expect("remove test dir");
+    }
+}
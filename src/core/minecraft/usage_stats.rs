@@ -0,0 +1,555 @@
+//! 本地使用统计：按版本累计游戏时长与启动次数、按天聚合，并根据存档列表里
+//! 最后游玩时间的变化估算最常玩的存档。数据只保存在 `BMCBL/stats.json`，不会上传。
+//!
+//! 游戏时长由游戏会话监视线程按心跳累计，每次最多计入两个心跳间隔，
+//! 因此系统时间回拨（负时长按 0 计）或向前跳变都不会产生异常数值。
+//! 只有数据变化时才写盘：会话开始与结束立即写入，心跳累计的时长最多每分钟写一次，
+//! 启动器中途被结束时，下次启动只保留已写入的部分。
+
+use crate::config::config::read_config;
+use crate::core::minecraft::game_session::watch_game_session;
+use crate::utils::file_ops;
+use chrono::{DateTime, Duration as ChronoDuration, Local, NaiveDate};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use sysinfo::{Pid, ProcessesToUpdate, System};
+use tracing::warn;
+
+const USAGE_STATS_FILE_NAME: &str = "stats.json";
+pub(crate) const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+/// 心跳累计的时长在内存中攒到这么久才写盘
+const SAVE_INTERVAL: Duration = Duration::from_secs(60);
+/// 单次心跳最多计入的秒数，避免休眠唤醒或系统时间跳变时把空档算作游戏时长
+const MAX_HEARTBEAT_CREDIT_SECS: i64 = 2 * HEARTBEAT_INTERVAL.as_secs() as i64;
+/// 进程启动时间晚于会话记录这么多秒时视为 PID 已被其他进程复用
+const PID_REUSE_TOLERANCE_SECS: u64 = 60;
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageStatsRange {
+    #[default]
+    Week,
+    Month,
+    Year,
+    All,
+}
+
+impl UsageStatsRange {
+    fn days(self) -> Option<i64> {
+        match self {
+            Self::Week => Some(7),
+            Self::Month => Some(30),
+            Self::Year => Some(365),
+            Self::All => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VersionUsage {
+    pub version: String,
+    pub playtime_secs: u64,
+    pub launches: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DailyUsage {
+    /// `YYYY-MM-DD`（本地时间）
+    pub date: String,
+    pub playtime_secs: u64,
+    pub launches: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WorldUsage {
+    pub world_path: String,
+    pub name: String,
+    /// 观察到最后游玩时间更新的次数
+    pub sessions: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UsageStats {
+    pub range: UsageStatsRange,
+    /// 按游戏时长降序
+    pub versions: Vec<VersionUsage>,
+    /// 按日期升序，没有记录的日期补 0，便于直接绘图
+    pub daily: Vec<DailyUsage>,
+    pub total_playtime_secs: u64,
+    pub total_launches: u32,
+    pub most_played_world: Option<WorldUsage>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct VersionDay {
+    #[serde(default)]
+    playtime_secs: u64,
+    #[serde(default)]
+    launches: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct OpenSession {
+    pid: u32,
+    version: String,
+    started_at: i64,
+    last_seen_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct WorldRecord {
+    name: String,
+    last_played_at: i64,
+    #[serde(default)]
+    sessions: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct UsageStatsFile {
+    /// 日期 → 版本 → 当天统计
+    #[serde(default)]
+    days: BTreeMap<String, BTreeMap<String, VersionDay>>,
+    #[serde(default)]
+    worlds: BTreeMap<String, WorldRecord>,
+    #[serde(default)]
+    open_sessions: Vec<OpenSession>,
+}
+
+fn day_key(time: DateTime<Local>) -> String {
+    time.format(DATE_FORMAT).to_string()
+}
+
+impl UsageStatsFile {
+    fn version_day(&mut self, version: &str, time: DateTime<Local>) -> &mut VersionDay {
+        self.days
+            .entry(day_key(time))
+            .or_default()
+            .entry(version.to_string())
+            .or_default()
+    }
+
+    fn record_launch(&mut self, version: &str, pid: u32, now: DateTime<Local>) {
+        self.version_day(version, now).launches += 1;
+        self.open_sessions.retain(|session| session.pid != pid);
+        self.open_sessions.push(OpenSession {
+            pid,
+            version: version.to_string(),
+            started_at: now.timestamp_millis(),
+            last_seen_at: now.timestamp_millis(),
+        });
+    }
+
+    /// 把上次心跳以来的时长计入当天；返回会话是否仍存在
+    fn heartbeat(&mut self, pid: u32, now: DateTime<Local>) -> bool {
+        let Some(index) = self
+            .open_sessions
+            .iter()
+            .position(|session| session.pid == pid)
+        else {
+            return false;
+        };
+        let session = &mut self.open_sessions[index];
+        let elapsed_secs = (now.timestamp_millis() - session.last_seen_at) / 1000;
+        let credit = elapsed_secs.clamp(0, MAX_HEARTBEAT_CREDIT_SECS) as u64;
+        // 时间回拨后也以当前时间为基准继续计时
+        session.last_seen_at = now.timestamp_millis();
+        let version = session.version.clone();
+        if credit > 0 {
+            self.version_day(&version, now).playtime_secs += credit;
+        }
+        true
+    }
+
+    /// 返回是否确实关闭了会话
+    fn close_session(&mut self, pid: u32) -> bool {
+        let before = self.open_sessions.len();
+        self.open_sessions.retain(|session| session.pid != pid);
+        self.open_sessions.len() != before
+    }
+
+    /// 返回记录是否有变化
+    fn observe_world(&mut self, world_path: &str, name: &str, last_played_at: i64) -> bool {
+        match self.worlds.get_mut(world_path) {
+            Some(record) => {
+                let mut changed = false;
+                if last_played_at > record.last_played_at {
+                    record.sessions += 1;
+                    record.last_played_at = last_played_at;
+                    changed = true;
+                }
+                if record.name != name {
+                    record.name = name.to_string();
+                    changed = true;
+                }
+                changed
+            }
+            // 首次看到的存档只记录基准时间
+            None => {
+                self.worlds.insert(
+                    world_path.to_string(),
+                    WorldRecord {
+                        name: name.to_string(),
+                        last_played_at,
+                        sessions: 0,
+                    },
+                );
+                true
+            }
+        }
+    }
+
+    fn summarize(&self, range: UsageStatsRange, today: NaiveDate) -> UsageStats {
+        let first_day = match range.days() {
+            Some(days) => Some(today - ChronoDuration::days(days - 1)),
+            None => self
+                .days
+                .keys()
+                .filter_map(|day| NaiveDate::parse_from_str(day, DATE_FORMAT).ok())
+                .min(),
+        };
+
+        let mut versions: BTreeMap<&str, VersionUsage> = BTreeMap::new();
+        let mut daily = Vec::new();
+        if let Some(first_day) = first_day {
+            for date in first_day.iter_days().take_while(|date| *date <= today) {
+                let key = date.format(DATE_FORMAT).to_string();
+                let mut day = DailyUsage {
+                    date: key.clone(),
+                    playtime_secs: 0,
+                    launches: 0,
+                };
+                for (version, usage) in self.days.get(&key).into_iter().flatten() {
+                    day.playtime_secs += usage.playtime_secs;
+                    day.launches += usage.launches;
+                    let total = versions
+                        .entry(version.as_str())
+                        .or_insert_with(|| VersionUsage {
+                            version: version.clone(),
+                            playtime_secs: 0,
+                            launches: 0,
+                        });
+                    total.playtime_secs += usage.playtime_secs;
+                    total.launches += usage.launches;
+                }
+                daily.push(day);
+            }
+        }
+
+        let mut versions = versions.into_values().collect::<Vec<_>>();
+        versions.sort_by(|left, right| {
+            right
+                .playtime_secs
+                .cmp(&left.playtime_secs)
+                .then_with(|| left.version.cmp(&right.version))
+        });
+        let most_played_world = self
+            .worlds
+            .iter()
+            .filter(|(_, record)| record.sessions > 0)
+            .max_by_key(|(_, record)| (record.sessions, record.last_played_at))
+            .map(|(path, record)| WorldUsage {
+                world_path: path.clone(),
+                name: record.name.clone(),
+                sessions: record.sessions,
+            });
+        UsageStats {
+            range,
+            total_playtime_secs: daily.iter().map(|day| day.playtime_secs).sum(),
+            total_launches: daily.iter().map(|day| day.launches).sum(),
+            versions,
+            daily,
+            most_played_world,
+        }
+    }
+}
+
+fn stats_path() -> PathBuf {
+    file_ops::bmcbl_dir().join(USAGE_STATS_FILE_NAME)
+}
+
+fn load_stats() -> UsageStatsFile {
+    let Ok(raw) = fs::read_to_string(stats_path()) else {
+        return UsageStatsFile::default();
+    };
+    match serde_json::from_str(&raw) {
+        Ok(stats) => stats,
+        Err(e) => {
+            warn!("解析使用统计失败，已忽略: {}", e);
+            UsageStatsFile::default()
+        }
+    }
+}
+
+fn save_stats(stats: &UsageStatsFile) -> Result<(), String> {
+    let path = stats_path();
+    let raw = serde_json::to_string_pretty(stats).map_err(|e| e.to_string())?;
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, raw)
+        .and_then(|_| fs::rename(&tmp_path, &path))
+        .map_err(|e| format!("保存使用统计失败 {}: {}", path.display(), e))
+}
+
+struct UsageStatsState {
+    stats: UsageStatsFile,
+    /// 内存中有尚未写入磁盘的改动
+    dirty: bool,
+    saved_at: Instant,
+}
+
+static USAGE_STATS: Lazy<Mutex<UsageStatsState>> = Lazy::new(|| {
+    Mutex::new(UsageStatsState {
+        stats: load_stats(),
+        dirty: false,
+        saved_at: Instant::now(),
+    })
+});
+
+fn collection_enabled() -> bool {
+    read_config()
+        .map(|config| config.launcher.local_usage_stats)
+        .unwrap_or(true)
+}
+
+/// `update` 返回数据是否有变化；`flush` 为 false 时变化先留在内存，到期再写盘
+fn update_stats(flush: bool, update: impl FnOnce(&mut UsageStatsFile) -> bool) {
+    let mut state = USAGE_STATS.lock().unwrap_or_else(|e| e.into_inner());
+    if update(&mut state.stats) {
+        state.dirty = true;
+    }
+    if !state.dirty || !(flush || state.saved_at.elapsed() >= SAVE_INTERVAL) {
+        return;
+    }
+    match save_stats(&state.stats) {
+        Ok(()) => {
+            state.dirty = false;
+            state.saved_at = Instant::now();
+        }
+        Err(e) => warn!("{}", e),
+    }
+}
+
+/// 进程仍在运行，且启动时间早于会话记录（排除 PID 被复用）
+fn is_session_process_alive(system: &mut System, session_pid: u32, started_at: i64) -> bool {
+    let pid = Pid::from_u32(session_pid);
+    system.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+    system.process(pid).is_some_and(|process| {
+        process.start_time() <= (started_at / 1000).max(0) as u64 + PID_REUSE_TOLERANCE_SECS
+    })
+}
+
+fn session_started_at(pid: u32) -> Option<i64> {
+    USAGE_STATS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .stats
+        .open_sessions
+        .iter()
+        .find(|session| session.pid == pid)
+        .map(|session| session.started_at)
+}
+
+/// 游戏会话监视线程按心跳间隔调用；PID 已被其他进程复用时结束会话
+pub(crate) fn heartbeat_session(system: &mut System, pid: u32) {
+    let Some(started_at) = session_started_at(pid) else {
+        return;
+    };
+    if is_session_process_alive(system, pid, started_at) {
+        update_stats(false, |stats| stats.heartbeat(pid, Local::now()));
+    } else {
+        end_session(pid);
+    }
+}
+
+/// 游戏进程退出后调用：计入最后一段时长并关闭会话
+pub(crate) fn end_session(pid: u32) {
+    update_stats(true, |stats| {
+        stats.heartbeat(pid, Local::now()) && stats.close_session(pid)
+    });
+}
+
+/// 游戏拉起后调用：记录一次启动并开始累计游戏时长
+pub fn record_game_session_start(version: &str, pid: u32) {
+    if !collection_enabled() {
+        return;
+    }
+    update_stats(true, |stats| {
+        stats.record_launch(version, pid, Local::now());
+        true
+    });
+    watch_game_session(pid);
+}
+
+/// 启动器启动时调用：游戏仍在运行的会话继续统计，已结束的会话按最后一次心跳收尾
+pub fn resume_usage_sessions() {
+    let sessions = USAGE_STATS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .stats
+        .open_sessions
+        .clone();
+    if sessions.is_empty() {
+        return;
+    }
+    let mut system = System::new();
+    let (running, ended): (Vec<_>, Vec<_>) = sessions.into_iter().partition(|session| {
+        is_session_process_alive(&mut system, session.pid, session.started_at)
+    });
+    if !ended.is_empty() {
+        update_stats(true, |stats| {
+            ended.iter().fold(false, |closed, session| {
+                stats.close_session(session.pid) || closed
+            })
+        });
+    }
+    if !collection_enabled() {
+        return;
+    }
+    for session in running {
+        watch_game_session(session.pid);
+    }
+}
+
+/// 存档列表读取后调用，最后游玩时间变化即视为玩过一次
+pub(crate) fn observe_worlds<'a>(
+    worlds: impl IntoIterator<Item = (&'a str, &'a str, Option<SystemTime>)>,
+) {
+    if !collection_enabled() {
+        return;
+    }
+    let observations = worlds
+        .into_iter()
+        .filter_map(|(path, name, modified)| {
+            let millis = modified?.duration_since(UNIX_EPOCH).ok()?.as_millis() as i64;
+            Some((path.to_string(), name.to_string(), millis))
+        })
+        .collect::<Vec<_>>();
+    if observations.is_empty() {
+        return;
+    }
+    update_stats(true, |stats| {
+        observations
+            .iter()
+            .fold(false, |changed, (path, name, last_played_at)| {
+                stats.observe_world(path, name, *last_played_at) || changed
+            })
+    });
+}
+
+/// 返回指定范围内的版本游戏时长、启动次数与每日时间序列
+pub fn get_usage_stats(range: UsageStatsRange) -> Result<UsageStats, String> {
+    let state = USAGE_STATS
+        .lock()
+        .map_err(|_| "使用统计锁已损坏".to_string())?;
+    Ok(state.stats.summarize(range, Local::now().date_naive()))
+}
+
+/// 清空所有使用统计；正在进行的会话从现在起重新计时
+pub fn clear_usage_stats() -> Result<(), String> {
+    let mut state = USAGE_STATS
+        .lock()
+        .map_err(|_| "使用统计锁已损坏".to_string())?;
+    let now = Local::now().timestamp_millis();
+    let open_sessions = state
+        .stats
+        .open_sessions
+        .iter()
+        .cloned()
+        .map(|session| OpenSession {
+            last_seen_at: now,
+            ..session
+        })
+        .collect();
+    state.stats = UsageStatsFile {
+        open_sessions,
+        ..Default::default()
+    };
+    save_stats(&state.stats)?;
+    state.dirty = false;
+    state.saved_at = Instant::now();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(day: u32, hour: u32, minute: u32, second: u32) -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2025, 3, day, hour, minute, second)
+            .single()
+            .expect("unambiguous local time")
+    }
+
+    #[test]
+    fn heartbeats_clamp_clock_jumps_and_aggregate_by_day() {
+        let mut stats = UsageStatsFile::default();
+        stats.record_launch("1.21.0", 42, at(10, 12, 0, 0));
+        assert!(stats.heartbeat(42, at(10, 12, 0, 15)));
+        // 时间回拨不扣减，之后从回拨后的时间继续计
+        assert!(stats.heartbeat(42, at(10, 11, 0, 0)));
+        assert!(stats.heartbeat(42, at(10, 11, 0, 10)));
+        // 休眠数小时后只计入上限
+        assert!(stats.heartbeat(42, at(11, 9, 0, 0)));
+        assert!(stats.close_session(42));
+        assert!(!stats.close_session(42));
+        assert!(!stats.heartbeat(42, at(11, 9, 0, 15)));
+
+        stats.record_launch("1.20.80", 7, at(11, 20, 0, 0));
+        assert!(stats.heartbeat(7, at(11, 20, 0, 20)));
+
+        assert!(stats.observe_world("w/a", "Alpha", 1_000));
+        assert!(stats.observe_world("w/a", "Alpha", 2_000));
+        // 列表重复读取、存档没有变化时不需要写盘
+        assert!(!stats.observe_world("w/a", "Alpha", 2_000));
+        assert!(stats.observe_world("w/a", "Alpha", 3_000));
+        assert!(stats.observe_world("w/b", "Beta", 5_000));
+        assert!(stats.observe_world("w/b", "Beta", 6_000));
+
+        let summary = stats.summarize(UsageStatsRange::Week, at(12, 8, 0, 0).date_naive());
+        assert_eq!(summary.daily.len(), 7);
+        assert_eq!(summary.daily[4].date, "2025-03-10");
+        assert_eq!(
+            (summary.daily[4].playtime_secs, summary.daily[4].launches),
+            (25, 1)
+        );
+        assert_eq!(
+            (summary.daily[5].playtime_secs, summary.daily[5].launches),
+            (MAX_HEARTBEAT_CREDIT_SECS as u64 + 20, 1)
+        );
+        assert_eq!(summary.daily[6].playtime_secs, 0);
+        assert_eq!(summary.total_launches, 2);
+        assert_eq!(
+            summary.versions,
+            vec![
+                VersionUsage {
+                    version: "1.21.0".to_string(),
+                    playtime_secs: 25 + MAX_HEARTBEAT_CREDIT_SECS as u64,
+                    launches: 1,
+                },
+                VersionUsage {
+                    version: "1.20.80".to_string(),
+                    playtime_secs: 20,
+                    launches: 1,
+                },
+            ]
+        );
+        assert_eq!(
+            summary.most_played_world.map(|world| world.name),
+            Some("Alpha".to_string())
+        );
+
+        let all = stats.summarize(UsageStatsRange::All, at(12, 8, 0, 0).date_naive());
+        assert_eq!(
+            all.daily.first().map(|day| day.date.as_str()),
+            Some("2025-03-10")
+        );
+        assert_eq!(all.daily.len(), 3);
+    }
+}
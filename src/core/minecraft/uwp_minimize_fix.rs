@@ -47,3 +47,35 @@ pub fn enable_debugging_for_package(package_name: &str) -> Result<()> {
         Ok(())
     }
 }
+
+/// 撤销 [`enable_debugging_for_package`]，恢复系统对该包的挂起管理
+pub fn disable_debugging_for_package(package_name: &str) -> Result<()> {
+    unsafe {
+        CoInitializeEx(None, COINIT_APARTMENTTHREADED | COINIT_DISABLE_OLE1DDE).ok()?;
+
+        let clsid_pkg_debug = GUID::from_u128(0xb1aec16f_2383_4852_b0e9_8f0b1dc66b4d);
+        let pkg_debug: IPackageDebugSettings =
+            CoCreateInstance(&clsid_pkg_debug, None, CLSCTX_INPROC_SERVER)?;
+
+        let wide: Vec<u16> = OsStr::new(package_name).encode_wide().chain([0]).collect();
+        let _ = pkg_debug.DisableDebugging(PCWSTR(wide.as_ptr()));
+
+        let pm = PackageManager::new()?;
+        let family_hs: HSTRING = HSTRING::from(package_name);
+        if let Ok(packages) = pm.FindPackagesByPackageFamilyName(&family_hs) {
+            for pkg in packages {
+                if let Ok(full_name_hstr) = pkg.Id().and_then(|pkg_id| pkg_id.FullName()) {
+                    let wide: Vec<u16> = full_name_hstr
+                        .to_string()
+                        .encode_utf16()
+                        .chain([0])
+                        .collect();
+                    let _ = pkg_debug.DisableDebugging(PCWSTR(wide.as_ptr()));
+                }
+            }
+        }
+
+        CoUninitialize();
+        Ok(())
+    }
+}
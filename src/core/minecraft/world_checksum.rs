@@ -0,0 +1,167 @@
+//! 存档完整性校验：按相对路径排序后逐个文件流式计算 SHA-256，
+//! 结果写入 `.bmcbl_checksum.json`，下次计算时与之比较以判断存档是否被改动。
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+use walkdir::WalkDir;
+
+const CHECKSUM_FILE_NAME: &str = ".bmcbl_checksum.json";
+const WORLD_ICON_FILE_NAME: &str = "world_icon.jpeg";
+const SIDECAR_PREFIX: &str = "bmcbl_";
+const HASH_BUFFER_SIZE: usize = 256 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorldChecksum {
+    pub checksum_hex: String,
+    pub file_count: usize,
+    pub total_bytes: u64,
+    /// RFC 3339 时间
+    pub computed_at: String,
+    /// 与上次保存的校验值是否一致；此前没有记录时为 `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matches_previous: Option<bool>,
+}
+
+/// 计算存档校验值并保存，返回结果中带有与上次记录的比较
+pub async fn compute_world_checksum(world_folder_path: String) -> Result<WorldChecksum, String> {
+    let world_path = PathBuf::from(world_folder_path);
+    tokio::task::spawn_blocking(move || compute_world_checksum_blocking(&world_path))
+        .await
+        .map_err(|e| format!("计算存档校验值任务失败: {}", e))?
+}
+
+fn is_excluded(file_name: &str) -> bool {
+    file_name.eq_ignore_ascii_case(WORLD_ICON_FILE_NAME)
+        || file_name
+            .trim_start_matches('.')
+            .starts_with(SIDECAR_PREFIX)
+}
+
+/// 以 `/` 分隔的相对路径，保证不同平台上的排序与哈希输入一致
+fn relative_key(world_path: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(world_path).ok()?;
+    let parts: Vec<String> = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    Some(parts.join("/"))
+}
+
+fn hash_world(world_path: &Path) -> Result<WorldChecksum, String> {
+    let mut files: Vec<(String, PathBuf)> = Vec::new();
+    for entry in WalkDir::new(world_path).follow_links(false) {
+        let entry = entry.map_err(|e| format!("遍历存档目录失败: {}", e))?;
+        if !entry.file_type().is_file() || is_excluded(&entry.file_name().to_string_lossy()) {
+            continue;
+        }
+        if let Some(key) = relative_key(world_path, entry.path()) {
+            files.push((key, entry.into_path()));
+        }
+    }
+    files.sort_by(|left, right| left.0.cmp(&right.0));
+
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; HASH_BUFFER_SIZE];
+    let mut total_bytes = 0u64;
+    for (key, path) in &files {
+        let mut file =
+            File::open(path).map_err(|e| format!("打开文件失败 {}: {}", path.display(), e))?;
+        // 路径参与哈希，重命名或移动文件同样会改变校验值
+        hasher.update(key.as_bytes());
+        hasher.update([0u8]);
+        let mut file_bytes = 0u64;
+        loop {
+            let read = file
+                .read(&mut buffer)
+                .map_err(|e| format!("读取文件失败 {}: {}", path.display(), e))?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+            file_bytes += read as u64;
+        }
+        hasher.update(file_bytes.to_le_bytes());
+        total_bytes += file_bytes;
+    }
+
+    Ok(WorldChecksum {
+        checksum_hex: hex::encode(hasher.finalize()),
+        file_count: files.len(),
+        total_bytes,
+        computed_at: Utc::now().to_rfc3339(),
+        matches_previous: None,
+    })
+}
+
+fn read_stored_checksum(world_path: &Path) -> Option<WorldChecksum> {
+    let path = world_path.join(CHECKSUM_FILE_NAME);
+    let content = fs::read_to_string(&path).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(stored) => Some(stored),
+        Err(e) => {
+            warn!("存档校验记录解析失败 {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+fn compute_world_checksum_blocking(world_path: &Path) -> Result<WorldChecksum, String> {
+    if !world_path.join("level.dat").is_file() {
+        return Err(format!("不是有效的存档目录: {}", world_path.display()));
+    }
+
+    let previous = read_stored_checksum(world_path);
+    let mut checksum = hash_world(world_path)?;
+
+    let content = serde_json::to_string_pretty(&checksum)
+        .map_err(|e| format!("序列化存档校验记录失败: {}", e))?;
+    fs::write(world_path.join(CHECKSUM_FILE_NAME), content)
+        .map_err(|e| format!("写入存档校验记录失败: {}", e))?;
+
+    checksum.matches_previous =
+        previous.map(|previous| previous.checksum_hex == checksum.checksum_hex);
+    Ok(checksum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_world() -> PathBuf {
+        let world_dir =
+            std::env::temp_dir().join(format!("bmcbl-world-checksum-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(world_dir.join("db")).expect("create world dir");
+        fs::write(world_dir.join("level.dat"), b"level").expect("write level.dat");
+        fs::write(world_dir.join("db").join("CURRENT"), b"MANIFEST-000001").expect("write db");
+        world_dir
+    }
+
+    #[test]
+    fn checksum_ignores_icon_and_sidecars_and_detects_changes() {
+        let world_dir = temp_world();
+
+        let first = compute_world_checksum_blocking(&world_dir).expect("first checksum");
+        assert_eq!(first.file_count, 2);
+        assert_eq!(first.total_bytes, 20);
+        assert_eq!(first.matches_previous, None);
+        assert!(world_dir.join(CHECKSUM_FILE_NAME).is_file());
+
+        fs::write(world_dir.join(WORLD_ICON_FILE_NAME), b"icon").expect("write icon");
+        fs::write(world_dir.join("bmcbl_notes.json"), b"{}").expect("write sidecar");
+        let second = compute_world_checksum_blocking(&world_dir).expect("second checksum");
+        assert_eq!(second.checksum_hex, first.checksum_hex);
+        assert_eq!(second.matches_previous, Some(true));
+
+        fs::write(world_dir.join("levelname.txt"), b"Renamed").expect("write level name");
+        let third = compute_world_checksum_blocking(&world_dir).expect("third checksum");
+        assert_ne!(third.checksum_hex, first.checksum_hex);
+        assert_eq!(third.matches_previous, Some(false));
+
+        fs::remove_dir_all(&world_dir).expect("remove test dir");
+    }
+}
@@ -0,0 +1,319 @@
+//! 存档区块统计：只遍历 `db/` 的键（不读值），按维度统计区块数与已探索范围，
+//! 并统计混合数据、实体摘要等附加记录。10GB 级存档也只需几秒。
+//! 结果按 `db/` 目录修改时间缓存；遇到无法识别的键布局时返回已解析部分并标记 `incomplete`。
+
+use crate::core::cache_registry::{CacheDescriptor, CacheUsage};
+use crate::core::minecraft::world_entities::dimension_name;
+use crate::tasks::task_manager;
+use bedrock_world::{BedrockWorld, CancelFlag, ChunkKey, ChunkRecordTag, WorldFormatHint};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Instant, SystemTime};
+use tracing::debug;
+
+const ACTOR_DIGEST_PREFIX: &[u8] = b"digp";
+const ACTOR_PREFIX: &[u8] = b"actorprefix";
+/// 每遍历这么多个键检查一次取消
+const CANCEL_CHECK_EVERY: u64 = 65_536;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DimensionChunkStats {
+    pub dimension: String,
+    pub chunk_count: usize,
+    pub min_chunk_x: i32,
+    pub max_chunk_x: i32,
+    pub min_chunk_z: i32,
+    pub max_chunk_z: i32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct WorldChunkStats {
+    /// 按维度 ID 排序
+    pub dimensions: Vec<DimensionChunkStats>,
+    pub total_chunks: usize,
+    /// BlendingData / BlendingBiomeHeight 记录数
+    pub blending_records: u64,
+    /// `digp` 实体摘要记录数
+    pub actor_digest_records: u64,
+    /// `actorprefix` 实体记录数
+    pub actor_records: u64,
+    /// 玩家、村庄、地图等命名记录数
+    pub named_records: u64,
+    pub unrecognized_keys: u64,
+    /// 存在无法识别的键时为 true，此时统计只覆盖能解析的部分
+    pub incomplete: bool,
+    pub scan_duration_ms: u64,
+}
+
+#[derive(Debug, Default)]
+struct ChunkStatsAccumulator {
+    dimensions: BTreeMap<i32, DimensionChunkStats>,
+    stats: WorldChunkStats,
+}
+
+impl ChunkStatsAccumulator {
+    fn record_key(&mut self, key: &[u8]) {
+        if key.starts_with(ACTOR_DIGEST_PREFIX) {
+            self.stats.actor_digest_records += 1;
+            return;
+        }
+        if key.starts_with(ACTOR_PREFIX) {
+            self.stats.actor_records += 1;
+            return;
+        }
+        if let Some(chunk_key) = decode_chunk_key(key) {
+            self.record_chunk_key(&chunk_key);
+            return;
+        }
+        if key.iter().all(u8::is_ascii_graphic) {
+            self.stats.named_records += 1;
+        } else {
+            self.stats.unrecognized_keys += 1;
+        }
+    }
+
+    fn record_chunk_key(&mut self, key: &ChunkKey) {
+        let pos = key.pos;
+        let dimension = self
+            .dimensions
+            .entry(pos.dimension.id())
+            .or_insert_with(|| DimensionChunkStats {
+                dimension: dimension_name(pos.dimension),
+                chunk_count: 0,
+                min_chunk_x: pos.x,
+                max_chunk_x: pos.x,
+                min_chunk_z: pos.z,
+                max_chunk_z: pos.z,
+            });
+        dimension.min_chunk_x = dimension.min_chunk_x.min(pos.x);
+        dimension.max_chunk_x = dimension.max_chunk_x.max(pos.x);
+        dimension.min_chunk_z = dimension.min_chunk_z.min(pos.z);
+        dimension.max_chunk_z = dimension.max_chunk_z.max(pos.z);
+        match key.tag {
+            // 每个区块恰有一条版本记录，用它计数可以省掉按坐标去重
+            ChunkRecordTag::Version
+            | ChunkRecordTag::VersionOld
+            | ChunkRecordTag::LegacyVersion => {
+                dimension.chunk_count += 1;
+            }
+            ChunkRecordTag::BlendingData | ChunkRecordTag::BlendingBiomeHeight => {
+                self.stats.blending_records += 1;
+            }
+            _ => {}
+        }
+    }
+
+    fn finish(mut self, scan_duration_ms: u64) -> WorldChunkStats {
+        self.stats.dimensions = self.dimensions.into_values().collect();
+        self.stats.total_chunks = self
+            .stats
+            .dimensions
+            .iter()
+            .map(|dimension| dimension.chunk_count)
+            .sum();
+        self.stats.incomplete = self.stats.unrecognized_keys > 0;
+        self.stats.scan_duration_ms = scan_duration_ms;
+        self.stats
+    }
+}
+
+/// 区块键为 8/12 字节坐标加标签（子区块再多一个索引字节），其他长度直接排除，
+/// 避免把 `mVillages` 这类 9 字节命名键误判成区块
+fn decode_chunk_key(key: &[u8]) -> Option<ChunkKey> {
+    if !matches!(key.len(), 9 | 10 | 13 | 14) {
+        return None;
+    }
+    ChunkKey::decode(key).ok()
+}
+
+/// 缓存键为 db 目录路径，值带上统计时的目录修改时间
+static CHUNK_STATS_CACHE: Lazy<Mutex<HashMap<PathBuf, (SystemTime, WorldChunkStats)>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub(crate) static CACHE: CacheDescriptor = CacheDescriptor {
+    name: "world_chunk_stats",
+    label: "存档区块统计",
+    default_selected: true,
+    usage: chunk_stats_cache_usage,
+    clear: clear_chunk_stats_cache,
+    warm: None,
+};
+
+fn chunk_stats_cache_usage() -> CacheUsage {
+    CacheUsage {
+        entries: CHUNK_STATS_CACHE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .len() as u64,
+        bytes: 0,
+    }
+}
+
+fn clear_chunk_stats_cache() -> Result<CacheUsage, String> {
+    let mut cache = CHUNK_STATS_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let entries = cache.len() as u64;
+    cache.clear();
+    Ok(CacheUsage { entries, bytes: 0 })
+}
+
+fn db_modified(db_path: &Path) -> Option<SystemTime> {
+    fs::metadata(db_path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+fn cached_stats(db_path: &Path, modified: SystemTime) -> Option<WorldChunkStats> {
+    let cache = CHUNK_STATS_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    cache
+        .get(db_path)
+        .filter(|(cached_modified, _)| *cached_modified == modified)
+        .map(|(_, stats)| stats.clone())
+}
+
+/// 统计存档区块；`task_id` 可用于 `cancel_task` 取消扫描
+pub async fn get_world_chunk_stats(
+    world_path: String,
+    task_id: Option<String>,
+) -> Result<WorldChunkStats, String> {
+    let world_path = PathBuf::from(world_path);
+    let db_path = world_path.join("db");
+    if !db_path.is_dir() {
+        return Err(format!("存档缺少 db 目录: {}", world_path.display()));
+    }
+    let modified = db_modified(&db_path);
+    if let Some(stats) = modified.and_then(|modified| cached_stats(&db_path, modified)) {
+        return Ok(stats);
+    }
+
+    let task_id = task_manager::create_task_with_details(
+        task_id,
+        "统计存档区块",
+        Some(world_path.display().to_string()),
+        "scanning",
+        None,
+        false,
+    );
+    let cancel = CancelFlag::new();
+    task_manager::register_task_cancel_hook(task_id.clone(), {
+        let cancel = cancel.clone();
+        move || cancel.cancel()
+    });
+
+    let result =
+        tokio::task::spawn_blocking(move || scan_chunk_stats_blocking(&world_path, &cancel))
+            .await
+            .map_err(|e| format!("统计区块任务失败: {}", e))
+            .and_then(|result| result);
+
+    match &result {
+        Ok(stats) => {
+            task_manager::finish_task(&task_id, "completed", None);
+            if let Some(modified) = modified {
+                CHUNK_STATS_CACHE
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .insert(db_path, (modified, stats.clone()));
+            }
+        }
+        Err(e) => task_manager::finish_task(&task_id, "error", Some(e.clone())),
+    }
+    result
+}
+
+fn scan_chunk_stats_blocking(
+    world_path: &Path,
+    cancel: &CancelFlag,
+) -> Result<WorldChunkStats, String> {
+    let started = Instant::now();
+    let world = BedrockWorld::open_blocking(
+        world_path,
+        bedrock_world::OpenOptions {
+            read_only: true,
+            format: WorldFormatHint::LevelDb,
+        },
+    )
+    .map_err(|e| format!("打开存档数据库失败: {}", e))?;
+
+    let mut accumulator = ChunkStatsAccumulator::default();
+    let mut visited = 0_u64;
+    world
+        .storage()
+        .for_each_key(&mut |key: &[u8]| {
+            accumulator.record_key(key);
+            visited += 1;
+            !(visited % CANCEL_CHECK_EVERY == 0 && cancel.is_cancelled())
+        })
+        .map_err(|e| format!("遍历存档数据库失败: {}", e))?;
+    if cancel.is_cancelled() {
+        return Err("统计区块已取消".to_string());
+    }
+
+    let stats = accumulator.finish(started.elapsed().as_millis() as u64);
+    debug!(
+        keys = visited,
+        chunks = stats.total_chunks,
+        incomplete = stats.incomplete,
+        elapsed_ms = stats.scan_duration_ms,
+        "已统计存档区块"
+    );
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bedrock_world::{ChunkPos, Dimension};
+
+    fn chunk_key(x: i32, z: i32, dimension: Dimension, tag: ChunkRecordTag) -> Vec<u8> {
+        ChunkKey::new(ChunkPos { x, z, dimension }, tag)
+            .encode()
+            .to_vec()
+    }
+
+    #[test]
+    fn accumulator_counts_chunks_bounds_and_extra_records() {
+        let mut accumulator = ChunkStatsAccumulator::default();
+        for key in [
+            chunk_key(-3, 5, Dimension::Overworld, ChunkRecordTag::Version),
+            chunk_key(-3, 5, Dimension::Overworld, ChunkRecordTag::Data3D),
+            chunk_key(7, -2, Dimension::Overworld, ChunkRecordTag::Version),
+            chunk_key(7, -2, Dimension::Overworld, ChunkRecordTag::BlendingData),
+            chunk_key(1, 1, Dimension::Nether, ChunkRecordTag::Version),
+            b"digp\x01\x00\x00\x00\x02\x00\x00\x00".to_vec(),
+            b"actorprefix\x00\x00\x00\x00\x00\x00\x00\x01".to_vec(),
+            b"~local_player".to_vec(),
+            b"mVillages".to_vec(),
+        ] {
+            accumulator.record_key(&key);
+        }
+
+        let stats = accumulator.finish(0);
+        assert!(!stats.incomplete);
+        assert_eq!(stats.total_chunks, 3);
+        assert_eq!(stats.dimensions.len(), 2);
+        let overworld = &stats.dimensions[0];
+        assert_eq!(overworld.dimension, "overworld");
+        assert_eq!(overworld.chunk_count, 2);
+        assert_eq!(
+            (
+                overworld.min_chunk_x,
+                overworld.max_chunk_x,
+                overworld.min_chunk_z,
+                overworld.max_chunk_z
+            ),
+            (-3, 7, -2, 5)
+        );
+        assert_eq!(stats.blending_records, 1);
+        assert_eq!(stats.actor_digest_records, 1);
+        assert_eq!(stats.actor_records, 1);
+        assert_eq!(stats.named_records, 2);
+
+        let mut accumulator = ChunkStatsAccumulator::default();
+        accumulator.record_key(&[0xff, 0x00, 0x01]);
+        assert!(accumulator.finish(0).incomplete);
+    }
+}
@@ -0,0 +1,140 @@
+//! 列出存档内附带的数据包：教育版实验性数据包放在 `development_behavior_packs/` 与
+//! `development_resource_packs/` 中，存档自带的正式包位于 `behavior_packs/` 与 `resource_packs/`。
+
+use crate::core::minecraft::import::{ImportTargetType, PackagePreview, get_pack_info_from_dir};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+const SCRIPTS_DIR_NAME: &str = "scripts";
+
+/// (目录名, 包类型, 是否为开发包)
+const DATAPACK_DIRS: [(&str, ImportTargetType, bool); 4] = [
+    (
+        "development_behavior_packs",
+        ImportTargetType::BehaviorPack,
+        true,
+    ),
+    (
+        "development_resource_packs",
+        ImportTargetType::ResourcePack,
+        true,
+    ),
+    ("behavior_packs", ImportTargetType::BehaviorPack, false),
+    ("resource_packs", ImportTargetType::ResourcePack, false),
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DatapackEntry {
+    pub pack_info: PackagePreview,
+    pub is_development: bool,
+    /// 包内存在 `scripts/` 目录（脚本 API）
+    pub has_scripts: bool,
+}
+
+/// 列出存档中的开发包与正式包，开发包排在前面
+pub async fn list_world_datapacks(world_folder_path: String) -> Result<Vec<DatapackEntry>, String> {
+    let world_path = PathBuf::from(world_folder_path);
+    tokio::task::spawn_blocking(move || list_world_datapacks_blocking(&world_path))
+        .await
+        .map_err(|e| format!("读取存档数据包任务失败: {}", e))?
+}
+
+fn list_world_datapacks_blocking(world_path: &Path) -> Result<Vec<DatapackEntry>, String> {
+    if !world_path.is_dir() {
+        return Err(format!("存档目录不存在: {}", world_path.display()));
+    }
+
+    let mut entries = Vec::new();
+    for (dir_name, target_type, is_development) in &DATAPACK_DIRS {
+        let packs_dir = world_path.join(dir_name);
+        let Ok(read_dir) = fs::read_dir(&packs_dir) else {
+            continue;
+        };
+        let mut pack_dirs: Vec<PathBuf> = read_dir
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.join("manifest.json").is_file())
+            .collect();
+        pack_dirs.sort();
+
+        for pack_dir in pack_dirs {
+            match get_pack_info_from_dir(&pack_dir, target_type, None) {
+                Ok(pack_info) => entries.push(DatapackEntry {
+                    pack_info,
+                    is_development: *is_development,
+                    has_scripts: pack_dir.join(SCRIPTS_DIR_NAME).is_dir(),
+                }),
+                Err(e) => warn!("读取数据包清单失败 {}: {}", pack_dir.display(), e),
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_manifest(pack_dir: &Path, name: &str, uuid: &str) {
+        fs::create_dir_all(pack_dir).expect("create pack dir");
+        let manifest = serde_json::json!({
+            "format_version": 2,
+            "header": {
+                "name": name,
+                "description": "",
+                "uuid": uuid,
+                "version": [1, 0, 0]
+            },
+            "modules": [{ "type": "data", "uuid": uuid, "version": [1, 0, 0] }]
+        });
+        fs::write(pack_dir.join("manifest.json"), manifest.to_string()).expect("write manifest");
+    }
+
+    #[test]
+    fn development_packs_are_listed_first_and_scripts_are_detected() {
+        let world_dir = std::env::temp_dir().join(format!(
+            "bmcbl-world-datapacks-test-{}",
+            uuid::Uuid::new_v4()
+        ));
+        let dev_pack = world_dir.join("development_behavior_packs").join("dev");
+        write_manifest(
+            &dev_pack,
+            "Dev Pack",
+            "5f0c7d5e-1a53-4c36-9d7e-6a3f1f1c0a01",
+        );
+        fs::create_dir_all(dev_pack.join(SCRIPTS_DIR_NAME)).expect("create scripts dir");
+        write_manifest(
+            &world_dir.join("behavior_packs").join("shipped"),
+            "Shipped Pack",
+            "5f0c7d5e-1a53-4c36-9d7e-6a3f1f1c0a02",
+        );
+        fs::create_dir_all(
+            world_dir
+                .join("development_resource_packs")
+                .join("no_manifest"),
+        )
+        .expect("create empty pack dir");
+
+        let entries = list_world_datapacks_blocking(&world_dir).expect("list datapacks");
+
+        let summary: Vec<_> = entries
+            .iter()
+            .map(|entry| {
+                (
+                    entry.pack_info.name.as_str(),
+                    entry.is_development,
+                    entry.has_scripts,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("Dev Pack", true, true), ("Shipped Pack", false, false)]
+        );
+
+        fs::remove_dir_all(&world_dir).expect("remove test dir");
+    }
+}
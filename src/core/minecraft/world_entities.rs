@@ -0,0 +1,146 @@
+//! 统计存档中各类实体的数量：只读打开 `db/`，按区块查询实体记录（含旧版
+//! `0x32` Entity 标签与新版 actorprefix 存储），按实体标识和维度汇总。
+
+use bedrock_world::{
+    BedrockWorld, CancelFlag, ChunkPos, ChunkRecordQuery, Dimension, ParsedChunkRecordValue,
+    WorldFormatHint, query_chunk_records_many_blocking_with_control,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const ENTITY_COUNT_TIMEOUT: Duration = Duration::from_secs(120);
+const CHUNK_QUERY_BATCH_SIZE: usize = 4096;
+const UNKNOWN_ENTITY_IDENTIFIER: &str = "unknown";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct WorldEntityCounts {
+    pub by_type: HashMap<String, usize>,
+    pub by_dimension: HashMap<String, usize>,
+    pub total: usize,
+}
+
+impl WorldEntityCounts {
+    fn record(&mut self, dimension: Dimension, identifier: Option<&str>) {
+        let identifier = identifier
+            .map(str::trim)
+            .filter(|identifier| !identifier.is_empty())
+            .unwrap_or(UNKNOWN_ENTITY_IDENTIFIER);
+        *self.by_type.entry(identifier.to_string()).or_default() += 1;
+        *self
+            .by_dimension
+            .entry(dimension_name(dimension))
+            .or_default() += 1;
+        self.total += 1;
+    }
+}
+
+/// 统计存档实体数量；大存档扫描较慢，超时后会取消后台扫描
+pub async fn list_world_entities_count(
+    world_folder_path: String,
+) -> Result<WorldEntityCounts, String> {
+    let world_path = PathBuf::from(world_folder_path);
+    if !world_path.join("db").is_dir() {
+        return Err(format!("存档缺少 db 目录: {}", world_path.display()));
+    }
+
+    let cancel = CancelFlag::new();
+    let task = tokio::task::spawn_blocking({
+        let cancel = cancel.clone();
+        move || count_world_entities_blocking(&world_path, &cancel)
+    });
+
+    match tokio::time::timeout(ENTITY_COUNT_TIMEOUT, task).await {
+        Ok(result) => result.map_err(|e| format!("统计实体任务失败: {}", e))?,
+        Err(_) => {
+            cancel.cancel();
+            Err(format!(
+                "统计实体超时（超过 {} 秒）",
+                ENTITY_COUNT_TIMEOUT.as_secs()
+            ))
+        }
+    }
+}
+
+fn count_world_entities_blocking(
+    world_path: &Path,
+    cancel: &CancelFlag,
+) -> Result<WorldEntityCounts, String> {
+    let world = BedrockWorld::open_blocking(
+        world_path,
+        bedrock_world::OpenOptions {
+            read_only: true,
+            format: WorldFormatHint::LevelDb,
+        },
+    )
+    .map_err(|e| format!("打开存档数据库失败: {}", e))?;
+
+    let chunks: Vec<ChunkPos> = world
+        .list_chunk_positions_blocking()
+        .map_err(|e| format!("读取区块列表失败: {}", e))?;
+    let mut counts = WorldEntityCounts::default();
+    for batch in chunks.chunks(CHUNK_QUERY_BATCH_SIZE) {
+        if cancel.is_cancelled() {
+            return Err("统计实体已取消".to_string());
+        }
+        let results = query_chunk_records_many_blocking_with_control(
+            &world,
+            batch.to_vec(),
+            entity_record_query(),
+            cancel,
+        )
+        .map_err(|e| format!("读取实体数据失败: {}", e))?;
+        for result in &results {
+            for record in &result.records {
+                if let ParsedChunkRecordValue::Entities(entities) = &record.value {
+                    for entity in entities {
+                        counts.record(result.pos.dimension, entity.identifier.as_deref());
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(counts)
+}
+
+fn entity_record_query() -> ChunkRecordQuery {
+    ChunkRecordQuery {
+        entities: true,
+        block_entities: false,
+        pending_ticks: false,
+        hardcoded_spawn_areas: false,
+    }
+}
+
+pub(crate) fn dimension_name(dimension: Dimension) -> String {
+    match dimension {
+        Dimension::Overworld => "overworld".to_string(),
+        Dimension::Nether => "nether".to_string(),
+        Dimension::End => "the_end".to_string(),
+        Dimension::Unknown(id) => format!("dimension_{id}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_group_by_type_and_dimension() {
+        let mut counts = WorldEntityCounts::default();
+        counts.record(Dimension::Overworld, Some("minecraft:cow"));
+        counts.record(Dimension::Overworld, Some("minecraft:cow"));
+        counts.record(Dimension::Nether, Some("minecraft:ghast"));
+        counts.record(Dimension::Unknown(7), Some("  "));
+
+        assert_eq!(counts.total, 4);
+        assert_eq!(counts.by_type.get("minecraft:cow"), Some(&2));
+        assert_eq!(counts.by_type.get("minecraft:ghast"), Some(&1));
+        assert_eq!(counts.by_type.get(UNKNOWN_ENTITY_IDENTIFIER), Some(&1));
+        assert_eq!(counts.by_dimension.get("overworld"), Some(&2));
+        assert_eq!(counts.by_dimension.get("nether"), Some(&1));
+        assert_eq!(counts.by_dimension.get("dimension_7"), Some(&1));
+    }
+}
@@ -0,0 +1,273 @@
+//! 存档实验性玩法开关：读写 level.dat 中的 `experiments` 复合标签。
+//! 首次开启任何实验会把存档永久标记为"曾使用实验性玩法"，因此需要调用方明确确认。
+
+use crate::core::minecraft::nbt::{NbtTag, read_level_dat_with_version, write_level_dat};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions, TryLockError};
+use std::path::Path;
+use tracing::{info, warn};
+
+/// 首次为存档开启实验性玩法但未确认时返回的错误
+pub const EXPERIMENTS_REQUIRE_ACKNOWLEDGEMENT_ERROR: &str = "requires_acknowledgement";
+
+const EXPERIMENTS_KEY: &str = "experiments";
+const EVER_USED_KEY: &str = "experiments_ever_used";
+const SAVED_WITH_TOGGLED_KEY: &str = "saved_with_toggled_experiments";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct WorldExperiments {
+    /// 实验名 -> 是否开启，只包含 Byte 类型的开关
+    pub flags: BTreeMap<String, bool>,
+    pub experiments_ever_used: bool,
+    pub saved_with_toggled_experiments: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct WorldExperimentChanges {
+    #[serde(default)]
+    pub toggles: BTreeMap<String, bool>,
+    /// 确认存档将被永久标记为使用过实验性玩法
+    #[serde(default)]
+    pub acknowledge_permanent: bool,
+}
+
+/// 读取存档当前的实验性玩法开关
+pub fn get_world_experiments(world_path: String) -> Result<WorldExperiments, String> {
+    let world_path = Path::new(&world_path);
+    ensure_world_unlocked(world_path)?;
+    let (_, root) = read_level_dat_with_version(&world_path.join("level.dat"))
+        .map_err(|e| format!("读取 level.dat 失败: {}", e))?;
+    Ok(experiments_from_root(&root))
+}
+
+/// 修改实验性玩法开关：写入前把原 level.dat 备份为 level.dat.bak，未知的开关原样保留
+pub fn set_world_experiments(
+    world_path: String,
+    changes: WorldExperimentChanges,
+) -> Result<WorldExperiments, String> {
+    let world_path = Path::new(&world_path);
+    ensure_world_unlocked(world_path)?;
+
+    let level_dat_path = world_path.join("level.dat");
+    let (version, mut root) = read_level_dat_with_version(&level_dat_path)
+        .map_err(|e| format!("读取 level.dat 失败: {}", e))?;
+    apply_experiment_changes(&mut root, &changes)?;
+
+    fs::copy(
+        &level_dat_path,
+        level_dat_path.with_file_name("level.dat.bak"),
+    )
+    .map_err(|e| format!("备份 level.dat 失败: {}", e))?;
+    write_level_dat(&level_dat_path, &root, version)
+        .map_err(|e| format!("写入 level.dat 失败: {}", e))?;
+    info!(
+        world = %world_path.display(),
+        toggles = ?changes.toggles,
+        "已修改存档实验性玩法"
+    );
+    Ok(experiments_from_root(&root))
+}
+
+/// 游戏打开存档时会独占 `db/LOCK`，此时修改 level.dat 会被游戏覆盖
+fn ensure_world_unlocked(world_path: &Path) -> Result<(), String> {
+    if !world_path.join("level.dat").is_file() {
+        return Err(format!("不是有效的存档目录: {}", world_path.display()));
+    }
+    if is_world_locked(world_path) {
+        return Err("存档正在被游戏使用，请先退出存档".to_string());
+    }
+    Ok(())
+}
+
+pub(crate) fn is_world_locked(world_path: &Path) -> bool {
+    let lock_path = world_path.join("db").join("LOCK");
+    if !lock_path.is_file() {
+        return false;
+    }
+    let file = match OpenOptions::new().read(true).write(true).open(&lock_path) {
+        Ok(file) => file,
+        // Windows 上游戏以不共享写入的方式打开 LOCK，再次打开会失败
+        Err(e) => return e.kind() != std::io::ErrorKind::NotFound,
+    };
+    match file.try_lock() {
+        Ok(()) => {
+            let _ = file.unlock();
+            false
+        }
+        Err(TryLockError::WouldBlock) => true,
+        Err(TryLockError::Error(e)) => {
+            warn!("检测存档锁失败 {}: {}", lock_path.display(), e);
+            false
+        }
+    }
+}
+
+fn byte_flag(tag: Option<&NbtTag>) -> Option<bool> {
+    match tag? {
+        NbtTag::Byte(value) => Some(*value != 0),
+        _ => None,
+    }
+}
+
+fn experiments_from_root(root: &NbtTag) -> WorldExperiments {
+    let NbtTag::Compound(root) = root else {
+        return WorldExperiments::default();
+    };
+    let Some(NbtTag::Compound(experiments)) = root.get(EXPERIMENTS_KEY) else {
+        return WorldExperiments::default();
+    };
+
+    let flags = experiments
+        .iter()
+        .filter(|(key, _)| !is_meta_key(key))
+        .filter_map(|(key, tag)| Some((key.clone(), byte_flag(Some(tag))?)))
+        .collect();
+    WorldExperiments {
+        flags,
+        experiments_ever_used: byte_flag(experiments.get(EVER_USED_KEY)).unwrap_or(false),
+        saved_with_toggled_experiments: byte_flag(experiments.get(SAVED_WITH_TOGGLED_KEY))
+            .unwrap_or(false),
+    }
+}
+
+fn is_meta_key(key: &str) -> bool {
+    key == EVER_USED_KEY || key == SAVED_WITH_TOGGLED_KEY
+}
+
+fn apply_experiment_changes(
+    root: &mut NbtTag,
+    changes: &WorldExperimentChanges,
+) -> Result<(), String> {
+    if let Some(key) = changes
+        .toggles
+        .keys()
+        .find(|key| key.trim().is_empty() || is_meta_key(key))
+    {
+        return Err(format!("无效的实验名称: {key:?}"));
+    }
+
+    let current = experiments_from_root(root);
+    let enables_new = changes
+        .toggles
+        .iter()
+        .any(|(key, enabled)| *enabled && !current.flags.get(key).copied().unwrap_or(false));
+    if enables_new && !current.experiments_ever_used && !changes.acknowledge_permanent {
+        return Err(EXPERIMENTS_REQUIRE_ACKNOWLEDGEMENT_ERROR.to_string());
+    }
+
+    let NbtTag::Compound(root) = root else {
+        return Err("level.dat 根标签不是 Compound".to_string());
+    };
+    let experiments = root
+        .entry(EXPERIMENTS_KEY.to_string())
+        .or_insert_with(|| NbtTag::Compound(IndexMap::new()));
+    let NbtTag::Compound(experiments) = experiments else {
+        return Err("level.dat 中的 experiments 不是 Compound".to_string());
+    };
+
+    for (key, enabled) in &changes.toggles {
+        // 关闭不存在的开关无需写入，避免凭空增加键
+        if !*enabled && !experiments.contains_key(key) {
+            continue;
+        }
+        experiments.insert(key.clone(), NbtTag::Byte(i8::from(*enabled)));
+    }
+    if enables_new {
+        experiments.insert(EVER_USED_KEY.to_string(), NbtTag::Byte(1));
+    }
+    let any_enabled = experiments
+        .iter()
+        .any(|(key, tag)| !is_meta_key(key) && byte_flag(Some(tag)) == Some(true));
+    if any_enabled {
+        experiments.insert(SAVED_WITH_TOGGLED_KEY.to_string(), NbtTag::Byte(1));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+
+    fn temp_world(experiments: IndexMap<String, NbtTag>) -> std::path::PathBuf {
+        let world_dir =
+            std::env::temp_dir().join(format!("bmcbl-world-experiments-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(world_dir.join("db")).expect("create world dir");
+        let mut root = IndexMap::new();
+        root.insert("LevelName".to_string(), NbtTag::String("Test".to_string()));
+        root.insert(EXPERIMENTS_KEY.to_string(), NbtTag::Compound(experiments));
+        write_level_dat(&world_dir.join("level.dat"), &NbtTag::Compound(root), 10)
+            .expect("write level.dat");
+        world_dir
+    }
+
+    fn changes(toggles: &[(&str, bool)], acknowledge_permanent: bool) -> WorldExperimentChanges {
+        WorldExperimentChanges {
+            toggles: toggles
+                .iter()
+                .map(|(key, enabled)| (key.to_string(), *enabled))
+                .collect(),
+            acknowledge_permanent,
+        }
+    }
+
+    #[test]
+    fn first_enable_requires_acknowledgement_and_keeps_unknown_keys() {
+        let mut experiments = IndexMap::new();
+        experiments.insert("gametest".to_string(), NbtTag::Byte(0));
+        experiments.insert("future_toggle".to_string(), NbtTag::Int(7));
+        let world_dir = temp_world(experiments);
+        let world_path = world_dir.to_string_lossy().into_owned();
+
+        let error =
+            set_world_experiments(world_path.clone(), changes(&[("gametest", true)], false))
+                .expect_err("must require acknowledgement");
+        assert_eq!(error, EXPERIMENTS_REQUIRE_ACKNOWLEDGEMENT_ERROR);
+        assert!(!world_dir.join("level.dat.bak").exists());
+
+        let updated = set_world_experiments(
+            world_path.clone(),
+            changes(&[("gametest", true), ("data_driven_biomes", true)], true),
+        )
+        .expect("set experiments");
+        assert!(updated.experiments_ever_used);
+        assert!(updated.saved_with_toggled_experiments);
+        assert_eq!(updated.flags.get("data_driven_biomes"), Some(&true));
+        assert!(world_dir.join("level.dat.bak").is_file());
+
+        let (_, root) = read_level_dat_with_version(&world_dir.join("level.dat")).expect("read");
+        let NbtTag::Compound(root) = root else {
+            panic!("root must be a compound");
+        };
+        let Some(NbtTag::Compound(stored)) = root.get(EXPERIMENTS_KEY) else {
+            panic!("experiments must be a compound");
+        };
+        assert!(matches!(stored.get("future_toggle"), Some(NbtTag::Int(7))));
+
+        // 曾经开启过实验后再次修改无需确认
+        let reread =
+            set_world_experiments(world_path.clone(), changes(&[("gametest", false)], false))
+                .expect("disable experiment");
+        assert_eq!(reread.flags.get("gametest"), Some(&false));
+        assert_eq!(get_world_experiments(world_path).expect("get"), reread);
+
+        fs::remove_dir_all(&world_dir).expect("remove test dir");
+    }
+
+    #[test]
+    fn locked_world_is_refused() {
+        let world_dir = temp_world(IndexMap::new());
+        let lock = File::create(world_dir.join("db").join("LOCK")).expect("create lock");
+        lock.try_lock().expect("hold lock");
+
+        let error = get_world_experiments(world_dir.to_string_lossy().into_owned())
+            .expect_err("locked world must be refused");
+        assert!(error.contains("正在被游戏使用"));
+
+        lock.unlock().expect("release lock");
+        drop(lock);
+        fs::remove_dir_all(&world_dir).expect("remove test dir");
+    }
+}
@@ -0,0 +1,156 @@
+//! 冻结存档：分享截图等只读浏览场景下，把 level.dat 标记为 `immutableWorld` 并切换到冒险模式，
+//! 防止误改方块。原游戏模式记录在存档目录的 `bmcbl_freeze_state.json` 中，解冻时还原。
+
+use crate::core::minecraft::nbt::{NbtTag, read_level_dat_with_version, write_level_dat};
+use crate::core::minecraft::world_experiments::is_world_locked;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use tracing::{info, warn};
+
+const FREEZE_STATE_FILE_NAME: &str = "bmcbl_freeze_state.json";
+const IMMUTABLE_WORLD_KEY: &str = "immutableWorld";
+const GAME_TYPE_KEY: &str = "GameType";
+const ADVENTURE_GAME_TYPE: i32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct FreezeState {
+    /// 冻结前的 GameType；level.dat 中原本没有该键时为 `None`
+    original_game_type: Option<i32>,
+}
+
+/// 冻结或解冻存档：写入前把原 level.dat 备份为 level.dat.bak
+pub async fn freeze_world(world_folder_path: String, freeze: bool) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        freeze_world_blocking(Path::new(&world_folder_path), freeze)
+    })
+    .await
+    .map_err(|e| format!("冻结存档任务失败: {}", e))?
+}
+
+fn freeze_world_blocking(world_path: &Path, freeze: bool) -> Result<(), String> {
+    let level_dat_path = world_path.join("level.dat");
+    if !level_dat_path.is_file() {
+        return Err(format!("不是有效的存档目录: {}", world_path.display()));
+    }
+    if is_world_locked(world_path) {
+        return Err("存档正在被游戏使用，请先退出存档".to_string());
+    }
+
+    let (version, mut root) = read_level_dat_with_version(&level_dat_path)
+        .map_err(|e| format!("读取 level.dat 失败: {}", e))?;
+    let NbtTag::Compound(fields) = &mut root else {
+        return Err("level.dat 根标签不是 Compound".to_string());
+    };
+
+    let state_path = world_path.join(FREEZE_STATE_FILE_NAME);
+    let saved_state = read_freeze_state(&state_path);
+    if freeze {
+        // 重复冻结时保留第一次记录的游戏模式，否则会把冒险模式当成原模式
+        let state = saved_state.unwrap_or(FreezeState {
+            original_game_type: match fields.get(GAME_TYPE_KEY) {
+                Some(NbtTag::Int(game_type)) => Some(*game_type),
+                _ => None,
+            },
+        });
+        fields.insert(IMMUTABLE_WORLD_KEY.to_string(), NbtTag::Byte(1));
+        fields.insert(GAME_TYPE_KEY.to_string(), NbtTag::Int(ADVENTURE_GAME_TYPE));
+        let raw = serde_json::to_string_pretty(&state).map_err(|e| e.to_string())?;
+        fs::write(&state_path, raw).map_err(|e| format!("写入冻结状态失败: {}", e))?;
+    } else {
+        fields.insert(IMMUTABLE_WORLD_KEY.to_string(), NbtTag::Byte(0));
+        match saved_state {
+            Some(FreezeState {
+                original_game_type: Some(game_type),
+            }) => {
+                fields.insert(GAME_TYPE_KEY.to_string(), NbtTag::Int(game_type));
+            }
+            Some(FreezeState {
+                original_game_type: None,
+            }) => {
+                fields.shift_remove(GAME_TYPE_KEY);
+            }
+            None => warn!(
+                world = %world_path.display(),
+                "未找到冻结状态记录，只解除 immutableWorld，游戏模式保持不变"
+            ),
+        }
+    }
+
+    fs::copy(
+        &level_dat_path,
+        level_dat_path.with_file_name("level.dat.bak"),
+    )
+    .map_err(|e| format!("备份 level.dat 失败: {}", e))?;
+    write_level_dat(&level_dat_path, &root, version)
+        .map_err(|e| format!("写入 level.dat 失败: {}", e))?;
+    if !freeze
+        && let Err(e) = fs::remove_file(&state_path)
+        && e.kind() != std::io::ErrorKind::NotFound
+    {
+        warn!("删除冻结状态记录失败 {}: {}", state_path.display(), e);
+    }
+
+    info!(world = %world_path.display(), freeze, "已修改存档冻结状态");
+    Ok(())
+}
+
+fn read_freeze_state(path: &Path) -> Option<FreezeState> {
+    let raw = fs::read_to_string(path).ok()?;
+    match serde_json::from_str(&raw) {
+        Ok(state) => Some(state),
+        Err(e) => {
+            warn!("解析冻结状态失败 {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indexmap::IndexMap;
+
+    fn level_fields(world_dir: &Path) -> IndexMap<String, NbtTag> {
+        let (_, root) = read_level_dat_with_version(&world_dir.join("level.dat")).expect("read");
+        let NbtTag::Compound(fields) = root else {
+            panic!("root must be a compound");
+        };
+        fields
+    }
+
+    #[test]
+    fn freeze_switches_to_adventure_and_unfreeze_restores_game_type() {
+        let world_dir =
+            std::env::temp_dir().join(format!("bmcbl-world-freeze-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(world_dir.join("db")).expect("create world dir");
+        let mut root = IndexMap::new();
+        root.insert(GAME_TYPE_KEY.to_string(), NbtTag::Int(1));
+        write_level_dat(&world_dir.join("level.dat"), &NbtTag::Compound(root), 10)
+            .expect("write level.dat");
+
+        freeze_world_blocking(&world_dir, true).expect("freeze");
+        // 再次冻结不能覆盖记录的原模式
+        freeze_world_blocking(&world_dir, true).expect("freeze again");
+        let frozen = level_fields(&world_dir);
+        assert!(matches!(
+            frozen.get(IMMUTABLE_WORLD_KEY),
+            Some(NbtTag::Byte(1))
+        ));
+        assert!(matches!(
+            frozen.get(GAME_TYPE_KEY),
+            Some(NbtTag::Int(ADVENTURE_GAME_TYPE))
+        ));
+
+        freeze_world_blocking(&world_dir, false).expect("unfreeze");
+        let restored = level_fields(&world_dir);
+        assert!(matches!(
+            restored.get(IMMUTABLE_WORLD_KEY),
+            Some(NbtTag::Byte(0))
+        ));
+        assert!(matches!(restored.get(GAME_TYPE_KEY), Some(NbtTag::Int(1))));
+        assert!(!world_dir.join(FREEZE_STATE_FILE_NAME).exists());
+
+        fs::remove_dir_all(&world_dir).expect("remove test dir");
+    }
+}
@@ -0,0 +1,421 @@
+//! 读取存档中的玩家摘要：LevelDB 中的 `~local_player` / `player_*` 记录，
+//! 以及部分旧存档或第三方工具导出的 `players/` 目录下的 NBT 文件。
+
+use crate::core::minecraft::nbt::{NbtTag, parse_root_nbt, parse_root_nbt_with_header};
+use crate::core::minecraft::world_experiments::is_world_locked;
+use bedrock_world::{BedrockWorld, PlayerId, WorldFormatHint};
+use indexmap::IndexMap;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+const PLAYERS_DIR_NAME: &str = "players";
+const HEALTH_ATTRIBUTE: &str = "minecraft:health";
+const HUNGER_ATTRIBUTE: &str = "minecraft:player.hunger";
+/// 摘要只包含背包前 36 格（快捷栏 + 主背包）
+const INVENTORY_SUMMARY_SLOTS: i32 = 36;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlayerSnapshot {
+    pub player_id: String,
+    pub position: [f64; 3],
+    pub dimension: i32,
+    pub health: f32,
+    pub food_level: i32,
+    pub game_mode: i32,
+    pub inventory_item_count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InventoryItemSummary {
+    pub slot: i32,
+    /// 原样返回存档中的物品标识，新版本游戏的未知物品也不会被过滤
+    pub identifier: String,
+    pub count: i32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct WorldPlayerSummary {
+    /// 没有可用的本地玩家数据时说明原因
+    pub reason: Option<String>,
+    pub position: Option<[f64; 3]>,
+    pub dimension: Option<i32>,
+    pub level: Option<i32>,
+    /// 当前等级的经验进度，0~1
+    pub level_progress: Option<f32>,
+    pub inventory: Vec<InventoryItemSummary>,
+}
+
+impl WorldPlayerSummary {
+    fn empty(reason: impl Into<String>) -> Self {
+        Self {
+            reason: Some(reason.into()),
+            ..Self::default()
+        }
+    }
+}
+
+/// 只读预览本地玩家（`~local_player`）的位置、等级与背包，供存档页展示
+pub async fn get_world_player_summary(world_path: String) -> Result<WorldPlayerSummary, String> {
+    let world_path = PathBuf::from(world_path);
+    tokio::task::spawn_blocking(move || world_player_summary_blocking(&world_path))
+        .await
+        .map_err(|e| format!("读取玩家数据任务失败: {}", e))?
+}
+
+fn world_player_summary_blocking(world_path: &Path) -> Result<WorldPlayerSummary, String> {
+    if !world_path.join("db").is_dir() {
+        return Ok(WorldPlayerSummary::empty("存档没有 LevelDB 数据库"));
+    }
+    if is_world_locked(world_path) {
+        return Err("存档正在被游戏使用，请先退出存档".to_string());
+    }
+
+    let world = BedrockWorld::open_blocking(
+        world_path,
+        bedrock_world::OpenOptions {
+            read_only: true,
+            format: WorldFormatHint::LevelDb,
+        },
+    )
+    .map_err(|e| format!("打开存档数据库失败: {}", e))?;
+    match world.get_player_blocking(&PlayerId::Local) {
+        Ok(Some(data)) => Ok(summary_from_nbt(&data.nbt)),
+        Ok(None) => Ok(WorldPlayerSummary::empty("存档中没有本地玩家记录")),
+        Err(e) => {
+            warn!("读取本地玩家记录失败 {}: {}", world_path.display(), e);
+            Ok(WorldPlayerSummary::empty(format!(
+                "本地玩家记录无法解析: {e}"
+            )))
+        }
+    }
+}
+
+fn summary_from_nbt(tag: &NbtTag) -> WorldPlayerSummary {
+    let NbtTag::Compound(root) = tag else {
+        return WorldPlayerSummary::empty("本地玩家记录不是 Compound");
+    };
+    let mut inventory: Vec<InventoryItemSummary> = match root.get("Inventory") {
+        Some(NbtTag::List(items)) => items
+            .iter()
+            .filter_map(compound)
+            .filter_map(inventory_item)
+            .filter(|item| item.slot < INVENTORY_SUMMARY_SLOTS)
+            .collect(),
+        _ => Vec::new(),
+    };
+    inventory.sort_by_key(|item| item.slot);
+
+    WorldPlayerSummary {
+        reason: None,
+        position: vec3_value(root.get("Pos")),
+        dimension: int_value(root.get("DimensionId")),
+        level: int_value(root.get("PlayerLevel")),
+        level_progress: root
+            .get("PlayerLevelProgress")
+            .and_then(number_value)
+            .map(|value| value as f32),
+        inventory,
+    }
+}
+
+fn inventory_item(item: &IndexMap<String, NbtTag>) -> Option<InventoryItemSummary> {
+    let count = int_value(item.get("Count")).filter(|count| *count > 0)?;
+    // 旧版存档只有数字 id，没有 Name
+    let identifier = match (item.get("Name"), item.get("id")) {
+        (Some(NbtTag::String(name)), _) if !name.is_empty() => name.clone(),
+        (_, id) => int_value(id)?.to_string(),
+    };
+    Some(InventoryItemSummary {
+        slot: int_value(item.get("Slot")).unwrap_or(0),
+        identifier,
+        count,
+    })
+}
+
+/// 列出存档内所有玩家的位置、状态与背包物品数量
+pub async fn list_world_player_data(
+    world_folder_path: String,
+) -> Result<Vec<PlayerSnapshot>, String> {
+    let world_path = PathBuf::from(world_folder_path);
+    tokio::task::spawn_blocking(move || list_world_player_data_blocking(&world_path))
+        .await
+        .map_err(|e| format!("读取玩家数据任务失败: {}", e))?
+}
+
+fn list_world_player_data_blocking(world_path: &Path) -> Result<Vec<PlayerSnapshot>, String> {
+    let mut snapshots = Vec::new();
+
+    if world_path.join("db").is_dir() {
+        let world = BedrockWorld::open_blocking(
+            world_path,
+            bedrock_world::OpenOptions {
+                read_only: true,
+                format: WorldFormatHint::LevelDb,
+            },
+        )
+        .map_err(|e| format!("打开存档数据库失败: {}", e))?;
+        let players = world
+            .list_players_blocking()
+            .map_err(|e| format!("读取玩家列表失败: {}", e))?;
+        for id in players {
+            match world.get_player_blocking(&id) {
+                Ok(Some(data)) => {
+                    if let Some(snapshot) = snapshot_from_nbt(player_id_label(&id), &data.nbt) {
+                        snapshots.push(snapshot);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => warn!("读取玩家记录失败 {}: {}", player_id_label(&id), e),
+            }
+        }
+    }
+
+    snapshots.extend(read_players_dir(&world_path.join(PLAYERS_DIR_NAME)));
+    Ok(snapshots)
+}
+
+fn read_players_dir(players_dir: &Path) -> Vec<PlayerSnapshot> {
+    let Ok(entries) = fs::read_dir(players_dir) else {
+        return Vec::new();
+    };
+    let mut snapshots = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let data = match fs::read(&path) {
+            Ok(data) => data,
+            Err(e) => {
+                warn!("读取玩家文件失败 {}: {}", path.display(), e);
+                continue;
+            }
+        };
+        // 带 level.dat 风格文件头的优先，失败再按裸 NBT 解析
+        let root = match parse_root_nbt_with_header(&data).or_else(|_| parse_root_nbt(&data)) {
+            Ok(root) => root,
+            Err(e) => {
+                warn!("解析玩家文件失败 {}: {}", path.display(), e);
+                continue;
+            }
+        };
+        let player_id = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if let Some(snapshot) = snapshot_from_nbt(player_id, &root) {
+            snapshots.push(snapshot);
+        }
+    }
+    snapshots.sort_by(|left, right| left.player_id.cmp(&right.player_id));
+    snapshots
+}
+
+fn player_id_label(id: &PlayerId) -> String {
+    match id {
+        PlayerId::Local => "~local_player".to_string(),
+        PlayerId::Xuid(xuid) => format!("player_{xuid}"),
+        PlayerId::LegacyLevelDat => "level.dat".to_string(),
+        PlayerId::Unknown(value) => value.clone(),
+    }
+}
+
+fn snapshot_from_nbt(player_id: String, tag: &NbtTag) -> Option<PlayerSnapshot> {
+    let NbtTag::Compound(root) = tag else {
+        warn!("玩家 NBT 根节点不是 Compound: {}", player_id);
+        return None;
+    };
+    let inventory_item_count = match root.get("Inventory") {
+        Some(NbtTag::List(items)) => items
+            .iter()
+            .filter(|item| {
+                compound(item)
+                    .and_then(|item| int_value(item.get("Count")))
+                    .is_some_and(|count| count > 0)
+            })
+            .count(),
+        _ => 0,
+    };
+
+    Some(PlayerSnapshot {
+        player_id,
+        position: vec3_value(root.get("Pos")).unwrap_or_default(),
+        dimension: int_value(root.get("DimensionId")).unwrap_or(0),
+        health: attribute_current(root, HEALTH_ATTRIBUTE).unwrap_or(0.0),
+        food_level: attribute_current(root, HUNGER_ATTRIBUTE)
+            .map(|value| value.round() as i32)
+            .unwrap_or(0),
+        game_mode: int_value(root.get("PlayerGameType")).unwrap_or(0),
+        inventory_item_count,
+    })
+}
+
+/// 生命值、饥饿值存放在 `Attributes` 列表中，按 `Name` 查找 `Current`
+fn attribute_current(root: &IndexMap<String, NbtTag>, name: &str) -> Option<f32> {
+    let NbtTag::List(attributes) = root.get("Attributes")? else {
+        return None;
+    };
+    attributes
+        .iter()
+        .filter_map(compound)
+        .find(|attribute| matches!(attribute.get("Name"), Some(NbtTag::String(value)) if value == name))
+        .and_then(|attribute| number_value(attribute.get("Current")?))
+        .map(|value| value as f32)
+}
+
+fn compound(tag: &NbtTag) -> Option<&IndexMap<String, NbtTag>> {
+    match tag {
+        NbtTag::Compound(values) => Some(values),
+        _ => None,
+    }
+}
+
+fn int_value(tag: Option<&NbtTag>) -> Option<i32> {
+    match tag? {
+        NbtTag::Byte(value) => Some(i32::from(*value)),
+        NbtTag::Short(value) => Some(i32::from(*value)),
+        NbtTag::Int(value) => Some(*value),
+        NbtTag::Long(value) => i32::try_from(*value).ok(),
+        _ => None,
+    }
+}
+
+fn number_value(tag: &NbtTag) -> Option<f64> {
+    match tag {
+        NbtTag::Byte(value) => Some(f64::from(*value)),
+        NbtTag::Short(value) => Some(f64::from(*value)),
+        NbtTag::Int(value) => Some(f64::from(*value)),
+        NbtTag::Long(value) => Some(*value as f64),
+        NbtTag::Float(value) => Some(f64::from(*value)),
+        NbtTag::Double(value) => Some(*value),
+        _ => None,
+    }
+}
+
+fn vec3_value(tag: Option<&NbtTag>) -> Option<[f64; 3]> {
+    let NbtTag::List(values) = tag? else {
+        return None;
+    };
+    match values.as_slice() {
+        [x, y, z, ..] => Some([number_value(x)?, number_value(y)?, number_value(z)?]),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attribute(name: &str, current: f32) -> NbtTag {
+        let mut values = IndexMap::new();
+        values.insert("Name".to_string(), NbtTag::String(name.to_string()));
+        values.insert("Current".to_string(), NbtTag::Float(current));
+        NbtTag::Compound(values)
+    }
+
+    fn item(count: i8) -> NbtTag {
+        let mut values = IndexMap::new();
+        values.insert(
+            "Name".to_string(),
+            NbtTag::String("minecraft:stone".to_string()),
+        );
+        values.insert("Count".to_string(), NbtTag::Byte(count));
+        NbtTag::Compound(values)
+    }
+
+    #[test]
+    fn snapshot_reads_position_attributes_and_inventory() {
+        let mut root = IndexMap::new();
+        root.insert(
+            "Pos".to_string(),
+            NbtTag::List(vec![
+                NbtTag::Float(1.5),
+                NbtTag::Float(64.0),
+                NbtTag::Float(-3.5),
+            ]),
+        );
+        root.insert("DimensionId".to_string(), NbtTag::Int(1));
+        root.insert("PlayerGameType".to_string(), NbtTag::Int(1));
+        root.insert(
+            "Attributes".to_string(),
+            NbtTag::List(vec![
+                attribute(HEALTH_ATTRIBUTE, 17.0),
+                attribute(HUNGER_ATTRIBUTE, 12.0),
+            ]),
+        );
+        root.insert(
+            "Inventory".to_string(),
+            NbtTag::List(vec![item(3), item(0), item(1)]),
+        );
+
+        let snapshot = snapshot_from_nbt("~local_player".to_string(), &NbtTag::Compound(root))
+            .expect("compound root should produce a snapshot");
+
+        assert_eq!(snapshot.position, [1.5, 64.0, -3.5]);
+        assert_eq!(snapshot.dimension, 1);
+        assert_eq!(snapshot.health, 17.0);
+        assert_eq!(snapshot.food_level, 12);
+        assert_eq!(snapshot.game_mode, 1);
+        assert_eq!(snapshot.inventory_item_count, 2);
+    }
+
+    #[test]
+    fn summary_keeps_unknown_item_ids_and_orders_slots() {
+        let mut future_item = IndexMap::new();
+        future_item.insert(
+            "Name".to_string(),
+            NbtTag::String("minecraft:future_gadget".to_string()),
+        );
+        future_item.insert("Count".to_string(), NbtTag::Byte(2));
+        future_item.insert("Slot".to_string(), NbtTag::Byte(4));
+        let mut legacy_item = IndexMap::new();
+        legacy_item.insert("id".to_string(), NbtTag::Short(276));
+        legacy_item.insert("Count".to_string(), NbtTag::Byte(1));
+        legacy_item.insert("Slot".to_string(), NbtTag::Byte(0));
+        let mut armor_slot = IndexMap::new();
+        armor_slot.insert(
+            "Name".to_string(),
+            NbtTag::String("minecraft:stone".to_string()),
+        );
+        armor_slot.insert("Count".to_string(), NbtTag::Byte(1));
+        armor_slot.insert("Slot".to_string(), NbtTag::Byte(40));
+
+        let mut root = IndexMap::new();
+        root.insert("PlayerLevel".to_string(), NbtTag::Int(30));
+        root.insert("PlayerLevelProgress".to_string(), NbtTag::Float(0.5));
+        root.insert(
+            "Inventory".to_string(),
+            NbtTag::List(vec![
+                NbtTag::Compound(future_item),
+                item(0),
+                NbtTag::Compound(legacy_item),
+                NbtTag::Compound(armor_slot),
+            ]),
+        );
+
+        let summary = summary_from_nbt(&NbtTag::Compound(root));
+
+        assert_eq!(summary.reason, None);
+        assert_eq!(summary.position, None);
+        assert_eq!(summary.level, Some(30));
+        assert_eq!(summary.level_progress, Some(0.5));
+        assert_eq!(
+            summary.inventory,
+            vec![
+                InventoryItemSummary {
+                    slot: 0,
+                    identifier: "276".to_string(),
+                    count: 1,
+                },
+                InventoryItemSummary {
+                    slot: 4,
+                    identifier: "minecraft:future_gadget".to_string(),
+                    count: 2,
+                },
+            ]
+        );
+        assert!(summary_from_nbt(&NbtTag::Int(0)).reason.is_some());
+    }
+}
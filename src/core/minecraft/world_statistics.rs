@@ -0,0 +1,134 @@
+//! 存档游玩统计：从 level.dat 汇总游玩时长、天数和时间戳。
+//! level.dat 中不存在的字段返回 `None`，不按 0 处理，避免把"未记录"显示成"从未发生"。
+
+use crate::core::minecraft::nbt::{NbtTag, read_level_dat};
+use indexmap::IndexMap;
+use serde::Serialize;
+use std::path::Path;
+
+const TICKS_PER_SECOND: i64 = 20;
+const TICKS_PER_DAY: i64 = 24_000;
+
+const CURRENT_TICK_KEY: &str = "currentTick";
+const TIME_KEY: &str = "Time";
+const LAST_PLAYED_KEY: &str = "LastPlayed";
+/// 以下键原版不写入，部分整合包/教育版会记录；按出现的第一个为准
+const DEATH_COUNT_KEYS: [&str; 2] = ["deathCount", "DeathCount"];
+const MOB_KILLS_KEYS: [&str; 2] = ["mobKills", "MobKills"];
+const PLAYER_KILLS_KEYS: [&str; 2] = ["playerKills", "PlayerKills"];
+const CREATED_KEYS: [&str; 2] = ["CreationTime", "creationTime"];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct WorldStatistics {
+    pub time_played_ticks: Option<i64>,
+    /// 例如 `12 days, 3 hours`
+    pub time_played_human: Option<String>,
+    pub day_count: Option<i64>,
+    pub death_count: Option<i32>,
+    pub mob_kills: Option<i32>,
+    pub player_kills: Option<i32>,
+    /// Unix 秒
+    pub last_played_timestamp: Option<i64>,
+    /// Unix 秒
+    pub created_timestamp: Option<i64>,
+}
+
+fn integer(fields: &IndexMap<String, NbtTag>, key: &str) -> Option<i64> {
+    match fields.get(key)? {
+        NbtTag::Byte(value) => Some(i64::from(*value)),
+        NbtTag::Short(value) => Some(i64::from(*value)),
+        NbtTag::Int(value) => Some(i64::from(*value)),
+        NbtTag::Long(value) => Some(*value),
+        _ => None,
+    }
+}
+
+fn first_integer(fields: &IndexMap<String, NbtTag>, keys: &[&str]) -> Option<i64> {
+    keys.iter().find_map(|key| integer(fields, key))
+}
+
+fn first_count(fields: &IndexMap<String, NbtTag>, keys: &[&str]) -> Option<i32> {
+    first_integer(fields, keys).and_then(|value| i32::try_from(value).ok())
+}
+
+fn plural(value: i64, unit: &str) -> String {
+    if value == 1 {
+        format!("1 {unit}")
+    } else {
+        format!("{value} {unit}s")
+    }
+}
+
+/// 按游戏刻换算为最多两个单位的时长，例如 `12 days, 3 hours`
+fn format_ticks_human(ticks: i64) -> String {
+    let total_minutes = ticks.max(0) / TICKS_PER_SECOND / 60;
+    let days = total_minutes / (24 * 60);
+    let hours = total_minutes / 60 % 24;
+    let minutes = total_minutes % 60;
+    let units = [(days, "day"), (hours, "hour"), (minutes, "minute")];
+    let parts = units
+        .iter()
+        .skip_while(|(value, _)| *value == 0)
+        .take(2)
+        .filter(|(value, _)| *value > 0)
+        .map(|(value, unit)| plural(*value, unit))
+        .collect::<Vec<_>>();
+    if parts.is_empty() {
+        plural(0, "minute")
+    } else {
+        parts.join(", ")
+    }
+}
+
+fn statistics_from_root(root: &NbtTag) -> WorldStatistics {
+    let NbtTag::Compound(fields) = root else {
+        return WorldStatistics::default();
+    };
+    let time_played_ticks = integer(fields, CURRENT_TICK_KEY);
+    WorldStatistics {
+        time_played_ticks,
+        time_played_human: time_played_ticks.map(format_ticks_human),
+        day_count: integer(fields, TIME_KEY).map(|time| time.max(0) / TICKS_PER_DAY),
+        death_count: first_count(fields, &DEATH_COUNT_KEYS),
+        mob_kills: first_count(fields, &MOB_KILLS_KEYS),
+        player_kills: first_count(fields, &PLAYER_KILLS_KEYS),
+        last_played_timestamp: integer(fields, LAST_PLAYED_KEY),
+        created_timestamp: first_integer(fields, &CREATED_KEYS),
+    }
+}
+
+pub fn get_world_statistics(world_folder_path: String) -> Result<WorldStatistics, String> {
+    let level_dat_path = Path::new(&world_folder_path).join("level.dat");
+    if !level_dat_path.is_file() {
+        return Err(format!("不是有效的存档目录: {}", world_folder_path));
+    }
+    let root =
+        read_level_dat(&level_dat_path).map_err(|e| format!("读取 level.dat 失败: {}", e))?;
+    Ok(statistics_from_root(&root))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statistics_keep_missing_fields_empty_and_format_play_time() {
+        let mut fields = IndexMap::new();
+        // 12 天 3 小时 20 分
+        let ticks = ((12 * 24 + 3) * 60 + 20) * 60 * TICKS_PER_SECOND;
+        fields.insert(CURRENT_TICK_KEY.to_string(), NbtTag::Long(ticks));
+        fields.insert(TIME_KEY.to_string(), NbtTag::Long(TICKS_PER_DAY * 41 + 100));
+        fields.insert(LAST_PLAYED_KEY.to_string(), NbtTag::Long(1_700_000_000));
+
+        let stats = statistics_from_root(&NbtTag::Compound(fields));
+
+        assert_eq!(stats.time_played_ticks, Some(ticks));
+        assert_eq!(stats.time_played_human.as_deref(), Some("12 days, 3 hours"));
+        assert_eq!(stats.day_count, Some(41));
+        assert_eq!(stats.last_played_timestamp, Some(1_700_000_000));
+        assert_eq!(stats.death_count, None);
+        assert_eq!(stats.created_timestamp, None);
+        assert_eq!(format_ticks_human(20 * 60 * 61), "1 hour, 1 minute");
+        assert_eq!(format_ticks_human(0), "0 minutes");
+    }
+}
@@ -0,0 +1,236 @@
+//! 列出存档中的结构方块：只读打开 `db/`，按区块查询 `0x31` BlockEntity 记录，
+//! 保留 `id == "StructureBlock"` 的方块实体并读取结构名、模式与数据字段。
+
+use crate::core::minecraft::nbt::NbtTag;
+use bedrock_world::{
+    BedrockWorld, CancelFlag, ChunkPos, ChunkRecordQuery, ParsedBlockEntity,
+    ParsedChunkRecordValue, WorldFormatHint, query_chunk_records_many_blocking_with_control,
+};
+use indexmap::IndexMap;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const STRUCTURE_BLOCK_TIMEOUT: Duration = Duration::from_secs(30);
+const CHUNK_QUERY_BATCH_SIZE: usize = 4096;
+const STRUCTURE_BLOCK_ID: &str = "StructureBlock";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StructureBlockEntry {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    pub dimension: i32,
+    pub structure_name: String,
+    /// `save` / `load` / `corner` / `data` / `export`
+    pub mode: String,
+    pub data_field: String,
+}
+
+/// 列出存档中的结构方块；超时后会取消后台扫描
+pub async fn list_world_structure_blocks(
+    world_folder_path: String,
+) -> Result<Vec<StructureBlockEntry>, String> {
+    let world_path = PathBuf::from(world_folder_path);
+    if !world_path.join("db").is_dir() {
+        return Err(format!("存档缺少 db 目录: {}", world_path.display()));
+    }
+
+    let cancel = CancelFlag::new();
+    let task = tokio::task::spawn_blocking({
+        let cancel = cancel.clone();
+        move || list_structure_blocks_blocking(&world_path, &cancel)
+    });
+
+    match tokio::time::timeout(STRUCTURE_BLOCK_TIMEOUT, task).await {
+        Ok(result) => result.map_err(|e| format!("读取结构方块任务失败: {}", e))?,
+        Err(_) => {
+            cancel.cancel();
+            Err(format!(
+                "读取结构方块超时（超过 {} 秒）",
+                STRUCTURE_BLOCK_TIMEOUT.as_secs()
+            ))
+        }
+    }
+}
+
+fn list_structure_blocks_blocking(
+    world_path: &Path,
+    cancel: &CancelFlag,
+) -> Result<Vec<StructureBlockEntry>, String> {
+    let world = BedrockWorld::open_blocking(
+        world_path,
+        bedrock_world::OpenOptions {
+            read_only: true,
+            format: WorldFormatHint::LevelDb,
+        },
+    )
+    .map_err(|e| format!("打开存档数据库失败: {}", e))?;
+
+    let chunks: Vec<ChunkPos> = world
+        .list_chunk_positions_blocking()
+        .map_err(|e| format!("读取区块列表失败: {}", e))?;
+    let mut entries = Vec::new();
+    for batch in chunks.chunks(CHUNK_QUERY_BATCH_SIZE) {
+        if cancel.is_cancelled() {
+            return Err("读取结构方块已取消".to_string());
+        }
+        let results = query_chunk_records_many_blocking_with_control(
+            &world,
+            batch.to_vec(),
+            block_entity_record_query(),
+            cancel,
+        )
+        .map_err(|e| format!("读取方块实体数据失败: {}", e))?;
+        for result in &results {
+            for record in &result.records {
+                if let ParsedChunkRecordValue::BlockEntities(block_entities) = &record.value {
+                    entries.extend(block_entities.iter().filter_map(|block_entity| {
+                        structure_block_entry(block_entity, result.pos.dimension.id())
+                    }));
+                }
+            }
+        }
+    }
+
+    entries.sort_by_key(|entry| (entry.dimension, entry.x, entry.y, entry.z));
+    Ok(entries)
+}
+
+fn block_entity_record_query() -> ChunkRecordQuery {
+    ChunkRecordQuery {
+        entities: false,
+        block_entities: true,
+        pending_ticks: false,
+        hardcoded_spawn_areas: false,
+    }
+}
+
+fn structure_block_entry(
+    block_entity: &ParsedBlockEntity,
+    dimension: i32,
+) -> Option<StructureBlockEntry> {
+    let NbtTag::Compound(root) = &block_entity.nbt else {
+        return None;
+    };
+    let id = block_entity
+        .id
+        .as_deref()
+        .or_else(|| string_value(root, "id"))?;
+    if id != STRUCTURE_BLOCK_ID {
+        return None;
+    }
+    let [x, y, z] = block_entity.position.or_else(|| {
+        Some([
+            int_value(root, "x")?,
+            int_value(root, "y")?,
+            int_value(root, "z")?,
+        ])
+    })?;
+
+    Some(StructureBlockEntry {
+        x,
+        y,
+        z,
+        dimension,
+        structure_name: string_value(root, "structureName")
+            .unwrap_or_default()
+            .to_string(),
+        mode: structure_block_mode(int_value(root, "data")),
+        data_field: string_value(root, "dataField")
+            .unwrap_or_default()
+            .to_string(),
+    })
+}
+
+/// `data` 字段对应游戏内的结构方块模式
+fn structure_block_mode(data: Option<i32>) -> String {
+    match data {
+        Some(0) => "data".to_string(),
+        Some(1) => "save".to_string(),
+        Some(2) => "load".to_string(),
+        Some(3) => "corner".to_string(),
+        Some(4) => "invalid".to_string(),
+        Some(5) => "export".to_string(),
+        Some(other) => format!("unknown_{other}"),
+        None => "unknown".to_string(),
+    }
+}
+
+fn string_value<'a>(root: &'a IndexMap<String, NbtTag>, key: &str) -> Option<&'a str> {
+    match root.get(key) {
+        Some(NbtTag::String(value)) => Some(value),
+        _ => None,
+    }
+}
+
+fn int_value(root: &IndexMap<String, NbtTag>, key: &str) -> Option<i32> {
+    match root.get(key) {
+        Some(NbtTag::Int(value)) => Some(*value),
+        Some(NbtTag::Short(value)) => Some(i32::from(*value)),
+        Some(NbtTag::Byte(value)) => Some(i32::from(*value)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block_entity(id: &str, fields: Vec<(&str, NbtTag)>) -> ParsedBlockEntity {
+        let mut root = IndexMap::new();
+        root.insert("id".to_string(), NbtTag::String(id.to_string()));
+        for (key, value) in fields {
+            root.insert(key.to_string(), value);
+        }
+        ParsedBlockEntity {
+            id: Some(id.to_string()),
+            position: None,
+            is_movable: None,
+            custom_name: None,
+            items: Vec::new(),
+            nbt: NbtTag::Compound(root),
+        }
+    }
+
+    #[test]
+    fn reads_structure_block_fields_and_skips_other_block_entities() {
+        let structure = block_entity(
+            STRUCTURE_BLOCK_ID,
+            vec![
+                ("x", NbtTag::Int(12)),
+                ("y", NbtTag::Int(-30)),
+                ("z", NbtTag::Int(-7)),
+                (
+                    "structureName",
+                    NbtTag::String("mystructure:house".to_string()),
+                ),
+                ("data", NbtTag::Int(1)),
+                ("dataField", NbtTag::String("spawn".to_string())),
+            ],
+        );
+        assert_eq!(
+            structure_block_entry(&structure, 1),
+            Some(StructureBlockEntry {
+                x: 12,
+                y: -30,
+                z: -7,
+                dimension: 1,
+                structure_name: "mystructure:house".to_string(),
+                mode: "save".to_string(),
+                data_field: "spawn".to_string(),
+            })
+        );
+
+        let chest = block_entity(
+            "Chest",
+            vec![
+                ("x", NbtTag::Int(0)),
+                ("y", NbtTag::Int(64)),
+                ("z", NbtTag::Int(0)),
+            ],
+        );
+        assert_eq!(structure_block_entry(&chest, 0), None);
+        assert_eq!(structure_block_mode(Some(9)), "unknown_9");
+    }
+}
@@ -0,0 +1,181 @@
+//! zip 条目名解码：国内压缩工具打出的包常用 GBK 写条目名且不设置 UTF-8 标志，
+//! zip 库按 CP437 解码后会得到乱码目录名。导入相关的扫描、清单匹配和解压都经由这里取条目名。
+
+use crate::core::minecraft::import::sanitize_filename;
+use encoding_rs::Encoding;
+use std::cell::OnceCell;
+use std::io::{Read, Seek};
+use std::path::{Component, Path, PathBuf};
+use zip::ZipArchive;
+use zip::read::ZipFile;
+use zip::result::{ZipError, ZipResult};
+
+/// 配置项为 0 时按系统代码页自动选择
+const AUTO_CODEPAGE: u32 = 0;
+const UTF8_CODEPAGE: u32 = 65001;
+const GBK_CODEPAGE: u32 = 936;
+const BIG5_CODEPAGE: u32 = 950;
+
+fn encoding_for_codepage(codepage: u32) -> Option<&'static Encoding> {
+    Some(match codepage {
+        936 => encoding_rs::GBK,
+        54936 => encoding_rs::GB18030,
+        950 => encoding_rs::BIG5,
+        932 => encoding_rs::SHIFT_JIS,
+        949 => encoding_rs::EUC_KR,
+        866 => encoding_rs::IBM866,
+        874 => encoding_rs::WINDOWS_874,
+        1250 => encoding_rs::WINDOWS_1250,
+        1251 => encoding_rs::WINDOWS_1251,
+        1252 => encoding_rs::WINDOWS_1252,
+        1253 => encoding_rs::WINDOWS_1253,
+        1254 => encoding_rs::WINDOWS_1254,
+        1255 => encoding_rs::WINDOWS_1255,
+        1256 => encoding_rs::WINDOWS_1256,
+        1257 => encoding_rs::WINDOWS_1257,
+        1258 => encoding_rs::WINDOWS_1258,
+        _ => return None,
+    })
+}
+
+/// 系统代码页为 UTF-8（或非 Windows）时按界面语言推断，中文系统默认 GBK
+fn auto_codepage() -> u32 {
+    let codepage = crate::utils::system_info::detect_system_encoding();
+    if codepage != UTF8_CODEPAGE {
+        return codepage;
+    }
+    let language = crate::utils::system_info::get_system_language();
+    match language.as_str() {
+        "zh-CN" | "zh-SG" | "zh-Hans" => GBK_CODEPAGE,
+        "zh-TW" | "zh-HK" | "zh-MO" | "zh-Hant" => BIG5_CODEPAGE,
+        _ => codepage,
+    }
+}
+
+/// 非 UTF-8 条目名使用的回退编码，由配置项 `import.zip_fallback_codepage` 决定
+fn fallback_encoding() -> Option<&'static Encoding> {
+    let configured = crate::config::config::read_config()
+        .map(|config| config.import.zip_fallback_codepage)
+        .unwrap_or(AUTO_CODEPAGE);
+    let codepage = if configured == AUTO_CODEPAGE {
+        auto_codepage()
+    } else {
+        configured
+    };
+    encoding_for_codepage(codepage)
+}
+
+/// 原始字节是合法 UTF-8 时直接使用（不少工具写 UTF-8 却不设标志），否则按回退编码严格解码
+fn decode_entry_name(raw: &[u8], fallback: Option<&'static Encoding>) -> Option<String> {
+    if let Ok(name) = std::str::from_utf8(raw) {
+        return Some(name.to_string());
+    }
+    fallback?
+        .decode_without_bom_handling_and_without_replacement(raw)
+        .map(|name| name.into_owned())
+}
+
+/// 单个归档内共用的条目名解码器：遇到第一个非 UTF-8 条目时才读取配置，之后整个归档复用
+#[derive(Default)]
+pub(crate) struct EntryNameDecoder {
+    fallback: OnceCell<Option<&'static Encoding>>,
+}
+
+impl EntryNameDecoder {
+    /// 条目名：UTF-8 → 回退代码页 → zip 库自身的解码结果
+    pub(crate) fn entry_name<R: Read + ?Sized>(&self, file: &ZipFile<'_, R>) -> ZipResult<String> {
+        let raw = file.name_raw();
+        let fallback = if std::str::from_utf8(raw).is_ok() {
+            None
+        } else {
+            *self.fallback.get_or_init(fallback_encoding)
+        };
+        match decode_entry_name(raw, fallback) {
+            Some(name) => Ok(name),
+            None => Ok(file.name()?.to_string()),
+        }
+    }
+}
+
+/// 按解码后的条目名查找索引；`by_name` 只认 zip 库的 CP437 结果，GBK 条目会找不到
+pub(crate) fn find_entry_index<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    name: &str,
+) -> Option<usize> {
+    // UTF-8 条目两种解码一致，先走 zip 库的哈希索引
+    if let Some(index) = archive.index_for_name(name) {
+        return Some(index);
+    }
+    let names = EntryNameDecoder::default();
+    (0..archive.len()).find(|&index| {
+        archive
+            .by_index_raw(index)
+            .ok()
+            .and_then(|file| names.entry_name(&file).ok())
+            .is_some_and(|entry| entry == name)
+    })
+}
+
+/// `ZipArchive::by_name` 的解码版本
+pub(crate) fn by_entry_name<'a, R: Read + Seek>(
+    archive: &'a mut ZipArchive<R>,
+    name: &str,
+) -> ZipResult<ZipFile<'a, R>> {
+    match find_entry_index(archive, name) {
+        Some(index) => archive.by_index(index),
+        None => Err(ZipError::FileNotFound),
+    }
+}
+
+fn needs_sanitizing(component: &str) -> bool {
+    const INVALID: [char; 7] = [':', '*', '?', '"', '<', '>', '|'];
+    component
+        .chars()
+        .any(|c| c.is_control() || INVALID.contains(&c))
+        || component.ends_with(['.', ' '])
+}
+
+/// 把条目名转成安全的相对路径：丢弃根、`.`、`..`，含非法字符的组件逐个经 `sanitize_filename` 处理；
+/// 没有剩余组件时返回 `None`
+pub(crate) fn safe_relative_path(name: &str) -> Option<PathBuf> {
+    let normalized = name.replace('\\', "/");
+    let mut path = PathBuf::new();
+    for component in Path::new(&normalized).components() {
+        let Component::Normal(component) = component else {
+            continue;
+        };
+        let component = component.to_string_lossy();
+        if needs_sanitizing(&component) {
+            path.push(sanitize_filename(&component));
+        } else {
+            path.push(component.as_ref());
+        }
+    }
+    (!path.as_os_str().is_empty()).then_some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_gbk_names_and_sanitizes_components() {
+        let (gbk, _, _) = encoding_rs::GBK.encode("材质包/纹理.png");
+        assert!(std::str::from_utf8(&gbk).is_err());
+        assert_eq!(
+            decode_entry_name(&gbk, encoding_for_codepage(936)).as_deref(),
+            Some("材质包/纹理.png")
+        );
+        assert_eq!(decode_entry_name(&gbk, None), None);
+        assert_eq!(
+            decode_entry_name("已是UTF-8".as_bytes(), None).as_deref(),
+            Some("已是UTF-8")
+        );
+
+        assert_eq!(
+            safe_relative_path("../材质包\\a:b?.png"),
+            Some(PathBuf::from("材质包").join("a_b_.png"))
+        );
+        assert_eq!(safe_relative_path("/./"), None);
+    }
+}
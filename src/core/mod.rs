@@ -1,7 +1,9 @@
+pub mod cache_registry;
 pub mod curseforge;
 pub mod easytier;
 pub mod inject;
 pub mod levilamina;
+pub mod library_search;
 #[cfg(target_os = "linux")]
 pub(crate) mod linux_runtime;
 pub mod minecraft;
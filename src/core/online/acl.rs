@@ -45,9 +45,12 @@ fn allow_rule(
 
 pub fn build_paperconnect_acl(
     is_host: bool,
-    host_vip: &str,
+    host_inet: &cidr::Ipv4Inet,
     host_protocol_port: Option<u16>,
 ) -> Acl {
+    let host_vip = host_inet.address().to_string();
+    let host_subnet = host_inet.network().to_string();
+    let host_broadcast = host_inet.network().last_address().to_string();
     let mut inbound_rules: Vec<Rule> = Vec::new();
     let mut outbound_rules: Vec<Rule> = Vec::new();
 
@@ -59,8 +62,7 @@ pub fn build_paperconnect_acl(
     let discovery_payload_prefix_hex: Vec<String> = vec![];
     let discovery_broadcast_ports: Vec<String> =
         vec!["7551".to_string(), "19132".to_string(), "19133".to_string()];
-    let discovery_broadcast_ips: Vec<String> =
-        vec!["10.144.144.255".to_string(), "255.255.255.255".to_string()];
+    let discovery_broadcast_ips: Vec<String> = vec![host_broadcast, "255.255.255.255".to_string()];
     let permissive_unicast_ports: Vec<String> = vec!["7551".to_string()];
 
     if is_host {
@@ -167,7 +169,7 @@ pub fn build_paperconnect_acl(
             Protocol::Udp,
             permissive_unicast_ports.clone(),
             vec![host_vip.to_string()],
-            vec!["10.144.144.0/24".to_string()],
+            vec![host_subnet.clone()],
             vec![],
             vec![],
             false,
@@ -186,7 +188,7 @@ pub fn build_paperconnect_acl(
             Protocol::Udp,
             vec!["0-65535".to_string()],
             vec![host_vip.to_string()],
-            vec!["10.144.144.0/24".to_string()],
+            vec![host_subnet.clone()],
             vec![],
             bedrock_udp_app_protocols.clone(),
             false,
@@ -206,7 +208,7 @@ pub fn build_paperconnect_acl(
                 Protocol::Tcp,
                 vec!["0-65535".to_string()],
                 vec![host_vip.to_string()],
-                vec!["10.144.144.0/24".to_string()],
+                vec![host_subnet.clone()],
                 vec![protocol_port.to_string()],
                 vec![],
                 true,
@@ -225,7 +227,7 @@ pub fn build_paperconnect_acl(
                 Protocol::Tcp,
                 vec!["0-65535".to_string()],
                 vec![host_vip.to_string()],
-                vec!["10.144.144.0/24".to_string()],
+                vec![host_subnet.clone()],
                 vec![],
                 vec![],
                 false,
@@ -264,7 +266,7 @@ pub fn build_paperconnect_acl(
             Protocol::Udp,
             permissive_unicast_ports.clone(),
             vec![host_vip.to_string()],
-            vec!["10.144.144.0/24".to_string()],
+            vec![host_subnet.clone()],
             vec![],
             vec![],
             false,
@@ -283,7 +285,7 @@ pub fn build_paperconnect_acl(
             Protocol::Udp,
             vec!["0-65535".to_string()],
             vec![host_vip.to_string()],
-            vec!["10.144.144.0/24".to_string()],
+            vec![host_subnet.clone()],
             vec![],
             bedrock_udp_app_protocols.clone(),
             false,
@@ -303,7 +305,7 @@ pub fn build_paperconnect_acl(
                 Protocol::Tcp,
                 vec!["0-65535".to_string()],
                 vec![host_vip.to_string()],
-                vec!["10.144.144.0/24".to_string()],
+                vec![host_subnet.clone()],
                 vec![protocol_port.to_string()],
                 vec![],
                 true,
@@ -322,7 +324,7 @@ pub fn build_paperconnect_acl(
                 Protocol::Tcp,
                 vec!["0-65535".to_string()],
                 vec![host_vip.to_string()],
-                vec!["10.144.144.0/24".to_string()],
+                vec![host_subnet.clone()],
                 vec![],
                 vec![],
                 false,
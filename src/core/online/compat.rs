@@ -0,0 +1,201 @@
+use anyhow::{Context as _, anyhow};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use super::now_ms;
+use super::paperconnect::TcpFraming;
+use crate::config::config::{DEFAULT_PAPERCONNECT_CLIENTS_URL, read_config};
+use crate::http::proxy::get_client_for_proxy;
+use crate::http::request::{GLOBAL_CLIENT, RequestOptions, send_request_with_options};
+use crate::utils::file_ops;
+use crate::utils::network::should_save_bandwidth;
+
+const COMPAT_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+const COMPAT_CACHE_FILE_NAME: &str = "compat_cache.json";
+const COMPAT_FETCH_TIMEOUT_MS: u64 = 5_000;
+
+/// 已知兼容 PaperConnect 的第三方客户端，供前端向非 BMCBL 玩家说明加入房间的条件。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaperConnectClientInfo {
+    pub name: String,
+    #[serde(alias = "min_version")]
+    pub min_version: String,
+    /// 取值对应 [`TcpFraming`] 的变体名（`half_close` / `keep_open`）
+    pub framing: String,
+    #[serde(default)]
+    pub notes: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CompatCacheFile {
+    ts_unix_ms: i64,
+    url: String,
+    clients: Vec<PaperConnectClientInfo>,
+}
+
+fn cache_path() -> PathBuf {
+    file_ops::bmcbl_subdir(COMPAT_CACHE_FILE_NAME)
+}
+
+fn read_cache(url: &str) -> Option<CompatCacheFile> {
+    let raw = fs::read_to_string(cache_path()).ok()?;
+    let cache: CompatCacheFile = serde_json::from_str(&raw).ok()?;
+    (cache.url == url).then_some(cache)
+}
+
+fn write_cache(cache: &CompatCacheFile) -> anyhow::Result<()> {
+    let path = cache_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context("create compat cache dir failed")?;
+    }
+    let raw = serde_json::to_string(cache).context("serialize compat cache failed")?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, raw).context("write compat cache failed")?;
+    fs::rename(&tmp, &path).context("replace compat cache failed")?;
+    Ok(())
+}
+
+fn clients_url() -> String {
+    let configured = read_config()
+        .map(|config| config.online.paperconnect_clients_url)
+        .unwrap_or_default();
+    if configured.trim().is_empty() {
+        DEFAULT_PAPERCONNECT_CLIENTS_URL.to_string()
+    } else {
+        configured.trim().to_string()
+    }
+}
+
+/// 丢弃缺少名称或分帧方式无法识别的条目，避免前端展示无法连接的客户端。
+fn sanitize_clients(clients: Vec<PaperConnectClientInfo>) -> Vec<PaperConnectClientInfo> {
+    clients
+        .into_iter()
+        .filter_map(|mut client| {
+            client.name = client.name.trim().to_string();
+            client.framing = client.framing.trim().to_ascii_lowercase();
+            if client.name.is_empty() {
+                return None;
+            }
+            if serde_json::from_value::<TcpFraming>(serde_json::Value::String(
+                client.framing.clone(),
+            ))
+            .is_err()
+            {
+                tracing::warn!(
+                    "paperconnect compat: unknown framing {:?} for {}",
+                    client.framing,
+                    client.name
+                );
+                return None;
+            }
+            Some(client)
+        })
+        .collect()
+}
+
+async fn fetch_clients(url: &str) -> anyhow::Result<Vec<PaperConnectClientInfo>> {
+    let url = reqwest::Url::parse(url)
+        .with_context(|| format!("invalid paperconnect clients url: {url}"))?;
+    let client = get_client_for_proxy().unwrap_or_else(|error| {
+        tracing::debug!("proxy client build failed, using global client: {error:?}");
+        GLOBAL_CLIENT.clone()
+    });
+
+    let mut headers = HashMap::new();
+    headers.insert("Accept".to_string(), "application/json".to_string());
+    let options = RequestOptions {
+        method: "GET",
+        headers: Some(&headers),
+        timeout_ms: Some(COMPAT_FETCH_TIMEOUT_MS),
+        allow_redirects: Some(true),
+    };
+
+    let response = send_request_with_options(&client, &url, &options)
+        .await
+        .map_err(|error| anyhow!(error))?;
+    let response = response
+        .error_for_status()
+        .context("paperconnect clients returned error status")?;
+    let body = response
+        .text()
+        .await
+        .context("read paperconnect clients body failed")?;
+    let clients: Vec<PaperConnectClientInfo> =
+        serde_json::from_str(&body).context("paperconnect clients: invalid json")?;
+    Ok(sanitize_clients(clients))
+}
+
+/// 获取兼容 PaperConnect 的第三方客户端列表：24 小时内的缓存直接使用（计费网络下不论新旧），
+/// 过期后重新拉取，拉取失败时退回仍可用的旧缓存。
+pub async fn get_paperconnect_compatible_clients() -> Result<Vec<PaperConnectClientInfo>, String> {
+    let url = clients_url();
+    let cached = read_cache(&url);
+
+    if let Some(cache) = cached.as_ref() {
+        let age_ms = now_ms().saturating_sub(cache.ts_unix_ms).max(0) as u64;
+        if Duration::from_millis(age_ms) <= COMPAT_CACHE_TTL || should_save_bandwidth() {
+            return Ok(cache.clients.clone());
+        }
+    }
+
+    match fetch_clients(&url).await {
+        Ok(clients) => {
+            let cache = CompatCacheFile {
+                ts_unix_ms: now_ms(),
+                url,
+                clients,
+            };
+            if let Err(error) = write_cache(&cache) {
+                tracing::warn!("write paperconnect compat cache failed: {error:#}");
+            }
+            Ok(cache.clients)
+        }
+        Err(error) => match cached {
+            Some(cache) => {
+                tracing::info!(
+                    "paperconnect compat list unavailable, using stale cache fetched at {}: {error:#}",
+                    cache.ts_unix_ms
+                );
+                Ok(cache.clients)
+            }
+            None => Err(format!("获取 PaperConnect 兼容客户端列表失败：{error:#}")),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PaperConnectClientInfo, sanitize_clients};
+
+    fn client(name: &str, framing: &str) -> PaperConnectClientInfo {
+        PaperConnectClientInfo {
+            name: name.to_string(),
+            min_version: "1.0.0".to_string(),
+            framing: framing.to_string(),
+            notes: String::new(),
+        }
+    }
+
+    #[test]
+    fn clients_with_unknown_framing_or_blank_name_are_dropped() {
+        let clients = sanitize_clients(vec![
+            client("Half Close Client", "half_close"),
+            client(" Keep Open Client ", "KEEP_OPEN"),
+            client("Length Prefixed Client", "length_prefixed"),
+            client("  ", "half_close"),
+        ]);
+
+        assert_eq!(
+            clients,
+            vec![
+                client("Half Close Client", "half_close"),
+                client("Keep Open Client", "keep_open"),
+            ]
+        );
+    }
+}
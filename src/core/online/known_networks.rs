@@ -0,0 +1,181 @@
+//! 已加入网络记录：每次成功启动 EasyTier 都记到 `BMCBL/known_networks.json`，
+//! 方便下次一键重新加入。密钥只保存 SHA-256，不落盘明文。
+
+use crate::utils::file_ops;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::warn;
+
+const KNOWN_NETWORKS_FILE_NAME: &str = "known_networks.json";
+
+/// 串行化读改写，避免同时启动和删除时互相覆盖
+static KNOWN_NETWORKS_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct KnownNetwork {
+    name: String,
+    /// 空密钥时为空字符串
+    secret_hash: String,
+    /// RFC3339
+    last_joined: String,
+    join_count: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KnownNetworkInfo {
+    pub name: String,
+    pub has_secret: bool,
+    pub last_joined: String,
+    pub join_count: u32,
+}
+
+impl From<KnownNetwork> for KnownNetworkInfo {
+    fn from(network: KnownNetwork) -> Self {
+        Self {
+            has_secret: !network.secret_hash.is_empty(),
+            name: network.name,
+            last_joined: network.last_joined,
+            join_count: network.join_count,
+        }
+    }
+}
+
+fn known_networks_path() -> PathBuf {
+    file_ops::bmcbl_subdir(KNOWN_NETWORKS_FILE_NAME)
+}
+
+fn load_networks(path: &Path) -> Vec<KnownNetwork> {
+    let Ok(raw) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    match serde_json::from_str(&raw) {
+        Ok(networks) => networks,
+        Err(e) => {
+            warn!("解析已加入网络记录失败，已忽略: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// 先写临时文件再替换，写入中断不会损坏已有记录
+fn save_networks(path: &Path, networks: &[KnownNetwork]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+    }
+    let raw = serde_json::to_string_pretty(networks).map_err(|e| e.to_string())?;
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, raw).map_err(|e| format!("写入已加入网络记录失败: {}", e))?;
+    fs::rename(&temp_path, path).map_err(|e| format!("保存已加入网络记录失败: {}", e))
+}
+
+fn hash_secret(secret: &str) -> String {
+    if secret.is_empty() {
+        return String::new();
+    }
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
+
+fn upsert_network(networks: &mut Vec<KnownNetwork>, name: &str, secret: &str, now: String) {
+    let secret_hash = hash_secret(secret);
+    match networks.iter_mut().find(|network| network.name == name) {
+        Some(network) => {
+            network.secret_hash = secret_hash;
+            network.last_joined = now;
+            network.join_count = network.join_count.saturating_add(1);
+        }
+        None => networks.push(KnownNetwork {
+            name: name.to_string(),
+            secret_hash,
+            last_joined: now,
+            join_count: 1,
+        }),
+    }
+}
+
+/// 记录一次成功加入；网络名为空时不记录
+pub(super) fn record_network_join(name: &str, secret: &str) -> Result<(), String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Ok(());
+    }
+    let _guard = KNOWN_NETWORKS_LOCK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let path = known_networks_path();
+    let mut networks = load_networks(&path);
+    upsert_network(&mut networks, name, secret, Utc::now().to_rfc3339());
+    save_networks(&path, &networks)
+}
+
+/// 已加入过的网络，最近加入的在前
+pub fn list_easytier_known_networks() -> Result<Vec<KnownNetworkInfo>, String> {
+    let _guard = KNOWN_NETWORKS_LOCK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let mut networks = load_networks(&known_networks_path());
+    // RFC3339 UTC 时间戳按字符串比较即为时间顺序
+    networks.sort_by(|left, right| right.last_joined.cmp(&left.last_joined));
+    Ok(networks.into_iter().map(KnownNetworkInfo::from).collect())
+}
+
+pub fn forget_network(name: String) -> Result<(), String> {
+    let _guard = KNOWN_NETWORKS_LOCK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let path = known_networks_path();
+    let mut networks = load_networks(&path);
+    let before = networks.len();
+    networks.retain(|network| network.name != name);
+    if networks.len() == before {
+        return Err(format!("未找到网络记录: {}", name));
+    }
+    save_networks(&path, &networks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upsert_counts_joins_and_never_stores_plain_secret() {
+        let dir =
+            std::env::temp_dir().join(format!("bmcbl-known-networks-{}", uuid::Uuid::new_v4()));
+        let path = dir.join(KNOWN_NETWORKS_FILE_NAME);
+        let mut networks = Vec::new();
+        upsert_network(
+            &mut networks,
+            "room",
+            "hunter2",
+            "2026-01-01T00:00:00+00:00".to_string(),
+        );
+        upsert_network(
+            &mut networks,
+            "open",
+            "",
+            "2026-01-02T00:00:00+00:00".to_string(),
+        );
+        upsert_network(
+            &mut networks,
+            "room",
+            "hunter2",
+            "2026-01-03T00:00:00+00:00".to_string(),
+        );
+        save_networks(&path, &networks).expect("save networks");
+
+        let raw = fs::read_to_string(&path).expect("read networks");
+        assert!(!raw.contains("hunter2"));
+        let loaded = load_networks(&path);
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].join_count, 2);
+        assert_eq!(loaded[0].last_joined, "2026-01-03T00:00:00+00:00");
+        assert_eq!(loaded[0].secret_hash, hash_secret("hunter2"));
+        assert_eq!(loaded[0].secret_hash.len(), 64);
+        assert!(!KnownNetworkInfo::from(loaded[1].clone()).has_secret);
+
+        fs::remove_dir_all(&dir).expect("remove test dir");
+    }
+}
@@ -23,14 +23,23 @@ use tokio::time::Instant;
 use uuid::Uuid;
 
 mod acl;
+mod compat;
+mod known_networks;
 mod paperconnect;
+mod policy;
 
-pub use paperconnect::PaperConnectPlayer;
+pub use compat::{PaperConnectClientInfo, get_paperconnect_compatible_clients};
+pub use known_networks::{KnownNetworkInfo, forget_network, list_easytier_known_networks};
+pub use paperconnect::{PaperConnectPlayer, PaperConnectServerSnapshot, TcpFraming};
 
 use crate::core::easytier::runtime::ensure_easytier_runtime_ready;
 use crate::http::proxy::{build_no_proxy_client_with_resolve, get_no_proxy_client};
 use crate::utils::cloudflare;
 use acl::build_paperconnect_acl;
+use policy::{OnlinePolicy, resolve_online_policy};
+pub use policy::{
+    OnlinePolicySource, OnlinePolicyStatus, cached_online_policy_status, refresh_online_policy,
+};
 
 const DEFAULT_PAPERCONNECT_VIP: &str = "10.144.144.1";
 const DEFAULT_BOOTSTRAP_PEERS: [&str; 1] = ["tcp://public.easytier.bmcbl.com:54321"];
@@ -86,9 +95,12 @@ pub struct EasyTierEmbeddedStatus {
     pub no_tun: bool,
     pub game_host: Option<String>,
     pub game_port: Option<u16>,
+    pub policy_source: OnlinePolicySource,
+    pub policy_fetched_at: Option<i64>,
+    pub paperconnect_server: Option<PaperConnectServerSnapshot>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct EasyTierStartOptions {
     #[serde(alias = "disableP2p", alias = "disable_p2p")]
     pub disable_p2p: Option<bool>,
@@ -104,6 +116,17 @@ pub struct EasyTierStartOptions {
     pub ipv4: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EasyTierModeRecommendation {
+    /// `tun` 或 `no_tun`
+    pub recommended_mode: String,
+    pub tun_available: bool,
+    /// 用户态协议栈（smoltcp）内置于 EasyTier，始终可用
+    pub smoltcp_available: bool,
+    pub reason: String,
+}
+
 #[derive(Debug)]
 pub struct EasyTierStartRequest {
     pub network_name: String,
@@ -125,6 +148,8 @@ struct EasyTierLastStart {
     resolved_ipv4: Option<String>,
     game_port: u16,
     options: Option<EasyTierStartOptions>,
+    paperconnect_vip: String,
+    policy: OnlinePolicyStatus,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -243,6 +268,14 @@ async fn fetch_public_bootstrap_peers() -> anyhow::Result<Vec<String>> {
     Ok(peers)
 }
 
+/// 联机策略中的节点优先，其后合并公共节点列表。
+async fn policy_bootstrap_peers(policy: &OnlinePolicy) -> Vec<String> {
+    merge_bootstrap_peers(
+        policy.bootstrap_peers.clone(),
+        default_bootstrap_peers().await,
+    )
+}
+
 async fn default_bootstrap_peers() -> Vec<String> {
     if let Ok(cache_guard) = BOOTSTRAP_PEERS_CACHE.lock() {
         if let Some(cache) = cache_guard.as_ref()
@@ -420,6 +453,7 @@ fn build_embedded_easytier_config(
     peers: Vec<String>,
     hostname: Option<String>,
     options: Option<EasyTierStartOptions>,
+    paperconnect_vip: cidr::Ipv4Inet,
 ) -> anyhow::Result<(TomlConfigLoader, Option<String>, Option<String>)> {
     let network_name_for_policy = network_name.clone();
     let cfg = TomlConfigLoader::default();
@@ -499,9 +533,7 @@ fn build_embedded_easytier_config(
 
     if ipv4.is_none() && is_paperconnect_network {
         if is_paperconnect_host {
-            ipv4 = Some(cidr::Ipv4Inet::from_str(&format!(
-                "{DEFAULT_PAPERCONNECT_VIP}/24"
-            ))?);
+            ipv4 = Some(paperconnect_vip);
             dhcp = false;
         }
     }
@@ -512,7 +544,7 @@ fn build_embedded_easytier_config(
     if is_paperconnect_network && !no_tun_enabled {
         let acl = build_paperconnect_acl(
             is_paperconnect_host,
-            DEFAULT_PAPERCONNECT_VIP,
+            &paperconnect_vip,
             host_port_from_hostname,
         );
         cfg.set_acl(Some(acl));
@@ -546,6 +578,58 @@ fn build_embedded_easytier_config(
     Ok((cfg, resolved_hostname, resolved_ipv4))
 }
 
+#[cfg(target_os = "windows")]
+fn tun_driver_available() -> bool {
+    crate::utils::registry::check_wintun_driver_installed()
+}
+
+#[cfg(not(target_os = "windows"))]
+fn tun_driver_available() -> bool {
+    std::path::Path::new("/dev/net/tun").exists()
+}
+
+/// 根据本机 TUN 驱动情况推荐联机模式：驱动可用时走 TUN，否则退回 no_tun（smoltcp）
+pub fn easytier_detect_best_mode() -> Result<EasyTierModeRecommendation, String> {
+    let tun_available = tun_driver_available();
+    let (recommended_mode, reason) = if tun_available {
+        ("tun", "已检测到 TUN 驱动，使用虚拟网卡模式")
+    } else if cfg!(target_os = "windows") {
+        ("no_tun", "未检测到 Wintun 驱动，使用无虚拟网卡模式")
+    } else {
+        ("no_tun", "TUN 设备不可用，使用无虚拟网卡模式")
+    };
+    Ok(EasyTierModeRecommendation {
+        recommended_mode: recommended_mode.to_string(),
+        tun_available,
+        smoltcp_available: true,
+        reason: reason.to_string(),
+    })
+}
+
+/// 未显式指定 `no_tun` 时按检测结果填入，保证后续状态查询与实际模式一致
+fn resolve_tun_mode(options: Option<EasyTierStartOptions>) -> Option<EasyTierStartOptions> {
+    if options.as_ref().is_some_and(|value| value.no_tun.is_some()) {
+        return options;
+    }
+    let mut options = options.unwrap_or_default();
+    let no_tun = match easytier_detect_best_mode() {
+        Ok(recommendation) => {
+            tracing::info!(
+                mode = %recommendation.recommended_mode,
+                "EasyTier 联机模式自动选择: {}",
+                recommendation.reason
+            );
+            recommendation.recommended_mode != "tun"
+        }
+        Err(e) => {
+            tracing::warn!("检测 EasyTier 联机模式失败，使用无虚拟网卡模式: {e}");
+            true
+        }
+    };
+    options.no_tun = Some(no_tun);
+    Some(options)
+}
+
 pub async fn easytier_start(request: EasyTierStartRequest) -> Result<(), String> {
     let EasyTierStartRequest {
         network_name,
@@ -564,17 +648,19 @@ pub async fn easytier_start(request: EasyTierStartRequest) -> Result<(), String>
     }
 
     ensure_easytier_runtime_ready()?;
+    let options = resolve_tun_mode(options);
 
+    let policy = resolve_online_policy().await;
     let peers = if peers.iter().any(|p| !p.trim().is_empty()) {
         let sanitized = sanitize_bootstrap_peers(peers);
         if sanitized.is_empty() {
             tracing::warn!("configured bootstrap peers are invalid; using fallback peers");
-            default_bootstrap_peers().await
+            policy_bootstrap_peers(&policy).await
         } else {
             sanitized
         }
     } else {
-        default_bootstrap_peers().await
+        policy_bootstrap_peers(&policy).await
     };
 
     {
@@ -594,6 +680,7 @@ pub async fn easytier_start(request: EasyTierStartRequest) -> Result<(), String>
             peers.clone(),
             hostname.clone(),
             options.clone(),
+            policy.paperconnect_vip,
         )
         .map_err(|e| e.to_string())?;
 
@@ -606,6 +693,8 @@ pub async fn easytier_start(request: EasyTierStartRequest) -> Result<(), String>
             resolved_ipv4,
             game_port,
             options: options.clone(),
+            paperconnect_vip: policy.vip_address(),
+            policy: policy.status(),
         });
 
         let no_tun = options
@@ -621,7 +710,7 @@ pub async fn easytier_start(request: EasyTierStartRequest) -> Result<(), String>
                 host: if no_tun {
                     "127.0.0.1".to_string()
                 } else {
-                    DEFAULT_PAPERCONNECT_VIP.to_string()
+                    policy.vip_address()
                 },
                 port: game_port,
             });
@@ -710,6 +799,10 @@ pub async fn easytier_start(request: EasyTierStartRequest) -> Result<(), String>
         );
     }
 
+    if let Err(error) = known_networks::record_network_join(&network_name, &network_secret) {
+        tracing::warn!("记录已加入网络失败：{error}");
+    }
+
     Ok(())
 }
 
@@ -1044,7 +1137,7 @@ pub async fn easytier_embedded_status() -> Result<Option<EasyTierEmbeddedStatus>
                 if ipv4.is_none() {
                     let hn = hostname.trim();
                     if paperconnect::server_port_from_hostname(hn).is_some() {
-                        ipv4 = Some(DEFAULT_PAPERCONNECT_VIP.to_string());
+                        ipv4 = Some(last.paperconnect_vip.clone());
                     }
                 }
             }
@@ -1062,6 +1155,10 @@ pub async fn easytier_embedded_status() -> Result<Option<EasyTierEmbeddedStatus>
         .map(|endpoint| endpoint.port)
         .or_else(|| last_start.as_ref().map(|value| value.game_port));
     let game_host = game_endpoint.map(|endpoint| endpoint.host);
+    let policy = last_start
+        .as_ref()
+        .map(|value| value.policy.clone())
+        .unwrap_or_else(|| OnlinePolicy::builtin().status());
 
     Ok(Some(EasyTierEmbeddedStatus {
         instance_id: inst_id,
@@ -1070,6 +1167,9 @@ pub async fn easytier_embedded_status() -> Result<Option<EasyTierEmbeddedStatus>
         no_tun,
         game_host,
         game_port,
+        policy_source: policy.source,
+        policy_fetched_at: policy.fetched_at,
+        paperconnect_server: paperconnect::server_snapshot(),
     }))
 }
 
@@ -1279,6 +1379,11 @@ pub fn paperconnect_players() -> Vec<PaperConnectPlayer> {
     paperconnect::players()
 }
 
+/// 房主向已连接的客户端发送广播，客户端通过 `c:poll` 拉取
+pub fn paperconnect_server_broadcast(message: String) -> Result<(), String> {
+    paperconnect::broadcast(message)
+}
+
 pub async fn online_debug_snapshot() -> serde_json::Value {
     serde_json::json!({
         "ts": now_ms(),
@@ -1291,8 +1396,9 @@ mod tests {
     use easytier::common::config::ConfigLoader as _;
 
     use super::{
-        EasyTierStartOptions, build_embedded_easytier_config, merge_bootstrap_peers,
-        paperconnect_parse_room_code, sanitize_bootstrap_peers,
+        EasyTierStartOptions, OnlinePolicy, build_embedded_easytier_config,
+        easytier_detect_best_mode, merge_bootstrap_peers, paperconnect_parse_room_code,
+        resolve_tun_mode, sanitize_bootstrap_peers,
     };
 
     #[tokio::test]
@@ -1344,6 +1450,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn explicit_tun_mode_is_kept_and_missing_mode_follows_detection() {
+        let explicit = resolve_tun_mode(Some(EasyTierStartOptions {
+            no_tun: Some(false),
+            ..Default::default()
+        }))
+        .expect("options are kept");
+        assert_eq!(explicit.no_tun, Some(false));
+
+        let recommendation = easytier_detect_best_mode().expect("detection never fails");
+        assert!(recommendation.smoltcp_available);
+        let detected = resolve_tun_mode(None).expect("options are filled in");
+        assert_eq!(detected.no_tun, Some(!recommendation.tun_available));
+    }
+
     #[test]
     fn paperconnect_config_preserves_requested_tun_mode() {
         let options = EasyTierStartOptions {
@@ -1358,6 +1479,7 @@ mod tests {
             vec!["tcp://public.example:54321".to_string()],
             Some("paper-connect-server-54321".to_string()),
             Some(options),
+            OnlinePolicy::builtin().paperconnect_vip,
         )
         .expect("PaperConnect no-TUN config should be valid");
 
@@ -1383,6 +1505,7 @@ mod tests {
             vec!["tcp://public.example:54321".to_string()],
             Some("bmcbl-client-player".to_string()),
             Some(options),
+            OnlinePolicy::builtin().paperconnect_vip,
         )
         .expect("PaperConnect TUN config should be valid");
 
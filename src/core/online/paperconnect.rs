@@ -1,9 +1,13 @@
+use crate::config::config::{DEFAULT_PAPERCONNECT_MAX_CONNECTIONS, read_config};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
 use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tokio::task::{JoinHandle, JoinSet};
 use tokio::time::MissedTickBehavior;
 
@@ -11,10 +15,26 @@ const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
 const PLAYER_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
 const PLAYER_EXPIRY: Duration = Duration::from_secs(10);
 const PLAYER_CLEANUP_INTERVAL: Duration = Duration::from_secs(1);
+/// 两次读取之间允许的最长空闲时间，防止慢速连接占住读缓冲
+const READ_IDLE_TIMEOUT: Duration = Duration::from_secs(1);
+/// 单个连接从接受到响应完成的总时长上限
+const CONNECTION_DEADLINE: Duration = Duration::from_secs(5);
+const CONNECTION_BURST_PER_SOURCE: f64 = 8.0;
+const CONNECTIONS_PER_SECOND_PER_SOURCE: f64 = 2.0;
+const REQUESTS_PER_MINUTE_PER_SOURCE: f64 = 120.0;
+const SOURCE_BUCKET_EXPIRY: Duration = Duration::from_secs(120);
+/// 房主广播最多保留的条数，超出时丢弃最早的
+const MAX_PENDING_BROADCASTS: usize = 10;
+const MAX_BROADCAST_CHARS: usize = 256;
 
 static SERVER_TASK: Mutex<Option<JoinHandle<()>>> = Mutex::new(None);
+static SERVER_GUARD: Mutex<Option<Arc<ServerGuard>>> = Mutex::new(None);
 static CLIENT_TASK: Mutex<Option<JoinHandle<()>>> = Mutex::new(None);
 static PLAYER_SNAPSHOT: Mutex<Vec<PaperConnectPlayer>> = Mutex::new(Vec::new());
+/// 联机中心待客户端拉取的广播消息；`c:poll` 不会清空，客户端凭 `since` 游标只取新消息
+static PENDING_BROADCASTS: Mutex<VecDeque<Broadcast>> = Mutex::new(VecDeque::new());
+/// 广播编号在整个进程内单调递增，联机中心重启后客户端的旧游标仍然有效
+static NEXT_BROADCAST_ID: AtomicU64 = AtomicU64::new(1);
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -52,6 +72,16 @@ pub struct PaperConnectPlayer {
     last_seen: i64,
 }
 
+/// PaperConnect 请求在 TCP 上的分帧方式，请求体均为 `c:<type>\0<json>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TcpFraming {
+    /// 发送请求后关闭写端，以 EOF 标记请求结束
+    HalfClose,
+    /// 写端保持打开直到收到响应，服务端在 JSON 请求体完整时即开始处理
+    KeepOpen,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 struct PingResponse {
     time: i64,
@@ -72,6 +102,228 @@ struct PlayerResponse {
     players: Vec<PaperConnectPlayer>,
 }
 
+#[derive(Debug, Deserialize)]
+struct BroadcastRequest {
+    message: String,
+    /// 房主本机才知道的口令；免 TUN 模式下远端连接也可能以回环地址到达
+    #[serde(rename = "hostToken")]
+    host_token: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct BroadcastResponse {
+    #[serde(rename = "returnTime")]
+    return_time: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+struct Broadcast {
+    id: u64,
+    message: String,
+    time: i64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PollRequest {
+    /// 客户端已读到的最大广播编号
+    #[serde(default)]
+    since: u64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct PollResponse {
+    broadcasts: Vec<Broadcast>,
+    /// 客户端下次轮询时作为 `since` 传回
+    #[serde(rename = "latestId")]
+    latest_id: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaperConnectServerSnapshot {
+    pub accepted: u64,
+    pub rejected_rate_limited: u64,
+    pub rejected_over_capacity: u64,
+    pub active: usize,
+    pub max_connections: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ServerLimits {
+    max_connections: usize,
+    connection_burst: f64,
+    connections_per_second: f64,
+    requests_per_minute: f64,
+}
+
+impl Default for ServerLimits {
+    fn default() -> Self {
+        Self {
+            max_connections: DEFAULT_PAPERCONNECT_MAX_CONNECTIONS,
+            connection_burst: CONNECTION_BURST_PER_SOURCE,
+            connections_per_second: CONNECTIONS_PER_SECOND_PER_SOURCE,
+            requests_per_minute: REQUESTS_PER_MINUTE_PER_SOURCE,
+        }
+    }
+}
+
+impl ServerLimits {
+    fn from_config() -> Self {
+        let max_connections = read_config()
+            .map(|config| config.online.paperconnect_max_connections)
+            .unwrap_or(DEFAULT_PAPERCONNECT_MAX_CONNECTIONS);
+        Self {
+            max_connections: if max_connections == 0 {
+                DEFAULT_PAPERCONNECT_MAX_CONNECTIONS
+            } else {
+                max_connections
+            },
+            ..Self::default()
+        }
+    }
+}
+
+struct TokenBucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    fn full(capacity: f64, now: Instant) -> Self {
+        Self {
+            tokens: capacity,
+            updated_at: now,
+        }
+    }
+
+    fn try_take(&mut self, capacity: f64, refill_per_second: f64, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * refill_per_second).min(capacity);
+        self.updated_at = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+struct SourceBuckets {
+    connections: TokenBucket,
+    requests: TokenBucket,
+    last_seen: Instant,
+}
+
+/// 联机中心的准入控制：全局并发上限 + 按来源 IP 的令牌桶
+struct ServerGuard {
+    limits: ServerLimits,
+    permits: Arc<Semaphore>,
+    sources: Mutex<HashMap<IpAddr, SourceBuckets>>,
+    accepted: AtomicU64,
+    rejected_rate_limited: AtomicU64,
+    rejected_over_capacity: AtomicU64,
+    /// 每次启动联机中心时随机生成，只有房主进程知道
+    host_token: String,
+}
+
+impl ServerGuard {
+    fn new(limits: ServerLimits) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(limits.max_connections)),
+            limits,
+            sources: Mutex::new(HashMap::new()),
+            accepted: AtomicU64::new(0),
+            rejected_rate_limited: AtomicU64::new(0),
+            rejected_over_capacity: AtomicU64::new(0),
+            host_token: uuid::Uuid::new_v4().to_string(),
+        }
+    }
+
+    /// 判断新连接能否进入；返回的许可在连接处理结束时释放
+    fn admit(&self, source: IpAddr) -> Option<OwnedSemaphorePermit> {
+        if !self.take_source_token(source, |buckets, limits, now| {
+            buckets.connections.try_take(
+                limits.connection_burst,
+                limits.connections_per_second,
+                now,
+            )
+        }) {
+            self.rejected_rate_limited.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+        let Ok(permit) = Arc::clone(&self.permits).try_acquire_owned() else {
+            self.rejected_over_capacity.fetch_add(1, Ordering::Relaxed);
+            return None;
+        };
+        self.accepted.fetch_add(1, Ordering::Relaxed);
+        Some(permit)
+    }
+
+    fn allow_request(&self, source: IpAddr) -> bool {
+        let allowed = self.take_source_token(source, |buckets, limits, now| {
+            buckets.requests.try_take(
+                limits.requests_per_minute,
+                limits.requests_per_minute / 60.0,
+                now,
+            )
+        });
+        if !allowed {
+            self.rejected_rate_limited.fetch_add(1, Ordering::Relaxed);
+        }
+        allowed
+    }
+
+    fn take_source_token(
+        &self,
+        source: IpAddr,
+        take: impl FnOnce(&mut SourceBuckets, &ServerLimits, Instant) -> bool,
+    ) -> bool {
+        let Ok(mut sources) = self.sources.lock() else {
+            tracing::warn!("PaperConnect 限流状态锁已损坏，拒绝连接");
+            return false;
+        };
+        let now = Instant::now();
+        let buckets = sources.entry(source).or_insert_with(|| SourceBuckets {
+            connections: TokenBucket::full(self.limits.connection_burst, now),
+            requests: TokenBucket::full(self.limits.requests_per_minute, now),
+            last_seen: now,
+        });
+        buckets.last_seen = now;
+        take(buckets, &self.limits, now)
+    }
+
+    fn prune_sources(&self) {
+        if let Ok(mut sources) = self.sources.lock() {
+            let now = Instant::now();
+            sources.retain(|_, buckets| {
+                now.saturating_duration_since(buckets.last_seen) < SOURCE_BUCKET_EXPIRY
+            });
+        }
+    }
+
+    fn snapshot(&self) -> PaperConnectServerSnapshot {
+        PaperConnectServerSnapshot {
+            accepted: self.accepted.load(Ordering::Relaxed),
+            rejected_rate_limited: self.rejected_rate_limited.load(Ordering::Relaxed),
+            rejected_over_capacity: self.rejected_over_capacity.load(Ordering::Relaxed),
+            active: self
+                .limits
+                .max_connections
+                .saturating_sub(self.permits.available_permits()),
+            max_connections: self.limits.max_connections,
+        }
+    }
+}
+
+/// 当前联机中心的连接统计；未启动时返回 None
+pub fn server_snapshot() -> Option<PaperConnectServerSnapshot> {
+    SERVER_GUARD
+        .lock()
+        .ok()
+        .and_then(|guard| guard.as_ref().map(|guard| guard.snapshot()))
+}
+
 pub fn players() -> Vec<PaperConnectPlayer> {
     PLAYER_SNAPSHOT
         .lock()
@@ -97,6 +349,48 @@ fn replace_player_snapshot(mut players: Vec<PaperConnectPlayer>) {
     }
 }
 
+/// 房主向所有客户端发送广播；联机中心未启动时返回错误
+pub fn broadcast(message: String) -> Result<(), String> {
+    let running = SERVER_TASK
+        .lock()
+        .map(|task| task.is_some())
+        .unwrap_or(false);
+    if !running {
+        return Err("PaperConnect 联机中心未启动".to_string());
+    }
+    let mut broadcasts = PENDING_BROADCASTS
+        .lock()
+        .map_err(|_| "PaperConnect 广播状态锁已损坏".to_string())?;
+    enqueue_broadcast(&mut broadcasts, &message)
+}
+
+fn clear_broadcasts() {
+    if let Ok(mut broadcasts) = PENDING_BROADCASTS.lock() {
+        broadcasts.clear();
+    }
+}
+
+fn enqueue_broadcast(broadcasts: &mut VecDeque<Broadcast>, message: &str) -> Result<(), String> {
+    let message = message.trim();
+    if message.is_empty() {
+        return Err("PaperConnect 广播内容不能为空".to_string());
+    }
+    if message.chars().count() > MAX_BROADCAST_CHARS {
+        return Err(format!(
+            "PaperConnect 广播内容过长（最多 {MAX_BROADCAST_CHARS} 个字符）"
+        ));
+    }
+    while broadcasts.len() >= MAX_PENDING_BROADCASTS {
+        broadcasts.pop_front();
+    }
+    broadcasts.push_back(Broadcast {
+        id: NEXT_BROADCAST_ID.fetch_add(1, Ordering::Relaxed),
+        message: message.to_string(),
+        time: now_ms(),
+    });
+    Ok(())
+}
+
 pub fn server_port_from_hostname(hostname: &str) -> Option<u16> {
     let port = hostname.trim().strip_prefix("paper-connect-server-")?;
     let port = port.parse::<u16>().ok()?;
@@ -119,6 +413,7 @@ pub async fn start_server(
     }
 
     stop_server();
+    clear_broadcasts();
     let listener = TcpListener::bind(("0.0.0.0", server_port))
         .await
         .map_err(|error| format!("PaperConnect 联机中心监听 {server_port} 失败：{error}"))?;
@@ -133,6 +428,10 @@ pub async fn start_server(
         host_player.clone(),
     )])));
     replace_player_snapshot(vec![host_player]);
+    let guard = Arc::new(ServerGuard::new(ServerLimits::from_config()));
+    if let Ok(mut server_guard) = SERVER_GUARD.lock() {
+        *server_guard = Some(Arc::clone(&guard));
+    }
     let task = tokio::spawn(async move {
         let mut cleanup = tokio::time::interval(PLAYER_CLEANUP_INTERVAL);
         cleanup.set_missed_tick_behavior(MissedTickBehavior::Skip);
@@ -140,18 +439,21 @@ pub async fn start_server(
         loop {
             tokio::select! {
                 accepted = listener.accept() => {
-                    let Ok((stream, _address)) = accepted else {
+                    let Ok((stream, address)) = accepted else {
                         break;
                     };
-                    let players = Arc::clone(&players);
-                    connections.spawn(async move {
-                        if let Err(error) = handle_connection(stream, game_port, players).await {
-                            tracing::debug!("PaperConnect 请求失败：{error}");
-                        }
-                    });
+                    accept_connection(
+                        &guard,
+                        &mut connections,
+                        stream,
+                        address,
+                        game_port,
+                        Arc::clone(&players),
+                    );
                 }
                 _ = cleanup.tick() => {
                     prune_inactive_players(&players);
+                    guard.prune_sources();
                 }
                 Some(joined) = connections.join_next(), if !connections.is_empty() => {
                     if let Err(error) = joined {
@@ -173,6 +475,10 @@ pub fn stop_server() {
     {
         task.abort();
     }
+    if let Ok(mut server_guard) = SERVER_GUARD.lock() {
+        *server_guard = None;
+    }
+    clear_broadcasts();
 }
 
 pub fn stop_client() {
@@ -248,21 +554,57 @@ pub async fn ping(host: &str, server_port: u16) -> Result<ServerInfo, String> {
     })
 }
 
+/// 准入检查通过才为连接分配处理任务；被拒绝的连接直接关闭，不分配读缓冲
+fn accept_connection(
+    guard: &Arc<ServerGuard>,
+    connections: &mut JoinSet<()>,
+    stream: TcpStream,
+    address: SocketAddr,
+    game_port: u16,
+    players: Arc<Mutex<HashMap<String, PaperConnectPlayer>>>,
+) {
+    let Some(permit) = guard.admit(address.ip()) else {
+        tracing::debug!(%address, "PaperConnect 连接被限流或超出并发上限，已关闭");
+        drop(stream);
+        return;
+    };
+    let guard = Arc::clone(guard);
+    connections.spawn(async move {
+        let _permit = permit;
+        let result = tokio::time::timeout(
+            CONNECTION_DEADLINE,
+            handle_connection(stream, address.ip(), game_port, players, &guard),
+        )
+        .await
+        .unwrap_or_else(|_| Err("PaperConnect 连接超过总时长上限".to_string()));
+        if let Err(error) = result {
+            tracing::debug!("PaperConnect 请求失败：{error}");
+        }
+    });
+}
+
 async fn handle_connection(
     mut stream: TcpStream,
+    source: IpAddr,
     game_port: u16,
     players: Arc<Mutex<HashMap<String, PaperConnectPlayer>>>,
+    guard: &ServerGuard,
 ) -> Result<(), String> {
     let request = tokio::time::timeout(REQUEST_TIMEOUT, read_request(&mut stream))
         .await
         .map_err(|_| "读取 PaperConnect 请求超时".to_string())?
         .map_err(|error| format!("读取 PaperConnect 请求失败：{error}"))?;
+    if !guard.allow_request(source) {
+        return Err(format!("PaperConnect 请求过于频繁：{source}"));
+    }
     let (request_type, body) = request
         .split_once('\0')
         .ok_or_else(|| "PaperConnect 请求缺少协议分隔符".to_string())?;
     let response = match request_type {
         "c:ping" => handle_ping(body, game_port)?,
         "c:player" => handle_player(body, players)?,
+        "c:broadcast" => handle_broadcast(body, source, &guard.host_token, &PENDING_BROADCASTS)?,
+        "c:poll" => handle_poll(body, &PENDING_BROADCASTS)?,
         _ => return Err(format!("未知 PaperConnect 请求：{request_type}")),
     };
     stream
@@ -282,9 +624,9 @@ async fn read_request(stream: &mut TcpStream) -> Result<String, String> {
     let mut buffer = [0_u8; 1024];
 
     loop {
-        let read = stream
-            .read(&mut buffer)
+        let read = tokio::time::timeout(READ_IDLE_TIMEOUT, stream.read(&mut buffer))
             .await
+            .map_err(|_| "PaperConnect 请求空闲超时".to_string())?
             .map_err(|error| format!("读取 PaperConnect 请求失败：{error}"))?;
         if read == 0 {
             break;
@@ -399,6 +741,52 @@ fn handle_player(
     .map_err(|error| format!("序列化 PaperConnect c:player 响应失败：{error}"))
 }
 
+/// 只接受房主本机发出的广播：来源必须是回环地址且携带本次联机中心的口令
+fn handle_broadcast(
+    body: &str,
+    source: IpAddr,
+    host_token: &str,
+    broadcasts: &Mutex<VecDeque<Broadcast>>,
+) -> Result<String, String> {
+    let request: BroadcastRequest = serde_json::from_str(body)
+        .map_err(|error| format!("PaperConnect c:broadcast 请求无效：{error}"))?;
+    if !source.is_loopback() || request.host_token != host_token {
+        return Err(format!("PaperConnect c:broadcast 只接受房主发送：{source}"));
+    }
+    let mut broadcasts = broadcasts
+        .lock()
+        .map_err(|_| "PaperConnect 广播状态锁已损坏".to_string())?;
+    enqueue_broadcast(&mut broadcasts, &request.message)?;
+    serde_json::to_string(&BroadcastResponse {
+        return_time: now_ms(),
+    })
+    .map_err(|error| format!("序列化 PaperConnect c:broadcast 响应失败：{error}"))
+}
+
+fn handle_poll(body: &str, broadcasts: &Mutex<VecDeque<Broadcast>>) -> Result<String, String> {
+    let request: PollRequest = if body.trim().is_empty() {
+        PollRequest::default()
+    } else {
+        serde_json::from_str(body)
+            .map_err(|error| format!("PaperConnect c:poll 请求无效：{error}"))?
+    };
+    let broadcasts: Vec<Broadcast> = broadcasts
+        .lock()
+        .map_err(|_| "PaperConnect 广播状态锁已损坏".to_string())?
+        .iter()
+        .filter(|broadcast| broadcast.id > request.since)
+        .cloned()
+        .collect();
+    let latest_id = broadcasts
+        .last()
+        .map_or(request.since, |broadcast| broadcast.id);
+    serde_json::to_string(&PollResponse {
+        broadcasts,
+        latest_id,
+    })
+    .map_err(|error| format!("序列化 PaperConnect c:poll 响应失败：{error}"))
+}
+
 fn prune_inactive_players(players: &Mutex<HashMap<String, PaperConnectPlayer>>) {
     let Ok(mut players) = players.lock() else {
         tracing::warn!("PaperConnect 玩家状态锁已损坏，跳过过期清理");
@@ -425,14 +813,183 @@ fn now_ms() -> i64 {
 #[cfg(test)]
 mod tests {
     use super::{
-        PaperConnectPlayer, PlayerResponse, REQUEST_TIMEOUT, client_id, handle_player, now_ms,
-        ping, players as player_snapshot, read_request, send_player, server_port_from_hostname,
-        start_client, start_server, stop_client, stop_server,
+        MAX_PENDING_BROADCASTS, PaperConnectPlayer, PlayerResponse, PollResponse, REQUEST_TIMEOUT,
+        ServerGuard, ServerLimits, accept_connection, client_id, handle_broadcast, handle_player,
+        handle_poll, now_ms, ping, players as player_snapshot, read_request, send_player,
+        server_port_from_hostname, start_client, start_server, stop_client, stop_server,
     };
-    use std::collections::HashMap;
+    use std::collections::{HashMap, VecDeque};
+    use std::net::IpAddr;
     use std::sync::{Arc, Mutex};
     use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
     use tokio::net::{TcpListener, TcpStream};
+    use tokio::task::JoinSet;
+
+    async fn accept_with_guard(
+        listener: &TcpListener,
+        guard: &Arc<ServerGuard>,
+        connections: &mut JoinSet<()>,
+    ) -> TcpStream {
+        let client = TcpStream::connect(listener.local_addr().expect("read guard test address"))
+            .await
+            .expect("connect guard test client");
+        let (stream, address) = listener.accept().await.expect("accept guard test client");
+        accept_connection(
+            guard,
+            connections,
+            stream,
+            address,
+            19132,
+            Arc::new(Mutex::new(HashMap::new())),
+        );
+        client
+    }
+
+    async fn assert_closed_immediately(mut client: TcpStream) {
+        let mut buffer = [0_u8; 1];
+        let read = tokio::time::timeout(REQUEST_TIMEOUT, client.read(&mut buffer))
+            .await
+            .expect("rejected connection should close without waiting");
+        assert!(matches!(read, Ok(0) | Err(_)));
+    }
+
+    #[tokio::test]
+    async fn connections_over_capacity_are_closed_immediately() {
+        let listener = TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .expect("bind capacity test listener");
+        let guard = Arc::new(ServerGuard::new(ServerLimits {
+            max_connections: 1,
+            ..ServerLimits::default()
+        }));
+        let mut connections = JoinSet::new();
+
+        let _idle_client = accept_with_guard(&listener, &guard, &mut connections).await;
+        let rejected_client = accept_with_guard(&listener, &guard, &mut connections).await;
+        assert_closed_immediately(rejected_client).await;
+
+        let snapshot = guard.snapshot();
+        assert_eq!(snapshot.accepted, 1);
+        assert_eq!(snapshot.rejected_over_capacity, 1);
+        assert_eq!(snapshot.active, 1);
+        connections.abort_all();
+    }
+
+    #[tokio::test]
+    async fn connection_bursts_from_one_source_are_rate_limited() {
+        let listener = TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .expect("bind rate limit test listener");
+        let guard = Arc::new(ServerGuard::new(ServerLimits {
+            connection_burst: 1.0,
+            connections_per_second: 0.001,
+            ..ServerLimits::default()
+        }));
+        let mut connections = JoinSet::new();
+
+        let mut first_client = accept_with_guard(&listener, &guard, &mut connections).await;
+        first_client
+            .write_all(b"c:ping\0{\"time\":1}")
+            .await
+            .expect("write first guarded request");
+        let mut response = Vec::new();
+        tokio::time::timeout(REQUEST_TIMEOUT, first_client.read_to_end(&mut response))
+            .await
+            .expect("first guarded request should be answered")
+            .expect("read first guarded response");
+        assert!(!response.is_empty());
+
+        let rejected_client = accept_with_guard(&listener, &guard, &mut connections).await;
+        assert_closed_immediately(rejected_client).await;
+
+        let snapshot = guard.snapshot();
+        assert_eq!(snapshot.accepted, 1);
+        assert_eq!(snapshot.rejected_rate_limited, 1);
+        connections.abort_all();
+    }
+
+    #[test]
+    fn requests_per_minute_are_limited_per_source() {
+        let guard = ServerGuard::new(ServerLimits {
+            requests_per_minute: 1.0,
+            ..ServerLimits::default()
+        });
+        let source = "10.144.144.2".parse().expect("parse test source");
+        let other_source = "10.144.144.3".parse().expect("parse other test source");
+
+        assert!(guard.allow_request(source));
+        assert!(!guard.allow_request(source));
+        assert!(guard.allow_request(other_source));
+        assert_eq!(guard.snapshot().rejected_rate_limited, 1);
+    }
+
+    #[test]
+    fn broadcasts_keep_latest_messages_and_poll_by_cursor() {
+        let broadcasts = Mutex::new(VecDeque::new());
+        let host = IpAddr::from([127, 0, 0, 1]);
+        let request = |message: &str, token: &str| {
+            serde_json::json!({ "message": message, "hostToken": token }).to_string()
+        };
+        for index in 0..=MAX_PENDING_BROADCASTS {
+            handle_broadcast(
+                &request(&format!("消息 {index}"), "token"),
+                host,
+                "token",
+                &broadcasts,
+            )
+            .expect("enqueue broadcast");
+        }
+        assert!(handle_broadcast(&request("   ", "token"), host, "token", &broadcasts).is_err());
+        assert!(
+            handle_broadcast(&request("冒充", "guess"), host, "token", &broadcasts).is_err(),
+            "wrong host token must be rejected"
+        );
+        assert!(
+            handle_broadcast(
+                &request("冒充", "token"),
+                IpAddr::from([10, 144, 144, 2]),
+                "token",
+                &broadcasts,
+            )
+            .is_err(),
+            "remote sources must be rejected"
+        );
+
+        let poll = |body: &str| -> PollResponse {
+            serde_json::from_str(&handle_poll(body, &broadcasts).expect("poll broadcasts"))
+                .expect("parse poll response")
+        };
+        let first = poll("");
+        assert_eq!(first.broadcasts.len(), MAX_PENDING_BROADCASTS);
+        assert_eq!(
+            first
+                .broadcasts
+                .first()
+                .map(|broadcast| broadcast.message.as_str()),
+            Some("消息 1")
+        );
+        assert!(
+            first
+                .broadcasts
+                .windows(2)
+                .all(|pair| pair[0].id < pair[1].id)
+        );
+        assert_eq!(
+            first.broadcasts.last().map(|broadcast| broadcast.id),
+            Some(first.latest_id)
+        );
+
+        let caught_up = poll(&serde_json::json!({ "since": first.latest_id }).to_string());
+        assert!(caught_up.broadcasts.is_empty());
+        assert_eq!(caught_up.latest_id, first.latest_id);
+
+        handle_broadcast(&request("新消息", "token"), host, "token", &broadcasts)
+            .expect("enqueue broadcast");
+        let next = poll(&serde_json::json!({ "since": first.latest_id }).to_string());
+        assert_eq!(next.broadcasts.len(), 1);
+        assert_eq!(next.broadcasts[0].message, "新消息");
+        assert!(next.latest_id > first.latest_id);
+    }
 
     #[test]
     fn only_paperconnect_server_hostname_is_discoverable() {
@@ -0,0 +1,438 @@
+use anyhow::{Context as _, anyhow};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::net::Ipv4Addr;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::{
+    DEFAULT_PAPERCONNECT_VIP, fallback_bootstrap_peers, merge_bootstrap_peers, now_ms,
+    sanitize_bootstrap_peers,
+};
+use crate::config::config::{get_default_config, read_config};
+use crate::http::proxy::get_client_for_proxy;
+use crate::http::request::{GLOBAL_CLIENT, RequestOptions, send_request_with_options};
+use crate::utils::file_ops;
+use crate::utils::network::should_save_bandwidth;
+
+const DEFAULT_PAPERCONNECT_PREFIX: u8 = 24;
+/// 当前构建内置的 PaperConnect ACL 模板版本，远端要求更高版本时整份策略作废。
+const SUPPORTED_ACL_TEMPLATE_VERSION: u32 = 1;
+const POLICY_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+const POLICY_CACHE_FILE_NAME: &str = "online_policy.json";
+const POLICY_CACHE_SCHEMA_VERSION: u32 = 1;
+const POLICY_FETCH_TIMEOUT_MS: u64 = 5_000;
+/// 拉取或校验失败后，在这段时间内直接使用旧缓存或内置默认值，不再重复请求。
+const POLICY_FAILURE_BACKOFF: Duration = Duration::from_secs(60 * 60);
+/// 发布构建注入的 Ed25519 公钥，`<policy_url>.sig` 为文档原始字节签名的十六进制，
+/// 签名流程见 docs/ONLINE_POLICY.md。未注入时不拉取远端策略，只使用内置默认值。
+const POLICY_PUBLIC_KEY_HEX: Option<&str> = option_env!("BMCBL_ONLINE_POLICY_PUBLIC_KEY");
+
+static LAST_POLICY_FAILURE: Mutex<Option<(String, Instant)>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum OnlinePolicySource {
+    Builtin,
+    Remote,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnlinePolicyStatus {
+    pub source: OnlinePolicySource,
+    pub fetched_at: Option<i64>,
+}
+
+/// 远端联机策略文档，所有字段均可省略，省略时沿用内置默认值。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OnlinePolicyDocument {
+    #[serde(default)]
+    bootstrap_peers: Vec<String>,
+    #[serde(default)]
+    paperconnect_vip: Option<String>,
+    #[serde(default)]
+    paperconnect_prefix: Option<u8>,
+    #[serde(default)]
+    acl_template_version: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PolicyCacheFile {
+    #[serde(default)]
+    schema_version: u32,
+    ts_unix_ms: i64,
+    url: String,
+    document: OnlinePolicyDocument,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct OnlinePolicy {
+    pub(super) bootstrap_peers: Vec<String>,
+    pub(super) paperconnect_vip: cidr::Ipv4Inet,
+    pub(super) source: OnlinePolicySource,
+    pub(super) fetched_at: Option<i64>,
+}
+
+impl OnlinePolicy {
+    pub(super) fn builtin() -> Self {
+        let vip =
+            Ipv4Addr::from_str(DEFAULT_PAPERCONNECT_VIP).unwrap_or(Ipv4Addr::new(10, 144, 144, 1));
+        let paperconnect_vip = cidr::Ipv4Inet::new(vip, DEFAULT_PAPERCONNECT_PREFIX)
+            .unwrap_or_else(|_| cidr::Ipv4Inet::new_host(vip));
+        Self {
+            bootstrap_peers: fallback_bootstrap_peers(),
+            paperconnect_vip,
+            source: OnlinePolicySource::Builtin,
+            fetched_at: None,
+        }
+    }
+
+    pub(super) fn vip_address(&self) -> String {
+        self.paperconnect_vip.address().to_string()
+    }
+
+    pub(super) fn status(&self) -> OnlinePolicyStatus {
+        OnlinePolicyStatus {
+            source: self.source,
+            fetched_at: self.fetched_at,
+        }
+    }
+}
+
+fn cache_path() -> PathBuf {
+    file_ops::cache_subdir("api").join(POLICY_CACHE_FILE_NAME)
+}
+
+fn read_cache(url: &str) -> Option<PolicyCacheFile> {
+    let raw = fs::read_to_string(cache_path()).ok()?;
+    let cache: PolicyCacheFile = serde_json::from_str(&raw).ok()?;
+    (cache.schema_version == POLICY_CACHE_SCHEMA_VERSION && cache.url == url).then_some(cache)
+}
+
+fn write_cache(cache: &PolicyCacheFile) -> anyhow::Result<()> {
+    let path = cache_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context("create online policy cache dir failed")?;
+    }
+    let raw = serde_json::to_string(cache).context("serialize online policy cache failed")?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, raw).context("write online policy cache failed")?;
+    fs::rename(&tmp, &path).context("replace online policy cache failed")?;
+    Ok(())
+}
+
+fn policy_url() -> String {
+    let configured = read_config()
+        .map(|config| config.online.policy_url)
+        .unwrap_or_default();
+    if configured.trim().is_empty() {
+        get_default_config().online.policy_url.trim().to_string()
+    } else {
+        configured.trim().to_string()
+    }
+}
+
+/// 远端策略地址与验签公钥，两者缺一就不启用远端策略
+fn remote_policy_source() -> Option<(String, &'static str)> {
+    // CI 里未配置的 secret 会以空字符串出现
+    let public_key_hex = POLICY_PUBLIC_KEY_HEX
+        .map(str::trim)
+        .filter(|key| !key.is_empty())?;
+    let url = policy_url();
+    (!url.is_empty()).then_some((url, public_key_hex))
+}
+
+fn recently_failed(url: &str) -> bool {
+    LAST_POLICY_FAILURE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .is_some_and(|(failed_url, failed_at)| {
+            failed_url == url && failed_at.elapsed() < POLICY_FAILURE_BACKOFF
+        })
+}
+
+fn set_last_failure(failure: Option<(String, Instant)>) {
+    *LAST_POLICY_FAILURE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner()) = failure;
+}
+
+fn verify_policy_signature(
+    body: &[u8],
+    signature_hex: &str,
+    public_key_hex: &str,
+) -> anyhow::Result<()> {
+    let signature =
+        hex::decode(signature_hex.trim()).context("online policy: invalid signature encoding")?;
+    let public_key = hex::decode(public_key_hex).context("online policy: invalid public key")?;
+    ring::signature::UnparsedPublicKey::new(&ring::signature::ED25519, public_key)
+        .verify(body, &signature)
+        .map_err(|_| anyhow!("online policy: signature mismatch"))
+}
+
+/// 校验远端策略并与内置默认值合并；任何一项不合法都整体拒绝，避免半套策略生效。
+fn validate_policy_document(
+    document: OnlinePolicyDocument,
+    fetched_at: i64,
+) -> anyhow::Result<OnlinePolicy> {
+    let acl_template_version = document
+        .acl_template_version
+        .unwrap_or(SUPPORTED_ACL_TEMPLATE_VERSION);
+    if acl_template_version == 0 || acl_template_version > SUPPORTED_ACL_TEMPLATE_VERSION {
+        return Err(anyhow!(
+            "online policy: unsupported acl template version {acl_template_version}"
+        ));
+    }
+
+    let remote_peers = sanitize_bootstrap_peers(document.bootstrap_peers);
+    if remote_peers.is_empty() {
+        return Err(anyhow!("online policy: no usable bootstrap peers"));
+    }
+
+    let builtin = OnlinePolicy::builtin();
+    let vip = match document.paperconnect_vip.as_deref().map(str::trim) {
+        Some(raw) if !raw.is_empty() => Ipv4Addr::from_str(raw)
+            .with_context(|| format!("online policy: invalid paperconnect vip: {raw}"))?,
+        _ => builtin.paperconnect_vip.address(),
+    };
+    let prefix = document
+        .paperconnect_prefix
+        .unwrap_or(DEFAULT_PAPERCONNECT_PREFIX);
+    if !(8..=30).contains(&prefix) {
+        return Err(anyhow!(
+            "online policy: invalid paperconnect prefix {prefix}"
+        ));
+    }
+    let paperconnect_vip = cidr::Ipv4Inet::new(vip, prefix)
+        .map_err(|error| anyhow!("online policy: invalid paperconnect subnet: {error}"))?;
+    let network = paperconnect_vip.network();
+    if vip == network.first_address() || vip == network.last_address() {
+        return Err(anyhow!(
+            "online policy: paperconnect vip {vip} is not a host address of {network}"
+        ));
+    }
+
+    Ok(OnlinePolicy {
+        bootstrap_peers: merge_bootstrap_peers(remote_peers, builtin.bootstrap_peers),
+        paperconnect_vip,
+        source: OnlinePolicySource::Remote,
+        fetched_at: Some(fetched_at),
+    })
+}
+
+async fn fetch_text(client: &reqwest::Client, url: &str, accept: &str) -> anyhow::Result<String> {
+    let url =
+        reqwest::Url::parse(url).with_context(|| format!("invalid online policy url: {url}"))?;
+    let mut headers = HashMap::new();
+    headers.insert("Accept".to_string(), accept.to_string());
+    let options = RequestOptions {
+        method: "GET",
+        headers: Some(&headers),
+        timeout_ms: Some(POLICY_FETCH_TIMEOUT_MS),
+        allow_redirects: Some(true),
+    };
+
+    let response = send_request_with_options(client, &url, &options)
+        .await
+        .map_err(|error| anyhow!(error))?;
+    let response = response
+        .error_for_status()
+        .with_context(|| format!("{url} returned error status"))?;
+    response
+        .text()
+        .await
+        .with_context(|| format!("read {url} failed"))
+}
+
+async fn fetch_policy_document(
+    url: &str,
+    public_key_hex: &str,
+) -> anyhow::Result<OnlinePolicyDocument> {
+    let client = get_client_for_proxy().unwrap_or_else(|error| {
+        tracing::debug!("proxy client build failed, using global client: {error:?}");
+        GLOBAL_CLIENT.clone()
+    });
+
+    let body = fetch_text(&client, url, "application/json").await?;
+    let signature = fetch_text(&client, &format!("{url}.sig"), "text/plain").await?;
+    verify_policy_signature(body.as_bytes(), &signature, public_key_hex)?;
+    serde_json::from_str(&body).context("online policy: invalid json")
+}
+
+async fn fetch_remote_policy(url: &str, public_key_hex: &str) -> anyhow::Result<OnlinePolicy> {
+    let document = fetch_policy_document(url, public_key_hex).await?;
+    let fetched_at = now_ms();
+    let policy = validate_policy_document(document.clone(), fetched_at)?;
+    let cache = PolicyCacheFile {
+        schema_version: POLICY_CACHE_SCHEMA_VERSION,
+        ts_unix_ms: fetched_at,
+        url: url.to_string(),
+        document,
+    };
+    if let Err(error) = write_cache(&cache) {
+        tracing::warn!("write online policy cache failed: {error:#}");
+    }
+    Ok(policy)
+}
+
+/// 解析当前生效的联机策略：24 小时内的缓存直接使用（计费网络下不论新旧），过期后重新拉取，
+/// 拉取或校验失败时退回仍可用的旧缓存，最终退回内置默认值；失败后一小时内不再重复拉取。
+/// 构建未注入签名公钥或没有策略地址时直接使用内置默认值。
+pub(super) async fn resolve_online_policy() -> OnlinePolicy {
+    let Some((url, public_key_hex)) = remote_policy_source() else {
+        return OnlinePolicy::builtin();
+    };
+    let cached = read_cache(&url);
+
+    if let Some(cache) = cached.as_ref() {
+        let age_ms = now_ms().saturating_sub(cache.ts_unix_ms).max(0) as u64;
+        // 计费网络下不重新验证，过期缓存只要仍能通过校验就继续使用
+        if Duration::from_millis(age_ms) <= POLICY_CACHE_TTL || should_save_bandwidth() {
+            match validate_policy_document(cache.document.clone(), cache.ts_unix_ms) {
+                Ok(policy) => return policy,
+                Err(error) => tracing::warn!("cached online policy rejected: {error:#}"),
+            }
+        }
+    }
+
+    if recently_failed(&url) {
+        tracing::debug!("online policy fetch failed recently; skipping remote fetch");
+    } else {
+        match fetch_remote_policy(&url, public_key_hex).await {
+            Ok(policy) => {
+                set_last_failure(None);
+                return policy;
+            }
+            Err(error) => {
+                tracing::warn!("online policy unavailable: {error:#}");
+                set_last_failure(Some((url, Instant::now())));
+            }
+        }
+    }
+
+    if let Some(cache) = cached
+        && let Ok(policy) = validate_policy_document(cache.document, cache.ts_unix_ms)
+    {
+        tracing::info!("using stale online policy fetched at {}", cache.ts_unix_ms);
+        return policy;
+    }
+
+    OnlinePolicy::builtin()
+}
+
+/// 刷新联机策略缓存（遵循每日一次的拉取频率）并返回生效来源。
+pub async fn refresh_online_policy() -> OnlinePolicyStatus {
+    resolve_online_policy().await.status()
+}
+
+/// 只读本地缓存得出当前生效来源，不发起网络请求。
+pub fn cached_online_policy_status() -> OnlinePolicyStatus {
+    remote_policy_source()
+        .and_then(|(url, _)| read_cache(&url))
+        .and_then(|cache| validate_policy_document(cache.document, cache.ts_unix_ms).ok())
+        .unwrap_or_else(OnlinePolicy::builtin)
+        .status()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        OnlinePolicy, OnlinePolicyDocument, OnlinePolicySource, recently_failed, set_last_failure,
+        validate_policy_document, verify_policy_signature,
+    };
+    use ring::signature::{Ed25519KeyPair, KeyPair as _};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn remote_policy_is_merged_with_builtin_peers() {
+        let document = OnlinePolicyDocument {
+            bootstrap_peers: vec![
+                "tcp://relay.example:54321".to_string(),
+                "not a url".to_string(),
+            ],
+            paperconnect_vip: Some("10.150.0.1".to_string()),
+            paperconnect_prefix: Some(16),
+            acl_template_version: Some(1),
+        };
+
+        let policy = validate_policy_document(document, 42).expect("policy should be accepted");
+
+        assert_eq!(policy.source, OnlinePolicySource::Remote);
+        assert_eq!(policy.fetched_at, Some(42));
+        assert_eq!(policy.bootstrap_peers[0], "tcp://relay.example:54321");
+        for peer in OnlinePolicy::builtin().bootstrap_peers {
+            assert!(policy.bootstrap_peers.contains(&peer));
+        }
+        assert_eq!(policy.paperconnect_vip.to_string(), "10.150.0.1/16");
+    }
+
+    #[test]
+    fn malformed_remote_policy_is_rejected() {
+        let unusable_peers = OnlinePolicyDocument {
+            bootstrap_peers: vec!["https://relay.example".to_string(), "::".to_string()],
+            ..OnlinePolicyDocument::default()
+        };
+        assert!(validate_policy_document(unusable_peers, 0).is_err());
+
+        let network_address_vip = OnlinePolicyDocument {
+            bootstrap_peers: vec!["tcp://relay.example:54321".to_string()],
+            paperconnect_vip: Some("10.144.144.0".to_string()),
+            ..OnlinePolicyDocument::default()
+        };
+        assert!(validate_policy_document(network_address_vip, 0).is_err());
+
+        let future_acl_template = OnlinePolicyDocument {
+            bootstrap_peers: vec!["tcp://relay.example:54321".to_string()],
+            acl_template_version: Some(99),
+            ..OnlinePolicyDocument::default()
+        };
+        assert!(validate_policy_document(future_acl_template, 0).is_err());
+    }
+
+    #[test]
+    fn policy_signature_must_match_document() {
+        let rng = ring::rand::SystemRandom::new();
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&rng).expect("generate test key");
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).expect("load test key");
+        let public_key = hex::encode(key_pair.public_key().as_ref());
+        let body = br#"{"bootstrapPeers":["tcp://relay.example:54321"]}"#;
+        let signature = hex::encode(key_pair.sign(body).as_ref());
+
+        assert!(verify_policy_signature(body, &signature, &public_key).is_ok());
+        assert!(verify_policy_signature(b"{}", &signature, &public_key).is_err());
+        assert!(verify_policy_signature(body, "not hex", &public_key).is_err());
+    }
+
+    #[test]
+    fn failed_fetch_is_not_retried_within_backoff() {
+        let url = "https://policy.example/failed.json";
+        assert!(!recently_failed(url));
+
+        set_last_failure(Some((url.to_string(), Instant::now())));
+        assert!(recently_failed(url));
+        assert!(!recently_failed("https://policy.example/other.json"));
+
+        let expired = Instant::now()
+            .checked_sub(Duration::from_secs(2 * 60 * 60))
+            .expect("instant in the past");
+        set_last_failure(Some((url.to_string(), expired)));
+        assert!(!recently_failed(url));
+        set_last_failure(None);
+    }
+
+    #[test]
+    fn builtin_policy_always_has_peers() {
+        let policy = OnlinePolicy::builtin();
+
+        assert_eq!(policy.source, OnlinePolicySource::Builtin);
+        assert!(!policy.bootstrap_peers.is_empty());
+        assert_eq!(policy.vip_address(), "10.144.144.1");
+    }
+}
@@ -1,3 +1,4 @@
+use crate::core::cache_registry::{CacheDescriptor, clear_dir, dir_usage};
 use crate::http::proxy::get_client_for_proxy;
 use serde::Deserialize;
 use std::fs;
@@ -8,6 +9,15 @@ const SPONSORS_API_URL: &str = "https://api.chlna6666.com/api/v1/sponsors";
 const SPONSOR_API_HOST: &str = "api.chlna6666.com";
 const SPONSOR_AVATAR_CACHE_DIR_NAME: &str = "bmbl_sponsors_avatar_cache";
 
+pub(crate) static CACHE: CacheDescriptor = CacheDescriptor {
+    name: "sponsor_avatars",
+    label: "赞助者头像",
+    default_selected: true,
+    usage: || dir_usage(&sponsor_avatar_cache_dir()),
+    clear: || clear_dir(&sponsor_avatar_cache_dir()),
+    warm: None,
+};
+
 #[derive(Clone, Debug)]
 pub(crate) struct SponsorRecord {
     pub(crate) user_id: String,
@@ -131,7 +141,7 @@ pub(crate) async fn load_sponsors() -> Result<Vec<SponsorRecord>, String> {
     Ok(sponsors)
 }
 
-fn sponsor_avatar_cache_dir() -> PathBuf {
+pub(crate) fn sponsor_avatar_cache_dir() -> PathBuf {
     #[cfg(target_os = "linux")]
     {
         crate::utils::file_ops::cache_subdir(SPONSOR_AVATAR_CACHE_DIR_NAME)
@@ -28,6 +28,17 @@ pub async fn delete_version(folder_name: &str) -> Result<()> {
     let version_dir_for_log = version_dir.clone();
     let start = Instant::now();
 
+    #[cfg(target_os = "windows")]
+    if let Some(install) = crate::core::version::packaged::read_packaged_install(&version_dir) {
+        debug!(
+            "删除 APPX 包版本，先注销已安装的包: folder={}, appx={}",
+            folder_name, install.appx_path
+        );
+        crate::core::version::packaged::unregister_packaged_install(&install)
+            .await
+            .map_err(anyhow::Error::msg)?;
+    }
+
     debug!(
         "开始删除版本目录: folder={}, path={}",
         folder_name,
@@ -1,9 +1,18 @@
 // src-tauri/src/commands/gdk_users.rs
+use once_cell::sync::Lazy;
+use rayon::prelude::*;
 use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use std::{fs, path::PathBuf};
 
 use tracing::{debug, error, info, warn};
 
+use crate::core::cache_registry::{CacheDescriptor, CacheUsage};
+use crate::utils::file_ops;
+
 #[derive(Debug, Serialize)]
 pub struct GdkUser {
     pub path: String,
@@ -18,15 +27,13 @@ pub fn get_gdk_users(edition: String) -> Result<Vec<GdkUser>, String> {
 
     debug!("get_gdk_users called (edition='{}')", edition);
 
-    let roaming_os = std::env::var_os("APPDATA");
-    let roaming = match roaming_os {
-        Some(v) => {
-            let p = PathBuf::from(v);
+    let roaming = match crate::core::minecraft::paths::roaming_appdata_dir() {
+        Some(p) => {
             debug!("APPDATA (Roaming) detected: {}", p.to_string_lossy());
             p
         }
         None => {
-            warn!("APPDATA environment variable not found; returning empty result");
+            warn!("Roaming AppData could not be resolved; returning empty result");
             return Ok(res);
         }
     };
@@ -100,3 +107,212 @@ pub fn get_gdk_users(edition: String) -> Result<Vec<GdkUser>, String> {
 
     Ok(res)
 }
+
+const GDK_DATA_ROOTS_CACHE_TTL: Duration = Duration::from_secs(30);
+const GDK_PACKAGE_FOLDER_PREFIX: &str = "Minecraft Bedrock";
+
+static GDK_DATA_ROOTS_CACHE: Lazy<Mutex<HashMap<String, GdkDataRootsCache>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub(crate) static CACHE: CacheDescriptor = CacheDescriptor {
+    name: "gdk_users",
+    label: "GDK 用户目录",
+    default_selected: true,
+    usage: gdk_data_roots_cache_usage,
+    clear: clear_gdk_data_roots_cache,
+    warm: None,
+};
+
+fn gdk_data_roots_cache_usage() -> CacheUsage {
+    CacheUsage {
+        entries: GDK_DATA_ROOTS_CACHE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .len() as u64,
+        bytes: 0,
+    }
+}
+
+fn clear_gdk_data_roots_cache() -> Result<CacheUsage, String> {
+    let mut cache = GDK_DATA_ROOTS_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let entries = cache.len() as u64;
+    cache.clear();
+    Ok(CacheUsage { entries, bytes: 0 })
+}
+
+struct GdkDataRootsCache {
+    fetched_at: Instant,
+    roots: Vec<GdkDataRoot>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GdkDataRoot {
+    pub user_folder: String,
+    pub mojang_dir: String,
+    pub edition: String,
+    pub exists: bool,
+    pub size_bytes: Option<u64>,
+}
+
+/// 列出版本隔离目录下所有 GDK 用户的 com.mojang 路径
+/// 扫描: <数据目录>/versions/<version_name>/Minecraft Bedrock*/Users/*/games/com.mojang
+pub fn get_gdk_data_roots(version_name: String) -> Result<Vec<GdkDataRoot>, String> {
+    if version_name.is_empty()
+        || version_name.contains("..")
+        || version_name.contains('/')
+        || version_name.contains('\\')
+    {
+        return Err("Invalid version name".into());
+    }
+
+    if let Ok(cache) = GDK_DATA_ROOTS_CACHE.lock()
+        && let Some(entry) = cache.get(&version_name)
+        && entry.fetched_at.elapsed() < GDK_DATA_ROOTS_CACHE_TTL
+    {
+        return Ok(entry.roots.clone());
+    }
+
+    let version_dir = file_ops::bmcbl_subdir("versions").join(&version_name);
+    if !version_dir.is_dir() {
+        return Err(format!("版本目录不存在: {}", version_dir.display()));
+    }
+
+    let started = Instant::now();
+    let roots = scan_gdk_data_roots(&version_dir);
+    debug!(
+        "get_gdk_data_roots 完成: version={}, roots={}, elapsed={:?}",
+        version_name,
+        roots.len(),
+        started.elapsed()
+    );
+
+    if let Ok(mut cache) = GDK_DATA_ROOTS_CACHE.lock() {
+        cache.insert(
+            version_name,
+            GdkDataRootsCache {
+                fetched_at: Instant::now(),
+                roots: roots.clone(),
+            },
+        );
+    }
+    Ok(roots)
+}
+
+fn scan_gdk_data_roots(version_dir: &Path) -> Vec<GdkDataRoot> {
+    let mut candidates = Vec::new();
+    for package_dir in read_child_dirs(version_dir) {
+        let Some(edition) = dir_name(&package_dir) else {
+            continue;
+        };
+        if !edition.starts_with(GDK_PACKAGE_FOLDER_PREFIX) {
+            continue;
+        }
+
+        for user_dir in read_child_dirs(&package_dir.join("Users")) {
+            let Some(user_folder) = dir_name(&user_dir) else {
+                continue;
+            };
+            if user_folder.eq_ignore_ascii_case("public") {
+                continue;
+            }
+            candidates.push((
+                user_folder,
+                edition.clone(),
+                user_dir.join("games").join("com.mojang"),
+            ));
+        }
+    }
+
+    let mut roots = candidates
+        .into_par_iter()
+        .map(|(user_folder, edition, mojang_dir)| {
+            let exists = mojang_dir.is_dir();
+            let size_bytes = exists.then(|| file_ops::dir_size(&mojang_dir)).flatten();
+            GdkDataRoot {
+                user_folder,
+                mojang_dir: mojang_dir.to_string_lossy().into_owned(),
+                edition,
+                exists,
+                size_bytes,
+            }
+        })
+        .collect::<Vec<_>>();
+    roots.sort_by(|left, right| {
+        left.edition
+            .cmp(&right.edition)
+            .then_with(|| left.user_folder.cmp(&right.user_folder))
+    });
+    roots
+}
+
+fn read_child_dirs(path: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(path) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+        .map(|entry| entry.path())
+        .collect()
+}
+
+fn dir_name(path: &Path) -> Option<String> {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_gdk_data_roots_lists_every_user_of_every_package_folder() {
+        let version_dir = std::env::temp_dir().join(format!(
+            "bmcbl-gdk-data-roots-{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .expect("system time should be after unix epoch")
+                .as_nanos()
+        ));
+        let release_user = version_dir
+            .join("Minecraft Bedrock")
+            .join("Users")
+            .join("4173542688423936997")
+            .join("games")
+            .join("com.mojang");
+        fs::create_dir_all(&release_user).expect("create release user dir");
+        fs::write(release_user.join("options.txt"), b"1234").expect("write options");
+        fs::create_dir_all(
+            version_dir
+                .join("Minecraft Bedrock Preview")
+                .join("Users")
+                .join("Shared"),
+        )
+        .expect("create preview shared dir");
+        fs::create_dir_all(
+            version_dir
+                .join("Minecraft Bedrock")
+                .join("Users")
+                .join("Public"),
+        )
+        .expect("create public dir");
+        fs::create_dir_all(version_dir.join("data").join("Users").join("ignored"))
+            .expect("create unrelated dir");
+
+        let roots = scan_gdk_data_roots(&version_dir);
+
+        assert_eq!(roots.len(), 2);
+        assert_eq!(roots[0].edition, "Minecraft Bedrock");
+        assert_eq!(roots[0].user_folder, "4173542688423936997");
+        assert!(roots[0].exists);
+        assert_eq!(roots[0].size_bytes, Some(4));
+        assert_eq!(roots[1].edition, "Minecraft Bedrock Preview");
+        assert_eq!(roots[1].user_folder, "Shared");
+        assert!(!roots[1].exists);
+        assert_eq!(roots[1].size_bytes, None);
+
+        fs::remove_dir_all(&version_dir).expect("remove test dir");
+    }
+}
@@ -0,0 +1,257 @@
+//! 已安装的 Minecraft 版本总览：合并系统中已注册的 UWP 包（按已知 AUMID 查询）
+//! 与 `versions/` 下带 `Minecraft.Windows.exe` 的散装版本目录。
+
+#[cfg(target_os = "windows")]
+use crate::core::minecraft::appx::utils::{
+    get_executable_product_version, get_manifest_identity_from_dir_blocking,
+};
+#[cfg(target_os = "linux")]
+use crate::core::minecraft::appx_utils::{
+    get_executable_product_version, get_manifest_identity_from_dir_blocking,
+};
+use crate::core::minecraft::editions::edition_by_identity;
+#[cfg(target_os = "windows")]
+use crate::core::minecraft::editions::known_editions;
+use crate::core::minecraft::launcher::history::get_launch_history;
+use crate::core::minecraft::paths::Edition;
+use crate::core::version::version_manager::is_win32_version;
+use crate::utils::file_ops;
+use chrono::DateTime;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+const GAME_EXECUTABLE_NAME: &str = "Minecraft.Windows.exe";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MinecraftEdition {
+    Release,
+    Preview,
+    Education,
+    EducationPreview,
+}
+
+impl From<&Edition> for MinecraftEdition {
+    fn from(edition: &Edition) -> Self {
+        match edition {
+            Edition::Release => MinecraftEdition::Release,
+            Edition::Preview => MinecraftEdition::Preview,
+            Edition::Education => MinecraftEdition::Education,
+            Edition::EducationPreview => MinecraftEdition::EducationPreview,
+        }
+    }
+}
+
+impl MinecraftEdition {
+    /// 按版本注册表识别包标识；未知的包标识按正式版处理，与启动时的 AUMID 映射保持一致
+    fn from_identity_name(identity_name: &str) -> Self {
+        edition_by_identity(identity_name)
+            .map(|entry| MinecraftEdition::from(&entry.edition))
+            .unwrap_or(MinecraftEdition::Release)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MinecraftEditionInfo {
+    pub edition: MinecraftEdition,
+    pub version: String,
+    pub install_path: String,
+    /// 是否为系统中当前注册的包
+    pub is_registered: bool,
+    pub is_gdk: bool,
+    /// 最近一次通过启动器启动的时间（RFC 3339），来自启动历史
+    pub last_launched: Option<String>,
+}
+
+/// 列出已注册的 UWP 包与 `versions/` 下的散装版本；注册的包指向版本目录时合并为一条
+pub async fn list_installed_minecraft_editions() -> Result<Vec<MinecraftEditionInfo>, String> {
+    tokio::task::spawn_blocking(list_installed_minecraft_editions_blocking)
+        .await
+        .map_err(|e| format!("查询已安装版本任务失败: {}", e))?
+}
+
+fn list_installed_minecraft_editions_blocking() -> Result<Vec<MinecraftEditionInfo>, String> {
+    let versions_dir = file_ops::versions_dir();
+    let mut editions = scan_version_dirs(&versions_dir)?;
+
+    for registered in registered_packages() {
+        let registered_path = normalized_path(Path::new(&registered.install_path));
+        if let Some(existing) = editions.iter_mut().find(|info| {
+            info.edition == registered.edition
+                && normalized_path(Path::new(&info.install_path)) == registered_path
+        }) {
+            existing.is_registered = true;
+            continue;
+        }
+        editions.push(registered);
+    }
+
+    editions.sort_by(|left, right| {
+        right
+            .is_registered
+            .cmp(&left.is_registered)
+            .then_with(|| left.install_path.cmp(&right.install_path))
+    });
+    Ok(editions)
+}
+
+fn normalized_path(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn last_launched(folder_name: &str) -> Option<String> {
+    let latest = get_launch_history(folder_name.to_string(), 1)
+        .map_err(|e| debug!("读取启动历史失败: {}", e))
+        .ok()?
+        .into_iter()
+        .next()?;
+    DateTime::from_timestamp_millis(latest.started_at).map(|time| time.to_rfc3339())
+}
+
+fn scan_version_dirs(versions_dir: &Path) -> Result<Vec<MinecraftEditionInfo>, String> {
+    let entries = match fs::read_dir(versions_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(format!(
+                "读取版本目录失败 {}: {}",
+                versions_dir.display(),
+                e
+            ));
+        }
+    };
+
+    let mut editions = Vec::new();
+    for entry in entries.flatten() {
+        let version_dir = entry.path();
+        let executable = version_dir.join(GAME_EXECUTABLE_NAME);
+        if !executable.is_file() {
+            continue;
+        }
+        let folder_name = entry.file_name().to_string_lossy().into_owned();
+
+        let (identity_name, manifest_version) =
+            match get_manifest_identity_from_dir_blocking(&version_dir) {
+                Ok(identity) => identity,
+                Err(e) => {
+                    debug!("读取版本 Manifest 失败，跳过: {}", e);
+                    continue;
+                }
+            };
+        let version = match get_executable_product_version(&executable) {
+            Ok(Some(version)) => version,
+            Ok(None) => manifest_version,
+            Err(e) => {
+                debug!("PE 版本解析失败，使用 manifest 版本: {}", e);
+                manifest_version
+            }
+        };
+
+        editions.push(MinecraftEditionInfo {
+            edition: MinecraftEdition::from_identity_name(&identity_name),
+            is_gdk: is_win32_version(&version),
+            version,
+            install_path: version_dir.to_string_lossy().into_owned(),
+            is_registered: false,
+            last_launched: last_launched(&folder_name),
+        });
+    }
+    Ok(editions)
+}
+
+#[cfg(target_os = "windows")]
+fn registered_packages() -> Vec<MinecraftEditionInfo> {
+    use crate::core::minecraft::appx::utils::get_package_info;
+
+    let versions_dir = normalized_path(&file_ops::versions_dir());
+    let mut editions = Vec::new();
+    for entry in known_editions() {
+        let aumid = entry.aumid;
+        let version = match get_package_info(&aumid) {
+            Ok(Some((version, _, _))) => version,
+            Ok(None) => continue,
+            Err(e) => {
+                debug!("未找到已注册的包 {}: {}", aumid, e);
+                continue;
+            }
+        };
+        let Some(install_path) = package_install_path(&aumid) else {
+            continue;
+        };
+
+        // 从启动器版本目录注册的包，按目录名查找启动历史
+        let install_dir = normalized_path(&install_path);
+        let launched_at = install_dir
+            .strip_prefix(&versions_dir)
+            .ok()
+            .and_then(|relative| relative.components().next())
+            .and_then(|folder| last_launched(&folder.as_os_str().to_string_lossy()));
+
+        editions.push(MinecraftEditionInfo {
+            edition: MinecraftEdition::from(&entry.edition),
+            is_gdk: is_win32_version(&version),
+            version,
+            install_path: install_path.to_string_lossy().into_owned(),
+            is_registered: true,
+            last_launched: launched_at,
+        });
+    }
+    editions
+}
+
+#[cfg(target_os = "windows")]
+fn package_install_path(aumid: &str) -> Option<PathBuf> {
+    let app_info = windows::ApplicationModel::AppInfo::GetFromAppUserModelId(&aumid.into()).ok()?;
+    let location = app_info.Package().ok()?.InstalledPath().ok()?;
+    Some(PathBuf::from(location.to_string()))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn registered_packages() -> Vec<MinecraftEditionInfo> {
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_identity_falls_back_to_release() {
+        assert_eq!(
+            MinecraftEdition::from_identity_name("microsoft.minecraftwindowsbeta"),
+            MinecraftEdition::Preview
+        );
+        assert_eq!(
+            MinecraftEdition::from_identity_name("Microsoft.MinecraftEducationPreview"),
+            MinecraftEdition::EducationPreview
+        );
+        assert_eq!(
+            MinecraftEdition::from_identity_name("Some.Other.Package"),
+            MinecraftEdition::Release
+        );
+    }
+
+    #[test]
+    fn version_dirs_without_game_executable_are_skipped() {
+        let versions_dir =
+            std::env::temp_dir().join(format!("bmcbl-installations-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(versions_dir.join("empty")).expect("create version dir");
+        fs::write(
+            versions_dir.join("empty").join("AppxManifest.xml"),
+            "<Package/>",
+        )
+        .expect("write manifest");
+
+        let editions = scan_version_dirs(&versions_dir).expect("scan versions");
+        assert!(editions.is_empty());
+        assert!(
+            scan_version_dirs(&versions_dir.join("missing"))
+                .expect("missing dir")
+                .is_empty()
+        );
+
+        fs::remove_dir_all(&versions_dir).expect("remove test dir");
+    }
+}
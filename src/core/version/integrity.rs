@@ -0,0 +1,353 @@
+//! 版本目录完整性锁文件：安装完成后记录 `versions/<name>/.bmcbl_integrity.json`
+//! （相对路径 → 大小、修改时间，exe/dll 额外记录 SHA-256），用于发现启动器之外的手动改动。
+//! 锁文件只在安装后或用户显式刷新时重新生成，认可的改动刷新后不再警告。
+
+use crate::core::minecraft::launcher::history::sha256_file;
+use crate::core::version::version_info::VERSION_INFO_FILE_NAME;
+use crate::utils::file_ops;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::warn;
+use walkdir::WalkDir;
+
+pub const INTEGRITY_FILE_NAME: &str = ".bmcbl_integrity.json";
+/// 启动器在启动时会改写或生成的文件，不参与校验
+const LAUNCHER_MANAGED_FILES: [&str; 6] = [
+    INTEGRITY_FILE_NAME,
+    VERSION_INFO_FILE_NAME,
+    "config.json",
+    "BLoader.dll",
+    "preloader.json",
+    "AppxSignature.p7x",
+];
+const LAUNCHER_MANAGED_ROOT_FILES: [&str; 1] = ["icon.png"];
+const LAUNCHER_MANAGED_DIRS: [&str; 2] = ["mods", "Minecraft Bedrock"];
+const HASHED_EXTENSIONS: [&str; 2] = ["exe", "dll"];
+const EXE_BACKUP_SUFFIX: &str = ".bak";
+const MAX_WARNING_PATHS: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntegrityEntry {
+    pub size: u64,
+    /// 修改时间（Unix 毫秒）
+    pub mtime: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IntegrityLock {
+    pub generated_at: i64,
+    pub files: BTreeMap<String, IntegrityEntry>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IntegrityReport {
+    pub has_lockfile: bool,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<String>,
+}
+
+impl IntegrityReport {
+    pub fn is_clean(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    /// 启动任务日志中展示的差异摘要，每类最多列出若干条
+    pub fn warning_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for (label, paths) in [
+            ("新增", &self.added),
+            ("缺失", &self.removed),
+            ("已修改", &self.modified),
+        ] {
+            if paths.is_empty() {
+                continue;
+            }
+            let mut line = format!(
+                "{label} {} 个文件: {}",
+                paths.len(),
+                paths
+                    .iter()
+                    .take(MAX_WARNING_PATHS)
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            if paths.len() > MAX_WARNING_PATHS {
+                line.push_str(" …");
+            }
+            lines.push(line);
+        }
+        lines
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CheckMode {
+    /// 只比较大小和修改时间，用于启动前检查
+    Quick,
+    /// 有哈希记录的文件以哈希为准
+    Full,
+}
+
+/// 完整校验版本目录（exe/dll 会重新计算哈希）
+pub async fn verify_version_integrity(name: String) -> Result<IntegrityReport, String> {
+    let version_dir = version_dir(&name)?;
+    tokio::task::spawn_blocking(move || check_integrity(&version_dir, CheckMode::Full))
+        .await
+        .map_err(|e| format!("校验任务失败: {}", e))?
+}
+
+/// 按当前目录内容重新生成锁文件，用于认可手动修改
+pub async fn refresh_version_integrity(name: String) -> Result<(), String> {
+    let version_dir = version_dir(&name)?;
+    tokio::task::spawn_blocking(move || write_integrity_lock(&version_dir))
+        .await
+        .map_err(|e| format!("生成完整性锁文件失败: {}", e))?
+}
+
+/// 启动前的快速检查：只比较大小和修改时间，不计算哈希
+pub fn quick_check_version_integrity(version_dir: &Path) -> Result<IntegrityReport, String> {
+    check_integrity(version_dir, CheckMode::Quick)
+}
+
+/// 启动流程使用：返回需要写入启动日志的差异摘要，没有锁文件或检查失败时返回空
+pub async fn launch_integrity_warnings(version_dir: PathBuf) -> Vec<String> {
+    let result =
+        tokio::task::spawn_blocking(move || quick_check_version_integrity(&version_dir)).await;
+    match result {
+        Ok(Ok(report)) => report.warning_lines(),
+        Ok(Err(e)) => {
+            warn!("启动前完整性检查失败: {}", e);
+            Vec::new()
+        }
+        Err(e) => {
+            warn!("启动前完整性检查任务失败: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// 安装完成后生成锁文件
+pub fn write_integrity_lock(version_dir: &Path) -> Result<(), String> {
+    let mut files = BTreeMap::new();
+    for (relative, path) in collect_tracked_files(version_dir)? {
+        let metadata = fs::metadata(&path)
+            .map_err(|e| format!("读取文件信息失败 {}: {}", path.display(), e))?;
+        let sha256 = if is_hashed(&path) {
+            Some(sha256_file(&path)?)
+        } else {
+            None
+        };
+        files.insert(
+            relative,
+            IntegrityEntry {
+                size: metadata.len(),
+                mtime: modified_ms(&metadata),
+                sha256,
+            },
+        );
+    }
+
+    let lock = IntegrityLock {
+        generated_at: modified_ms_of(SystemTime::now()),
+        files,
+    };
+    let raw = serde_json::to_string_pretty(&lock).map_err(|e| e.to_string())?;
+    let lock_path = version_dir.join(INTEGRITY_FILE_NAME);
+    fs::write(&lock_path, raw).map_err(|e| format!("写入 {} 失败: {}", lock_path.display(), e))
+}
+
+fn version_dir(name: &str) -> Result<PathBuf, String> {
+    let version_dir = file_ops::versions_dir().join(name);
+    if !version_dir.is_dir() {
+        return Err(format!("版本目录不存在: {}", version_dir.display()));
+    }
+    Ok(version_dir)
+}
+
+fn check_integrity(version_dir: &Path, mode: CheckMode) -> Result<IntegrityReport, String> {
+    let lock_path = version_dir.join(INTEGRITY_FILE_NAME);
+    let raw = match fs::read_to_string(&lock_path) {
+        Ok(raw) => raw,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(IntegrityReport::default());
+        }
+        Err(e) => return Err(format!("读取 {} 失败: {}", lock_path.display(), e)),
+    };
+    let lock: IntegrityLock = serde_json::from_str(&raw)
+        .map_err(|e| format!("解析 {} 失败: {}", lock_path.display(), e))?;
+
+    let mut report = IntegrityReport {
+        has_lockfile: true,
+        ..IntegrityReport::default()
+    };
+    let current = collect_tracked_files(version_dir)?;
+    for (relative, path) in &current {
+        let Some(expected) = lock.files.get(relative) else {
+            report.added.push(relative.clone());
+            continue;
+        };
+        if !matches_entry(&comparable_path(path), expected, mode)? {
+            report.modified.push(relative.clone());
+        }
+    }
+    report.removed = lock
+        .files
+        .keys()
+        .filter(|relative| !current.contains_key(*relative))
+        .cloned()
+        .collect();
+    Ok(report)
+}
+
+fn matches_entry(path: &Path, expected: &IntegrityEntry, mode: CheckMode) -> Result<bool, String> {
+    let metadata =
+        fs::metadata(path).map_err(|e| format!("读取文件信息失败 {}: {}", path.display(), e))?;
+    if metadata.len() != expected.size {
+        return Ok(false);
+    }
+    match (&expected.sha256, mode) {
+        (Some(sha256), CheckMode::Full) => Ok(sha256_file(path)?.eq_ignore_ascii_case(sha256)),
+        _ => Ok(modified_ms(&metadata) == expected.mtime),
+    }
+}
+
+/// 启动时会给游戏 exe 打导入表补丁，补丁前的原文件保存在 `<exe>.bak`，
+/// 存在备份时用备份与锁文件比较，避免把启动器自己的修改报告为外部改动
+fn comparable_path(path: &Path) -> PathBuf {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(EXE_BACKUP_SUFFIX);
+    let backup = PathBuf::from(backup);
+    if is_exe(path) && backup.is_file() {
+        backup
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// 相对路径统一使用 `/` 分隔
+fn collect_tracked_files(version_dir: &Path) -> Result<BTreeMap<String, PathBuf>, String> {
+    let mut files = BTreeMap::new();
+    let walker = WalkDir::new(version_dir)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|entry| {
+            !(entry.file_type().is_dir()
+                && LAUNCHER_MANAGED_DIRS
+                    .iter()
+                    .any(|name| entry.file_name().eq_ignore_ascii_case(name)))
+        });
+    for entry in walker {
+        let entry = entry.map_err(|e| format!("遍历版本目录失败: {}", e))?;
+        if !entry.file_type().is_file() || is_launcher_managed(entry.path(), entry.depth()) {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(version_dir) else {
+            continue;
+        };
+        let relative = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        files.insert(relative, entry.into_path());
+    }
+    Ok(files)
+}
+
+fn is_launcher_managed(path: &Path, depth: usize) -> bool {
+    let Some(file_name) = path.file_name() else {
+        return false;
+    };
+    if LAUNCHER_MANAGED_FILES
+        .iter()
+        .any(|name| file_name.eq_ignore_ascii_case(name))
+    {
+        return true;
+    }
+    if depth == 1
+        && LAUNCHER_MANAGED_ROOT_FILES
+            .iter()
+            .any(|name| file_name.eq_ignore_ascii_case(name))
+    {
+        return true;
+    }
+    file_name
+        .to_string_lossy()
+        .to_ascii_lowercase()
+        .ends_with(&format!(".exe{EXE_BACKUP_SUFFIX}"))
+}
+
+fn is_hashed(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            HASHED_EXTENSIONS
+                .iter()
+                .any(|known| ext.eq_ignore_ascii_case(known))
+        })
+}
+
+fn is_exe(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("exe"))
+}
+
+fn modified_ms(metadata: &fs::Metadata) -> i64 {
+    metadata.modified().map(modified_ms_of).unwrap_or_default()
+}
+
+fn modified_ms_of(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_added_removed_and_modified_files() {
+        let test_dir = std::env::temp_dir().join(format!(
+            "bmcbl-integrity-test-{}",
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("system time should be after unix epoch")
+                .as_nanos()
+        ));
+        fs::create_dir_all(test_dir.join("data")).expect("create test dir");
+        fs::create_dir_all(test_dir.join("mods")).expect("create mods dir");
+        fs::write(test_dir.join("Minecraft.Windows.exe"), b"original").expect("write exe");
+        fs::write(test_dir.join("data").join("a.txt"), b"a").expect("write a");
+        fs::write(test_dir.join("data").join("b.txt"), b"b").expect("write b");
+        write_integrity_lock(&test_dir).expect("write lock");
+
+        fs::write(test_dir.join("mods").join("mod.dll"), b"mod").expect("write mod");
+        fs::write(test_dir.join("config.json"), b"{}").expect("write config");
+        assert!(
+            quick_check_version_integrity(&test_dir)
+                .expect("check")
+                .is_clean()
+        );
+
+        fs::remove_file(test_dir.join("data").join("a.txt")).expect("remove a");
+        fs::write(test_dir.join("data").join("c.txt"), b"c").expect("write c");
+        fs::write(test_dir.join("Minecraft.Windows.exe"), b"patched!!").expect("patch exe");
+        let report = check_integrity(&test_dir, CheckMode::Full).expect("check");
+
+        assert!(report.has_lockfile);
+        assert_eq!(report.added, vec!["data/c.txt".to_string()]);
+        assert_eq!(report.removed, vec!["data/a.txt".to_string()]);
+        assert_eq!(report.modified, vec!["Minecraft.Windows.exe".to_string()]);
+
+        fs::remove_dir_all(&test_dir).expect("remove test dir");
+    }
+}
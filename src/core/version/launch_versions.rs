@@ -1,6 +1,15 @@
 use std::cmp::Ordering;
 use std::sync::Arc;
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum InstallKind {
+    /// 解压到 `versions/<name>` 的散装目录
+    #[default]
+    Extracted,
+    /// 直接从原始 .appx 文件安装，版本目录只保存登记文件和 mods
+    Packaged,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub(crate) struct LaunchVersionEntry {
     pub(crate) folder: Arc<str>,
@@ -9,6 +18,7 @@ pub(crate) struct LaunchVersionEntry {
     pub(crate) manifest_version: Arc<str>,
     pub(crate) path: Arc<str>,
     pub(crate) kind: Arc<str>,
+    pub(crate) install_kind: InstallKind,
     pub(crate) custom_icon_path: Option<Arc<str>>,
 }
 
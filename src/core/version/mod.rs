@@ -1,6 +1,12 @@
 pub mod api;
 pub mod gdk_users;
 pub mod icons;
+pub mod installations;
+pub mod integrity;
 pub mod launch_versions;
+pub mod packaged;
+pub mod reset_data;
 pub mod settings;
+pub mod vanilla_assets;
+pub mod version_info;
 pub mod version_manager;
@@ -0,0 +1,256 @@
+//! 以单个 .appx 文件保存的版本：不解压，直接从包文件安装注册。
+//! 启动器只在 `versions/<identity>_<version>_pkg/` 中保存登记文件和 mods 目录，
+//! 删除版本时注销已安装的包，用户原始的 .appx 文件保持不动。
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use tracing::warn;
+
+pub const PACKAGED_RECORD_FILE_NAME: &str = "bmcbl_package.json";
+const PACKAGED_FOLDER_SUFFIX: &str = "_pkg";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackagedInstall {
+    pub appx_path: String,
+    pub identity_name: String,
+    pub identity_version: String,
+    pub package_family_name: String,
+    /// 登记时间（Unix 毫秒）
+    pub registered_at: i64,
+}
+
+pub fn packaged_folder_name(identity_name: &str, identity_version: &str) -> String {
+    format!("{identity_name}_{identity_version}{PACKAGED_FOLDER_SUFFIX}")
+}
+
+/// 读取版本目录中的包登记文件；普通散装版本返回 `None`
+pub fn read_packaged_install(version_dir: &Path) -> Option<PackagedInstall> {
+    let record_path = version_dir.join(PACKAGED_RECORD_FILE_NAME);
+    let content = fs::read_to_string(&record_path).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(install) => Some(install),
+        Err(error) => {
+            warn!("包登记文件解析失败 {}: {error}", record_path.display());
+            None
+        }
+    }
+}
+
+fn write_packaged_install(version_dir: &Path, install: &PackagedInstall) -> Result<(), String> {
+    fs::create_dir_all(version_dir.join("mods"))
+        .map_err(|error| format!("创建版本目录失败 {}: {error}", version_dir.display()))?;
+    let content = serde_json::to_string_pretty(install)
+        .map_err(|error| format!("序列化包登记文件失败: {error}"))?;
+    fs::write(version_dir.join(PACKAGED_RECORD_FILE_NAME), content)
+        .map_err(|error| format!("写入包登记文件失败: {error}"))
+}
+
+fn is_appx_file(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("appx"))
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::{PackagedInstall, is_appx_file, packaged_folder_name, write_packaged_install};
+    use crate::core::minecraft::appx::register::add_appx_package_async;
+    use crate::core::minecraft::appx::remove::remove_package;
+    use crate::core::minecraft::appx::utils::get_manifest_identity_from_appx_file_blocking;
+    use crate::core::minecraft::launcher::task::identity_to_aumid;
+    use crate::core::version::version_manager::is_win32_version;
+    use crate::utils::file_ops;
+    use std::path::{Path, PathBuf};
+    use std::time::{SystemTime, UNIX_EPOCH};
+    use tracing::info;
+    use windows::Management::Deployment::PackageManager;
+    use windows::core::HSTRING;
+
+    struct InstalledPackageState {
+        version: String,
+        is_development_mode: bool,
+        install_location: Option<PathBuf>,
+    }
+
+    fn installed_package_state(package_family_name: &str) -> Option<InstalledPackageState> {
+        let package_manager = PackageManager::new().ok()?;
+        let packages = package_manager
+            .FindPackagesByUserSecurityIdPackageFamilyName(
+                &HSTRING::new(),
+                &HSTRING::from(package_family_name),
+            )
+            .ok()?;
+        for package in packages {
+            let Ok(version) = package.Id().and_then(|id| id.Version()) else {
+                continue;
+            };
+            return Some(InstalledPackageState {
+                version: format!(
+                    "{}.{}.{}.{}",
+                    version.Major, version.Minor, version.Build, version.Revision
+                ),
+                is_development_mode: package.IsDevelopmentMode().unwrap_or(false),
+                install_location: package
+                    .InstalledLocation()
+                    .and_then(|location| location.Path())
+                    .ok()
+                    .map(|path| PathBuf::from(path.to_string_lossy())),
+            });
+        }
+        None
+    }
+
+    /// 确保登记的 .appx 已以正常安装模式注册；返回本次是否重新安装
+    pub async fn ensure_packaged_registered(install: &PackagedInstall) -> Result<bool, String> {
+        if let Some(state) = installed_package_state(&install.package_family_name) {
+            if state.version == install.identity_version && !state.is_development_mode {
+                return Ok(false);
+            }
+            info!(
+                family_name = %install.package_family_name,
+                installed_version = %state.version,
+                development_mode = state.is_development_mode,
+                "已安装包与登记的 APPX 不一致，准备移除"
+            );
+            remove_package(&install.package_family_name)
+                .await
+                .map_err(|error| {
+                    format!("卸载旧包失败 ({}): {error:?}", install.package_family_name)
+                })?;
+        }
+
+        let appx_path = Path::new(&install.appx_path);
+        if !is_appx_file(appx_path) {
+            return Err(format!("原始 APPX 文件不存在: {}", install.appx_path));
+        }
+        add_appx_package_async(appx_path)
+            .await
+            .map_err(|error| format!("安装 APPX 失败 ({}): {error:?}", install.appx_path))?;
+        Ok(true)
+    }
+
+    /// 登记的 .appx 当前注册的安装位置；未注册或已注册的是其他版本时返回 `None`
+    pub fn packaged_install_location(install: &PackagedInstall) -> Option<PathBuf> {
+        installed_package_state(&install.package_family_name)
+            .filter(|state| state.version == install.identity_version)
+            .and_then(|state| state.install_location)
+    }
+
+    /// 校验并直接从 .appx 文件注册版本，返回 `versions` 下的版本目录名
+    pub async fn register_appx_file(appx_file: &Path) -> Result<String, String> {
+        if !is_appx_file(appx_file) {
+            return Err(format!("不是可读取的 APPX 文件: {}", appx_file.display()));
+        }
+        let appx_file = std::fs::canonicalize(appx_file)
+            .map_err(|error| format!("获取绝对路径失败 {}: {error}", appx_file.display()))?;
+
+        let identity_source = appx_file.clone();
+        let (identity_name, identity_version) = tokio::task::spawn_blocking(move || {
+            get_manifest_identity_from_appx_file_blocking(&identity_source)
+        })
+        .await
+        .map_err(|error| format!("读取 APPX 清单任务失败: {error}"))??;
+        if is_win32_version(&identity_version) {
+            return Err(format!(
+                "{identity_version} 为 GDK 版本，不支持以 APPX 包形式注册"
+            ));
+        }
+
+        let aumid = identity_to_aumid(&identity_name);
+        let package_family_name = aumid.split('!').next().unwrap_or_default().to_string();
+        let install = PackagedInstall {
+            appx_path: appx_file.to_string_lossy().into_owned(),
+            identity_name,
+            identity_version,
+            package_family_name,
+            registered_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_millis() as i64)
+                .unwrap_or_default(),
+        };
+
+        ensure_packaged_registered(&install).await?;
+
+        let folder_name = packaged_folder_name(&install.identity_name, &install.identity_version);
+        let version_dir = file_ops::versions_dir().join(&folder_name);
+        write_packaged_install(&version_dir, &install)?;
+        info!(
+            folder_name = %folder_name,
+            appx_path = %install.appx_path,
+            "已登记 APPX 包版本"
+        );
+        Ok(folder_name)
+    }
+
+    /// 注销登记的包；仅当当前安装的正是该 .appx 的版本时才移除，不会删除原始文件
+    pub async fn unregister_packaged_install(install: &PackagedInstall) -> Result<(), String> {
+        let Some(state) = installed_package_state(&install.package_family_name) else {
+            return Ok(());
+        };
+        if state.version != install.identity_version || state.is_development_mode {
+            info!(
+                family_name = %install.package_family_name,
+                installed_version = %state.version,
+                "已安装包不是登记的 APPX 版本，跳过注销"
+            );
+            return Ok(());
+        }
+        remove_package(&install.package_family_name)
+            .await
+            .map_err(|error| {
+                format!(
+                    "注销 APPX 包失败 ({}): {error:?}",
+                    install.package_family_name
+                )
+            })
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub use platform::{
+    ensure_packaged_registered, packaged_install_location, register_appx_file,
+    unregister_packaged_install,
+};
+
+#[cfg(not(target_os = "windows"))]
+pub fn packaged_install_location(_install: &PackagedInstall) -> Option<std::path::PathBuf> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        PackagedInstall, packaged_folder_name, read_packaged_install, write_packaged_install,
+    };
+    use std::fs;
+    use uuid::Uuid;
+
+    #[test]
+    fn packaged_record_round_trips_and_creates_mods_directory() {
+        let directory =
+            std::env::temp_dir().join(format!("bmcbl-packaged-record-{}", Uuid::new_v4()));
+        let install = PackagedInstall {
+            appx_path: "D:/Packages/Minecraft-1.20.81.1.appx".to_string(),
+            identity_name: "Microsoft.MinecraftUWP".to_string(),
+            identity_version: "1.20.81.1".to_string(),
+            package_family_name: "Microsoft.MinecraftUWP_8wekyb3d8bbwe".to_string(),
+            registered_at: 1,
+        };
+
+        write_packaged_install(&directory, &install).expect("record should be written");
+
+        assert!(directory.join("mods").is_dir());
+        assert_eq!(read_packaged_install(&directory), Some(install));
+        fs::remove_dir_all(directory).expect("temporary directory should be removed");
+    }
+
+    #[test]
+    fn packaged_folder_name_uses_pkg_suffix() {
+        assert_eq!(
+            packaged_folder_name("Microsoft.MinecraftUWP", "1.20.81.1"),
+            "Microsoft.MinecraftUWP_1.20.81.1_pkg"
+        );
+    }
+}
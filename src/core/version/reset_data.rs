@@ -0,0 +1,507 @@
+//! 重置版本隔离数据：只在 `versions/<版本>/Minecraft Bedrock*/` 中按类别清理存档、设置、
+//! 资源包与缓存，游戏文件本身不受影响。删除走回收站（失败时移入启动器备份目录），
+//! 清理后重建游戏期望的空目录结构。
+
+use crate::core::minecraft::launcher::get_active_launches;
+use crate::core::minecraft::map::export_world_archive;
+use crate::core::minecraft::recent_deletions::delete_with_undo;
+use crate::core::version::packaged::read_packaged_install;
+use crate::tasks::task_manager::{
+    append_task_log, create_task_with_details, finish_task, update_progress,
+};
+use crate::utils::file_ops;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use sysinfo::{ProcessesToUpdate, System};
+use tracing::{info, warn};
+
+const DATA_FOLDER_PREFIX: &str = "Minecraft Bedrock";
+const WORLD_BACKUP_DIR: &str = "backups/worlds";
+const WORLDS_DIR: &str = "minecraftWorlds";
+const SETTINGS_DIRS: [&str; 1] = ["minecraftpe"];
+const PACK_DIRS: [&str; 7] = [
+    "resource_packs",
+    "behavior_packs",
+    "skin_packs",
+    "world_templates",
+    "development_resource_packs",
+    "development_behavior_packs",
+    "development_skin_packs",
+];
+/// 位于数据根目录（LocalState 对应层级）下、可随时重建的缓存目录
+const CACHE_DIRS: [&str; 5] = ["logs", "premium_cache", "blob_cache", "cache", "treatments"];
+/// 游戏启动时期望存在的 com.mojang 子目录
+const SKELETON_DIRS: [&str; 4] = [
+    WORLDS_DIR,
+    "resource_packs",
+    "behavior_packs",
+    "minecraftpe",
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorldsResetMode {
+    #[default]
+    Keep,
+    Delete,
+    /// 先打包到 `BMCBL/backups/worlds/` 再删除
+    Backup,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VersionDataResetOptions {
+    #[serde(default)]
+    pub worlds: WorldsResetMode,
+    #[serde(default)]
+    pub settings: bool,
+    #[serde(default)]
+    pub packs: bool,
+    #[serde(default)]
+    pub cache: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResetCategory {
+    Worlds,
+    Settings,
+    Packs,
+    Cache,
+}
+
+impl ResetCategory {
+    fn label(self) -> &'static str {
+        match self {
+            ResetCategory::Worlds => "存档",
+            ResetCategory::Settings => "设置",
+            ResetCategory::Packs => "资源包",
+            ResetCategory::Cache => "缓存",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResetCategorySummary {
+    pub category: ResetCategory,
+    pub removed_entries: usize,
+    pub freed_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionDataResetSummary {
+    pub task_id: String,
+    pub categories: Vec<ResetCategorySummary>,
+    pub total_freed_bytes: u64,
+    /// 存档备份文件（.mcworld）
+    pub world_backups: Vec<String>,
+}
+
+struct ResetTarget {
+    category: ResetCategory,
+    path: PathBuf,
+}
+
+fn validate_version_name(version_name: &str) -> Result<(), String> {
+    if version_name.is_empty()
+        || version_name.contains("..")
+        || version_name.contains('/')
+        || version_name.contains('\\')
+    {
+        return Err("无效的版本名称".to_string());
+    }
+    Ok(())
+}
+
+fn read_child_dirs(path: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(path) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+        .map(|entry| entry.path())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+/// 隔离数据根目录：UWP 为 `Minecraft Bedrock/`，GDK 为 `Minecraft Bedrock*/`（含 Users 层级）
+fn data_roots(version_dir: &Path) -> Vec<PathBuf> {
+    read_child_dirs(version_dir)
+        .into_iter()
+        .filter(|path| {
+            path.file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with(DATA_FOLDER_PREFIX))
+        })
+        .collect()
+}
+
+/// 数据根下所有 com.mojang 目录：UWP 直接位于 `games/`，GDK 位于 `Users/<id>/games/`
+fn mojang_dirs(data_root: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![data_root.join("games").join("com.mojang")];
+    for user_dir in read_child_dirs(&data_root.join("Users")) {
+        dirs.push(user_dir.join("games").join("com.mojang"));
+    }
+    dirs.retain(|dir| dir.is_dir());
+    dirs
+}
+
+fn collect_targets(version_dir: &Path, what: &VersionDataResetOptions) -> Vec<ResetTarget> {
+    let mut targets = Vec::new();
+    let mut push_dir = |category: ResetCategory, path: PathBuf| {
+        if path.is_dir() {
+            targets.push(ResetTarget { category, path });
+        }
+    };
+
+    for data_root in data_roots(version_dir) {
+        for mojang_dir in mojang_dirs(&data_root) {
+            // 存档逐个处理，备份与删除的进度都能精确到单个世界
+            if what.worlds != WorldsResetMode::Keep {
+                for world_dir in read_child_dirs(&mojang_dir.join(WORLDS_DIR)) {
+                    push_dir(ResetCategory::Worlds, world_dir);
+                }
+            }
+            if what.settings {
+                for name in SETTINGS_DIRS {
+                    push_dir(ResetCategory::Settings, mojang_dir.join(name));
+                }
+            }
+            if what.packs {
+                for name in PACK_DIRS {
+                    push_dir(ResetCategory::Packs, mojang_dir.join(name));
+                }
+            }
+        }
+        if what.cache {
+            for name in CACHE_DIRS {
+                push_dir(ResetCategory::Cache, data_root.join(name));
+            }
+        }
+    }
+    targets
+}
+
+fn path_is_packaged_install(path: &Path, identity_prefix: &str) -> bool {
+    path.components().any(|component| {
+        component
+            .as_os_str()
+            .to_string_lossy()
+            .to_ascii_lowercase()
+            .starts_with(identity_prefix)
+    })
+}
+
+/// 版本正在启动或其游戏进程仍在运行
+fn is_version_game_running(version_name: &str, version_dir: &Path) -> bool {
+    if get_active_launches()
+        .iter()
+        .any(|launch| launch.folder_name == version_name)
+    {
+        return true;
+    }
+
+    let version_dir =
+        std::path::absolute(version_dir).unwrap_or_else(|_| version_dir.to_path_buf());
+    // 以单个 .appx 安装的版本从系统包目录运行，按包标识匹配安装路径
+    let packaged_prefix = read_packaged_install(&version_dir).map(|install| {
+        format!("{}_{}_", install.identity_name, install.identity_version).to_ascii_lowercase()
+    });
+
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::All, true);
+    system.processes().values().any(|process| {
+        process.exe().is_some_and(|exe| {
+            exe.starts_with(&version_dir)
+                || packaged_prefix
+                    .as_deref()
+                    .is_some_and(|prefix| path_is_packaged_install(exe, prefix))
+        })
+    })
+}
+
+fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|character| {
+            if character.is_alphanumeric() || character == '-' || character == '_' {
+                character
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn backup_world(
+    version_name: &str,
+    world_dir: &Path,
+    backup_dir: &Path,
+) -> Result<PathBuf, String> {
+    fs::create_dir_all(backup_dir)
+        .map_err(|e| format!("创建存档备份目录失败 {}: {}", backup_dir.display(), e))?;
+    let world_name = fs::read_to_string(world_dir.join("levelname.txt"))
+        .ok()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .or_else(|| {
+            world_dir
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+        })
+        .unwrap_or_else(|| "world".to_string());
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+    let mut target = backup_dir.join(format!(
+        "{}_{}_{timestamp}.mcworld",
+        sanitize_file_name(version_name),
+        sanitize_file_name(&world_name)
+    ));
+    // 同一秒内备份的同名存档（多个 GDK 用户）追加序号避免覆盖
+    let mut suffix = 1;
+    while target.exists() {
+        target = backup_dir.join(format!(
+            "{}_{}_{timestamp}_{suffix}.mcworld",
+            sanitize_file_name(version_name),
+            sanitize_file_name(&world_name)
+        ));
+        suffix += 1;
+    }
+    export_world_archive(world_dir, &target)
+        .map_err(|e| format!("备份存档失败 {}: {}", world_dir.display(), e))?;
+    Ok(target)
+}
+
+/// 重建清理后缺失的 com.mojang 子目录
+fn recreate_skeleton(version_dir: &Path) {
+    for data_root in data_roots(version_dir) {
+        for mojang_dir in mojang_dirs(&data_root) {
+            for name in SKELETON_DIRS {
+                let dir = mojang_dir.join(name);
+                if let Err(e) = fs::create_dir_all(&dir) {
+                    warn!("重建目录失败 {}: {}", dir.display(), e);
+                }
+            }
+        }
+    }
+}
+
+fn reset_version_data_blocking(
+    task_id: &str,
+    version_name: &str,
+    version_dir: &Path,
+    worlds_mode: WorldsResetMode,
+    targets: Vec<ResetTarget>,
+) -> Result<VersionDataResetSummary, String> {
+    let backup_dir = file_ops::bmcbl_subdir(WORLD_BACKUP_DIR);
+    let mut categories: Vec<ResetCategorySummary> = Vec::new();
+    let mut world_backups = Vec::new();
+
+    for target in targets {
+        let display_name = format!(
+            "{} {}",
+            target.category.label(),
+            target
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        );
+        if target.category == ResetCategory::Worlds && worlds_mode == WorldsResetMode::Backup {
+            update_progress(task_id, 0, None, Some("backup_worlds"));
+            let backup = backup_world(version_name, &target.path, &backup_dir)?;
+            append_task_log(task_id, format!("已备份 {}", backup.display()));
+            world_backups.push(backup.to_string_lossy().into_owned());
+        }
+
+        let size = file_ops::dir_size(&target.path).unwrap_or(0);
+        delete_with_undo(&target.path, &display_name)?;
+        append_task_log(task_id, format!("已删除 {}", target.path.display()));
+        update_progress(task_id, 1, None, Some("delete_data"));
+
+        match categories
+            .iter_mut()
+            .find(|summary| summary.category == target.category)
+        {
+            Some(summary) => {
+                summary.removed_entries += 1;
+                summary.freed_bytes += size;
+            }
+            None => categories.push(ResetCategorySummary {
+                category: target.category,
+                removed_entries: 1,
+                freed_bytes: size,
+            }),
+        }
+    }
+
+    recreate_skeleton(version_dir);
+    let total_freed_bytes = categories.iter().map(|summary| summary.freed_bytes).sum();
+    Ok(VersionDataResetSummary {
+        task_id: task_id.to_string(),
+        categories,
+        total_freed_bytes,
+        world_backups,
+    })
+}
+
+/// 按类别重置版本隔离数据，返回各类别释放的空间；游戏运行中时拒绝执行
+pub async fn reset_version_data(
+    version_name: String,
+    what: VersionDataResetOptions,
+) -> Result<VersionDataResetSummary, String> {
+    validate_version_name(&version_name)?;
+    let version_dir = file_ops::versions_dir().join(&version_name);
+    if !version_dir.is_dir() {
+        return Err(format!("版本目录不存在: {}", version_dir.display()));
+    }
+
+    let check_dir = version_dir.clone();
+    let check_name = version_name.clone();
+    let running =
+        tokio::task::spawn_blocking(move || is_version_game_running(&check_name, &check_dir))
+            .await
+            .map_err(|e| format!("检测游戏进程失败: {}", e))?;
+    if running {
+        return Err("该版本的游戏正在运行，请先关闭游戏再重置数据".to_string());
+    }
+
+    let targets = collect_targets(&version_dir, &what);
+    let task_id = create_task_with_details(
+        None,
+        "重置版本数据",
+        Some(version_name.clone()),
+        "delete_data",
+        Some(targets.len() as u64),
+        false,
+    );
+    info!(
+        version = %version_name,
+        targets = targets.len(),
+        ?what,
+        "开始重置版本数据"
+    );
+
+    let blocking_task_id = task_id.clone();
+    let worlds_mode = what.worlds;
+    let result = tokio::task::spawn_blocking(move || {
+        reset_version_data_blocking(
+            &blocking_task_id,
+            &version_name,
+            &version_dir,
+            worlds_mode,
+            targets,
+        )
+    })
+    .await
+    .map_err(|e| format!("重置版本数据任务失败: {}", e))
+    .and_then(|result| result);
+
+    match &result {
+        Ok(summary) => {
+            crate::core::library_search::invalidate_library_index();
+            finish_task(
+                &task_id,
+                "completed",
+                Some(format!(
+                    "已释放 {}",
+                    crate::utils::format_bytes::format_bytes(summary.total_freed_bytes)
+                )),
+            );
+        }
+        Err(error) => finish_task(&task_id, "error", Some(error.clone())),
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_file(path: &Path, content: &[u8]) {
+        fs::create_dir_all(path.parent().expect("parent")).expect("create parent");
+        fs::write(path, content).expect("write file");
+    }
+
+    #[test]
+    fn targets_stay_inside_selected_categories_of_isolated_data() {
+        let version_dir =
+            std::env::temp_dir().join(format!("bmcbl-reset-data-{}", uuid::Uuid::new_v4()));
+        let uwp_mojang = version_dir
+            .join("Minecraft Bedrock")
+            .join("games")
+            .join("com.mojang");
+        let gdk_mojang = version_dir
+            .join("Minecraft Bedrock Preview")
+            .join("Users")
+            .join("123")
+            .join("games")
+            .join("com.mojang");
+        write_file(
+            &uwp_mojang.join(WORLDS_DIR).join("a").join("level.dat"),
+            b"a",
+        );
+        write_file(
+            &gdk_mojang.join(WORLDS_DIR).join("b").join("level.dat"),
+            b"b",
+        );
+        write_file(&uwp_mojang.join("minecraftpe").join("options.txt"), b"o");
+        write_file(
+            &gdk_mojang
+                .join("resource_packs")
+                .join("p")
+                .join("manifest.json"),
+            b"{}",
+        );
+        write_file(
+            &version_dir
+                .join("Minecraft Bedrock")
+                .join("logs")
+                .join("l.txt"),
+            b"l",
+        );
+        write_file(&version_dir.join("Minecraft.Windows.exe"), b"exe");
+
+        let targets = collect_targets(
+            &version_dir,
+            &VersionDataResetOptions {
+                worlds: WorldsResetMode::Delete,
+                packs: true,
+                ..Default::default()
+            },
+        );
+        let mut summary: Vec<_> = targets
+            .iter()
+            .map(|target| {
+                (
+                    target.category,
+                    target
+                        .path
+                        .file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_default(),
+                )
+            })
+            .collect();
+        summary.sort_by(|left, right| left.1.cmp(&right.1));
+        assert_eq!(
+            summary,
+            vec![
+                (ResetCategory::Worlds, "a".to_string()),
+                (ResetCategory::Worlds, "b".to_string()),
+                (ResetCategory::Packs, "resource_packs".to_string()),
+            ]
+        );
+        assert!(
+            targets
+                .iter()
+                .all(|target| target.path.starts_with(&version_dir))
+        );
+
+        fs::remove_dir_all(uwp_mojang.join(WORLDS_DIR)).expect("remove worlds");
+        recreate_skeleton(&version_dir);
+        assert!(uwp_mojang.join(WORLDS_DIR).is_dir());
+        assert!(gdk_mojang.join("behavior_packs").is_dir());
+
+        fs::remove_dir_all(&version_dir).expect("remove test dir");
+    }
+}
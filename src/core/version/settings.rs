@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs as std_fs;
 use std::path::Path;
 use tokio::fs;
@@ -16,6 +17,14 @@ pub struct FileRedirectionConfig {
     pub kind: Option<String>,
 }
 
+/// 额外 mods 目录中单个 DLL 在本版本的状态，首次发现时按其 manifest 填写
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct ExtraModState {
+    pub enabled: bool,
+    #[serde(default)]
+    pub inject_delay_ms: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct VersionConfig {
     #[serde(default)]
@@ -40,6 +49,12 @@ pub struct VersionConfig {
     pub file_redirections: Vec<FileRedirectionConfig>,
     #[serde(default = "default_true")]
     pub shortcut_silent_launch: bool,
+    /// 额外加载的 mods 目录（绝对路径或相对版本目录），多个版本共用一套模组时使用
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_mod_dirs: Vec<String>,
+    /// 额外目录中的 DLL，键为完整路径，可逐个禁用或调整注入延迟
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extra_mods: BTreeMap<String, ExtraModState>,
     #[serde(default, flatten)]
     pub extra: serde_json::Map<String, serde_json::Value>,
 }
@@ -69,6 +84,8 @@ impl Default for VersionConfig {
             vanilla_skin_pack_redirect: None,
             file_redirections: Vec::new(),
             shortcut_silent_launch: true,
+            extra_mod_dirs: Vec::new(),
+            extra_mods: BTreeMap::new(),
             extra: serde_json::Map::new(),
         }
     }
@@ -178,7 +195,7 @@ fn parse_version_config(content: &str) -> VersionConfig {
 }
 
 pub fn get_version_config_blocking(folder_name: &str) -> Result<VersionConfig, String> {
-    let versions_root = Path::new("./BMCBL/versions");
+    let versions_root = crate::utils::file_ops::versions_dir();
     let config_path = versions_root.join(folder_name).join("config.json");
 
     if !config_path.exists() {
@@ -191,7 +208,7 @@ pub fn get_version_config_blocking(folder_name: &str) -> Result<VersionConfig, S
 }
 
 pub async fn get_version_config(folder_name: String) -> Result<VersionConfig, String> {
-    let versions_root = Path::new("./BMCBL/versions");
+    let versions_root = crate::utils::file_ops::versions_dir();
     let config_path = versions_root.join(folder_name).join("config.json");
 
     if !config_path.exists() {
@@ -205,7 +222,7 @@ pub async fn get_version_config(folder_name: String) -> Result<VersionConfig, St
 }
 
 pub async fn save_version_config(folder_name: String, config: VersionConfig) -> Result<(), String> {
-    let versions_root = Path::new("./BMCBL/versions");
+    let versions_root = crate::utils::file_ops::versions_dir();
     let version_dir = versions_root.join(&folder_name);
 
     if !version_dir.exists() {
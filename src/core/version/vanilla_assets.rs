@@ -0,0 +1,432 @@
+//! 提取已安装版本自带的原版资源（`data/resource_packs/vanilla*`），供包作者对照同版本的贴图和 json。
+//! 散装版本（包括 GDK）直接从版本目录复制；登记为 .appx 的版本从包的安装位置读取，
+//! 该位置位于 WindowsApps 下，无权读取的分类会在结果中列出，需改用散装安装。
+
+#[cfg(target_os = "windows")]
+use crate::core::minecraft::appx::utils::get_manifest_identity_from_dir_blocking;
+#[cfg(target_os = "linux")]
+use crate::core::minecraft::appx_utils::get_manifest_identity_from_dir_blocking;
+use crate::core::minecraft::import::ensure_free_space;
+use crate::core::version::packaged::{packaged_install_location, read_packaged_install};
+use crate::tasks::task_manager::{
+    create_task_with_details, finish_task, is_cancelled, set_total, update_progress,
+};
+use crate::utils::file_ops;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+use walkdir::WalkDir;
+
+pub const VANILLA_PROVENANCE_FILE_NAME: &str = "bmcbl_vanilla_source.json";
+const VANILLA_PACKS_DIR: &str = "data/resource_packs";
+const VANILLA_PACK_PREFIX: &str = "vanilla";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VanillaAssetCategory {
+    Textures,
+    Models,
+    Texts,
+    Ui,
+}
+
+impl VanillaAssetCategory {
+    fn dir_name(self) -> &'static str {
+        match self {
+            Self::Textures => "textures",
+            Self::Models => "models",
+            Self::Texts => "texts",
+            Self::Ui => "ui",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct VanillaSource {
+    /// 游戏安装目录（含 `data/`）
+    install_root: PathBuf,
+    /// AppxManifest 中的版本号，读不到时为版本名
+    source_version: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct VanillaAssetExtraction {
+    pub task_id: String,
+    pub dest_dir: String,
+    pub source_dir: String,
+    pub source_version: String,
+    pub copied_files: usize,
+    pub copied_bytes: u64,
+    pub copied_categories: Vec<VanillaAssetCategory>,
+    /// 无权读取的分类（WindowsApps 下的已注册包），需改用散装安装后再提取
+    pub access_denied_categories: Vec<VanillaAssetCategory>,
+}
+
+#[derive(Debug, Serialize)]
+struct VanillaAssetProvenance<'a> {
+    version_name: &'a str,
+    source_version: &'a str,
+    source_dir: String,
+    categories: &'a [VanillaAssetCategory],
+    /// ISO 8601 格式
+    extracted_at: String,
+    launcher_version: &'static str,
+}
+
+struct PlannedFile {
+    category: VanillaAssetCategory,
+    source: PathBuf,
+    relative: PathBuf,
+    size: u64,
+}
+
+#[derive(Default)]
+struct CopyPlan {
+    files: Vec<PlannedFile>,
+    denied: Vec<VanillaAssetCategory>,
+}
+
+fn is_access_denied(error: &io::Error) -> bool {
+    error.kind() == io::ErrorKind::PermissionDenied
+}
+
+fn resolve_vanilla_source(version_name: &str) -> Result<VanillaSource, String> {
+    let version_dir = file_ops::versions_dir().join(version_name);
+    if !version_dir.is_dir() {
+        return Err(format!("版本目录不存在: {}", version_dir.display()));
+    }
+    if let Some(install) = read_packaged_install(&version_dir) {
+        let install_root = packaged_install_location(&install)
+            .ok_or_else(|| format!("版本 {} 尚未注册，请先启动一次该版本后再提取", version_name))?;
+        return Ok(VanillaSource {
+            install_root,
+            source_version: install.identity_version,
+        });
+    }
+    let source_version = get_manifest_identity_from_dir_blocking(&version_dir)
+        .map(|(_, version)| version)
+        .unwrap_or_else(|_| version_name.to_string());
+    Ok(VanillaSource {
+        install_root: version_dir,
+        source_version,
+    })
+}
+
+/// `data/resource_packs` 下所有 `vanilla*` 目录，例如 `vanilla`、`vanilla_1.20.50`
+fn vanilla_pack_dirs(install_root: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut dirs: Vec<PathBuf> = fs::read_dir(install_root.join(VANILLA_PACKS_DIR))?
+        .flatten()
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .to_ascii_lowercase()
+                .starts_with(VANILLA_PACK_PREFIX)
+        })
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+    Ok(dirs)
+}
+
+/// 列出要复制的文件；读取时遇到拒绝访问的分类整体记为无权限，不复制其中一部分
+fn plan_copy(
+    pack_dirs: &[PathBuf],
+    categories: &[VanillaAssetCategory],
+) -> Result<CopyPlan, String> {
+    let mut plan = CopyPlan::default();
+    for &category in categories {
+        let mut files = Vec::new();
+        let mut denied = false;
+        'packs: for pack_dir in pack_dirs {
+            let category_dir = pack_dir.join(category.dir_name());
+            if !category_dir.is_dir() {
+                continue;
+            }
+            let pack_name = pack_dir.file_name().unwrap_or_default();
+            for entry in WalkDir::new(&category_dir) {
+                let entry = match entry {
+                    Ok(entry) => entry,
+                    Err(error) if error.io_error().is_some_and(is_access_denied) => {
+                        denied = true;
+                        break 'packs;
+                    }
+                    Err(error) => return Err(format!("读取原版资源失败: {}", error)),
+                };
+                if !entry.file_type().is_file() {
+                    continue;
+                }
+                let metadata = match entry.metadata() {
+                    Ok(metadata) => metadata,
+                    Err(error) if error.io_error().is_some_and(is_access_denied) => {
+                        denied = true;
+                        break 'packs;
+                    }
+                    Err(error) => return Err(format!("读取原版资源失败: {}", error)),
+                };
+                let relative = entry
+                    .path()
+                    .strip_prefix(pack_dir)
+                    .map_err(|e| e.to_string())?;
+                files.push(PlannedFile {
+                    category,
+                    source: entry.path().to_path_buf(),
+                    relative: Path::new(pack_name).join(relative),
+                    size: metadata.len(),
+                });
+            }
+        }
+        if denied {
+            plan.denied.push(category);
+        } else {
+            plan.files.extend(files);
+        }
+    }
+    Ok(plan)
+}
+
+/// 按计划复制并写入来源说明；`on_progress` 返回 false 时取消，结果为 `Ok(None)`
+fn extract_vanilla_assets_blocking(
+    version_name: &str,
+    source: &VanillaSource,
+    categories: &[VanillaAssetCategory],
+    dest_dir: &Path,
+    mut on_total: impl FnMut(u64),
+    mut on_progress: impl FnMut(u64) -> bool,
+) -> Result<Option<VanillaAssetExtraction>, String> {
+    let pack_dirs = match vanilla_pack_dirs(&source.install_root) {
+        Ok(dirs) => dirs,
+        Err(error) if is_access_denied(&error) => {
+            return Ok(Some(VanillaAssetExtraction {
+                dest_dir: dest_dir.to_string_lossy().into_owned(),
+                source_dir: source.install_root.to_string_lossy().into_owned(),
+                source_version: source.source_version.clone(),
+                access_denied_categories: categories.to_vec(),
+                ..Default::default()
+            }));
+        }
+        Err(error) => {
+            return Err(format!(
+                "读取原版资源目录失败 {}: {}",
+                source.install_root.join(VANILLA_PACKS_DIR).display(),
+                error
+            ));
+        }
+    };
+    if pack_dirs.is_empty() {
+        return Err(format!(
+            "未找到原版资源包: {}",
+            source.install_root.join(VANILLA_PACKS_DIR).display()
+        ));
+    }
+
+    let mut plan = plan_copy(&pack_dirs, categories)?;
+    let total_bytes: u64 = plan.files.iter().map(|file| file.size).sum();
+    on_total(total_bytes);
+    fs::create_dir_all(dest_dir)
+        .map_err(|e| format!("创建目标目录失败 {}: {}", dest_dir.display(), e))?;
+    ensure_free_space(&[(dest_dir, total_bytes)]).map_err(|e| e.to_string())?;
+
+    let mut copied_files = 0;
+    let mut copied_bytes = 0;
+    for file in &plan.files {
+        if plan.denied.contains(&file.category) {
+            continue;
+        }
+        let target = dest_dir.join(&file.relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("创建目录失败 {}: {}", parent.display(), e))?;
+        }
+        match fs::copy(&file.source, &target) {
+            Ok(_) => {
+                copied_files += 1;
+                copied_bytes += file.size;
+            }
+            Err(error) if is_access_denied(&error) => {
+                warn!("原版资源无权读取: {}", file.source.display());
+                plan.denied.push(file.category);
+                continue;
+            }
+            Err(error) => {
+                return Err(format!(
+                    "复制原版资源失败 {}: {}",
+                    file.source.display(),
+                    error
+                ));
+            }
+        }
+        if !on_progress(file.size) {
+            return Ok(None);
+        }
+    }
+
+    let copied_categories: Vec<VanillaAssetCategory> = categories
+        .iter()
+        .copied()
+        .filter(|category| !plan.denied.contains(category))
+        .collect();
+    let source_dir = source.install_root.join(VANILLA_PACKS_DIR);
+    let provenance = VanillaAssetProvenance {
+        version_name,
+        source_version: &source.source_version,
+        source_dir: source_dir.to_string_lossy().into_owned(),
+        categories: &copied_categories,
+        extracted_at: chrono::Local::now().to_rfc3339(),
+        launcher_version: env!("CARGO_PKG_VERSION"),
+    };
+    let provenance_json =
+        serde_json::to_string_pretty(&provenance).map_err(|e| format!("序列化失败: {}", e))?;
+    fs::write(dest_dir.join(VANILLA_PROVENANCE_FILE_NAME), provenance_json)
+        .map_err(|e| format!("写入来源说明失败: {}", e))?;
+
+    Ok(Some(VanillaAssetExtraction {
+        task_id: String::new(),
+        dest_dir: dest_dir.to_string_lossy().into_owned(),
+        source_dir: source_dir.to_string_lossy().into_owned(),
+        source_version: source.source_version.clone(),
+        copied_files,
+        copied_bytes,
+        copied_categories,
+        access_denied_categories: plan.denied,
+    }))
+}
+
+/// 把指定版本的原版资源按分类复制到 `dest_dir`，保留 `vanilla*/<分类>/...` 结构，
+/// 并写入记录来源版本和提取时间的 `bmcbl_vanilla_source.json`
+pub async fn extract_vanilla_assets(
+    version_name: String,
+    categories: Vec<VanillaAssetCategory>,
+    dest_dir: String,
+) -> Result<VanillaAssetExtraction, String> {
+    let mut categories = categories;
+    categories.sort();
+    categories.dedup();
+    if categories.is_empty() {
+        return Err("请至少选择一个资源分类".to_string());
+    }
+    let dest_dir = PathBuf::from(dest_dir);
+    let task_id = create_task_with_details(
+        None,
+        "提取原版资源",
+        Some(version_name.clone()),
+        "preparing",
+        None,
+        false,
+    );
+    let blocking_task_id = task_id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let source = resolve_vanilla_source(&version_name)?;
+        extract_vanilla_assets_blocking(
+            &version_name,
+            &source,
+            &categories,
+            &dest_dir,
+            |total| set_total(&blocking_task_id, Some(total)),
+            |bytes| {
+                update_progress(&blocking_task_id, bytes, None, Some("copying"));
+                !is_cancelled(&blocking_task_id)
+            },
+        )
+    })
+    .await
+    .map_err(|e| format!("提取原版资源任务失败: {}", e))
+    .and_then(|result| result);
+
+    match result {
+        Ok(Some(mut extraction)) => {
+            info!(
+                dest = %extraction.dest_dir,
+                files = extraction.copied_files,
+                denied = extraction.access_denied_categories.len(),
+                "原版资源提取完成"
+            );
+            let message = if extraction.access_denied_categories.is_empty() {
+                format!("已提取 {} 个文件", extraction.copied_files)
+            } else {
+                format!(
+                    "已提取 {} 个文件，{} 个分类无权读取，需改用散装安装",
+                    extraction.copied_files,
+                    extraction.access_denied_categories.len()
+                )
+            };
+            finish_task(&task_id, "completed", Some(message));
+            extraction.task_id = task_id;
+            Ok(extraction)
+        }
+        Ok(None) => {
+            finish_task(&task_id, "cancelled", None);
+            Err("已取消提取原版资源".to_string())
+        }
+        Err(error) => {
+            finish_task(&task_id, "error", Some(error.clone()));
+            Err(error)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copies_selected_categories_and_writes_provenance() {
+        let root =
+            std::env::temp_dir().join(format!("bmcbl-vanilla-assets-{}", uuid::Uuid::new_v4()));
+        let packs = root.join("install").join(VANILLA_PACKS_DIR);
+        for (relative, content) in [
+            ("vanilla/textures/blocks/stone.png", "png"),
+            ("vanilla/texts/en_US.lang", "lang"),
+            ("vanilla/models/entity/pig.geo.json", "{}"),
+            ("vanilla_1.20.50/textures/items/apple.png", "apple"),
+            ("chemistry/textures/blocks/element.png", "other"),
+        ] {
+            let path = packs.join(relative);
+            fs::create_dir_all(path.parent().expect("parent")).expect("create pack dir");
+            fs::write(path, content).expect("write asset");
+        }
+        let source = VanillaSource {
+            install_root: root.join("install"),
+            source_version: "1.21.0.3".to_string(),
+        };
+        let dest = root.join("out");
+
+        let mut total = 0;
+        let extraction = extract_vanilla_assets_blocking(
+            "1.21.0.3",
+            &source,
+            &[VanillaAssetCategory::Textures, VanillaAssetCategory::Texts],
+            &dest,
+            |bytes| total = bytes,
+            |_| true,
+        )
+        .expect("extract")
+        .expect("not cancelled");
+
+        assert_eq!(extraction.copied_files, 3);
+        assert_eq!(extraction.copied_bytes, total);
+        assert!(extraction.access_denied_categories.is_empty());
+        assert!(dest.join("vanilla/textures/blocks/stone.png").is_file());
+        assert!(
+            dest.join("vanilla_1.20.50/textures/items/apple.png")
+                .is_file()
+        );
+        assert!(dest.join("vanilla/texts/en_US.lang").is_file());
+        assert!(!dest.join("vanilla/models").exists());
+        assert!(!dest.join("chemistry").exists());
+        let provenance: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(dest.join(VANILLA_PROVENANCE_FILE_NAME)).expect("read provenance"),
+        )
+        .expect("parse provenance");
+        assert_eq!(provenance["source_version"], "1.21.0.3");
+        assert_eq!(
+            provenance["categories"],
+            serde_json::json!(["textures", "texts"])
+        );
+
+        fs::remove_dir_all(&root).expect("remove test dir");
+    }
+}
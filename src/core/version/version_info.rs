@@ -0,0 +1,169 @@
+//! 版本目录的创建信息：启动器每次注册成功后写入 `versions/<name>/.bmcbl_version_info.json`，
+//! 记录用过这个目录的最新启动器版本。降级启动器后再启动该版本时发出兼容性警告。
+
+use crate::core::version::version_manager::compare_versions;
+use crate::utils::file_ops;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fs;
+use std::path::Path;
+use tokio::sync::broadcast;
+use tracing::warn;
+
+pub const VERSION_INFO_FILE_NAME: &str = ".bmcbl_version_info.json";
+const LAUNCHER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionCreationInfo {
+    /// ISO 8601 格式，首次写入的时间
+    pub created_at: String,
+    /// 注册过该目录的最新启动器版本
+    pub launcher_version: String,
+}
+
+/// `version-compatibility-warning` 事件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VersionCompatibilityWarning {
+    pub version_name: String,
+    pub folder_launcher_version: String,
+    pub current_launcher_version: String,
+}
+
+impl VersionCompatibilityWarning {
+    pub fn message(&self) -> String {
+        format!(
+            "版本 {} 由更新的启动器 {} 创建，当前启动器为 {}，部分功能可能无法正常使用",
+            self.version_name, self.folder_launcher_version, self.current_launcher_version
+        )
+    }
+}
+
+static VERSION_COMPATIBILITY_EVENTS: Lazy<broadcast::Sender<VersionCompatibilityWarning>> =
+    Lazy::new(|| {
+        let (tx, _rx) = broadcast::channel(16);
+        tx
+    });
+
+pub fn subscribe_version_compatibility_warnings() -> broadcast::Receiver<VersionCompatibilityWarning>
+{
+    VERSION_COMPATIBILITY_EVENTS.subscribe()
+}
+
+fn read_version_info(version_dir: &Path) -> Result<Option<VersionCreationInfo>, String> {
+    let path = version_dir.join(VERSION_INFO_FILE_NAME);
+    let raw = match fs::read_to_string(&path) {
+        Ok(raw) => raw,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("读取版本信息失败 {}: {}", path.display(), e)),
+    };
+    serde_json::from_str(raw.trim_start_matches('\u{feff}'))
+        .map(Some)
+        .map_err(|e| format!("解析版本信息失败 {}: {}", path.display(), e))
+}
+
+/// 注册成功后调用。已有记录时保留创建时间，只在当前启动器不更旧时更新版本号，
+/// 这样旧版启动器启动后也不会抹掉"由新版创建"的记录
+pub fn write_version_creation_metadata(version_dir: &Path) -> Result<(), String> {
+    let existing = read_version_info(version_dir).unwrap_or_else(|e| {
+        warn!("{}", e);
+        None
+    });
+    let info = match existing {
+        Some(info)
+            if compare_versions(&info.launcher_version, LAUNCHER_VERSION) != Ordering::Less =>
+        {
+            return Ok(());
+        }
+        Some(info) => VersionCreationInfo {
+            created_at: info.created_at,
+            launcher_version: LAUNCHER_VERSION.to_string(),
+        },
+        None => VersionCreationInfo {
+            created_at: chrono::Local::now().to_rfc3339(),
+            launcher_version: LAUNCHER_VERSION.to_string(),
+        },
+    };
+    let path = version_dir.join(VERSION_INFO_FILE_NAME);
+    let json = serde_json::to_string_pretty(&info).map_err(|e| format!("序列化失败: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("写入版本信息失败 {}: {}", path.display(), e))
+}
+
+/// 读取版本目录记录的启动器版本，没有记录（旧版启动器创建的目录）时返回 `None`
+pub fn get_launcher_version_in_version_folder(
+    version_name: String,
+) -> Result<Option<String>, String> {
+    let version_dir = file_ops::versions_dir().join(&version_name);
+    if !version_dir.is_dir() {
+        return Err(format!("版本目录不存在: {}", version_dir.display()));
+    }
+    Ok(read_version_info(&version_dir)?.map(|info| info.launcher_version))
+}
+
+fn compatibility_warning(
+    version_name: &str,
+    folder_launcher_version: Option<String>,
+    current_launcher_version: &str,
+) -> Option<VersionCompatibilityWarning> {
+    let folder_launcher_version = folder_launcher_version?;
+    (compare_versions(&folder_launcher_version, current_launcher_version) == Ordering::Greater)
+        .then(|| VersionCompatibilityWarning {
+            version_name: version_name.to_string(),
+            folder_launcher_version,
+            current_launcher_version: current_launcher_version.to_string(),
+        })
+}
+
+/// 版本目录由更新的启动器创建时发出 `version-compatibility-warning` 事件并返回警告。
+/// `version_dir` 须与 [`write_version_creation_metadata`] 写入的目录一致
+pub fn check_launcher_version_compatibility(
+    version_name: &str,
+    version_dir: &Path,
+) -> Result<Option<VersionCompatibilityWarning>, String> {
+    let folder_launcher_version = read_version_info(version_dir)?.map(|info| info.launcher_version);
+    let warning = compatibility_warning(version_name, folder_launcher_version, LAUNCHER_VERSION);
+    if let Some(warning) = &warning {
+        let _ = VERSION_COMPATIBILITY_EVENTS.send(warning.clone());
+    }
+    Ok(warning)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_newest_launcher_version_and_warns_after_downgrade() {
+        let dir = std::env::temp_dir().join(format!("bmcbl-version-info-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).expect("create version dir");
+
+        assert_eq!(read_version_info(&dir), Ok(None));
+        write_version_creation_metadata(&dir).expect("write info");
+        let info = read_version_info(&dir).expect("read info").expect("info");
+        assert_eq!(info.launcher_version, LAUNCHER_VERSION);
+
+        let newer = VersionCreationInfo {
+            created_at: info.created_at.clone(),
+            launcher_version: "999.0.0".to_string(),
+        };
+        fs::write(
+            dir.join(VERSION_INFO_FILE_NAME),
+            serde_json::to_string(&newer).expect("serialize"),
+        )
+        .expect("write newer info");
+        write_version_creation_metadata(&dir).expect("rewrite info");
+        assert_eq!(read_version_info(&dir), Ok(Some(newer)));
+        let warning = check_launcher_version_compatibility("1.21.0", &dir)
+            .expect("check written dir")
+            .expect("warning from written dir");
+        assert_eq!(warning.folder_launcher_version, "999.0.0");
+
+        let warning =
+            compatibility_warning("1.21.0", Some("999.0.0".to_string()), "1.0.0").expect("warning");
+        assert_eq!(warning.folder_launcher_version, "999.0.0");
+        assert!(compatibility_warning("1.21.0", Some("1.0.0".to_string()), "1.0.0").is_none());
+        assert!(compatibility_warning("1.21.0", None, "1.0.0").is_none());
+
+        fs::remove_dir_all(&dir).expect("remove test dir");
+    }
+}
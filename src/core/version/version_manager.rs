@@ -8,7 +8,8 @@ use crate::core::minecraft::appx_utils::{
     find_any_game_executable_in_dir, get_executable_product_version,
     get_manifest_identity_from_dir_blocking,
 };
-use crate::core::version::launch_versions::LaunchVersionEntry;
+use crate::core::version::launch_versions::{InstallKind, LaunchVersionEntry};
+use crate::core::version::packaged::{PackagedInstall, read_packaged_install};
 use anyhow::{Context as _, Result};
 use rayon::prelude::*;
 use std::cmp::Ordering;
@@ -42,7 +43,7 @@ fn next_version_number(version: &str, cursor: &mut usize) -> Option<u64> {
     version[start..*cursor].parse::<u64>().ok()
 }
 
-fn compare_versions(left: &str, right: &str) -> Ordering {
+pub(crate) fn compare_versions(left: &str, right: &str) -> Ordering {
     let mut left_cursor = 0;
     let mut right_cursor = 0;
 
@@ -117,6 +118,10 @@ fn read_appx_version_entry(entry: DirEntry, root: &Path) -> Option<LaunchVersion
     };
 
     let display_path = absolute_display_path(root, &path);
+    if let Some(install) = read_packaged_install(&path) {
+        return packaged_version_entry(folder, &path, display_path, install);
+    }
+
     let executable_path = match find_any_game_executable_in_dir(&path) {
         Some(executable_path) => executable_path,
         None => {
@@ -183,11 +188,45 @@ fn read_appx_version_entry(entry: DirEntry, root: &Path) -> Option<LaunchVersion
         manifest_version,
         path: Arc::<str>::from(display_path),
         kind: Arc::<str>::from(kind),
+        install_kind: InstallKind::Extracted,
         custom_icon_path: crate::core::version::icons::custom_version_icon_path(&path)
             .map(|icon_path| Arc::<str>::from(icon_path.to_string_lossy().into_owned())),
     })
 }
 
+fn packaged_version_entry(
+    folder: String,
+    path: &Path,
+    display_path: String,
+    install: PackagedInstall,
+) -> Option<LaunchVersionEntry> {
+    if !cfg!(target_os = "windows") {
+        debug!("当前平台不支持 APPX 包版本，跳过: {}", path.display());
+        return None;
+    }
+
+    debug!(
+        "APPX 包版本条目解析成功: dir={}, appx={}, identity={}, version={}",
+        path.display(),
+        install.appx_path,
+        install.identity_name,
+        install.identity_version
+    );
+    let version: Arc<str> = Arc::from(install.identity_version);
+    let kind = determine_kind_from_version(version.as_ref());
+    Some(LaunchVersionEntry {
+        folder: Arc::<str>::from(folder),
+        name: Arc::<str>::from(install.identity_name),
+        version: version.clone(),
+        manifest_version: version,
+        path: Arc::<str>::from(display_path),
+        kind: Arc::<str>::from(kind),
+        install_kind: InstallKind::Packaged,
+        custom_icon_path: crate::core::version::icons::custom_version_icon_path(path)
+            .map(|icon_path| Arc::<str>::from(icon_path.to_string_lossy().into_owned())),
+    })
+}
+
 fn absolute_display_path(root: &Path, path: &Path) -> String {
     if path.is_absolute() {
         return path.to_string_lossy().into_owned();
@@ -2,6 +2,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use tracing::debug;
 
+use crate::core::cache_registry::{CacheDescriptor, clear_dir, dir_usage};
 use crate::downloads::integrity::verify_download_integrity;
 use crate::downloads::manager::{DownloadOptions, DownloaderManager};
 use crate::downloads::wu_client::client::WuClient;
@@ -50,7 +51,7 @@ fn remove_download_temp(dest: &Path) {
     }
 }
 
-fn downloads_dir() -> PathBuf {
+pub(crate) fn downloads_dir() -> PathBuf {
     file_ops::downloads_dir()
 }
 
@@ -155,6 +156,7 @@ pub async fn download_appx(
         None,
         true,
     );
+    crate::downloads::mark_download_task_metered(&task_id);
 
     let update_id = update_id.to_string();
     let revision = revision.to_string();
@@ -293,6 +295,7 @@ pub async fn download_resource(
         None,
         true,
     );
+    crate::downloads::mark_download_task_metered(&task_id);
 
     let manager = DownloaderManager::with_client(client);
     let dest_clone = dest.clone();
@@ -349,6 +352,24 @@ pub async fn download_resource(
     Ok(task_id)
 }
 
+/// HTTP 下载缓存；重新获取需要联网，默认不在 `rebuild_caches` 中清理
+pub(crate) static CACHE: CacheDescriptor = CacheDescriptor {
+    name: "http_downloads",
+    label: "HTTP 下载缓存",
+    default_selected: false,
+    usage: || dir_usage(&resource_download_cache_dir()),
+    clear: || clear_dir(&resource_download_cache_dir()),
+    warm: None,
+};
+
+fn resource_download_cache_dir() -> PathBuf {
+    if cfg!(target_os = "linux") {
+        file_ops::cache_subdir("resource-downloads")
+    } else {
+        file_ops::launcher_temp_dir().join("cache_downloads")
+    }
+}
+
 /// Download a remote file into a temp cache directory.
 ///
 /// This mirrors the upstream tauri command `download_resource_to_cache` and integrates with the
@@ -363,10 +384,7 @@ pub async fn download_resource_to_cache(
     let client =
         get_download_client_for_proxy().map_err(|e| format!("构建 HTTP 客户端失败: {}", e))?;
 
-    #[cfg(target_os = "linux")]
-    let cache_dir = file_ops::cache_subdir("resource-downloads");
-    #[cfg(not(target_os = "linux"))]
-    let cache_dir = std::env::temp_dir().join("BMCBL").join("cache_downloads");
+    let cache_dir = resource_download_cache_dir();
     fs::create_dir_all(&cache_dir).map_err(|e| e.to_string())?;
     let safe_name = sanitize_filename(&file_name);
     let dest = cache_dir.join(&safe_name);
@@ -379,6 +397,7 @@ pub async fn download_resource_to_cache(
         None,
         true,
     );
+    crate::downloads::mark_download_task_metered(&task_id);
 
     let manager = DownloaderManager::with_client(client);
     let dest_clone = dest.clone();
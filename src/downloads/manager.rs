@@ -306,7 +306,10 @@ fn is_trivial_candidate_failure(path: &Path, error: &CoreError) -> bool {
 }
 
 fn is_non_retryable_candidate_error(error: &CoreError) -> bool {
-    matches!(error, CoreError::ChecksumMismatch(_))
+    matches!(
+        error,
+        CoreError::ChecksumMismatch(_) | CoreError::InsufficientDiskSpace { .. }
+    )
 }
 
 fn resolve_thread_count(preferred: Option<usize>) -> usize {
@@ -556,6 +559,7 @@ impl DownloaderManager {
             None,
             true,
         );
+        crate::downloads::mark_download_task_metered(&task_id);
         let client = self.client.clone();
 
         let url_clone = url.clone();
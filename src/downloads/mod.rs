@@ -2,7 +2,9 @@ pub mod api;
 mod integrity;
 pub mod manager;
 mod multi;
+mod preallocate;
 mod runtime;
+pub mod scheduled;
 mod single;
 
 mod md5;
@@ -25,3 +27,10 @@ const DOWNLOAD_TASK_STAGE_LABELS: [(&str, &str); 9] = [
 pub(crate) fn register_download_task_stage_labels() {
     crate::tasks::task_manager::register_task_stage_labels(DOWNLOAD_TASK_STAGE_LABELS);
 }
+
+/// 用户主动发起的下载在计费网络下照常执行，只在任务上打标记供界面提示
+pub(crate) fn mark_download_task_metered(task_id: &str) {
+    if crate::utils::network::should_save_bandwidth() {
+        crate::tasks::task_manager::set_task_metered(task_id, true);
+    }
+}
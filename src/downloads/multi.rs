@@ -17,6 +17,9 @@ use tokio::time::{Duration, sleep};
 use tracing::{debug, error, warn};
 
 use crate::downloads::md5::{is_md5_digest, verify_md5};
+use crate::downloads::preallocate::{
+    PreallocationMode, ensure_download_space, preallocate_download_file,
+};
 use crate::downloads::single::download_file;
 use crate::http::proxy::{apply_download_request_headers, validate_download_response_headers};
 use crate::result::{CoreError, CoreResult};
@@ -379,7 +382,8 @@ fn should_fallback_to_single_thread(error: &CoreError) -> bool {
         | CoreError::BadUpdateIdentity
         | CoreError::UnknownContentLength
         | CoreError::Join(_)
-        | CoreError::Config(_) => false,
+        | CoreError::Config(_)
+        | CoreError::InsufficientDiskSpace { .. } => false,
     }
 }
 
@@ -575,30 +579,39 @@ fn write_all_at(file: &StdFile, mut offset: u64, mut buffer: &[u8]) -> io::Resul
     Ok(())
 }
 
-fn spawn_direct_writer(
-    dest: std::path::PathBuf,
-    total: u64,
-    mut rx: mpsc::Receiver<WriterMsg>,
-) -> thread::JoinHandle<io::Result<()>> {
-    thread::spawn(move || {
-        if let Some(parent) = dest.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
-
+/// 创建分段下载的输出文件：检查剩余空间后按最终大小预分配为稀疏文件
+async fn create_direct_output(dest: &Path, total: u64) -> Result<StdFile, CoreError> {
+    prepare_direct_output(dest).await?;
+    let dest = dest.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        ensure_download_space(&dest, total)?;
         let file = StdOpenOptions::new()
             .create(true)
             .write(true)
             .truncate(true)
             .open(&dest)?;
-        if let Err(error) = file.set_len(total) {
-            warn!(
-                "download writer failed to preallocate file path={} size={} error={}",
-                dest.to_string_lossy(),
-                total,
-                error
-            );
+        if let Err(error) =
+            preallocate_download_file(&file, &dest, total, PreallocationMode::Sparse)
+        {
+            drop(file);
+            if let Err(remove_error) = std::fs::remove_file(&dest) {
+                warn!(
+                    "failed to remove {} after pre-allocation failed: {remove_error}",
+                    dest.display()
+                );
+            }
+            return Err(error);
         }
+        Ok(file)
+    })
+    .await?
+}
 
+fn spawn_direct_writer(
+    file: StdFile,
+    mut rx: mpsc::Receiver<WriterMsg>,
+) -> thread::JoinHandle<io::Result<()>> {
+    thread::spawn(move || {
         while let Some(message) = rx.blocking_recv() {
             match message {
                 WriterMsg::Write { offset, chunks } => {
@@ -634,7 +647,7 @@ async fn download_multi_partitioned(
     md5_expected: Option<&str>,
     total: u64,
 ) -> Result<CoreResult<()>, CoreError> {
-    prepare_direct_output(dest_path.as_path()).await?;
+    let output_file = create_direct_output(dest_path.as_path(), total).await?;
 
     let scheduler = Arc::new(DynamicRangeScheduler::new(total, threads));
     let active_threads = scheduler.effective_worker_count().await;
@@ -672,7 +685,7 @@ async fn download_multi_partitioned(
     let error_occurred = Arc::new(Notify::new());
     let error_store = Arc::new(Mutex::new(None));
     let (write_tx, write_rx) = mpsc::channel::<WriterMsg>(WRITE_CHANNEL_SIZE);
-    let writer_thread = spawn_direct_writer(dest_path.as_ref().clone(), total, write_rx);
+    let writer_thread = spawn_direct_writer(output_file, write_rx);
     let mut workers = JoinSet::new();
 
     for worker_id in 0..active_threads {
@@ -1616,7 +1629,10 @@ mod tests {
         remove_test_file_if_exists(&dest).await;
 
         let (write_tx, write_rx) = mpsc::channel::<WriterMsg>(8);
-        let writer_thread = spawn_direct_writer(dest.clone(), data.len() as u64, write_rx);
+        let output_file = create_direct_output(&dest, data.len() as u64)
+            .await
+            .expect("output file should be preallocated");
+        let writer_thread = spawn_direct_writer(output_file, write_rx);
 
         let middle_start = 4096usize;
         let tail_start = 48 * 1024usize;
@@ -0,0 +1,222 @@
+//! 下载文件预分配：开始写入前检查剩余空间并按最终大小占用磁盘，
+//! 避免下载到一半才因磁盘已满失败，也减少文件逐步增长带来的碎片。
+//! 分段下载使用稀疏文件，尚未写入的区间暂不占用空间。
+
+use crate::result::CoreError;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// 临时文件改名、校验旁路文件及文件系统元数据所需的额外空间
+const SPACE_OVERHEAD_BYTES: u64 = 64 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PreallocationMode {
+    /// 分段下载：标记为稀疏文件并直接设置最终长度
+    Sparse,
+    /// 顺序下载：只预留簇、不改变文件长度，断点续传仍以文件长度为准
+    Reserve,
+}
+
+struct VolumeSpace {
+    mount_point: PathBuf,
+    available: u64,
+}
+
+/// 路径所在磁盘及剩余空间；无法确定所在磁盘（网络路径等）时返回 `None`
+fn volume_space(path: &Path) -> Option<VolumeSpace> {
+    let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|disk| absolute.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| VolumeSpace {
+            mount_point: disk.mount_point().to_path_buf(),
+            available: disk.available_space(),
+        })
+}
+
+fn volume_label(dest: &Path, volume: Option<&VolumeSpace>) -> String {
+    match volume {
+        Some(volume) => volume.mount_point.display().to_string(),
+        None => dest.parent().unwrap_or(dest).display().to_string(),
+    }
+}
+
+/// 下载开始前检查目标磁盘是否放得下整个文件及额外开销
+pub(crate) fn ensure_download_space(dest: &Path, total: u64) -> Result<(), CoreError> {
+    let Some(volume) = volume_space(dest) else {
+        debug!("下载空间检查跳过，未找到所在磁盘: {}", dest.display());
+        return Ok(());
+    };
+    let required = total.saturating_add(SPACE_OVERHEAD_BYTES);
+    if required > volume.available {
+        return Err(CoreError::InsufficientDiskSpace {
+            volume: volume_label(dest, Some(&volume)),
+            shortfall: required - volume.available,
+        });
+    }
+    Ok(())
+}
+
+/// 按最终大小预分配；失败视为空间不足，由调用方立即中止任务
+pub(crate) fn preallocate_download_file(
+    file: &File,
+    dest: &Path,
+    total: u64,
+    mode: PreallocationMode,
+) -> Result<(), CoreError> {
+    if total == 0 {
+        return Ok(());
+    }
+    platform::preallocate(file, total, mode).map_err(|error| {
+        warn!(
+            "下载文件预分配失败 path={} size={} mode={:?} error={}",
+            dest.display(),
+            total,
+            mode,
+            error
+        );
+        let volume = volume_space(dest);
+        CoreError::InsufficientDiskSpace {
+            volume: volume_label(dest, volume.as_ref()),
+            shortfall: total.saturating_sub(volume.map_or(0, |volume| volume.available)),
+        }
+    })
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::PreallocationMode;
+    use std::ffi::c_void;
+    use std::fs::File;
+    use std::io;
+    use std::os::windows::fs::FileExt as _;
+    use std::os::windows::io::AsRawHandle as _;
+    use tracing::debug;
+    use windows::Win32::Foundation::HANDLE;
+    use windows::Win32::Storage::FileSystem::{
+        FILE_ALLOCATION_INFO, FileAllocationInfo, SetFileInformationByHandle, SetFileValidData,
+    };
+    use windows::Win32::System::IO::DeviceIoControl;
+    use windows::Win32::System::Ioctl::FSCTL_SET_SPARSE;
+
+    fn handle(file: &File) -> HANDLE {
+        HANDLE(file.as_raw_handle())
+    }
+
+    fn set_sparse(file: &File) -> windows::core::Result<()> {
+        let mut bytes_returned = 0u32;
+        unsafe {
+            DeviceIoControl(
+                handle(file),
+                FSCTL_SET_SPARSE,
+                None,
+                0,
+                None,
+                0,
+                Some(&mut bytes_returned),
+                None,
+            )
+        }
+    }
+
+    /// 只预留簇，不改变文件末尾位置
+    fn reserve_clusters(file: &File, total: u64) -> windows::core::Result<()> {
+        let info = FILE_ALLOCATION_INFO {
+            AllocationSize: total as i64,
+        };
+        unsafe {
+            // SAFETY: 输入缓冲区是按 FILE_ALLOCATION_INFO 大小构造的栈变量
+            SetFileInformationByHandle(
+                handle(file),
+                FileAllocationInfo,
+                &info as *const FILE_ALLOCATION_INFO as *const c_void,
+                std::mem::size_of::<FILE_ALLOCATION_INFO>() as u32,
+            )
+        }
+    }
+
+    /// 预留簇并设置文件末尾；有卷维护权限时再跳过乱序写入触发的清零。
+    /// 预留失败时退回写入最后一个字节，强制文件系统分配空间
+    fn allocate_full(file: &File, total: u64) -> io::Result<()> {
+        let reserved = reserve_clusters(file, total);
+        file.set_len(total)?;
+        match reserved {
+            Ok(()) => {
+                if let Err(error) = unsafe { SetFileValidData(handle(file), total as i64) } {
+                    debug!("SetFileValidData 不可用，保留默认清零行为: {error}");
+                }
+                Ok(())
+            }
+            Err(error) => {
+                debug!("预留簇失败，改为写入末尾字节: {error}");
+                file.seek_write(&[0], total - 1).map(|_| ())
+            }
+        }
+    }
+
+    pub(super) fn preallocate(file: &File, total: u64, mode: PreallocationMode) -> io::Result<()> {
+        match mode {
+            PreallocationMode::Sparse => match set_sparse(file) {
+                Ok(()) => file.set_len(total),
+                Err(error) => {
+                    debug!("文件系统不支持稀疏文件，改为完整预分配: {error}");
+                    allocate_full(file, total)
+                }
+            },
+            PreallocationMode::Reserve => reserve_clusters(file, total).map_err(io::Error::from),
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod platform {
+    use super::PreallocationMode;
+    use std::fs::File;
+    use std::io;
+
+    pub(super) fn preallocate(file: &File, total: u64, mode: PreallocationMode) -> io::Result<()> {
+        match mode {
+            // set_len 扩展出的区间在常见 Unix 文件系统上本身就是稀疏的
+            PreallocationMode::Sparse => file.set_len(total),
+            // 无法在不改变长度的前提下预留空间，只依赖前置的剩余空间检查
+            PreallocationMode::Reserve => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sparse_preallocation_sets_final_length() {
+        let dest =
+            std::env::temp_dir().join(format!("bmcbl-preallocate-{}.bin", uuid::Uuid::new_v4()));
+        let file = File::create(&dest).expect("create file");
+
+        preallocate_download_file(&file, &dest, 3 * 1024 * 1024, PreallocationMode::Sparse)
+            .expect("preallocate");
+        assert_eq!(file.metadata().expect("metadata").len(), 3 * 1024 * 1024);
+
+        drop(file);
+        std::fs::remove_file(&dest).expect("remove test file");
+    }
+
+    #[test]
+    fn space_check_reports_shortfall_for_impossible_size() {
+        let dest = std::env::temp_dir().join("bmcbl-preallocate-space-check.bin");
+        if volume_space(&dest).is_none() {
+            return;
+        }
+        let error = ensure_download_space(&dest, u64::MAX / 2).expect_err("must not fit");
+        assert!(matches!(
+            error,
+            CoreError::InsufficientDiskSpace { shortfall, .. } if shortfall > 0
+        ));
+        assert!(error.to_string().starts_with("insufficient_disk_space"));
+    }
+}
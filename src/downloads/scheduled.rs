@@ -0,0 +1,674 @@
+//! 计划任务：把版本安装或游戏包下载排到指定时间（例如夜间不限流量时段）自动开始。
+//! 任务保存在 `BMCBL/scheduled_tasks.json`，启动后由调度循环按时经正常下载/安装流程执行；
+//! 启动器未运行而错过时间的任务，在宽限窗口内会于下次启动时补做。
+
+use crate::config::config::read_config;
+use crate::tasks::task_manager::{get_snapshot, subscribe_task_updates};
+use crate::utils::file_ops;
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+const SCHEDULED_TASKS_FILE_NAME: &str = "scheduled_tasks.json";
+const SCHEDULER_TICK: Duration = Duration::from_secs(30);
+const MAX_ATTEMPTS: u32 = 3;
+/// 第 n 次失败后推迟 n 倍的重试间隔
+const RETRY_DELAY_MINUTES: i64 = 10;
+/// 未提供包大小时按该值检查剩余空间
+const DEFAULT_REQUIRED_SPACE: u64 = 4 * 1024 * 1024 * 1024;
+/// 只保留最近这么多条已结束的任务
+const MAX_FINISHED_TASKS: usize = 50;
+
+/// 串行化读改写，避免调度循环和界面操作互相覆盖
+static SCHEDULED_TASKS_LOCK: Mutex<()> = Mutex::new(());
+static SCHEDULER_STARTED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduledTaskKind {
+    /// 下载游戏包并解压安装到 `versions`
+    InstallVersion,
+    /// 只下载游戏包
+    DownloadAppx,
+}
+
+impl std::str::FromStr for ScheduledTaskKind {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim() {
+            "install_version" => Ok(Self::InstallVersion),
+            "download_appx" => Ok(Self::DownloadAppx),
+            other => Err(format!("不支持的计划任务类型: {other}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduledTaskParams {
+    /// `<update_id>_<revision>`，与 `download_appx` 相同
+    pub package_id: String,
+    pub file_name: String,
+    #[serde(default)]
+    pub md5: Option<String>,
+    /// 安装到 `versions` 下的目录名；`install_version` 必填
+    #[serde(default)]
+    pub install_folder: Option<String>,
+    /// 包大小，用于开始前检查剩余空间
+    #[serde(default)]
+    pub size_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduledTaskStatus {
+    Pending,
+    Running,
+    Completed,
+    Skipped,
+    Abandoned,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledTask {
+    pub id: String,
+    pub kind: ScheduledTaskKind,
+    pub params: ScheduledTaskParams,
+    /// RFC3339，重试时会被推迟
+    pub start_at: String,
+    pub created_at: String,
+    pub status: ScheduledTaskStatus,
+    #[serde(default)]
+    pub attempts: u32,
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduledTaskEventKind {
+    Started,
+    Skipped,
+}
+
+/// `scheduled-task-started` / `scheduled-task-skipped` 事件
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScheduledTaskEvent {
+    pub kind: ScheduledTaskEventKind,
+    pub task_id: String,
+    pub reason: Option<String>,
+}
+
+static SCHEDULED_TASK_EVENTS: Lazy<broadcast::Sender<ScheduledTaskEvent>> = Lazy::new(|| {
+    let (tx, _rx) = broadcast::channel(16);
+    tx
+});
+
+pub fn subscribe_scheduled_task_events() -> broadcast::Receiver<ScheduledTaskEvent> {
+    SCHEDULED_TASK_EVENTS.subscribe()
+}
+
+fn emit(kind: ScheduledTaskEventKind, task_id: &str, reason: Option<String>) {
+    let _ = SCHEDULED_TASK_EVENTS.send(ScheduledTaskEvent {
+        kind,
+        task_id: task_id.to_string(),
+        reason,
+    });
+}
+
+fn tasks_path() -> PathBuf {
+    file_ops::bmcbl_subdir(SCHEDULED_TASKS_FILE_NAME)
+}
+
+fn load_tasks(path: &Path) -> Vec<ScheduledTask> {
+    let Ok(raw) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    match serde_json::from_str(&raw) {
+        Ok(tasks) => tasks,
+        Err(e) => {
+            warn!("解析计划任务失败，已忽略: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// 先写临时文件再替换，写入中断不会损坏已有任务
+fn save_tasks(path: &Path, tasks: &[ScheduledTask]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+    }
+    let raw = serde_json::to_string_pretty(tasks).map_err(|e| e.to_string())?;
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, raw).map_err(|e| format!("写入计划任务失败: {}", e))?;
+    fs::rename(&temp_path, path).map_err(|e| format!("保存计划任务失败: {}", e))
+}
+
+/// 在锁内读改写任务文件
+fn with_tasks<T>(
+    mutate: impl FnOnce(&mut Vec<ScheduledTask>) -> Result<T, String>,
+) -> Result<T, String> {
+    let _guard = SCHEDULED_TASKS_LOCK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let path = tasks_path();
+    let mut tasks = load_tasks(&path);
+    let result = mutate(&mut tasks)?;
+    prune_finished(&mut tasks);
+    save_tasks(&path, &tasks)?;
+    Ok(result)
+}
+
+fn is_finished(status: ScheduledTaskStatus) -> bool {
+    !matches!(
+        status,
+        ScheduledTaskStatus::Pending | ScheduledTaskStatus::Running
+    )
+}
+
+fn prune_finished(tasks: &mut Vec<ScheduledTask>) {
+    let finished = tasks.iter().filter(|task| is_finished(task.status)).count();
+    let mut excess = finished.saturating_sub(MAX_FINISHED_TASKS);
+    tasks.retain(|task| {
+        if excess > 0 && is_finished(task.status) {
+            excess -= 1;
+            return false;
+        }
+        true
+    });
+}
+
+/// 带时区的 RFC3339，或不带时区的 `YYYY-MM-DDTHH:MM[:SS]`（按本地时间）
+fn parse_start_at(value: &str) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    [
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+    .and_then(|naive| Local.from_local_datetime(&naive).earliest())
+    .map(|time| time.with_timezone(&Utc))
+    .ok_or_else(|| format!("无法解析开始时间: {value}"))
+}
+
+fn grace_window() -> chrono::Duration {
+    let minutes = read_config()
+        .map(|config| config.launcher.download.scheduled_grace_minutes)
+        .unwrap_or(crate::config::config::DEFAULT_SCHEDULED_GRACE_MINUTES);
+    chrono::Duration::minutes(i64::from(minutes))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum DueAction {
+    Wait,
+    Run,
+    /// 超过宽限窗口
+    Missed,
+}
+
+fn due_action(task: &ScheduledTask, now: DateTime<Utc>, grace: chrono::Duration) -> DueAction {
+    if task.status != ScheduledTaskStatus::Pending {
+        return DueAction::Wait;
+    }
+    let Ok(start_at) = parse_start_at(&task.start_at) else {
+        return DueAction::Missed;
+    };
+    if start_at > now {
+        DueAction::Wait
+    } else if now - start_at > grace {
+        DueAction::Missed
+    } else {
+        DueAction::Run
+    }
+}
+
+/// 按重试策略推迟任务；次数用尽后放弃
+fn record_failure(task: &mut ScheduledTask, error: String, now: DateTime<Utc>) {
+    task.attempts = task.attempts.saturating_add(1);
+    task.last_error = Some(error);
+    if task.attempts >= MAX_ATTEMPTS {
+        task.status = ScheduledTaskStatus::Abandoned;
+        return;
+    }
+    let delay = chrono::Duration::minutes(RETRY_DELAY_MINUTES * i64::from(task.attempts));
+    task.start_at = (now + delay).to_rfc3339();
+    task.status = ScheduledTaskStatus::Pending;
+}
+
+fn validate_params(kind: ScheduledTaskKind, params: &ScheduledTaskParams) -> Result<(), String> {
+    if params.package_id.split('_').count() != 2 {
+        return Err("package_id 格式无效，必须形如 `<id>_<revision>`".to_string());
+    }
+    if params.file_name.trim().is_empty() {
+        return Err("文件名不能为空".to_string());
+    }
+    if kind == ScheduledTaskKind::InstallVersion {
+        let folder = params.install_folder.as_deref().unwrap_or_default().trim();
+        if folder.is_empty() {
+            return Err("安装任务必须指定安装目录".to_string());
+        }
+        if folder.contains("..") || folder.contains('/') || folder.contains('\\') {
+            return Err("无效的安装目录名称".to_string());
+        }
+    }
+    Ok(())
+}
+
+pub fn schedule_task(
+    kind: String,
+    params: ScheduledTaskParams,
+    start_at: String,
+) -> Result<ScheduledTask, String> {
+    let kind = kind.parse::<ScheduledTaskKind>()?;
+    validate_params(kind, &params)?;
+    let start_at = parse_start_at(&start_at)?;
+    let task = ScheduledTask {
+        id: uuid::Uuid::new_v4().to_string(),
+        kind,
+        params,
+        start_at: start_at.to_rfc3339(),
+        created_at: Utc::now().to_rfc3339(),
+        status: ScheduledTaskStatus::Pending,
+        attempts: 0,
+        last_error: None,
+    };
+    with_tasks(|tasks| {
+        tasks.push(task.clone());
+        Ok(())
+    })?;
+    info!(id = %task.id, start_at = %task.start_at, "已添加计划任务");
+    Ok(task)
+}
+
+pub fn list_scheduled_tasks() -> Result<Vec<ScheduledTask>, String> {
+    let _guard = SCHEDULED_TASKS_LOCK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let mut tasks = load_tasks(&tasks_path());
+    tasks.sort_by(|left, right| left.start_at.cmp(&right.start_at));
+    Ok(tasks)
+}
+
+pub fn cancel_scheduled_task(id: String) -> Result<(), String> {
+    with_tasks(|tasks| {
+        let task = tasks
+            .iter_mut()
+            .find(|task| task.id == id)
+            .ok_or_else(|| format!("未找到计划任务: {id}"))?;
+        match task.status {
+            ScheduledTaskStatus::Pending => {
+                task.status = ScheduledTaskStatus::Cancelled;
+                Ok(())
+            }
+            ScheduledTaskStatus::Running => Err("任务已开始，请在任务列表中取消下载".to_string()),
+            _ => Err("任务已结束".to_string()),
+        }
+    })
+}
+
+fn update_task(id: &str, mutate: impl FnOnce(&mut ScheduledTask)) {
+    let result = with_tasks(|tasks| {
+        if let Some(task) = tasks.iter_mut().find(|task| task.id == id) {
+            mutate(task);
+        }
+        Ok(())
+    });
+    if let Err(error) = result {
+        warn!(id, "更新计划任务失败: {}", error);
+    }
+}
+
+/// 任务仍处于 Pending 时才修改并返回修改后的任务；前面的任务可能执行很久，
+/// 期间用户取消的任务不能再被改回执行中
+fn claim_pending_in(
+    tasks: &mut [ScheduledTask],
+    id: &str,
+    mutate: impl FnOnce(&mut ScheduledTask),
+) -> Option<ScheduledTask> {
+    let task = tasks
+        .iter_mut()
+        .find(|task| task.id == id && task.status == ScheduledTaskStatus::Pending)?;
+    mutate(task);
+    Some(task.clone())
+}
+
+fn claim_pending(id: &str, mutate: impl FnOnce(&mut ScheduledTask)) -> Option<ScheduledTask> {
+    match with_tasks(|tasks| Ok(claim_pending_in(tasks, id, mutate))) {
+        Ok(task) => task,
+        Err(error) => {
+            warn!(id, "更新计划任务失败: {}", error);
+            None
+        }
+    }
+}
+
+/// 开始时检查前置条件，返回跳过原因
+fn precondition_failure(task: &ScheduledTask) -> Option<String> {
+    if task.kind == ScheduledTaskKind::InstallVersion
+        && let Some(folder) = task.params.install_folder.as_deref()
+        && file_ops::versions_dir().join(folder).exists()
+    {
+        return Some(format!("版本已安装: {folder}"));
+    }
+    let package_size = task.params.size_bytes.unwrap_or(DEFAULT_REQUIRED_SPACE);
+    // 安装需要同时放下安装包和解压后的文件
+    let required = match task.kind {
+        ScheduledTaskKind::InstallVersion => package_size.saturating_mul(2),
+        ScheduledTaskKind::DownloadAppx => package_size,
+    };
+    super::preallocate::ensure_download_space(&super::api::downloads_dir(), required)
+        .err()
+        .map(|error| error.to_string())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum RunFailure {
+    /// 用户在任务列表中取消，不再重试
+    Cancelled,
+    Failed(String),
+}
+
+impl From<String> for RunFailure {
+    fn from(error: String) -> Self {
+        Self::Failed(error)
+    }
+}
+
+impl RunFailure {
+    fn context(self, prefix: &str) -> Self {
+        match self {
+            Self::Cancelled => Self::Cancelled,
+            Self::Failed(error) => Self::Failed(format!("{prefix}: {error}")),
+        }
+    }
+}
+
+/// 等待任务管理器中的任务结束，成功时返回完成消息
+async fn wait_task_finished(task_id: &str) -> Result<Option<String>, RunFailure> {
+    let mut receiver = subscribe_task_updates();
+    loop {
+        let Some(snapshot) = get_snapshot(task_id) else {
+            return Err(RunFailure::Failed("任务已不存在".to_string()));
+        };
+        match snapshot.status.as_ref() {
+            "completed" => return Ok(snapshot.message.map(|message| message.to_string())),
+            "cancelled" => return Err(RunFailure::Cancelled),
+            "error" => {
+                return Err(RunFailure::Failed(
+                    snapshot
+                        .message
+                        .map(|message| message.to_string())
+                        .unwrap_or_else(|| "未知错误".to_string()),
+                ));
+            }
+            _ => {}
+        }
+        match tokio::time::timeout(Duration::from_millis(500), receiver.recv()).await {
+            Ok(Err(broadcast::error::RecvError::Closed)) => {
+                return Err(RunFailure::Failed("任务管理器已关闭".to_string()));
+            }
+            Ok(Ok(_)) | Ok(Err(broadcast::error::RecvError::Lagged(_))) | Err(_) => {}
+        }
+    }
+}
+
+/// 走与界面相同的下载 → 解压流程
+async fn run_task(task: &ScheduledTask) -> Result<(), RunFailure> {
+    let params = &task.params;
+    let download_task_id = super::api::download_appx(
+        params.package_id.clone(),
+        params.file_name.clone(),
+        params.md5.clone(),
+        Some(false),
+        None,
+    )
+    .await?;
+    let file_path = wait_task_finished(&download_task_id)
+        .await
+        .map_err(|failure| failure.context("下载失败"))?
+        .ok_or_else(|| RunFailure::Failed("下载完成但未返回文件路径".to_string()))?;
+    if task.kind == ScheduledTaskKind::DownloadAppx {
+        return Ok(());
+    }
+
+    let install_folder = params.install_folder.clone().unwrap_or_default();
+    let extract_task_id = crate::archive::api::extract_zip_appx(
+        format!("{install_folder}.appx"),
+        file_path,
+        true,
+        true,
+    )
+    .await?;
+    wait_task_finished(&extract_task_id)
+        .await
+        .map(|_| ())
+        .map_err(|failure| failure.context("安装失败"))
+}
+
+async fn run_due_tasks() {
+    let now = Utc::now();
+    let grace = grace_window();
+    let tasks = match list_scheduled_tasks() {
+        Ok(tasks) => tasks,
+        Err(error) => {
+            warn!("读取计划任务失败: {}", error);
+            return;
+        }
+    };
+
+    for task in tasks {
+        match due_action(&task, now, grace) {
+            DueAction::Wait => continue,
+            DueAction::Missed => {
+                let reason = format!("已超过开始时间 {} 的宽限窗口", task.start_at);
+                if claim_pending(&task.id, |task| {
+                    task.status = ScheduledTaskStatus::Skipped;
+                    task.last_error = Some(reason.clone());
+                })
+                .is_some()
+                {
+                    emit(ScheduledTaskEventKind::Skipped, &task.id, Some(reason));
+                }
+                continue;
+            }
+            DueAction::Run => {}
+        }
+
+        if let Some(reason) = precondition_failure(&task) {
+            if claim_pending(&task.id, |task| {
+                task.status = ScheduledTaskStatus::Skipped;
+                task.last_error = Some(reason.clone());
+            })
+            .is_some()
+            {
+                info!(id = %task.id, %reason, "计划任务前置条件不满足，已跳过");
+                emit(ScheduledTaskEventKind::Skipped, &task.id, Some(reason));
+            }
+            continue;
+        }
+
+        let Some(task) = claim_pending(&task.id, |task| {
+            task.status = ScheduledTaskStatus::Running;
+        }) else {
+            debug!(id = %task.id, "计划任务已不在等待状态，跳过执行");
+            continue;
+        };
+        emit(ScheduledTaskEventKind::Started, &task.id, None);
+        info!(id = %task.id, kind = ?task.kind, "开始执行计划任务");
+        match run_task(&task).await {
+            Ok(()) => update_task(&task.id, |task| {
+                task.status = ScheduledTaskStatus::Completed;
+                task.last_error = None;
+            }),
+            Err(RunFailure::Cancelled) => {
+                info!(id = %task.id, "计划任务已被取消");
+                update_task(&task.id, |task| {
+                    task.status = ScheduledTaskStatus::Cancelled;
+                    task.last_error = None;
+                });
+            }
+            Err(RunFailure::Failed(error)) => {
+                warn!(id = %task.id, "计划任务失败: {}", error);
+                update_task(&task.id, |task| record_failure(task, error, Utc::now()));
+            }
+        }
+    }
+}
+
+/// 启动调度循环，整个会话只启动一次。上次退出时仍在执行的任务按失败处理并进入重试
+pub fn start_scheduler() {
+    if SCHEDULER_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let result = with_tasks(|tasks| {
+        let now = Utc::now();
+        for task in tasks
+            .iter_mut()
+            .filter(|task| task.status == ScheduledTaskStatus::Running)
+        {
+            record_failure(task, "启动器在任务执行期间退出".to_string(), now);
+        }
+        Ok(())
+    });
+    if let Err(error) = result {
+        warn!("恢复计划任务失败: {}", error);
+    }
+
+    tokio::spawn(async {
+        debug!("计划任务调度循环已启动");
+        loop {
+            run_due_tasks().await;
+            tokio::time::sleep(SCHEDULER_TICK).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending_task(start_at: DateTime<Utc>) -> ScheduledTask {
+        ScheduledTask {
+            id: "task".to_string(),
+            kind: ScheduledTaskKind::DownloadAppx,
+            params: ScheduledTaskParams {
+                package_id: "update_1".to_string(),
+                file_name: "Minecraft.appx".to_string(),
+                md5: None,
+                install_folder: None,
+                size_bytes: None,
+            },
+            start_at: start_at.to_rfc3339(),
+            created_at: start_at.to_rfc3339(),
+            status: ScheduledTaskStatus::Pending,
+            attempts: 0,
+            last_error: None,
+        }
+    }
+
+    #[test]
+    fn due_tasks_respect_grace_window_and_retry_policy() {
+        let now = Utc::now();
+        let grace = chrono::Duration::minutes(60);
+        assert_eq!(
+            due_action(
+                &pending_task(now + chrono::Duration::minutes(5)),
+                now,
+                grace
+            ),
+            DueAction::Wait
+        );
+        assert_eq!(
+            due_action(
+                &pending_task(now - chrono::Duration::minutes(30)),
+                now,
+                grace
+            ),
+            DueAction::Run
+        );
+        assert_eq!(
+            due_action(
+                &pending_task(now - chrono::Duration::minutes(90)),
+                now,
+                grace
+            ),
+            DueAction::Missed
+        );
+
+        let mut task = pending_task(now);
+        record_failure(&mut task, "network".to_string(), now);
+        assert_eq!(task.status, ScheduledTaskStatus::Pending);
+        assert_eq!(due_action(&task, now, grace), DueAction::Wait);
+        record_failure(&mut task, "network".to_string(), now);
+        record_failure(&mut task, "network".to_string(), now);
+        assert_eq!(task.status, ScheduledTaskStatus::Abandoned);
+        assert_eq!(task.attempts, MAX_ATTEMPTS);
+
+        assert!(
+            validate_params(ScheduledTaskKind::InstallVersion, &task.params).is_err(),
+            "install tasks need an install folder"
+        );
+        for folder in ["../escape", "nested/folder", "nested\\folder", ".."] {
+            let mut params = task.params.clone();
+            params.install_folder = Some(folder.to_string());
+            assert!(
+                validate_params(ScheduledTaskKind::InstallVersion, &params).is_err(),
+                "{folder} must be rejected"
+            );
+        }
+        let mut params = task.params.clone();
+        params.install_folder = Some("1.21.50".to_string());
+        assert!(validate_params(ScheduledTaskKind::InstallVersion, &params).is_ok());
+    }
+
+    #[test]
+    fn only_pending_tasks_can_be_claimed() {
+        let now = Utc::now();
+        let mut tasks = vec![pending_task(now)];
+        tasks[0].status = ScheduledTaskStatus::Cancelled;
+        assert!(
+            claim_pending_in(&mut tasks, "task", |task| {
+                task.status = ScheduledTaskStatus::Running
+            })
+            .is_none()
+        );
+        assert_eq!(tasks[0].status, ScheduledTaskStatus::Cancelled);
+
+        tasks[0].status = ScheduledTaskStatus::Pending;
+        let claimed = claim_pending_in(&mut tasks, "task", |task| {
+            task.status = ScheduledTaskStatus::Running
+        });
+        assert_eq!(
+            claimed.map(|task| task.status),
+            Some(ScheduledTaskStatus::Running)
+        );
+        assert_eq!(tasks[0].status, ScheduledTaskStatus::Running);
+        assert!(claim_pending_in(&mut tasks, "missing", |_| {}).is_none());
+    }
+
+    #[tokio::test]
+    async fn missing_task_snapshot_counts_as_failure() {
+        assert!(matches!(
+            wait_task_finished("scheduled-missing-task").await,
+            Err(RunFailure::Failed(_))
+        ));
+        assert_eq!(
+            RunFailure::Cancelled.context("下载失败"),
+            RunFailure::Cancelled
+        );
+    }
+}
@@ -1,6 +1,9 @@
 // src/downloads/single.rs
 use crate::downloads::integrity::{has_zip_header, should_verify_zip_during_download};
 use crate::downloads::md5::{is_md5_digest, verify_md5};
+use crate::downloads::preallocate::{
+    PreallocationMode, ensure_download_space, preallocate_download_file,
+};
 use crate::http::proxy::{apply_download_request_headers, validate_download_response_headers};
 use crate::result::{CoreError, CoreResult};
 use crate::tasks::task_manager::{
@@ -10,10 +13,10 @@ use crate::tasks::task_manager::{
 };
 use futures_util::StreamExt;
 use reqwest::header::HeaderMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::fs::OpenOptions as TokioOpenOptions;
+use tokio::fs::{File as TokioFile, OpenOptions as TokioOpenOptions};
 use tokio::io::{AsyncWriteExt, BufWriter};
 use tracing::debug;
 
@@ -49,6 +52,22 @@ fn clear_single_visualization(task_id: &str) {
     set_task_visualization(task_id, None);
 }
 
+/// 从头下载且已知大小时，先检查剩余空间并预留簇，空间不足立即中止
+async fn reserve_download_space(
+    file: TokioFile,
+    dest: PathBuf,
+    total: u64,
+) -> Result<TokioFile, CoreError> {
+    let file = file.into_std().await;
+    let file = tokio::task::spawn_blocking(move || {
+        ensure_download_space(&dest, total)?;
+        preallocate_download_file(&file, &dest, total, PreallocationMode::Reserve)?;
+        Ok::<_, CoreError>(file)
+    })
+    .await??;
+    Ok(TokioFile::from_std(file))
+}
+
 pub async fn download_file(
     client: reqwest::Client,
     task_control: Arc<TaskControl>,
@@ -131,7 +150,18 @@ pub async fn download_file(
                 } else {
                     open_options.truncate(true);
                 }
-                let file = open_options.open(&dest_buf).await.map_err(CoreError::Io)?;
+                let mut file = open_options.open(&dest_buf).await.map_err(CoreError::Io)?;
+                if !supports_resume && let Some(expected_total) = total {
+                    file = match reserve_download_space(file, dest_buf.clone(), expected_total)
+                        .await
+                    {
+                        Ok(file) => file,
+                        Err(error) => {
+                            clear_single_visualization(task_id);
+                            return Err(error);
+                        }
+                    };
+                }
                 let mut writer = BufWriter::with_capacity(DISK_BUFFER_SIZE, file);
 
                 let mut stream = resp.bytes_stream();
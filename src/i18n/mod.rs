@@ -1,6 +1,9 @@
+use serde::Serialize;
 use std::borrow::Cow;
 use std::fmt;
 use std::fmt::Write as _;
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 
 include!(concat!(env!("OUT_DIR"), "/generated_locales.rs"));
 
@@ -65,6 +68,10 @@ impl Locale {
         ALL
     }
 
+    fn index(self) -> usize {
+        self as usize
+    }
+
     pub fn next(self) -> Self {
         let all = Self::all();
         let index = all.iter().position(|locale| *locale == self).unwrap_or(0);
@@ -78,10 +85,31 @@ impl Default for Locale {
     }
 }
 
+/// 当前语言缺少某条文案时回退到的语言，构建时保证它的文案完整
+const FALLBACK_LOCALE: Locale = Locale::EnUs;
+
+static FALLBACK_MARKERS: AtomicBool = AtomicBool::new(false);
+/// 各语言回退的次数，内层下标与回退语言的文案表一致；只在未命中时原子自增，不加锁
+static FALLBACK_HITS: LazyLock<Vec<Vec<AtomicU64>>> = LazyLock::new(|| {
+    let len = locale_entries(FALLBACK_LOCALE.code()).len();
+    Locale::all()
+        .iter()
+        .map(|_| (0..len).map(|_| AtomicU64::new(0)).collect())
+        .collect()
+});
+
+/// 调试用：用 `⟦…⟧` 包住回退的文案，方便翻译校对时找出未翻译的位置
+pub fn set_fallback_markers(enabled: bool) {
+    FALLBACK_MARKERS.store(enabled, Ordering::Relaxed);
+}
+
 #[derive(Clone)]
 pub struct Translator {
     locale: Locale,
     entries: &'static [(&'static str, &'static str)],
+    fallback_entries: &'static [(&'static str, &'static str)],
+    /// 当前语言的回退计数，下标与 `fallback_entries` 一致
+    fallback_hits: &'static [AtomicU64],
 }
 
 impl Translator {
@@ -90,6 +118,8 @@ impl Translator {
         Self {
             locale,
             entries: locale_entries(locale.code()),
+            fallback_entries: locale_entries(FALLBACK_LOCALE.code()),
+            fallback_hits: FALLBACK_HITS[locale.index()].as_slice(),
         }
     }
 
@@ -100,16 +130,30 @@ impl Translator {
     pub fn set_locale(&mut self, locale: Locale) {
         self.locale = locale;
         self.entries = locale_entries(locale.code());
+        self.fallback_hits = FALLBACK_HITS[locale.index()].as_slice();
     }
 
     pub fn ensure_loaded(&mut self) {}
 
     pub fn ensure_locale_loaded(&mut self, _locale: Locale) {}
 
+    /// 当前语言缺少该键时取回退语言的文案并记录一次回退，第二项表示是否回退
+    fn lookup(&self, key: &str) -> Option<(&'static str, bool)> {
+        if let Some(value) = lookup_entries_value(self.entries, key) {
+            return Some((value, false));
+        }
+        let index = lookup_entries_index(self.fallback_entries, key)?;
+        if let Some(counter) = self.fallback_hits.get(index) {
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+        Some((self.fallback_entries[index].1, true))
+    }
+
     pub fn translate(&self, key: &str) -> Cow<'static, str> {
-        lookup_entries_value(self.entries, key)
-            .map(Cow::Borrowed)
-            .unwrap_or_else(|| Cow::Owned(key.to_string()))
+        match self.lookup(key) {
+            Some((value, fallback)) => mark_fallback(Cow::Borrowed(value), fallback),
+            None => Cow::Owned(key.to_string()),
+        }
     }
 
     pub fn translate_args<const N: usize>(
@@ -121,11 +165,111 @@ impl Translator {
             return self.translate(key);
         }
 
-        let Some(value) = lookup_entries_value(self.entries, key) else {
+        let Some((value, fallback)) = self.lookup(key) else {
             return Cow::Owned(key.to_string());
         };
 
-        Cow::Owned(interpolate_args(value, args))
+        mark_fallback(Cow::Owned(interpolate_args(value, args)), fallback)
+    }
+
+    /// 运行时变量版本：参数个数不固定时使用，`vars` 为空时不分配
+    pub fn translate_vars(&self, key: &str, vars: &[(&str, &str)]) -> Cow<'static, str> {
+        if vars.is_empty() {
+            return self.translate(key);
+        }
+
+        let Some((value, fallback)) = self.lookup(key) else {
+            return Cow::Owned(key.to_string());
+        };
+
+        let value = interpolate(value, |placeholder, output| {
+            let Some((_, value)) = vars.iter().find(|(name, _)| *name == placeholder) else {
+                return false;
+            };
+            output.push_str(value);
+            true
+        });
+        mark_fallback(Cow::Owned(value), fallback)
+    }
+}
+
+fn mark_fallback(value: Cow<'static, str>, fallback: bool) -> Cow<'static, str> {
+    if fallback && FALLBACK_MARKERS.load(Ordering::Relaxed) {
+        wrap_fallback_marker(value)
+    } else {
+        value
+    }
+}
+
+fn wrap_fallback_marker(value: Cow<'static, str>) -> Cow<'static, str> {
+    Cow::Owned(format!("⟦{value}⟧"))
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct I18nMissingKey {
+    pub key: String,
+    /// 本次运行中该键回退的次数
+    pub fallback_hits: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LocaleCoverage {
+    pub locale: String,
+    pub total_keys: usize,
+    pub translated_keys: usize,
+    pub coverage_percent: f64,
+    pub missing_keys: Vec<I18nMissingKey>,
+}
+
+/// 各语言相对回退语言的翻译覆盖率和缺失的键
+pub fn get_i18n_coverage() -> Vec<LocaleCoverage> {
+    let fallback_entries = locale_entries(FALLBACK_LOCALE.code());
+    Locale::all()
+        .iter()
+        .map(|&locale| {
+            locale_coverage(
+                locale,
+                locale_entries(locale.code()),
+                fallback_entries,
+                &FALLBACK_HITS[locale.index()],
+            )
+        })
+        .collect()
+}
+
+fn locale_coverage(
+    locale: Locale,
+    entries: &[(&str, &str)],
+    fallback_entries: &[(&str, &str)],
+    hits: &[AtomicU64],
+) -> LocaleCoverage {
+    let missing_keys: Vec<I18nMissingKey> = fallback_entries
+        .iter()
+        .enumerate()
+        .filter(|(_, (key, _))| {
+            entries
+                .binary_search_by_key(key, |(entry_key, _)| entry_key)
+                .is_err()
+        })
+        .map(|(index, (key, _))| I18nMissingKey {
+            key: key.to_string(),
+            fallback_hits: hits
+                .get(index)
+                .map_or(0, |counter| counter.load(Ordering::Relaxed)),
+        })
+        .collect();
+    let total_keys = fallback_entries.len();
+    let translated_keys = total_keys - missing_keys.len();
+    LocaleCoverage {
+        locale: locale.code().to_string(),
+        total_keys,
+        translated_keys,
+        coverage_percent: if total_keys == 0 {
+            100.0
+        } else {
+            translated_keys as f64 * 100.0 / total_keys as f64
+        },
+        missing_keys,
     }
 }
 
@@ -157,17 +301,34 @@ impl Default for Translator {
     }
 }
 
+fn lookup_entries_index(entries: &[(&str, &str)], key: &str) -> Option<usize> {
+    entries
+        .binary_search_by_key(&key, |(entry_key, _)| *entry_key)
+        .ok()
+}
+
 fn lookup_entries_value(
     entries: &'static [(&'static str, &'static str)],
     key: &str,
 ) -> Option<&'static str> {
-    entries
-        .binary_search_by_key(&key, |(entry_key, _)| *entry_key)
-        .ok()
-        .map(|index| entries[index].1)
+    lookup_entries_index(entries, key).map(|index| entries[index].1)
 }
 
 fn interpolate_args<const N: usize>(template: &'static str, args: [I18nArg<'_>; N]) -> String {
+    interpolate(template, |placeholder, output| {
+        let Some(argument) = args.iter().find(|argument| argument.key == placeholder) else {
+            return false;
+        };
+        let _ = output.write_fmt(argument.value);
+        true
+    })
+}
+
+/// 替换 `{{name}}` 占位符；`write_value` 返回 false 时原样保留占位符
+fn interpolate(
+    template: &'static str,
+    mut write_value: impl FnMut(&str, &mut String) -> bool,
+) -> String {
     let mut output = String::with_capacity(template.len());
     let mut cursor = 0;
 
@@ -182,9 +343,7 @@ fn interpolate_args<const N: usize>(template: &'static str, args: [I18nArg<'_>;
 
         let close = open + 2 + close_offset;
         let placeholder = &template[open + 2..close];
-        if let Some(argument) = args.iter().find(|argument| argument.key == placeholder) {
-            let _ = output.write_fmt(argument.value);
-        } else {
+        if !write_value(placeholder, &mut output) {
             output.push_str(&template[open..close + 2]);
         }
 
@@ -194,3 +353,73 @@ fn interpolate_args<const N: usize>(template: &'static str, args: [I18nArg<'_>;
     output.push_str(&template[cursor..]);
     output
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static FALLBACK: &[(&str, &str)] = &[
+        ("A.one", "One"),
+        ("A.two", "Two {{count}}"),
+        ("B.three", "Three"),
+    ];
+    static PARTIAL: &[(&str, &str)] = &[("A.one", "イチ")];
+    static FIXTURE_HITS: [AtomicU64; 3] = [const { AtomicU64::new(0) }; 3];
+
+    #[test]
+    fn partial_locale_falls_back_per_message_and_reports_coverage() {
+        let translator = Translator {
+            locale: Locale::JaJp,
+            entries: PARTIAL,
+            fallback_entries: FALLBACK,
+            fallback_hits: &FIXTURE_HITS,
+        };
+        assert_eq!(translator.translate("A.one"), "イチ");
+        assert_eq!(translator.translate("B.three"), "Three");
+        assert_eq!(translator.translate("B.three"), "Three");
+        assert_eq!(
+            translator.translate_vars("A.two", &[("count", "2")]),
+            "Two 2"
+        );
+        assert_eq!(translator.translate("Missing.key"), "Missing.key");
+        assert_eq!(wrap_fallback_marker(Cow::Borrowed("Three")), "⟦Three⟧");
+
+        let coverage = locale_coverage(Locale::JaJp, PARTIAL, FALLBACK, &FIXTURE_HITS);
+        assert_eq!(coverage.total_keys, 3);
+        assert_eq!(coverage.translated_keys, 1);
+        assert_eq!(
+            coverage.missing_keys,
+            vec![
+                I18nMissingKey {
+                    key: "A.two".to_string(),
+                    fallback_hits: 1,
+                },
+                I18nMissingKey {
+                    key: "B.three".to_string(),
+                    fallback_hits: 2,
+                },
+            ]
+        );
+        assert_eq!(FIXTURE_HITS[0].load(Ordering::Relaxed), 0);
+        assert!(
+            get_i18n_coverage()
+                .iter()
+                .any(|coverage| coverage.locale == "en-US" && coverage.missing_keys.is_empty())
+        );
+    }
+
+    #[test]
+    fn real_fallback_lookup_bumps_the_locale_counter_for_the_en_us_key() {
+        let mut translator = Translator::new();
+        translator.set_locale(Locale::JaJp);
+        translator.entries = &[];
+        let fallback_entries = locale_entries(FALLBACK_LOCALE.code());
+        let index = fallback_entries.len() / 2;
+        let (key, value) = fallback_entries[index];
+        let counter = &FALLBACK_HITS[Locale::JaJp.index()][index];
+
+        let before = counter.load(Ordering::Relaxed);
+        assert_eq!(translator.translate(key), value);
+        assert!(counter.load(Ordering::Relaxed) > before);
+    }
+}
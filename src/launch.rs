@@ -66,6 +66,14 @@ struct Cli {
     #[arg(long = "gui")]
     gui: bool,
 
+    /// 启动前把渲染后端和显卡设置恢复为自动，用于主窗口白屏时
+    #[arg(long = "reset-renderer", alias = "reset-webview")]
+    reset_renderer: bool,
+
+    /// 提权重启时携带的恢复令牌，见 `utils::elevation`
+    #[arg(long = "resume-elevated", value_name = "TOKEN", hide = true)]
+    resume_elevated: Option<String>,
+
     #[arg(value_name = "FILE")]
     shell_open_target: Option<PathBuf>,
 
@@ -87,6 +95,16 @@ pub fn parse_launch_mode() -> LaunchMode {
     parse_launch_mode_from_cli(Cli::parse())
 }
 
+/// 命令行是否带有 `--reset-renderer`
+pub fn reset_renderer_requested() -> bool {
+    Cli::try_parse().is_ok_and(|cli| cli.reset_renderer)
+}
+
+/// 命令行中 `--resume-elevated` 的恢复令牌
+pub fn resume_elevated_token() -> Option<String> {
+    Cli::try_parse().ok().and_then(|cli| cli.resume_elevated)
+}
+
 fn parse_launch_mode_from_cli(cli: Cli) -> LaunchMode {
     if let Some(args) = cli.legacy_run_updater
         && let [source_path, destination_path, timeout_path] = args.as_slice()
@@ -230,4 +248,26 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn resume_elevated_token_keeps_main_mode() {
+        use clap::Parser;
+
+        let args = ["BMCBL", "--resume-elevated", "7b7d"];
+        let cli = super::Cli::try_parse_from(args).expect("parse args");
+        assert_eq!(cli.resume_elevated.as_deref(), Some("7b7d"));
+        let launch_mode = parse_launch_mode_from(args).expect("parse launch args");
+        assert_eq!(launch_mode, LaunchMode::Main);
+    }
+
+    #[test]
+    fn reset_webview_flag_is_an_alias_for_reset_renderer() {
+        use clap::Parser;
+
+        let cli = super::Cli::try_parse_from(["BMCBL", "--reset-webview"]).expect("parse args");
+        assert!(cli.reset_renderer);
+        let launch_mode =
+            parse_launch_mode_from(["BMCBL", "--reset-renderer"]).expect("parse launch args");
+        assert_eq!(launch_mode, LaunchMode::Main);
+    }
 }
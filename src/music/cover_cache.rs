@@ -1,3 +1,4 @@
+use crate::core::cache_registry::{CacheDescriptor, CacheUsage};
 use crate::music::cover;
 use crate::music::types::DecodedCoverImage;
 use std::collections::{HashMap, VecDeque};
@@ -38,6 +39,17 @@ impl CoverImageCache {
         }
     }
 
+    fn clear(&mut self) -> CacheUsage {
+        let usage = CacheUsage {
+            entries: self.entries.len() as u64,
+            bytes: self.decoded_byte_len as u64,
+        };
+        self.entries.clear();
+        self.usage_order.clear();
+        self.decoded_byte_len = 0;
+        usage
+    }
+
     fn get(&mut self, cover_cache_key: u64, started: Instant) -> Option<DecodedCoverImage> {
         let mut image = self
             .entries
@@ -109,6 +121,32 @@ impl CoverImageCache {
     }
 }
 
+pub(crate) static CACHE: CacheDescriptor = CacheDescriptor {
+    name: "music_covers",
+    label: "音乐封面",
+    default_selected: true,
+    usage: cover_cache_usage,
+    clear: clear_cover_cache,
+    warm: None,
+};
+
+fn cover_cache_usage() -> CacheUsage {
+    let cache = cover_image_cache()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    CacheUsage {
+        entries: cache.entries.len() as u64,
+        bytes: cache.decoded_byte_len as u64,
+    }
+}
+
+fn clear_cover_cache() -> Result<CacheUsage, String> {
+    Ok(cover_image_cache()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clear())
+}
+
 fn cover_image_cache() -> &'static Mutex<CoverImageCache> {
     COVER_IMAGE_CACHE.get_or_init(|| {
         Mutex::new(CoverImageCache::new(
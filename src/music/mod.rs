@@ -1,5 +1,5 @@
 mod cover;
-mod cover_cache;
+pub(crate) mod cover_cache;
 mod library;
 pub mod service;
 pub mod types;
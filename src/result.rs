@@ -45,6 +45,13 @@ pub enum CoreError {
     /// 校验和不匹配（例如 MD5 校验失败）
     #[error("Checksum mismatch: {0}")]
     ChecksumMismatch(String),
+
+    /// 目标磁盘空间不足（下载前检查或预分配失败），`shortfall` 为还差的字节数
+    #[error(
+        "insufficient_disk_space: 磁盘 {volume} 空间不足，还需 {} MB",
+        .shortfall.div_ceil(1024 * 1024)
+    )]
+    InsufficientDiskSpace { volume: String, shortfall: u64 },
 }
 
 impl From<tokio::time::error::Elapsed> for CoreError {
@@ -7,6 +7,8 @@ use tracing::{debug, error, info};
 
 #[cfg(windows)]
 const SINGLE_INSTANCE_MUTEX_NAME: &str = "Global\\com.bmcbl.app.single_instance";
+#[cfg(windows)]
+const ELEVATED_HANDOFF_TIMEOUT: Duration = Duration::from_secs(15);
 
 #[cfg(windows)]
 fn bring_main_window_to_foreground() {
@@ -55,13 +57,16 @@ impl Drop for SingleInstanceGuard {
     }
 }
 
+/// `wait_for_previous` 为 true 时（提权重启），等待旧实例释放互斥量后接管，而不是直接退出
 #[cfg(windows)]
-fn check_single_instance() -> Option<bool> {
+fn check_single_instance(wait_for_previous: bool) -> Option<bool> {
     use std::ffi::OsStr;
     #[cfg(target_os = "windows")]
     use std::os::windows::ffi::OsStrExt;
-    use windows::Win32::Foundation::{CloseHandle, ERROR_ALREADY_EXISTS, GetLastError};
-    use windows::Win32::System::Threading::CreateMutexW;
+    use windows::Win32::Foundation::{
+        CloseHandle, ERROR_ALREADY_EXISTS, GetLastError, WAIT_ABANDONED, WAIT_OBJECT_0,
+    };
+    use windows::Win32::System::Threading::{CreateMutexW, WaitForSingleObject};
     use windows::core::PCWSTR;
 
     let wide_name: Vec<u16> = OsStr::new(SINGLE_INSTANCE_MUTEX_NAME)
@@ -80,6 +85,22 @@ fn check_single_instance() -> Option<bool> {
 
     // SAFETY: `GetLastError` reads the thread-local Win32 error after `CreateMutexW`.
     if unsafe { GetLastError() }.0 == ERROR_ALREADY_EXISTS.0 {
+        if wait_for_previous {
+            // SAFETY: `mutex_handle` is the valid handle returned by `CreateMutexW`.
+            let wait = unsafe {
+                WaitForSingleObject(mutex_handle, ELEVATED_HANDOFF_TIMEOUT.as_millis() as u32)
+            };
+            // 旧实例退出时未释放互斥量会得到 WAIT_ABANDONED，同样视为已接管
+            if wait == WAIT_OBJECT_0 || wait == WAIT_ABANDONED {
+                info!("took over single instance mutex from previous unelevated instance");
+                crate::utils::single_instance::set_mutex_handle(mutex_handle);
+                return Some(true);
+            }
+            error!(
+                ?wait,
+                "previous instance did not release single instance mutex"
+            );
+        }
         // SAFETY: `mutex_handle` is the valid handle returned by `CreateMutexW`.
         let _ = unsafe { CloseHandle(mutex_handle) };
         bring_main_window_to_foreground();
@@ -93,7 +114,8 @@ fn check_single_instance() -> Option<bool> {
 #[cfg(windows)]
 fn single_instance_guard(launch_mode: &LaunchMode) -> Option<SingleInstanceGuard> {
     if matches!(launch_mode, LaunchMode::Main) {
-        match check_single_instance() {
+        let wait_for_previous = crate::launch::resume_elevated_token().is_some();
+        match check_single_instance(wait_for_previous) {
             Some(true) => Some(SingleInstanceGuard),
             Some(false) => {
                 info!(
@@ -139,7 +161,7 @@ async fn async_main(startup_started: Instant) -> Result<()> {
     }
 
     crate::utils::file_ops::create_initial_directories();
-    let config = match crate::config::config::initialize_config_cache() {
+    let mut config = match crate::config::config::initialize_config_cache() {
         Ok(config) => config,
         Err(error) => {
             let message = format!("读取配置失败: {error:?}\n程序将退出。");
@@ -153,6 +175,13 @@ async fn async_main(startup_started: Instant) -> Result<()> {
         }
     };
     crate::utils::logger::init_logging(config.launcher.debug);
+    info!(
+        data_root = %crate::utils::file_ops::bmcbl_dir().display(),
+        working_dir = %env::current_dir()
+            .map(|dir| dir.display().to_string())
+            .unwrap_or_default(),
+        "resolved data root"
+    );
     debug!(
         elapsed_ms = startup_started.elapsed().as_millis(),
         debug_enabled = config.launcher.debug,
@@ -161,6 +190,35 @@ async fn async_main(startup_started: Instant) -> Result<()> {
         "configuration loaded and debug logging state applied"
     );
 
+    if launch_mode.is_main() && crate::launch::reset_renderer_requested() {
+        match crate::utils::render_recovery::reset_renderer_settings() {
+            Ok(updated) => {
+                info!("renderer settings reset by --reset-renderer");
+                config = updated;
+            }
+            Err(error) => error!(?error, "failed to reset renderer settings"),
+        }
+    }
+
+    if launch_mode.is_main()
+        && let Err(message) = crate::utils::data_root::ensure_data_root_writable()
+    {
+        crate::result::show_application_error("数据目录不可写", "data_root_probe", message);
+    }
+
+    // 覆盖文件有误时在启动阶段就报告，而不是等到第一次启动游戏
+    let edition_errors = crate::core::minecraft::editions::load_edition_registry();
+    if !edition_errors.is_empty() {
+        crate::result::report_application_error(
+            "load_edition_registry",
+            format!(
+                "editions.json 中的无效项已被忽略:\n{}",
+                edition_errors.join("\n")
+            ),
+            crate::utils::diagnostics::DiagnosticsSeverity::Warning,
+        );
+    }
+
     if let LaunchMode::DirectLaunch(ref direct_ctx) = launch_mode {
         let version_config =
             crate::core::version::settings::get_version_config(direct_ctx.version_folder.clone())
@@ -178,11 +236,23 @@ async fn async_main(startup_started: Instant) -> Result<()> {
         }
     }
 
+    #[cfg(windows)]
+    if matches!(launch_mode, LaunchMode::Main)
+        && let Some(token) = crate::launch::resume_elevated_token()
+    {
+        tokio::spawn(crate::utils::elevation::resume_after_elevation(token));
+    }
+
     if launch_mode.is_main() && config.launcher.stats_upload {
         crate::utils::stats::spawn_startup_ingest();
     }
 
     if launch_mode.is_main() {
+        crate::downloads::scheduled::start_scheduler();
+        tokio::spawn(async {
+            let status = crate::core::online::refresh_online_policy().await;
+            debug!("联机策略来源：{:?}", status.source);
+        });
         spawn_noncritical_startup_work();
     } else {
         info!("Import-mode preinit done");
@@ -209,6 +279,12 @@ fn spawn_noncritical_startup_work() {
                 error!(?error, "failed to mark diagnostics session as started");
             }
             crate::utils::updater_child::clean_old_versions();
+            for report in crate::core::minecraft::pack_reverts::resume_pending_reverts() {
+                if let Some(conflict) = report.conflict {
+                    error!(world = %report.world_path, %conflict, "pending pack revert conflicted");
+                }
+            }
+            crate::core::minecraft::usage_stats::resume_usage_sessions();
             #[cfg(target_os = "windows")]
             crate::utils::registry::register_file_associations();
             log_system_info();
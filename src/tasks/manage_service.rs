@@ -4,7 +4,7 @@ use std::path::PathBuf;
 use serde::Deserialize;
 use tracing::warn;
 
-use crate::core::minecraft::map::McMapInfo;
+use crate::core::minecraft::map::WorldListing;
 use crate::core::minecraft::paths::{GamePathOptions, get_game_root};
 use crate::core::minecraft::resource_packs::McPackInfo;
 use crate::core::minecraft::screenshots::McScreenshotInfo;
@@ -85,6 +85,9 @@ pub async fn load_gdk_users(options: GamePathOptions) -> Result<Vec<String>, Str
 }
 
 pub async fn load_mods(version_folder: String) -> Result<Vec<ManagedModInfo>, String> {
+    let _guard =
+        crate::core::minecraft::mods_config::lock_mods_dir(&version_mods_dir(&version_folder))
+            .await;
     run_blocking(BlockingTaskOptions::hidden("读取 Mod"), move || {
         load_mods_blocking(&version_folder)
     })
@@ -118,9 +121,9 @@ pub async fn load_skin_packs(
     .await
 }
 
-pub async fn load_maps(options: GamePathOptions) -> Result<Vec<McMapInfo>, String> {
+pub async fn load_maps(options: GamePathOptions) -> Result<WorldListing, String> {
     run_blocking(BlockingTaskOptions::hidden("读取地图"), move || {
-        crate::core::minecraft::map::list_worlds_standard(&options)
+        crate::core::minecraft::map::list_minecraft_worlds(&options)
             .map_err(|error| format!("读取地图失败: {error:?}"))
     })
     .await
@@ -144,10 +147,14 @@ pub async fn load_external_servers(
     .await
 }
 
-fn load_mods_blocking(version_folder: &str) -> Result<Vec<ManagedModInfo>, String> {
-    let mods_dir = crate::utils::file_ops::bmcbl_subdir("versions")
+fn version_mods_dir(version_folder: &str) -> PathBuf {
+    crate::utils::file_ops::bmcbl_subdir("versions")
         .join(version_folder)
-        .join("mods");
+        .join("mods")
+}
+
+fn load_mods_blocking(version_folder: &str) -> Result<Vec<ManagedModInfo>, String> {
+    let mods_dir = version_mods_dir(version_folder);
     if !mods_dir.exists() {
         fs::create_dir_all(&mods_dir).map_err(|error| format!("创建 mods 目录失败: {error}"))?;
         return Ok(Vec::new());
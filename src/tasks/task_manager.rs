@@ -8,7 +8,7 @@ use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 use tokio::sync::{Notify, broadcast};
 use tokio::task::AbortHandle;
-use tracing::debug;
+use tracing::{debug, warn};
 
 const TASK_EMIT_INTERVAL_MS: u128 = 250;
 const TASK_PROGRESS_MIN_UPDATE_INTERVAL: f64 = 0.25;
@@ -16,6 +16,8 @@ const TASK_PROGRESS_IDLE_RESET_SECONDS: f64 = 2.0;
 const TASK_PROGRESS_EMA_ALPHA: f64 = 0.2;
 const TASK_VISUALIZATION_ENABLED: bool = true;
 const TASK_LOG_LIMIT: usize = 128;
+const CANCEL_ALL_ACK_TIMEOUT: Duration = Duration::from_secs(5);
+const CANCEL_ALL_POLL_INTERVAL: Duration = Duration::from_millis(50);
 type TaskCancelHook = Box<dyn Fn() + Send + Sync + 'static>;
 
 static TASK_COUNTER: AtomicU64 = AtomicU64::new(1);
@@ -81,6 +83,8 @@ impl TaskVisualization {
 pub struct TaskControl {
     cancelled: AtomicBool,
     paused: AtomicBool,
+    /// 工作线程已观察到取消标记或已经结束
+    acknowledged: AtomicBool,
     notify: Notify,
 }
 
@@ -89,10 +93,15 @@ impl TaskControl {
         Self {
             cancelled: AtomicBool::new(false),
             paused: AtomicBool::new(false),
+            acknowledged: AtomicBool::new(false),
             notify: Notify::new(),
         }
     }
 
+    fn acknowledge(&self) {
+        self.acknowledged.store(true, Ordering::Relaxed);
+    }
+
     fn cancel(&self) {
         self.cancelled.store(true, Ordering::Relaxed);
         self.paused.store(false, Ordering::Relaxed);
@@ -109,7 +118,11 @@ impl TaskControl {
     }
 
     pub fn cancelled_requested(&self) -> bool {
-        self.cancelled.load(Ordering::Relaxed)
+        let cancelled = self.cancelled.load(Ordering::Relaxed);
+        if cancelled {
+            self.acknowledge();
+        }
+        cancelled
     }
 
     pub fn paused_requested(&self) -> bool {
@@ -140,6 +153,27 @@ pub struct TaskSnapshot {
     pub sequence: u64,
     #[serde(default)]
     pub visibility: TaskVisibility,
+    /// 任务在计费网络下由用户发起，界面据此提示流量消耗
+    #[serde(default)]
+    pub metered: bool,
+}
+
+#[derive(Clone, Copy, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskCancelOutcome {
+    Cancelled,
+    AlreadyDone,
+    /// 超时仍未响应取消，日志中会记录该任务以便排查
+    Timeout,
+}
+
+#[derive(Clone, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskCancelReport {
+    pub task_id: String,
+    pub title: String,
+    pub stage: String,
+    pub outcome: TaskCancelOutcome,
 }
 
 #[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
@@ -177,6 +211,7 @@ struct Task {
     last_emit_instant: Instant,
     sequence: u64,
     visibility: TaskVisibility,
+    metered: bool,
 }
 
 impl Task {
@@ -210,6 +245,7 @@ impl Task {
             last_emit_instant: now,
             sequence: 0,
             visibility,
+            metered: false,
         }
     }
 
@@ -259,6 +295,7 @@ impl Task {
             last_update_unix: unix_now_seconds(),
             sequence: self.sequence,
             visibility: self.visibility,
+            metered: self.metered,
         }
     }
 
@@ -345,6 +382,9 @@ pub fn abort_task(task_id: &str) -> bool {
     let abort_handle = TASK_ABORT_HANDLES.lock().unwrap().remove(task_id);
     if let Some(abort_handle) = abort_handle {
         abort_handle.abort();
+        if let Some(control) = task_control(task_id) {
+            control.acknowledge();
+        }
         true
     } else {
         false
@@ -464,6 +504,28 @@ pub fn set_task_labels(task_id: &str, title: impl Into<String>, detail: Option<S
     changed
 }
 
+pub fn set_task_metered(task_id: &str, metered: bool) -> bool {
+    let mut snapshot_to_emit: Option<TaskSnapshot> = None;
+    let mut changed = false;
+    {
+        let mut map = TASKS.lock().unwrap();
+        if let Some(task) = map.get_mut(task_id)
+            && task.metered != metered
+        {
+            task.metered = metered;
+            task.touch();
+            snapshot_to_emit = Some(task.snapshot());
+            changed = true;
+        }
+    }
+
+    if let Some(snapshot) = snapshot_to_emit {
+        emit_task_update(snapshot);
+    }
+
+    changed
+}
+
 pub fn set_task_message(task_id: &str, message: Option<String>) -> bool {
     let mut snapshot_to_emit: Option<TaskSnapshot> = None;
     let mut changed = false;
@@ -498,6 +560,11 @@ pub fn append_task_log(task_id: &str, line: impl Into<String>) -> bool {
     true
 }
 
+/// 所有任务保留的日志行总数（启动任务的注入日志也记录在这里）
+pub fn task_log_line_count() -> usize {
+    TASK_LOGS.lock().unwrap().values().map(VecDeque::len).sum()
+}
+
 pub fn task_logs(task_id: &str) -> Arc<[Arc<str>]> {
     let logs = TASK_LOGS.lock().unwrap();
     logs.get(task_id)
@@ -662,6 +729,9 @@ pub fn maybe_set_task_visualization(
 }
 
 pub fn finish_task(task_id: &str, status: &str, message: Option<String>) {
+    if let Some(control) = task_control(task_id) {
+        control.acknowledge();
+    }
     let mut snapshot_to_emit: Option<TaskSnapshot> = None;
     {
         let mut map = TASKS.lock().unwrap();
@@ -758,10 +828,96 @@ pub fn cancel_task(task_id: &str) {
 
 pub fn is_cancelled(task_id: &str) -> bool {
     task_control(task_id)
-        .map(|control| control.cancelled.load(Ordering::Relaxed))
+        .map(|control| control.cancelled_requested())
         .unwrap_or(false)
 }
 
+/// 已结束、已被移除或已观察到取消的任务视为已响应
+fn cancel_acknowledged(task_id: &str) -> bool {
+    task_control(task_id)
+        .map(|control| control.acknowledged.load(Ordering::Relaxed))
+        .unwrap_or(true)
+}
+
+/// 一键停止：按 `cancel_task` 的路径取消所有进行中的任务，并等待工作线程响应。
+/// `kinds` 按任务阶段键（如 `downloading`、`extracting`）过滤，为空时取消全部
+pub async fn cancel_all_tasks(kinds: Option<Vec<String>>) -> Result<Vec<TaskCancelReport>, String> {
+    Ok(cancel_matching_tasks(kinds.as_deref(), CANCEL_ALL_ACK_TIMEOUT).await)
+}
+
+async fn cancel_matching_tasks(
+    kinds: Option<&[String]>,
+    timeout: Duration,
+) -> Vec<TaskCancelReport> {
+    let targets: Vec<(TaskCancelReport, bool)> = {
+        let map = TASKS.lock().unwrap();
+        map.values()
+            .filter(|task| {
+                kinds.is_none_or(|kinds| {
+                    kinds.is_empty()
+                        || kinds
+                            .iter()
+                            .any(|kind| kind.eq_ignore_ascii_case(task.stage.as_ref()))
+                })
+            })
+            .map(|task| {
+                (
+                    TaskCancelReport {
+                        task_id: task.id.to_string(),
+                        title: task.title.to_string(),
+                        stage: task.stage.to_string(),
+                        outcome: TaskCancelOutcome::AlreadyDone,
+                    },
+                    is_terminal_status(task.status.as_ref()),
+                )
+            })
+            .collect()
+    };
+
+    let mut reports = Vec::with_capacity(targets.len());
+    let mut pending = Vec::new();
+    for (report, done) in targets {
+        if done {
+            reports.push(report);
+        } else {
+            cancel_task(&report.task_id);
+            pending.push(report);
+        }
+    }
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        let (acknowledged, waiting): (Vec<_>, Vec<_>) = pending
+            .into_iter()
+            .partition(|report| cancel_acknowledged(&report.task_id));
+        reports.extend(acknowledged.into_iter().map(|report| TaskCancelReport {
+            outcome: TaskCancelOutcome::Cancelled,
+            ..report
+        }));
+        pending = waiting;
+        if pending.is_empty() || Instant::now() >= deadline {
+            break;
+        }
+        tokio::time::sleep(CANCEL_ALL_POLL_INTERVAL).await;
+    }
+
+    for report in pending {
+        warn!(
+            task_id = %report.task_id,
+            title = %report.title,
+            stage = %report.stage,
+            timeout_ms = timeout.as_millis() as u64,
+            "task_manager: task ignored cancellation past timeout, check its cancel checkpoints"
+        );
+        reports.push(TaskCancelReport {
+            outcome: TaskCancelOutcome::Timeout,
+            ..report
+        });
+    }
+    reports.sort_by(|left, right| left.task_id.cmp(&right.task_id));
+    reports
+}
+
 pub fn pause_task(task_id: &str) -> bool {
     let mut snapshot_to_emit: Option<TaskSnapshot> = None;
     let mut changed = false;
@@ -840,7 +996,7 @@ pub async fn wait_until_active(task_id: &str) -> bool {
 
 pub async fn wait_until_active_fast(control: &TaskControl) -> bool {
     loop {
-        if control.cancelled.load(Ordering::Relaxed) {
+        if control.cancelled_requested() {
             return false;
         }
         if !control.paused.load(Ordering::Relaxed) {
@@ -852,7 +1008,7 @@ pub async fn wait_until_active_fast(control: &TaskControl) -> bool {
 }
 
 pub fn is_cancelled_fast(control: &TaskControl) -> bool {
-    control.cancelled.load(Ordering::Relaxed)
+    control.cancelled_requested()
 }
 
 pub fn get_snapshot(task_id: &str) -> Option<TaskSnapshot> {
@@ -1176,4 +1332,53 @@ mod tests {
         );
         assert!(remove_task(&task_id));
     }
+
+    #[tokio::test]
+    async fn cancel_all_reports_per_task_outcome() {
+        let stage = format!(
+            "task_manager_cancel_all_test_{}",
+            TASK_COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+        let create = |label: &str| {
+            create_task_with_details(
+                Some(format!("{stage}-{label}")),
+                label,
+                None,
+                &stage,
+                None,
+                false,
+            )
+        };
+        let responsive = create("a-responsive");
+        let stuck = create("b-stuck");
+        let done = create("c-done");
+        finish_task(&done, "completed", None);
+
+        let worker_task_id = responsive.clone();
+        let worker = std::thread::spawn(move || {
+            while !is_cancelled(&worker_task_id) {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+        });
+
+        let reports =
+            cancel_matching_tasks(Some(&[stage.clone()]), Duration::from_millis(500)).await;
+        worker.join().expect("worker thread");
+
+        let outcomes: Vec<_> = reports
+            .iter()
+            .map(|report| (report.task_id.as_str(), report.outcome))
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                (responsive.as_str(), TaskCancelOutcome::Cancelled),
+                (stuck.as_str(), TaskCancelOutcome::Timeout),
+                (done.as_str(), TaskCancelOutcome::AlreadyDone),
+            ]
+        );
+        for task_id in [responsive, stuck, done] {
+            assert!(remove_task(&task_id));
+        }
+    }
 }
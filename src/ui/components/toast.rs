@@ -2,9 +2,11 @@ use crate::ui::animation::{
     ease_in_cubic, ease_out_back, ease_out_cubic, request_animation_frame_if,
 };
 use crate::ui::theme::colors::ThemeColors;
+use gpui::prelude::FluentBuilder as _;
 use gpui::*;
 use lucide_gpui::icons as lucide_icons;
 use std::collections::VecDeque;
+use std::rc::Rc;
 use std::time::{Duration, Instant};
 
 const MAX_TOASTS: usize = 4;
@@ -164,6 +166,13 @@ impl Default for ToastOverlayOptions {
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
 pub struct ToastId(u64);
 
+/// Toast 右侧的操作按钮，点击后执行回调并关闭 Toast
+#[derive(Clone)]
+pub struct ToastAction {
+    pub label: SharedString,
+    pub handler: Rc<dyn Fn(&mut App)>,
+}
+
 #[derive(Clone)]
 struct ToastItem {
     id: ToastId,
@@ -174,6 +183,7 @@ struct ToastItem {
     anim_delay: Duration,
     duration: Duration,
     dismissed_at: Option<Instant>,
+    action: Option<ToastAction>,
 }
 
 pub struct ToastState {
@@ -210,6 +220,7 @@ impl ToastState {
         kind: ToastKind,
         message: SharedString,
         duration: Duration,
+        action: Option<ToastAction>,
         now: Instant,
     ) -> ToastId {
         if message.as_ref().trim().is_empty() {
@@ -230,6 +241,7 @@ impl ToastState {
             anim_delay,
             duration,
             dismissed_at: None,
+            action,
         });
 
         // If we exceed the visible limit, start an exit animation for the oldest toast instead of
@@ -294,6 +306,7 @@ impl ToastState {
                 anim_delay: Duration::ZERO,
                 duration: DEFAULT_DURATION,
                 dismissed_at: None,
+                action: None,
             });
             id
         };
@@ -456,7 +469,23 @@ pub fn push_kind_duration(
     let target_window = target_window(cx);
     cx.update_global(|state: &mut ToastState, cx| {
         state.prune_expired(now);
-        let id = state.push(target_window, kind, message, duration, now);
+        let id = state.push(target_window, kind, message, duration, None, now);
+        id
+    })
+}
+
+pub fn push_with_action(
+    cx: &mut App,
+    kind: ToastKind,
+    message: SharedString,
+    duration: Duration,
+    action: ToastAction,
+) -> ToastId {
+    let now = Instant::now();
+    let target_window = target_window(cx);
+    cx.update_global(|state: &mut ToastState, cx| {
+        state.prune_expired(now);
+        let id = state.push(target_window, kind, message, duration, Some(action), now);
         id
     })
 }
@@ -510,7 +539,7 @@ pub fn push_async(cx: &mut AsyncApp, kind: ToastKind, message: SharedString) ->
         .flatten();
     match cx.update_global(|state: &mut ToastState, cx| {
         state.prune_expired(now);
-        let id = state.push(target_window, kind, message, DEFAULT_DURATION, now);
+        let id = state.push(target_window, kind, message, DEFAULT_DURATION, None, now);
         id
     }) {
         Ok(id) => id,
@@ -534,6 +563,7 @@ pub fn pending_async(cx: &mut AsyncApp, message: SharedString) -> ToastId {
             ToastKind::Info,
             message,
             Duration::from_secs(60),
+            None,
             now,
         );
         id
@@ -670,7 +700,11 @@ pub fn render_overlay_with_options(
         let exit_slide = ease_in_cubic(disappear_t) * EXIT_SLIDE_PX;
 
         let toast_id = item.id;
-        let toast_width = toast_width_for_message(item.message.as_ref());
+        let toast_width = if item.action.is_some() {
+            px(TOAST_MAX_WIDTH_PX)
+        } else {
+            toast_width_for_message(item.message.as_ref())
+        };
         let shell = toast_shell(colors, item);
 
         layout_items.push((
@@ -867,6 +901,7 @@ mod tests {
             ToastKind::Info,
             SharedString::from("ready"),
             DEFAULT_DURATION,
+            None,
             now,
         );
         let steady = now + FADE_IN + Duration::from_millis(1);
@@ -887,6 +922,7 @@ mod tests {
             ToastKind::Info,
             SharedString::from("done"),
             Duration::ZERO,
+            None,
             now,
         );
         state.prune_expired(now + FADE_OUT + Duration::from_millis(1));
@@ -905,6 +941,7 @@ mod tests {
             ToastKind::Info,
             SharedString::from("targeted"),
             DEFAULT_DURATION,
+            None,
             now,
         );
 
@@ -972,6 +1009,27 @@ fn toast_shell(colors: &ThemeColors, item: &ToastItem) -> impl IntoElement {
                         .truncate()
                         .text_color(colors.text_primary)
                         .child(item.message.clone()),
-                ),
+                )
+                .when_some(item.action.clone(), |this, action| {
+                    let handler = action.handler.clone();
+                    let toast_id = item.id;
+                    this.child(
+                        div()
+                            .id(SharedString::from(format!("toast-action-{}", item.id.0)))
+                            .flex_none()
+                            .px(px(8.))
+                            .py(px(2.))
+                            .rounded(px(6.))
+                            .text_size(px(12.))
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(accent)
+                            .hover(|this| this.bg(Hsla { a: 0.12, ..accent }))
+                            .child(action.label)
+                            .on_mouse_down(MouseButton::Left, move |_ev, _window, cx| {
+                                handler(cx);
+                                dismiss(cx, toast_id);
+                            }),
+                    )
+                }),
         )
 }
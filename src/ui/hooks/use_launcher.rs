@@ -24,6 +24,7 @@ use crate::ui::state::launch_prereq::{
 };
 use crate::ui::state::launcher::LauncherState;
 use crate::utils::developer_mode::{self, DeveloperModeError};
+use crate::utils::elevation;
 use crate::utils::mc_dependency::{self, DependencyEvent};
 
 const DEPENDENCY_EVENT_BATCH_DELAY: Duration = Duration::from_millis(50);
@@ -514,8 +515,9 @@ pub fn enable_launch_prereq_developer_mode(cx: &mut App) {
                 warn!(
                     request_id = context.request_id,
                     version_name = %context.version.name,
-                    "启用开发者模式需要管理员权限，改为引导用户手动开启"
+                    "启用开发者模式需要管理员权限，询问用户是否提权重试"
                 );
+                offer_elevated_relaunch(elevation::ResumeAction::EnableDeveloperMode).await;
                 let admin_notice = async_i18n_text(cx, "LaunchPrereq.adminRunRequired");
                 let manual_toast =
                     async_i18n_text(cx, "LaunchPrereq.issueDeveloperMode.manualToast");
@@ -1366,6 +1368,23 @@ fn format_uwp_dependency_issue_label(
     format!("{} ({reason})", dependency.name)
 }
 
+/// 用户确认后以管理员身份重启并继续 `action`；返回说明用户拒绝或重启失败，调用方按未提权继续
+async fn offer_elevated_relaunch(action: elevation::ResumeAction) {
+    let result = tokio::task::spawn_blocking(move || {
+        if !elevation::confirm_elevation(action.operation()) {
+            info!(operation = ?action.operation(), "用户拒绝以管理员身份重启");
+            return Ok(());
+        }
+        elevation::relaunch_elevated(action)
+    })
+    .await;
+    match result {
+        Ok(Ok(())) => {}
+        Ok(Err(error)) => warn!("以管理员身份重启失败: {error}"),
+        Err(error) => warn!("以管理员身份重启任务失败: {error}"),
+    }
+}
+
 fn requires_admin_notice(message: &str) -> bool {
     let lower = message.to_ascii_lowercase();
     lower.contains("access denied")
@@ -1417,6 +1436,15 @@ fn spawn_launcher_snapshot_pump(task_id: Arc<str>, cx: &mut App) {
                 }
 
                 if terminal {
+                    if snapshot.status.as_ref() == "error"
+                        && let Some(resume) = snapshot
+                            .message
+                            .as_deref()
+                            .and_then(elevation::ElevationRequired::parse)
+                            .and_then(|required| required.resume)
+                    {
+                        offer_elevated_relaunch(resume).await;
+                    }
                     if snapshot.status.as_ref() == "completed" {
                         tokio::time::sleep(Duration::from_millis(900)).await;
                         let now = std::time::Instant::now();
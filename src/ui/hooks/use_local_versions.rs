@@ -287,6 +287,8 @@ pub fn ensure_local_versions_loaded(force_refresh: bool, cx: &mut App) {
         };
 
         cx.update(sync_manage_page_state_from_local_versions)?;
+        #[cfg(target_os = "windows")]
+        cx.update(start_package_watcher_if_needed)?;
         if refresh_again {
             cx.update(|cx| ensure_local_versions_loaded(true, cx))?;
         }
@@ -295,6 +297,42 @@ pub fn ensure_local_versions_loaded(force_refresh: bool, cx: &mut App) {
     .detach_and_log_err(cx);
 }
 
+/// 库中存在 UWP 版本时才启动系统包监听，包在启动器外变化后重新加载版本列表
+#[cfg(target_os = "windows")]
+fn start_package_watcher_if_needed(cx: &mut App) {
+    let has_uwp_version = cx.read_global(|state: &LocalVersionsState, _cx| {
+        state
+            .versions
+            .iter()
+            .any(|version| version_build_type(version) == BuildType::Uwp)
+    });
+    if !has_uwp_version || !crate::core::minecraft::package_watcher::start_package_watcher() {
+        return;
+    }
+
+    let mut events = crate::core::minecraft::package_watcher::subscribe_package_events();
+    cx.spawn(async move |cx| {
+        loop {
+            match events.recv().await {
+                Ok(event) => {
+                    info!(
+                        family_name = %event.family_name,
+                        change = ?event.change,
+                        "package changed, refreshing local versions"
+                    );
+                    cx.update(|cx| ensure_local_versions_loaded(true, cx))?;
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {
+                    cx.update(|cx| ensure_local_versions_loaded(true, cx))?;
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            }
+        }
+        Ok::<(), anyhow::Error>(())
+    })
+    .detach_and_log_err(cx);
+}
+
 pub fn version_build_type(version: &LaunchVersionEntry) -> BuildType {
     if version.kind.eq_ignore_ascii_case("gdk") {
         BuildType::Gdk
@@ -1648,6 +1648,10 @@ impl Render for MainWindowView {
                 render_started.elapsed(),
             );
         }
+        // render 返回时画面还没提交，等下一帧回调（上一帧已呈现）再报告就绪
+        if !crate::utils::render_recovery::is_frontend_ready() {
+            window.on_next_frame(|_, _| crate::utils::render_recovery::frontend_ready());
+        }
 
         root
     }
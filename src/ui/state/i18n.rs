@@ -38,6 +38,19 @@ impl I18n {
     pub fn t_args<const N: usize>(&self, key: &str, args: [I18nArg<'_>; N]) -> SharedString {
         shared_string_from_cow(self.translator.translate_args(key, args))
     }
+
+    /// 以 `(名称, 值)` 切片填充占位符，适合参数在运行时才确定的场景
+    #[inline]
+    pub fn t_with_vars(&self, key: &str, vars: &[(&str, &str)]) -> SharedString {
+        shared_string_from_cow(self.translator.translate_vars(key, vars))
+    }
+
+    /// 填充 `{{count}}` 占位符，用于带数量的文案
+    #[inline]
+    pub fn t_with_num(&self, key: &str, count: i64) -> SharedString {
+        let count = count.to_string();
+        self.t_with_vars(key, &[("count", &count)])
+    }
 }
 
 impl Default for I18n {
@@ -54,3 +67,47 @@ fn shared_string_from_cow(value: Cow<'static, str>) -> SharedString {
         Cow::Owned(value) => SharedString::from(value),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_with_vars_fills_placeholders_and_keeps_unknown_ones() {
+        let mut i18n = I18n::new();
+        i18n.set_locale(Locale::EnUs);
+
+        assert_eq!(
+            i18n.t_with_vars("AppxDeps.done.fail", &[("message", "disk full")])
+                .as_ref(),
+            "Install failed: disk full"
+        );
+        assert_eq!(
+            i18n.t_with_vars("AppxDeps.done.fail", &[("other", "x")])
+                .as_ref(),
+            "Install failed: {{message}}"
+        );
+        assert_eq!(
+            i18n.t_with_vars("AppxDeps.done.fail", &[]).as_ref(),
+            i18n.t("AppxDeps.done.fail").as_ref()
+        );
+    }
+
+    #[test]
+    fn t_with_num_fills_count_for_each_locale() {
+        let mut i18n = I18n::new();
+        assert_eq!(
+            i18n.t_with_num("AboutSection.dependencies.count", 3)
+                .as_ref(),
+            "共 3 项"
+        );
+
+        i18n.set_locale(Locale::EnUs);
+        assert_eq!(
+            i18n.t_with_num("AboutSection.dependencies.count", -1)
+                .as_ref(),
+            "-1 total"
+        );
+        assert_eq!(i18n.t_with_num("Missing.key", 1).as_ref(), "Missing.key");
+    }
+}
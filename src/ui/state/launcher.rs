@@ -231,6 +231,7 @@ mod tests {
             last_update_unix: 0,
             sequence: 0,
             visibility: crate::tasks::task_manager::TaskVisibility::Visible,
+            metered: false,
         })
     }
 }
@@ -63,10 +63,11 @@ impl UpdateCheckOutcome {
     }
 }
 
-pub(crate) fn check_for_updates_blocking() -> UpdateCheckOutcome {
+pub(crate) fn check_for_updates_blocking(manifest_only: bool) -> UpdateCheckOutcome {
     let started_at = Instant::now();
     tracing::info!(
         thread = ?std::thread::current().id(),
+        manifest_only,
         "update check started"
     );
 
@@ -74,6 +75,7 @@ pub(crate) fn check_for_updates_blocking() -> UpdateCheckOutcome {
         OWNER.to_string(),
         REPO.to_string(),
         None,
+        manifest_only,
     ) {
         Ok(value) => update_check_outcome_from_value(value),
         Err(error) => UpdateCheckOutcome::with_error(error),
@@ -111,7 +113,9 @@ pub(crate) fn spawn_update_check_thread(
             thread = ?std::thread::current().id(),
             "update check worker thread started"
         );
-        let outcome = check_for_updates_blocking();
+        // 计费网络下自动检查只拉取发布清单；用户手动检查保持完整流程
+        let manifest_only = reason != "manual" && crate::utils::network::should_save_bandwidth();
+        let outcome = check_for_updates_blocking(manifest_only);
         let sender = thread_sender
             .lock()
             .ok()
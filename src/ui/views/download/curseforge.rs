@@ -3413,7 +3413,7 @@ fn render_curseforge_install_modal(
                     .map_err(|e| e.to_string())?;
 
                     let preview =
-                        crate::core::minecraft::assets::inspect_import_file(path.to_string(), None)
+                        crate::core::minecraft::assets::inspect_import_file(path.to_string(), None, false)
                             .await?;
                     if !preview.valid {
                         let msg = preview
@@ -3474,6 +3474,7 @@ fn render_curseforge_install_modal(
                             file_paths: vec![path.to_string()],
                             overwrite,
                             allow_shared_fallback: false,
+                            task_id: None,
                         },
                     )
                     .await?;
@@ -218,6 +218,8 @@ fn ensure_results_loaded_impl(
             query.index = Some(index);
 
             let response = client.search_mods(query).await?;
+            // 计费网络下不加载结果图标，避免翻页时批量下载缩略图
+            let skip_logos = crate::utils::network::should_save_bandwidth();
             let mods = response
                 .data
                 .into_iter()
@@ -233,6 +235,7 @@ fn ensure_results_loaded_impl(
                             .collect(),
                         logo_url: mod_entry
                             .logo
+                            .filter(|_| !skip_logos)
                             .map(|logo| SharedString::from(logo.thumbnail_url.unwrap_or(logo.url))),
                         download_count: mod_entry.download_count,
                         date_modified: SharedString::from(mod_entry.date_modified),
@@ -1,4 +1,8 @@
 use super::*;
+#[cfg(target_os = "windows")]
+use crate::core::minecraft::appx::signature::get_package_signature_info;
+#[cfg(target_os = "linux")]
+use crate::core::minecraft::appx_signature::get_package_signature_info;
 
 impl ManagePageView {
     pub fn close_version_settings(&mut self, cx: &mut Context<Self>) {
@@ -128,13 +132,34 @@ impl ManagePageView {
         let Some(version) = self.selected_version(state).cloned() else {
             return;
         };
+        let package_folder = version.path.to_string();
         self.version_settings_modal = Some(version_settings::VersionSettingsModalState {
             version,
             config: state.version_config.clone(),
             icon_source_path: None,
             saving: false,
+            signature: None,
         });
         cx.notify();
+
+        cx.spawn(async move |handle, cx| {
+            let signature_folder = package_folder.clone();
+            let signature = crate::tasks::runtime::run_blocking(
+                crate::tasks::runtime::BlockingTaskOptions::hidden("读取包签名"),
+                move || get_package_signature_info(&signature_folder),
+            )
+            .await;
+            let _ = handle.update(cx, |this, cx| {
+                if let Some(modal) = this.version_settings_modal.as_mut()
+                    && modal.version.path.as_ref() == package_folder
+                {
+                    modal.signature = Some(signature);
+                    cx.notify();
+                }
+            });
+            Ok::<(), anyhow::Error>(())
+        })
+        .detach();
     }
 
     pub fn select_version_icon(&mut self, window: &mut Window, cx: &mut Context<Self>) {
@@ -231,6 +231,7 @@ impl ManagePageView {
                 pack_subtype: state.pack_subtype,
                 selected_gdk_user: state.selected_gdk_user.clone(),
                 folder_names,
+                planned_tokens: Vec::new(),
             },
         });
         cx.notify();
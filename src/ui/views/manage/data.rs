@@ -1,21 +1,18 @@
-use anyhow::Context as _;
 use gpui::SharedString;
 use serde::{Deserialize, Serialize};
-use std::fs::File;
-use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::fs;
-use tokio::io::AsyncWriteExt;
 use tracing::warn;
-use zip::write::SimpleFileOptions;
 
 use crate::core::minecraft::assets::{
     CheckImportRequest, DeleteAssetPayload, ImportAssetsRequest, ImportAssetsResult,
     check_import_conflict, delete_game_asset, import_assets, inspect_import_file,
+    plan_delete_game_asset,
 };
 use crate::core::minecraft::import::{ImportCheckResult, PackagePreview};
-use crate::core::minecraft::map::McMapInfo;
+use crate::core::minecraft::map::{McMapInfo, export_world_archive};
+use crate::core::minecraft::mods_config;
 pub use crate::core::minecraft::nbt::LevelDatDocument;
 use crate::core::minecraft::nbt::{
     read_level_dat_document as read_level_dat_file_document,
@@ -33,6 +30,7 @@ use crate::ui::views::manage::state::{
     ManageServerEntry, ManageServerMotd, ManageServerMotdStatus, ManageServerMotdTarget,
     ManageSkinPreviewEntry, ManageTab, ManageVersionConfig, ManagedVersionEntry,
 };
+use crate::utils::format_bytes::format_bytes;
 use futures_util::stream::{self, StreamExt as _};
 use std::time::{Duration, Instant};
 
@@ -131,6 +129,19 @@ pub async fn load_assets(
     }
 }
 
+pub enum DeleteAssetsOutcome {
+    Deleted,
+    /// 已生成删除计划，尚未删除任何内容；附带给用户确认的说明（文件数、大小、仍引用包的世界）
+    /// 以及确认后执行删除所需的令牌
+    Planned {
+        description: String,
+        tokens: Vec<String>,
+        referenced: bool,
+    },
+}
+
+/// `planned_tokens` 为空时只生成删除计划并返回 `Planned`，不删除任何内容；
+/// 用户确认后带着这些令牌再次调用才执行删除，仍被世界引用的包会同时清理这些世界中的引用
 pub async fn delete_assets(
     version: &ManagedVersionEntry,
     config: &ManageVersionConfig,
@@ -138,10 +149,20 @@ pub async fn delete_assets(
     pack_subtype: ManagePackSubtype,
     selected_gdk_user: Option<&str>,
     folder_names: &[String],
-) -> Result<(), String> {
+    planned_tokens: &[String],
+) -> Result<DeleteAssetsOutcome, String> {
     match tab {
-        ManageTab::Mod => delete_mods(version.folder.as_ref(), folder_names).await,
+        ManageTab::Mod => delete_mods(version.folder.as_ref(), folder_names)
+            .await
+            .map(|()| DeleteAssetsOutcome::Deleted),
         ManageTab::ResourcePack | ManageTab::SkinPack | ManageTab::Map => {
+            if !planned_tokens.is_empty() {
+                for token in planned_tokens {
+                    delete_game_asset(token.clone(), true, true).await?;
+                }
+                return Ok(DeleteAssetsOutcome::Deleted);
+            }
+
             let build_type = version.build_type();
             let edition = version.edition();
             let delete_type = match tab {
@@ -155,6 +176,7 @@ pub async fn delete_assets(
                 ManageTab::Screenshot | ManageTab::Server => unreachable!(),
             };
 
+            let mut plans = Vec::with_capacity(folder_names.len());
             for folder_name in folder_names {
                 let payload = DeleteAssetPayload {
                     build_type: build_type.clone(),
@@ -165,11 +187,42 @@ pub async fn delete_assets(
                     delete_type: delete_type.to_string(),
                     name: folder_name.clone(),
                 };
-                delete_game_asset(payload)?;
+                plans.push((folder_name, plan_delete_game_asset(payload).await?));
+            }
+
+            let item_count: u64 = plans.iter().map(|(_, plan)| plan.item_count).sum();
+            let total_bytes: u64 = plans.iter().map(|(_, plan)| plan.total_bytes).sum();
+            let mut description = format!(
+                "将删除 {} 个资源，共 {} 个文件（{}），删除后可在回收站中找回。",
+                plans.len(),
+                item_count,
+                format_bytes(total_bytes)
+            );
+            let referenced: Vec<String> = plans
+                .iter()
+                .filter(|(_, plan)| !plan.referencing_worlds.is_empty())
+                .map(|(folder_name, plan)| {
+                    let worlds: Vec<&str> = plan
+                        .referencing_worlds
+                        .iter()
+                        .map(|world| world.world_name.as_str())
+                        .collect();
+                    format!("{}：{}", folder_name, worlds.join("、"))
+                })
+                .collect();
+            if !referenced.is_empty() {
+                description.push_str(&format!(
+                    "\n以下包仍被世界使用，删除后这些世界会缺少对应的包：\n{}\n仍然删除会同时从这些世界中移除引用（原列表会备份）。",
+                    referenced.join("\n")
+                ));
             }
-            Ok(())
+            Ok(DeleteAssetsOutcome::Planned {
+                description,
+                tokens: plans.into_iter().map(|(_, plan)| plan.token).collect(),
+                referenced: !referenced.is_empty(),
+            })
         }
-        ManageTab::Screenshot | ManageTab::Server => Ok(()),
+        ManageTab::Screenshot | ManageTab::Server => Ok(DeleteAssetsOutcome::Deleted),
     }
 }
 
@@ -343,45 +396,7 @@ pub async fn set_mod_enabled(
     mod_id: &str,
     enabled: bool,
 ) -> Result<(), String> {
-    let mods_dir = version_mods_dir(version_folder);
-    let mod_dir = mods_dir.join(mod_id);
-    if !mod_dir.exists() {
-        return Err(format!("Mod 目录不存在: {mod_id}"));
-    }
-
-    let enabled_path = mod_dir.join("manifest.json");
-    let disabled_path = mod_dir.join(".manifest.json");
-
-    if enabled {
-        if enabled_path.exists() {
-            return Ok(());
-        }
-        if disabled_path.exists() {
-            fs::rename(disabled_path, enabled_path)
-                .await
-                .map_err(|error| format!("启用 Mod 失败: {error}"))?;
-            return Ok(());
-        }
-        return Err("未找到 .manifest.json，无法启用".to_string());
-    }
-
-    if disabled_path.exists() {
-        if enabled_path.exists() {
-            fs::remove_file(enabled_path)
-                .await
-                .map_err(|error| format!("清理冲突 manifest 失败: {error}"))?;
-        }
-        return Ok(());
-    }
-
-    if enabled_path.exists() {
-        fs::rename(enabled_path, disabled_path)
-            .await
-            .map_err(|error| format!("禁用 Mod 失败: {error}"))?;
-        return Ok(());
-    }
-
-    Err("未找到 manifest.json，无法禁用".to_string())
+    mods_config::set_mod_enabled(&version_mods_dir(version_folder), mod_id, enabled).await
 }
 
 pub async fn set_mod_type(
@@ -389,22 +404,12 @@ pub async fn set_mod_type(
     mod_id: &str,
     mod_type: &str,
 ) -> Result<(), String> {
-    let manifest_path = editable_manifest_path(version_folder, mod_id).await?;
-    let content = fs::read_to_string(&manifest_path)
-        .await
-        .map_err(|error| format!("读取 Manifest 失败: {error}"))?;
-    let mut manifest: ModManifest =
-        serde_json::from_str(&content).map_err(|error| format!("Manifest 解析失败: {error}"))?;
-    manifest.mod_type = mod_type.trim().to_string();
-    let formatted = serde_json::to_string_pretty(&manifest)
-        .map_err(|error| format!("Manifest 序列化失败: {error}"))?;
-    let mut file = fs::File::create(manifest_path)
-        .await
-        .map_err(|error| format!("写入 Manifest 失败: {error}"))?;
-    file.write_all(formatted.as_bytes())
-        .await
-        .map_err(|error| format!("写入 Manifest 失败: {error}"))?;
-    Ok(())
+    let mod_type = mod_type.trim().to_string();
+    mods_config::update_mod_manifest(&version_mods_dir(version_folder), mod_id, move |manifest| {
+        manifest.insert("type".to_string(), serde_json::json!(mod_type));
+    })
+    .await
+    .map(|_| ())
 }
 
 pub async fn set_mod_inject_delay(
@@ -412,22 +417,14 @@ pub async fn set_mod_inject_delay(
     mod_id: &str,
     inject_delay_ms: u64,
 ) -> Result<(), String> {
-    let manifest_path = editable_manifest_path(version_folder, mod_id).await?;
-    let content = fs::read_to_string(&manifest_path)
-        .await
-        .map_err(|error| format!("读取 Manifest 失败: {error}"))?;
-    let mut manifest: ModManifest =
-        serde_json::from_str(&content).map_err(|error| format!("Manifest 解析失败: {error}"))?;
-    manifest.inject_delay_ms = Some(inject_delay_ms);
-    let formatted = serde_json::to_string_pretty(&manifest)
-        .map_err(|error| format!("Manifest 序列化失败: {error}"))?;
-    let mut file = fs::File::create(manifest_path)
-        .await
-        .map_err(|error| format!("写入 Manifest 失败: {error}"))?;
-    file.write_all(formatted.as_bytes())
-        .await
-        .map_err(|error| format!("写入 Manifest 失败: {error}"))?;
-    Ok(())
+    mods_config::update_mod_manifest(&version_mods_dir(version_folder), mod_id, move |manifest| {
+        manifest.insert(
+            "inject_delay_ms".to_string(),
+            serde_json::json!(inject_delay_ms),
+        );
+    })
+    .await
+    .map(|_| ())
 }
 
 pub async fn set_vanilla_skin_pack_redirect(
@@ -483,6 +480,7 @@ pub async fn import_mod_files(version_folder: &str, paths: &[String]) -> Result<
     fs::create_dir_all(&mods_dir)
         .await
         .map_err(|error| format!("创建 mods 目录失败: {error}"))?;
+    let _guard = mods_config::lock_mods_dir(&mods_dir).await;
 
     for path in paths {
         let source_path = PathBuf::from(path);
@@ -556,6 +554,7 @@ pub async fn import_non_mod_files(
         file_paths,
         overwrite,
         allow_shared_fallback,
+        task_id: None,
     })
     .await
 }
@@ -564,7 +563,7 @@ pub async fn inspect_import_path(
     file_path: String,
     locale_code: Option<String>,
 ) -> Result<PackagePreview, String> {
-    inspect_import_file(file_path, locale_code).await
+    inspect_import_file(file_path, locale_code, false).await
 }
 
 pub async fn check_asset_import_conflict(
@@ -609,7 +608,7 @@ pub fn write_level_dat_document(
 pub fn export_map(folder_path: &str, target_path: &str) -> Result<(), String> {
     let source = PathBuf::from(folder_path);
     let target = PathBuf::from(target_path);
-    zip_directory(&source, &target).map_err(|error| error.to_string())
+    export_world_archive(&source, &target).map_err(|error| error.to_string())
 }
 
 pub fn backup_map(folder_path: &str, map_name: &str) -> Result<String, String> {
@@ -629,7 +628,7 @@ pub fn backup_map(folder_path: &str, map_name: &str) -> Result<String, String> {
         })
         .collect();
     let target_path = backup_dir.join(format!("{safe_name}_{timestamp}.mcworld"));
-    zip_directory(&source, &target_path).map_err(|error| error.to_string())?;
+    export_world_archive(&source, &target_path).map_err(|error| error.to_string())?;
     Ok(target_path.to_string_lossy().to_string())
 }
 
@@ -716,9 +715,19 @@ async fn load_map_assets(
         user_id: selected_gdk_user.map(ToString::to_string),
         allow_shared_fallback: false,
     };
-    let entries = manage_service::load_maps(options).await?;
+    let listing = manage_service::load_maps(options).await?;
+    // 数据目录被重定向到离线的网络位置等情况，直接说明原因而不是显示空列表
+    if listing.worlds.is_empty()
+        && let Some(reason) = listing.unavailable_reason()
+    {
+        return Err(reason.to_string());
+    }
 
-    Ok(entries.into_iter().map(manage_asset_from_map).collect())
+    Ok(listing
+        .worlds
+        .into_iter()
+        .map(manage_asset_from_map)
+        .collect())
 }
 
 fn manage_asset_from_mod(managed_mod: ManagedModInfo) -> ManageAssetEntry {
@@ -764,18 +773,6 @@ fn manage_asset_from_pack(pack: McPackInfo, pack_subtype: ManagePackSubtype) ->
         .manifest_parsed
         .as_ref()
         .and_then(|manifest| manifest.header.as_ref());
-    let display_name = manifest_header
-        .and_then(|header| header.name.clone())
-        .or_else(|| {
-            pack.manifest.get("header").and_then(|header| {
-                header
-                    .get("name")
-                    .and_then(serde_json::Value::as_str)
-                    .map(ToString::to_string)
-            })
-        })
-        .unwrap_or_else(|| pack.folder_name.clone());
-
     let detail = manifest_version_label(manifest_header);
     let description = pack
         .short_description
@@ -785,7 +782,7 @@ fn manage_asset_from_pack(pack: McPackInfo, pack_subtype: ManagePackSubtype) ->
     ManageAssetEntry {
         key: SharedString::from(format!("pack:{}", pack.folder_name)),
         folder_name: SharedString::from(pack.folder_name.clone()),
-        display_name: SharedString::from(display_name),
+        display_name: SharedString::from(pack.display_name),
         detail,
         description,
         file_path: SharedString::from(pack.folder_path.clone()),
@@ -995,6 +992,8 @@ fn external_server_options(
 
 async fn delete_mods(version_folder: &str, mod_ids: &[String]) -> Result<(), String> {
     let mods_dir = version_mods_dir(version_folder);
+    // 与切换启用状态、启动扫描互斥，避免扫描读到删除到一半的模组
+    let _guard = mods_config::lock_mods_dir(&mods_dir).await;
     for mod_id in mod_ids {
         let target_dir = mods_dir.join(mod_id);
         if !target_dir.exists() {
@@ -1007,25 +1006,8 @@ async fn delete_mods(version_folder: &str, mod_ids: &[String]) -> Result<(), Str
     Ok(())
 }
 
-async fn editable_manifest_path(version_folder: &str, mod_id: &str) -> Result<PathBuf, String> {
-    let mod_dir = version_mods_dir(version_folder).join(mod_id);
-    if !mod_dir.exists() {
-        return Err(format!("Mod 目录不存在: {mod_id}"));
-    }
-
-    let enabled_path = mod_dir.join("manifest.json");
-    if enabled_path.exists() {
-        return Ok(enabled_path);
-    }
-    let disabled_path = mod_dir.join(".manifest.json");
-    if disabled_path.exists() {
-        return Ok(disabled_path);
-    }
-    Err("未找到 manifest.json 或 .manifest.json".to_string())
-}
-
 fn version_mods_dir(version_folder: &str) -> PathBuf {
-    crate::utils::file_ops::bmcbl_subdir("versions")
+    crate::utils::file_ops::versions_dir()
         .join(version_folder)
         .join("mods")
 }
@@ -1069,39 +1051,6 @@ fn bytes_to_human(bytes: u64) -> String {
     }
 }
 
-fn zip_directory(source_dir: &Path, target_file: &Path) -> anyhow::Result<()> {
-    anyhow::ensure!(source_dir.exists(), "源目录不存在");
-
-    let file = File::create(target_file)
-        .with_context(|| format!("创建目标文件失败: {}", target_file.display()))?;
-    let mut zip = zip::ZipWriter::new(file);
-    let options = SimpleFileOptions::default()
-        .compression_method(zip::CompressionMethod::Deflated)
-        .unix_permissions(0o755);
-
-    let walker = walkdir::WalkDir::new(source_dir);
-    for entry in walker.into_iter().filter_map(Result::ok) {
-        let path = entry.path();
-        let relative_name = path
-            .strip_prefix(source_dir)?
-            .to_string_lossy()
-            .replace('\\', "/");
-
-        if path.is_file() {
-            zip.start_file(relative_name, options)?;
-            let mut source_file = File::open(path)?;
-            let mut buffer = Vec::new();
-            source_file.read_to_end(&mut buffer)?;
-            zip.write_all(&buffer)?;
-        } else if !relative_name.is_empty() {
-            zip.add_directory(relative_name, options)?;
-        }
-    }
-
-    zip.finish()?;
-    Ok(())
-}
-
 pub async fn rename_version_instance(old_name: &str, new_name: &str) -> Result<(), String> {
     let old_name = old_name.trim();
     let new_name = new_name.trim();
@@ -1,5 +1,51 @@
 use super::*;
+use crate::core::minecraft::recent_deletions::{self, DeletionRecord};
 use crate::ui::components::dialog;
+use std::time::Duration;
+
+/// 删除存档后撤销按钮的可见时长
+const DELETION_UNDO_WINDOW: Duration = Duration::from_secs(30);
+
+pub(super) fn show_world_deletion_toast(record: DeletionRecord, cx: &mut App) {
+    let message = SharedString::from(format!("已删除存档「{}」", record.display_name));
+    if !record.can_undo() {
+        toast::success(cx, message);
+        return;
+    }
+
+    let record_id = record.id;
+    toast::push_with_action(
+        cx,
+        toast::ToastKind::Success,
+        message,
+        DELETION_UNDO_WINDOW,
+        toast::ToastAction {
+            label: SharedString::from("撤销"),
+            handler: Rc::new(move |cx| undo_world_deletion(record_id.clone(), cx)),
+        },
+    );
+}
+
+fn undo_world_deletion(id: String, cx: &mut App) {
+    cx.spawn(async move |cx| {
+        let result = crate::tasks::runtime::run_blocking(
+            crate::tasks::runtime::BlockingTaskOptions::hidden("撤销删除存档"),
+            move || recent_deletions::undo_deletion(&id),
+        )
+        .await;
+        cx.update(|cx| match result {
+            Ok(_) => {
+                toast::success(cx, SharedString::from("存档已还原"));
+                cx.update_global(|state: &mut ManagePageState, _cx| {
+                    state.assets_loaded = false;
+                });
+            }
+            Err(error) => toast::error(cx, SharedString::from(error)),
+        })?;
+        Ok::<(), anyhow::Error>(())
+    })
+    .detach();
+}
 
 #[derive(Clone)]
 pub(super) enum ConfirmAction {
@@ -13,6 +59,8 @@ pub(super) enum ConfirmAction {
         pack_subtype: ManagePackSubtype,
         selected_gdk_user: Option<SharedString>,
         folder_names: Vec<String>,
+        /// 删除计划的令牌；为空时确认只生成计划并展示文件数与大小，再次确认才删除
+        planned_tokens: Vec<String>,
     },
     DeleteScreenshot {
         entry: ManageScreenshotEntry,
@@ -119,6 +167,7 @@ impl ManagePageView {
                 pack_subtype,
                 selected_gdk_user,
                 folder_names,
+                planned_tokens,
             } => {
                 cx.spawn(async move |handle, cx| {
                     let result = data::delete_assets(
@@ -128,12 +177,36 @@ impl ManagePageView {
                         pack_subtype,
                         selected_gdk_user.as_ref().map(SharedString::as_ref),
                         &folder_names,
+                        &planned_tokens,
                     )
                     .await;
                     let _ = handle.update(cx, |this, cx| {
                         match result {
-                            Ok(()) => {
-                                toast::success(cx, SharedString::from("资源已删除"));
+                            Ok(data::DeleteAssetsOutcome::Planned {
+                                description,
+                                tokens,
+                                referenced,
+                            }) => {
+                                if let Some(dialog) = this.confirm_dialog.as_mut() {
+                                    dialog.description = SharedString::from(description);
+                                    dialog.confirm_label = SharedString::from(if referenced {
+                                        "仍然删除"
+                                    } else {
+                                        "确认删除"
+                                    });
+                                    dialog.pending = false;
+                                    if let ConfirmAction::DeleteAssets { planned_tokens, .. } =
+                                        &mut dialog.action
+                                    {
+                                        *planned_tokens = tokens;
+                                    }
+                                }
+                            }
+                            Ok(data::DeleteAssetsOutcome::Deleted) => {
+                                // 存档删除由 deletion-performed 事件弹出带撤销按钮的提示
+                                if tab != ManageTab::Map {
+                                    toast::success(cx, SharedString::from("资源已删除"));
+                                }
                                 this.confirm_dialog = None;
                                 cx.update_global(|state: &mut ManagePageState, _cx| {
                                     state.selected_asset_keys.clear();
@@ -1,4 +1,8 @@
 use super::ManagePageView;
+#[cfg(target_os = "windows")]
+use crate::core::minecraft::appx::signature::PackageSignatureInfo;
+#[cfg(target_os = "linux")]
+use crate::core::minecraft::appx_signature::PackageSignatureInfo;
 use crate::ui::components::modal;
 use crate::ui::components::scroll::ScrollableElement as _;
 use crate::ui::components::toggle_switch::ToggleSwitch;
@@ -22,6 +26,7 @@ pub struct VersionSettingsModalState {
     pub config: ManageVersionConfig,
     pub icon_source_path: Option<SharedString>,
     pub saving: bool,
+    pub signature: Option<Result<PackageSignatureInfo, String>>,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -151,7 +156,8 @@ pub fn render(
                                     VersionSettingsToggle::EditorMode,
                                     view_handle.clone(),
                                 ))
-                            }),
+                            })
+                            .child(render_signature_card(state, colors, i18n)),
                     ),
             )
             .child(
@@ -228,6 +234,98 @@ fn render_header(
         )
 }
 
+fn render_signature_card(
+    state: &VersionSettingsModalState,
+    colors: &ThemeColors,
+    i18n: &I18n,
+) -> Div {
+    let detail_row = |label: SharedString, value: String| {
+        div()
+            .w_full()
+            .flex()
+            .gap(px(10.))
+            .child(
+                div()
+                    .w(px(84.))
+                    .flex_none()
+                    .text_size(px(12.))
+                    .text_color(colors.text_muted)
+                    .child(label),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .min_w(px(0.))
+                    .text_size(px(12.))
+                    .line_height(relative(1.45))
+                    .text_color(colors.text_secondary)
+                    .child(value),
+            )
+    };
+
+    let body = match state.signature.as_ref() {
+        None => div()
+            .text_size(px(12.))
+            .text_color(colors.text_secondary)
+            .child(i18n.t("VersionSettingsModal.signature_loading")),
+        Some(Err(error)) => div()
+            .text_size(px(12.))
+            .text_color(colors.text_secondary)
+            .child(i18n.t_args(
+                "VersionSettingsModal.signature_failed",
+                crate::i18n_args![("message", error)],
+            )),
+        Some(Ok(info)) if !info.is_signed => div()
+            .text_size(px(12.))
+            .text_color(colors.text_secondary)
+            .child(i18n.t("VersionSettingsModal.signature_unsigned")),
+        Some(Ok(info)) => div()
+            .w_full()
+            .flex()
+            .flex_col()
+            .gap(px(6.))
+            .child(
+                div()
+                    .text_size(px(12.))
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(colors.text_secondary)
+                    .child(if info.claims_microsoft_subject {
+                        i18n.t("VersionSettingsModal.signature_claims_microsoft")
+                    } else {
+                        i18n.t("VersionSettingsModal.signature_third_party")
+                    }),
+            )
+            .child(detail_row(
+                i18n.t("VersionSettingsModal.signature_subject"),
+                info.subject.clone(),
+            ))
+            .child(detail_row(
+                i18n.t("VersionSettingsModal.signature_issuer"),
+                info.issuer.clone(),
+            ))
+            .child(detail_row(
+                i18n.t("VersionSettingsModal.signature_thumbprint"),
+                info.thumbprint.clone(),
+            ))
+            .child(detail_row(
+                i18n.t("VersionSettingsModal.signature_validity"),
+                format!("{} ~ {}", info.valid_from, info.valid_to),
+            )),
+    };
+
+    panel_shell(colors)
+        .w_full()
+        .p(px(14.))
+        .flex()
+        .flex_col()
+        .gap(px(10.))
+        .child(card_title(
+            colors,
+            i18n.t("VersionSettingsModal.signature_label"),
+        ))
+        .child(body)
+}
+
 fn render_toggle_card(
     id: &'static str,
     colors: &ThemeColors,
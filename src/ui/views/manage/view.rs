@@ -2,6 +2,7 @@ use super::*;
 
 pub struct ManagePageView {
     pub(super) _subscriptions: Vec<Subscription>,
+    pub(super) _deletion_events_task: Task<()>,
     pub(super) asset_search_input: Option<Entity<InputState>>,
     pub(super) screenshot_search_input: Option<Entity<InputState>>,
     pub(super) server_search_input: Option<Entity<InputState>>,
@@ -78,8 +79,28 @@ impl ManagePageView {
             }),
         ];
 
+        let mut deletion_events =
+            crate::core::minecraft::recent_deletions::subscribe_deletion_events();
+        let deletion_events_task = cx.spawn(async move |handle, cx| {
+            loop {
+                let record = match deletion_events.recv().await {
+                    Ok(record) => record,
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => return,
+                };
+
+                if handle
+                    .update(cx, |_this, cx| show_world_deletion_toast(record, cx))
+                    .is_err()
+                {
+                    return;
+                }
+            }
+        });
+
         Self {
             _subscriptions: subscriptions,
+            _deletion_events_task: deletion_events_task,
             asset_search_input: None,
             screenshot_search_input: None,
             server_search_input: None,
@@ -2478,6 +2478,20 @@ impl Render for DebugView {
                                         bool_label(map_memory.preview_3d_render_in_flight)
                                     )),
                                 ),
+                                (
+                                    SharedString::from("复合包缓存"),
+                                    SharedString::from(format!(
+                                        "{} entries",
+                                        runtime.bmcbl_memory.compound_cache_entries
+                                    )),
+                                ),
+                                (
+                                    SharedString::from("任务日志"),
+                                    SharedString::from(format!(
+                                        "{} lines",
+                                        runtime.bmcbl_memory.task_log_lines
+                                    )),
+                                ),
                             ],
                             muted,
                         )
@@ -1,9 +1,10 @@
 use crate::core::minecraft::assets::{
     CheckImportRequest, ImportAssetsRequest, ImportAssetsResult, check_import_conflict,
-    import_assets, inspect_import_file,
+    diff_import_conflict, import_assets, inspect_import_file,
 };
 use crate::core::minecraft::import::{
-    ImportCheckResult, PackagePreview, PreviewIconData, PreviewImageFormat, WorldPackReference,
+    ImportCheckResult, PackContentDiff, PackagePreview, PreviewIconData, PreviewImageFormat,
+    VersionComparison, WorldPackReference,
 };
 use crate::core::minecraft::paths::{BuildType, Edition, GamePathOptions, get_game_root};
 use crate::launch::ImportLaunchContext;
@@ -49,6 +50,8 @@ pub struct ImportWindowView {
     selected_folder: Option<SharedString>,
     status: Option<(StatusKind, SharedString)>,
     conflict: Option<ImportCheckResult>,
+    /// 冲突对话框打开后在后台计算，完成前只显示版本对比
+    conflict_diff: Option<PackContentDiff>,
     show_conflict_dialog: bool,
     is_inspecting: bool,
     is_importing: bool,
@@ -96,6 +99,7 @@ impl ImportWindowView {
             selected_folder: None,
             status: None,
             conflict: None,
+            conflict_diff: None,
             show_conflict_dialog: false,
             is_inspecting: false,
             is_importing: false,
@@ -143,7 +147,7 @@ impl ImportWindowView {
             file_path, locale
         );
         cx.spawn(async move |handle, cx| {
-            let preview = inspect_import_file(file_path.clone(), Some(locale.clone())).await;
+            let preview = inspect_import_file(file_path.clone(), Some(locale.clone()), false).await;
             handle.update(cx, |this, cx| {
                 this.is_inspecting = false;
                 match preview {
@@ -269,12 +273,34 @@ impl ImportWindowView {
             );
 
             if conflict.has_conflict && !overwrite {
+                let existing_pack_path = conflict.existing_pack_path.clone();
                 handle.update(cx, |this, cx| {
                     this.is_importing = false;
                     this.conflict = Some(conflict);
+                    this.conflict_diff = None;
                     this.show_conflict_dialog = true;
                     cx.notify();
                 })?;
+                let Some(existing_pack_path) = existing_pack_path else {
+                    return Ok::<(), anyhow::Error>(());
+                };
+                match diff_import_conflict(file_path, existing_pack_path.clone()).await {
+                    Ok(diff) => handle.update(cx, |this, cx| {
+                        // 计算期间可能已经切换到另一次冲突检查
+                        let current = this
+                            .conflict
+                            .as_ref()
+                            .and_then(|conflict| conflict.existing_pack_path.as_deref());
+                        if current == Some(existing_pack_path.as_str()) {
+                            this.conflict_diff = Some(diff);
+                            cx.notify();
+                        }
+                    })?,
+                    Err(error) => warn!(
+                        "Import window pack diff failed: path={}, error={}",
+                        file_path_for_log, error
+                    ),
+                }
                 return Ok::<(), anyhow::Error>(());
             }
 
@@ -287,6 +313,7 @@ impl ImportWindowView {
                 file_paths: vec![file_path],
                 overwrite,
                 allow_shared_fallback,
+                task_id: None,
             })
             .await;
 
@@ -343,6 +370,11 @@ impl ImportWindowView {
                         "{}，5 秒后自动关闭窗口",
                         cx.global::<I18n>().t("Import.importSuccess")
                     ))
+                } else if let Some(summary) = &result.blocked_files_summary {
+                    SharedString::from(format!(
+                        "导入完成，成功 {} 个，失败 {} 个（{}）",
+                        result.imported_count, result.failed_count, summary
+                    ))
                 } else {
                     SharedString::from(format!(
                         "导入完成，成功 {} 个，失败 {} 个",
@@ -2425,6 +2457,23 @@ fn render_conflict_dialog(
                                         view,
                                         cx,
                                     ))
+                                    .when_some(
+                                        conflict_diff_summary(
+                                            conflict,
+                                            view.conflict_diff.as_ref(),
+                                            cx,
+                                        ),
+                                        |panel, summary| {
+                                            panel.child(
+                                                div()
+                                                    .text_size(px(12.))
+                                                    .line_height(relative(1.45))
+                                                    .text_center()
+                                                    .text_color(colors.text_secondary)
+                                                    .child(summary),
+                                            )
+                                        },
+                                    )
                                     .child(
                                         div()
                                             .text_size(px(12.))
@@ -2479,6 +2528,61 @@ fn render_conflict_dialog(
         .into_any_element()
 }
 
+/// 版本对比 + 文件差异摘要，例如 "导入的包版本更新 · +12 textures, 清单 1.2.0 → 1.3.1"
+fn conflict_diff_summary(
+    conflict: &ImportCheckResult,
+    diff: Option<&PackContentDiff>,
+    cx: &Context<ImportWindowView>,
+) -> Option<SharedString> {
+    let i18n = cx.global::<I18n>();
+    let comparison =
+        conflict
+            .version_comparison
+            .as_ref()
+            .map(|comparison| match comparison.comparison {
+                VersionComparison::Newer => i18n.t("Import.conflict.versionNewer"),
+                VersionComparison::Older => i18n.t("Import.conflict.versionOlder"),
+                VersionComparison::Same => i18n.t("Import.conflict.versionSame"),
+            });
+    let summary = diff.map(|diff| pack_diff_summary(diff, i18n));
+    match (comparison, summary) {
+        (Some(comparison), Some(summary)) => {
+            Some(SharedString::from(format!("{comparison} · {summary}")))
+        }
+        (Some(comparison), None) => Some(comparison),
+        (None, Some(summary)) => Some(summary),
+        (None, None) => None,
+    }
+}
+
+fn pack_diff_summary(diff: &PackContentDiff, i18n: &I18n) -> SharedString {
+    let mut parts: Vec<SharedString> = Vec::new();
+    for category in &diff.categories {
+        for (key, count) in [
+            ("Import.conflict.diffAdded", category.added),
+            ("Import.conflict.diffRemoved", category.removed),
+            ("Import.conflict.diffChanged", category.changed),
+        ] {
+            if count > 0 {
+                let count = count.to_string();
+                parts.push(i18n.t_with_vars(key, &[("count", &count), ("name", &category.name)]));
+            }
+        }
+    }
+    if let (Some(existing), Some(incoming)) = (&diff.existing_version, &diff.incoming_version)
+        && existing != incoming
+    {
+        parts.push(i18n.t_with_vars(
+            "Import.conflict.diffManifest",
+            &[("from", existing), ("to", incoming)],
+        ));
+    }
+    if parts.is_empty() {
+        return i18n.t("Import.conflict.diffNoChanges");
+    }
+    SharedString::from(parts.join(", "))
+}
+
 fn render_conflict_compare_panel(
     colors: &ThemeColors,
     existing_preview: Option<&PackagePreview>,
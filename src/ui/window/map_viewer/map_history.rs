@@ -544,7 +544,8 @@ pub(super) fn prune_history(
         if !path.is_dir() || !path.join(HISTORY_ENTRY_FILE).exists() {
             continue;
         }
-        let size = dir_size(&path)?;
+        let size = file_ops::dir_size(&path)
+            .ok_or_else(|| format!("计算历史目录大小失败: {}", path.display()))?;
         let entry = fs::read_to_string(path.join(HISTORY_ENTRY_FILE))
             .ok()
             .and_then(|raw| serde_json::from_str::<MapHistoryEntry>(&raw).ok());
@@ -1182,22 +1183,6 @@ fn write_atomic(path: impl AsRef<Path>, bytes: &[u8]) -> Result<(), String> {
     fs::rename(&temp, path).map_err(|error| format!("提交文件失败 {}: {error}", path.display()))
 }
 
-fn dir_size(path: &Path) -> Result<u64, String> {
-    let mut total = 0u64;
-    for entry in fs::read_dir(path).map_err(|error| format!("读取目录失败: {error}"))? {
-        let entry = entry.map_err(|error| format!("读取目录项失败: {error}"))?;
-        let metadata = entry
-            .metadata()
-            .map_err(|error| format!("读取文件信息失败: {error}"))?;
-        if metadata.is_dir() {
-            total = total.saturating_add(dir_size(&entry.path())?);
-        } else {
-            total = total.saturating_add(metadata.len());
-        }
-    }
-    Ok(total)
-}
-
 fn remove_dir_all_if_exists(path: &Path) -> Result<(), String> {
     match fs::remove_dir_all(path) {
         Ok(()) => Ok(()),
@@ -0,0 +1,150 @@
+//! 数据目录可写性探测：安装在 Program Files 等受保护位置时，解压、写配置会逐个文件报拒绝访问。
+//! 启动时和安装/导入前先写一个探测文件，不可写时直接给出统一的 `data_root_not_writable` 错误。
+
+use crate::utils::file_ops;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+pub const DATA_ROOT_NOT_WRITABLE_ERROR: &str = "data_root_not_writable";
+
+const PROBE_FILE_PREFIX: &str = ".bmcbl_write_probe";
+
+/// 在目录中创建并删除一个探测文件
+fn probe_writable(dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let probe_path = dir.join(format!("{PROBE_FILE_PREFIX}_{}", std::process::id()));
+    let result = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe_path)
+        .and_then(|mut file| file.write_all(b"probe"));
+    let _ = fs::remove_file(&probe_path);
+    result
+}
+
+#[cfg(target_os = "windows")]
+fn path_owner(path: &Path) -> Option<String> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows::Win32::Foundation::{HLOCAL, LocalFree};
+    use windows::Win32::Security::Authorization::{GetNamedSecurityInfoW, SE_FILE_OBJECT};
+    use windows::Win32::Security::{
+        LookupAccountSidW, OWNER_SECURITY_INFORMATION, PSECURITY_DESCRIPTOR, PSID, SID_NAME_USE,
+    };
+    use windows::core::{PCWSTR, PWSTR};
+
+    let wide_path: Vec<u16> = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    let mut owner = PSID::default();
+    let mut descriptor = PSECURITY_DESCRIPTOR::default();
+    // SAFETY: `wide_path` is NUL-terminated; the returned descriptor is freed with LocalFree below.
+    let status = unsafe {
+        GetNamedSecurityInfoW(
+            PCWSTR(wide_path.as_ptr()),
+            SE_FILE_OBJECT,
+            OWNER_SECURITY_INFORMATION,
+            Some(&mut owner as *mut PSID),
+            None,
+            None,
+            None,
+            &mut descriptor,
+        )
+    };
+    if status.is_err() {
+        return None;
+    }
+
+    let mut name = [0u16; 256];
+    let mut domain = [0u16; 256];
+    let mut name_len = name.len() as u32;
+    let mut domain_len = domain.len() as u32;
+    let mut sid_type = SID_NAME_USE::default();
+    // SAFETY: `owner` points into `descriptor`, which stays alive until LocalFree; the buffers
+    // outlive the call and their lengths are passed alongside.
+    let lookup = unsafe {
+        LookupAccountSidW(
+            PCWSTR::null(),
+            owner,
+            Some(PWSTR(name.as_mut_ptr())),
+            &mut name_len,
+            Some(PWSTR(domain.as_mut_ptr())),
+            &mut domain_len,
+            &mut sid_type,
+        )
+    };
+    // SAFETY: `descriptor` was allocated by GetNamedSecurityInfoW and is not used afterwards.
+    unsafe {
+        let _ = LocalFree(Some(HLOCAL(descriptor.0)));
+    }
+    lookup.ok()?;
+
+    let name = String::from_utf16_lossy(&name[..name_len as usize]);
+    let domain = String::from_utf16_lossy(&domain[..domain_len as usize]);
+    Some(if domain.is_empty() {
+        name
+    } else {
+        format!("{domain}\\{name}")
+    })
+}
+
+#[cfg(unix)]
+fn path_owner(path: &Path) -> Option<String> {
+    use std::os::unix::fs::MetadataExt;
+    fs::metadata(path)
+        .ok()
+        .map(|metadata| format!("uid {}", metadata.uid()))
+}
+
+#[cfg(not(any(target_os = "windows", unix)))]
+fn path_owner(_path: &Path) -> Option<String> {
+    None
+}
+
+/// 离目标最近的已存在祖先目录，用于查询所有者
+fn nearest_existing(path: &Path) -> PathBuf {
+    path.ancestors()
+        .find(|ancestor| ancestor.exists())
+        .unwrap_or(path)
+        .to_path_buf()
+}
+
+fn not_writable_message(dir: &Path, error: &io::Error) -> String {
+    let owner = path_owner(&nearest_existing(dir)).unwrap_or_else(|| "未知".to_string());
+    format!(
+        "{DATA_ROOT_NOT_WRITABLE_ERROR}: 数据目录不可写: {} (所有者: {owner}, {error})。\
+         请把启动器移动到有写入权限的位置（例如用户文档目录，不要放在 Program Files 下）后重新启动",
+        dir.display()
+    )
+}
+
+/// 探测数据目录是否可写；不可写时返回以 `data_root_not_writable` 开头的错误
+pub fn ensure_data_root_writable() -> Result<(), String> {
+    let root = file_ops::bmcbl_dir();
+    probe_writable(&root).map_err(|error| {
+        let message = not_writable_message(&root, &error);
+        warn!("{}", message);
+        message
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn probe_leaves_no_file_and_reports_error_code() {
+        let dir = std::env::temp_dir().join(format!("bmcbl-data-root-{}", uuid::Uuid::new_v4()));
+        probe_writable(&dir).expect("temp dir is writable");
+        assert_eq!(fs::read_dir(&dir).expect("read dir").count(), 0);
+
+        let error = io::Error::from(io::ErrorKind::PermissionDenied);
+        let message = not_writable_message(&dir.join("missing").join("child"), &error);
+        assert!(message.starts_with(DATA_ROOT_NOT_WRITABLE_ERROR));
+
+        fs::remove_dir_all(&dir).expect("remove test dir");
+    }
+}
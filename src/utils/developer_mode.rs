@@ -1,12 +1,9 @@
 #![cfg(target_os = "windows")]
 use std::io;
-use std::mem::size_of;
 
 use thiserror::Error;
 use tracing::{debug, info, warn};
-use windows::Win32::Foundation::{CloseHandle, HANDLE, HWND};
-use windows::Win32::Security::{GetTokenInformation, TOKEN_ELEVATION, TOKEN_QUERY, TokenElevation};
-use windows::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
+use windows::Win32::Foundation::HWND;
 use windows::Win32::UI::Shell::ShellExecuteW;
 use windows::Win32::UI::WindowsAndMessaging::SW_SHOW;
 use windows::core::{HSTRING, PCWSTR};
@@ -14,7 +11,8 @@ use windows::core::{HSTRING, PCWSTR};
 use winreg::RegKey;
 use winreg::enums::{HKEY_LOCAL_MACHINE, KEY_READ, KEY_WOW64_64KEY};
 
-const DEVELOPER_MODE_REG_PATH: &str = r"SOFTWARE\Microsoft\Windows\CurrentVersion\AppModelUnlock";
+pub(crate) const DEVELOPER_MODE_REG_PATH: &str =
+    r"SOFTWARE\Microsoft\Windows\CurrentVersion\AppModelUnlock";
 const DEVELOPER_MODE_VALUE_NAME: &str = "AllowDevelopmentWithoutDevLicense";
 
 #[derive(Debug, Error)]
@@ -27,30 +25,6 @@ pub enum DeveloperModeError {
     OpenSettings(String),
 }
 
-pub fn is_process_elevated() -> bool {
-    let elevated = unsafe {
-        let mut token = HANDLE::default();
-        if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token).is_err() {
-            return false;
-        }
-
-        let mut elevation = TOKEN_ELEVATION::default();
-        let mut returned_length = 0u32;
-        let result = GetTokenInformation(
-            token,
-            TokenElevation,
-            Some((&mut elevation as *mut TOKEN_ELEVATION).cast()),
-            size_of::<TOKEN_ELEVATION>() as u32,
-            &mut returned_length,
-        );
-        let _ = CloseHandle(token);
-
-        result.is_ok() && elevation.TokenIsElevated != 0
-    };
-    debug!(elevated, "已检查当前进程管理员权限状态");
-    elevated
-}
-
 pub fn is_developer_mode_enabled() -> bool {
     let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
     let enabled = hklm
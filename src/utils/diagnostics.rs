@@ -124,6 +124,7 @@ pub fn prepare_previous_run_reports() -> Result<()> {
     if let Some(report) = report_from_crash_signal()? {
         write_pending_report(&report)?;
         archive_report(&report)?;
+        archive_last_launch_session(&report);
         remove_file_if_exists(&crash_signal_path())?;
         clear_session_marker()?;
         return Ok(());
@@ -497,6 +498,19 @@ fn archive_report(report: &DiagnosticsReport) -> Result<()> {
     write_json(&archive_dir.join(format!("{}.json", report.id)), report)
 }
 
+/// 崩溃报告旁附带最近一次游戏启动的历史记录（注入的 DLL 及其版本），便于复现
+fn archive_last_launch_session(report: &DiagnosticsReport) {
+    let Some(session) = crate::core::minecraft::launcher::history::latest_launch_session() else {
+        return;
+    };
+    let path = diagnostics_dir()
+        .join(REPORT_ARCHIVE_DIR)
+        .join(format!("{}.launch-session.json", report.id));
+    if let Err(error) = write_json(&path, &session) {
+        warn!(?error, path = %path.display(), "failed to archive last launch session");
+    }
+}
+
 fn write_pending_report(report: &DiagnosticsReport) -> Result<()> {
     write_json(&pending_report_path(), report)
 }
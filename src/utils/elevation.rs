@@ -0,0 +1,357 @@
+#![cfg(target_os = "windows")]
+//! 管理员权限检测与按需提权。
+//!
+//! 启动器默认不以管理员身份运行。少数操作（企业策略限制下注册包、写入开发者模式开关）
+//! 在未提权时会因拒绝访问失败，此时返回带 [`ElevationRequired`] 标记的错误；
+//! 用户确认后由 [`relaunch_elevated`] 以 `runas` 重启启动器，并通过 `--resume-elevated`
+//! 传入恢复令牌，新实例接管单实例互斥量后由 [`resume_after_elevation`] 继续原操作。
+
+use std::fmt;
+use std::mem::size_of;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+use windows::Win32::Foundation::{
+    CloseHandle, E_ACCESSDENIED, ERROR_ACCESS_DENIED, ERROR_CANCELLED, HANDLE,
+};
+use windows::Win32::Security::{GetTokenInformation, TOKEN_ELEVATION, TOKEN_QUERY, TokenElevation};
+use windows::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
+use windows::Win32::UI::Shell::{SEE_MASK_NOASYNC, SHELLEXECUTEINFOW, ShellExecuteExW};
+use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+use windows::core::{HSTRING, PCWSTR, w};
+use winreg::RegKey;
+use winreg::enums::{HKEY_LOCAL_MACHINE, KEY_READ, KEY_SET_VALUE, KEY_WOW64_64KEY};
+
+use crate::utils::developer_mode::{self, DEVELOPER_MODE_REG_PATH};
+
+/// 命令行参数，值为 [`ResumeAction::encode`] 生成的令牌
+pub const RESUME_ELEVATED_ARG: &str = "--resume-elevated";
+const ELEVATION_REQUIRED_MARKER: &str = "[elevation_required:";
+const APPX_POLICY_REG_PATH: &str = r"SOFTWARE\Policies\Microsoft\Windows\Appx";
+const BLOCK_NON_ADMIN_INSTALL_VALUE_NAME: &str = "BlockNonAdminUserInstall";
+const RELAUNCH_EXIT_DELAY: Duration = Duration::from_millis(300);
+
+/// 已知在未提权时可能因拒绝访问失败的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ElevatedOperation {
+    /// 注册 / 安装 APPX 包（企业策略禁止非管理员安装时）
+    RegisterPackage,
+    /// 写入 HKLM 下的开发者模式开关
+    DeveloperMode,
+}
+
+impl ElevatedOperation {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::RegisterPackage => "注册游戏包",
+            Self::DeveloperMode => "开启开发者模式",
+        }
+    }
+}
+
+/// 提权重启后要继续执行的操作
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ResumeAction {
+    EnableDeveloperMode,
+    /// 重新执行启动流程，注册包是其中一步
+    LaunchVersion {
+        version_folder: String,
+    },
+}
+
+impl ResumeAction {
+    pub fn operation(&self) -> ElevatedOperation {
+        match self {
+            Self::EnableDeveloperMode => ElevatedOperation::DeveloperMode,
+            Self::LaunchVersion { .. } => ElevatedOperation::RegisterPackage,
+        }
+    }
+
+    /// JSON 再转十六进制，作为命令行参数时无需转义引号
+    pub fn encode(&self) -> String {
+        hex::encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    pub fn decode(token: &str) -> Result<Self, String> {
+        let raw = hex::decode(token.trim()).map_err(|e| format!("恢复令牌格式无效: {}", e))?;
+        serde_json::from_slice(&raw).map_err(|e| format!("恢复令牌解析失败: {}", e))
+    }
+}
+
+/// 附在错误信息末尾的结构化标记，界面据此询问用户是否提权重试
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElevationRequired {
+    pub operation: ElevatedOperation,
+    #[serde(default)]
+    pub resume: Option<ResumeAction>,
+}
+
+impl ElevationRequired {
+    pub fn new(operation: ElevatedOperation) -> Self {
+        Self {
+            operation,
+            resume: None,
+        }
+    }
+
+    pub fn with_resume(mut self, resume: ResumeAction) -> Self {
+        self.resume = Some(resume);
+        self
+    }
+
+    /// 生成 `<可读说明> [elevation_required:<json>]` 形式的错误信息
+    pub fn error_message(&self, detail: impl fmt::Display) -> String {
+        format!(
+            "{}需要管理员权限: {} {}{}]",
+            self.operation.label(),
+            detail,
+            ELEVATION_REQUIRED_MARKER,
+            serde_json::to_string(self).unwrap_or_default()
+        )
+    }
+
+    /// 从错误信息中取出标记；调用方在外层再包一层前缀也能识别
+    pub fn parse(message: &str) -> Option<Self> {
+        let start = message.rfind(ELEVATION_REQUIRED_MARKER)? + ELEVATION_REQUIRED_MARKER.len();
+        let payload = message[start..].trim_end().strip_suffix(']')?;
+        serde_json::from_str(payload).ok()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OperationCapability {
+    pub operation: ElevatedOperation,
+    /// 以当前权限执行预计不会被拒绝
+    pub allowed: bool,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ElevationCapabilities {
+    pub elevated: bool,
+    pub operations: Vec<OperationCapability>,
+}
+
+pub fn is_elevated() -> bool {
+    let elevated = unsafe {
+        let mut token = HANDLE::default();
+        if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token).is_err() {
+            return false;
+        }
+
+        let mut elevation = TOKEN_ELEVATION::default();
+        let mut returned_length = 0u32;
+        let result = GetTokenInformation(
+            token,
+            TokenElevation,
+            Some((&mut elevation as *mut TOKEN_ELEVATION).cast()),
+            size_of::<TOKEN_ELEVATION>() as u32,
+            &mut returned_length,
+        );
+        let _ = CloseHandle(token);
+
+        result.is_ok() && elevation.TokenIsElevated != 0
+    };
+    debug!(elevated, "已检查当前进程管理员权限状态");
+    elevated
+}
+
+/// E_ACCESSDENIED 或 Win32 ERROR_ACCESS_DENIED
+pub fn is_access_denied(error: &windows::core::Error) -> bool {
+    let code = error.code();
+    code == E_ACCESSDENIED || code == ERROR_ACCESS_DENIED.to_hresult()
+}
+
+fn probe_developer_mode() -> OperationCapability {
+    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+    let (allowed, reason) = match hklm
+        .open_subkey_with_flags(DEVELOPER_MODE_REG_PATH, KEY_SET_VALUE | KEY_WOW64_64KEY)
+    {
+        Ok(_) => (true, None),
+        Err(error) if error.kind() == std::io::ErrorKind::PermissionDenied => {
+            (false, Some("无权写入 HKLM 下的开发者模式开关".to_string()))
+        }
+        Err(error) => (
+            false,
+            Some(format!("无法打开开发者模式注册表项: {}", error)),
+        ),
+    };
+    OperationCapability {
+        operation: ElevatedOperation::DeveloperMode,
+        allowed,
+        reason,
+    }
+}
+
+fn probe_register_package(elevated: bool) -> OperationCapability {
+    let blocked = RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey_with_flags(APPX_POLICY_REG_PATH, KEY_READ | KEY_WOW64_64KEY)
+        .ok()
+        .and_then(|key| {
+            key.get_value::<u32, _>(BLOCK_NON_ADMIN_INSTALL_VALUE_NAME)
+                .ok()
+        })
+        .is_some_and(|value| value == 1);
+    OperationCapability {
+        operation: ElevatedOperation::RegisterPackage,
+        allowed: elevated || !blocked,
+        reason: (blocked && !elevated).then(|| "组策略禁止非管理员用户安装打包应用".to_string()),
+    }
+}
+
+/// 检查单个操作以当前权限能否执行
+pub fn probe_operation(operation: ElevatedOperation) -> OperationCapability {
+    match operation {
+        ElevatedOperation::DeveloperMode => probe_developer_mode(),
+        ElevatedOperation::RegisterPackage => probe_register_package(is_elevated()),
+    }
+}
+
+pub fn probe_elevation_capabilities() -> ElevationCapabilities {
+    let elevated = is_elevated();
+    ElevationCapabilities {
+        elevated,
+        operations: vec![probe_register_package(elevated), probe_developer_mode()],
+    }
+}
+
+/// 询问用户是否为该操作以管理员身份重启，只在用户点击“是”时返回 true
+pub fn confirm_elevation(operation: ElevatedOperation) -> bool {
+    let confirmed = rfd::MessageDialog::new()
+        .set_title("需要管理员权限")
+        .set_description(format!(
+            "{}需要管理员权限。\n\n是否以管理员身份重启启动器并继续该操作？仅本次重启会提权。",
+            operation.label()
+        ))
+        .set_level(rfd::MessageLevel::Warning)
+        .set_buttons(rfd::MessageButtons::YesNo)
+        .show();
+    matches!(confirmed, rfd::MessageDialogResult::Yes)
+}
+
+/// 以 `runas` 重启启动器并携带恢复令牌；成功时释放单实例互斥量并退出当前进程，
+/// 用户在 UAC 中取消时返回错误，当前实例继续运行
+pub fn relaunch_elevated(resume_action: ResumeAction) -> Result<(), String> {
+    if is_elevated() {
+        return Err("启动器已以管理员身份运行".to_string());
+    }
+    let exe = std::env::current_exe().map_err(|e| format!("获取 current_exe 失败：{}", e))?;
+    let file = HSTRING::from(exe.as_os_str());
+    let parameters = HSTRING::from(format!(
+        "{} {}",
+        RESUME_ELEVATED_ARG,
+        resume_action.encode()
+    ));
+    let mut info = SHELLEXECUTEINFOW {
+        cbSize: size_of::<SHELLEXECUTEINFOW>() as u32,
+        fMask: SEE_MASK_NOASYNC,
+        lpVerb: w!("runas"),
+        lpFile: PCWSTR(file.as_ptr()),
+        lpParameters: PCWSTR(parameters.as_ptr()),
+        nShow: SW_SHOWNORMAL.0,
+        ..Default::default()
+    };
+    if let Err(error) = unsafe { ShellExecuteExW(&mut info) } {
+        if error.code() == ERROR_CANCELLED.to_hresult() {
+            info!(operation = ?resume_action.operation(), "用户取消了管理员授权");
+            return Err("已取消管理员授权".to_string());
+        }
+        return Err(format!("以管理员身份启动失败: {}", error));
+    }
+
+    info!(operation = ?resume_action.operation(), "已以管理员身份重启启动器");
+    crate::utils::single_instance::release_mutex();
+    std::thread::sleep(RELAUNCH_EXIT_DELAY);
+    std::process::exit(0);
+}
+
+/// 提权实例启动后继续执行原操作
+pub async fn resume_after_elevation(token: String) {
+    let action = match ResumeAction::decode(&token) {
+        Ok(action) => action,
+        Err(error) => {
+            warn!("{error}");
+            return;
+        }
+    };
+    if !is_elevated() {
+        warn!(?action, "未处于管理员权限，跳过提权后的恢复操作");
+        return;
+    }
+    info!(?action, "提权后继续执行操作");
+
+    let result = match &action {
+        ResumeAction::EnableDeveloperMode => {
+            tokio::task::spawn_blocking(developer_mode::try_enable_developer_mode)
+                .await
+                .map_err(|e| format!("开启开发者模式任务失败: {}", e))
+                .and_then(|result| result.map_err(|e| e.to_string()))
+        }
+        ResumeAction::LaunchVersion { version_folder } => {
+            resume_launch_version(version_folder).await
+        }
+    };
+    match result {
+        Ok(()) => info!(?action, "提权后的操作已完成"),
+        Err(error) => {
+            let title = format!("{}失败", action.operation().label());
+            let _ = tokio::task::spawn_blocking(move || {
+                crate::result::show_application_error(&title, "resume_after_elevation", error);
+            })
+            .await;
+        }
+    }
+}
+
+async fn resume_launch_version(version_folder: &str) -> Result<(), String> {
+    let version = crate::core::version::api::get_version_list()
+        .await
+        .map_err(|e| format!("获取本地版本列表失败: {}", e))?
+        .into_iter()
+        .find(|version| version.folder.as_ref() == version_folder)
+        .ok_or_else(|| format!("未找到目标游戏版本: {}", version_folder))?;
+    let request = crate::core::minecraft::launcher::LaunchRequest::new(
+        version.folder.as_ref(),
+        version.name.as_ref(),
+        version.version.as_ref(),
+        version.path.as_ref(),
+    );
+    let task_id = crate::core::minecraft::launcher::try_start_launch_task(request)?;
+    info!(task_id = %task_id, version_folder, "提权后已重新发起启动任务");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resume_token_and_error_marker_round_trip() {
+        let action = ResumeAction::LaunchVersion {
+            version_folder: "1.21.0.3 \"test\"".to_string(),
+        };
+        let token = action.encode();
+        assert!(token.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(ResumeAction::decode(&token).expect("decode"), action);
+        assert!(ResumeAction::decode("zz").is_err());
+
+        let required =
+            ElevationRequired::new(ElevatedOperation::RegisterPackage).with_resume(action.clone());
+        let message = format!(
+            "启动失败: {}",
+            required.error_message("拒绝访问 [0x80070005]")
+        );
+        assert_eq!(ElevationRequired::parse(&message), Some(required));
+        assert_eq!(ElevationRequired::parse("拒绝访问 [0x80070005]"), None);
+
+        assert!(is_access_denied(&windows::core::Error::from_hresult(
+            E_ACCESSDENIED
+        )));
+        assert!(!is_access_denied(&windows::core::Error::from_hresult(
+            ERROR_CANCELLED.to_hresult()
+        )));
+    }
+}
@@ -0,0 +1,387 @@
+//! 杀毒软件（Defender 等）会在文件刚写出后短暂锁住 DLL / exe，此时创建、重命名或
+//! 规范化路径会得到共享冲突。这里统一做有限次数的退避重试；重试用尽时返回
+//! `file_locked_by_other_process` 错误，并尽量通过 Restart Manager 找出占用的进程。
+//!
+//! 被锁住的文件会记录到当前线程所在的 [`LockReportScope`]，任务结束时汇总成一句说明，
+//! 而不是悄悄少装几个文件。
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tracing::warn;
+
+pub const FILE_LOCKED_ERROR_CODE: &str = "file_locked_by_other_process";
+
+/// ERROR_SHARING_VIOLATION / ERROR_LOCK_VIOLATION
+const LOCK_ERROR_CODES: [i32; 2] = [32, 33];
+/// 每次重试前的基础等待，另加最多一半的随机抖动；合计约 3~4.5 秒
+const RETRY_DELAYS_MS: [u64; 7] = [50, 100, 200, 300, 500, 800, 1000];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BlockedFile {
+    pub path: String,
+    /// Restart Manager 报告的占用进程，查不到时为空
+    pub process_names: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct FileLockedError {
+    pub blocked: BlockedFile,
+    source: io::Error,
+}
+
+impl fmt::Display for FileLockedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{FILE_LOCKED_ERROR_CODE}: {} {}（{}）",
+            self.blocked.path,
+            holder_phrase(&self.blocked.process_names),
+            self.source
+        )
+    }
+}
+
+impl std::error::Error for FileLockedError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+fn holder_phrase(process_names: &[String]) -> String {
+    if process_names.is_empty() {
+        "被其他程序占用".to_string()
+    } else {
+        format!("被 {} 占用", process_names.join("、"))
+    }
+}
+
+pub fn is_lock_violation(error: &io::Error) -> bool {
+    cfg!(target_os = "windows")
+        && error
+            .raw_os_error()
+            .is_some_and(|code| LOCK_ERROR_CODES.contains(&code))
+}
+
+/// 取出重试用尽后包装的 [`FileLockedError`]
+pub fn as_file_locked(error: &io::Error) -> Option<&FileLockedError> {
+    error.get_ref()?.downcast_ref::<FileLockedError>()
+}
+
+fn jittered(base_ms: u64) -> Duration {
+    Duration::from_millis(base_ms + rand::random::<u64>() % (base_ms / 2 + 1))
+}
+
+fn lock_exhausted(path: &Path, source: io::Error) -> io::Error {
+    let blocked = BlockedFile {
+        path: path.to_string_lossy().into_owned(),
+        process_names: locking_process_names(path),
+    };
+    record_blocked(&blocked);
+    let error = FileLockedError { blocked, source };
+    warn!("{error}");
+    io::Error::other(error)
+}
+
+/// 对 `path` 执行 `op`，遇到共享冲突时退避重试
+pub fn retry_on_lock<T>(path: &Path, op: impl FnMut() -> io::Result<T>) -> io::Result<T> {
+    retry_with(path, op, is_lock_violation, &RETRY_DELAYS_MS)
+}
+
+fn retry_with<T>(
+    path: &Path,
+    mut op: impl FnMut() -> io::Result<T>,
+    is_locked: impl Fn(&io::Error) -> bool,
+    delays_ms: &[u64],
+) -> io::Result<T> {
+    let mut delays = delays_ms.iter();
+    loop {
+        match op() {
+            Err(error) if is_locked(&error) => match delays.next() {
+                Some(delay) => std::thread::sleep(jittered(*delay)),
+                None => return Err(lock_exhausted(path, error)),
+            },
+            result => return result,
+        }
+    }
+}
+
+/// [`retry_on_lock`] 的异步版本，等待期间不占用运行时线程
+pub async fn retry_on_lock_async<T, F, Fut>(path: &Path, mut op: F) -> io::Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = io::Result<T>>,
+{
+    let mut delays = RETRY_DELAYS_MS.iter();
+    loop {
+        match op().await {
+            Err(error) if is_lock_violation(&error) => match delays.next() {
+                Some(delay) => tokio::time::sleep(jittered(*delay)).await,
+                None => return Err(lock_exhausted(path, error)),
+            },
+            result => return result,
+        }
+    }
+}
+
+static NEXT_SCOPE_ID: AtomicU64 = AtomicU64::new(1);
+static SCOPES: Lazy<Mutex<HashMap<u64, Vec<BlockedFile>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+thread_local! {
+    static CURRENT_SCOPE: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+fn record_blocked(blocked: &BlockedFile) {
+    let Some(id) = CURRENT_SCOPE.with(Cell::get) else {
+        return;
+    };
+    if let Some(files) = SCOPES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get_mut(&id)
+    {
+        files.push(blocked.clone());
+    }
+}
+
+/// 收集当前线程（以及通过 [`ScopeToken::run`] 传入的工作线程）中被锁住的文件
+pub struct LockReportScope {
+    id: u64,
+    previous: Option<u64>,
+}
+
+impl LockReportScope {
+    pub fn enter() -> Self {
+        let id = NEXT_SCOPE_ID.fetch_add(1, Ordering::Relaxed);
+        SCOPES
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(id, Vec::new());
+        let previous = CURRENT_SCOPE.with(|scope| scope.replace(Some(id)));
+        Self { id, previous }
+    }
+
+    pub fn take(&self) -> Vec<BlockedFile> {
+        SCOPES
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_mut(&self.id)
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for LockReportScope {
+    fn drop(&mut self) {
+        CURRENT_SCOPE.with(|scope| scope.set(self.previous));
+        SCOPES
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&self.id);
+    }
+}
+
+/// 当前线程所在的汇总范围，供 rayon 等工作线程沿用
+#[derive(Debug, Clone, Copy)]
+pub struct ScopeToken(Option<u64>);
+
+pub fn current_scope() -> ScopeToken {
+    ScopeToken(CURRENT_SCOPE.with(Cell::get))
+}
+
+impl ScopeToken {
+    pub fn run<R>(self, f: impl FnOnce() -> R) -> R {
+        let previous = CURRENT_SCOPE.with(|scope| scope.replace(self.0));
+        let result = f();
+        CURRENT_SCOPE.with(|scope| scope.set(previous));
+        result
+    }
+}
+
+/// 例如 "3 个文件被 MsMpEng.exe 占用，1 个文件被其他程序占用"
+pub fn summarize_blocked_files(files: &[BlockedFile]) -> Option<String> {
+    if files.is_empty() {
+        return None;
+    }
+    let mut by_holder: BTreeMap<String, usize> = BTreeMap::new();
+    for file in files {
+        *by_holder
+            .entry(holder_phrase(&file.process_names))
+            .or_default() += 1;
+    }
+    Some(
+        by_holder
+            .into_iter()
+            .map(|(holder, count)| format!("{count} 个文件{holder}"))
+            .collect::<Vec<_>>()
+            .join("，"),
+    )
+}
+
+/// 通过 Restart Manager 查询占用 `path` 的进程名
+#[cfg(target_os = "windows")]
+pub fn locking_process_names(path: &Path) -> Vec<String> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows::Win32::Foundation::ERROR_MORE_DATA;
+    use windows::Win32::System::RestartManager::{
+        CCH_RM_SESSION_KEY, RM_PROCESS_INFO, RmEndSession, RmGetList, RmRegisterResources,
+        RmStartSession,
+    };
+    use windows::core::{PCWSTR, PWSTR};
+
+    let wide_path: Vec<u16> = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    let mut session = 0u32;
+    let mut session_key = [0u16; CCH_RM_SESSION_KEY as usize + 1];
+    // SAFETY: 缓冲区与句柄都在本函数内有效，会话在返回前结束
+    unsafe {
+        if RmStartSession(&mut session, None, PWSTR(session_key.as_mut_ptr())).is_err() {
+            return Vec::new();
+        }
+        let files = [PCWSTR(wide_path.as_ptr())];
+        let mut names = Vec::new();
+        if RmRegisterResources(session, Some(&files), None, None).is_ok() {
+            let mut needed = 0u32;
+            let mut count = 0u32;
+            let mut reasons = 0u32;
+            let mut infos: Vec<RM_PROCESS_INFO> = Vec::new();
+            let mut status = RmGetList(session, &mut needed, &mut count, None, &mut reasons);
+            if status == ERROR_MORE_DATA && needed > 0 {
+                infos.resize(needed as usize, RM_PROCESS_INFO::default());
+                count = needed;
+                status = RmGetList(
+                    session,
+                    &mut needed,
+                    &mut count,
+                    Some(infos.as_mut_ptr()),
+                    &mut reasons,
+                );
+            }
+            if status.is_ok() {
+                names = infos
+                    .iter()
+                    .take(count as usize)
+                    .map(|info| {
+                        let len = info
+                            .strAppName
+                            .iter()
+                            .position(|c| *c == 0)
+                            .unwrap_or(info.strAppName.len());
+                        String::from_utf16_lossy(&info.strAppName[..len])
+                    })
+                    .filter(|name| !name.is_empty())
+                    .collect();
+            }
+        }
+        let _ = RmEndSession(session);
+        names.sort();
+        names.dedup();
+        names
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn locking_process_names(_path: &Path) -> Vec<String> {
+    Vec::new()
+}
+
+/// 带重试的 `File::create`
+pub fn create_file(path: &Path) -> io::Result<std::fs::File> {
+    retry_on_lock(path, || std::fs::File::create(path))
+}
+
+/// 带重试的 `fs::rename`
+pub fn rename(from: &Path, to: &Path) -> io::Result<()> {
+    retry_on_lock(from, || std::fs::rename(from, to))
+}
+
+/// 带重试的异步 `canonicalize`
+pub async fn canonicalize(path: &Path) -> io::Result<PathBuf> {
+    retry_on_lock_async(path, || tokio::fs::canonicalize(path)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retries_locked_files_then_reports_them_to_the_scope() {
+        let locked = |error: &io::Error| error.kind() == io::ErrorKind::WouldBlock;
+        let path = Path::new("mods/example/example.dll");
+
+        let mut attempts = 0;
+        let value = retry_with(
+            path,
+            || {
+                attempts += 1;
+                if attempts < 3 {
+                    Err(io::Error::from(io::ErrorKind::WouldBlock))
+                } else {
+                    Ok(attempts)
+                }
+            },
+            locked,
+            &[1, 1, 1],
+        )
+        .expect("succeeds after retries");
+        assert_eq!(value, 3);
+
+        let scope = LockReportScope::enter();
+        let worker_scope = current_scope();
+        let error = std::thread::spawn(move || {
+            worker_scope.run(|| {
+                retry_with::<()>(
+                    path,
+                    || Err(io::Error::from(io::ErrorKind::WouldBlock)),
+                    locked,
+                    &[1],
+                )
+                .expect_err("retries exhausted")
+            })
+        })
+        .join()
+        .expect("worker thread");
+        assert!(as_file_locked(&error).is_some());
+        assert!(error.to_string().starts_with(FILE_LOCKED_ERROR_CODE));
+
+        let not_locked = retry_with::<()>(
+            path,
+            || Err(io::Error::from(io::ErrorKind::NotFound)),
+            locked,
+            &[1],
+        )
+        .expect_err("other errors are returned as-is");
+        assert_eq!(not_locked.kind(), io::ErrorKind::NotFound);
+
+        let blocked = scope.take();
+        assert_eq!(blocked.len(), 1);
+        assert_eq!(
+            summarize_blocked_files(&[
+                blocked[0].clone(),
+                BlockedFile {
+                    path: "a.dll".to_string(),
+                    process_names: vec!["MsMpEng.exe".to_string()],
+                },
+                BlockedFile {
+                    path: "b.dll".to_string(),
+                    process_names: vec!["MsMpEng.exe".to_string()],
+                },
+            ])
+            .as_deref(),
+            Some("2 个文件被 MsMpEng.exe 占用，1 个文件被其他程序占用")
+        );
+        assert_eq!(summarize_blocked_files(&[]), None);
+    }
+}
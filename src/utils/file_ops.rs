@@ -2,6 +2,8 @@
 use std::ffi::OsStr;
 use std::fs;
 use std::path::{Path, PathBuf};
+use tracing::warn;
+use walkdir::WalkDir;
 
 pub fn exe_dir() -> PathBuf {
     std::env::current_exe()
@@ -82,6 +84,20 @@ pub fn downloads_dir() -> PathBuf {
     bmcbl_subdir("downloads")
 }
 
+/// 版本目录；始终基于数据目录解析，不依赖进程工作目录
+pub fn versions_dir() -> PathBuf {
+    versions_dir_in(&bmcbl_dir())
+}
+
+fn versions_dir_in(data_root: &Path) -> PathBuf {
+    data_root.join("versions")
+}
+
+/// 系统临时目录下启动器专用的子目录
+pub fn launcher_temp_dir() -> PathBuf {
+    std::env::temp_dir().join("BMCBL")
+}
+
 pub fn runners_dir() -> PathBuf {
     bmcbl_subdir("runners")
 }
@@ -90,6 +106,35 @@ pub fn prefixes_dir() -> PathBuf {
     bmcbl_subdir("prefixes")
 }
 
+/// 递归统计目录内文件总大小，不跟随符号链接；遍历出错时返回 None
+pub fn dir_size(path: &Path) -> Option<u64> {
+    dir_size_limited(path, usize::MAX)
+}
+
+/// 同 [`dir_size`]，文件数超过 `file_limit` 时提前放弃并返回 None
+pub fn dir_size_limited(path: &Path, file_limit: usize) -> Option<u64> {
+    let mut total = 0u64;
+    let mut files_seen = 0usize;
+    for entry in WalkDir::new(path).follow_links(false) {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(error) => {
+                warn!("计算目录大小失败: path={}, error={}", path.display(), error);
+                return None;
+            }
+        };
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        files_seen = files_seen.saturating_add(1);
+        if files_seen > file_limit {
+            return None;
+        }
+        total = total.saturating_add(entry.metadata().map_or(0, |metadata| metadata.len()));
+    }
+    Some(total)
+}
+
 pub fn create_initial_directories() {
     let root = bmcbl_dir();
     let dirs = vec![
@@ -100,7 +145,7 @@ pub fn create_initial_directories() {
         downloads_dir(),
         bmcbl_subdir("plugins"),
         bmcbl_subdir("music"),
-        bmcbl_subdir("versions"),
+        versions_dir(),
         cache_subdir("data"),
         cache_subdir("api"),
     ];
@@ -177,6 +222,50 @@ fn migrate_legacy_linux_config() {
     }
 }
 
+#[cfg(test)]
+mod cwd_tests {
+    use super::{bmcbl_dir, versions_dir, versions_dir_in};
+
+    /// 通过文件关联或 bmcbl:// 启动时工作目录可能是 System32
+    #[test]
+    fn versions_dir_ignores_foreign_working_directory() {
+        let data_root =
+            std::env::temp_dir().join(format!("bmcbl-data-root-{}", uuid::Uuid::new_v4()));
+        assert_eq!(versions_dir_in(&data_root), data_root.join("versions"));
+
+        let resolved = versions_dir();
+        assert_eq!(resolved, versions_dir_in(&bmcbl_dir()));
+        assert!(resolved.is_absolute());
+    }
+}
+
+#[cfg(test)]
+mod dir_size_tests {
+    use super::{dir_size, dir_size_limited};
+    use std::fs;
+
+    #[test]
+    fn limited_size_scan_stops_before_large_world_walk() {
+        let test_dir =
+            std::env::temp_dir().join(format!("bmcbl-dir-size-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(test_dir.join("db")).expect("create test dir");
+        for index in 0..3 {
+            fs::write(
+                test_dir.join("db").join(format!("{index}.dat")),
+                [1, 2, 3, 4],
+            )
+            .expect("write test file");
+        }
+
+        assert_eq!(dir_size(&test_dir), Some(12));
+        assert_eq!(dir_size_limited(&test_dir, 3), Some(12));
+        assert_eq!(dir_size_limited(&test_dir, 2), None);
+        assert_eq!(dir_size(&test_dir.join("missing")), None);
+
+        fs::remove_dir_all(&test_dir).expect("remove test dir");
+    }
+}
+
 #[cfg(all(test, target_os = "linux"))]
 mod tests {
     use super::linux_xdg_app_dir_from;
@@ -0,0 +1,266 @@
+//! 磁盘碎片检查：统计目录中大文件的区段（extent）数量。机械硬盘上碎片严重的
+//! APPX 包会明显拖慢解压，这里只给出建议，不执行整理。
+
+use serde::Serialize;
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// 只检查大于该大小的文件，小文件即使有碎片影响也可以忽略
+const LARGE_FILE_THRESHOLD: u64 = 10 * 1024 * 1024;
+/// 超过该比例的大文件存在碎片时建议整理
+const FRAGMENTED_RATIO_THRESHOLD: f64 = 0.1;
+/// 单个文件区段数超过该值时建议整理
+const WORST_FRAGMENT_THRESHOLD: usize = 16;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FragmentationReport {
+    pub total_files_checked: usize,
+    pub fragmented_files: usize,
+    pub worst_file: Option<String>,
+    pub worst_fragment_count: usize,
+    pub recommendation: String,
+}
+
+/// 检查目录下大文件的碎片情况
+pub fn check_storage_fragmentation(path: String) -> Result<FragmentationReport, String> {
+    let root = Path::new(&path);
+    if !root.is_dir() {
+        return Err(format!("目录不存在: {}", path));
+    }
+    let geometry_available = platform::drive_geometry_available(root);
+
+    let mut report = FragmentationReport::default();
+    for entry in WalkDir::new(root)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+    {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.len() <= LARGE_FILE_THRESHOLD {
+            continue;
+        }
+        let fragment_count = match platform::count_file_fragments(entry.path()) {
+            Ok(count) => count,
+            Err(e) => {
+                tracing::debug!("读取文件区段失败 {}: {}", entry.path().display(), e);
+                continue;
+            }
+        };
+
+        report.total_files_checked += 1;
+        if fragment_count > 1 {
+            report.fragmented_files += 1;
+        }
+        if fragment_count > report.worst_fragment_count {
+            report.worst_fragment_count = fragment_count;
+            report.worst_file = Some(entry.path().to_string_lossy().to_string());
+        }
+    }
+
+    report.recommendation = recommendation(&report, geometry_available);
+    Ok(report)
+}
+
+fn recommendation(report: &FragmentationReport, geometry_available: bool) -> String {
+    let mut recommendation = if report.total_files_checked == 0 {
+        "未找到大于 10MB 的文件，无需整理".to_string()
+    } else {
+        let fragmented_ratio = report.fragmented_files as f64 / report.total_files_checked as f64;
+        if fragmented_ratio >= FRAGMENTED_RATIO_THRESHOLD
+            || report.worst_fragment_count > WORST_FRAGMENT_THRESHOLD
+        {
+            "碎片较多，若该目录位于机械硬盘，建议运行系统的“碎片整理和优化驱动器”；固态硬盘无需整理"
+                .to_string()
+        } else {
+            "碎片程度较低，无需整理".to_string()
+        }
+    };
+    if !geometry_available {
+        recommendation.push_str("（无法读取磁盘信息，可能是网络或虚拟磁盘，结果仅供参考）");
+    }
+    recommendation
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use std::ffi::c_void;
+    use std::path::{Component, Path, Prefix};
+    use windows::Win32::Foundation::{CloseHandle, ERROR_MORE_DATA, HANDLE};
+    use windows::Win32::Storage::FileSystem::{
+        CreateFileW, FILE_FLAG_BACKUP_SEMANTICS, FILE_READ_ATTRIBUTES, FILE_SHARE_DELETE,
+        FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING,
+    };
+    use windows::Win32::System::IO::DeviceIoControl;
+    use windows::Win32::System::Ioctl::{
+        DISK_GEOMETRY_EX, FSCTL_GET_RETRIEVAL_POINTERS, IOCTL_DISK_GET_DRIVE_GEOMETRY_EX,
+        RETRIEVAL_POINTERS_BUFFER, RETRIEVAL_POINTERS_BUFFER_0, STARTING_VCN_INPUT_BUFFER,
+    };
+    use windows::core::HSTRING;
+
+    /// 每次查询最多返回的区段数，超出时按 NextVcn 继续查询
+    const EXTENTS_PER_QUERY: usize = 512;
+
+    struct OwnedHandle(HANDLE);
+
+    impl Drop for OwnedHandle {
+        fn drop(&mut self) {
+            unsafe {
+                if let Err(e) = CloseHandle(self.0) {
+                    tracing::debug!("关闭句柄失败: {}", e);
+                }
+            }
+        }
+    }
+
+    fn open_for_query(path: &HSTRING) -> windows::core::Result<OwnedHandle> {
+        unsafe {
+            CreateFileW(
+                path,
+                FILE_READ_ATTRIBUTES.0,
+                FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE,
+                None,
+                OPEN_EXISTING,
+                FILE_FLAG_BACKUP_SEMANTICS,
+                None,
+            )
+            .map(OwnedHandle)
+        }
+    }
+
+    /// 读取路径所在物理磁盘的几何信息；网络路径、虚拟磁盘等会失败
+    pub(super) fn drive_geometry_available(path: &Path) -> bool {
+        let Some(Component::Prefix(prefix)) = std::path::absolute(path)
+            .ok()
+            .and_then(|path| path.components().next())
+        else {
+            return false;
+        };
+        let drive = match prefix.kind() {
+            Prefix::Disk(letter) | Prefix::VerbatimDisk(letter) => letter as char,
+            _ => return false,
+        };
+        let Ok(volume) = open_for_query(&HSTRING::from(format!(r"\\.\{drive}:"))) else {
+            return false;
+        };
+
+        let mut geometry = DISK_GEOMETRY_EX::default();
+        let mut bytes_returned = 0u32;
+        unsafe {
+            // SAFETY: 输出缓冲区是按 DISK_GEOMETRY_EX 大小分配的栈变量
+            DeviceIoControl(
+                volume.0,
+                IOCTL_DISK_GET_DRIVE_GEOMETRY_EX,
+                None,
+                0,
+                Some(&mut geometry as *mut DISK_GEOMETRY_EX as *mut c_void),
+                std::mem::size_of::<DISK_GEOMETRY_EX>() as u32,
+                Some(&mut bytes_returned),
+                None,
+            )
+            .is_ok()
+        }
+    }
+
+    /// 统计文件的区段数；常驻 MFT 的小文件返回 0
+    pub(super) fn count_file_fragments(path: &Path) -> Result<usize, String> {
+        let file = open_for_query(&HSTRING::from(path)).map_err(|e| e.to_string())?;
+        let header_size = std::mem::offset_of!(RETRIEVAL_POINTERS_BUFFER, Extents);
+        let buffer_size =
+            header_size + EXTENTS_PER_QUERY * std::mem::size_of::<RETRIEVAL_POINTERS_BUFFER_0>();
+        // 用 u64 数组保证 8 字节对齐
+        let mut buffer = vec![0u64; buffer_size.div_ceil(8)];
+
+        let mut fragments = 0usize;
+        let mut previous_end_lcn: Option<i64> = None;
+        let mut input = STARTING_VCN_INPUT_BUFFER { StartingVcn: 0 };
+        loop {
+            let mut bytes_returned = 0u32;
+            let result = unsafe {
+                DeviceIoControl(
+                    file.0,
+                    FSCTL_GET_RETRIEVAL_POINTERS,
+                    Some(&input as *const STARTING_VCN_INPUT_BUFFER as *const c_void),
+                    std::mem::size_of::<STARTING_VCN_INPUT_BUFFER>() as u32,
+                    Some(buffer.as_mut_ptr() as *mut c_void),
+                    (buffer.len() * 8) as u32,
+                    Some(&mut bytes_returned),
+                    None,
+                )
+            };
+            let has_more = match result {
+                Ok(()) => false,
+                Err(e) if e.code() == ERROR_MORE_DATA.to_hresult() => true,
+                Err(e) => return Err(e.to_string()),
+            };
+
+            // SAFETY: 缓冲区足够容纳头部与 EXTENTS_PER_QUERY 个区段，区段数按容量截断
+            let (starting_vcn, extents) = unsafe {
+                let pointers = &*(buffer.as_ptr() as *const RETRIEVAL_POINTERS_BUFFER);
+                let count = (pointers.ExtentCount as usize).min(EXTENTS_PER_QUERY);
+                (
+                    pointers.StartingVcn,
+                    std::slice::from_raw_parts(pointers.Extents.as_ptr(), count),
+                )
+            };
+            let mut current_vcn = starting_vcn;
+            for extent in extents {
+                let cluster_count = extent.NextVcn - current_vcn;
+                current_vcn = extent.NextVcn;
+                // Lcn 为 -1 表示稀疏或压缩的空洞，不占用磁盘空间
+                if extent.Lcn < 0 {
+                    continue;
+                }
+                if previous_end_lcn != Some(extent.Lcn) {
+                    fragments += 1;
+                }
+                previous_end_lcn = Some(extent.Lcn + cluster_count);
+            }
+
+            if !has_more || extents.is_empty() {
+                return Ok(fragments);
+            }
+            input.StartingVcn = current_vcn;
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod platform {
+    use std::path::Path;
+
+    pub(super) fn drive_geometry_available(_path: &Path) -> bool {
+        false
+    }
+
+    pub(super) fn count_file_fragments(_path: &Path) -> Result<usize, String> {
+        Err("当前系统不支持读取文件区段".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recommendation_depends_on_fragmentation_level() {
+        let mut report = FragmentationReport {
+            total_files_checked: 20,
+            fragmented_files: 1,
+            worst_fragment_count: 4,
+            ..Default::default()
+        };
+        assert_eq!(recommendation(&report, true), "碎片程度较低，无需整理");
+
+        report.worst_fragment_count = 64;
+        assert!(recommendation(&report, true).contains("碎片整理"));
+
+        let empty = FragmentationReport::default();
+        assert!(recommendation(&empty, false).starts_with("未找到大于 10MB 的文件"));
+        assert!(recommendation(&empty, false).contains("仅供参考"));
+    }
+}
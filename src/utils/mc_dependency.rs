@@ -32,8 +32,6 @@ use winreg::enums::{HKEY_LOCAL_MACHINE, KEY_READ, KEY_WOW64_64KEY};
 use crate::http::proxy::get_client_for_proxy;
 use crate::http::request::GLOBAL_CLIENT;
 use crate::i18n::{Locale, Translator};
-#[cfg(windows)]
-use crate::utils::developer_mode;
 
 mod windows_app_sdk;
 pub use windows_app_sdk::{
@@ -685,7 +683,7 @@ fn emit_game_input_admin_notice(
     translator: &Translator,
     plan: &GameInputInstallPlan,
 ) {
-    if !developer_mode::is_process_elevated() {
+    if !crate::utils::elevation::is_elevated() {
         warn!(
             installer_path = %plan.installer_path.display(),
             "安装 GameInput Runtime 可能需要管理员权限"
@@ -307,7 +307,7 @@ fn emit_windows_app_sdk_admin_notice(
     translator: &crate::i18n::Translator,
     plan: &WindowsAppSdkInstallPlan,
 ) {
-    if !crate::utils::developer_mode::is_process_elevated() {
+    if !crate::utils::elevation::is_elevated() {
         tracing::warn!(
             installer_path = %plan.installer_path.display(),
             "安装 Windows App SDK Runtime 1.8 可能需要管理员权限"
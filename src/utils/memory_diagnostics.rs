@@ -1,4 +1,6 @@
+use crate::utils::memory::MemoryStats;
 use once_cell::sync::Lazy;
+use serde::Serialize;
 use std::sync::Mutex;
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -32,6 +34,8 @@ impl MapViewerMemorySnapshot {
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct BmcblMemorySnapshot {
     pub map_viewer: MapViewerMemorySnapshot,
+    pub compound_cache_entries: usize,
+    pub task_log_lines: usize,
 }
 
 impl BmcblMemorySnapshot {
@@ -55,8 +59,49 @@ pub fn clear_map_viewer_memory() {
 }
 
 pub fn snapshot_bmcbl_memory() -> BmcblMemorySnapshot {
-    BMCBL_MEMORY_SNAPSHOT
+    let mut snapshot = BMCBL_MEMORY_SNAPSHOT
         .lock()
         .unwrap_or_else(|poison| poison.into_inner())
-        .clone()
+        .clone();
+    snapshot.compound_cache_entries = crate::core::minecraft::import::compound_cache_entries();
+    snapshot.task_log_lines = crate::tasks::task_manager::task_log_line_count();
+    snapshot
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct LauncherMemoryInfo {
+    pub working_set_mb: f64,
+    pub compound_cache_entries: usize,
+    /// 皮肤预览图与赞助者头像的磁盘缓存
+    pub icon_cache_disk_mb: f64,
+    /// 注入过程写入启动任务日志，这里统计所有任务保留的日志行
+    pub inject_log_entries: usize,
+}
+
+fn bytes_to_mb(bytes: u64) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0)
+}
+
+/// 启动器内存诊断：进程工作集与各类缓存规模，用于排查加载大量图标、存档信息后的卡顿
+pub async fn get_launcher_memory_usage() -> Result<LauncherMemoryInfo, String> {
+    tokio::task::spawn_blocking(|| {
+        let mut memory_stats = MemoryStats::new();
+        memory_stats.refresh();
+        let icon_cache_bytes = [
+            &crate::core::minecraft::skin_pack_preview::CACHE,
+            &crate::core::sponsors::CACHE,
+        ]
+        .iter()
+        .map(|cache| (cache.usage)().bytes)
+        .sum();
+
+        LauncherMemoryInfo {
+            working_set_mb: memory_stats.working_set_kb as f64 / 1024.0,
+            compound_cache_entries: crate::core::minecraft::import::compound_cache_entries(),
+            icon_cache_disk_mb: bytes_to_mb(icon_cache_bytes),
+            inject_log_entries: crate::tasks::task_manager::task_log_line_count(),
+        }
+    })
+    .await
+    .map_err(|e| format!("读取内存信息失败: {}", e))
 }
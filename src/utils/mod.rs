@@ -1,12 +1,17 @@
 pub mod app_info;
 pub mod cloudflare;
+pub mod data_root;
 #[cfg(target_os = "windows")]
 pub mod developer_mode;
 pub mod diagnostics;
+#[cfg(target_os = "windows")]
+pub mod elevation;
+pub mod file_locks;
 pub mod file_ops;
 pub mod file_picker;
 pub mod font_settings;
 pub mod format_bytes;
+pub mod fragmentation;
 pub mod logger;
 #[cfg(target_os = "windows")]
 pub mod mc_dependency;
@@ -14,8 +19,10 @@ pub mod memory;
 pub mod memory_diagnostics;
 pub mod network;
 pub mod open_path;
+pub mod recycle_bin;
 #[cfg(target_os = "windows")]
 pub mod registry;
+pub mod render_recovery;
 #[cfg(target_os = "windows")]
 pub mod shortcut;
 pub mod single_instance;
@@ -1,10 +1,86 @@
+use crate::config::config::read_config;
 use crate::http::proxy::get_blocking_client_for_proxy;
 use crate::http::proxy::get_client_for_proxy;
 use futures_util::future::join_all;
+use once_cell::sync::Lazy;
 use reqwest::Url;
 use serde::Serialize;
+use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
+/// 网络计费状态变化不频繁，短时间内复用上次查询结果
+const METERED_CACHE_TTL: Duration = Duration::from_secs(30);
+
+static METERED_CACHE: Lazy<Mutex<Option<(Instant, bool)>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkStatus {
+    pub metered: bool,
+    pub respect_metered: bool,
+    /// 当前是否处于仅拉取元数据的省流模式
+    pub save_bandwidth: bool,
+}
+
+#[cfg(target_os = "windows")]
+fn query_metered_connection() -> bool {
+    use windows::Networking::Connectivity::{NetworkCostType, NetworkInformation};
+
+    let cost = match NetworkInformation::GetInternetConnectionProfile()
+        .and_then(|profile| profile.GetConnectionCost())
+    {
+        Ok(cost) => cost,
+        Err(error) => {
+            tracing::debug!("查询网络计费状态失败，按不计费处理: {error:?}");
+            return false;
+        }
+    };
+    let cost_type = cost.NetworkCostType().unwrap_or(NetworkCostType::Unknown);
+    cost_type == NetworkCostType::Fixed
+        || cost_type == NetworkCostType::Variable
+        || cost.Roaming().unwrap_or(false)
+        || cost.OverDataLimit().unwrap_or(false)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn query_metered_connection() -> bool {
+    false
+}
+
+/// 当前 Internet 连接是否按流量计费（含漫游、超出流量上限）；非 Windows 平台总是返回 false
+pub fn is_metered_connection() -> bool {
+    let mut cache = METERED_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((checked_at, metered)) = *cache
+        && checked_at.elapsed() < METERED_CACHE_TTL
+    {
+        return metered;
+    }
+    let metered = query_metered_connection();
+    *cache = Some((Instant::now(), metered));
+    metered
+}
+
+/// 计费网络且配置 `network.respect_metered` 开启时返回 true：
+/// 目录类请求直接使用旧缓存，跳过图标预取，自动更新检查只拉取清单
+pub fn should_save_bandwidth() -> bool {
+    let respect_metered = read_config()
+        .map(|config| config.network.respect_metered)
+        .unwrap_or(true);
+    respect_metered && is_metered_connection()
+}
+
+pub fn get_network_status() -> Result<NetworkStatus, String> {
+    let respect_metered = read_config()
+        .map(|config| config.network.respect_metered)
+        .map_err(|e| e.to_string())?;
+    let metered = is_metered_connection();
+    Ok(NetworkStatus {
+        metered,
+        respect_metered,
+        save_bandwidth: respect_metered && metered,
+    })
+}
+
 pub fn test_network_connectivity_blocking(url: String) -> Result<u64, String> {
     let client = get_blocking_client_for_proxy().map_err(|e| e.to_string())?;
 
@@ -6,6 +6,8 @@ use windows::{
     core::{HSTRING, PCWSTR},
 };
 
+use std::path::{Path, PathBuf};
+
 pub async fn open_path(path: String) -> Result<(), String> {
     #[cfg(target_os = "windows")]
     {
@@ -44,3 +46,284 @@ pub async fn open_path(path: String) -> Result<(), String> {
         Ok(())
     }
 }
+
+/// 复制路径时使用的格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PathCopyStyle {
+    /// `C:\Users\...`
+    Windows,
+    /// `/mnt/c/Users/...`
+    Wsl,
+    /// `file:///C:/Users/...`，非 ASCII 字符会被百分号编码
+    Uri,
+}
+
+/// 在文件管理器中定位并选中目标；目标必须存在且位于启动器管理的目录内
+pub async fn reveal_in_explorer(path: String) -> Result<(), String> {
+    let target = validate_shell_target(&path)?;
+    tokio::task::spawn_blocking(move || reveal_path_blocking(&target))
+        .await
+        .map_err(|error| format!("定位文件任务失败: {error}"))?
+}
+
+/// 按指定格式把路径写入剪贴板；校验规则与 `reveal_in_explorer` 相同
+pub fn copy_path_to_clipboard(
+    cx: &mut gpui::App,
+    path: &str,
+    style: PathCopyStyle,
+) -> Result<(), String> {
+    let target = validate_shell_target(path)?;
+    let text = format_path_for_copy(&target, style)?;
+    cx.write_to_clipboard(gpui::ClipboardItem::new_string(text));
+    Ok(())
+}
+
+/// 校验交给系统 Shell 的路径，避免被插件脚本当作任意启动入口
+pub fn validate_shell_target(path: &str) -> Result<PathBuf, String> {
+    let trimmed = path.trim();
+    if trimmed.is_empty() {
+        return Err("路径为空".to_string());
+    }
+    let target = match std::fs::canonicalize(trimmed) {
+        Ok(target) => target,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            return Err(format!("路径不存在: {trimmed}"));
+        }
+        Err(error) => return Err(format!("无法解析路径 {trimmed}: {error}")),
+    };
+    if !is_within_known_roots(&target, &known_shell_roots()) {
+        return Err(format!("路径不在启动器管理的目录内，已拒绝: {trimmed}"));
+    }
+    Ok(target)
+}
+
+fn known_shell_roots() -> Vec<PathBuf> {
+    use crate::core::minecraft::paths::{BuildType, Edition, GamePathOptions, get_game_root};
+    use crate::utils::file_ops;
+
+    let mut roots = vec![
+        file_ops::bmcbl_dir(),
+        file_ops::config_dir(),
+        file_ops::cache_dir(),
+        file_ops::state_dir(),
+        file_ops::logs_dir(),
+        // 只放行启动器自己的临时子目录，系统临时目录里的其他程序文件一律拒绝
+        file_ops::launcher_temp_dir(),
+    ];
+    for build_type in [BuildType::Uwp, BuildType::Gdk] {
+        for edition in [
+            Edition::Release,
+            Edition::Preview,
+            Edition::Education,
+            Edition::EducationPreview,
+        ] {
+            let options = GamePathOptions {
+                build_type: build_type.clone(),
+                edition,
+                version_name: String::new(),
+                enable_isolation: false,
+                user_id: None,
+                allow_shared_fallback: true,
+            };
+            if let Some(root) = get_game_root(&options) {
+                roots.push(root);
+            }
+        }
+    }
+    // 根目录同样需要规范化，否则 `\\?\` 前缀或符号链接会导致前缀比较失败
+    roots
+        .into_iter()
+        .filter_map(|root| std::fs::canonicalize(root).ok())
+        .collect()
+}
+
+fn is_within_known_roots(target: &Path, roots: &[PathBuf]) -> bool {
+    roots.iter().any(|root| target.starts_with(root))
+}
+
+/// 去掉 `canonicalize` 在 Windows 上加的 `\\?\` 前缀，便于展示和交给 Shell
+fn display_path(path: &Path) -> String {
+    let raw = path.to_string_lossy();
+    if let Some(rest) = raw.strip_prefix(r"\\?\UNC\") {
+        format!(r"\\{rest}")
+    } else if let Some(rest) = raw.strip_prefix(r"\\?\") {
+        rest.to_string()
+    } else {
+        raw.to_string()
+    }
+}
+
+fn format_path_for_copy(path: &Path, style: PathCopyStyle) -> Result<String, String> {
+    let display = display_path(path);
+    match style {
+        PathCopyStyle::Windows => Ok(display),
+        PathCopyStyle::Wsl => Ok(windows_path_to_wsl(&display).unwrap_or(display)),
+        PathCopyStyle::Uri => url::Url::from_file_path(&display)
+            .map(String::from)
+            .map_err(|_| format!("无法转换为 file URI: {display}")),
+    }
+}
+
+/// `C:\Games\世界` -> `/mnt/c/Games/世界`；UNC 等非盘符路径返回 None
+fn windows_path_to_wsl(path: &str) -> Option<String> {
+    let mut chars = path.chars();
+    let drive = chars.next().filter(char::is_ascii_alphabetic)?;
+    if chars.next() != Some(':') {
+        return None;
+    }
+    let rest = chars.as_str().replace('\\', "/");
+    let rest = rest.trim_start_matches('/');
+    let drive = drive.to_ascii_lowercase();
+    if rest.is_empty() {
+        Some(format!("/mnt/{drive}"))
+    } else {
+        Some(format!("/mnt/{drive}/{rest}"))
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn reveal_path_blocking(target: &Path) -> Result<(), String> {
+    match select_in_shell(target) {
+        Ok(()) => Ok(()),
+        Err(error) => {
+            tracing::warn!("SHOpenFolderAndSelectItems 失败，改用 explorer /select: {error}");
+            use std::os::windows::process::CommandExt as _;
+            std::process::Command::new("explorer.exe")
+                .raw_arg(format!("/select,\"{}\"", display_path(target)))
+                .spawn()
+                .map(|_| ())
+                .map_err(|error| format!("无法打开资源管理器: {error}"))
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn select_in_shell(target: &Path) -> Result<(), String> {
+    use windows::Win32::System::Com::{COINIT_APARTMENTTHREADED, CoInitializeEx, CoUninitialize};
+    use windows::Win32::UI::Shell::Common::ITEMIDLIST;
+    use windows::Win32::UI::Shell::{ILFree, SHOpenFolderAndSelectItems, SHParseDisplayName};
+
+    // 超过 MAX_PATH 时保留 `\\?\` 前缀，Shell 解析失败会回退到 explorer
+    const MAX_PATH_CHARS: usize = 260;
+    let display = display_path(target);
+    let shell_path = if display.encode_utf16().count() < MAX_PATH_CHARS {
+        HSTRING::from(display)
+    } else {
+        HSTRING::from(target)
+    };
+
+    unsafe {
+        // SAFETY: pidl 由 SHParseDisplayName 分配，使用后通过 ILFree 释放且只释放一次
+        let com_initialized = CoInitializeEx(None, COINIT_APARTMENTTHREADED).is_ok();
+        let mut pidl: *mut ITEMIDLIST = std::ptr::null_mut();
+        let result = SHParseDisplayName(&shell_path, None, &mut pidl, 0, None)
+            .and_then(|()| SHOpenFolderAndSelectItems(pidl, None, 0));
+        if !pidl.is_null() {
+            ILFree(Some(pidl));
+        }
+        if com_initialized {
+            CoUninitialize();
+        }
+        result.map_err(|error| error.to_string())
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn reveal_path_blocking(target: &Path) -> Result<(), String> {
+    let mut command = if cfg!(target_os = "macos") {
+        let mut command = std::process::Command::new("open");
+        command.arg("-R").arg(target);
+        command
+    } else {
+        // xdg-open 不支持选中文件，退而打开所在目录
+        let folder = if target.is_dir() {
+            target
+        } else {
+            target.parent().unwrap_or(target)
+        };
+        let mut command = std::process::Command::new("xdg-open");
+        command.arg(folder);
+        command
+    };
+    command
+        .spawn()
+        .map(|_| ())
+        .map_err(|error| format!("无法打开文件管理器: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_paths_convert_to_wsl_mounts() {
+        assert_eq!(
+            windows_path_to_wsl(r"C:\Games\世界 1\level.dat").as_deref(),
+            Some("/mnt/c/Games/世界 1/level.dat")
+        );
+        assert_eq!(windows_path_to_wsl(r"D:\").as_deref(), Some("/mnt/d"));
+        assert_eq!(windows_path_to_wsl(r"\\server\share\pack"), None);
+    }
+
+    #[test]
+    fn verbatim_prefix_is_stripped_for_display() {
+        assert_eq!(
+            display_path(Path::new(r"\\?\C:\BMCBL\versions")),
+            r"C:\BMCBL\versions"
+        );
+        assert_eq!(
+            display_path(Path::new(r"\\?\UNC\server\share\world")),
+            r"\\server\share\world"
+        );
+    }
+
+    #[test]
+    fn targets_outside_known_roots_are_denied() {
+        let roots = vec![PathBuf::from("/data/BMCBL"), PathBuf::from("/tmp/BMCBL")];
+
+        assert!(is_within_known_roots(
+            Path::new("/data/BMCBL/versions/1.21/level.dat"),
+            &roots
+        ));
+        assert!(!is_within_known_roots(
+            Path::new("/data/BMCBL-other/evil.exe"),
+            &roots
+        ));
+        assert!(!is_within_known_roots(Path::new("/usr/bin/sh"), &roots));
+        assert!(!is_within_known_roots(
+            Path::new("/tmp/payload.exe"),
+            &roots
+        ));
+        assert!(is_within_known_roots(
+            Path::new("/tmp/BMCBL/cache_downloads/pack.mcpack"),
+            &roots
+        ));
+    }
+
+    #[test]
+    fn only_the_launcher_temp_subdirectory_is_allowed() {
+        let foreign =
+            std::env::temp_dir().join(format!("bmcbl-foreign-{}.txt", uuid::Uuid::new_v4()));
+        std::fs::write(&foreign, b"x").expect("write foreign temp file");
+        let owned_dir = crate::utils::file_ops::launcher_temp_dir();
+        std::fs::create_dir_all(&owned_dir).expect("create launcher temp dir");
+        let owned = owned_dir.join(format!("open-path-{}.txt", uuid::Uuid::new_v4()));
+        std::fs::write(&owned, b"x").expect("write launcher temp file");
+
+        let denied = validate_shell_target(&foreign.to_string_lossy());
+        let allowed = validate_shell_target(&owned.to_string_lossy());
+        std::fs::remove_file(&foreign).expect("remove foreign temp file");
+        std::fs::remove_file(&owned).expect("remove launcher temp file");
+
+        assert!(denied.is_err());
+        assert!(allowed.is_ok(), "{allowed:?}");
+    }
+
+    #[test]
+    fn missing_targets_report_not_found() {
+        let error = validate_shell_target("/definitely/missing/bmcbl-path")
+            .expect_err("missing path should be rejected");
+        assert!(error.starts_with("路径不存在"));
+    }
+}
@@ -0,0 +1,193 @@
+//! 回收站辅助：通过 IFileOperation（FOF_ALLOWUNDO）把文件或目录移入系统回收站，
+//! 并借助 `$Recycle.Bin` 中的 `$I` 元数据文件找回条目，用于启动器内撤销。
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// `$I` 文件 v1（Vista ~ Win8）固定 260 个 UTF-16 字符的路径
+const RECYCLE_INFO_V1_PATH_CHARS: usize = 260;
+const RECYCLE_INFO_HEADER_LEN: usize = 24;
+
+/// 把路径移入回收站；卷上没有回收站时系统会先弹出永久删除警告
+#[cfg(target_os = "windows")]
+pub fn move_to_recycle_bin(path: &Path) -> Result<(), String> {
+    use windows::Win32::System::Com::{
+        CLSCTX_ALL, COINIT_APARTMENTTHREADED, CoCreateInstance, CoInitializeEx, CoUninitialize,
+    };
+    use windows::Win32::UI::Shell::{
+        FOF_ALLOWUNDO, FOF_NOCONFIRMATION, FOF_NOERRORUI, FOF_SILENT, FOF_WANTNUKEWARNING,
+        FileOperation, IFileOperation, IShellItem, SHCreateItemFromParsingName,
+    };
+    use windows::core::HSTRING;
+
+    let absolute_path =
+        std::path::absolute(path).map_err(|e| format!("解析路径失败 {}: {}", path.display(), e))?;
+
+    unsafe {
+        let com_initialized = CoInitializeEx(None, COINIT_APARTMENTTHREADED).is_ok();
+        let result = (|| -> windows::core::Result<bool> {
+            let operation: IFileOperation = CoCreateInstance(&FileOperation, None, CLSCTX_ALL)?;
+            operation.SetOperationFlags(
+                FOF_ALLOWUNDO
+                    | FOF_NOCONFIRMATION
+                    | FOF_NOERRORUI
+                    | FOF_SILENT
+                    | FOF_WANTNUKEWARNING,
+            )?;
+            let item: IShellItem =
+                SHCreateItemFromParsingName(&HSTRING::from(absolute_path.as_path()), None)?;
+            operation.DeleteItem(&item, None)?;
+            operation.PerformOperations()?;
+            Ok(operation.GetAnyOperationsAborted()?.as_bool())
+        })();
+        if com_initialized {
+            CoUninitialize();
+        }
+
+        match result {
+            Ok(false) => Ok(()),
+            Ok(true) => Err("移入回收站的操作已被取消".to_string()),
+            Err(e) => Err(format!("移入回收站失败: {}", e)),
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn move_to_recycle_bin(path: &Path) -> Result<(), String> {
+    Err(format!("当前系统不支持回收站: {}", path.display()))
+}
+
+/// 在原路径所在卷的 `$Recycle.Bin` 中查找最近一次删除该路径产生的 `$R` 条目
+pub fn find_recycled_item(original_path: &Path) -> Option<PathBuf> {
+    let absolute_path = std::path::absolute(original_path).ok()?;
+    let volume_root = absolute_path.ancestors().last()?;
+    let recycle_root = volume_root.join("$Recycle.Bin");
+    let expected = absolute_path.to_string_lossy().to_lowercase();
+
+    let mut newest: Option<(u64, PathBuf)> = None;
+    // 每个用户一个 SID 子目录，其他用户的目录无权读取，直接跳过
+    for sid_dir in fs::read_dir(&recycle_root).ok()?.flatten() {
+        let Ok(entries) = fs::read_dir(sid_dir.path()) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let Some(suffix) = file_name.strip_prefix("$I") else {
+                continue;
+            };
+            let Ok(bytes) = fs::read(entry.path()) else {
+                continue;
+            };
+            let Some((deleted_at, path)) = parse_recycle_info(&bytes) else {
+                continue;
+            };
+            if path.to_string_lossy().to_lowercase() != expected {
+                continue;
+            }
+            let item_path = sid_dir.path().join(format!("$R{suffix}"));
+            if !item_path.exists() {
+                continue;
+            }
+            if newest
+                .as_ref()
+                .is_none_or(|(newest_at, _)| deleted_at > *newest_at)
+            {
+                newest = Some((deleted_at, item_path));
+            }
+        }
+    }
+
+    newest.map(|(_, item_path)| item_path)
+}
+
+/// 把 `$R` 条目移回目标路径，并清理对应的 `$I` 元数据
+pub fn restore_recycled_item(item_path: &Path, target: &Path) -> Result<(), String> {
+    if !item_path.exists() {
+        return Err(format!("回收站条目已不存在: {}", item_path.display()));
+    }
+    fs::rename(item_path, target).map_err(|e| {
+        format!(
+            "从回收站还原失败 {} -> {}: {}",
+            item_path.display(),
+            target.display(),
+            e
+        )
+    })?;
+
+    let info_path = item_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.strip_prefix("$R"))
+        .map(|suffix| item_path.with_file_name(format!("$I{suffix}")));
+    if let Some(info_path) = info_path
+        && let Err(e) = fs::remove_file(&info_path)
+    {
+        warn!("清理回收站元数据失败 {}: {}", info_path.display(), e);
+    }
+    Ok(())
+}
+
+/// 解析 `$I` 文件，返回 (删除时间 FILETIME, 原始路径)
+fn parse_recycle_info(bytes: &[u8]) -> Option<(u64, PathBuf)> {
+    if bytes.len() < RECYCLE_INFO_HEADER_LEN {
+        return None;
+    }
+    let version = u64::from_le_bytes(bytes[0..8].try_into().ok()?);
+    let deleted_at = u64::from_le_bytes(bytes[16..24].try_into().ok()?);
+
+    let path_bytes = match version {
+        1 => bytes.get(24..24 + RECYCLE_INFO_V1_PATH_CHARS * 2)?,
+        2 => {
+            let char_count = u32::from_le_bytes(bytes.get(24..28)?.try_into().ok()?) as usize;
+            bytes.get(28..28 + char_count * 2)?
+        }
+        _ => return None,
+    };
+    let units: Vec<u16> = path_bytes
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .take_while(|unit| *unit != 0)
+        .collect();
+    if units.is_empty() {
+        return None;
+    }
+
+    Some((deleted_at, PathBuf::from(String::from_utf16_lossy(&units))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recycle_info_v2(path: &str, deleted_at: u64) -> Vec<u8> {
+        let units: Vec<u16> = path.encode_utf16().chain([0]).collect();
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&2u64.to_le_bytes());
+        bytes.extend_from_slice(&4096u64.to_le_bytes());
+        bytes.extend_from_slice(&deleted_at.to_le_bytes());
+        bytes.extend_from_slice(&(units.len() as u32).to_le_bytes());
+        for unit in units {
+            bytes.extend_from_slice(&unit.to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn parses_v2_recycle_info() {
+        let bytes = recycle_info_v2(r"C:\Games\minecraftWorlds\abc", 133_000_000_000_000_000);
+
+        let (deleted_at, path) = parse_recycle_info(&bytes).expect("v2 info should parse");
+
+        assert_eq!(deleted_at, 133_000_000_000_000_000);
+        assert_eq!(path, PathBuf::from(r"C:\Games\minecraftWorlds\abc"));
+    }
+
+    #[test]
+    fn rejects_truncated_recycle_info() {
+        let bytes = recycle_info_v2(r"C:\Games\world", 1);
+
+        assert!(parse_recycle_info(&bytes[..20]).is_none());
+        assert!(parse_recycle_info(&bytes[..30]).is_none());
+    }
+}
@@ -15,6 +15,7 @@ const EXTENSIONS: &[&str] = &[".mcpack", ".mcworld", ".mcaddon", ".mctemplate"];
 const REG_APP_PATH_KEY: &str = "AppPath";
 const REG_VERSION_KEY: &str = "AssocVersion";
 const ASSOC_VERSION: u32 = 1; // 关联版本号，格式变更时可递增强制刷新
+const WINTUN_SERVICE_KEY: &str = "SYSTEM\\CurrentControlSet\\Services\\wintun";
 
 /// 注册文件关联 (仅在 Windows 下有效)
 /// 包含检查机制，仅在路径变更、版本变更或未注册时执行写入
@@ -28,6 +29,13 @@ pub fn register_file_associations() {
     }
 }
 
+/// 检测 Wintun 驱动是否已安装（EasyTier TUN 模式依赖该驱动）
+pub fn check_wintun_driver_installed() -> bool {
+    RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey(WINTUN_SERVICE_KEY)
+        .is_ok()
+}
+
 /// 规范化路径字符串用于比较（统一小写、去除首尾空格，Windows 路径不区分大小写）
 #[cfg(target_os = "windows")]
 fn normalize_path_for_compare(path: &str) -> String {
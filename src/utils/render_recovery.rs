@@ -0,0 +1,97 @@
+//! 主窗口白屏恢复：显卡驱动或渲染后端设置异常时，主窗口可能一直不出图，
+//! 用户无法进入设置页修改。主窗口首帧实际呈现后调用 [`frontend_ready`]；
+//! 超时仍未就绪则弹出系统对话框，确认后把渲染后端和显卡设置恢复为自动并重启启动器。
+//! 也可以用 `--reset-renderer`（兼容旧说明中的 `--reset-webview`）在启动前直接恢复。
+
+use crate::config::config::{Config, default_gpu_adapter_name, update_config};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+static FRONTEND_READY: AtomicBool = AtomicBool::new(false);
+const RELAUNCH_EXIT_DELAY: Duration = Duration::from_millis(300);
+
+/// 主窗口首帧已呈现
+pub fn frontend_ready() {
+    if !FRONTEND_READY.swap(true, Ordering::Relaxed) {
+        info!("main window reported frontend ready");
+    }
+}
+
+pub fn is_frontend_ready() -> bool {
+    FRONTEND_READY.load(Ordering::Relaxed)
+}
+
+fn reset_renderer_config(config: &mut Config) {
+    config.launcher.renderer_backend = "auto".to_string();
+    config.launcher.gpu_adapter_name = default_gpu_adapter_name();
+}
+
+/// 把渲染后端和显卡选择恢复为自动，返回恢复后的配置
+pub fn reset_renderer_settings() -> std::io::Result<Config> {
+    update_config(|config| {
+        reset_renderer_config(config);
+        config.clone()
+    })
+}
+
+/// 主窗口打开后调用；`timeout` 为 0 时不检查
+pub fn spawn_frontend_ready_watchdog(timeout: Duration) {
+    if timeout.is_zero() {
+        return;
+    }
+    let result = std::thread::Builder::new()
+        .name("bmcbl-frontend-watchdog".to_string())
+        .spawn(move || {
+            std::thread::sleep(timeout);
+            if FRONTEND_READY.load(Ordering::Relaxed) {
+                return;
+            }
+            error!(
+                timeout_secs = timeout.as_secs(),
+                "main window did not render within timeout"
+            );
+            offer_renderer_reset();
+        });
+    if let Err(error) = result {
+        warn!(?error, "failed to start frontend ready watchdog");
+    }
+}
+
+fn offer_renderer_reset() {
+    let confirmed = rfd::MessageDialog::new()
+        .set_title("界面未能显示")
+        .set_description(
+            "启动器主界面长时间没有显示内容，可能是渲染后端或显卡设置不兼容。\n\n是否恢复为自动设置并重启启动器？",
+        )
+        .set_level(rfd::MessageLevel::Warning)
+        .set_buttons(rfd::MessageButtons::YesNo)
+        .show();
+    if !matches!(confirmed, rfd::MessageDialogResult::Yes) {
+        info!("user declined renderer reset");
+        return;
+    }
+
+    if let Err(error) = reset_renderer_settings() {
+        crate::result::show_error_dialog("恢复渲染设置失败", &error.to_string());
+        return;
+    }
+    info!("renderer settings reset, relaunching");
+    if let Err(message) = relaunch_launcher() {
+        crate::result::show_error_dialog("重启启动器失败", &message);
+    }
+}
+
+/// 与更新流程相同：启动新进程后稍等片刻再退出当前进程
+fn relaunch_launcher() -> Result<(), String> {
+    let exe = std::env::current_exe().map_err(|e| format!("获取 current_exe 失败：{}", e))?;
+    #[cfg(windows)]
+    crate::utils::single_instance::release_mutex();
+    let child = Command::new(&exe)
+        .spawn()
+        .map_err(|e| format!("启动新进程失败：{}", e))?;
+    info!(pid = child.id(), "relaunched launcher");
+    std::thread::sleep(RELAUNCH_EXIT_DELAY);
+    std::process::exit(0);
+}
@@ -186,12 +186,15 @@ fn accelerate_download_url(url: &str, use_acceleration: bool) -> String {
     }
 }
 
+/// `manifest_only` 为 true 时只拉取发布清单：跳过加速通道测速、不返回更新日志、
+/// 也不顺带刷新联机策略，供计费网络下的自动检查使用
 pub async fn check_updates(
     owner: String,
     repo: String,
     api_base: Option<String>,
+    manifest_only: bool,
 ) -> Result<serde_json::Value, String> {
-    let use_acceleration = should_use_acceleration().await;
+    let use_acceleration = !manifest_only && should_use_acceleration().await;
 
     let final_api_base = if let Some(base) = api_base {
         base
@@ -210,8 +213,8 @@ pub async fn check_updates(
     let channel = channel.to_lowercase();
 
     info!(
-        "检查更新：{}/{} (api_base={}, channel={:?}, accelerated={})",
-        owner, repo, final_api_base, update_channel, use_acceleration
+        "检查更新：{}/{} (api_base={}, channel={:?}, accelerated={}, manifest_only={})",
+        owner, repo, final_api_base, update_channel, use_acceleration, manifest_only
     );
 
     let url = format!(
@@ -382,6 +385,19 @@ pub async fn check_updates(
         }
     }
 
+    if manifest_only {
+        for summary in [
+            &mut selected_release,
+            &mut latest_stable,
+            &mut latest_prerelease,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            summary.body = None;
+        }
+    }
+
     let latest_stable_changelog = latest_stable.as_ref().and_then(|s| s.body.clone());
     let latest_prerelease_changelog = latest_prerelease.as_ref().and_then(|s| s.body.clone());
 
@@ -392,6 +408,15 @@ pub async fn check_updates(
     );
     debug!("是否有更新：{} (channel={})", update_available, channel);
 
+    // 联机策略（引导节点 / PaperConnect 网段 / ACL 模板）由启动时的后台任务刷新，这里只报告已缓存的来源
+    let online_policy = if manifest_only {
+        None
+    } else {
+        let online_policy = crate::core::online::cached_online_policy_status();
+        debug!("联机策略来源：{:?}", online_policy.source);
+        Some(online_policy)
+    };
+
     Ok(serde_json::json!({
         "current_version": current,
         "current_semver_parsed": current_ver.to_string(),
@@ -403,7 +428,9 @@ pub async fn check_updates(
         "latest_stable_changelog": latest_stable_changelog,
         "latest_prerelease_changelog": latest_prerelease_changelog,
         "update_available": update_available,
-        "is_accelerated": use_acceleration
+        "is_accelerated": use_acceleration,
+        "manifest_only": manifest_only,
+        "online_policy": online_policy
     }))
 }
 
@@ -426,6 +453,7 @@ pub async fn download_and_apply_update(
         None,
         false,
     );
+    crate::downloads::mark_download_task_metered(&task_id);
 
     info!("开始下载并应用：url={} task_id={}", url, task_id);
 
@@ -618,8 +646,9 @@ pub fn check_updates_blocking(
     owner: String,
     repo: String,
     api_base: Option<String>,
+    manifest_only: bool,
 ) -> Result<serde_json::Value, String> {
-    block_on_tokio(check_updates(owner, repo, api_base))
+    block_on_tokio(check_updates(owner, repo, api_base, manifest_only))
 }
 
 /// 阻塞式下载并应用更新函数 - 在 GPUI 线程池中使用